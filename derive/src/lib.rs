@@ -0,0 +1,553 @@
+//! `#[derive(OpenApiSchema)]`, implementing `openapiv3::OpenApiSchema` for
+//! structs and enums as serde serializes them. See the documentation of the
+//! trait for what is supported.
+
+extern crate proc_macro;
+
+use proc_macro::{Delimiter, Spacing, TokenStream, TokenTree};
+use std::iter::FromIterator;
+
+#[proc_macro_derive(OpenApiSchema, attributes(serde))]
+pub fn derive_openapi_schema(input: TokenStream) -> TokenStream {
+    let code = match parse_item(input).and_then(|item| generate(&item)) {
+        Ok(code) => code,
+        Err(message) => format!("compile_error!({:?});", message),
+    };
+    code.parse().expect("generated code is valid")
+}
+
+/// What the attributes of a type, field or variant say.
+#[derive(Default)]
+struct Attributes {
+    doc: Vec<String>,
+    rename: Option<String>,
+    rename_all: Option<String>,
+    tag: Option<String>,
+    content: Option<String>,
+    untagged: bool,
+    deny_unknown_fields: bool,
+    default: bool,
+    skip: bool,
+    skip_serializing_if: bool,
+    flatten: bool,
+}
+
+impl Attributes {
+    /// The doc comment, without the space that follows `///`.
+    fn description(&self) -> Option<String> {
+        let lines: Vec<&str> = self
+            .doc
+            .iter()
+            .flat_map(|doc| doc.split('\n'))
+            .map(|line| line.strip_prefix(' ').unwrap_or(line).trim_end())
+            .collect();
+        let description = lines.join("\n").trim().to_owned();
+        if description.is_empty() {
+            None
+        } else {
+            Some(description)
+        }
+    }
+}
+
+struct Item {
+    attributes: Attributes,
+    name: String,
+    body: Body,
+}
+
+enum Body {
+    Struct(Fields),
+    Enum(Vec<Variant>),
+}
+
+enum Fields {
+    Named(Vec<Field>),
+    Unnamed(Vec<String>),
+    Unit,
+}
+
+struct Field {
+    attributes: Attributes,
+    name: String,
+    ty: String,
+}
+
+struct Variant {
+    attributes: Attributes,
+    name: String,
+    fields: Fields,
+}
+
+fn parse_item(input: TokenStream) -> Result<Item, String> {
+    let tokens: Vec<TokenTree> = input.into_iter().collect();
+    let mut tokens = &tokens[..];
+    let attributes = parse_attributes(&mut tokens)?;
+    skip_visibility(&mut tokens);
+    let keyword = expect_ident(&mut tokens)?;
+    let name = expect_ident(&mut tokens)?;
+    if is_punct(tokens.first(), '<') {
+        return Err(format!(
+            "OpenApiSchema cannot be derived for `{}`, which is generic",
+            name
+        ));
+    }
+    let body = match keyword.as_str() {
+        "struct" => Body::Struct(parse_fields(tokens)?),
+        "enum" => match tokens.first() {
+            Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => {
+                Body::Enum(parse_variants(group.stream())?)
+            }
+            _ => return Err(format!("expected the variants of `{}`", name)),
+        },
+        _ => {
+            return Err(format!(
+                "OpenApiSchema can only be derived for structs and enums, not `{}`",
+                name
+            ))
+        }
+    };
+    Ok(Item {
+        attributes,
+        name,
+        body,
+    })
+}
+
+fn parse_fields(tokens: &[TokenTree]) -> Result<Fields, String> {
+    match tokens.first() {
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => {
+            let mut fields = vec![];
+            for tokens in split_commas(group.stream()) {
+                let mut tokens = &tokens[..];
+                let attributes = parse_attributes(&mut tokens)?;
+                skip_visibility(&mut tokens);
+                let name = expect_ident(&mut tokens)?;
+                if !is_punct(tokens.first(), ':') {
+                    return Err(format!("expected the type of `{}`", name));
+                }
+                fields.push(Field {
+                    attributes,
+                    name: name.trim_start_matches("r#").to_owned(),
+                    ty: TokenStream::from_iter(tokens[1..].iter().cloned()).to_string(),
+                });
+            }
+            Ok(Fields::Named(fields))
+        }
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis => {
+            let mut types = vec![];
+            for tokens in split_commas(group.stream()) {
+                let mut tokens = &tokens[..];
+                parse_attributes(&mut tokens)?;
+                skip_visibility(&mut tokens);
+                types.push(TokenStream::from_iter(tokens.iter().cloned()).to_string());
+            }
+            Ok(Fields::Unnamed(types))
+        }
+        _ => Ok(Fields::Unit),
+    }
+}
+
+fn parse_variants(input: TokenStream) -> Result<Vec<Variant>, String> {
+    let mut variants = vec![];
+    for tokens in split_commas(input) {
+        let mut tokens = &tokens[..];
+        let attributes = parse_attributes(&mut tokens)?;
+        let name = expect_ident(&mut tokens)?;
+        variants.push(Variant {
+            attributes,
+            name,
+            fields: parse_fields(tokens)?,
+        });
+    }
+    Ok(variants)
+}
+
+/// Splits at the commas that are not within angle brackets, dropping empty
+/// pieces such as the one after a trailing comma.
+fn split_commas(input: TokenStream) -> Vec<Vec<TokenTree>> {
+    let mut pieces = vec![vec![]];
+    let mut depth = 0usize;
+    let mut arrow = false;
+    for token in input {
+        if let TokenTree::Punct(punct) = &token {
+            match punct.as_char() {
+                ',' if depth == 0 => {
+                    pieces.push(vec![]);
+                    continue;
+                }
+                '<' => depth += 1,
+                '>' if !arrow => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+            arrow = punct.as_char() == '-' && punct.spacing() == Spacing::Joint;
+        } else {
+            arrow = false;
+        }
+        pieces.last_mut().unwrap().push(token);
+    }
+    pieces.retain(|piece| !piece.is_empty());
+    pieces
+}
+
+fn parse_attributes(tokens: &mut &[TokenTree]) -> Result<Attributes, String> {
+    let mut attributes = Attributes::default();
+    while is_punct(tokens.first(), '#') {
+        let group = match tokens.get(1) {
+            Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Bracket => group,
+            _ => break,
+        };
+        *tokens = &tokens[2..];
+        let inner: Vec<TokenTree> = group.stream().into_iter().collect();
+        match (inner.first(), inner.get(1), inner.get(2)) {
+            (Some(TokenTree::Ident(ident)), Some(TokenTree::Punct(eq)), Some(literal))
+                if ident.to_string() == "doc" && eq.as_char() == '=' =>
+            {
+                attributes.doc.push(parse_string(literal)?);
+            }
+            (Some(TokenTree::Ident(ident)), Some(TokenTree::Group(args)), None)
+                if ident.to_string() == "serde" =>
+            {
+                parse_serde(&mut attributes, args.stream())?;
+            }
+            _ => {}
+        }
+    }
+    Ok(attributes)
+}
+
+fn parse_serde(attributes: &mut Attributes, args: TokenStream) -> Result<(), String> {
+    for arg in split_commas(args) {
+        let key = match arg.first() {
+            Some(TokenTree::Ident(ident)) => ident.to_string(),
+            _ => continue,
+        };
+        let value = match (arg.get(1), arg.get(2)) {
+            (Some(TokenTree::Punct(eq)), Some(literal)) if eq.as_char() == '=' => {
+                Some(parse_string(literal)?)
+            }
+            // `rename(serialize = "..", deserialize = "..")`: the name values
+            // are serialized with.
+            (Some(TokenTree::Group(group)), None) => split_commas(group.stream())
+                .into_iter()
+                .find(|arg| matches!(arg.first(), Some(TokenTree::Ident(ident)) if ident.to_string() == "serialize"))
+                .and_then(|arg| arg.get(2).map(parse_string))
+                .transpose()?,
+            _ => None,
+        };
+        match key.as_str() {
+            "rename" => attributes.rename = value,
+            "rename_all" => attributes.rename_all = value,
+            "tag" => attributes.tag = value,
+            "content" => attributes.content = value,
+            "untagged" => attributes.untagged = true,
+            "deny_unknown_fields" => attributes.deny_unknown_fields = true,
+            "default" => attributes.default = true,
+            "skip" | "skip_serializing" => attributes.skip = true,
+            "skip_serializing_if" => attributes.skip_serializing_if = true,
+            "flatten" => attributes.flatten = true,
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// The value of a string literal, raw or not.
+fn parse_string(token: &TokenTree) -> Result<String, String> {
+    let literal = token.to_string();
+    if let Some(raw) = literal.strip_prefix('r') {
+        let hashes = raw.len() - raw.trim_start_matches('#').len();
+        return Ok(raw[hashes + 1..raw.len() - hashes - 1].to_owned());
+    }
+    let inner = literal
+        .strip_prefix('"')
+        .and_then(|literal| literal.strip_suffix('"'))
+        .ok_or_else(|| format!("expected a string, not `{}`", literal))?;
+    let mut value = String::new();
+    let mut chars = inner.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            value.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => value.push('\n'),
+            Some('r') => value.push('\r'),
+            Some('t') => value.push('\t'),
+            Some('0') => value.push('\0'),
+            Some('x') => {
+                let code: String = chars.by_ref().take(2).collect();
+                value.push(u8::from_str_radix(&code, 16).map_err(|e| e.to_string())? as char);
+            }
+            Some('u') => {
+                let code: String = chars.by_ref().skip(1).take_while(|&c| c != '}').collect();
+                let code = u32::from_str_radix(&code, 16).map_err(|e| e.to_string())?;
+                value.extend(std::char::from_u32(code));
+            }
+            Some('\n') => {
+                while chars.peek().is_some_and(|c| c.is_whitespace()) {
+                    chars.next();
+                }
+            }
+            Some(c) => value.push(c),
+            None => {}
+        }
+    }
+    Ok(value)
+}
+
+fn skip_visibility(tokens: &mut &[TokenTree]) {
+    if matches!(tokens.first(), Some(TokenTree::Ident(ident)) if ident.to_string() == "pub") {
+        *tokens = &tokens[1..];
+        if matches!(tokens.first(), Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis)
+        {
+            *tokens = &tokens[1..];
+        }
+    }
+}
+
+fn expect_ident(tokens: &mut &[TokenTree]) -> Result<String, String> {
+    match tokens.first() {
+        Some(TokenTree::Ident(ident)) => {
+            *tokens = &tokens[1..];
+            Ok(ident.to_string())
+        }
+        Some(token) => Err(format!("expected a name, not `{}`", token)),
+        None => Err("expected a name".to_owned()),
+    }
+}
+
+fn is_punct(token: Option<&TokenTree>, c: char) -> bool {
+    matches!(token, Some(TokenTree::Punct(punct)) if punct.as_char() == c)
+}
+
+/// Renames a variant, written in PascalCase, or a field, in snake_case, as
+/// `#[serde(rename_all)]` does.
+fn rename(name: &str, rule: Option<&str>, variant: bool) -> Result<String, String> {
+    let rule = match rule {
+        Some(rule) => rule,
+        None => return Ok(name.to_owned()),
+    };
+    let snake = if variant {
+        let mut snake = String::new();
+        for (i, c) in name.chars().enumerate() {
+            if c.is_uppercase() && i > 0 {
+                snake.push('_');
+            }
+            snake.extend(c.to_lowercase());
+        }
+        snake
+    } else {
+        name.to_owned()
+    };
+    let pascal = if variant {
+        name.to_owned()
+    } else {
+        name.split('_')
+            .map(|word| {
+                let mut chars = word.chars();
+                chars
+                    .next()
+                    .map(|c| c.to_uppercase().chain(chars).collect::<String>())
+                    .unwrap_or_default()
+            })
+            .collect()
+    };
+    Ok(match rule {
+        "lowercase" if variant => name.to_lowercase(),
+        "UPPERCASE" if variant => name.to_uppercase(),
+        "lowercase" | "snake_case" => snake,
+        "UPPERCASE" | "SCREAMING_SNAKE_CASE" => snake.to_uppercase(),
+        "PascalCase" => pascal,
+        "camelCase" => {
+            let mut chars = pascal.chars();
+            chars
+                .next()
+                .map(|c| c.to_lowercase().chain(chars).collect())
+                .unwrap_or_default()
+        }
+        "kebab-case" => snake.replace('_', "-"),
+        "SCREAMING-KEBAB-CASE" => snake.to_uppercase().replace('_', "-"),
+        _ => return Err(format!("unknown rename rule `rename_all = {:?}`", rule)),
+    })
+}
+
+fn generate(item: &Item) -> Result<String, String> {
+    let body = match &item.body {
+        Body::Struct(Fields::Named(fields)) => object(
+            item.attributes.rename_all.as_deref(),
+            item.attributes.deny_unknown_fields,
+            fields,
+            &[],
+            item.attributes.default,
+        )?,
+        Body::Struct(Fields::Unnamed(types)) if types.len() == 1 => schema_in(&types[0]),
+        Body::Struct(_) => {
+            return Err(format!(
+                "OpenApiSchema cannot be derived for `{}`, which is neither a struct with named \
+                 fields nor a newtype",
+                item.name
+            ))
+        }
+        Body::Enum(variants) => enumeration(&item.attributes, variants)?,
+    };
+    let name = item.attributes.rename.as_deref().unwrap_or(&item.name);
+    Ok(format!(
+        "impl ::openapiv3::OpenApiSchema for {ty} {{
+            fn schema_in(
+                components: &mut ::openapiv3::Components,
+            ) -> ::openapiv3::ReferenceOr<::openapiv3::Schema> {{
+                ::openapiv3::__private::component(components, {name:?}, |components| {{
+                    ::openapiv3::__private::schema_of({body})
+                }})
+            }}
+        }}",
+        ty = item.name,
+        name = name,
+        body = described(body, &item.attributes),
+    ))
+}
+
+fn schema_in(ty: &str) -> String {
+    format!(
+        "<{} as ::openapiv3::OpenApiSchema>::schema_in(components)",
+        ty
+    )
+}
+
+fn described(schema: String, attributes: &Attributes) -> String {
+    match attributes.description() {
+        Some(description) => format!(
+            "::openapiv3::__private::described({}, ::std::option::Option::Some({:?}))",
+            schema, description
+        ),
+        None => schema,
+    }
+}
+
+/// An object of the fields, preceded by `extra` fields such as tags, each
+/// given as its name and the code of its schema.
+fn object(
+    rename_all: Option<&str>,
+    deny_unknown_fields: bool,
+    fields: &[Field],
+    extra: &[(String, String)],
+    default: bool,
+) -> Result<String, String> {
+    let mut code = String::from("::std::vec![");
+    for (name, schema) in extra {
+        code += &format!("({:?}, {}, false),", name, schema);
+    }
+    for field in fields {
+        if field.attributes.skip {
+            continue;
+        }
+        if field.attributes.flatten {
+            return Err(format!(
+                "OpenApiSchema does not support `#[serde(flatten)]`, as on `{}`",
+                field.name
+            ));
+        }
+        let name = match &field.attributes.rename {
+            Some(name) => name.clone(),
+            None => rename(&field.name, rename_all, false)?,
+        };
+        let optional =
+            if default || field.attributes.default || field.attributes.skip_serializing_if {
+                "true".to_owned()
+            } else {
+                format!("<{} as ::openapiv3::OpenApiSchema>::optional()", field.ty)
+            };
+        code += &format!(
+            "({:?}, {}, {}),",
+            name,
+            described(schema_in(&field.ty), &field.attributes),
+            optional
+        );
+    }
+    code += "]";
+    Ok(format!(
+        "::openapiv3::__private::object({}, {})",
+        code, deny_unknown_fields
+    ))
+}
+
+fn enumeration(container: &Attributes, variants: &[Variant]) -> Result<String, String> {
+    let variants: Vec<&Variant> = variants
+        .iter()
+        .filter(|variant| !variant.attributes.skip)
+        .collect();
+    let mut names = vec![];
+    for variant in &variants {
+        names.push(match &variant.attributes.rename {
+            Some(name) => name.clone(),
+            None => rename(&variant.name, container.rename_all.as_deref(), true)?,
+        });
+    }
+    let units = variants
+        .iter()
+        .all(|variant| matches!(variant.fields, Fields::Unit));
+    if units && container.tag.is_none() && !container.untagged {
+        return Ok(strings(&names));
+    }
+
+    let deny = container.deny_unknown_fields;
+    let mut schemas = vec![];
+    for (variant, name) in variants.iter().zip(&names) {
+        let rename_all = variant.attributes.rename_all.as_deref();
+        let content = match &variant.fields {
+            Fields::Unit => None,
+            Fields::Unnamed(types) if types.len() == 1 => Some(schema_in(&types[0])),
+            Fields::Named(fields) => Some(object(rename_all, deny, fields, &[], false)?),
+            Fields::Unnamed(_) => {
+                return Err(format!(
+                    "OpenApiSchema does not support variants with several unnamed fields, such \
+                     as `{}`",
+                    variant.name
+                ))
+            }
+        };
+        let schema = match (&container.tag, &container.content, content) {
+            (_, _, content) if container.untagged => {
+                content.unwrap_or_else(|| "::openapiv3::__private::null()".to_owned())
+            }
+            (None, _, None) => strings(std::slice::from_ref(name)),
+            (None, _, Some(content)) => object(None, deny, &[], &[(name.clone(), content)], false)?,
+            (Some(tag), content_key, content) => {
+                let mut extra = vec![(tag.clone(), strings(std::slice::from_ref(name)))];
+                match (content_key, content, &variant.fields) {
+                    (Some(key), Some(content), _) => extra.push((key.clone(), content)),
+                    (None, Some(_), Fields::Named(fields)) => {
+                        let schema = object(rename_all, deny, fields, &extra, false)?;
+                        schemas.push(described(schema, &variant.attributes));
+                        continue;
+                    }
+                    (None, Some(content), _) => {
+                        let tag = object(None, false, &[], &extra, false)?;
+                        schemas.push(described(
+                            format!(
+                                "::openapiv3::ReferenceOr::Item(::openapiv3::__private::all_of(\
+                                 ::std::vec![{}, {}]))",
+                                tag, content
+                            ),
+                            &variant.attributes,
+                        ));
+                        continue;
+                    }
+                    _ => {}
+                }
+                object(None, deny, &[], &extra, false)?
+            }
+        };
+        schemas.push(described(schema, &variant.attributes));
+    }
+    Ok(format!(
+        "::openapiv3::__private::one_of(::std::vec![{}])",
+        schemas.join(", ")
+    ))
+}
+
+fn strings(values: &[String]) -> String {
+    let values: Vec<String> = values.iter().map(|value| format!("{:?}", value)).collect();
+    format!("::openapiv3::__private::strings(&[{}])", values.join(", "))
+}
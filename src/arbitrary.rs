@@ -0,0 +1,278 @@
+//! Generation of documents for property tests, with the `proptest` feature.
+//!
+//! The documents generated are valid: names are identifiers, operation IDs
+//! are unique, every operation has a response, and numeric and length
+//! bounds are in order, so that what a test finds wrong with one comes
+//! from the code under test rather than from the document.
+//!
+//! ```
+//! use openapiv3::OpenAPI;
+//! use proptest::prelude::*;
+//! use proptest::test_runner::TestRunner;
+//!
+//! TestRunner::default()
+//!     .run(&any::<OpenAPI>(), |api| {
+//!         let json = serde_json::to_string(&api).unwrap();
+//!         prop_assert_eq!(serde_json::from_str::<OpenAPI>(&json).unwrap(), api);
+//!         Ok(())
+//!     })
+//!     .unwrap();
+//! ```
+
+use crate::*;
+use proptest::collection::{btree_set, vec};
+use proptest::option;
+use proptest::prelude::*;
+
+/// A name, such as of a property or path segment.
+fn name() -> impl Strategy<Value = String> {
+    "[a-z][a-zA-Z0-9]{0,7}"
+}
+
+/// Some text, such as a summary or description.
+fn text() -> impl Strategy<Value = String> {
+    "[A-Za-z][A-Za-z ,.]{0,20}"
+}
+
+/// Bounds that are in order, either of which may be absent.
+fn bounds<T: Arbitrary + PartialOrd + Copy>(
+    range: impl Strategy<Value = T> + Clone,
+) -> impl Strategy<Value = (Option<T>, Option<T>)> {
+    (option::of(range.clone()), option::of(range)).prop_map(|(a, b)| match (a, b) {
+        (Some(a), Some(b)) if b < a => (Some(b), Some(a)),
+        bounds => bounds,
+    })
+}
+
+fn string_type() -> impl Strategy<Value = Type> {
+    let format = prop_oneof![
+        Just(VariantOrUnknownOrEmpty::Empty),
+        Just(VariantOrUnknownOrEmpty::Item(StringFormat::Date)),
+        Just(VariantOrUnknownOrEmpty::Item(StringFormat::Byte)),
+        Just(VariantOrUnknownOrEmpty::Unknown("uuid".to_owned())),
+    ];
+    (format, bounds(0..64usize), vec(name(), 0..3)).prop_map(|(format, (min, max), values)| {
+        Type::String(StringType {
+            format,
+            min_length: min,
+            max_length: max,
+            enumeration: values,
+            ..StringType::default()
+        })
+    })
+}
+
+fn integer_type() -> impl Strategy<Value = Type> {
+    let format = prop_oneof![
+        Just(VariantOrUnknownOrEmpty::Empty),
+        Just(VariantOrUnknownOrEmpty::Item(IntegerFormat::Int32)),
+        Just(VariantOrUnknownOrEmpty::Item(IntegerFormat::Int64)),
+    ];
+    (format, bounds(-1000..1000i64)).prop_map(|(format, (minimum, maximum))| {
+        Type::Integer(IntegerType {
+            format,
+            minimum,
+            maximum,
+            ..IntegerType::default()
+        })
+    })
+}
+
+fn number_type() -> impl Strategy<Value = Type> {
+    // Bounds are whole numbers, which every format writes back the same.
+    bounds(-1000..1000i32).prop_map(|(minimum, maximum)| {
+        Type::Number(NumberType {
+            minimum: minimum.map(f64::from),
+            maximum: maximum.map(f64::from),
+            ..NumberType::default()
+        })
+    })
+}
+
+fn schema_of(kind: impl Strategy<Value = Type>) -> impl Strategy<Value = Schema> {
+    (option::of(text()), any::<bool>(), kind).prop_map(|(description, nullable, kind)| Schema {
+        schema_data: SchemaData {
+            description,
+            nullable,
+            ..SchemaData::default()
+        },
+        schema_kind: SchemaKind::Type(kind),
+    })
+}
+
+fn object_type(inner: BoxedStrategy<Schema>) -> impl Strategy<Value = Type> {
+    vec((name(), inner, any::<bool>()), 0..4).prop_map(|properties| {
+        let mut object = ObjectType::default();
+        for (name, schema, required) in properties {
+            let name = Name::from(name);
+            if object.properties.contains_key(name.as_str()) {
+                continue;
+            }
+            if required {
+                object.required.push(name.clone());
+            }
+            object
+                .properties
+                .insert(name, ReferenceOr::boxed_item(schema));
+        }
+        Type::Object(object)
+    })
+}
+
+fn array_type(inner: BoxedStrategy<Schema>) -> impl Strategy<Value = Type> {
+    (inner, bounds(0..16usize), any::<bool>()).prop_map(|(items, (min, max), unique)| {
+        Type::Array(ArrayType {
+            items: ReferenceOr::boxed_item(items),
+            min_items: min,
+            max_items: max,
+            unique_items: unique,
+        })
+    })
+}
+
+impl Arbitrary for Schema {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Schema>;
+
+    /// Schemas of every type, nested up to four levels deep.
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        let leaf = prop_oneof![
+            schema_of(string_type()),
+            schema_of(integer_type()),
+            schema_of(number_type()),
+            schema_of(Just(Type::Boolean {})),
+        ];
+        leaf.prop_recursive(4, 32, 4, |inner| {
+            prop_oneof![
+                schema_of(object_type(inner.clone())),
+                schema_of(array_type(inner)),
+            ]
+        })
+        .boxed()
+    }
+}
+
+fn parameter() -> impl Strategy<Value = Parameter> {
+    (name(), any::<bool>(), any::<bool>(), any::<Schema>()).prop_map(
+        |(name, header, required, schema)| {
+            let builder = if header {
+                Parameter::header(name)
+            } else {
+                Parameter::query(name)
+            };
+            builder.required(required).schema(schema).build()
+        },
+    )
+}
+
+impl Arbitrary for Operation {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Operation>;
+
+    /// Operations with query and header parameters, perhaps a JSON body,
+    /// and from one to three responses. Their operation IDs are left to
+    /// the document to give.
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        let statuses = btree_set(prop::sample::select(vec![200u16, 201, 204, 400, 404]), 1..4);
+        let responses = vec((text(), option::of(any::<Schema>())), 3);
+        (
+            option::of(text()),
+            vec(parameter(), 0..3),
+            option::of(any::<Schema>()),
+            statuses,
+            responses,
+            any::<bool>(),
+        )
+            .prop_map(
+                |(summary, parameters, body, statuses, responses, deprecated)| {
+                    let mut builder = Operation::builder();
+                    if let Some(summary) = summary {
+                        builder = builder.summary(summary);
+                    }
+                    let mut names = Vec::new();
+                    for parameter in parameters {
+                        let data = parameter.parameter_data_ref();
+                        let key = (parameter.location(), data.name.to_ascii_lowercase());
+                        if !names.contains(&key) {
+                            names.push(key);
+                            builder = builder.parameter(parameter);
+                        }
+                    }
+                    if let Some(body) = body {
+                        builder = builder.request_body(RequestBody::builder().json(body).build());
+                    }
+                    for (status, (description, schema)) in statuses.into_iter().zip(responses) {
+                        let mut response = Response::builder(description);
+                        if let Some(schema) = schema.filter(|_| status != 204) {
+                            response = response.json(schema);
+                        }
+                        builder = builder.response(StatusCode::Code(status), response.build());
+                    }
+                    if deprecated {
+                        builder = builder.deprecated();
+                    }
+                    builder.build()
+                },
+            )
+            .boxed()
+    }
+}
+
+impl Arbitrary for OpenAPI {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<OpenAPI>;
+
+    /// Documents of up to four paths, each with a `get` operation and
+    /// perhaps a `post` one, and up to four component schemas.
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        let paths = vec(
+            (name(), any::<Operation>(), option::of(any::<Operation>())),
+            0..4,
+        );
+        let schemas = vec((name(), any::<Schema>()), 0..4);
+        (text(), "[0-9]\\.[0-9]", paths, schemas)
+            .prop_map(|(title, version, paths, schemas)| {
+                let mut builder = OpenAPI::builder(title, version);
+                let mut seen = Vec::new();
+                for (i, (segment, mut get, post)) in paths.into_iter().enumerate() {
+                    let path = format!("/{}", segment);
+                    if seen.contains(&path) {
+                        continue;
+                    }
+                    seen.push(path.clone());
+                    get.operation_id = Some(format!("get{}", i));
+                    let mut item = PathItem::builder().get(get);
+                    if let Some(mut post) = post {
+                        post.operation_id = Some(format!("post{}", i));
+                        item = item.post(post);
+                    }
+                    builder = builder.path(path, item.build());
+                }
+                for (name, schema) in schemas {
+                    builder = builder.schema(name, schema);
+                }
+                builder.build()
+            })
+            .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn reads_back_what_it_writes(api in any::<OpenAPI>()) {
+            let json = serde_json::to_string(&api).unwrap();
+            prop_assert_eq!(&serde_json::from_str::<OpenAPI>(&json).unwrap(), &api);
+            let yaml = serde_yaml::to_string(&api).unwrap();
+            prop_assert_eq!(&serde_yaml::from_str::<OpenAPI>(&yaml).unwrap(), &api);
+        }
+
+        #[test]
+        fn generates_valid_documents(api in any::<OpenAPI>()) {
+            prop_assert_eq!(api.validate(), Vec::new());
+        }
+    }
+}
@@ -0,0 +1,219 @@
+use crate::reference::{escape_pointer_segment, unescape_pointer_segment};
+use crate::visit::VisitMut;
+use crate::*;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+
+/// Why [`OpenAPI::prefix_paths`] or [`OpenAPI::strip_path_prefix`] left
+/// the paths as they were: two paths would have become the same.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathConflict {
+    pub first: String,
+    pub second: String,
+    /// What both would have become.
+    pub path: String,
+}
+
+impl fmt::Display for PathConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "paths `{}` and `{}` would both be `{}`",
+            self.first, self.second, self.path
+        )
+    }
+}
+
+impl Error for PathConflict {}
+
+impl OpenAPI {
+    /// Moves every path under `prefix`, such as `/pets` to `/v2/pets` given
+    /// `/v2`, for serving the API under a base path. Path items keep their
+    /// operations and parameters, and references into them, such as the
+    /// `operationRef` of links, are rewritten.
+    ///
+    /// ```
+    /// use openapiv3::OpenAPI;
+    ///
+    /// let mut api: OpenAPI = serde_yaml::from_str(
+    ///     "
+    /// openapi: 3.0.0
+    /// info: {title: t, version: '1'}
+    /// paths:
+    ///   /api/health: {get: {responses: {}}}
+    ///   /status: {get: {responses: {}}}
+    /// ",
+    /// )
+    /// .unwrap();
+    /// api.strip_path_prefix("/api").unwrap();
+    /// api.prefix_paths("/v2").unwrap();
+    /// let paths: Vec<_> = api.paths.keys().map(String::as_str).collect();
+    /// assert_eq!(paths, ["/v2/health", "/v2/status"]);
+    /// ```
+    pub fn prefix_paths(&mut self, prefix: &str) -> Result<(), PathConflict> {
+        let prefix = normalize(prefix);
+        self.move_paths(|path| {
+            let separator = if path.starts_with('/') { "" } else { "/" };
+            Some(format!("{}{}{}", prefix, separator, path))
+        })
+    }
+
+    /// Moves the paths under `prefix` out from under it, such as
+    /// `/api/pets` to `/pets` and `/api` to `/` given `/api`, rewriting
+    /// references into them as [`OpenAPI::prefix_paths`] does. Other paths,
+    /// such as `/apis`, are left where they are.
+    ///
+    /// Fails, leaving the paths as they were, if a path would take the
+    /// place of another.
+    pub fn strip_path_prefix(&mut self, prefix: &str) -> Result<(), PathConflict> {
+        let prefix = normalize(prefix);
+        if prefix.is_empty() {
+            return Ok(());
+        }
+        self.move_paths(|path| match path.strip_prefix(&prefix)? {
+            "" => Some("/".to_owned()),
+            rest if rest.starts_with('/') => Some(rest.to_owned()),
+            _ => None,
+        })
+    }
+
+    /// Moves each path to where `f` puts it, if it moves it.
+    fn move_paths(&mut self, f: impl Fn(&str) -> Option<String>) -> Result<(), PathConflict> {
+        let moves: BTreeMap<String, String> = self
+            .paths
+            .keys()
+            .filter_map(|path| Some((path.clone(), f(path)?)))
+            .filter(|(old, new)| old != new)
+            .collect();
+        let mut taken: BTreeMap<&str, &str> = BTreeMap::new();
+        for path in self.paths.keys() {
+            let new = moves.get(path).unwrap_or(path);
+            if let Some(first) = taken.insert(new, path) {
+                return Err(PathConflict {
+                    first: first.to_owned(),
+                    second: path.clone(),
+                    path: new.clone(),
+                });
+            }
+        }
+
+        self.paths.paths = std::mem::take(&mut self.paths.paths)
+            .into_iter()
+            .map(|(path, item)| match moves.get(&path) {
+                Some(new) => (new.clone(), item),
+                None => (path, item),
+            })
+            .collect();
+        self.visit_mut(&mut PathRefs(&moves));
+        Ok(())
+    }
+}
+
+/// `prefix` with a leading `/` and without a trailing one, or empty.
+fn normalize(prefix: &str) -> String {
+    match prefix.trim_matches('/') {
+        "" => String::new(),
+        prefix => format!("/{}", prefix),
+    }
+}
+
+/// Rewrites references into paths that moved, by old path.
+struct PathRefs<'a>(&'a BTreeMap<String, String>);
+
+impl PathRefs<'_> {
+    fn rewrite(&self, target: &str) -> Option<String> {
+        let mut segments = target.strip_prefix("#/paths/")?.splitn(2, '/');
+        let path = unescape_pointer_segment(segments.next()?);
+        let new = format!("#/paths/{}", escape_pointer_segment(self.0.get(&path)?));
+        Some(match segments.next() {
+            Some(rest) => format!("{}/{}", new, rest),
+            None => new,
+        })
+    }
+}
+
+impl VisitMut for PathRefs<'_> {
+    fn visit_link_mut(&mut self, _: &str, link: &mut Link) {
+        if let Some(new) = link.operation_ref.as_deref().and_then(|r| self.rewrite(r)) {
+            link.operation_ref = Some(new);
+        }
+    }
+
+    fn visit_reference_mut(&mut self, _: &str, reference: &mut Reference) {
+        let new = self.rewrite(reference.as_str());
+        if let Some(new) = new.and_then(|r| r.parse().ok()) {
+            *reference = new;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn moves_paths() {
+        let text = "
+openapi: 3.0.0
+info: {title: t, version: '1'}
+paths:
+  /api:
+    get: {responses: {}}
+  /api/pets/{id}:
+    parameters: [{name: id, in: path, required: true, schema: {type: string}}]
+    get:
+      responses:
+        200:
+          description: a pet
+          links:
+            self: {operationRef: '#/paths/~1api~1pets~1{id}/get'}
+  /apis:
+    get: {responses: {}}
+  /pet:
+    $ref: '#/paths/~1api~1pets~1{id}'
+  /pets/{id}:
+    get: {responses: {}}
+";
+        let mut api: OpenAPI = serde_yaml::from_str(text).unwrap();
+        let original = api.clone();
+        assert_eq!(
+            api.strip_path_prefix("api/"),
+            Err(PathConflict {
+                first: "/api/pets/{id}".to_owned(),
+                second: "/pets/{id}".to_owned(),
+                path: "/pets/{id}".to_owned(),
+            })
+        );
+        assert_eq!(api, original);
+
+        remove_entry(&mut api.paths, "/pets/{id}");
+        api.strip_path_prefix("/api").unwrap();
+        api.prefix_paths("/v2/").unwrap();
+        let mut paths: Vec<_> = api.paths.keys().map(String::as_str).collect();
+        paths.sort_unstable();
+        assert_eq!(paths, ["/v2/", "/v2/apis", "/v2/pet", "/v2/pets/{id}"]);
+        assert_eq!(
+            api.paths["/v2/pet"],
+            ReferenceOr::ref_("#/paths/~1v2~1pets~1{id}")
+        );
+
+        let item = match &api.paths["/v2/pets/{id}"] {
+            ReferenceOr::Item(item) => item,
+            ReferenceOr::Reference { .. } => panic!("not an item"),
+        };
+        assert_eq!(item.parameters.len(), 1);
+        let response = match &item.get.as_ref().unwrap().responses.responses[&StatusCode::Code(200)]
+        {
+            ReferenceOr::Item(response) => response,
+            ReferenceOr::Reference { .. } => panic!("not an item"),
+        };
+        assert_eq!(
+            response.links["self"],
+            ReferenceOr::Item(Link {
+                operation_ref: Some("#/paths/~1v2~1pets~1{id}/get".to_owned()),
+                ..Link::default()
+            })
+        );
+    }
+}
@@ -0,0 +1,637 @@
+//! Documents that borrow their strings from the source text where they
+//! can, for reading a large document without allocating each of its
+//! names, descriptions and keys.
+//!
+//! The model covers what makes up the bulk of large documents: the
+//! operations of the paths, with their parameters, request bodies and
+//! responses, and the schemas, parameters, request bodies and responses of
+//! the components, schemas down to their properties, items and
+//! subschemas. Everything else, such as security, links, callbacks and
+//! extensions, is skipped over without being read. References are the
+//! [`Reference`](crate::Reference)s of the owned model.
+//!
+//! Strings are borrowed from JSON text unless they have escapes, which
+//! must be unescaped into a string of their own. serde_yaml does not lend
+//! out its input, so YAML is read by way of a `serde_yaml::Value`, into
+//! owned strings.
+//!
+//! ```
+//! use openapiv3::borrowed::OpenAPI;
+//! use openapiv3::{Method, ReferenceOr};
+//!
+//! let text = r##"{
+//!     "openapi": "3.0.0",
+//!     "info": {"title": "Pets", "version": "1"},
+//!     "paths": {
+//!         "/pets": {"get": {"operationId": "listPets", "summary": "List \"pets\"", "responses": {
+//!             "200": {"description": "The pets.", "content": {"application/json": {
+//!                 "schema": {"type": "array", "items": {"$ref": "#/components/schemas/Pet"}}
+//!             }}}
+//!         }}}
+//!     }
+//! }"##;
+//! let api: OpenAPI = serde_json::from_str(text).unwrap();
+//! assert!(api.info.title.is_borrowed());
+//!
+//! let (path, method, operation) = api.operations().next().unwrap();
+//! assert_eq!((path, method), ("/pets", Method::Get));
+//! assert_eq!(operation.operation_id.as_deref(), Some("listPets"));
+//! assert_eq!(operation.summary.as_deref(), Some("List \"pets\""));
+//! assert!(!operation.summary.as_ref().unwrap().is_borrowed());
+//!
+//! let response = match &operation.responses["200"] {
+//!     ReferenceOr::Item(response) => response,
+//!     ReferenceOr::Reference { .. } => unreachable!(),
+//! };
+//! let schema = match &response.content["application/json"].schema {
+//!     Some(ReferenceOr::Item(schema)) => schema,
+//!     _ => unreachable!(),
+//! };
+//! assert_eq!(schema.schema_type.as_deref(), Some("array"));
+//! ```
+
+use crate::{Map, Method, ReferenceOr};
+use serde::de::{self, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::Deserialize;
+use std::borrow::{Borrow, Cow};
+use std::fmt;
+use std::ops::Deref;
+
+/// A string of a document, borrowed from its source text where possible.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Text<'a>(pub Cow<'a, str>);
+
+impl Text<'_> {
+    /// Whether the string is borrowed from the source text, rather than
+    /// allocated.
+    pub fn is_borrowed(&self) -> bool {
+        matches!(self.0, Cow::Borrowed(_))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Deref for Text<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Borrow<str> for Text<'_> {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Text<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for Text<'a> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct TextVisitor;
+
+        impl<'de> Visitor<'de> for TextVisitor {
+            type Value = Text<'de>;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a string")
+            }
+
+            fn visit_borrowed_str<E: de::Error>(self, s: &'de str) -> Result<Self::Value, E> {
+                Ok(Text(Cow::Borrowed(s)))
+            }
+
+            fn visit_str<E: de::Error>(self, s: &str) -> Result<Self::Value, E> {
+                Ok(Text(Cow::Owned(s.to_owned())))
+            }
+
+            fn visit_string<E: de::Error>(self, s: String) -> Result<Self::Value, E> {
+                Ok(Text(Cow::Owned(s)))
+            }
+        }
+
+        deserializer.deserialize_str(TextVisitor)
+    }
+}
+
+/// A value of a document, such as an example or the values of an `enum`,
+/// with its strings borrowed where possible.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value<'a> {
+    Null,
+    Bool(bool),
+    Number(serde_json::Number),
+    String(Text<'a>),
+    Array(Vec<Value<'a>>),
+    Object(Map<Text<'a>, Value<'a>>),
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for Value<'a> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ValueVisitor;
+
+        impl<'de> Visitor<'de> for ValueVisitor {
+            type Value = Value<'de>;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a value")
+            }
+
+            fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+                Ok(Value::Null)
+            }
+
+            fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+                Ok(Value::Null)
+            }
+
+            fn visit_some<D: Deserializer<'de>>(self, d: D) -> Result<Self::Value, D::Error> {
+                Value::deserialize(d)
+            }
+
+            fn visit_bool<E: de::Error>(self, b: bool) -> Result<Self::Value, E> {
+                Ok(Value::Bool(b))
+            }
+
+            fn visit_i64<E: de::Error>(self, n: i64) -> Result<Self::Value, E> {
+                Ok(Value::Number(n.into()))
+            }
+
+            fn visit_u64<E: de::Error>(self, n: u64) -> Result<Self::Value, E> {
+                Ok(Value::Number(n.into()))
+            }
+
+            fn visit_f64<E: de::Error>(self, n: f64) -> Result<Self::Value, E> {
+                serde_json::Number::from_f64(n)
+                    .map(Value::Number)
+                    .ok_or_else(|| E::custom("numbers must be finite"))
+            }
+
+            fn visit_borrowed_str<E: de::Error>(self, s: &'de str) -> Result<Self::Value, E> {
+                Ok(Value::String(Text(Cow::Borrowed(s))))
+            }
+
+            fn visit_str<E: de::Error>(self, s: &str) -> Result<Self::Value, E> {
+                Ok(Value::String(Text(Cow::Owned(s.to_owned()))))
+            }
+
+            fn visit_string<E: de::Error>(self, s: String) -> Result<Self::Value, E> {
+                Ok(Value::String(Text(Cow::Owned(s))))
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut values = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+                while let Some(value) = seq.next_element()? {
+                    values.push(value);
+                }
+                Ok(Value::Array(values))
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut object = Map::new();
+                while let Some((key, value)) = map.next_entry()? {
+                    object.insert(key, value);
+                }
+                Ok(Value::Object(object))
+            }
+        }
+
+        deserializer.deserialize_any(ValueVisitor)
+    }
+}
+
+/// A document: its metadata, operations and components.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+pub struct OpenAPI<'a> {
+    #[serde(borrow)]
+    pub openapi: Text<'a>,
+    #[serde(borrow)]
+    pub info: Info<'a>,
+    #[serde(borrow, default)]
+    pub servers: Vec<Server<'a>>,
+    #[serde(borrow, default)]
+    pub paths: Map<Text<'a>, PathItem<'a>>,
+    #[serde(borrow)]
+    pub components: Option<Components<'a>>,
+    #[serde(borrow, default)]
+    pub tags: Vec<Tag<'a>>,
+}
+
+impl<'a> OpenAPI<'a> {
+    /// Every operation of the document, along with its path and method, as
+    /// [`crate::OpenAPI::operations`] iterates over them.
+    pub fn operations(&self) -> impl Iterator<Item = (&str, Method, &Operation<'a>)> {
+        self.paths.iter().flat_map(|(path, item)| {
+            item.operations()
+                .map(move |(method, operation)| (path.as_str(), method, operation))
+        })
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+pub struct Info<'a> {
+    #[serde(borrow)]
+    pub title: Text<'a>,
+    #[serde(borrow)]
+    pub description: Option<Text<'a>>,
+    #[serde(borrow)]
+    pub version: Text<'a>,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+pub struct Server<'a> {
+    #[serde(borrow)]
+    pub url: Text<'a>,
+    #[serde(borrow)]
+    pub description: Option<Text<'a>>,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+pub struct Tag<'a> {
+    #[serde(borrow)]
+    pub name: Text<'a>,
+    #[serde(borrow)]
+    pub description: Option<Text<'a>>,
+}
+
+/// A path item. A path item that is a reference has no operations.
+#[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq)]
+pub struct PathItem<'a> {
+    #[serde(borrow)]
+    pub summary: Option<Text<'a>>,
+    #[serde(borrow)]
+    pub description: Option<Text<'a>>,
+    #[serde(borrow)]
+    pub get: Option<Operation<'a>>,
+    #[serde(borrow)]
+    pub put: Option<Operation<'a>>,
+    #[serde(borrow)]
+    pub post: Option<Operation<'a>>,
+    #[serde(borrow)]
+    pub delete: Option<Operation<'a>>,
+    #[serde(borrow)]
+    pub options: Option<Operation<'a>>,
+    #[serde(borrow)]
+    pub head: Option<Operation<'a>>,
+    #[serde(borrow)]
+    pub patch: Option<Operation<'a>>,
+    #[serde(borrow)]
+    pub trace: Option<Operation<'a>>,
+    /// The parameters of all the operations of the path item.
+    #[serde(borrow, default)]
+    pub parameters: Vec<ReferenceOr<Parameter<'a>>>,
+}
+
+impl<'a> PathItem<'a> {
+    /// The operation for a method, if the path item has one.
+    pub fn operation(&self, method: Method) -> Option<&Operation<'a>> {
+        match method {
+            Method::Get => self.get.as_ref(),
+            Method::Put => self.put.as_ref(),
+            Method::Post => self.post.as_ref(),
+            Method::Delete => self.delete.as_ref(),
+            Method::Options => self.options.as_ref(),
+            Method::Head => self.head.as_ref(),
+            Method::Patch => self.patch.as_ref(),
+            Method::Trace => self.trace.as_ref(),
+        }
+    }
+
+    /// The operations of the path item, with their methods, in the order
+    /// of [`Method::ALL`].
+    pub fn operations(&self) -> impl Iterator<Item = (Method, &Operation<'a>)> {
+        Method::ALL
+            .iter()
+            .filter_map(move |&method| self.operation(method).map(|operation| (method, operation)))
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Operation<'a> {
+    #[serde(borrow)]
+    pub operation_id: Option<Text<'a>>,
+    #[serde(borrow)]
+    pub summary: Option<Text<'a>>,
+    #[serde(borrow)]
+    pub description: Option<Text<'a>>,
+    #[serde(borrow, default)]
+    pub tags: Vec<Text<'a>>,
+    #[serde(borrow, default)]
+    pub parameters: Vec<ReferenceOr<Parameter<'a>>>,
+    #[serde(borrow)]
+    pub request_body: Option<ReferenceOr<RequestBody<'a>>>,
+    /// The responses of the operation, by status code or `default`.
+    #[serde(borrow, default)]
+    pub responses: Map<Text<'a>, ReferenceOr<Response<'a>>>,
+    #[serde(default)]
+    pub deprecated: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+pub struct Parameter<'a> {
+    #[serde(borrow)]
+    pub name: Text<'a>,
+    /// Where the parameter is: `query`, `header`, `path` or `cookie`.
+    #[serde(borrow, rename = "in")]
+    pub location: Text<'a>,
+    #[serde(borrow)]
+    pub description: Option<Text<'a>>,
+    #[serde(default)]
+    pub required: bool,
+    #[serde(default)]
+    pub deprecated: bool,
+    #[serde(borrow)]
+    pub schema: Option<ReferenceOr<Schema<'a>>>,
+    #[serde(borrow, default)]
+    pub content: Map<Text<'a>, MediaType<'a>>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq)]
+pub struct RequestBody<'a> {
+    #[serde(borrow)]
+    pub description: Option<Text<'a>>,
+    #[serde(borrow, default)]
+    pub content: Map<Text<'a>, MediaType<'a>>,
+    #[serde(default)]
+    pub required: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+pub struct Response<'a> {
+    #[serde(borrow)]
+    pub description: Text<'a>,
+    #[serde(borrow, default)]
+    pub headers: Map<Text<'a>, ReferenceOr<Header<'a>>>,
+    #[serde(borrow, default)]
+    pub content: Map<Text<'a>, MediaType<'a>>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq)]
+pub struct Header<'a> {
+    #[serde(borrow)]
+    pub description: Option<Text<'a>>,
+    #[serde(default)]
+    pub required: bool,
+    #[serde(borrow)]
+    pub schema: Option<ReferenceOr<Schema<'a>>>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq)]
+pub struct MediaType<'a> {
+    #[serde(borrow)]
+    pub schema: Option<ReferenceOr<Schema<'a>>>,
+    #[serde(borrow)]
+    pub example: Option<Value<'a>>,
+}
+
+/// The components of a document that the model covers, by name.
+#[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Components<'a> {
+    #[serde(borrow, default)]
+    pub schemas: Map<Text<'a>, ReferenceOr<Schema<'a>>>,
+    #[serde(borrow, default)]
+    pub responses: Map<Text<'a>, ReferenceOr<Response<'a>>>,
+    #[serde(borrow, default)]
+    pub parameters: Map<Text<'a>, ReferenceOr<Parameter<'a>>>,
+    #[serde(borrow, default)]
+    pub request_bodies: Map<Text<'a>, ReferenceOr<RequestBody<'a>>>,
+}
+
+/// A schema, with its annotations, structure and the constraints that
+/// hold strings. Numeric constraints are skipped over.
+#[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Schema<'a> {
+    #[serde(borrow)]
+    pub title: Option<Text<'a>>,
+    #[serde(borrow)]
+    pub description: Option<Text<'a>>,
+    #[serde(borrow, rename = "type")]
+    pub schema_type: Option<Text<'a>>,
+    #[serde(borrow)]
+    pub format: Option<Text<'a>>,
+    #[serde(borrow)]
+    pub pattern: Option<Text<'a>>,
+    #[serde(default)]
+    pub nullable: bool,
+    #[serde(default)]
+    pub read_only: bool,
+    #[serde(default)]
+    pub write_only: bool,
+    #[serde(default)]
+    pub deprecated: bool,
+    #[serde(borrow, default)]
+    pub properties: Map<Text<'a>, ReferenceOr<Box<Schema<'a>>>>,
+    #[serde(borrow, default)]
+    pub required: Vec<Text<'a>>,
+    #[serde(borrow)]
+    pub items: Option<ReferenceOr<Box<Schema<'a>>>>,
+    #[serde(borrow)]
+    pub additional_properties: Option<AdditionalProperties<'a>>,
+    #[serde(borrow, default)]
+    pub all_of: Vec<ReferenceOr<Schema<'a>>>,
+    #[serde(borrow, default)]
+    pub one_of: Vec<ReferenceOr<Schema<'a>>>,
+    #[serde(borrow, default)]
+    pub any_of: Vec<ReferenceOr<Schema<'a>>>,
+    #[serde(borrow, default, rename = "enum")]
+    pub enumeration: Vec<Value<'a>>,
+    #[serde(borrow)]
+    pub default: Option<Value<'a>>,
+    #[serde(borrow)]
+    pub example: Option<Value<'a>>,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum AdditionalProperties<'a> {
+    Any(bool),
+    Schema(#[serde(borrow)] Box<ReferenceOr<Schema<'a>>>),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn borrows_from_json() {
+        let text = r#"{
+            "openapi": "3.0.0",
+            "info": {"title": "Pets", "version": "1", "x-logo": {"url": "a.png"}},
+            "tags": [{"name": "pets"}],
+            "paths": {
+                "/pets/{id}": {
+                    "parameters": [],
+                    "put": {"tags": ["pets", "a\u00e9"], "deprecated": true, "responses": {}}
+                },
+                "/owners": {"$ref": "owners.yaml"}
+            },
+            "components": {
+                "schemas": {
+                    "Pet": {"type": "object", "description": "A pet"},
+                    "Dog": {"$ref": "dog.yaml"}
+                }
+            }
+        }"#;
+        let api: OpenAPI = serde_json::from_str(text).unwrap();
+        let operations: Vec<_> = api.operations().collect();
+        assert_eq!(operations.len(), 1);
+        let (path, method, operation) = operations[0];
+        assert_eq!((path, method), ("/pets/{id}", Method::Put));
+        assert!(operation.deprecated);
+        assert_eq!(operation.tags[1].as_str(), "aé");
+        assert!(operation.tags[0].is_borrowed() && !operation.tags[1].is_borrowed());
+        assert!(api.paths.keys().all(Text::is_borrowed));
+
+        let schemas = &api.components.as_ref().unwrap().schemas;
+        match &schemas["Pet"] {
+            ReferenceOr::Item(pet) => assert_eq!(pet.description.as_deref(), Some("A pet")),
+            ReferenceOr::Reference { .. } => panic!("not an item"),
+        }
+        assert_eq!(schemas["Dog"], ReferenceOr::ref_("dog.yaml"));
+
+        let yaml: serde_yaml::Value = serde_yaml::from_str(text).unwrap();
+        let yaml = OpenAPI::deserialize(yaml).unwrap();
+        assert_eq!(yaml, api);
+        assert!(!yaml.info.title.is_borrowed());
+    }
+
+    #[test]
+    fn reads_schemas_and_operations() {
+        let text = r##"{
+            "openapi": "3.0.0",
+            "info": {"title": "Pets", "version": "1"},
+            "paths": {
+                "/pets": {
+                    "parameters": [{"$ref": "#/components/parameters/Limit"}],
+                    "post": {
+                        "parameters": [{"name": "dry", "in": "query", "schema": {"type": "boolean"}}],
+                        "requestBody": {
+                            "required": true,
+                            "content": {"application/json": {"schema": {"$ref": "#/components/schemas/Pet"}}}
+                        },
+                        "responses": {
+                            "201": {
+                                "description": "Created.",
+                                "headers": {"Location": {"schema": {"type": "string"}}}
+                            },
+                            "default": {"$ref": "#/components/responses/Error"}
+                        },
+                        "security": [{"key": []}]
+                    }
+                }
+            },
+            "components": {
+                "schemas": {
+                    "Pet": {
+                        "type": "object",
+                        "required": ["name"],
+                        "properties": {
+                            "name": {"type": "string", "pattern": "^[a-z]+$", "example": "rex"},
+                            "kind": {"type": "string", "enum": ["dog", "cat", null], "nullable": true},
+                            "tags": {"type": "array", "items": {"type": "string"}},
+                            "owner": {"$ref": "#/components/schemas/Owner"}
+                        },
+                        "additionalProperties": {"type": "integer", "minimum": 0},
+                        "allOf": [{"$ref": "#/components/schemas/Base"}],
+                        "example": {"name": "rex", "age": 3, "weight": 1.5, "good": true}
+                    }
+                },
+                "parameters": {"Limit": {"name": "limit", "in": "query", "required": false}},
+                "responses": {"Error": {"description": "An error."}}
+            }
+        }"##;
+        let api: OpenAPI = serde_json::from_str(text).unwrap();
+
+        let item = &api.paths["/pets"];
+        assert_eq!(
+            item.parameters,
+            [ReferenceOr::ref_("#/components/parameters/Limit")]
+        );
+        let operation = item.post.as_ref().unwrap();
+        match &operation.parameters[0] {
+            ReferenceOr::Item(parameter) => {
+                assert_eq!((&*parameter.name, &*parameter.location), ("dry", "query"));
+                assert!(parameter.name.is_borrowed());
+            }
+            ReferenceOr::Reference { .. } => panic!("not an item"),
+        }
+        match &operation.request_body {
+            Some(ReferenceOr::Item(body)) => {
+                assert!(body.required);
+                assert_eq!(
+                    body.content["application/json"].schema,
+                    Some(ReferenceOr::ref_("#/components/schemas/Pet"))
+                );
+            }
+            _ => panic!("no request body"),
+        }
+        match &operation.responses["201"] {
+            ReferenceOr::Item(response) => {
+                assert_eq!(response.description.as_str(), "Created.");
+                assert!(response.headers.contains_key("Location"));
+            }
+            ReferenceOr::Reference { .. } => panic!("not an item"),
+        }
+        assert_eq!(
+            operation.responses["default"],
+            ReferenceOr::ref_("#/components/responses/Error")
+        );
+
+        let components = api.components.as_ref().unwrap();
+        let pet = match &components.schemas["Pet"] {
+            ReferenceOr::Item(pet) => pet,
+            ReferenceOr::Reference { .. } => panic!("not an item"),
+        };
+        assert_eq!(pet.required, [Text(Cow::Borrowed("name"))]);
+        assert_eq!(
+            pet.properties["owner"],
+            ReferenceOr::ref_("#/components/schemas/Owner")
+        );
+        let kind = match &pet.properties["kind"] {
+            ReferenceOr::Item(kind) => kind,
+            ReferenceOr::Reference { .. } => panic!("not an item"),
+        };
+        assert!(kind.nullable);
+        assert_eq!(
+            kind.enumeration,
+            [
+                Value::String(Text(Cow::Borrowed("dog"))),
+                Value::String(Text(Cow::Borrowed("cat"))),
+                Value::Null
+            ]
+        );
+        match &pet.properties["tags"] {
+            ReferenceOr::Item(tags) => assert!(tags.items.is_some()),
+            ReferenceOr::Reference { .. } => panic!("not an item"),
+        }
+        assert!(matches!(
+            &pet.additional_properties,
+            Some(AdditionalProperties::Schema(_))
+        ));
+        assert_eq!(pet.all_of, [ReferenceOr::ref_("#/components/schemas/Base")]);
+        match &pet.example {
+            Some(Value::Object(example)) => {
+                assert_eq!(example["age"], Value::Number(3.into()));
+                assert_eq!(example["good"], Value::Bool(true));
+            }
+            _ => panic!("no example"),
+        }
+        match &components.parameters["Limit"] {
+            ReferenceOr::Item(limit) => assert!(!limit.required),
+            ReferenceOr::Reference { .. } => panic!("not an item"),
+        }
+
+        let yaml: serde_yaml::Value = serde_yaml::from_str(text).unwrap();
+        assert_eq!(OpenAPI::deserialize(yaml).unwrap(), api);
+    }
+}
@@ -0,0 +1,498 @@
+//! Fluent construction of the objects that have many optional fields.
+
+use crate::*;
+use serde_json::Value;
+
+impl OpenAPI {
+    /// Starts building a 3.0 document with the given title and API version.
+    pub fn builder(title: impl Into<String>, version: impl Into<String>) -> OpenAPIBuilder {
+        OpenAPIBuilder {
+            api: OpenAPI {
+                openapi: "3.0.3".to_owned(),
+                info: Info {
+                    title: title.into(),
+                    version: version.into(),
+                    ..Info::default()
+                },
+                ..OpenAPI::default()
+            },
+        }
+    }
+}
+
+/// Builds an [`OpenAPI`] document; see [`OpenAPI::builder`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct OpenAPIBuilder {
+    api: OpenAPI,
+}
+
+impl OpenAPIBuilder {
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.api.info.description = Some(description.into());
+        self
+    }
+
+    /// Adds a server with the given URL.
+    pub fn server(mut self, url: impl Into<String>) -> Self {
+        self.api.servers.push(Server {
+            url: url.into(),
+            ..Server::default()
+        });
+        self
+    }
+
+    pub fn path(mut self, path: impl Into<String>, item: impl Into<ReferenceOr<PathItem>>) -> Self {
+        self.api.paths.insert(path.into(), item.into());
+        self
+    }
+
+    /// Adds a schema to the components.
+    pub fn schema(
+        mut self,
+        name: impl Into<String>,
+        schema: impl Into<ReferenceOr<Schema>>,
+    ) -> Self {
+        self.components().schemas.insert(name.into(), schema.into());
+        self
+    }
+
+    /// Adds a security scheme to the components.
+    pub fn security_scheme(
+        mut self,
+        name: impl Into<String>,
+        scheme: impl Into<ReferenceOr<SecurityScheme>>,
+    ) -> Self {
+        self.components()
+            .security_schemes
+            .insert(name.into(), scheme.into());
+        self
+    }
+
+    /// Replaces the components.
+    pub fn with_components(mut self, components: Components) -> Self {
+        self.api.components = Some(components);
+        self
+    }
+
+    pub fn security(mut self, requirement: SecurityRequirement) -> Self {
+        self.api.security.push(requirement);
+        self
+    }
+
+    pub fn tag(mut self, tag: Tag) -> Self {
+        self.api.tags.push(tag);
+        self
+    }
+
+    pub fn external_docs(mut self, docs: ExternalDocumentation) -> Self {
+        self.api.external_docs = Some(docs);
+        self
+    }
+
+    pub fn build(self) -> OpenAPI {
+        self.api
+    }
+
+    fn components(&mut self) -> &mut Components {
+        self.api.components.get_or_insert_with(Components::default)
+    }
+}
+
+impl PathItem {
+    pub fn builder() -> PathItemBuilder {
+        PathItemBuilder::default()
+    }
+}
+
+/// Builds a [`PathItem`]; see [`PathItem::builder`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PathItemBuilder {
+    item: PathItem,
+}
+
+macro_rules! operation_setters {
+    ($($method:ident),*) => {
+        $(pub fn $method(mut self, operation: Operation) -> Self {
+            self.item.$method = Some(operation);
+            self
+        })*
+    };
+}
+
+impl PathItemBuilder {
+    operation_setters!(get, put, post, delete, options, head, patch, trace);
+
+    pub fn server(mut self, server: Server) -> Self {
+        self.item.servers.push(server);
+        self
+    }
+
+    pub fn parameter(mut self, parameter: impl Into<ReferenceOr<Parameter>>) -> Self {
+        self.item.parameters.push(parameter.into());
+        self
+    }
+
+    pub fn build(self) -> PathItem {
+        self.item
+    }
+}
+
+impl Operation {
+    pub fn builder() -> OperationBuilder {
+        OperationBuilder::default()
+    }
+}
+
+/// Builds an [`Operation`]; see [`Operation::builder`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OperationBuilder {
+    operation: Operation,
+}
+
+impl OperationBuilder {
+    pub fn tag(mut self, tag: impl Into<Name>) -> Self {
+        self.operation.tags.push(tag.into());
+        self
+    }
+
+    pub fn summary(mut self, summary: impl Into<String>) -> Self {
+        self.operation.summary = Some(summary.into());
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.operation.description = Some(description.into());
+        self
+    }
+
+    pub fn external_docs(mut self, docs: ExternalDocumentation) -> Self {
+        self.operation.external_documentation = Some(docs);
+        self
+    }
+
+    pub fn operation_id(mut self, operation_id: impl Into<String>) -> Self {
+        self.operation.operation_id = Some(operation_id.into());
+        self
+    }
+
+    pub fn parameter(mut self, parameter: impl Into<ReferenceOr<Parameter>>) -> Self {
+        self.operation.parameters.push(parameter.into());
+        self
+    }
+
+    pub fn request_body(mut self, body: impl Into<ReferenceOr<RequestBody>>) -> Self {
+        self.operation.request_body = Some(body.into());
+        self
+    }
+
+    pub fn response(
+        mut self,
+        status: StatusCode,
+        response: impl Into<ReferenceOr<Response>>,
+    ) -> Self {
+        self.operation
+            .responses
+            .responses
+            .insert(status, response.into());
+        self
+    }
+
+    pub fn default_response(mut self, response: impl Into<ReferenceOr<Response>>) -> Self {
+        self.operation.responses.default = Some(response.into());
+        self
+    }
+
+    pub fn deprecated(mut self) -> Self {
+        self.operation.deprecated = true;
+        self
+    }
+
+    pub fn security(mut self, requirement: SecurityRequirement) -> Self {
+        self.operation
+            .security
+            .get_or_insert_with(Vec::new)
+            .push(requirement);
+        self
+    }
+
+    pub fn server(mut self, server: Server) -> Self {
+        self.operation.servers.push(server);
+        self
+    }
+
+    pub fn build(self) -> Operation {
+        self.operation
+    }
+}
+
+impl Parameter {
+    /// Starts building a query parameter.
+    pub fn query(name: impl Into<String>) -> ParameterBuilder {
+        ParameterBuilder::new(name, ParameterLocation::Query)
+    }
+
+    /// Starts building a header parameter.
+    pub fn header(name: impl Into<String>) -> ParameterBuilder {
+        ParameterBuilder::new(name, ParameterLocation::Header)
+    }
+
+    /// Starts building a path parameter, which is required.
+    pub fn path(name: impl Into<String>) -> ParameterBuilder {
+        ParameterBuilder::new(name, ParameterLocation::Path).required(true)
+    }
+
+    /// Starts building a cookie parameter.
+    pub fn cookie(name: impl Into<String>) -> ParameterBuilder {
+        ParameterBuilder::new(name, ParameterLocation::Cookie)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ParameterLocation {
+    Query,
+    Header,
+    Path,
+    Cookie,
+}
+
+/// Builds a [`Parameter`]; see [`Parameter::query`] and its siblings.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParameterBuilder {
+    location: ParameterLocation,
+    data: ParameterData,
+}
+
+impl ParameterBuilder {
+    fn new(name: impl Into<String>, location: ParameterLocation) -> Self {
+        ParameterBuilder {
+            location,
+            data: ParameterData {
+                name: name.into(),
+                ..ParameterData::default()
+            },
+        }
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.data.description = Some(description.into());
+        self
+    }
+
+    pub fn required(mut self, required: bool) -> Self {
+        self.data.required = required;
+        self
+    }
+
+    pub fn deprecated(mut self) -> Self {
+        self.data.deprecated = Some(true);
+        self
+    }
+
+    pub fn schema(mut self, schema: impl Into<ReferenceOr<Schema>>) -> Self {
+        self.data.format = ParameterSchemaOrContent::Schema(schema.into());
+        self
+    }
+
+    /// Describes the parameter by a media type instead of a schema.
+    pub fn content(mut self, media_type: impl Into<MediaTypeRange>, content: MediaType) -> Self {
+        let mut map = match self.data.format {
+            ParameterSchemaOrContent::Content(map) => map,
+            ParameterSchemaOrContent::Schema(_) => Content::new(),
+        };
+        map.insert(media_type.into(), content);
+        self.data.format = ParameterSchemaOrContent::Content(map);
+        self
+    }
+
+    pub fn example(mut self, example: Value) -> Self {
+        self.data.example = Some(example);
+        self
+    }
+
+    pub fn build(self) -> Parameter {
+        let parameter_data = self.data;
+        match self.location {
+            ParameterLocation::Query => Parameter::Query {
+                parameter_data,
+                allow_reserved: false,
+                style: QueryStyle::default(),
+                allow_empty_value: None,
+            },
+            ParameterLocation::Header => Parameter::Header {
+                parameter_data,
+                style: HeaderStyle::default(),
+            },
+            ParameterLocation::Path => Parameter::Path {
+                parameter_data,
+                style: PathStyle::default(),
+            },
+            ParameterLocation::Cookie => Parameter::Cookie {
+                parameter_data,
+                style: CookieStyle::default(),
+            },
+        }
+    }
+}
+
+impl RequestBody {
+    pub fn builder() -> RequestBodyBuilder {
+        RequestBodyBuilder::default()
+    }
+}
+
+/// Builds a [`RequestBody`]; see [`RequestBody::builder`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RequestBodyBuilder {
+    body: RequestBody,
+}
+
+impl RequestBodyBuilder {
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.body.description = Some(description.into());
+        self
+    }
+
+    pub fn content(mut self, media_type: impl Into<MediaTypeRange>, content: MediaType) -> Self {
+        self.body.content.insert(media_type.into(), content);
+        self
+    }
+
+    /// Adds an `application/json` body of the given schema.
+    pub fn json(self, schema: impl Into<ReferenceOr<Schema>>) -> Self {
+        self.content(
+            "application/json",
+            MediaType {
+                schema: Some(schema.into()),
+                ..MediaType::default()
+            },
+        )
+    }
+
+    pub fn required(mut self, required: bool) -> Self {
+        self.body.required = required;
+        self
+    }
+
+    pub fn build(self) -> RequestBody {
+        self.body
+    }
+}
+
+impl Response {
+    pub fn builder(description: impl Into<String>) -> ResponseBuilder {
+        ResponseBuilder {
+            response: Response {
+                description: description.into(),
+                ..Response::default()
+            },
+        }
+    }
+}
+
+/// Builds a [`Response`]; see [`Response::builder`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResponseBuilder {
+    response: Response,
+}
+
+impl ResponseBuilder {
+    pub fn header(
+        mut self,
+        name: impl Into<String>,
+        header: impl Into<ReferenceOr<Header>>,
+    ) -> Self {
+        self.response.headers.insert(name.into(), header.into());
+        self
+    }
+
+    pub fn content(
+        mut self,
+        media_type: impl Into<MediaTypeRange>,
+        content: impl Into<ReferenceOr<MediaType>>,
+    ) -> Self {
+        self.response
+            .content
+            .insert(media_type.into(), content.into());
+        self
+    }
+
+    /// Adds an `application/json` body of the given schema.
+    pub fn json(self, schema: impl Into<ReferenceOr<Schema>>) -> Self {
+        self.content(
+            "application/json",
+            MediaType {
+                schema: Some(schema.into()),
+                ..MediaType::default()
+            },
+        )
+    }
+
+    pub fn link(mut self, name: impl Into<String>, link: impl Into<ReferenceOr<Link>>) -> Self {
+        self.response.links.insert(name.into(), link.into());
+        self
+    }
+
+    pub fn build(self) -> Response {
+        self.response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn builds_documents() {
+        let pets = ReferenceOr::ref_("#/components/schemas/Pets");
+        let api = OpenAPI::builder("Pets", "1.0")
+            .server("https://pets.example.com")
+            .path(
+                "/pets",
+                PathItem::builder()
+                    .get(
+                        Operation::builder()
+                            .operation_id("listPets")
+                            .tag("pets")
+                            .parameter(Parameter::query("limit").description("page size").build())
+                            .response(
+                                StatusCode::Code(200),
+                                Response::builder("the pets").json(pets).build(),
+                            )
+                            .build(),
+                    )
+                    .build(),
+            )
+            .schema(
+                "Pets",
+                serde_yaml::from_str::<Schema>("type: array\nitems: {}").unwrap(),
+            )
+            .build();
+
+        let expected: OpenAPI = serde_yaml::from_str(
+            "
+openapi: 3.0.3
+info: {title: Pets, version: '1.0'}
+servers: [{url: 'https://pets.example.com'}]
+paths:
+  /pets:
+    get:
+      operationId: listPets
+      tags: [pets]
+      parameters: [{name: limit, in: query, description: page size, schema: {}}]
+      responses:
+        '200':
+          description: the pets
+          content:
+            application/json: {schema: {$ref: '#/components/schemas/Pets'}}
+components:
+  schemas:
+    Pets: {type: array, items: {}}
+",
+        )
+        .unwrap();
+        assert_eq!(api, expected);
+
+        let id = Parameter::path("id").build();
+        assert!(id.parameter_data_ref().required);
+    }
+}
@@ -0,0 +1,386 @@
+use crate::discriminator::target_reference;
+use crate::external::{join_location, Documents};
+use crate::reference::unescape_pointer_segment;
+use crate::walk::{Referenceable, VisitRefs, Walk};
+use crate::*;
+use std::collections::{HashMap, HashSet};
+
+impl OpenAPI {
+    /// Copies every object referenced in another document into the
+    /// components of this one, and rewrites the references to point there,
+    /// so that the document no longer depends on any other file.
+    ///
+    /// `location` is where this document itself was loaded from, and is what
+    /// relative references are resolved against. References inside loaded
+    /// documents are bundled as well, relative to their own document.
+    ///
+    /// Each bundled object is named after the last segment of its reference,
+    /// or after the file when the reference has no fragment, with a numeric
+    /// suffix such as `Error_2` when the name is already taken. Components
+    /// that are themselves references to another document, as those of a
+    /// document made by [`OpenAPI::split`] are, are replaced by what they
+    /// refer to, keeping their name. Objects that have no section in the
+    /// Components Object, such as path items, are inlined instead.
+    pub fn bundle<R: ReferenceResolver>(
+        &mut self,
+        location: &str,
+        resolver: &R,
+    ) -> Result<(), ExternalReferenceError> {
+        let mut taken = HashSet::new();
+        if let Some(components) = &self.components {
+            for kind in ComponentKind::ALL.iter().copied() {
+                taken.extend(
+                    components
+                        .names(kind)
+                        .into_iter()
+                        .map(|name| (kind, name.to_owned())),
+                );
+            }
+        }
+        let mut bundler = Bundler {
+            documents: Documents::new(resolver),
+            bases: vec![location.to_owned()],
+            stack: Vec::new(),
+            names: HashMap::new(),
+            taken,
+            bundled: Components::default(),
+        };
+        if let Some(components) = &mut self.components {
+            bundler.claim::<SecurityScheme>(components);
+            bundler.claim::<Response>(components);
+            bundler.claim::<Parameter>(components);
+            bundler.claim::<Example>(components);
+            bundler.claim::<RequestBody>(components);
+            bundler.claim::<Header>(components);
+            bundler.claim::<Schema>(components);
+            bundler.claim::<Link>(components);
+            bundler.claim::<Callback>(components);
+            bundler.adopt::<SecurityScheme>(components)?;
+            bundler.adopt::<Response>(components)?;
+            bundler.adopt::<Parameter>(components)?;
+            bundler.adopt::<Example>(components)?;
+            bundler.adopt::<RequestBody>(components)?;
+            bundler.adopt::<Header>(components)?;
+            bundler.adopt::<Schema>(components)?;
+            bundler.adopt::<Link>(components)?;
+            bundler.adopt::<Callback>(components)?;
+        }
+        self.walk(&mut bundler)?;
+
+        let bundled = bundler.bundled;
+        if bundled != Components::default() {
+            let components = self.components.get_or_insert_with(Default::default);
+            components.security_schemes.extend(bundled.security_schemes);
+            components.responses.extend(bundled.responses);
+            components.parameters.extend(bundled.parameters);
+            components.examples.extend(bundled.examples);
+            components.request_bodies.extend(bundled.request_bodies);
+            components.headers.extend(bundled.headers);
+            components.schemas.extend(bundled.schemas);
+            components.links.extend(bundled.links);
+            components.callbacks.extend(bundled.callbacks);
+        }
+        Ok(())
+    }
+}
+
+struct Bundler<'r, R> {
+    documents: Documents<'r, R>,
+    /// The locations of the documents being walked; the first is the root.
+    bases: Vec<String>,
+    /// The absolute references currently being inlined, outermost first.
+    stack: Vec<String>,
+    /// The internal reference each bundled absolute reference was given.
+    names: HashMap<String, Reference>,
+    /// The component names in use, in the document or by bundled objects.
+    taken: HashSet<(ComponentKind, String)>,
+    bundled: Components,
+}
+
+impl<'r, R: ReferenceResolver> Bundler<'r, R> {
+    /// Picks a name for the object at `fragment` within `location`
+    /// that no other component of the same kind has.
+    fn unique_name(&mut self, kind: ComponentKind, location: &str, fragment: &str) -> String {
+        let last = match fragment.rsplit('/').next() {
+            Some(segment) if !segment.is_empty() => unescape_pointer_segment(segment),
+            _ => {
+                let file = location.rsplit('/').next().unwrap_or(location);
+                match file.find('.') {
+                    Some(i) if i > 0 => file[..i].to_owned(),
+                    _ => file.to_owned(),
+                }
+            }
+        };
+        // Component names must match `^[a-zA-Z0-9\.\-_]+$`.
+        let mut base: String = last
+            .chars()
+            .map(|c| match c {
+                'a'..='z' | 'A'..='Z' | '0'..='9' | '.' | '-' | '_' => c,
+                _ => '_',
+            })
+            .collect();
+        if base.is_empty() {
+            base = kind.as_str().to_owned();
+        }
+
+        let mut name = base.clone();
+        let mut suffix = 2;
+        while !self.taken.insert((kind, name.clone())) {
+            name = format!("{}_{}", base, suffix);
+            suffix += 1;
+        }
+        name
+    }
+
+    /// The absolute location and fragment of a reference of the root
+    /// document to another one.
+    fn external_target(&self, reference: &Reference) -> Option<(String, String)> {
+        if !reference.is_external() {
+            return None;
+        }
+        let location = join_location(&self.bases[0], reference.document());
+        if location == self.bases[0] {
+            return None;
+        }
+        let absolute = format!("{}#{}", location, reference.fragment().unwrap_or(""));
+        Some((location, absolute))
+    }
+
+    /// Gives each component of the root document that refers to another
+    /// document the name of what it refers to, unless an earlier one
+    /// already has.
+    fn claim<T: Referenceable>(&mut self, components: &mut Components) {
+        let (kind, section) = match (T::COMPONENT_KIND, T::section_mut(components)) {
+            (Some(kind), Some(section)) => (kind, section),
+            _ => return,
+        };
+        for (name, value) in section.iter() {
+            if let ReferenceOr::Reference { reference } = value {
+                if let Some((_, absolute)) = self.external_target(reference) {
+                    self.names
+                        .entry(absolute)
+                        .or_insert_with(|| Reference::component(kind, name));
+                }
+            }
+        }
+    }
+
+    /// Replaces the components claimed by [`Bundler::claim`] with what
+    /// they refer to.
+    fn adopt<T: Referenceable>(
+        &mut self,
+        components: &mut Components,
+    ) -> Result<(), ExternalReferenceError> {
+        let (kind, section) = match (T::COMPONENT_KIND, T::section_mut(components)) {
+            (Some(kind), Some(section)) => (kind, section),
+            _ => return Ok(()),
+        };
+        for (name, value) in section.iter_mut() {
+            let reference = match value {
+                ReferenceOr::Reference { reference } => reference,
+                ReferenceOr::Item(_) => continue,
+            };
+            let (location, absolute) = match self.external_target(reference) {
+                Some(target) => target,
+                None => continue,
+            };
+            if self.names.get(&absolute) != Some(&Reference::component(kind, name)) {
+                continue;
+            }
+            let fragment = reference.fragment().unwrap_or("").to_owned();
+            let mut loaded: ReferenceOr<T> =
+                self.documents.load(&location, &fragment, &absolute)?;
+            self.within(location, |bundler| bundler.reference(&mut loaded))?;
+            *value = loaded;
+        }
+        Ok(())
+    }
+
+    fn within<F>(&mut self, location: String, f: F) -> Result<(), ExternalReferenceError>
+    where
+        F: FnOnce(&mut Self) -> Result<(), ExternalReferenceError>,
+    {
+        self.bases.push(location);
+        let result = f(self);
+        self.bases.pop();
+        result
+    }
+}
+
+impl<'r, R: ReferenceResolver> VisitRefs for Bundler<'r, R> {
+    type Error = ExternalReferenceError;
+
+    fn reference<T: Referenceable>(
+        &mut self,
+        value: &mut ReferenceOr<T>,
+    ) -> Result<(), ExternalReferenceError> {
+        let reference = match value {
+            ReferenceOr::Item(item) => return item.walk(self),
+            ReferenceOr::Reference { reference } => reference.clone(),
+        };
+        if !reference.is_external() && self.bases.len() == 1 {
+            // An internal reference of the root document.
+            return Ok(());
+        }
+        let location = join_location(self.bases.last().unwrap(), reference.document());
+        let fragment = reference.fragment().unwrap_or("");
+        if location == self.bases[0] {
+            // A reference from another document back into this one.
+            *value = ReferenceOr::Reference {
+                reference: format!("#{}", fragment).parse().unwrap(),
+            };
+            return Ok(());
+        }
+        let absolute = format!("{}#{}", location, fragment);
+        if let Some(internal) = self.names.get(&absolute) {
+            *value = ReferenceOr::Reference {
+                reference: internal.clone(),
+            };
+            return Ok(());
+        }
+
+        let mut loaded: ReferenceOr<T> = self.documents.load(&location, fragment, &absolute)?;
+        match T::COMPONENT_KIND {
+            Some(kind) => {
+                // Named before walking, so that recursive references
+                // find the name rather than bundling another copy.
+                let name = self.unique_name(kind, &location, fragment);
+                let internal = Reference::component(kind, &name);
+                self.names.insert(absolute, internal.clone());
+                self.within(location, |bundler| bundler.reference(&mut loaded))?;
+                if let Some(section) = T::section_mut(&mut self.bundled) {
+                    section.insert(name, loaded);
+                }
+                *value = ReferenceOr::Reference {
+                    reference: internal,
+                };
+            }
+            None => {
+                if let Some(start) = self.stack.iter().position(|r| *r == absolute) {
+                    let mut chain = self.stack[start..].to_vec();
+                    chain.push(absolute);
+                    return Err(ExternalReferenceError::Cycle(chain));
+                }
+                self.stack.push(absolute);
+                let result = self.within(location, |bundler| bundler.reference(&mut loaded));
+                self.stack.pop();
+                result?;
+                *value = loaded;
+            }
+        }
+        Ok(())
+    }
+
+    fn mapping(&mut self, target: &mut String) -> Result<(), ExternalReferenceError> {
+        let reference: Reference = match target_reference(target).parse() {
+            Ok(reference) => reference,
+            Err(_) => return Ok(()),
+        };
+        if !reference.is_external() && self.bases.len() == 1 {
+            return Ok(());
+        }
+        let mut value = ReferenceOr::<Schema>::Reference { reference };
+        self.reference(&mut value)?;
+        if let ReferenceOr::Reference { reference } = value {
+            *target = reference.into();
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::external::InMemory;
+    use crate::*;
+
+    #[test]
+    fn bundles_into_components() {
+        let resolver: InMemory = vec![
+            (
+                "specs/common.yaml",
+                "
+components:
+  schemas:
+    Error:
+      type: object
+      properties:
+        code: {$ref: '#/components/schemas/Code'}
+        cause: {$ref: '#/components/schemas/Error'}
+        local: {$ref: 'main.yaml#/components/schemas/Local'}
+    Code: {type: integer}
+",
+            ),
+            (
+                "specs/paths/pets.yaml",
+                "
+get:
+  responses:
+    default:
+      description: failure
+      content:
+        application/json:
+          schema: {$ref: '../common.yaml#/components/schemas/Error'}
+",
+            ),
+        ]
+        .into_iter()
+        .collect();
+
+        let mut api: OpenAPI = serde_yaml::from_str(
+            "
+openapi: 3.0.0
+info: {title: t, version: '1'}
+paths:
+  /pets: {$ref: './paths/pets.yaml'}
+components:
+  schemas:
+    Error: {$ref: './common.yaml#/components/schemas/Error'}
+    Local: {$ref: '#/components/schemas/Error'}
+",
+        )
+        .unwrap();
+        api.bundle("specs/main.yaml", &resolver).unwrap();
+
+        let schemas = &api.components.as_ref().unwrap().schemas;
+        let mut names: Vec<_> = schemas.keys().collect();
+        names.sort();
+        assert_eq!(names, vec!["Code", "Error", "Local"]);
+        match &schemas["Error"] {
+            ReferenceOr::Item(Schema {
+                schema_kind: SchemaKind::Type(Type::Object(object)),
+                ..
+            }) => {
+                assert_eq!(
+                    object.properties["code"],
+                    ReferenceOr::ref_("#/components/schemas/Code")
+                );
+                assert_eq!(
+                    object.properties["cause"],
+                    ReferenceOr::ref_("#/components/schemas/Error")
+                );
+                assert_eq!(
+                    object.properties["local"],
+                    ReferenceOr::ref_("#/components/schemas/Local")
+                );
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+
+        let operation = match &api.paths["/pets"] {
+            ReferenceOr::Item(item) => item.get.as_ref().unwrap(),
+            reference => panic!("unexpected {:?}", reference),
+        };
+        match &operation.responses.default {
+            Some(ReferenceOr::Item(response)) => {
+                match &response.content[&MediaTypeRange::from("application/json")] {
+                    ReferenceOr::Item(media) => assert_eq!(
+                        media.schema,
+                        Some(ReferenceOr::ref_("#/components/schemas/Error"))
+                    ),
+                    reference => panic!("unexpected {:?}", reference),
+                }
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+}
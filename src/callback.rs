@@ -1,4 +1,6 @@
 use crate::*;
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
 /// A map of possible out-of band callbacks related to the parent operation.
@@ -6,4 +8,19 @@ use std::collections::BTreeMap;
 /// requests that may be initiated by the API provider and the expected responses.
 /// The key value used to identify the callback object is an expression,
 /// evaluated at runtime, that identifies a URL to use for the callback operation.
-pub type Callback = BTreeMap<String, PathItem>;
+///
+/// Dereferences to the map of path items, so that it can be used as one.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct Callback {
+    /// The path items, by the expression of their URL.
+    #[serde(
+        flatten,
+        deserialize_with = "crate::util::deserialize_without_extensions"
+    )]
+    pub paths: BTreeMap<String, PathItem>,
+    /// Inline extensions to this object.
+    #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
+    pub extensions: IndexMap<String, serde_json::Value>,
+}
+
+crate::util::entries_of!(Callback, paths: BTreeMap<String, PathItem>, PathItem);
@@ -0,0 +1,197 @@
+use crate::walk::{Referenceable, VisitRefs, Walk};
+use crate::*;
+use std::any::Any;
+use std::cmp::Ordering;
+use std::convert::Infallible;
+
+impl OpenAPI {
+    /// Puts the document in a canonical form, so that equivalent documents
+    /// serialize identically.
+    ///
+    /// Maps, such as `paths`, are ordered by key, which they already are
+    /// unless the `preserve_order` feature is enabled. In addition, this
+    /// orders the root tags by name, the values of enums, the `required`
+    /// properties of schemas, and security requirements along with their
+    /// scopes, dropping duplicate requirements. A `oneOf`, `allOf` or
+    /// `anyOf` of a single schema is replaced by that schema, where no
+    /// other keywords are lost in doing so.
+    ///
+    /// Servers and parameters keep their order, which is significant.
+    pub fn canonicalize(&mut self) {
+        self.tags.sort_by(|a, b| a.name.cmp(&b.name));
+        sort_requirements(&mut self.security);
+        sort_keys(&mut self.paths);
+        for item in self.paths.values_mut() {
+            if let ReferenceOr::Item(item) = item {
+                item.operations_mut()
+                    .for_each(|(_, operation)| canonicalize_operation(operation));
+            }
+        }
+        if let Some(components) = &mut self.components {
+            sort_keys(&mut components.schemas);
+            sort_keys(&mut components.responses);
+            sort_keys(&mut components.parameters);
+            sort_keys(&mut components.examples);
+            sort_keys(&mut components.request_bodies);
+            sort_keys(&mut components.headers);
+            sort_keys(&mut components.security_schemes);
+            sort_keys(&mut components.links);
+            sort_keys(&mut components.callbacks);
+            components
+                .callbacks
+                .values_mut()
+                .for_each(canonicalize_callback);
+        }
+
+        let _ = self.walk(&mut Canonicalizer);
+    }
+}
+
+fn canonicalize_operation(operation: &mut Operation) {
+    if let Some(security) = &mut operation.security {
+        sort_requirements(security);
+    }
+    sort_keys(&mut operation.responses.responses);
+    operation
+        .callbacks
+        .values_mut()
+        .for_each(canonicalize_callback);
+}
+
+fn canonicalize_callback(callback: &mut ReferenceOr<Callback>) {
+    if let ReferenceOr::Item(callback) = callback {
+        for item in callback.values_mut() {
+            item.operations_mut()
+                .for_each(|(_, operation)| canonicalize_operation(operation));
+        }
+    }
+}
+
+fn sort_requirements(requirements: &mut Vec<SecurityRequirement>) {
+    for requirement in requirements.iter_mut() {
+        for scopes in requirement.values_mut() {
+            scopes.sort();
+        }
+    }
+    requirements.sort();
+    requirements.dedup();
+}
+
+/// Canonicalizes every schema, innermost first.
+struct Canonicalizer;
+
+impl VisitRefs for Canonicalizer {
+    type Error = Infallible;
+
+    fn reference<T: Referenceable>(
+        &mut self,
+        value: &mut ReferenceOr<T>,
+    ) -> Result<(), Infallible> {
+        if let ReferenceOr::Item(item) = value {
+            item.walk(self)?;
+        }
+        if let Some(schema) = (value as &mut dyn Any).downcast_mut::<ReferenceOr<Schema>>() {
+            canonicalize_schema(schema);
+        }
+        Ok(())
+    }
+}
+
+fn canonicalize_schema(value: &mut ReferenceOr<Schema>) {
+    let schema = match value {
+        ReferenceOr::Item(schema) => schema,
+        ReferenceOr::Reference { .. } => return,
+    };
+
+    let by_value = |a: &f64, b: &f64| a.partial_cmp(b).unwrap_or(Ordering::Equal);
+    match &mut schema.schema_kind {
+        SchemaKind::Type(Type::String(string)) => string.enumeration.sort(),
+        SchemaKind::Type(Type::Number(number)) => number.enumeration.sort_by(by_value),
+        SchemaKind::Type(Type::Integer(integer)) => integer.enumeration.sort(),
+        SchemaKind::Type(Type::Object(object)) => {
+            object.required.sort();
+            sort_keys(&mut object.properties);
+        }
+        SchemaKind::Any(any) => {
+            any.required.sort();
+            sort_keys(&mut any.properties);
+        }
+        _ => {}
+    }
+
+    let only = match &mut schema.schema_kind {
+        SchemaKind::OneOf { one_of: schemas }
+        | SchemaKind::AllOf { all_of: schemas }
+        | SchemaKind::AnyOf { any_of: schemas }
+            if schemas.len() == 1 =>
+        {
+            &mut schemas[0]
+        }
+        _ => return,
+    };
+    let plain = schema.schema_data == SchemaData::default();
+    match only {
+        // A reference can carry nothing alongside it.
+        ReferenceOr::Reference { .. } if plain => *value = only.clone(),
+        ReferenceOr::Reference { .. } => {}
+        ReferenceOr::Item(inner) if plain => *value = ReferenceOr::Item(inner.clone()),
+        ReferenceOr::Item(inner) if inner.schema_data == SchemaData::default() => {
+            schema.schema_kind = inner.schema_kind.clone();
+        }
+        ReferenceOr::Item(_) => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn canonicalizes_documents() {
+        let mut api: OpenAPI = serde_yaml::from_str(
+            "
+openapi: 3.0.0
+info: {title: t, version: '1'}
+tags: [{name: pets}, {name: owners}]
+security: [{oauth: [write, read]}, {key: []}, {key: []}]
+paths: {}
+components:
+  schemas:
+    Pet:
+      type: object
+      required: [name, id]
+      properties:
+        kind: {type: string, enum: [dog, cat]}
+        owner: {allOf: [{$ref: '#/components/schemas/Owner'}]}
+        described: {allOf: [{$ref: '#/components/schemas/Owner'}], description: kept}
+        size: {oneOf: [{type: number, enum: [2.5, 1]}], description: small}
+    Owner: {type: object}
+",
+        )
+        .unwrap();
+        api.canonicalize();
+
+        let expected: OpenAPI = serde_yaml::from_str(
+            "
+openapi: 3.0.0
+info: {title: t, version: '1'}
+tags: [{name: owners}, {name: pets}]
+security: [{key: []}, {oauth: [read, write]}]
+paths: {}
+components:
+  schemas:
+    Pet:
+      type: object
+      required: [id, name]
+      properties:
+        kind: {type: string, enum: [cat, dog]}
+        owner: {$ref: '#/components/schemas/Owner'}
+        described: {allOf: [{$ref: '#/components/schemas/Owner'}], description: kept}
+        size: {type: number, enum: [1, 2.5], description: small}
+    Owner: {type: object}
+",
+        )
+        .unwrap();
+        assert_eq!(api, expected);
+    }
+}
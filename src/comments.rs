@@ -0,0 +1,309 @@
+use crate::*;
+use std::collections::{BTreeMap, BTreeSet};
+use yaml_rust::parser::{Event, MarkedEventReceiver, Parser};
+use yaml_rust::scanner::{Marker, TScalarStyle};
+
+/// The comments of a YAML document, by the JSON Pointer of the value each
+/// belongs to, for writing them out again with
+/// [`OpenAPI::to_yaml_string_with_comments`].
+///
+/// Comments on lines of their own belong to the value on the next line,
+/// and a comment after a value to the last value starting on its line.
+/// Comments after the last value are kept for the end of the document.
+/// This is a best effort: comments are kept with values, not lines, so
+/// that values moved or written in another style keep their comments,
+/// but those of values that are gone are dropped.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Comments {
+    leading: BTreeMap<String, Vec<String>>,
+    trailing: BTreeMap<String, String>,
+    end: Vec<String>,
+}
+
+impl Comments {
+    /// Records the comments of a YAML document. JSON documents have none.
+    pub fn parse(text: &str) -> Result<Comments, ReadError> {
+        let spans = SourceMap::parse(text)?;
+        // Where several values start on a line, comments above it belong
+        // to the outermost, and comments after it to the innermost.
+        let mut first = BTreeMap::<usize, (usize, &str)>::new();
+        let mut last = BTreeMap::<usize, (usize, &str)>::new();
+        for (pointer, span) in spans.iter() {
+            let at = (span.column, pointer);
+            first
+                .entry(span.line)
+                .and_modify(|first| *first = (*first).min(at))
+                .or_insert(at);
+            last.entry(span.line)
+                .and_modify(|last| *last = (*last).max(at))
+                .or_insert(at);
+        }
+
+        let mut blocks = BlockScalars::default();
+        let _ = Parser::new(text.chars()).load(&mut blocks, false);
+        let content = blocks.content_lines(text);
+
+        let mut comments = Comments::default();
+        let mut pending = Vec::new();
+        for (i, line) in text.lines().enumerate() {
+            let number = i + 1;
+            let trimmed = line.trim_start();
+            if content.contains(&number) || trimmed.is_empty() {
+                continue;
+            }
+            if let Some(comment) = trimmed.strip_prefix('#') {
+                pending.push(comment.trim_end().to_owned());
+                continue;
+            }
+            if !pending.is_empty() {
+                if let Some((_, (_, pointer))) = first.range(number..).next() {
+                    comments
+                        .leading
+                        .entry((*pointer).to_owned())
+                        .or_default()
+                        .append(&mut pending);
+                }
+            }
+            if let (Some(comment), Some((_, pointer))) = (trailing_comment(line), last.get(&number))
+            {
+                comments
+                    .trailing
+                    .insert((*pointer).to_owned(), comment.to_owned());
+            }
+        }
+        comments.end = pending;
+        Ok(comments)
+    }
+
+    /// The comments on the lines above the value at `pointer`, without
+    /// their `#`.
+    pub fn leading(&self, pointer: &str) -> &[String] {
+        self.leading.get(pointer).map_or(&[], Vec::as_slice)
+    }
+
+    /// The comment after the start of the value at `pointer`, on the same
+    /// line, without its `#`.
+    pub fn trailing(&self, pointer: &str) -> Option<&str> {
+        self.trailing.get(pointer).map(String::as_str)
+    }
+
+    /// The comments after the last value of the document.
+    pub fn end(&self) -> &[String] {
+        &self.end
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.leading.is_empty() && self.trailing.is_empty() && self.end.is_empty()
+    }
+}
+
+impl OpenAPI {
+    /// Reads a JSON or YAML document along with its comments; see
+    /// [`Comments`].
+    ///
+    /// ```
+    /// use openapiv3::{OpenAPI, YamlOptions};
+    ///
+    /// let text = "\
+    /// ## The pet store.
+    /// openapi: 3.0.0
+    /// info:
+    ///   title: Pets
+    ///   version: '1.0' # bumped on release
+    /// paths: {}
+    /// ";
+    /// let (mut api, comments) = OpenAPI::from_str_with_comments(text).unwrap();
+    /// api.info.title = "Pet store".to_owned();
+    /// let text = api
+    ///     .to_yaml_string_with_comments(YamlOptions::default(), &comments)
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     text,
+    ///     "\
+    /// ## The pet store.
+    /// openapi: 3.0.0
+    /// info:
+    ///   title: Pet store
+    ///   version: '1.0' # bumped on release
+    /// paths: {}
+    /// "
+    /// );
+    /// ```
+    pub fn from_str_with_comments(text: &str) -> Result<(OpenAPI, Comments), ReadError> {
+        let api = parse(text)?;
+        Ok((api, Comments::parse(text)?))
+    }
+
+    /// Writes the document as YAML as [`OpenAPI::to_yaml_string`] does,
+    /// with `comments` by the values they belong to.
+    pub fn to_yaml_string_with_comments(
+        &self,
+        options: YamlOptions,
+        comments: &Comments,
+    ) -> Result<String, serde_yaml::Error> {
+        Ok(crate::yaml::emit(
+            &serde_yaml::to_value(self)?,
+            &options,
+            comments,
+        ))
+    }
+}
+
+/// The comment ending a line that is not only a comment, if there is one,
+/// skipping `#`s within quoted strings.
+fn trailing_comment(line: &str) -> Option<&str> {
+    let mut quote = None;
+    let mut previous = ' ';
+    let mut escaped = false;
+    let mut closed = false;
+    for (i, c) in line.char_indices() {
+        let closing = closed;
+        closed = false;
+        match quote {
+            Some('"') if escaped => escaped = false,
+            Some('"') if c == '\\' => escaped = true,
+            Some(q) if c == q => {
+                quote = None;
+                closed = true;
+            }
+            Some(_) => {}
+            // `''` is a quote within a single-quoted string.
+            None if c == '\'' && closing && previous == '\'' => quote = Some(c),
+            // A quote only starts a string where a value may start, and
+            // not within one such as `it's`.
+            None if (c == '\'' || c == '"') && " :-[{,?".contains(previous) => quote = Some(c),
+            None if c == '#' && previous.is_whitespace() => {
+                return Some(line[i + 1..].trim_end());
+            }
+            None => {}
+        }
+        previous = if c.is_whitespace() && quote.is_none() {
+            ' '
+        } else {
+            c
+        };
+    }
+    None
+}
+
+/// The lines of the literal and folded block scalars of a document, as
+/// their starts and the starts of the events after them.
+#[derive(Default)]
+struct BlockScalars {
+    blocks: Vec<(usize, usize)>,
+    open: Option<usize>,
+}
+
+impl BlockScalars {
+    /// The lines that are the content of block scalars. Comments may
+    /// follow a block scalar only less indented than its content.
+    fn content_lines(&self, text: &str) -> BTreeSet<usize> {
+        let lines: Vec<&str> = text.lines().collect();
+        let indent = |line: &str| line.len() - line.trim_start().len();
+        let mut content = BTreeSet::new();
+        for &(start, end) in &self.blocks {
+            let block = (start + 1..end).filter(|&n| n <= lines.len());
+            let mut block_indent = None;
+            for number in block {
+                let line = lines[number - 1];
+                if line.trim().is_empty() {
+                    content.insert(number);
+                    continue;
+                }
+                let block_indent = *block_indent.get_or_insert(indent(line));
+                if indent(line) >= block_indent {
+                    content.insert(number);
+                }
+            }
+        }
+        content
+    }
+}
+
+impl MarkedEventReceiver for BlockScalars {
+    fn on_event(&mut self, event: Event, mark: Marker) {
+        if let Some(start) = self.open.take() {
+            self.blocks.push((start, mark.line()));
+        }
+        if let Event::Scalar(_, TScalarStyle::Literal | TScalarStyle::Foled, ..) = event {
+            self.open = Some(mark.line());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_comments_with_values() {
+        let text = "\
+openapi: 3.0.0 # the version
+info:
+  title: Pets
+  # Not shown to users.
+  description: |
+    Lists pets.
+    # Not a comment.
+
+  # Bumped on release.
+  version: '1.0'
+paths:
+  /pets: # all pets
+    get:
+      tags: [pets, 'it''s # not a comment'] # tagged
+      responses: {}
+tags:
+  # The first tag.
+  - name: pets
+    description: \"\\\" # not a comment\" # pets
+# The end.
+";
+        let (api, comments) = OpenAPI::from_str_with_comments(text).unwrap();
+        assert_eq!(comments.trailing("/openapi"), Some(" the version"));
+        assert_eq!(
+            comments.leading("/info/description"),
+            [" Not shown to users."]
+        );
+        assert_eq!(comments.leading("/info/version"), [" Bumped on release."]);
+        assert_eq!(comments.trailing("/paths/~1pets"), Some(" all pets"));
+        assert_eq!(
+            comments.trailing("/paths/~1pets/get/tags/1"),
+            Some(" tagged")
+        );
+        assert_eq!(comments.leading("/tags/0"), [" The first tag."]);
+        assert_eq!(comments.trailing("/tags/0/description"), Some(" pets"));
+        assert_eq!(comments.end(), [" The end."]);
+
+        let written = api
+            .to_yaml_string_with_comments(YamlOptions::default(), &comments)
+            .unwrap();
+        assert_eq!(
+            written,
+            "\
+openapi: 3.0.0 # the version
+info:
+  title: Pets
+  # Not shown to users.
+  description: |
+    Lists pets.
+    # Not a comment.
+  # Bumped on release.
+  version: '1.0'
+paths:
+  /pets: # all pets
+    get:
+      tags:
+        - pets
+        - 'it''s # not a comment' # tagged
+      responses: {}
+tags:
+  # The first tag.
+  - name: pets
+    description: '\" # not a comment' # pets
+# The end.
+"
+        );
+        assert_eq!(Comments::parse(&written).unwrap(), comments);
+    }
+}
@@ -0,0 +1,786 @@
+//! Classification of the changes between two versions of a document as
+//! breaking for existing clients or not.
+//!
+//! ```
+//! use openapiv3::compat::check_compatibility;
+//! use openapiv3::OpenAPI;
+//!
+//! let old: OpenAPI = serde_yaml::from_str(
+//!     "
+//! openapi: 3.0.0
+//! info: {title: Pets, version: '1'}
+//! paths:
+//!   /pets: {get: {responses: {'200': {description: pets}}}}
+//! ",
+//! )
+//! .unwrap();
+//! let mut new = old.clone();
+//! new.paths.remove("/pets");
+//!
+//! let report = check_compatibility(&old, &new);
+//! assert!(!report.is_compatible());
+//! assert_eq!(report.to_string(), "breaking: /paths/~1pets: path removed\n");
+//! ```
+//!
+//! Schemas are judged by where they are used: a change that makes a
+//! schema accept fewer values, such as a newly required property or a
+//! removed enum value, breaks clients sending it in a request, while one
+//! that makes it accept more breaks clients reading it from a response.
+
+use crate::diff::{self, *};
+use crate::walk::Location;
+use crate::{Components, MediaTypeRange, Name, OpenAPI, Operation, Parameter, ReferenceOr};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Compares `new` against `old`, classifying every change found by
+/// [`diff::diff`].
+pub fn check_compatibility(old: &OpenAPI, new: &OpenAPI) -> CompatibilityReport {
+    let empty = Components::default();
+    let mut checker = Checker {
+        new,
+        components: new.components.as_ref().unwrap_or(&empty),
+        at: Location::default(),
+        changes: Vec::new(),
+    };
+    checker.document(&diff::diff(old, new));
+    CompatibilityReport {
+        changes: checker.changes,
+    }
+}
+
+/// The changes between two documents, breaking or not.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CompatibilityReport {
+    pub changes: Vec<CompatibilityChange>,
+}
+
+impl CompatibilityReport {
+    /// Whether none of the changes are breaking.
+    pub fn is_compatible(&self) -> bool {
+        !self.changes.iter().any(|change| change.breaking)
+    }
+
+    pub fn breaking(&self) -> impl Iterator<Item = &CompatibilityChange> {
+        self.changes.iter().filter(|change| change.breaking)
+    }
+}
+
+/// One change per line.
+impl fmt::Display for CompatibilityReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{}", change)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompatibilityChange {
+    /// Where the change is, in the manner of a JSON Pointer into the new
+    /// document, but with parameters named rather than numbered and
+    /// references followed.
+    pub location: String,
+    pub kind: ChangeKind,
+    /// Whether clients of the old document may fail against the new one.
+    pub breaking: bool,
+}
+
+impl fmt::Display for CompatibilityChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let class = if self.breaking {
+            "breaking"
+        } else {
+            "compatible"
+        };
+        write!(f, "{}: {}: {}", class, self.location, self.kind)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChangeKind {
+    PathAdded,
+    PathRemoved,
+    OperationAdded,
+    OperationRemoved,
+    Deprecated(bool),
+    ParameterAdded {
+        required: bool,
+    },
+    ParameterRemoved,
+    ParameterRequired(bool),
+    /// The parameter moved to another location, such as from the query
+    /// to a header.
+    ParameterMoved {
+        old: String,
+        new: String,
+    },
+    RequestBodyAdded {
+        required: bool,
+    },
+    RequestBodyRemoved,
+    RequestBodyRequired(bool),
+    ResponseAdded,
+    ResponseRemoved,
+    MediaTypeAdded,
+    MediaTypeRemoved,
+    SchemaAdded,
+    SchemaRemoved,
+    /// The type, or combinator, of the schema changed.
+    KindChanged {
+        old: String,
+        new: String,
+    },
+    PropertyAdded,
+    PropertyRemoved,
+    RequiredPropertyAdded(Name),
+    RequiredPropertyRemoved(Name),
+    EnumValueAdded(Value),
+    EnumValueRemoved(Value),
+    SubschemaAdded,
+    SubschemaRemoved,
+    KeywordChanged {
+        keyword: String,
+        old: Option<Value>,
+        new: Option<Value>,
+    },
+}
+
+impl fmt::Display for ChangeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let keyword_value = |value: &Option<Value>| match value {
+            Some(value) => value.to_string(),
+            None => "absent".to_owned(),
+        };
+        match self {
+            ChangeKind::PathAdded => f.write_str("path added"),
+            ChangeKind::PathRemoved => f.write_str("path removed"),
+            ChangeKind::OperationAdded => f.write_str("operation added"),
+            ChangeKind::OperationRemoved => f.write_str("operation removed"),
+            ChangeKind::Deprecated(true) => f.write_str("deprecated"),
+            ChangeKind::Deprecated(false) => f.write_str("no longer deprecated"),
+            ChangeKind::ParameterAdded { required: true } => {
+                f.write_str("required parameter added")
+            }
+            ChangeKind::ParameterAdded { required: false } => {
+                f.write_str("optional parameter added")
+            }
+            ChangeKind::ParameterRemoved => f.write_str("parameter removed"),
+            ChangeKind::ParameterRequired(true) => f.write_str("parameter became required"),
+            ChangeKind::ParameterRequired(false) => f.write_str("parameter became optional"),
+            ChangeKind::ParameterMoved { old, new } => {
+                write!(f, "parameter moved from {} to {}", old, new)
+            }
+            ChangeKind::RequestBodyAdded { required: true } => {
+                f.write_str("required request body added")
+            }
+            ChangeKind::RequestBodyAdded { required: false } => {
+                f.write_str("optional request body added")
+            }
+            ChangeKind::RequestBodyRemoved => f.write_str("request body removed"),
+            ChangeKind::RequestBodyRequired(true) => f.write_str("request body became required"),
+            ChangeKind::RequestBodyRequired(false) => f.write_str("request body became optional"),
+            ChangeKind::ResponseAdded => f.write_str("response added"),
+            ChangeKind::ResponseRemoved => f.write_str("response removed"),
+            ChangeKind::MediaTypeAdded => f.write_str("media type added"),
+            ChangeKind::MediaTypeRemoved => f.write_str("media type removed"),
+            ChangeKind::SchemaAdded => f.write_str("schema added"),
+            ChangeKind::SchemaRemoved => f.write_str("schema removed"),
+            ChangeKind::KindChanged { old, new } => {
+                write!(f, "type changed from {} to {}", old, new)
+            }
+            ChangeKind::PropertyAdded => f.write_str("property added"),
+            ChangeKind::PropertyRemoved => f.write_str("property removed"),
+            ChangeKind::RequiredPropertyAdded(name) => {
+                write!(f, "property `{}` became required", name)
+            }
+            ChangeKind::RequiredPropertyRemoved(name) => {
+                write!(f, "property `{}` became optional", name)
+            }
+            ChangeKind::EnumValueAdded(value) => write!(f, "enum value {} added", value),
+            ChangeKind::EnumValueRemoved(value) => write!(f, "enum value {} removed", value),
+            ChangeKind::SubschemaAdded => f.write_str("subschema added"),
+            ChangeKind::SubschemaRemoved => f.write_str("subschema removed"),
+            ChangeKind::KeywordChanged { keyword, old, new } => write!(
+                f,
+                "`{}` changed from {} to {}",
+                keyword,
+                keyword_value(old),
+                keyword_value(new)
+            ),
+        }
+    }
+}
+
+/// Which way a schema changed the values it accepts.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Direction {
+    /// It accepts fewer values.
+    Narrowing,
+    /// It accepts more values.
+    Widening,
+    /// It accepts other values, or may.
+    Incompatible,
+    /// It accepts the same values.
+    Neutral,
+}
+
+/// Whether a schema is sent by clients or received by them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Side {
+    Request,
+    Response,
+}
+
+impl Direction {
+    fn breaks(self, side: Side) -> bool {
+        match self {
+            Direction::Narrowing => side == Side::Request,
+            Direction::Widening => side == Side::Response,
+            Direction::Incompatible => true,
+            Direction::Neutral => false,
+        }
+    }
+}
+
+/// Bounds that apply from below, and from above.
+const LOWER_BOUNDS: &[&str] = &["minimum", "minLength", "minItems", "minProperties"];
+const UPPER_BOUNDS: &[&str] = &["maximum", "maxLength", "maxItems", "maxProperties"];
+/// Keywords that constrain values when present.
+const CONSTRAINTS: &[&str] = &["multipleOf", "pattern", "format"];
+/// Keywords that constrain values when `true`.
+const FLAGS: &[&str] = &["exclusiveMinimum", "exclusiveMaximum", "uniqueItems"];
+
+fn keyword_direction(keyword: &str, old: Option<&Value>, new: Option<&Value>) -> Direction {
+    let number = |value: Option<&Value>| value.and_then(Value::as_f64);
+    let flag = |value: Option<&Value>| value.and_then(Value::as_bool).unwrap_or(false);
+    let bound = |old: Option<f64>, new: Option<f64>, tighter: fn(f64, f64) -> bool| match (old, new)
+    {
+        (None, Some(_)) => Direction::Narrowing,
+        (Some(_), None) => Direction::Widening,
+        (Some(old), Some(new)) if tighter(new, old) => Direction::Narrowing,
+        (Some(old), Some(new)) if tighter(old, new) => Direction::Widening,
+        _ => Direction::Neutral,
+    };
+
+    if LOWER_BOUNDS.contains(&keyword) {
+        bound(number(old), number(new), |a, b| a > b)
+    } else if UPPER_BOUNDS.contains(&keyword) {
+        bound(number(old), number(new), |a, b| a < b)
+    } else if CONSTRAINTS.contains(&keyword) {
+        match (old, new) {
+            (None, Some(_)) => Direction::Narrowing,
+            (Some(_), None) => Direction::Widening,
+            _ => Direction::Incompatible,
+        }
+    } else if FLAGS.contains(&keyword) {
+        match (flag(old), flag(new)) {
+            (false, true) => Direction::Narrowing,
+            (true, false) => Direction::Widening,
+            _ => Direction::Neutral,
+        }
+    } else if keyword == "nullable" {
+        match (flag(old), flag(new)) {
+            (true, false) => Direction::Narrowing,
+            (false, true) => Direction::Widening,
+            _ => Direction::Neutral,
+        }
+    } else if keyword == "additionalProperties" {
+        let forbidden = |value: Option<&Value>| value == Some(&Value::Bool(false));
+        let allowed = |value: Option<&Value>| value.is_none() || value == Some(&Value::Bool(true));
+        match (old, new) {
+            (old, new) if forbidden(new) || allowed(old) => Direction::Narrowing,
+            (old, new) if forbidden(old) || allowed(new) => Direction::Widening,
+            _ => Direction::Incompatible,
+        }
+    } else {
+        // Annotations such as `default` and `readOnly`.
+        Direction::Neutral
+    }
+}
+
+struct Checker<'a> {
+    /// The new document, for what the diff does not record about
+    /// added parameters and request bodies.
+    new: &'a OpenAPI,
+    components: &'a Components,
+    at: Location,
+    changes: Vec<CompatibilityChange>,
+}
+
+impl<'a> Checker<'a> {
+    fn nested<F: FnOnce(&mut Self)>(&mut self, segment: &str, f: F) {
+        let mut at = std::mem::take(&mut self.at);
+        at.with(segment, |at| {
+            std::mem::swap(&mut self.at, at);
+            f(self);
+            std::mem::swap(&mut self.at, at);
+        });
+        self.at = at;
+    }
+
+    fn report(&mut self, kind: ChangeKind, breaking: bool) {
+        self.changes.push(CompatibilityChange {
+            location: self.at.as_str().to_owned(),
+            kind,
+            breaking,
+        });
+    }
+
+    /// Reports the addition or removal of an entry, or checks the
+    /// changes within it with `f`.
+    fn entry<T, F>(
+        &mut self,
+        segment: &str,
+        change: &Change<T>,
+        added: (ChangeKind, bool),
+        removed: (ChangeKind, bool),
+        f: F,
+    ) where
+        F: FnOnce(&mut Self, &T),
+    {
+        self.nested(segment, |checker| match change {
+            Change::Added => checker.report(added.0, added.1),
+            Change::Removed => checker.report(removed.0, removed.1),
+            Change::Changed(diff) => f(checker, diff),
+        });
+    }
+
+    fn document(&mut self, diff: &DocumentDiff) {
+        self.nested("paths", |checker| {
+            for (path, change) in &diff.paths {
+                checker.entry(
+                    path,
+                    change,
+                    (ChangeKind::PathAdded, false),
+                    (ChangeKind::PathRemoved, true),
+                    |checker, diff| checker.path_item(path, diff),
+                );
+            }
+        });
+    }
+
+    fn path_item(&mut self, path: &str, diff: &PathItemDiff) {
+        let item = match self.new.paths.get(path) {
+            Some(ReferenceOr::Item(item)) => item,
+            _ => return,
+        };
+        for (method, change) in &diff.operations {
+            let operation = item
+                .operations()
+                .find(|(name, _)| name.as_str() == method)
+                .map(|(_, operation)| operation);
+            let parameters = match operation {
+                Some(operation) => {
+                    diff::parameters(self.components, &item.parameters, &operation.parameters)
+                }
+                None => BTreeMap::new(),
+            };
+            self.entry(
+                method,
+                change,
+                (ChangeKind::OperationAdded, false),
+                (ChangeKind::OperationRemoved, true),
+                |checker, diff| checker.operation(operation, &parameters, diff),
+            );
+        }
+    }
+
+    /// Checks the changes of an operation, given the operation of the new
+    /// document and its parameters by name.
+    fn operation(
+        &mut self,
+        new: Option<&Operation>,
+        parameters: &BTreeMap<String, &Parameter>,
+        diff: &OperationDiff,
+    ) {
+        if let Some(deprecated) = &diff.deprecated {
+            self.nested("deprecated", |checker| {
+                checker.report(ChangeKind::Deprecated(deprecated.new), false)
+            });
+        }
+
+        self.nested("parameters", |checker| {
+            for (name, change) in &diff.parameters {
+                let required = parameters
+                    .get(name)
+                    .is_some_and(|parameter| parameter.parameter_data_ref().required);
+                checker.entry(
+                    name,
+                    change,
+                    (ChangeKind::ParameterAdded { required }, required),
+                    (ChangeKind::ParameterRemoved, true),
+                    Checker::parameter,
+                );
+            }
+        });
+
+        if let Some(change) = &diff.request_body {
+            let components = self.components;
+            let required = new
+                .and_then(|new| new.request_body.as_ref())
+                .and_then(|body| components.resolve(body).ok())
+                .is_some_and(|body| body.required);
+            self.entry(
+                "requestBody",
+                change,
+                (ChangeKind::RequestBodyAdded { required }, required),
+                (ChangeKind::RequestBodyRemoved, true),
+                Checker::request_body,
+            );
+        }
+
+        self.nested("responses", |checker| {
+            for (status, change) in &diff.responses {
+                checker.entry(
+                    status,
+                    change,
+                    (ChangeKind::ResponseAdded, false),
+                    (ChangeKind::ResponseRemoved, true),
+                    |checker, diff| checker.content(&diff.content, Side::Response),
+                );
+            }
+        });
+    }
+
+    fn parameter(&mut self, diff: &ParameterDiff) {
+        if let Some(location) = &diff.location {
+            self.nested("in", |checker| {
+                checker.report(
+                    ChangeKind::ParameterMoved {
+                        old: location.old.clone(),
+                        new: location.new.clone(),
+                    },
+                    true,
+                )
+            });
+        }
+        if let Some(required) = &diff.required {
+            self.nested("required", |checker| {
+                checker.report(ChangeKind::ParameterRequired(required.new), required.new)
+            });
+        }
+        if let Some(schema) = &diff.schema {
+            self.nested("schema", |checker| checker.schema(schema, Side::Request));
+        }
+    }
+
+    fn request_body(&mut self, diff: &RequestBodyDiff) {
+        if let Some(required) = &diff.required {
+            self.nested("required", |checker| {
+                checker.report(ChangeKind::RequestBodyRequired(required.new), required.new)
+            });
+        }
+        self.content(&diff.content, Side::Request);
+    }
+
+    fn content(&mut self, content: &BTreeMap<MediaTypeRange, Change<MediaTypeDiff>>, side: Side) {
+        self.nested("content", |checker| {
+            for (name, change) in content {
+                // Media types clients send are chosen by them, and an
+                // added one they read is only sent to those who ask for it.
+                checker.entry(
+                    name.as_str(),
+                    change,
+                    (ChangeKind::MediaTypeAdded, false),
+                    (ChangeKind::MediaTypeRemoved, true),
+                    |checker, diff| {
+                        if let Some(change) = &diff.schema {
+                            checker.entry(
+                                "schema",
+                                change,
+                                (ChangeKind::SchemaAdded, Direction::Narrowing.breaks(side)),
+                                (ChangeKind::SchemaRemoved, Direction::Widening.breaks(side)),
+                                |checker, diff| checker.schema(diff, side),
+                            );
+                        }
+                    },
+                );
+            }
+        });
+    }
+
+    fn schema(&mut self, diff: &SchemaDiff, side: Side) {
+        if let Some(kind) = &diff.kind {
+            self.nested("type", |checker| {
+                checker.report(
+                    ChangeKind::KindChanged {
+                        old: kind.old.clone(),
+                        new: kind.new.clone(),
+                    },
+                    Direction::Incompatible.breaks(side),
+                )
+            });
+        }
+
+        self.nested("properties", |checker| {
+            for (name, change) in &diff.properties {
+                checker.entry(
+                    name,
+                    change,
+                    (ChangeKind::PropertyAdded, false),
+                    (
+                        ChangeKind::PropertyRemoved,
+                        Direction::Widening.breaks(side),
+                    ),
+                    |checker, diff| checker.schema(diff, side),
+                );
+            }
+        });
+
+        self.nested("required", |checker| {
+            for name in &diff.required.added {
+                checker.report(
+                    ChangeKind::RequiredPropertyAdded(name.clone()),
+                    Direction::Narrowing.breaks(side),
+                );
+            }
+            for name in &diff.required.removed {
+                checker.report(
+                    ChangeKind::RequiredPropertyRemoved(name.clone()),
+                    Direction::Widening.breaks(side),
+                );
+            }
+        });
+
+        self.nested("enum", |checker| {
+            for value in &diff.enum_values.added {
+                checker.report(
+                    ChangeKind::EnumValueAdded(value.clone()),
+                    Direction::Widening.breaks(side),
+                );
+            }
+            for value in &diff.enum_values.removed {
+                checker.report(
+                    ChangeKind::EnumValueRemoved(value.clone()),
+                    Direction::Narrowing.breaks(side),
+                );
+            }
+        });
+
+        if let Some(items) = &diff.items {
+            self.nested("items", |checker| checker.schema(items, side));
+        }
+
+        self.nested("subschemas", |checker| {
+            let subschemas = &diff.subschemas;
+            // Another branch of `oneOf` or `anyOf` is another way to be
+            // valid, while another part of `allOf` is one more to satisfy.
+            let added = if subschemas.combinator == "allOf" {
+                Direction::Narrowing
+            } else {
+                Direction::Widening
+            };
+            let removed = if added == Direction::Narrowing {
+                Direction::Widening
+            } else {
+                Direction::Narrowing
+            };
+            for i in &subschemas.removed {
+                checker.nested(&i.to_string(), |checker| {
+                    checker.report(ChangeKind::SubschemaRemoved, removed.breaks(side))
+                });
+            }
+            for i in &subschemas.added {
+                checker.nested(&i.to_string(), |checker| {
+                    checker.report(ChangeKind::SubschemaAdded, added.breaks(side))
+                });
+            }
+            for (i, diff) in &subschemas.changed {
+                checker.nested(&i.to_string(), |checker| checker.schema(diff, side));
+            }
+        });
+
+        for (keyword, change) in &diff.keywords {
+            let direction = keyword_direction(keyword, change.old.as_ref(), change.new.as_ref());
+            self.nested(keyword, |checker| {
+                checker.report(
+                    ChangeKind::KeywordChanged {
+                        keyword: keyword.clone(),
+                        old: change.old.clone(),
+                        new: change.new.clone(),
+                    },
+                    direction.breaks(side),
+                )
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_changes() {
+        let old: OpenAPI = serde_yaml::from_str(
+            "
+openapi: 3.0.0
+info: {title: Pets, version: '1'}
+paths:
+  /pets:
+    get:
+      parameters: [{name: limit, in: query, schema: {type: integer, maximum: 100}}]
+      responses:
+        '200':
+          description: pets
+          content:
+            application/json:
+              schema: {$ref: '#/components/schemas/Pet'}
+        '404': {description: none}
+    post:
+      requestBody:
+        content:
+          application/json:
+            schema: {$ref: '#/components/schemas/Pet'}
+      responses: {'201': {description: created}}
+components:
+  schemas:
+    Pet:
+      type: object
+      properties:
+        name: {type: string}
+        kind: {type: string, enum: [cat, dog]}
+",
+        )
+        .unwrap();
+        let new: OpenAPI = serde_yaml::from_str(
+            "
+openapi: 3.0.0
+info: {title: Pets, version: '2'}
+paths:
+  /pets:
+    get:
+      parameters:
+        - {name: limit, in: query, schema: {type: integer, maximum: 200}}
+        - {name: sort, in: query, required: true, schema: {type: string}}
+      responses:
+        '200':
+          description: pets
+          content:
+            application/json:
+              schema: {$ref: '#/components/schemas/Pet'}
+    post:
+      requestBody:
+        content:
+          application/json:
+            schema: {$ref: '#/components/schemas/Pet'}
+      responses: {'201': {description: created}}
+components:
+  schemas:
+    Pet:
+      type: object
+      required: [name]
+      properties:
+        name: {type: string}
+        kind: {type: string, enum: [cat, dog, bird]}
+",
+        )
+        .unwrap();
+
+        let report = check_compatibility(&old, &new);
+        let lines: Vec<String> = report.changes.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            lines,
+            vec![
+                "compatible: /paths/~1pets/get/parameters/limit/schema/maximum: \
+                 `maximum` changed from 100 to 200",
+                "breaking: /paths/~1pets/get/parameters/sort: required parameter added",
+                "breaking: /paths/~1pets/get/responses/200/content/application~1json/schema\
+                 /properties/kind/enum: enum value \"bird\" added",
+                "compatible: /paths/~1pets/get/responses/200/content/application~1json/schema\
+                 /required: property `name` became required",
+                "breaking: /paths/~1pets/get/responses/404: response removed",
+                "compatible: /paths/~1pets/post/requestBody/content/application~1json/schema\
+                 /properties/kind/enum: enum value \"bird\" added",
+                "breaking: /paths/~1pets/post/requestBody/content/application~1json/schema\
+                 /required: property `name` became required",
+            ]
+        );
+        assert_eq!(report.breaking().count(), 4);
+    }
+
+    /// A document whose `/pets` operation sends `request` and receives
+    /// `response`, where `A` and `B` each require a property.
+    fn subschema_document(request: &str, response: &str) -> OpenAPI {
+        serde_yaml::from_str(&format!(
+            "
+openapi: 3.0.0
+info: {{title: Pets, version: '1'}}
+paths:
+  /pets:
+    post:
+      requestBody:
+        content:
+          application/json:
+            schema: {}
+      responses:
+        '200':
+          description: pet
+          content:
+            application/json:
+              schema: {}
+components:
+  schemas:
+    A: {{type: object, required: [a], properties: {{a: {{type: string}}}}}}
+    B: {{type: object, required: [b], properties: {{b: {{type: string}}}}}}
+",
+            request, response
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn classifies_subschema_changes() {
+        let a = "{$ref: '#/components/schemas/A'}";
+        let b = "{$ref: '#/components/schemas/B'}";
+        let all_of = |members: &[&str]| format!("{{allOf: [{}]}}", members.join(", "));
+        let one_of = |members: &[&str]| format!("{{oneOf: [{}]}}", members.join(", "));
+        let lines = |old: &OpenAPI, new: &OpenAPI| -> Vec<String> {
+            check_compatibility(old, new)
+                .changes
+                .iter()
+                .map(ToString::to_string)
+                .collect()
+        };
+        let request = "/paths/~1pets/post/requestBody/content/application~1json/schema";
+        let response = "/paths/~1pets/post/responses/200/content/application~1json/schema";
+
+        // Another part of `allOf` narrows the schema, and one fewer widens it.
+        let old = subschema_document(&all_of(&[a]), &all_of(&[a, b]));
+        let new = subschema_document(&all_of(&[a, b]), &all_of(&[a]));
+        assert_eq!(
+            lines(&old, &new),
+            vec![
+                format!("breaking: {}/subschemas/1: subschema added", request),
+                format!("breaking: {}/subschemas/1: subschema removed", response),
+            ]
+        );
+        assert_eq!(
+            lines(&new, &old),
+            vec![
+                format!("compatible: {}/subschemas/1: subschema removed", request),
+                format!("compatible: {}/subschemas/1: subschema added", response),
+            ]
+        );
+
+        // Another branch of `oneOf` widens it.
+        let old = subschema_document(&one_of(&[a]), &one_of(&[a]));
+        let new = subschema_document(&one_of(&[a, b]), &one_of(&[b, a]));
+        assert_eq!(
+            lines(&old, &new),
+            vec![
+                format!("compatible: {}/subschemas/1: subschema added", request),
+                format!("breaking: {}/subschemas/0: subschema added", response),
+            ]
+        );
+
+        // Members are matched by reference, or by structure, not by position.
+        let inline = "{type: object, required: [c]}";
+        let old = subschema_document(&one_of(&[a, b, inline]), &all_of(&[a, inline, b]));
+        let new = subschema_document(&one_of(&[inline, b, a]), &all_of(&[b, a, inline]));
+        assert!(lines(&old, &new).is_empty());
+    }
+}
@@ -1,6 +1,6 @@
 use crate::*;
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
-use std::collections::BTreeMap;
 
 /// Holds a set of reusable objects for different aspects of the OAS.
 /// All objects defined within the components object will have no effect
@@ -10,30 +10,33 @@ use std::collections::BTreeMap;
 #[serde(rename_all = "camelCase")]
 pub struct Components {
     /// An object to hold reusable Security Scheme Objects.
-    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
-    pub security_schemes: BTreeMap<String, ReferenceOr<SecurityScheme>>,
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub security_schemes: Map<String, ReferenceOr<SecurityScheme>>,
     /// An object to hold reusable Response Objects.
-    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
-    pub responses: BTreeMap<String, ReferenceOr<Response>>,
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub responses: Map<String, ReferenceOr<Response>>,
     /// An object to hold reusable Parameter Objects.
-    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
-    pub parameters: BTreeMap<String, ReferenceOr<Parameter>>,
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub parameters: Map<String, ReferenceOr<Parameter>>,
     /// An object to hold reusable Example Objects.
-    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
-    pub examples: BTreeMap<String, ReferenceOr<Example>>,
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub examples: Map<String, ReferenceOr<Example>>,
     /// An object to hold reusable Request Body Objects.
-    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
-    pub request_bodies: BTreeMap<String, ReferenceOr<RequestBody>>,
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub request_bodies: Map<String, ReferenceOr<RequestBody>>,
     /// An object to hold reusable Header Objects.
-    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
-    pub headers: BTreeMap<String, ReferenceOr<Header>>,
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub headers: Map<String, ReferenceOr<Header>>,
     /// An object to hold reusable Schema Objects.
-    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
-    pub schemas: BTreeMap<String, ReferenceOr<Schema>>,
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub schemas: Map<String, ReferenceOr<Schema>>,
     /// An object to hold reusable Link Objects.
-    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
-    pub links: BTreeMap<String, ReferenceOr<Link>>,
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub links: Map<String, ReferenceOr<Link>>,
     /// An object to hold reusable Callback Objects.
-    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
-    pub callbacks: BTreeMap<String, ReferenceOr<Callback>>,
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub callbacks: Map<String, ReferenceOr<Callback>>,
+    /// Inline extensions to this object.
+    #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
+    pub extensions: IndexMap<String, serde_json::Value>,
 }
@@ -1,7 +1,8 @@
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 
 /// Contact information for the exposed API.
-#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
 pub struct Contact {
     /// The identifying name of the contact person/organization.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -14,4 +15,7 @@ pub struct Contact {
     /// MUST be in the format of an email address.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub email: Option<String>,
+    /// Inline extensions to this object.
+    #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
+    pub extensions: IndexMap<String, serde_json::Value>,
 }
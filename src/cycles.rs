@@ -0,0 +1,200 @@
+use crate::walk::{FindRefs, Location};
+use crate::*;
+use std::collections::BTreeMap;
+
+impl OpenAPI {
+    /// Finds the groups of component schemas that reference each other,
+    /// directly or indirectly, i.e. the strongly connected components of
+    /// the graph of schema references.
+    ///
+    /// Each group is a sorted list of references such as
+    /// `#/components/schemas/Node`. A schema that only references itself
+    /// forms a group on its own; schemas that are not part of any cycle
+    /// are not reported.
+    pub fn detect_reference_cycles(&self) -> Vec<Vec<String>> {
+        let schemas = match &self.components {
+            Some(components) => &components.schemas,
+            None => return Vec::new(),
+        };
+
+        let names: Vec<&str> = schemas.keys().map(String::as_str).collect();
+        let nodes: BTreeMap<&str, usize> = names
+            .iter()
+            .enumerate()
+            .map(|(node, &name)| (name, node))
+            .collect();
+        let edges: Vec<Vec<usize>> = schemas
+            .values()
+            .map(|schema| {
+                let mut targets = Vec::new();
+                let mut at = Location::default();
+                schema.find_refs(&mut at, &mut |_: &Location, reference: &Reference| {
+                    if reference.is_external()
+                        || reference.component_kind() != Some(ComponentKind::Schemas)
+                    {
+                        return;
+                    }
+                    if let Some(&node) = reference.name().and_then(|name| nodes.get(&*name)) {
+                        targets.push(node);
+                    }
+                });
+                targets.sort_unstable();
+                targets.dedup();
+                targets
+            })
+            .collect();
+
+        let mut tarjan = Tarjan {
+            edges: &edges,
+            index: vec![None; names.len()],
+            low_link: vec![0; names.len()],
+            on_stack: vec![false; names.len()],
+            stack: Vec::new(),
+            next_index: 0,
+            components: Vec::new(),
+        };
+        for node in 0..names.len() {
+            if tarjan.index[node].is_none() {
+                tarjan.connect(node);
+            }
+        }
+
+        let mut cycles: Vec<Vec<String>> = tarjan
+            .components
+            .into_iter()
+            .filter(|component| component.len() > 1 || edges[component[0]].contains(&component[0]))
+            .map(|component| {
+                let mut references: Vec<String> = component
+                    .into_iter()
+                    .map(|node| Reference::component(ComponentKind::Schemas, names[node]).into())
+                    .collect();
+                references.sort();
+                references
+            })
+            .collect();
+        cycles.sort();
+        cycles
+    }
+}
+
+struct Tarjan<'a> {
+    edges: &'a [Vec<usize>],
+    index: Vec<Option<usize>>,
+    low_link: Vec<usize>,
+    on_stack: Vec<bool>,
+    stack: Vec<usize>,
+    next_index: usize,
+    components: Vec<Vec<usize>>,
+}
+
+impl<'a> Tarjan<'a> {
+    /// Visits `start` and every node reachable from it that has not been
+    /// visited yet, keeping its own stack of the nodes being visited and
+    /// how many of their edges have been followed, so that long chains of
+    /// references do not overflow the call stack.
+    fn connect(&mut self, start: usize) {
+        self.visit(start);
+        let mut calls = vec![(start, 0)];
+        while let Some(&mut (node, ref mut edge)) = calls.last_mut() {
+            if let Some(&target) = self.edges[node].get(*edge) {
+                *edge += 1;
+                match self.index[target] {
+                    None => {
+                        self.visit(target);
+                        calls.push((target, 0));
+                    }
+                    Some(index) if self.on_stack[target] => {
+                        self.low_link[node] = self.low_link[node].min(index);
+                    }
+                    Some(_) => {}
+                }
+                continue;
+            }
+
+            calls.pop();
+            if let Some(&(caller, _)) = calls.last() {
+                self.low_link[caller] = self.low_link[caller].min(self.low_link[node]);
+            }
+            if Some(self.low_link[node]) == self.index[node] {
+                let mut component = Vec::new();
+                while let Some(member) = self.stack.pop() {
+                    self.on_stack[member] = false;
+                    component.push(member);
+                    if member == node {
+                        break;
+                    }
+                }
+                self.components.push(component);
+            }
+        }
+    }
+
+    fn visit(&mut self, node: usize) {
+        self.index[node] = Some(self.next_index);
+        self.low_link[node] = self.next_index;
+        self.next_index += 1;
+        self.stack.push(node);
+        self.on_stack[node] = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn finds_recursive_schemas() {
+        let api: OpenAPI = serde_yaml::from_str(
+            "
+openapi: 3.0.0
+info: {title: t, version: '1'}
+paths: {}
+components:
+  schemas:
+    Leaf: {type: string}
+    Tree:
+      type: object
+      properties:
+        children: {type: array, items: {$ref: '#/components/schemas/Tree'}}
+        leaf: {$ref: '#/components/schemas/Leaf'}
+    Ping: {allOf: [{$ref: '#/components/schemas/Pong'}]}
+    Pong: {type: object, additionalProperties: {$ref: '#/components/schemas/Ping'}}
+    a/b: {type: array, items: {$ref: '#/components/schemas/a~1b'}}
+",
+        )
+        .unwrap();
+        assert_eq!(
+            api.detect_reference_cycles(),
+            vec![
+                vec!["#/components/schemas/Ping", "#/components/schemas/Pong"],
+                vec!["#/components/schemas/Tree"],
+                vec!["#/components/schemas/a~1b"],
+            ]
+        );
+    }
+
+    #[test]
+    fn follows_long_chains() {
+        let mut components = Components::default();
+        let length = 100_000;
+        for i in 0..length {
+            let next = format!("#/components/schemas/S{}", (i + 1) % length);
+            components.schemas.insert(
+                format!("S{}", i),
+                ReferenceOr::Item(Schema {
+                    schema_data: Default::default(),
+                    schema_kind: SchemaKind::AllOf {
+                        all_of: vec![ReferenceOr::ref_(&next)],
+                    },
+                }),
+            );
+        }
+        let api = OpenAPI {
+            components: Some(components),
+            ..OpenAPI::default()
+        };
+        let cycles = api.detect_reference_cycles();
+        assert_eq!(cycles.len(), 1);
+        assert_eq!(cycles[0].len(), length);
+    }
+}
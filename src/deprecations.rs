@@ -0,0 +1,213 @@
+use crate::reference::unescape_pointer_segment;
+use crate::visit::Visitor;
+use crate::*;
+use indexmap::IndexMap;
+use serde::Serialize;
+use serde_json::Value;
+
+/// Something a document deprecates.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Deprecation {
+    /// A JSON Pointer to the deprecated object.
+    pub location: String,
+    pub kind: DeprecationKind,
+    /// The `operationId` of an operation, or its method and path such as
+    /// `GET /pets`; the name of a parameter or property; and the last
+    /// segment of the location of any other schema, such as the name of
+    /// a component.
+    pub name: String,
+    /// When it is to be removed, as the `x-sunset` extension gives it.
+    pub sunset: Option<String>,
+    /// Why it is deprecated, or what to use instead, as an `x-deprecated`
+    /// extension that is a string gives it.
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DeprecationKind {
+    Operation,
+    Parameter,
+    /// A schema that is a property of another.
+    Property,
+    Schema,
+}
+
+impl OpenAPI {
+    /// Every deprecated operation, parameter and schema of the document,
+    /// those of components included, in the order [`OpenAPI::visit`] comes
+    /// across them. Those not marked `deprecated` are also deprecated if
+    /// they have an `x-sunset` extension, or an `x-deprecated` extension
+    /// that is `true` or a message.
+    ///
+    /// ```
+    /// use openapiv3::{DeprecationKind, OpenAPI};
+    ///
+    /// let api: OpenAPI = serde_yaml::from_str(
+    ///     "
+    /// openapi: 3.0.0
+    /// info: {title: Pets, version: '1'}
+    /// paths:
+    ///   /pets:
+    ///     get:
+    ///       x-sunset: '2025-01-01'
+    ///       x-deprecated: Use /animals instead.
+    ///       responses: {}
+    /// components:
+    ///   schemas:
+    ///     Pet:
+    ///       type: object
+    ///       properties:
+    ///         kind: {type: string, deprecated: true}
+    /// ",
+    /// )
+    /// .unwrap();
+    /// let deprecations = api.deprecations();
+    /// assert_eq!(deprecations[0].kind, DeprecationKind::Operation);
+    /// assert_eq!(deprecations[0].name, "GET /pets");
+    /// assert_eq!(deprecations[0].sunset.as_deref(), Some("2025-01-01"));
+    /// assert_eq!(deprecations[0].message.as_deref(), Some("Use /animals instead."));
+    /// assert_eq!(deprecations[1].location, "/components/schemas/Pet/properties/kind");
+    /// assert_eq!(deprecations[1].kind, DeprecationKind::Property);
+    /// ```
+    pub fn deprecations(&self) -> Vec<Deprecation> {
+        let mut finder = DeprecationFinder(Vec::new());
+        self.visit(&mut finder);
+        finder.0
+    }
+}
+
+struct DeprecationFinder(Vec<Deprecation>);
+
+impl DeprecationFinder {
+    fn push(
+        &mut self,
+        location: &str,
+        kind: DeprecationKind,
+        name: String,
+        deprecated: bool,
+        extensions: &IndexMap<String, Value>,
+    ) {
+        let sunset = extensions.get("x-sunset").map(|sunset| match sunset {
+            Value::String(sunset) => sunset.clone(),
+            sunset => sunset.to_string(),
+        });
+        let (marked, message) = match extensions.get("x-deprecated") {
+            Some(Value::Bool(marked)) => (*marked, None),
+            Some(Value::String(message)) => (true, Some(message.clone())),
+            _ => (false, None),
+        };
+        if deprecated || marked || sunset.is_some() {
+            self.0.push(Deprecation {
+                location: location.to_owned(),
+                kind,
+                name,
+                sunset,
+                message,
+            });
+        }
+    }
+}
+
+impl Visitor<'_> for DeprecationFinder {
+    fn visit_operation(&mut self, location: &str, operation: &Operation) {
+        let name = match &operation.operation_id {
+            Some(id) => id.clone(),
+            None => {
+                let mut segments = location.rsplit('/').map(unescape_pointer_segment);
+                let method = segments.next().unwrap_or_default().to_ascii_uppercase();
+                format!("{} {}", method, segments.next().unwrap_or_default())
+            }
+        };
+        self.push(
+            location,
+            DeprecationKind::Operation,
+            name,
+            operation.deprecated,
+            &operation.extensions,
+        );
+    }
+
+    fn visit_parameter(&mut self, location: &str, parameter: &Parameter) {
+        let data = parameter.parameter_data_ref();
+        self.push(
+            location,
+            DeprecationKind::Parameter,
+            data.name.clone(),
+            data.deprecated.unwrap_or(false),
+            &data.extensions,
+        );
+    }
+
+    fn visit_schema(&mut self, location: &str, schema: &Schema) {
+        let mut segments = location.rsplit('/');
+        let name = unescape_pointer_segment(segments.next().unwrap_or_default());
+        let kind = match segments.next() {
+            Some("properties") => DeprecationKind::Property,
+            _ => DeprecationKind::Schema,
+        };
+        let data = &schema.schema_data;
+        self.push(location, kind, name, data.deprecated, &data.extensions);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_deprecations() {
+        let api: OpenAPI = serde_yaml::from_str(
+            "
+openapi: 3.0.0
+info: {title: Pets, version: '1'}
+paths:
+  /pets/{id}:
+    delete:
+      operationId: deletePet
+      deprecated: true
+      parameters:
+        - {name: id, in: path, required: true, schema: {type: string}}
+        - {name: force, in: query, x-deprecated: true, schema: {type: boolean}}
+      responses: {}
+    get:
+      x-deprecated: false
+      responses: {}
+components:
+  schemas:
+    Legacy:
+      type: string
+      x-sunset: 2030-06-30
+",
+        )
+        .unwrap();
+        let found: Vec<_> = api
+            .deprecations()
+            .into_iter()
+            .map(|d| (d.location, d.kind, d.name, d.sunset))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (
+                    "/paths/~1pets~1{id}/delete".to_owned(),
+                    DeprecationKind::Operation,
+                    "deletePet".to_owned(),
+                    None
+                ),
+                (
+                    "/paths/~1pets~1{id}/delete/parameters/1".to_owned(),
+                    DeprecationKind::Parameter,
+                    "force".to_owned(),
+                    None
+                ),
+                (
+                    "/components/schemas/Legacy".to_owned(),
+                    DeprecationKind::Schema,
+                    "Legacy".to_owned(),
+                    Some("2030-06-30".to_owned())
+                ),
+            ]
+        );
+    }
+}
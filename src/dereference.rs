@@ -0,0 +1,246 @@
+use crate::walk::{Referenceable, VisitRefs, Walk};
+use crate::*;
+use std::error::Error;
+use std::fmt;
+
+/// The reasons a document can fail to be dereferenced.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DereferenceError {
+    /// A reference could not be resolved.
+    Resolve(ResolveError),
+    /// Expanding a reference would never terminate. Holds the chain of
+    /// references, ending with the one that was revisited.
+    Cycle(Vec<String>),
+}
+
+impl fmt::Display for DereferenceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DereferenceError::Resolve(e) => e.fmt(f),
+            DereferenceError::Cycle(chain) => {
+                write!(f, "circular reference: {}", chain.join(" -> "))
+            }
+        }
+    }
+}
+
+impl Error for DereferenceError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            DereferenceError::Resolve(e) => Some(e),
+            DereferenceError::Cycle(_) => None,
+        }
+    }
+}
+
+impl From<ResolveError> for DereferenceError {
+    fn from(e: ResolveError) -> Self {
+        DereferenceError::Resolve(e)
+    }
+}
+
+impl OpenAPI {
+    /// Replaces every reference in the document with a copy of the object
+    /// it points to, so that no `ReferenceOr::Reference` remains.
+    ///
+    /// The components are kept, themselves dereferenced. Fails on
+    /// references that cannot be resolved within the document and on
+    /// recursive definitions, which have no finite expansion.
+    pub fn into_dereferenced(mut self) -> Result<OpenAPI, DereferenceError> {
+        let components = self.components.clone().unwrap_or_default();
+        let mut dereferencer = Dereferencer {
+            components: &components,
+            stack: Vec::new(),
+        };
+        self.paths.walk(&mut dereferencer)?;
+        if let Some(components) = &mut self.components {
+            dereferencer.components(components)?;
+        }
+        Ok(self)
+    }
+
+    /// Like [`OpenAPI::into_dereferenced`], dereferencing the path items on
+    /// several threads.
+    #[cfg(feature = "parallel")]
+    pub fn par_into_dereferenced(mut self) -> Result<OpenAPI, DereferenceError> {
+        let components = self.components.clone().unwrap_or_default();
+        let mut items: Vec<_> = self.paths.values_mut().collect();
+        let results = crate::parallel::map_chunks_mut(&mut items, |items| {
+            let mut dereferencer = Dereferencer {
+                components: &components,
+                stack: Vec::new(),
+            };
+            items
+                .iter_mut()
+                .try_for_each(|item| item.walk(&mut dereferencer))
+        });
+        results.into_iter().collect::<Result<(), _>>()?;
+        let mut dereferencer = Dereferencer {
+            components: &components,
+            stack: Vec::new(),
+        };
+        if let Some(components) = &mut self.components {
+            dereferencer.components(components)?;
+        }
+        Ok(self)
+    }
+}
+
+struct Dereferencer<'a> {
+    components: &'a Components,
+    /// The references currently being expanded, outermost first.
+    stack: Vec<String>,
+}
+
+impl<'a> Dereferencer<'a> {
+    fn within<F>(&mut self, reference: String, f: F) -> Result<(), DereferenceError>
+    where
+        F: FnOnce(&mut Self) -> Result<(), DereferenceError>,
+    {
+        self.stack.push(reference);
+        let result = f(self);
+        self.stack.pop();
+        result
+    }
+
+    fn section<T: Referenceable + Component>(
+        &mut self,
+        section: &mut Map<String, ReferenceOr<T>>,
+    ) -> Result<(), DereferenceError> {
+        for (name, value) in section.iter_mut() {
+            let pointer = Reference::component(T::KIND, name).into();
+            self.within(pointer, |d| value.walk(d))?;
+        }
+        Ok(())
+    }
+
+    fn components(&mut self, components: &mut Components) -> Result<(), DereferenceError> {
+        self.section(&mut components.security_schemes)?;
+        self.section(&mut components.responses)?;
+        self.section(&mut components.parameters)?;
+        self.section(&mut components.examples)?;
+        self.section(&mut components.request_bodies)?;
+        self.section(&mut components.headers)?;
+        self.section(&mut components.schemas)?;
+        self.section(&mut components.links)?;
+        self.section(&mut components.callbacks)
+    }
+}
+
+impl<'a> VisitRefs for Dereferencer<'a> {
+    type Error = DereferenceError;
+
+    fn reference<T: Referenceable>(
+        &mut self,
+        value: &mut ReferenceOr<T>,
+    ) -> Result<(), DereferenceError> {
+        let reference = match value {
+            ReferenceOr::Item(item) => return item.walk(self),
+            ReferenceOr::Reference { reference } => reference.to_string(),
+        };
+        if let Some(start) = self.stack.iter().position(|r| *r == reference) {
+            let mut chain = self.stack[start..].to_vec();
+            chain.push(reference);
+            return Err(DereferenceError::Cycle(chain));
+        }
+        let mut item = T::resolve_in(self.components, &reference)?.clone();
+        self.within(reference, |d| item.walk(d))?;
+        *value = ReferenceOr::Item(item);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    fn schema(kind: SchemaKind) -> Schema {
+        Schema {
+            schema_data: Default::default(),
+            schema_kind: kind,
+        }
+    }
+
+    fn array_of(reference: &str) -> ReferenceOr<Schema> {
+        ReferenceOr::Item(schema(SchemaKind::Type(Type::Array(ArrayType {
+            items: ReferenceOr::ref_(reference),
+            min_items: None,
+            max_items: None,
+            unique_items: false,
+        }))))
+    }
+
+    #[test]
+    fn inlines_references() {
+        let mut components = Components::default();
+        components.schemas.insert(
+            "Name".to_owned(),
+            ReferenceOr::Item(schema(SchemaKind::Type(Type::String(Default::default())))),
+        );
+        components
+            .schemas
+            .insert("Names".to_owned(), array_of("#/components/schemas/Name"));
+        let api = OpenAPI {
+            components: Some(components),
+            ..Default::default()
+        }
+        .into_dereferenced()
+        .unwrap();
+
+        let names = &api.components.unwrap().schemas["Names"];
+        assert_eq!(
+            names,
+            &ReferenceOr::Item(schema(SchemaKind::Type(Type::Array(ArrayType {
+                items: ReferenceOr::boxed_item(schema(SchemaKind::Type(Type::String(
+                    Default::default()
+                )))),
+                min_items: None,
+                max_items: None,
+                unique_items: false,
+            }))))
+        );
+    }
+
+    #[test]
+    fn reports_cycles() {
+        let mut components = Components::default();
+        components
+            .schemas
+            .insert("Tree".to_owned(), array_of("#/components/schemas/Tree"));
+        let result = OpenAPI {
+            components: Some(components),
+            ..Default::default()
+        }
+        .into_dereferenced();
+
+        assert_eq!(
+            result,
+            Err(DereferenceError::Cycle(vec![
+                "#/components/schemas/Tree".to_owned(),
+                "#/components/schemas/Tree".to_owned(),
+            ]))
+        );
+
+        let mut components = Components::default();
+        components
+            .schemas
+            .insert("a/b".to_owned(), array_of("#/components/schemas/c"));
+        components
+            .schemas
+            .insert("c".to_owned(), array_of("#/components/schemas/a~1b"));
+        let result = OpenAPI {
+            components: Some(components),
+            ..Default::default()
+        }
+        .into_dereferenced();
+
+        assert_eq!(
+            result,
+            Err(DereferenceError::Cycle(vec![
+                "#/components/schemas/a~1b".to_owned(),
+                "#/components/schemas/c".to_owned(),
+                "#/components/schemas/a~1b".to_owned(),
+            ]))
+        );
+    }
+}
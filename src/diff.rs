@@ -0,0 +1,986 @@
+//! Structural differences between two versions of a document.
+//!
+//! [`diff`] compares the operations of two documents, and the schemas of
+//! their components, returning a [`DocumentDiff`]: a tree holding only
+//! what changed, which serializes to JSON for rendering elsewhere.
+//!
+//! ```
+//! use openapiv3::diff::{diff, Change};
+//! use openapiv3::OpenAPI;
+//!
+//! let old: OpenAPI = serde_yaml::from_str(
+//!     "
+//! openapi: 3.0.0
+//! info: {title: Pets, version: '1'}
+//! paths:
+//!   /pets: {get: {responses: {'200': {description: pets}}}}
+//! ",
+//! )
+//! .unwrap();
+//! let mut new = old.clone();
+//! new.paths.remove("/pets");
+//!
+//! assert_eq!(diff(&old, &new).paths["/pets"], Change::Removed);
+//! ```
+//!
+//! References are followed, so that a change to a component shows
+//! wherever the component is used. Parameters are matched by name, so
+//! that moving one to another location is a change rather than a removal
+//! and an addition. Path items given by reference are not compared.
+
+use crate::operation::merge_parameters;
+use crate::reference::escape_pointer_segment;
+use crate::*;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// How an entry of a map, such as a path or a property, changed.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "change", rename_all = "lowercase")]
+pub enum Change<T> {
+    Added,
+    Removed,
+    /// The entry is in both documents, and differs as described.
+    Changed(T),
+}
+
+/// A value that differs between the documents.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ValueChange<T> {
+    pub old: T,
+    pub new: T,
+}
+
+/// The members added to, and removed from, a set of values,
+/// such as the required properties of a schema.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SetChange<T> {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub added: Vec<T>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub removed: Vec<T>,
+}
+
+impl<T> Default for SetChange<T> {
+    fn default() -> Self {
+        SetChange {
+            added: Vec::new(),
+            removed: Vec::new(),
+        }
+    }
+}
+
+impl<T: Clone + PartialEq> SetChange<T> {
+    fn between(old: &[T], new: &[T]) -> Self {
+        let missing = |from: &[T], of: &[T]| -> Vec<T> {
+            of.iter()
+                .filter(|value| !from.contains(value))
+                .cloned()
+                .collect()
+        };
+        SetChange {
+            added: missing(old, new),
+            removed: missing(new, old),
+        }
+    }
+}
+
+impl<T> SetChange<T> {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct DocumentDiff {
+    /// The change of `info.version`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<ValueChange<String>>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub paths: BTreeMap<String, Change<PathItemDiff>>,
+    /// The schemas of the components.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub schemas: BTreeMap<String, Change<SchemaDiff>>,
+}
+
+impl DocumentDiff {
+    /// Whether the documents are the same, as far as the diff can tell.
+    pub fn is_empty(&self) -> bool {
+        self.version.is_none() && self.paths.is_empty() && self.schemas.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct PathItemDiff {
+    /// The operations, by HTTP method.
+    pub operations: BTreeMap<String, Change<OperationDiff>>,
+}
+
+/// The changes of an operation, which include those of the parameters
+/// shared by its path item.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct OperationDiff {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<ValueChange<bool>>,
+    /// The parameters, by name. A name used in several locations is
+    /// followed by the location, as in `id (query)`.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub parameters: BTreeMap<String, Change<ParameterDiff>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_body: Option<Change<RequestBodyDiff>>,
+    /// The responses, by status code or `default`.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub responses: BTreeMap<String, Change<ResponseDiff>>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ParameterDiff {
+    /// The change of `in`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<ValueChange<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required: Option<ValueChange<bool>>,
+    /// The change of the schema, or of that of the media type
+    /// of a parameter described by `content`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema: Option<SchemaDiff>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct RequestBodyDiff {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required: Option<ValueChange<bool>>,
+    /// The media types, by name.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub content: BTreeMap<MediaTypeRange, Change<MediaTypeDiff>>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ResponseDiff {
+    /// The media types, by name.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub content: BTreeMap<MediaTypeRange, Change<MediaTypeDiff>>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct MediaTypeDiff {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema: Option<Change<SchemaDiff>>,
+}
+
+/// The changes of a schema, after following references.
+///
+/// Documentation, such as `description` and `example`, is not compared.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct SchemaDiff {
+    /// The change of the `$ref`, where either schema is a reference
+    /// and the two differ.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reference: Option<ValueChange<Option<String>>>,
+    /// The change of the type, or of the combinator (`oneOf`, `allOf`
+    /// or `anyOf`); schemas of neither are of the kind `any`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<ValueChange<String>>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub properties: BTreeMap<Name, Change<SchemaDiff>>,
+    #[serde(skip_serializing_if = "SetChange::is_empty")]
+    pub required: SetChange<Name>,
+    #[serde(skip_serializing_if = "SetChange::is_empty")]
+    pub enum_values: SetChange<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub items: Option<Box<SchemaDiff>>,
+    #[serde(skip_serializing_if = "SubschemaDiff::is_empty")]
+    pub subschemas: SubschemaDiff,
+    /// The changes of every other keyword, such as `maximum`, with `None`
+    /// for a keyword that is absent.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub keywords: BTreeMap<String, ValueChange<Option<Value>>>,
+}
+
+/// The changes of the schemas of `oneOf`, `allOf` or `anyOf`.
+///
+/// Members are matched by their `$ref`, or else by being the same but for
+/// documentation, so that reordering them is no change. Those left over
+/// are compared in order, and any beyond are added or removed.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct SubschemaDiff {
+    /// The combinator of the new schema, or of the old one if the new
+    /// schema has none.
+    pub combinator: String,
+    /// The positions of the members of the new schema matching none of
+    /// the old.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub added: Vec<usize>,
+    /// The positions of the members of the old schema matching none of
+    /// the new.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub removed: Vec<usize>,
+    /// The members that changed, by their position in the new schema.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub changed: BTreeMap<usize, SchemaDiff>,
+}
+
+impl SubschemaDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+trait IsEmpty {
+    fn is_empty(&self) -> bool;
+}
+
+macro_rules! is_empty {
+    ($($ty:ty { $($field:ident: $check:ident),* })*) => {
+        $(impl IsEmpty for $ty {
+            fn is_empty(&self) -> bool {
+                true $(&& self.$field.$check())*
+            }
+        })*
+    };
+}
+
+is_empty! {
+    PathItemDiff { operations: is_empty }
+    OperationDiff {
+        deprecated: is_none,
+        parameters: is_empty,
+        request_body: is_none,
+        responses: is_empty
+    }
+    ParameterDiff { location: is_none, required: is_none, schema: is_none }
+    RequestBodyDiff { required: is_none, content: is_empty }
+    ResponseDiff { content: is_empty }
+    MediaTypeDiff { schema: is_none }
+    SchemaDiff {
+        reference: is_none,
+        kind: is_none,
+        properties: is_empty,
+        required: is_empty,
+        enum_values: is_empty,
+        items: is_none,
+        subschemas: is_empty,
+        keywords: is_empty
+    }
+}
+
+/// `Some` of the diff, unless it is empty.
+fn non_empty<D: IsEmpty>(diff: D) -> Option<D> {
+    if diff.is_empty() {
+        None
+    } else {
+        Some(diff)
+    }
+}
+
+fn value_change<T: PartialEq>(old: T, new: T) -> Option<ValueChange<T>> {
+    if old == new {
+        None
+    } else {
+        Some(ValueChange { old, new })
+    }
+}
+
+/// Compares `new` against `old`.
+pub fn diff(old: &OpenAPI, new: &OpenAPI) -> DocumentDiff {
+    let empty = Components::default();
+    let old_components = old.components.as_ref().unwrap_or(&empty);
+    let new_components = new.components.as_ref().unwrap_or(&empty);
+    let mut differ = Differ {
+        old: old_components,
+        new: new_components,
+        expanding: Vec::new(),
+    };
+
+    let paths = differ.map(&old.paths, &new.paths, |differ, old, new| {
+        match (old, new) {
+            (ReferenceOr::Item(old), ReferenceOr::Item(new)) => differ.path_item(old, new),
+            _ => PathItemDiff::default(),
+        }
+    });
+    // Comparing each schema through a reference to it keeps a schema
+    // that refers to itself from being expanded once more.
+    let references = |components: &Components| -> BTreeMap<String, ReferenceOr<Schema>> {
+        components
+            .schemas
+            .keys()
+            .map(|name| {
+                let reference = format!("#/components/schemas/{}", escape_pointer_segment(name));
+                (name.clone(), ReferenceOr::ref_(&reference))
+            })
+            .collect()
+    };
+    let schemas = differ.map(
+        &references(old_components),
+        &references(new_components),
+        Differ::schema,
+    );
+
+    DocumentDiff {
+        version: value_change(old.info.version.clone(), new.info.version.clone()),
+        paths,
+        schemas,
+    }
+}
+
+struct Differ<'a> {
+    old: &'a Components,
+    new: &'a Components,
+    /// The pairs of references being compared, outermost first.
+    expanding: Vec<(Option<String>, Option<String>)>,
+}
+
+/// The parts of a schema that are compared structurally.
+struct Shape<'s> {
+    kind: &'static str,
+    properties: Option<&'s Map<Name, ReferenceOr<Box<Schema>>>>,
+    required: &'s [Name],
+    enum_values: Vec<Value>,
+    items: Option<&'s ReferenceOr<Box<Schema>>>,
+    subschemas: &'s [ReferenceOr<Schema>],
+}
+
+impl<'s> Shape<'s> {
+    fn of(schema: &'s Schema) -> Self {
+        let mut shape = Shape {
+            kind: "any",
+            properties: None,
+            required: &[],
+            enum_values: Vec::new(),
+            items: None,
+            subschemas: &[],
+        };
+        match &schema.schema_kind {
+            SchemaKind::Type(Type::String(string)) => {
+                shape.kind = "string";
+                shape.enum_values = string
+                    .enumeration
+                    .iter()
+                    .map(|v| v.as_str().into())
+                    .collect();
+            }
+            SchemaKind::Type(Type::Number(number)) => {
+                shape.kind = "number";
+                shape.enum_values = number.enumeration.iter().map(|&v| v.into()).collect();
+            }
+            SchemaKind::Type(Type::Integer(integer)) => {
+                shape.kind = "integer";
+                shape.enum_values = integer.enumeration.iter().map(|&v| v.into()).collect();
+            }
+            SchemaKind::Type(Type::Object(object)) => {
+                shape.kind = "object";
+                shape.properties = Some(&object.properties);
+                shape.required = &object.required;
+            }
+            SchemaKind::Type(Type::Array(array)) => {
+                shape.kind = "array";
+                shape.items = Some(&array.items);
+            }
+            SchemaKind::Type(Type::Boolean {}) => shape.kind = "boolean",
+            SchemaKind::OneOf { one_of } => {
+                shape.kind = "oneOf";
+                shape.subschemas = one_of;
+            }
+            SchemaKind::AllOf { all_of } => {
+                shape.kind = "allOf";
+                shape.subschemas = all_of;
+            }
+            SchemaKind::AnyOf { any_of } => {
+                shape.kind = "anyOf";
+                shape.subschemas = any_of;
+            }
+            SchemaKind::Any(any) => {
+                shape.properties = Some(&any.properties);
+                shape.required = &any.required;
+                shape.items = any.items.as_ref();
+            }
+        }
+        shape
+    }
+}
+
+/// Keywords compared by [`Shape`], or not compared as they only document.
+const NOT_KEYWORDS: &[&str] = &[
+    "type",
+    "properties",
+    "required",
+    "enum",
+    "items",
+    "oneOf",
+    "allOf",
+    "anyOf",
+    "title",
+    "description",
+    "example",
+    "externalDocs",
+];
+
+fn keywords(schema: &Schema) -> BTreeMap<String, Value> {
+    match serde_json::to_value(schema) {
+        Ok(Value::Object(object)) => object
+            .into_iter()
+            .filter(|(keyword, _)| !NOT_KEYWORDS.contains(&keyword.as_str()))
+            .collect(),
+        _ => BTreeMap::new(),
+    }
+}
+
+fn reference_of<T>(schema: &ReferenceOr<T>) -> Option<String> {
+    match schema {
+        ReferenceOr::Reference { reference } => Some(reference.as_str().to_owned()),
+        ReferenceOr::Item(_) => None,
+    }
+}
+
+impl<'a> Differ<'a> {
+    /// Compares two maps entry by entry, with `f` comparing the entries
+    /// present in both.
+    fn map<'m, K, V, D, F>(
+        &mut self,
+        old: impl IntoIterator<Item = (&'m K, &'m V)>,
+        new: impl IntoIterator<Item = (&'m K, &'m V)>,
+        mut f: F,
+    ) -> BTreeMap<K, Change<D>>
+    where
+        K: Ord + Clone + 'm,
+        V: 'm,
+        D: IsEmpty,
+        F: FnMut(&mut Self, &V, &V) -> D,
+    {
+        let old: BTreeMap<&K, &V> = old.into_iter().collect();
+        let new: BTreeMap<&K, &V> = new.into_iter().collect();
+        let mut changes = BTreeMap::new();
+        for (&key, &old_value) in &old {
+            match new.get(key) {
+                Some(&new_value) => {
+                    if let Some(diff) = non_empty(f(self, old_value, new_value)) {
+                        changes.insert(key.clone(), Change::Changed(diff));
+                    }
+                }
+                None => {
+                    changes.insert(key.clone(), Change::Removed);
+                }
+            }
+        }
+        for &key in new.keys() {
+            if !old.contains_key(key) {
+                changes.insert(key.clone(), Change::Added);
+            }
+        }
+        changes
+    }
+
+    /// Compares two optional values, with `f` comparing them if both are present.
+    fn option<V, D, F>(&mut self, old: Option<&V>, new: Option<&V>, f: F) -> Option<Change<D>>
+    where
+        D: IsEmpty,
+        F: FnOnce(&mut Self, &V, &V) -> D,
+    {
+        match (old, new) {
+            (Some(old), Some(new)) => non_empty(f(self, old, new)).map(Change::Changed),
+            (Some(_), None) => Some(Change::Removed),
+            (None, Some(_)) => Some(Change::Added),
+            (None, None) => None,
+        }
+    }
+
+    fn path_item<'i>(&mut self, old: &'i PathItem, new: &'i PathItem) -> PathItemDiff {
+        let operations = |item: &'i PathItem| -> BTreeMap<String, &'i Operation> {
+            item.operations()
+                .map(|(method, operation)| (method.as_str().to_owned(), operation))
+                .collect()
+        };
+        let operations = self.map(&operations(old), &operations(new), |differ, o, n| {
+            differ.operation((&old.parameters, o), (&new.parameters, n))
+        });
+        PathItemDiff { operations }
+    }
+
+    /// Compares operations, each with the parameters of its path item.
+    fn operation<'o>(
+        &mut self,
+        (old_shared, old): (&'o [ReferenceOr<Parameter>], &'o Operation),
+        (new_shared, new): (&'o [ReferenceOr<Parameter>], &'o Operation),
+    ) -> OperationDiff {
+        let old_parameters = parameters(self.old, old_shared, &old.parameters);
+        let new_parameters = parameters(self.new, new_shared, &new.parameters);
+        let parameters = self.map(&old_parameters, &new_parameters, |differ, o, n| {
+            differ.parameter(o, n)
+        });
+
+        let request_body = self.option(
+            old.request_body.as_ref(),
+            new.request_body.as_ref(),
+            Differ::request_body,
+        );
+
+        let responses = |responses: &'o Responses| -> BTreeMap<String, &'o ReferenceOr<Response>> {
+            let default = responses
+                .default
+                .iter()
+                .map(|response| ("default".to_owned(), response));
+            responses
+                .responses
+                .iter()
+                .map(|(status, response)| (status.to_string(), response))
+                .chain(default)
+                .collect()
+        };
+        let responses = self.map(
+            &responses(&old.responses),
+            &responses(&new.responses),
+            |differ, old, new| differ.response(old, new),
+        );
+
+        OperationDiff {
+            deprecated: value_change(old.deprecated, new.deprecated),
+            parameters,
+            request_body,
+            responses,
+        }
+    }
+
+    fn parameter(&mut self, old: &Parameter, new: &Parameter) -> ParameterDiff {
+        let (old_data, new_data) = (old.parameter_data_ref(), new.parameter_data_ref());
+        let schema = |format: &ParameterSchemaOrContent| match format {
+            ParameterSchemaOrContent::Schema(schema) => Some(schema.clone()),
+            ParameterSchemaOrContent::Content(content) => content
+                .values()
+                .next()
+                .and_then(|media| media.schema.clone()),
+        };
+        let schema = match (schema(&old_data.format), schema(&new_data.format)) {
+            (Some(old), Some(new)) => non_empty(self.schema(&old, &new)),
+            _ => None,
+        };
+        ParameterDiff {
+            location: value_change(old.location(), new.location()).map(|change| ValueChange {
+                old: change.old.to_owned(),
+                new: change.new.to_owned(),
+            }),
+            required: value_change(old_data.required, new_data.required),
+            schema,
+        }
+    }
+
+    fn request_body(
+        &mut self,
+        old: &ReferenceOr<RequestBody>,
+        new: &ReferenceOr<RequestBody>,
+    ) -> RequestBodyDiff {
+        let (old, new) = match (self.old.resolve(old), self.new.resolve(new)) {
+            (Ok(old), Ok(new)) => (old, new),
+            _ => return RequestBodyDiff::default(),
+        };
+        RequestBodyDiff {
+            required: value_change(old.required, new.required),
+            content: self.map(&old.content, &new.content, Differ::media_type),
+        }
+    }
+
+    fn response(
+        &mut self,
+        old: &ReferenceOr<Response>,
+        new: &ReferenceOr<Response>,
+    ) -> ResponseDiff {
+        let (old, new) = match (self.old.resolve(old), self.new.resolve(new)) {
+            (Ok(old), Ok(new)) => (old, new),
+            _ => return ResponseDiff::default(),
+        };
+        let items = |content: &BTreeMap<MediaTypeRange, ReferenceOr<MediaType>>| {
+            content
+                .iter()
+                .filter_map(|(name, media)| match media {
+                    ReferenceOr::Item(media) => Some((name.clone(), media.clone())),
+                    ReferenceOr::Reference { .. } => None,
+                })
+                .collect::<BTreeMap<_, _>>()
+        };
+        ResponseDiff {
+            content: self.map(
+                &items(&old.content),
+                &items(&new.content),
+                Differ::media_type,
+            ),
+        }
+    }
+
+    fn media_type(&mut self, old: &MediaType, new: &MediaType) -> MediaTypeDiff {
+        MediaTypeDiff {
+            schema: self.option(old.schema.as_ref(), new.schema.as_ref(), Differ::schema),
+        }
+    }
+
+    fn schema(&mut self, old: &ReferenceOr<Schema>, new: &ReferenceOr<Schema>) -> SchemaDiff {
+        let (old_components, new_components) = (self.old, self.new);
+        self.resolved(
+            (reference_of(old), old_components.resolve(old).ok()),
+            (reference_of(new), new_components.resolve(new).ok()),
+        )
+    }
+
+    fn boxed(
+        &mut self,
+        old: &ReferenceOr<Box<Schema>>,
+        new: &ReferenceOr<Box<Schema>>,
+    ) -> SchemaDiff {
+        let (old_components, new_components) = (self.old, self.new);
+        self.resolved(
+            (reference_of(old), old_components.resolve_boxed(old).ok()),
+            (reference_of(new), new_components.resolve_boxed(new).ok()),
+        )
+    }
+
+    /// Compares schemas given with the references they were reached by.
+    fn resolved(
+        &mut self,
+        (old_reference, old): (Option<String>, Option<&Schema>),
+        (new_reference, new): (Option<String>, Option<&Schema>),
+    ) -> SchemaDiff {
+        let references = (old_reference, new_reference);
+        let reference = if references.0.is_some() || references.1.is_some() {
+            value_change(references.0.clone(), references.1.clone())
+        } else {
+            None
+        };
+        let mut diff = match (old, new) {
+            // Schemas already being compared further out are not again.
+            (Some(old), Some(new)) if !self.expanding.contains(&references) => {
+                let expands = references.0.is_some() && references.1.is_some();
+                if expands {
+                    self.expanding.push(references);
+                }
+                let diff = self.schema_item(old, new);
+                if expands {
+                    self.expanding.pop();
+                }
+                diff
+            }
+            // Unresolved references are reported by `validate`.
+            _ => SchemaDiff::default(),
+        };
+        diff.reference = reference;
+        diff
+    }
+
+    fn schema_item(&mut self, old: &Schema, new: &Schema) -> SchemaDiff {
+        let (old_shape, new_shape) = (Shape::of(old), Shape::of(new));
+
+        let empty = Map::new();
+        let properties = self.map(
+            old_shape.properties.unwrap_or(&empty),
+            new_shape.properties.unwrap_or(&empty),
+            Differ::boxed,
+        );
+
+        let items = match (old_shape.items, new_shape.items) {
+            (Some(old), Some(new)) => non_empty(self.boxed(old, new)).map(Box::new),
+            _ => None,
+        };
+
+        let combinator = if new_shape.subschemas.is_empty() {
+            old_shape.kind
+        } else {
+            new_shape.kind
+        };
+        let subschemas = SubschemaDiff {
+            combinator: combinator.to_owned(),
+            ..self.subschemas(old_shape.subschemas, new_shape.subschemas)
+        };
+
+        let (old_keywords, new_keywords) = (keywords(old), keywords(new));
+        let mut keywords = BTreeMap::new();
+        for keyword in old_keywords.keys().chain(new_keywords.keys()) {
+            let (old, new) = (old_keywords.get(keyword), new_keywords.get(keyword));
+            if old != new {
+                keywords.insert(
+                    keyword.clone(),
+                    ValueChange {
+                        old: old.cloned(),
+                        new: new.cloned(),
+                    },
+                );
+            }
+        }
+
+        SchemaDiff {
+            reference: None,
+            kind: value_change(old_shape.kind, new_shape.kind).map(|change| ValueChange {
+                old: change.old.to_owned(),
+                new: change.new.to_owned(),
+            }),
+            properties,
+            required: SetChange::between(old_shape.required, new_shape.required),
+            enum_values: SetChange::between(&old_shape.enum_values, &new_shape.enum_values),
+            items,
+            subschemas,
+            keywords,
+        }
+    }
+
+    /// Matches the members of two `oneOf`, `allOf` or `anyOf` lists.
+    fn subschemas(
+        &mut self,
+        old: &[ReferenceOr<Schema>],
+        new: &[ReferenceOr<Schema>],
+    ) -> SubschemaDiff {
+        // The member of `old` matched to each of `new`.
+        let mut matches: Vec<Option<usize>> = vec![None; new.len()];
+        let unmatched = |matches: &[Option<usize>], j: usize| !matches.contains(&Some(j));
+
+        for (i, member) in new.iter().enumerate() {
+            if let Some(reference) = reference_of(member) {
+                matches[i] = (0..old.len()).find(|&j| {
+                    unmatched(&matches, j) && reference_of(&old[j]).as_ref() == Some(&reference)
+                });
+            }
+        }
+        for (i, member) in new.iter().enumerate() {
+            if matches[i].is_some() || reference_of(member).is_some() {
+                continue;
+            }
+            for (j, old_member) in old.iter().enumerate() {
+                if unmatched(&matches, j)
+                    && reference_of(old_member).is_none()
+                    && self.schema(old_member, member).is_empty()
+                {
+                    matches[i] = Some(j);
+                    break;
+                }
+            }
+        }
+        let mut left_over = (0..old.len())
+            .filter(|&j| unmatched(&matches, j))
+            .collect::<Vec<_>>()
+            .into_iter();
+        for m in matches.iter_mut().filter(|m| m.is_none()) {
+            *m = left_over.next();
+        }
+        let removed = left_over.collect();
+
+        let mut added = Vec::new();
+        let mut changed = BTreeMap::new();
+        for (i, m) in matches.into_iter().enumerate() {
+            match m {
+                Some(j) => {
+                    if let Some(diff) = non_empty(self.schema(&old[j], &new[i])) {
+                        changed.insert(i, diff);
+                    }
+                }
+                None => added.push(i),
+            }
+        }
+        SubschemaDiff {
+            combinator: String::new(),
+            added,
+            removed,
+            changed,
+        }
+    }
+}
+
+/// The parameters of an operation by name, including those of its path
+/// item that it does not override. Unresolved references are left out.
+pub(crate) fn parameters<'p>(
+    components: &'p Components,
+    shared: &'p [ReferenceOr<Parameter>],
+    own: &'p [ReferenceOr<Parameter>],
+) -> BTreeMap<String, &'p Parameter> {
+    let all = merge_parameters(components, shared, own);
+
+    let uses = |name: &str| {
+        all.iter()
+            .filter(|parameter| parameter.parameter_data_ref().name == name)
+            .count()
+    };
+    all.iter()
+        .map(|parameter| {
+            let name = &parameter.parameter_data_ref().name;
+            let key = if uses(name) > 1 {
+                format!("{} ({})", name, parameter.location())
+            } else {
+                name.clone()
+            };
+            (key, *parameter)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn diffs_documents() {
+        let old: OpenAPI = serde_yaml::from_str(
+            "
+openapi: 3.0.0
+info: {title: Pets, version: '1'}
+paths:
+  /pets:
+    parameters: [{name: limit, in: query, schema: {type: integer}}]
+    get:
+      responses:
+        '200':
+          description: pets
+          content:
+            application/json:
+              schema: {type: array, items: {$ref: '#/components/schemas/Pet'}}
+  /owners:
+    get: {responses: {default: {description: owners}}}
+components:
+  schemas:
+    Pet:
+      type: object
+      properties:
+        name: {type: string}
+        kind: {type: string, enum: [cat, dog]}
+        parent: {$ref: '#/components/schemas/Pet'}
+",
+        )
+        .unwrap();
+        let new: OpenAPI = serde_yaml::from_str(
+            "
+openapi: 3.0.0
+info: {title: Pets, version: '2'}
+paths:
+  /pets:
+    get:
+      parameters: [{name: limit, in: header, required: true, schema: {type: integer, maximum: 50}}]
+      responses:
+        '200':
+          description: pets
+          content:
+            application/json:
+              schema: {type: array, items: {$ref: '#/components/schemas/Pet'}}
+    post: {responses: {'201': {description: created}}}
+components:
+  schemas:
+    Pet:
+      type: object
+      required: [name]
+      properties:
+        name: {type: string, description: documentation is not compared}
+        kind: {type: string, enum: [cat, bird]}
+        parent: {$ref: '#/components/schemas/Pet'}
+        age: {type: integer}
+",
+        )
+        .unwrap();
+
+        let pet = json!({
+            "required": {"added": ["name"]},
+            "properties": {
+                "age": {"change": "added"},
+                "kind": {
+                    "change": "changed",
+                    "enum_values": {"added": ["bird"], "removed": ["dog"]}
+                }
+            }
+        });
+        assert_eq!(
+            serde_json::to_value(diff(&old, &new)).unwrap(),
+            json!({
+                "version": {"old": "1", "new": "2"},
+                "paths": {
+                    "/owners": {"change": "removed"},
+                    "/pets": {
+                        "change": "changed",
+                        "operations": {
+                            "get": {
+                                "change": "changed",
+                                "parameters": {
+                                    "limit": {
+                                        "change": "changed",
+                                        "location": {"old": "query", "new": "header"},
+                                        "required": {"old": false, "new": true},
+                                        "schema": {
+                                            "keywords": {"maximum": {"old": null, "new": 50}}
+                                        }
+                                    }
+                                },
+                                "responses": {
+                                    "200": {
+                                        "change": "changed",
+                                        "content": {
+                                            "application/json": {
+                                                "change": "changed",
+                                                "schema": {
+                                                    "change": "changed",
+                                                    "items": pet.clone()
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            },
+                            "post": {"change": "added"}
+                        }
+                    }
+                },
+                "schemas": {
+                    "Pet": {
+                        "change": "changed",
+                        "required": pet["required"],
+                        "properties": pet["properties"]
+                    }
+                }
+            })
+        );
+
+        assert!(diff(&new, &new).is_empty());
+    }
+
+    #[test]
+    fn matches_subschemas() {
+        let schema = |schema: &str| -> OpenAPI {
+            serde_yaml::from_str(&format!(
+                "
+openapi: 3.0.0
+info: {{title: Pets, version: '1'}}
+paths: {{}}
+components:
+  schemas:
+    A: {{type: string}}
+    B: {{type: integer}}
+    Pet: {}
+",
+                schema
+            ))
+            .unwrap()
+        };
+        let old = schema(
+            "
+      oneOf:
+        - {$ref: '#/components/schemas/A'}
+        - {type: boolean}
+        - {type: object, required: [name]}
+",
+        );
+        let new = schema(
+            "
+      oneOf:
+        - {type: boolean, description: documentation is not compared}
+        - {type: object, required: [name, age]}
+        - {$ref: '#/components/schemas/A'}
+        - {$ref: '#/components/schemas/B'}
+",
+        );
+
+        assert_eq!(
+            serde_json::to_value(diff(&old, &new).schemas).unwrap(),
+            json!({
+                "Pet": {
+                    "change": "changed",
+                    "subschemas": {
+                        "combinator": "oneOf",
+                        "added": [3],
+                        "changed": {"1": {"required": {"added": ["age"]}}}
+                    }
+                }
+            })
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use crate::*;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
@@ -8,7 +9,7 @@ use std::collections::BTreeMap;
 /// on the value associated with it.
 ///
 /// When using the discriminator, inline schemas will not be considered.
-#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct Discriminator {
     /// REQUIRED. The name of the property in the payload that
@@ -18,3 +19,230 @@ pub struct Discriminator {
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub mapping: BTreeMap<String, String>,
 }
+
+impl Discriminator {
+    /// The reference to the schema that a value of the property selects:
+    /// the one `mapping` gives for it, or else the schema of that name in
+    /// the components.
+    ///
+    /// ```
+    /// use openapiv3::Discriminator;
+    ///
+    /// let discriminator: Discriminator =
+    ///     serde_yaml::from_str("{propertyName: kind, mapping: {dog: Hound, cat: 'other.yaml#/Cat'}}")
+    ///         .unwrap();
+    /// assert_eq!(discriminator.reference_for("dog"), "#/components/schemas/Hound");
+    /// assert_eq!(discriminator.reference_for("cat"), "other.yaml#/Cat");
+    /// assert_eq!(discriminator.reference_for("Cow"), "#/components/schemas/Cow");
+    /// ```
+    pub fn reference_for(&self, value: &str) -> String {
+        match self.mapping.get(value) {
+            Some(target) => target_reference(target),
+            None => Reference::component(ComponentKind::Schemas, value).to_string(),
+        }
+    }
+
+    /// The schema that a value of the property selects; see
+    /// [`reference_for`](Discriminator::reference_for).
+    pub fn resolve<'a>(
+        &self,
+        value: &str,
+        components: &'a Components,
+    ) -> Result<&'a Schema, ResolveError> {
+        components.resolve_reference(&self.reference_for(value))
+    }
+
+    /// The values of the property that select the schema of a reference:
+    /// those `mapping` gives for it, and the name of the schema unless
+    /// `mapping` gives that value to another.
+    pub fn values_for(&self, reference: &str) -> Vec<String> {
+        let mut values: Vec<String> = self
+            .mapping
+            .iter()
+            .filter(|(_, target)| target_reference(target) == reference)
+            .map(|(value, _)| value.clone())
+            .collect();
+        let name = reference.parse::<Reference>().ok().and_then(|parsed| {
+            match (parsed.is_external(), parsed.component_kind(), parsed.name()) {
+                (false, Some(ComponentKind::Schemas), Some(name)) => Some(name.into_owned()),
+                _ => None,
+            }
+        });
+        if let Some(name) = name {
+            if !self.mapping.contains_key(&name) {
+                values.push(name);
+            }
+        }
+        values
+    }
+}
+
+/// A target of a discriminator `mapping` as a reference: as it is if it is
+/// one, or else as the schema it names.
+pub(crate) fn target_reference(target: &str) -> String {
+    if target.contains('/') || target.contains('#') {
+        target.to_owned()
+    } else {
+        Reference::component(ComponentKind::Schemas, target).to_string()
+    }
+}
+
+/// One of the schemas a discriminator chooses between.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiscriminatorVariant<'a> {
+    /// The reference to the schema, as written in the `oneOf` or `anyOf`.
+    pub reference: &'a str,
+    pub schema: &'a Schema,
+    /// The values of the discriminator property that select the schema;
+    /// see [`Discriminator::values_for`].
+    pub values: Vec<String>,
+    /// The name of the discriminator property.
+    pub property_name: &'a str,
+}
+
+impl DiscriminatorVariant<'_> {
+    /// Whether the schema declares the discriminator property, in its own
+    /// `properties` or in those of its `allOf`. A schema whose `allOf`
+    /// cannot be merged with [`Schema::flatten_all_of`] is taken to declare
+    /// it.
+    pub fn declares_property(&self, components: &Components) -> bool {
+        match self.schema.flatten_all_of(components) {
+            Ok(schema) => match &schema.schema_kind {
+                SchemaKind::Type(Type::Object(object)) => {
+                    object.properties.contains_key(self.property_name)
+                }
+                SchemaKind::Any(any) => any.properties.contains_key(self.property_name),
+                _ => false,
+            },
+            Err(_) => true,
+        }
+    }
+}
+
+impl Schema {
+    /// The schemas the discriminator of a `oneOf` or `anyOf` chooses
+    /// between, in order; none if the schema has no discriminator or is
+    /// neither. As the specification has it, inline schemas are not
+    /// considered.
+    ///
+    /// ```
+    /// use openapiv3::{Components, Schema};
+    ///
+    /// let components: Components = serde_yaml::from_str(
+    ///     "
+    /// schemas:
+    ///   Dog: {type: object, properties: {kind: {type: string}}}
+    ///   Cat: {type: object, properties: {kind: {type: string}}}
+    /// ",
+    /// )
+    /// .unwrap();
+    /// let pet: Schema = serde_yaml::from_str(
+    ///     "
+    /// oneOf: [{$ref: '#/components/schemas/Dog'}, {$ref: '#/components/schemas/Cat'}]
+    /// discriminator: {propertyName: kind, mapping: {dog: Dog}}
+    /// ",
+    /// )
+    /// .unwrap();
+    /// let variants = pet.discriminator_variants(&components).unwrap();
+    /// assert_eq!(variants[0].values, ["dog", "Dog"]);
+    /// assert_eq!(variants[1].values, ["Cat"]);
+    /// assert!(variants.iter().all(|variant| variant.declares_property(&components)));
+    /// ```
+    pub fn discriminator_variants<'a>(
+        &'a self,
+        components: &'a Components,
+    ) -> Result<Vec<DiscriminatorVariant<'a>>, ResolveError> {
+        let discriminator = match &self.schema_data.discriminator {
+            Some(discriminator) => discriminator,
+            None => return Ok(Vec::new()),
+        };
+        let branches = match &self.schema_kind {
+            SchemaKind::OneOf { one_of } => one_of,
+            SchemaKind::AnyOf { any_of } => any_of,
+            _ => return Ok(Vec::new()),
+        };
+        branches
+            .iter()
+            .filter_map(|branch| match branch {
+                ReferenceOr::Reference { reference } => Some(reference),
+                ReferenceOr::Item(_) => None,
+            })
+            .map(|reference| {
+                Ok(DiscriminatorVariant {
+                    reference,
+                    schema: components.resolve_reference(reference)?,
+                    values: discriminator.values_for(reference),
+                    property_name: &discriminator.property_name,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_variants() {
+        let components: Components = serde_yaml::from_str(
+            "
+schemas:
+  Base: {type: object, required: [kind], properties: {kind: {type: string}}}
+  Dog: {allOf: [{$ref: '#/components/schemas/Base'}, {properties: {bark: {type: boolean}}}]}
+  Cat: {type: object, properties: {meow: {type: boolean}}}
+",
+        )
+        .unwrap();
+        let pet: Schema = serde_yaml::from_str(
+            "
+anyOf:
+  - $ref: '#/components/schemas/Dog'
+  - $ref: '#/components/schemas/Cat'
+  - {type: string}
+discriminator:
+  propertyName: kind
+  mapping: {dog: Dog, hound: '#/components/schemas/Dog', Dog: Cat}
+",
+        )
+        .unwrap();
+        let discriminator = pet.schema_data.discriminator.as_ref().unwrap();
+        assert_eq!(
+            discriminator.resolve("hound", &components),
+            components.resolve_reference("#/components/schemas/Dog")
+        );
+        assert_eq!(
+            discriminator.resolve("Cow", &components),
+            Err(ResolveError::NotFound(
+                "#/components/schemas/Cow".to_owned()
+            ))
+        );
+
+        let variants = pet.discriminator_variants(&components).unwrap();
+        let summary: Vec<(&str, Vec<String>, bool)> = variants
+            .iter()
+            .map(|variant| {
+                (
+                    variant.reference,
+                    variant.values.clone(),
+                    variant.declares_property(&components),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "#/components/schemas/Dog",
+                    vec!["dog".to_owned(), "hound".to_owned()],
+                    true
+                ),
+                (
+                    "#/components/schemas/Cat",
+                    vec!["Dog".to_owned(), "Cat".to_owned()],
+                    false
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,879 @@
+use crate::v3_1::{self, SchemaObject, SchemaType, SchemaTypes};
+use crate::walk::Location;
+use crate::*;
+use serde::de::DeserializeOwned;
+use serde_json::{Number, Value};
+use std::fmt;
+use std::iter::FromIterator;
+
+/// Something of a 3.1 document, or of a JSON Schema, that could not be
+/// expressed in 3.0.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DowngradeIssue {
+    /// A JSON Pointer to the affected part of the 3.1 document.
+    pub location: String,
+    pub kind: DowngradeIssueKind,
+}
+
+impl fmt::Display for DowngradeIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.location, self.kind)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DowngradeIssueKind {
+    /// A field or keyword without a 3.0 counterpart was dropped.
+    Dropped(String),
+    /// The schema allows more than one type besides `null`, so its type
+    /// was dropped.
+    MultipleTypes,
+    /// The `false` schema, which accepts nothing, became the empty schema.
+    FalseSchema,
+    /// Values of `enum` or `const` that do not fit the schema's type were dropped.
+    EnumValues,
+    /// A schema with a `$ref` had other keywords, which were dropped.
+    ReferenceSiblings,
+    /// An operation without `responses`, which 3.0 requires, was given none.
+    MissingResponses,
+    /// A `$ref` that is not a valid reference was dropped.
+    InvalidReference(String),
+}
+
+impl fmt::Display for DowngradeIssueKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DowngradeIssueKind::Dropped(field) => write!(f, "`{}` is not supported in 3.0", field),
+            DowngradeIssueKind::MultipleTypes => {
+                f.write_str("schemas of more than one type are not supported in 3.0")
+            }
+            DowngradeIssueKind::FalseSchema => {
+                f.write_str("the `false` schema is not supported in 3.0")
+            }
+            DowngradeIssueKind::EnumValues => {
+                f.write_str("enumerated values that do not match the type were dropped")
+            }
+            DowngradeIssueKind::ReferenceSiblings => {
+                f.write_str("keywords alongside `$ref` are not supported in 3.0")
+            }
+            DowngradeIssueKind::MissingResponses => f.write_str("operation has no responses"),
+            DowngradeIssueKind::InvalidReference(reference) => {
+                write!(f, "invalid reference `{}`", reference)
+            }
+        }
+    }
+}
+
+impl v3_1::OpenAPI {
+    /// Converts the document to version 3.0 of the specification as far as
+    /// possible, reporting what could not be converted.
+    ///
+    /// Path items referenced from `components/pathItems` are inlined, and
+    /// `const` becomes a single-valued `enum`. Webhooks, and JSON Schema
+    /// keywords that 3.0 does not know of, such as `prefixItems`, are dropped.
+    pub fn downgrade_to_3_0(self) -> (OpenAPI, Vec<DowngradeIssue>) {
+        let v3_1::OpenAPI {
+            openapi: _,
+            info,
+            json_schema_dialect,
+            servers,
+            paths,
+            webhooks,
+            mut components,
+            security,
+            tags,
+            external_docs,
+            extensions,
+        } = self;
+        let mut downgrader = Downgrader {
+            at: Location::default(),
+            issues: Vec::new(),
+            path_items: Map::new(),
+        };
+        let d = &mut downgrader;
+
+        let info = d.nested("info", |d| d.info(info));
+        if json_schema_dialect.is_some() {
+            d.dropped("jsonSchemaDialect");
+        }
+        if !webhooks.is_empty() {
+            d.dropped("webhooks");
+        }
+        if let Some(components) = &mut components {
+            d.path_items = std::mem::take(&mut components.path_items);
+            if !d.path_items.is_empty() {
+                d.nested("components", |d| d.dropped("pathItems"));
+            }
+        }
+        let paths = d.nested("paths", |d| Paths {
+            paths: paths
+                .paths
+                .into_iter()
+                .map(|(path, item)| {
+                    let item = d.nested(&path, |d| d.path_item_ref(item));
+                    (path, item)
+                })
+                .collect(),
+            extensions: paths.extensions,
+        });
+        let components = components.map(|c| d.nested("components", |d| d.components(c)));
+
+        let api = OpenAPI {
+            openapi: "3.0.3".to_owned(),
+            info,
+            servers,
+            paths,
+            components,
+            security,
+            tags,
+            external_docs,
+            extensions,
+        };
+        (api, downgrader.issues)
+    }
+}
+
+/// Converts a 3.1 schema to a 3.0 one as far as possible, reporting what
+/// could not be converted at locations within the schema.
+pub(crate) fn downgrade_schema(schema: v3_1::Schema) -> (ReferenceOr<Schema>, Vec<DowngradeIssue>) {
+    let mut downgrader = Downgrader {
+        at: Location::default(),
+        issues: Vec::new(),
+        path_items: Map::new(),
+    };
+    let schema = downgrader.schema(schema);
+    (schema, downgrader.issues)
+}
+
+struct Downgrader {
+    at: Location,
+    issues: Vec<DowngradeIssue>,
+    /// The path items of the components, which 3.0 lacks, to inline.
+    path_items: Map<String, ReferenceOr<v3_1::PathItem>>,
+}
+
+impl Downgrader {
+    fn nested<R, F: FnOnce(&mut Self) -> R>(&mut self, segment: &str, f: F) -> R {
+        let mut at = std::mem::take(&mut self.at);
+        let result = at.with(segment, |at| {
+            std::mem::swap(&mut self.at, at);
+            let result = f(self);
+            std::mem::swap(&mut self.at, at);
+            result
+        });
+        self.at = at;
+        result
+    }
+
+    fn issue(&mut self, kind: DowngradeIssueKind) {
+        self.issues.push(DowngradeIssue {
+            location: self.at.as_str().to_owned(),
+            kind,
+        });
+    }
+
+    fn dropped(&mut self, field: &str) {
+        self.issue(DowngradeIssueKind::Dropped(field.to_owned()));
+    }
+
+    /// Converts the values of a map, each at its key.
+    fn map<K, T, U, M, F>(
+        &mut self,
+        segment: &str,
+        map: impl IntoIterator<Item = (K, T)>,
+        mut f: F,
+    ) -> M
+    where
+        K: AsRef<str>,
+        M: FromIterator<(K, U)>,
+        F: FnMut(&mut Self, T) -> U,
+    {
+        self.nested(segment, |d| {
+            map.into_iter()
+                .map(|(key, value)| {
+                    let value = d.nested(key.as_ref(), |d| f(d, value));
+                    (key, value)
+                })
+                .collect()
+        })
+    }
+
+    fn info(&mut self, info: v3_1::Info) -> Info {
+        if info.summary.is_some() {
+            self.dropped("summary");
+        }
+        let license = info.license.map(|license| {
+            if license.identifier.is_some() {
+                self.nested("license", |d| d.dropped("identifier"));
+            }
+            License {
+                name: license.name,
+                url: license.url,
+                extensions: license.extensions,
+            }
+        });
+        Info {
+            title: info.title,
+            description: info.description,
+            terms_of_service: info.terms_of_service,
+            contact: info.contact,
+            license,
+            version: info.version,
+            extensions: info.extensions,
+        }
+    }
+
+    fn components(&mut self, components: v3_1::Components) -> Components {
+        Components {
+            security_schemes: components.security_schemes,
+            responses: self.map("responses", components.responses, |d, r| {
+                r.map(|r| d.response(r))
+            }),
+            parameters: self.map("parameters", components.parameters, |d, p| {
+                p.map(|p| d.parameter(p))
+            }),
+            examples: components.examples,
+            request_bodies: self.map("requestBodies", components.request_bodies, |d, r| {
+                r.map(|r| d.request_body(r))
+            }),
+            headers: self.map("headers", components.headers, |d, h| h.map(|h| d.header(h))),
+            schemas: self.map("schemas", components.schemas, Downgrader::schema),
+            links: components.links,
+            callbacks: self.map("callbacks", components.callbacks, |d, c| {
+                c.map(|c| d.callback(c))
+            }),
+            extensions: components.extensions,
+        }
+    }
+
+    fn path_item_ref(&mut self, item: ReferenceOr<v3_1::PathItem>) -> ReferenceOr<PathItem> {
+        if let ReferenceOr::Reference { reference } = &item {
+            let name = reference
+                .as_str()
+                .strip_prefix("#/components/pathItems/")
+                .map(crate::reference::unescape_pointer_segment);
+            if let Some(ReferenceOr::Item(item)) = name.and_then(|name| self.path_items.get(&name))
+            {
+                return ReferenceOr::Item(self.path_item(item.clone()));
+            }
+        }
+        item.map(|item| self.path_item(item))
+    }
+
+    fn path_item(&mut self, item: v3_1::PathItem) -> PathItem {
+        if item.summary.is_some() {
+            self.dropped("summary");
+        }
+        if item.description.is_some() {
+            self.dropped("description");
+        }
+        let operation = |d: &mut Self, method: &str, operation: Option<v3_1::Operation>| {
+            operation.map(|operation| d.nested(method, |d| d.operation(operation)))
+        };
+        PathItem {
+            get: operation(self, "get", item.get),
+            put: operation(self, "put", item.put),
+            post: operation(self, "post", item.post),
+            delete: operation(self, "delete", item.delete),
+            options: operation(self, "options", item.options),
+            head: operation(self, "head", item.head),
+            patch: operation(self, "patch", item.patch),
+            trace: operation(self, "trace", item.trace),
+            servers: item.servers,
+            parameters: self.parameters(item.parameters),
+            extensions: item.extensions,
+        }
+    }
+
+    fn callback(&mut self, callback: v3_1::Callback) -> Callback {
+        Callback {
+            paths: callback
+                .paths
+                .into_iter()
+                .map(|(expression, item)| {
+                    let item = self.nested(&expression, |d| d.path_item(item));
+                    (expression, item)
+                })
+                .collect(),
+            extensions: callback.extensions,
+        }
+    }
+
+    fn operation(&mut self, operation: v3_1::Operation) -> Operation {
+        let responses = match operation.responses {
+            Some(responses) => self.nested("responses", |d| Responses {
+                default: responses
+                    .default
+                    .map(|r| d.nested("default", |d| r.map(|r| d.response(r)))),
+                responses: responses
+                    .responses
+                    .into_iter()
+                    .map(|(code, r)| {
+                        let r = d.nested(&code.to_string(), |d| r.map(|r| d.response(r)));
+                        (code, r)
+                    })
+                    .collect(),
+                extensions: responses.extensions,
+            }),
+            None => {
+                self.issue(DowngradeIssueKind::MissingResponses);
+                Responses::default()
+            }
+        };
+        Operation {
+            tags: operation.tags,
+            summary: operation.summary,
+            description: operation.description,
+            external_documentation: operation.external_documentation,
+            operation_id: operation.operation_id,
+            parameters: self.parameters(operation.parameters),
+            request_body: operation
+                .request_body
+                .map(|body| self.nested("requestBody", |d| body.map(|body| d.request_body(body)))),
+            responses,
+            callbacks: self.map("callbacks", operation.callbacks, |d, c| {
+                c.map(|c| d.callback(c))
+            }),
+            deprecated: operation.deprecated,
+            security: operation.security,
+            servers: operation.servers,
+            extensions: operation.extensions,
+        }
+    }
+
+    fn parameters(
+        &mut self,
+        parameters: Vec<ReferenceOr<v3_1::Parameter>>,
+    ) -> Vec<ReferenceOr<Parameter>> {
+        self.nested("parameters", |d| {
+            parameters
+                .into_iter()
+                .enumerate()
+                .map(|(i, p)| d.nested(&i.to_string(), |d| p.map(|p| d.parameter(p))))
+                .collect()
+        })
+    }
+
+    fn parameter(&mut self, parameter: v3_1::Parameter) -> Parameter {
+        match parameter {
+            v3_1::Parameter::Query {
+                parameter_data,
+                allow_reserved,
+                style,
+                allow_empty_value,
+            } => Parameter::Query {
+                parameter_data: self.data(parameter_data),
+                allow_reserved,
+                style,
+                allow_empty_value,
+            },
+            v3_1::Parameter::Header {
+                parameter_data,
+                style,
+            } => Parameter::Header {
+                parameter_data: self.data(parameter_data),
+                style,
+            },
+            v3_1::Parameter::Path {
+                parameter_data,
+                style,
+            } => Parameter::Path {
+                parameter_data: self.data(parameter_data),
+                style,
+            },
+            v3_1::Parameter::Cookie {
+                parameter_data,
+                style,
+            } => Parameter::Cookie {
+                parameter_data: self.data(parameter_data),
+                style,
+            },
+        }
+    }
+
+    fn data(&mut self, data: v3_1::ParameterData) -> ParameterData {
+        ParameterData {
+            name: data.name,
+            description: data.description,
+            required: data.required,
+            deprecated: data.deprecated,
+            explode: data.explode,
+            format: self.format(data.format),
+            example: data.example,
+            examples: data.examples,
+            extensions: data.extensions,
+        }
+    }
+
+    fn format(&mut self, format: v3_1::ParameterSchemaOrContent) -> ParameterSchemaOrContent {
+        match format {
+            v3_1::ParameterSchemaOrContent::Schema(schema) => {
+                ParameterSchemaOrContent::Schema(self.nested("schema", |d| d.schema(schema)))
+            }
+            v3_1::ParameterSchemaOrContent::Content(content) => ParameterSchemaOrContent::Content(
+                self.map("content", content, Downgrader::media_type),
+            ),
+        }
+    }
+
+    fn header(&mut self, header: v3_1::Header) -> Header {
+        Header {
+            description: header.description,
+            style: header.style,
+            explode: header.explode,
+            required: header.required,
+            deprecated: header.deprecated,
+            format: self.format(header.format),
+            example: header.example,
+            examples: header.examples,
+            extensions: header.extensions,
+        }
+    }
+
+    fn request_body(&mut self, body: v3_1::RequestBody) -> RequestBody {
+        RequestBody {
+            description: body.description,
+            content: self.map("content", body.content, Downgrader::media_type),
+            required: body.required,
+            extensions: body.extensions,
+        }
+    }
+
+    fn response(&mut self, response: v3_1::Response) -> Response {
+        Response {
+            description: response.description,
+            headers: self.map("headers", response.headers, |d, h| h.map(|h| d.header(h))),
+            content: self.map("content", response.content, |d, m| {
+                m.map(|m| d.media_type(m))
+            }),
+            links: response.links,
+            extensions: response.extensions,
+        }
+    }
+
+    fn media_type(&mut self, media_type: v3_1::MediaType) -> MediaType {
+        MediaType {
+            schema: media_type
+                .schema
+                .map(|schema| self.nested("schema", |d| d.schema(schema))),
+            example: media_type.example,
+            examples: media_type.examples,
+            encoding: self.map("encoding", media_type.encoding, |d, encoding| Encoding {
+                content_type: encoding.content_type,
+                headers: d.map("headers", encoding.headers, |d, h| h.map(|h| d.header(h))),
+                style: encoding.style,
+                explode: Some(encoding.explode).filter(|&explode| explode),
+                allow_reserved: encoding.allow_reserved,
+                extensions: encoding.extensions,
+            }),
+            extensions: media_type.extensions,
+        }
+    }
+
+    fn boxed(&mut self, schema: v3_1::Schema) -> ReferenceOr<Box<Schema>> {
+        match self.schema(schema) {
+            ReferenceOr::Reference { reference } => ReferenceOr::Reference { reference },
+            ReferenceOr::Item(schema) => ReferenceOr::boxed_item(schema),
+        }
+    }
+
+    fn schemas(&mut self, segment: &str, schemas: Vec<v3_1::Schema>) -> Vec<ReferenceOr<Schema>> {
+        self.nested(segment, |d| {
+            schemas
+                .into_iter()
+                .enumerate()
+                .map(|(i, schema)| d.nested(&i.to_string(), |d| d.schema(schema)))
+                .collect()
+        })
+    }
+
+    fn schema(&mut self, schema: v3_1::Schema) -> ReferenceOr<Schema> {
+        let mut object = match schema {
+            v3_1::Schema::Bool(accepts) => {
+                if !accepts {
+                    self.issue(DowngradeIssueKind::FalseSchema);
+                }
+                return ReferenceOr::Item(any_schema(AnySchema::default()));
+            }
+            v3_1::Schema::Object(object) => *object,
+        };
+
+        if let Some(reference) = object.reference.take() {
+            if object != SchemaObject::default() {
+                self.issue(DowngradeIssueKind::ReferenceSiblings);
+            }
+            return match reference.parse() {
+                Ok(reference) => ReferenceOr::Reference { reference },
+                Err(_) => {
+                    self.issue(DowngradeIssueKind::InvalidReference(reference));
+                    ReferenceOr::Item(any_schema(AnySchema::default()))
+                }
+            };
+        }
+        self.unsupported_keywords(&mut object);
+
+        let mut nullable = false;
+        let ty = match object.schema_type.take() {
+            None => None,
+            Some(SchemaTypes::Single(SchemaType::Null)) => {
+                nullable = true;
+                None
+            }
+            Some(SchemaTypes::Single(ty)) => Some(ty),
+            Some(SchemaTypes::Multiple(types)) => {
+                nullable = types.contains(&SchemaType::Null);
+                let mut types = types.into_iter().filter(|&ty| ty != SchemaType::Null);
+                match (types.next(), types.next()) {
+                    (ty, None) => ty,
+                    (Some(_), Some(_)) => {
+                        self.issue(DowngradeIssueKind::MultipleTypes);
+                        None
+                    }
+                    (None, Some(_)) => unreachable!(),
+                }
+            }
+        };
+
+        if let Some(value) = object.const_.take() {
+            if object.enumeration.is_some() {
+                self.dropped("const");
+            } else {
+                object.enumeration = Some(vec![value]);
+            }
+        }
+        let mut examples = std::mem::take(&mut object.examples).into_iter();
+        let example = object.example.take().or_else(|| examples.next());
+        if examples.next().is_some() {
+            self.dropped("examples");
+        }
+
+        let xml = object
+            .other
+            .remove("xml")
+            .and_then(|xml| serde_json::from_value(xml).ok());
+        let (extensions, other): (Vec<_>, Vec<_>) = std::mem::take(&mut object.other)
+            .into_iter()
+            .partition(|(keyword, _)| keyword.starts_with("x-"));
+        object.other = other.into_iter().collect();
+        let schema_data = SchemaData {
+            nullable,
+            read_only: object.read_only,
+            write_only: object.write_only,
+            deprecated: object.deprecated,
+            external_docs: object.external_docs.take(),
+            example,
+            title: object.title.take(),
+            description: object.description.take(),
+            discriminator: object.discriminator.take(),
+            default: object.default.take(),
+            xml,
+            extensions: extensions.into_iter().collect(),
+        };
+
+        let all_of = self.schemas("allOf", std::mem::take(&mut object.all_of));
+        let one_of = self.schemas("oneOf", std::mem::take(&mut object.one_of));
+        let any_of = self.schemas("anyOf", std::mem::take(&mut object.any_of));
+        let base = self.schema_kind(ty, object);
+
+        // 3.0 schemas hold a single kind, so a type alongside subschemas, or
+        // several kinds of subschemas, are combined with `allOf`.
+        let mut parts = Vec::new();
+        if base != SchemaKind::Any(AnySchema::default()) {
+            parts.push(base);
+        }
+        if !one_of.is_empty() {
+            parts.push(SchemaKind::OneOf { one_of });
+        }
+        if !any_of.is_empty() {
+            parts.push(SchemaKind::AnyOf { any_of });
+        }
+        let schema_kind = match (parts.len(), all_of.is_empty()) {
+            (0, true) => SchemaKind::Any(AnySchema::default()),
+            (0, false) => SchemaKind::AllOf { all_of },
+            (1, true) => parts.remove(0),
+            _ => {
+                let mut all_of = all_of;
+                all_of.extend(parts.into_iter().map(|schema_kind| {
+                    ReferenceOr::Item(Schema {
+                        schema_data: SchemaData::default(),
+                        schema_kind,
+                    })
+                }));
+                SchemaKind::AllOf { all_of }
+            }
+        };
+        ReferenceOr::Item(Schema {
+            schema_data,
+            schema_kind,
+        })
+    }
+
+    /// Reports, and drops, the keywords 3.0 schemas cannot hold.
+    fn unsupported_keywords(&mut self, object: &mut SchemaObject) {
+        let mut dropped = |keyword: &str, present: bool| {
+            if present {
+                self.dropped(keyword);
+            }
+        };
+        dropped("$schema", object.schema.is_some());
+        dropped("$id", object.id.is_some());
+        dropped("$anchor", object.anchor.is_some());
+        dropped("$dynamicRef", object.dynamic_ref.is_some());
+        dropped("$dynamicAnchor", object.dynamic_anchor.is_some());
+        dropped("$defs", !object.defs.is_empty());
+        dropped("$comment", object.comment.is_some());
+        dropped("not", object.not.is_some());
+        dropped("if", object.if_.is_some());
+        dropped("then", object.then.is_some());
+        dropped("else", object.else_.is_some());
+        dropped("dependentSchemas", !object.dependent_schemas.is_empty());
+        dropped("prefixItems", !object.prefix_items.is_empty());
+        dropped("contains", object.contains.is_some());
+        dropped("patternProperties", !object.pattern_properties.is_empty());
+        dropped("propertyNames", object.property_names.is_some());
+        dropped("unevaluatedItems", object.unevaluated_items.is_some());
+        dropped(
+            "unevaluatedProperties",
+            object.unevaluated_properties.is_some(),
+        );
+        dropped("maxContains", object.max_contains.is_some());
+        dropped("minContains", object.min_contains.is_some());
+        dropped("dependentRequired", !object.dependent_required.is_empty());
+        dropped("contentEncoding", object.content_encoding.is_some());
+        dropped("contentMediaType", object.content_media_type.is_some());
+        dropped("contentSchema", object.content_schema.is_some());
+        for keyword in std::mem::take(&mut object.other).keys() {
+            self.dropped(keyword);
+        }
+    }
+
+    fn schema_kind(&mut self, ty: Option<SchemaType>, object: SchemaObject) -> SchemaKind {
+        let enumeration = object.enumeration.unwrap_or_default();
+        let values = |d: &mut Self, f: fn(&Value) -> bool| {
+            if !enumeration.iter().all(|v| v.is_null() || f(v)) {
+                d.issue(DowngradeIssueKind::EnumValues);
+            }
+            enumeration
+                .iter()
+                .filter(|v| f(v))
+                .cloned()
+                .collect::<Vec<_>>()
+        };
+        let (minimum, exclusive_minimum) =
+            bound(object.minimum, object.exclusive_minimum, |a, b| a > b);
+        let (maximum, exclusive_maximum) =
+            bound(object.maximum, object.exclusive_maximum, |a, b| a < b);
+
+        match ty {
+            Some(SchemaType::String) => SchemaKind::Type(Type::String(StringType {
+                format: format(object.format),
+                pattern: object.pattern,
+                enumeration: values(self, Value::is_string)
+                    .into_iter()
+                    .filter_map(|v| v.as_str().map(str::to_owned))
+                    .collect(),
+                min_length: object.min_length,
+                max_length: object.max_length,
+            })),
+            Some(SchemaType::Number) => SchemaKind::Type(Type::Number(NumberType {
+                format: format(object.format),
+                multiple_of: object.multiple_of.as_ref().and_then(Number::as_f64),
+                exclusive_minimum,
+                exclusive_maximum,
+                minimum: minimum.as_ref().and_then(Number::as_f64),
+                maximum: maximum.as_ref().and_then(Number::as_f64),
+                enumeration: values(self, Value::is_number)
+                    .iter()
+                    .filter_map(Value::as_f64)
+                    .collect(),
+            })),
+            Some(SchemaType::Integer) => {
+                let integer = |d: &mut Self, keyword: &str, n: Option<Number>| {
+                    n.and_then(|n| {
+                        let whole = n.as_f64().filter(|n| n.fract() == 0.0);
+                        match n.as_i64().or_else(|| whole.map(|n| n as i64)) {
+                            Some(n) => Some(n),
+                            None => {
+                                d.dropped(keyword);
+                                None
+                            }
+                        }
+                    })
+                };
+                SchemaKind::Type(Type::Integer(IntegerType {
+                    format: format(object.format),
+                    multiple_of: integer(self, "multipleOf", object.multiple_of),
+                    exclusive_minimum,
+                    exclusive_maximum,
+                    minimum: integer(self, "minimum", minimum),
+                    maximum: integer(self, "maximum", maximum),
+                    enumeration: values(self, |v| v.is_i64())
+                        .iter()
+                        .filter_map(Value::as_i64)
+                        .collect(),
+                }))
+            }
+            Some(SchemaType::Object) => SchemaKind::Type(Type::Object(ObjectType {
+                properties: self.map("properties", object.properties, Downgrader::boxed),
+                required: object.required,
+                additional_properties: object
+                    .additional_properties
+                    .map(|schema| self.nested("additionalProperties", |d| d.additional(schema))),
+                min_properties: object.min_properties,
+                max_properties: object.max_properties,
+            })),
+            Some(SchemaType::Array) => SchemaKind::Type(Type::Array(ArrayType {
+                items: match object.items {
+                    Some(items) => self.nested("items", |d| d.boxed(items)),
+                    None => ReferenceOr::boxed_item(any_schema(AnySchema::default())),
+                },
+                min_items: object.min_items,
+                max_items: object.max_items,
+                unique_items: object.unique_items,
+            })),
+            Some(SchemaType::Boolean) => SchemaKind::Type(Type::Boolean {}),
+            Some(SchemaType::Null) | None => {
+                if !enumeration.is_empty() {
+                    self.dropped("enum");
+                }
+                SchemaKind::Any(AnySchema {
+                    pattern: object.pattern,
+                    multiple_of: object.multiple_of.as_ref().and_then(Number::as_f64),
+                    exclusive_minimum: Some(exclusive_minimum).filter(|&e| e),
+                    exclusive_maximum: Some(exclusive_maximum).filter(|&e| e),
+                    minimum: minimum.as_ref().and_then(Number::as_f64),
+                    maximum: maximum.as_ref().and_then(Number::as_f64),
+                    properties: self.map("properties", object.properties, Downgrader::boxed),
+                    required: object.required,
+                    additional_properties: object.additional_properties.map(|schema| {
+                        self.nested("additionalProperties", |d| d.additional(schema))
+                    }),
+                    min_propeties: object.min_properties,
+                    max_properties: object.max_properties,
+                    items: object
+                        .items
+                        .map(|items| self.nested("items", |d| d.boxed(items))),
+                    min_items: object.min_items,
+                    max_items: object.max_items,
+                    unique_items: Some(object.unique_items).filter(|&u| u),
+                    format: object.format,
+                    min_length: object.min_length,
+                    max_length: object.max_length,
+                })
+            }
+        }
+    }
+
+    fn additional(&mut self, schema: v3_1::Schema) -> AdditionalProperties {
+        match schema {
+            v3_1::Schema::Bool(allowed) => allowed.into(),
+            schema => AdditionalProperties::Schema(Box::new(self.schema(schema))),
+        }
+    }
+}
+
+/// Parses a format as a 3.0 document would be.
+fn format<T: DeserializeOwned>(format: Option<String>) -> VariantOrUnknownOrEmpty<T> {
+    format
+        .and_then(|format| serde_json::from_value(Value::String(format)).ok())
+        .unwrap_or_default()
+}
+
+fn any_schema(any: AnySchema) -> Schema {
+    Schema {
+        schema_data: SchemaData::default(),
+        schema_kind: SchemaKind::Any(any),
+    }
+}
+
+/// The 3.0 form of a bound, given the inclusive and exclusive 3.1 ones
+/// and whether one bound is stricter than another.
+fn bound(
+    inclusive: Option<Number>,
+    exclusive: Option<Number>,
+    stricter: fn(f64, f64) -> bool,
+) -> (Option<Number>, bool) {
+    let value = |n: &Number| n.as_f64().unwrap_or_default();
+    match (inclusive, exclusive) {
+        (Some(inclusive), Some(exclusive)) if stricter(value(&inclusive), value(&exclusive)) => {
+            (Some(inclusive), false)
+        }
+        (_, Some(exclusive)) => (Some(exclusive), true),
+        (inclusive, None) => (inclusive, false),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use serde_json::json;
+
+    #[test]
+    fn downgrades_documents() {
+        let api: v3_1::OpenAPI = serde_yaml::from_str(
+            "
+openapi: 3.1.0
+info: {title: t, version: '1', license: {name: MIT, identifier: MIT}}
+paths:
+  /ping: {$ref: '#/components/pathItems/Ping'}
+webhooks:
+  newPet: {post: {responses: {'200': {description: ok}}}}
+components:
+  pathItems:
+    Ping: {get: {responses: {'204': {description: pong}}}}
+  schemas:
+    Pet:
+      type: object
+      properties:
+        id: {type: integer, exclusiveMinimum: 0}
+        name: {type: [string, 'null'], examples: [Rex]}
+        kind: {const: pet, type: string}
+        owner: {$ref: '#/components/schemas/Owner', description: the owner}
+        position: {type: array, prefixItems: [{type: number}], items: false}
+    Owner: {type: object, allOf: [{$ref: '#/components/schemas/Pet'}], required: [pets]}
+",
+        )
+        .unwrap();
+
+        let (downgraded, issues) = api.downgrade_to_3_0();
+        let issues: Vec<String> = issues.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            issues,
+            vec![
+                "/info/license: `identifier` is not supported in 3.0",
+                ": `webhooks` is not supported in 3.0",
+                "/components: `pathItems` is not supported in 3.0",
+                "/components/schemas/Pet/properties/owner: \
+                 keywords alongside `$ref` are not supported in 3.0",
+                "/components/schemas/Pet/properties/position: \
+                 `prefixItems` is not supported in 3.0",
+                "/components/schemas/Pet/properties/position/items: \
+                 the `false` schema is not supported in 3.0",
+            ]
+        );
+
+        assert!(matches!(downgraded.paths["/ping"], ReferenceOr::Item(_)));
+        let schemas = serde_json::to_value(&downgraded.components.unwrap().schemas).unwrap();
+        assert_eq!(
+            schemas,
+            json!({
+                "Owner": {
+                    "allOf": [
+                        {"$ref": "#/components/schemas/Pet"},
+                        {"type": "object", "required": ["pets"]},
+                    ]
+                },
+                "Pet": {
+                    "type": "object",
+                    "properties": {
+                        "id": {"type": "integer", "exclusiveMinimum": true, "minimum": 0},
+                        "kind": {"type": "string", "enum": ["pet"]},
+                        "name": {"type": "string", "nullable": true, "example": "Rex"},
+                        "owner": {"$ref": "#/components/schemas/Owner"},
+                        "position": {"type": "array", "items": {}},
+                    },
+                },
+            })
+        );
+    }
+}
@@ -0,0 +1,312 @@
+//! Construction of whole documents from nested closures, checked when built.
+//!
+//! ```
+//! use openapiv3::dsl::OpenApiBuilder;
+//! use openapiv3::ReferenceOr;
+//!
+//! let api = OpenApiBuilder::new("Pets", "1.0")
+//!     .path("/pets/{id}", |p| {
+//!         p.path_parameter("id", ReferenceOr::ref_("#/components/schemas/Id"))
+//!             .get(|op| {
+//!                 let pet = ReferenceOr::ref_("#/components/schemas/Pet");
+//!                 op.operation_id("showPet")
+//!                     .response(200, "a pet", |r| r.json(pet))
+//!             })
+//!     })
+//!     .build();
+//! // The schemas referenced have not been added.
+//! assert_eq!(api.unwrap_err().len(), 2);
+//! ```
+//!
+//! Unlike the builders of the individual objects, such as
+//! [`Operation::builder`], [`OpenApiBuilder::build`] refuses documents
+//! that [`OpenAPI::validate`] finds fault with, such as an operation
+//! without responses.
+
+use crate::*;
+
+/// Builds an [`OpenAPI`] document path by path.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OpenApiBuilder {
+    api: OpenAPI,
+}
+
+impl OpenApiBuilder {
+    /// Starts a 3.0 document with the given title and API version.
+    pub fn new(title: impl Into<String>, version: impl Into<String>) -> Self {
+        OpenApiBuilder {
+            api: OpenAPI::builder(title, version).build(),
+        }
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.api.info.description = Some(description.into());
+        self
+    }
+
+    /// Adds a server with the given URL.
+    pub fn server(mut self, url: impl Into<String>) -> Self {
+        self.api.servers.push(Server {
+            url: url.into(),
+            ..Server::default()
+        });
+        self
+    }
+
+    /// Declares a tag in the root `tags`.
+    pub fn tag(mut self, name: impl Into<Name>, description: impl Into<String>) -> Self {
+        self.api.tags.push(Tag {
+            name: name.into(),
+            description: Some(description.into()),
+            ..Tag::default()
+        });
+        self
+    }
+
+    /// Adds a schema to the components.
+    pub fn schema(
+        mut self,
+        name: impl Into<String>,
+        schema: impl Into<ReferenceOr<Schema>>,
+    ) -> Self {
+        self.components().schemas.insert(name.into(), schema.into());
+        self
+    }
+
+    /// Adds a security scheme to the components.
+    pub fn security_scheme(
+        mut self,
+        name: impl Into<String>,
+        scheme: impl Into<ReferenceOr<SecurityScheme>>,
+    ) -> Self {
+        self.components()
+            .security_schemes
+            .insert(name.into(), scheme.into());
+        self
+    }
+
+    /// Describes the item of `path`, adding to what earlier calls for the
+    /// same path described.
+    pub fn path<F>(mut self, path: impl Into<String>, f: F) -> Self
+    where
+        F: FnOnce(PathScope) -> PathScope,
+    {
+        let entry = self
+            .api
+            .paths
+            .entry(path.into())
+            .or_insert_with(|| ReferenceOr::Item(PathItem::default()));
+        let item = match std::mem::replace(entry, ReferenceOr::Item(PathItem::default())) {
+            ReferenceOr::Item(item) => item,
+            ReferenceOr::Reference { .. } => PathItem::default(),
+        };
+        *entry = ReferenceOr::Item(f(PathScope { item }).item);
+        self
+    }
+
+    /// The document, or every problem [`OpenAPI::validate`] finds with it.
+    pub fn build(self) -> Result<OpenAPI, Vec<ValidationError>> {
+        let errors = self.api.validate();
+        if errors.is_empty() {
+            Ok(self.api)
+        } else {
+            Err(errors)
+        }
+    }
+
+    fn components(&mut self) -> &mut Components {
+        self.api.components.get_or_insert_with(Components::default)
+    }
+}
+
+/// The operations and shared parameters of a path; see [`OpenApiBuilder::path`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PathScope {
+    item: PathItem,
+}
+
+macro_rules! operation_scopes {
+    ($($method:ident),*) => {
+        $(pub fn $method<F>(mut self, f: F) -> Self
+        where
+            F: FnOnce(OperationScope) -> OperationScope,
+        {
+            let operation = self.item.$method.take().unwrap_or_default();
+            self.item.$method = Some(f(OperationScope { operation }).operation);
+            self
+        })*
+    };
+}
+
+impl PathScope {
+    operation_scopes!(get, put, post, delete, options, head, patch, trace);
+
+    /// Adds a parameter shared by every operation of the path.
+    pub fn parameter(mut self, parameter: impl Into<ReferenceOr<Parameter>>) -> Self {
+        self.item.parameters.push(parameter.into());
+        self
+    }
+
+    /// Adds a required path parameter of the given schema, shared by every
+    /// operation of the path.
+    pub fn path_parameter(
+        self,
+        name: impl Into<String>,
+        schema: impl Into<ReferenceOr<Schema>>,
+    ) -> Self {
+        self.parameter(Parameter::path(name).schema(schema).build())
+    }
+}
+
+/// An operation of a path; see [`PathScope`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OperationScope {
+    operation: Operation,
+}
+
+impl OperationScope {
+    pub fn operation_id(mut self, operation_id: impl Into<String>) -> Self {
+        self.operation.operation_id = Some(operation_id.into());
+        self
+    }
+
+    pub fn summary(mut self, summary: impl Into<String>) -> Self {
+        self.operation.summary = Some(summary.into());
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.operation.description = Some(description.into());
+        self
+    }
+
+    pub fn tag(mut self, tag: impl Into<Name>) -> Self {
+        self.operation.tags.push(tag.into());
+        self
+    }
+
+    pub fn deprecated(mut self) -> Self {
+        self.operation.deprecated = true;
+        self
+    }
+
+    pub fn parameter(mut self, parameter: impl Into<ReferenceOr<Parameter>>) -> Self {
+        self.operation.parameters.push(parameter.into());
+        self
+    }
+
+    /// Adds an optional query parameter of the given schema.
+    pub fn query(self, name: impl Into<String>, schema: impl Into<ReferenceOr<Schema>>) -> Self {
+        self.parameter(Parameter::query(name).schema(schema).build())
+    }
+
+    pub fn request_body(mut self, body: impl Into<ReferenceOr<RequestBody>>) -> Self {
+        self.operation.request_body = Some(body.into());
+        self
+    }
+
+    /// Sets a required `application/json` request body of the given schema.
+    pub fn json_body(self, schema: impl Into<ReferenceOr<Schema>>) -> Self {
+        self.request_body(RequestBody::builder().json(schema).required(true).build())
+    }
+
+    /// Adds the response for an HTTP status code.
+    pub fn response<F>(mut self, status: u16, description: impl Into<String>, f: F) -> Self
+    where
+        F: FnOnce(ResponseBuilder) -> ResponseBuilder,
+    {
+        let response = f(Response::builder(description)).build();
+        self.operation
+            .responses
+            .responses
+            .insert(StatusCode::Code(status), ReferenceOr::Item(response));
+        self
+    }
+
+    /// Sets the response for the status codes without one of their own.
+    pub fn default_response<F>(mut self, description: impl Into<String>, f: F) -> Self
+    where
+        F: FnOnce(ResponseBuilder) -> ResponseBuilder,
+    {
+        let response = f(Response::builder(description)).build();
+        self.operation.responses.default = Some(ReferenceOr::Item(response));
+        self
+    }
+
+    pub fn security(mut self, requirement: SecurityRequirement) -> Self {
+        self.operation
+            .security
+            .get_or_insert_with(Vec::new)
+            .push(requirement);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schema(yaml: &str) -> Schema {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn builds_valid_documents() {
+        let api = OpenApiBuilder::new("Pets", "1.0")
+            .tag("pets", "Everything about pets")
+            .schema("Pet", schema("type: object"))
+            .path("/pets", |p| {
+                p.get(|op| {
+                    op.operation_id("listPets")
+                        .tag("pets")
+                        .query("limit", schema("type: integer"))
+                        .response(200, "the pets", |r| {
+                            r.json(schema("type: array\nitems: {}"))
+                        })
+                })
+            })
+            .path("/pets", |p| {
+                p.post(|op| {
+                    op.operation_id("createPet")
+                        .json_body(ReferenceOr::ref_("#/components/schemas/Pet"))
+                        .response(201, "created", |r| r)
+                })
+            })
+            .build()
+            .unwrap();
+
+        let pets = match &api.paths["/pets"] {
+            ReferenceOr::Item(item) => item,
+            ReferenceOr::Reference { .. } => panic!("expected a path item"),
+        };
+        assert_eq!(
+            pets.get.as_ref().unwrap().operation_id.as_deref(),
+            Some("listPets")
+        );
+        assert!(pets.post.as_ref().unwrap().request_body.is_some());
+    }
+
+    #[test]
+    fn rejects_invalid_documents() {
+        let errors: Vec<String> = OpenApiBuilder::new("Pets", "1.0")
+            .path("/owners", |p| {
+                p.get(|op| op.operation_id("showPet").default_response("owners", |r| r))
+            })
+            .path("/pets/{id}", |p| p.get(|op| op.operation_id("showPet")))
+            .build()
+            .unwrap_err()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            errors,
+            vec![
+                "/paths/~1pets~1{id}/get: \
+                 path template parameter `id` is not declared as a path parameter",
+                "/paths/~1pets~1{id}/get/operationId: \
+                 operationId `showPet` is already used at /paths/~1owners/get",
+                "/paths/~1pets~1{id}/get/responses: operation has no responses",
+            ]
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use crate::*;
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
@@ -14,7 +15,8 @@ pub struct Encoding {
     /// for array – the default is defined based on the inner type.
     /// The value can be a specific media type (e.g. application/json),
     /// a wildcard media type (e.g. image/*), or a comma-separated list of the two types.
-    pub content_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
     /// A map allowing additional information to be provided as headers,
     /// for example Content-Disposition. Content-Type is described separately
     /// and SHALL be ignored in this section. This property SHALL be ignored
@@ -35,14 +37,255 @@ pub struct Encoding {
     /// For all other styles, the default value is false. This property
     /// SHALL be ignored if the request body media type is
     /// not application/x-www-form-urlencoded.
-    ///
-    /// In this Library this value defaults to false always despite the specification.
-    #[serde(default, skip_serializing_if = "is_false")]
-    pub explode: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explode: Option<bool>,
     /// Determines whether the parameter value SHOULD allow reserved characters,
     /// as defined by RFC3986 :/?#[]@!$&'()*+,;= to be included without percent-encoding.
     /// The default value is false. This property SHALL be ignored if the request
     /// body media type is not application/x-www-form-urlencoded.
     #[serde(default, skip_serializing_if = "is_false")]
     pub allow_reserved: bool,
+    /// Inline extensions to this object.
+    #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
+    pub extensions: IndexMap<String, serde_json::Value>,
+}
+
+/// How a property of a `multipart/form-data` or
+/// `application/x-www-form-urlencoded` body is encoded, with the defaults
+/// of the specification applied; see [`MediaType::property_encodings`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct PropertyEncoding<'a> {
+    /// The content type of the part of a multipart body, or of the value
+    /// of a form.
+    pub content_type: String,
+    /// The headers of the part of a multipart body, but for
+    /// `Content-Type`, which is described by `content_type`. Forms have no
+    /// headers.
+    pub headers: BTreeMap<&'a str, &'a ReferenceOr<Header>>,
+    /// How the value is serialized in a form, as a query parameter would
+    /// be.
+    pub style: ParameterStyle,
+    pub explode: bool,
+    pub allow_reserved: bool,
+}
+
+impl Encoding {
+    /// The style of the property in a form: as declared, else `form`.
+    pub fn effective_style(&self) -> ParameterStyle {
+        match self.style {
+            None | Some(QueryStyle::Form) => ParameterStyle::Form,
+            Some(QueryStyle::SpaceDelimited) => ParameterStyle::SpaceDelimited,
+            Some(QueryStyle::PipeDelimited) => ParameterStyle::PipeDelimited,
+            Some(QueryStyle::DeepObject) => ParameterStyle::DeepObject,
+        }
+    }
+
+    /// Whether array and object values of the property are exploded in a
+    /// form: as declared, else true for the `form` and `deepObject` styles
+    /// and false for every other, as for query parameters.
+    pub fn effective_explode(&self) -> bool {
+        self.explode.unwrap_or_else(|| {
+            matches!(
+                self.effective_style(),
+                ParameterStyle::Form | ParameterStyle::DeepObject
+            )
+        })
+    }
+}
+
+/// The content type of a property of the schema, if the encoding of the
+/// property does not give one: `application/octet-stream` for binary
+/// strings, `text/plain` for other primitive values, `application/json`
+/// for objects and that of the items of arrays. Schemas of no type, and
+/// references that do not resolve, are taken to be binary.
+fn default_content_type(components: &Components, schema: &Schema) -> String {
+    let content_type = match &schema.schema_kind {
+        SchemaKind::Type(Type::String(string)) => match &string.format {
+            VariantOrUnknownOrEmpty::Item(StringFormat::Binary) => "application/octet-stream",
+            _ => "text/plain",
+        },
+        SchemaKind::Type(Type::Number(_))
+        | SchemaKind::Type(Type::Integer(_))
+        | SchemaKind::Type(Type::Boolean {}) => "text/plain",
+        SchemaKind::Type(Type::Array(array)) => {
+            return match components.resolve_boxed(&array.items) {
+                Ok(items) => default_content_type(components, items),
+                Err(_) => "application/octet-stream".to_owned(),
+            }
+        }
+        SchemaKind::Type(Type::Object(_))
+        | SchemaKind::OneOf { .. }
+        | SchemaKind::AllOf { .. }
+        | SchemaKind::AnyOf { .. } => "application/json",
+        SchemaKind::Any(_) => "application/octet-stream",
+    };
+    content_type.to_owned()
+}
+
+impl MediaType {
+    /// The encoding of each property of a body of the media type, which is
+    /// `content_type`, with what the `encoding` map leaves out taken from
+    /// the defaults of the specification. Returns `None` unless the media
+    /// type is `application/x-www-form-urlencoded` or a `multipart` one.
+    ///
+    /// Properties are those of the schema, resolving references in
+    /// `components` and merging `allOf`, along with any others the
+    /// `encoding` map names.
+    ///
+    /// ```
+    /// use openapiv3::{Components, MediaType, ParameterStyle};
+    ///
+    /// let media_type: MediaType = serde_yaml::from_str(
+    ///     "
+    /// schema:
+    ///   type: object
+    ///   properties:
+    ///     id: {type: integer}
+    ///     photo: {type: string, format: binary}
+    ///     tags: {type: array, items: {type: string}}
+    /// encoding:
+    ///   tags: {style: spaceDelimited}
+    /// ",
+    /// )
+    /// .unwrap();
+    /// let components = Components::default();
+    /// let form = media_type
+    ///     .property_encodings("multipart/form-data", &components)
+    ///     .unwrap();
+    /// assert_eq!(form["id"].content_type, "text/plain");
+    /// assert_eq!(form["photo"].content_type, "application/octet-stream");
+    /// assert_eq!(form["tags"].style, ParameterStyle::SpaceDelimited);
+    /// assert!(!form["tags"].explode);
+    /// assert!(media_type
+    ///     .property_encodings("application/json", &components)
+    ///     .is_none());
+    /// ```
+    pub fn property_encodings<'a>(
+        &'a self,
+        content_type: &str,
+        components: &Components,
+    ) -> Option<BTreeMap<String, PropertyEncoding<'a>>> {
+        let range = MediaTypeRange::parse(content_type).ok()?;
+        let multipart = range.type_().eq_ignore_ascii_case("multipart");
+        let form = range.type_().eq_ignore_ascii_case("application")
+            && range
+                .subtype()
+                .eq_ignore_ascii_case("x-www-form-urlencoded");
+        if !multipart && !form {
+            return None;
+        }
+
+        let schema = self
+            .schema
+            .as_ref()
+            .and_then(|schema| components.resolve(schema).ok())
+            .and_then(|schema| schema.flatten_all_of(components).ok());
+        let properties = match schema.as_ref().map(|schema| &schema.schema_kind) {
+            Some(SchemaKind::Type(Type::Object(object))) => Some(&object.properties),
+            Some(SchemaKind::Any(any)) => Some(&any.properties),
+            _ => None,
+        };
+        let mut encodings = BTreeMap::new();
+        let names = properties
+            .into_iter()
+            .flat_map(|properties| properties.keys().map(Name::as_str))
+            .chain(self.encoding.keys().map(String::as_str));
+        for name in names {
+            if encodings.contains_key(name) {
+                continue;
+            }
+            let default = Encoding::default();
+            let encoding = self.encoding.get(name).unwrap_or(&default);
+            let property = properties
+                .and_then(|properties| properties.get(name))
+                .and_then(|property| components.resolve_boxed(property).ok());
+            let content_type = match (&encoding.content_type, property) {
+                (Some(content_type), _) => content_type.clone(),
+                (None, Some(property)) => default_content_type(components, property),
+                (None, None) => "application/octet-stream".to_owned(),
+            };
+            let headers = match self.encoding.get(name) {
+                Some(encoding) if multipart => encoding
+                    .headers
+                    .iter()
+                    .filter(|(name, _)| !name.eq_ignore_ascii_case("content-type"))
+                    .map(|(name, header)| (name.as_str(), header))
+                    .collect(),
+                _ => BTreeMap::new(),
+            };
+            encodings.insert(
+                name.to_owned(),
+                PropertyEncoding {
+                    content_type,
+                    headers,
+                    style: encoding.effective_style(),
+                    explode: encoding.effective_explode(),
+                    allow_reserved: encoding.allow_reserved,
+                },
+            );
+        }
+        Some(encodings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn applies_encoding_defaults() {
+        let components: Components = serde_yaml::from_str(
+            "
+schemas:
+  Upload:
+    allOf:
+      - type: object
+        properties:
+          meta: {type: object}
+          files: {type: array, items: {type: string, format: binary}}
+      - properties:
+          flag: {type: boolean}
+",
+        )
+        .unwrap();
+        let media_type: MediaType = serde_yaml::from_str(
+            "
+schema: {$ref: '#/components/schemas/Upload'}
+encoding:
+  files:
+    headers:
+      Content-Type: {schema: {type: string}}
+      X-Rate-Limit: {schema: {type: integer}}
+  meta: {contentType: application/xml, explode: false}
+  extra: {style: deepObject}
+",
+        )
+        .unwrap();
+
+        let multipart = media_type
+            .property_encodings("Multipart/Mixed; boundary=x", &components)
+            .unwrap();
+        let content_types: Vec<(&str, &str)> = multipart
+            .iter()
+            .map(|(name, encoding)| (name.as_str(), encoding.content_type.as_str()))
+            .collect();
+        assert_eq!(
+            content_types,
+            [
+                ("extra", "application/octet-stream"),
+                ("files", "application/octet-stream"),
+                ("flag", "text/plain"),
+                ("meta", "application/xml"),
+            ]
+        );
+        let headers: Vec<&str> = multipart["files"].headers.keys().copied().collect();
+        assert_eq!(headers, ["X-Rate-Limit"]);
+
+        let form = media_type
+            .property_encodings("application/x-www-form-urlencoded", &components)
+            .unwrap();
+        assert!(form["files"].headers.is_empty());
+        assert_eq!(form["extra"].style, ParameterStyle::DeepObject);
+        assert!(form["extra"].explode && form["flag"].explode && !form["meta"].explode);
+    }
 }
@@ -0,0 +1,253 @@
+use crate::*;
+
+impl Schema {
+    /// Whether the schemas are the same but for how they are written,
+    /// resolving references in `components`.
+    ///
+    /// References are compared by the schemas they resolve to, so that two
+    /// components that are copies of each other are equal, as are a
+    /// reference and the schema it resolves to. An `allOf` of a single
+    /// branch and nothing else is compared as that branch. `required` lists
+    /// are compared as sets, and properties regardless of their order.
+    /// Everything else, annotations such as `description` included, must be
+    /// equal. References that do not resolve are equal only to themselves,
+    /// and schemas that recurse are equal if they recurse alike.
+    ///
+    /// ```
+    /// use openapiv3::{Components, Schema};
+    ///
+    /// let components: Components = serde_yaml::from_str(
+    ///     "
+    /// schemas:
+    ///   Name: {type: string}
+    ///   Label: {type: string}
+    /// ",
+    /// )
+    /// .unwrap();
+    /// let a: Schema = serde_yaml::from_str(
+    ///     "
+    /// type: object
+    /// required: [name, label]
+    /// properties:
+    ///   name: {$ref: '#/components/schemas/Name'}
+    ///   label: {allOf: [{$ref: '#/components/schemas/Label'}]}
+    /// ",
+    /// )
+    /// .unwrap();
+    /// let b: Schema = serde_yaml::from_str(
+    ///     "
+    /// type: object
+    /// required: [label, name]
+    /// properties:
+    ///   label: {type: string}
+    ///   name: {$ref: '#/components/schemas/Label'}
+    /// ",
+    /// )
+    /// .unwrap();
+    /// assert!(a.structurally_equal(&b, &components));
+    /// ```
+    pub fn structurally_equal(&self, other: &Schema, components: &Components) -> bool {
+        Comparer {
+            components,
+            assumed: Vec::new(),
+        }
+        .schema(self, other)
+    }
+}
+
+struct Comparer<'a> {
+    components: &'a Components,
+    /// The pairs of schemas being compared through references, assumed
+    /// equal should they be compared again.
+    assumed: Vec<(*const Schema, *const Schema)>,
+}
+
+impl<'a> Comparer<'a> {
+    /// The schema a reference resolves to, or `None` if it does not.
+    fn resolve<T: std::borrow::Borrow<Schema>>(
+        &self,
+        schema: &'a ReferenceOr<T>,
+    ) -> Option<&'a Schema> {
+        match schema {
+            ReferenceOr::Item(schema) => Some(schema.borrow()),
+            ReferenceOr::Reference { reference } => {
+                self.components.resolve_reference(reference.as_str()).ok()
+            }
+        }
+    }
+
+    /// The schema an `allOf` of a single branch and nothing else wraps,
+    /// through any number of such wrappers.
+    fn unwrap(&self, mut schema: &'a Schema) -> &'a Schema {
+        while let SchemaKind::AllOf { all_of } = &schema.schema_kind {
+            match (&all_of[..], schema.schema_data == SchemaData::default()) {
+                ([branch], true) => match self.resolve(branch) {
+                    Some(branch) => schema = branch,
+                    None => break,
+                },
+                _ => break,
+            }
+        }
+        schema
+    }
+
+    fn reference<T: std::borrow::Borrow<Schema>>(
+        &mut self,
+        a: &'a ReferenceOr<T>,
+        b: &'a ReferenceOr<T>,
+    ) -> bool {
+        match (self.resolve(a), self.resolve(b)) {
+            (Some(a), Some(b)) => self.schema(a, b),
+            _ => match (a, b) {
+                (
+                    ReferenceOr::Reference { reference: a },
+                    ReferenceOr::Reference { reference: b },
+                ) => a == b,
+                _ => false,
+            },
+        }
+    }
+
+    fn schema(&mut self, a: &'a Schema, b: &'a Schema) -> bool {
+        let (a, b) = (self.unwrap(a), self.unwrap(b));
+        let pair = (a as *const Schema, b as *const Schema);
+        if pair.0 == pair.1 || self.assumed.contains(&pair) {
+            return true;
+        }
+        self.assumed.push(pair);
+        let equal = a.schema_data == b.schema_data && self.kind(&a.schema_kind, &b.schema_kind);
+        self.assumed.pop();
+        equal
+    }
+
+    fn kind(&mut self, a: &'a SchemaKind, b: &'a SchemaKind) -> bool {
+        match (a, b) {
+            (SchemaKind::Type(Type::Object(a)), SchemaKind::Type(Type::Object(b))) => {
+                (a.min_properties, a.max_properties) == (b.min_properties, b.max_properties)
+                    && same_set(&a.required, &b.required)
+                    && self.properties(&a.properties, &b.properties)
+                    && self.additional(&a.additional_properties, &b.additional_properties)
+            }
+            (SchemaKind::Type(Type::Array(a)), SchemaKind::Type(Type::Array(b))) => {
+                (a.min_items, a.max_items, a.unique_items)
+                    == (b.min_items, b.max_items, b.unique_items)
+                    && self.reference(&a.items, &b.items)
+            }
+            (SchemaKind::OneOf { one_of: a }, SchemaKind::OneOf { one_of: b })
+            | (SchemaKind::AllOf { all_of: a }, SchemaKind::AllOf { all_of: b })
+            | (SchemaKind::AnyOf { any_of: a }, SchemaKind::AnyOf { any_of: b }) => {
+                a.len() == b.len() && a.iter().zip(b).all(|(a, b)| self.reference(a, b))
+            }
+            (SchemaKind::Any(a), SchemaKind::Any(b)) => {
+                let scalars = |any: &AnySchema| {
+                    (
+                        any.pattern.clone(),
+                        any.multiple_of,
+                        any.exclusive_minimum,
+                        any.exclusive_maximum,
+                        any.minimum,
+                        any.maximum,
+                        any.format.clone(),
+                        (any.min_propeties, any.max_properties),
+                        (any.min_items, any.max_items, any.unique_items),
+                        (any.min_length, any.max_length),
+                    )
+                };
+                scalars(a) == scalars(b)
+                    && same_set(&a.required, &b.required)
+                    && self.properties(&a.properties, &b.properties)
+                    && self.additional(&a.additional_properties, &b.additional_properties)
+                    && match (&a.items, &b.items) {
+                        (Some(a), Some(b)) => self.reference(a, b),
+                        (a, b) => a.is_none() && b.is_none(),
+                    }
+            }
+            (a, b) => a == b,
+        }
+    }
+
+    fn properties(
+        &mut self,
+        a: &'a Map<Name, ReferenceOr<Box<Schema>>>,
+        b: &'a Map<Name, ReferenceOr<Box<Schema>>>,
+    ) -> bool {
+        a.len() == b.len()
+            && a.iter().all(|(name, a)| match b.get(name) {
+                Some(b) => self.reference(a, b),
+                None => false,
+            })
+    }
+
+    fn additional(
+        &mut self,
+        a: &'a Option<AdditionalProperties>,
+        b: &'a Option<AdditionalProperties>,
+    ) -> bool {
+        match (a, b) {
+            (Some(AdditionalProperties::Schema(a)), Some(AdditionalProperties::Schema(b))) => {
+                self.reference(a.as_ref(), b.as_ref())
+            }
+            (a, b) => a == b,
+        }
+    }
+}
+
+fn same_set(a: &[Name], b: &[Name]) -> bool {
+    a.iter().all(|name| b.contains(name)) && b.iter().all(|name| a.contains(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn compares_structurally() {
+        let components: Components = serde_yaml::from_str(
+            "
+schemas:
+  List:
+    type: object
+    properties:
+      next: {$ref: '#/components/schemas/List'}
+  Chain:
+    type: object
+    properties:
+      next: {allOf: [{$ref: '#/components/schemas/Chain'}]}
+  Described: {type: string, description: a name}
+  Missing: {$ref: '#/components/schemas/Nowhere'}
+",
+        )
+        .unwrap();
+        let schema = |source: &str| -> Schema { serde_yaml::from_str(source).unwrap() };
+        let equal = |a: &str, b: &str| schema(a).structurally_equal(&schema(b), &components);
+
+        assert!(equal(
+            "{allOf: [{$ref: '#/components/schemas/List'}]}",
+            "{allOf: [{$ref: '#/components/schemas/Chain'}]}"
+        ));
+        assert!(equal(
+            "{type: array, items: {$ref: '#/components/schemas/Nowhere'}}",
+            "{type: array, items: {$ref: '#/components/schemas/Nowhere'}}"
+        ));
+        assert!(!equal(
+            "{type: array, items: {$ref: '#/components/schemas/Nowhere'}}",
+            "{type: array, items: {$ref: '#/components/schemas/Elsewhere'}}"
+        ));
+        assert!(!equal(
+            "{allOf: [{$ref: '#/components/schemas/Described'}]}",
+            "{type: string}"
+        ));
+        assert!(!equal(
+            "{description: wrapped, allOf: [{type: string}]}",
+            "{type: string}"
+        ));
+        assert!(!equal(
+            "{oneOf: [{type: string}, {type: integer}]}",
+            "{oneOf: [{type: integer}, {type: string}]}"
+        ));
+        assert!(equal(
+            "{required: [a, b], properties: {a: {}, b: {}}}",
+            "{required: [b, a], properties: {b: {}, a: {}}}"
+        ));
+    }
+}
@@ -1,6 +1,7 @@
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
 pub struct Example {
     /// Short description for the example.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -13,7 +14,15 @@ pub struct Example {
     /// field are mutually exclusive. To represent examples of
     /// media types that cannot naturally represented in JSON or YAML,
     /// use a string value to contain the example, escaping where necessary.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<serde_json::Value>,
+    /// A URL that points to the literal example. This provides the
+    /// capability to reference examples that cannot easily be included
+    /// in JSON or YAML documents.
     #[serde(rename = "externalValue")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub external_value: Option<String>,
+    /// Inline extensions to this object.
+    #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
+    pub extensions: IndexMap<String, serde_json::Value>,
 }
@@ -0,0 +1,381 @@
+use crate::value::ValueChecker;
+use crate::walk::Location;
+use crate::*;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+
+/// An example that does not conform to the schema it illustrates.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExampleError {
+    /// A JSON Pointer to the example within the document.
+    pub location: String,
+    pub violation: SchemaViolation,
+}
+
+impl fmt::Display for ExampleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.location, self.violation)
+    }
+}
+
+impl Error for ExampleError {}
+
+impl OpenAPI {
+    /// Checks every `example` and `examples` value of media types, parameters,
+    /// headers and schemas against the schema it belongs to.
+    ///
+    /// Components are checked where they are declared rather than where
+    /// they are referenced. Examples given by `externalValue` are not
+    /// checked, and neither are those whose schema is missing.
+    pub fn validate_examples(&self) -> Vec<ExampleError> {
+        let paths: Vec<_> = self.paths.iter().collect();
+        let mut errors = self.path_examples(&paths);
+        errors.extend(self.component_examples());
+        errors
+    }
+
+    /// Like [`OpenAPI::validate_examples`], checking the path items on
+    /// several threads.
+    #[cfg(feature = "parallel")]
+    pub fn par_validate_examples(&self) -> Vec<ExampleError> {
+        let paths: Vec<_> = self.paths.iter().collect();
+        let mut errors: Vec<ExampleError> =
+            crate::parallel::map_chunks(&paths, |paths| self.path_examples(paths))
+                .into_iter()
+                .flatten()
+                .collect();
+        errors.extend(self.component_examples());
+        errors
+    }
+
+    /// Checks the examples of the given path items.
+    fn path_examples(&self, paths: &[(&String, &ReferenceOr<PathItem>)]) -> Vec<ExampleError> {
+        let empty = Components::default();
+        let mut checker = ExampleChecker::new(self.components.as_ref().unwrap_or(&empty));
+        checker.nested("paths", |checker| {
+            for &(path, item) in paths {
+                if let ReferenceOr::Item(item) = item {
+                    checker.nested(path, |checker| checker.path_item(item));
+                }
+            }
+        });
+        checker.errors
+    }
+
+    /// Checks the examples of the components.
+    fn component_examples(&self) -> Vec<ExampleError> {
+        let empty = Components::default();
+        let mut checker = ExampleChecker::new(self.components.as_ref().unwrap_or(&empty));
+        if let Some(components) = &self.components {
+            checker.nested("components", |checker| {
+                checker.each("schemas", &components.schemas, ExampleChecker::schema);
+                checker.each("responses", &components.responses, ExampleChecker::response);
+                checker.each(
+                    "parameters",
+                    &components.parameters,
+                    ExampleChecker::parameter,
+                );
+                checker.each(
+                    "requestBodies",
+                    &components.request_bodies,
+                    ExampleChecker::request_body,
+                );
+                checker.each("headers", &components.headers, ExampleChecker::header);
+                checker.each("callbacks", &components.callbacks, ExampleChecker::callback);
+            });
+        }
+        checker.errors
+    }
+}
+
+struct ExampleChecker<'a> {
+    components: &'a Components,
+    at: Location,
+    errors: Vec<ExampleError>,
+}
+
+impl<'a> ExampleChecker<'a> {
+    fn new(components: &'a Components) -> Self {
+        ExampleChecker {
+            components,
+            at: Location::default(),
+            errors: Vec::new(),
+        }
+    }
+
+    fn nested<F: FnOnce(&mut Self)>(&mut self, segment: &str, f: F) {
+        let mut at = std::mem::take(&mut self.at);
+        at.with(segment, |at| {
+            std::mem::swap(&mut self.at, at);
+            f(self);
+            std::mem::swap(&mut self.at, at);
+        });
+        self.at = at;
+    }
+
+    /// Checks the items, but not the references, of a map under `segment`.
+    fn each<'m, K, T: 'm, F>(
+        &mut self,
+        segment: &str,
+        map: impl IntoIterator<Item = (&'m K, &'m ReferenceOr<T>)>,
+        mut f: F,
+    ) where
+        K: AsRef<str> + 'm,
+        F: FnMut(&mut Self, &T),
+    {
+        self.nested(segment, |checker| {
+            for (name, value) in map {
+                if let ReferenceOr::Item(item) = value {
+                    checker.nested(name.as_ref(), |checker| f(checker, item));
+                }
+            }
+        });
+    }
+
+    fn check(&mut self, schema: &ReferenceOr<Schema>, value: &Value) {
+        for violation in ValueChecker::check(self.components, schema, value) {
+            self.errors.push(ExampleError {
+                location: self.at.as_str().to_owned(),
+                violation,
+            });
+        }
+    }
+
+    /// Checks `example` and the values of `examples` against `schema`.
+    fn examples(
+        &mut self,
+        schema: &ReferenceOr<Schema>,
+        example: &Option<Value>,
+        examples: &BTreeMap<String, ReferenceOr<Example>>,
+    ) {
+        if let Some(example) = example {
+            self.nested("example", |checker| checker.check(schema, example));
+        }
+        self.nested("examples", |checker| {
+            for (name, example) in examples {
+                let value = match checker.components.resolve(example) {
+                    Ok(Example {
+                        value: Some(value), ..
+                    }) => value,
+                    // Unresolved references are reported by `validate`.
+                    _ => continue,
+                };
+                checker.nested(name, |checker| {
+                    checker.nested("value", |checker| checker.check(schema, value))
+                });
+            }
+        });
+    }
+
+    fn path_item(&mut self, item: &PathItem) {
+        self.each_parameter(&item.parameters);
+        for (method, operation) in item.operations() {
+            self.nested(method.as_str(), |checker| {
+                checker.each_parameter(&operation.parameters);
+                if let Some(ReferenceOr::Item(body)) = &operation.request_body {
+                    checker.nested("requestBody", |checker| checker.request_body(body));
+                }
+                checker.nested("responses", |checker| {
+                    if let Some(ReferenceOr::Item(response)) = &operation.responses.default {
+                        checker.nested("default", |checker| checker.response(response));
+                    }
+                    for (code, response) in &operation.responses.responses {
+                        if let ReferenceOr::Item(response) = response {
+                            checker.nested(&code.to_string(), |checker| checker.response(response));
+                        }
+                    }
+                });
+                checker.each("callbacks", &operation.callbacks, ExampleChecker::callback);
+            });
+        }
+    }
+
+    fn callback(&mut self, callback: &Callback) {
+        for (expression, item) in callback {
+            self.nested(expression, |checker| checker.path_item(item));
+        }
+    }
+
+    fn each_parameter(&mut self, parameters: &[ReferenceOr<Parameter>]) {
+        self.nested("parameters", |checker| {
+            for (i, parameter) in parameters.iter().enumerate() {
+                if let ReferenceOr::Item(parameter) = parameter {
+                    checker.nested(&i.to_string(), |checker| checker.parameter(parameter));
+                }
+            }
+        });
+    }
+
+    fn parameter(&mut self, parameter: &Parameter) {
+        let data = parameter.parameter_data_ref();
+        self.format(&data.format, &data.example, &data.examples);
+    }
+
+    fn header(&mut self, header: &Header) {
+        self.format(&header.format, &header.example, &header.examples);
+    }
+
+    fn format(
+        &mut self,
+        format: &ParameterSchemaOrContent,
+        example: &Option<Value>,
+        examples: &BTreeMap<String, ReferenceOr<Example>>,
+    ) {
+        match format {
+            ParameterSchemaOrContent::Schema(schema) => {
+                self.examples(schema, example, examples);
+                if let ReferenceOr::Item(schema) = schema {
+                    self.nested("schema", |checker| checker.schema(schema));
+                }
+            }
+            ParameterSchemaOrContent::Content(content) => self.nested("content", |checker| {
+                for (name, media_type) in content {
+                    checker.nested(name.as_str(), |checker| checker.media_type(media_type));
+                }
+            }),
+        }
+    }
+
+    fn request_body(&mut self, body: &RequestBody) {
+        self.nested("content", |checker| {
+            for (name, media_type) in &body.content {
+                checker.nested(name.as_str(), |checker| checker.media_type(media_type));
+            }
+        });
+    }
+
+    fn response(&mut self, response: &Response) {
+        self.each("headers", &response.headers, ExampleChecker::header);
+        self.each("content", &response.content, ExampleChecker::media_type);
+    }
+
+    fn media_type(&mut self, media_type: &MediaType) {
+        if let Some(schema) = &media_type.schema {
+            self.examples(schema, &media_type.example, &media_type.examples);
+            if let ReferenceOr::Item(schema) = schema {
+                self.nested("schema", |checker| checker.schema(schema));
+            }
+        }
+        self.nested("encoding", |checker| {
+            for (name, encoding) in &media_type.encoding {
+                checker.nested(name, |checker| {
+                    checker.each("headers", &encoding.headers, ExampleChecker::header)
+                });
+            }
+        });
+    }
+
+    /// Checks the example of a schema, and those of the schemas within it.
+    fn schema(&mut self, schema: &Schema) {
+        if let Some(example) = &schema.schema_data.example {
+            let schema = ReferenceOr::Item(schema.clone());
+            self.nested("example", |checker| checker.check(&schema, example));
+        }
+
+        let boxed = |checker: &mut Self, segment: &str, schema: &ReferenceOr<Box<Schema>>| {
+            if let ReferenceOr::Item(schema) = schema {
+                checker.nested(segment, |checker| checker.schema(schema));
+            }
+        };
+        let properties = |checker: &mut Self, properties: &Map<Name, ReferenceOr<Box<Schema>>>| {
+            checker.nested("properties", |checker| {
+                for (name, property) in properties {
+                    boxed(checker, name, property);
+                }
+            });
+        };
+        let additional = |checker: &mut Self, additional: &Option<AdditionalProperties>| {
+            if let Some(AdditionalProperties::Schema(schema)) = additional {
+                if let ReferenceOr::Item(schema) = &**schema {
+                    checker.nested("additionalProperties", |checker| checker.schema(schema));
+                }
+            }
+        };
+        let list = |checker: &mut Self, segment: &str, schemas: &[ReferenceOr<Schema>]| {
+            checker.nested(segment, |checker| {
+                for (i, schema) in schemas.iter().enumerate() {
+                    if let ReferenceOr::Item(schema) = schema {
+                        checker.nested(&i.to_string(), |checker| checker.schema(schema));
+                    }
+                }
+            });
+        };
+
+        match &schema.schema_kind {
+            SchemaKind::Type(Type::Object(object)) => {
+                properties(self, &object.properties);
+                additional(self, &object.additional_properties);
+            }
+            SchemaKind::Type(Type::Array(array)) => boxed(self, "items", &array.items),
+            SchemaKind::Type(_) => {}
+            SchemaKind::OneOf { one_of } => list(self, "oneOf", one_of),
+            SchemaKind::AllOf { all_of } => list(self, "allOf", all_of),
+            SchemaKind::AnyOf { any_of } => list(self, "anyOf", any_of),
+            SchemaKind::Any(any) => {
+                properties(self, &any.properties);
+                if let Some(items) = &any.items {
+                    boxed(self, "items", items);
+                }
+                additional(self, &any.additional_properties);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn reports_mismatched_examples() {
+        let api: OpenAPI = serde_yaml::from_str(
+            "
+openapi: 3.0.0
+info: {title: t, version: '1'}
+paths:
+  /pets:
+    get:
+      parameters:
+        - {name: limit, in: query, schema: {type: integer, maximum: 100}, example: 1000}
+      responses:
+        '200':
+          description: pets
+          content:
+            application/json:
+              schema: {type: array, items: {$ref: '#/components/schemas/Pet'}}
+              examples:
+                good: {value: [{name: Rex}]}
+                bad: {$ref: '#/components/examples/Nameless'}
+components:
+  schemas:
+    Pet:
+      type: object
+      required: [name]
+      properties:
+        name: {type: string}
+        age: {type: integer, example: old}
+      example: {name: Tom, age: 3}
+  examples:
+    Nameless: {value: [{age: 3}]}
+",
+        )
+        .unwrap();
+
+        let errors: Vec<String> = api
+            .validate_examples()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            errors,
+            vec![
+                "/paths/~1pets/get/parameters/0/example: must be less than or equal to 100",
+                "/paths/~1pets/get/responses/200/content/application~1json/examples/bad/value: \
+                 /0: missing required property `name`",
+                "/components/schemas/Pet/properties/age/example: expected integer",
+            ]
+        );
+    }
+}
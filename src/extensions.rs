@@ -0,0 +1,183 @@
+//! Typed views of well-known specification extensions.
+//!
+//! Every object keeps its extensions as JSON values, such as
+//! [`Operation::extensions`](crate::Operation::extensions). The types here
+//! read the extensions that are common across tools, and [`get`] reads any
+//! other into a type of the caller's choosing.
+//!
+//! ```
+//! use openapiv3::extensions::{CodeSamples, Extension, Internal};
+//! use openapiv3::Operation;
+//!
+//! let operation: Operation = serde_yaml::from_str(
+//!     "
+//! responses: {}
+//! x-internal: true
+//! x-codeSamples: [{lang: Shell, source: curl /pets}]
+//! ",
+//! )
+//! .unwrap();
+//! assert_eq!(
+//!     operation.extension::<Internal>(Internal::KEY).unwrap().unwrap(),
+//!     Internal(true)
+//! );
+//! let samples = operation.extension::<CodeSamples>(CodeSamples::KEY).unwrap().unwrap();
+//! assert_eq!(samples.0[0].lang, "Shell");
+//! assert!(operation.extension::<u32>("x-rate-limit").is_none());
+//! ```
+
+use crate::*;
+use indexmap::IndexMap;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// An extension with a well-known name.
+pub trait Extension: DeserializeOwned {
+    /// The name of the extension, including its `x-` prefix.
+    const KEY: &'static str;
+}
+
+/// Reads the extension named `key` of `extensions`, or `None` if there is
+/// no such extension.
+pub fn get<T: DeserializeOwned>(
+    extensions: &IndexMap<String, Value>,
+    key: &str,
+) -> Option<Result<T, serde_json::Error>> {
+    extensions.get(key).map(T::deserialize)
+}
+
+/// `x-internal`: whether the object is meant for internal use only, and
+/// left out of public documentation.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Internal(pub bool);
+
+impl Extension for Internal {
+    const KEY: &'static str = "x-internal";
+}
+
+/// `x-deprecated-at`: when the object was, or will be, deprecated, usually
+/// as a date such as `2021-06-01` or a version.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DeprecatedAt(pub String);
+
+impl Extension for DeprecatedAt {
+    const KEY: &'static str = "x-deprecated-at";
+}
+
+/// `x-codeSamples`: examples of calling an operation, as shown by
+/// documentation tools such as Redoc.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CodeSamples(pub Vec<CodeSample>);
+
+impl Extension for CodeSamples {
+    const KEY: &'static str = "x-codeSamples";
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CodeSample {
+    /// The language of the sample, such as `JavaScript` or `Shell`.
+    pub lang: String,
+    /// The name shown for the sample, if not its language.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// The code of the sample.
+    pub source: String,
+}
+
+/// `x-amazon-apigateway-integration`: how Amazon API Gateway passes
+/// requests for an operation to its backend.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiGatewayIntegration {
+    /// The kind of backend, such as `aws_proxy`, `http_proxy` or `mock`.
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// The address of the backend.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uri: Option<String>,
+    /// The method of requests to the backend, which may differ from that
+    /// of the operation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub http_method: Option<String>,
+    /// The role API Gateway assumes to call the backend.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub credentials: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub passthrough_behavior: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout_in_millis: Option<u64>,
+    /// Maps parameters of requests to the backend to their sources, such
+    /// as `method.request.path.id`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub request_parameters: BTreeMap<String, String>,
+    /// Mapping templates by content type.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub request_templates: BTreeMap<String, String>,
+    /// How responses of the backend map to those of the operation, by a
+    /// pattern matching the backend status code or error.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub responses: BTreeMap<String, Value>,
+    /// Other settings of the integration.
+    #[serde(flatten)]
+    pub other: BTreeMap<String, Value>,
+}
+
+impl Extension for ApiGatewayIntegration {
+    const KEY: &'static str = "x-amazon-apigateway-integration";
+}
+
+impl Operation {
+    /// Reads the extension named `key` of this operation; see
+    /// [`extensions::get`](get).
+    pub fn extension<T: DeserializeOwned>(
+        &self,
+        key: &str,
+    ) -> Option<Result<T, serde_json::Error>> {
+        get(&self.extensions, key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_known_extensions() {
+        let operation: Operation = serde_yaml::from_str(
+            "
+responses: {}
+x-deprecated-at: '2021-06-01'
+x-internal: maybe
+x-amazon-apigateway-integration:
+  type: aws_proxy
+  httpMethod: POST
+  uri: arn:aws:apigateway:lambda
+  requestParameters: {integration.request.path.id: method.request.path.id}
+  contentHandling: CONVERT_TO_TEXT
+",
+        )
+        .unwrap();
+        assert_eq!(
+            operation
+                .extension::<DeprecatedAt>(DeprecatedAt::KEY)
+                .unwrap()
+                .unwrap(),
+            DeprecatedAt("2021-06-01".to_owned())
+        );
+        assert!(operation
+            .extension::<Internal>(Internal::KEY)
+            .unwrap()
+            .is_err());
+
+        let integration = operation
+            .extension::<ApiGatewayIntegration>(ApiGatewayIntegration::KEY)
+            .unwrap()
+            .unwrap();
+        assert_eq!(integration.kind, "aws_proxy");
+        assert_eq!(integration.http_method.as_deref(), Some("POST"));
+        assert_eq!(integration.request_parameters.len(), 1);
+        assert_eq!(integration.other["contentHandling"], "CONVERT_TO_TEXT");
+    }
+}
@@ -0,0 +1,347 @@
+use crate::reference::unescape_pointer_segment;
+use crate::walk::{Referenceable, VisitRefs, Walk};
+use crate::*;
+use serde::de::DeserializeOwned;
+use serde_yaml::Value;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+
+/// Loads the documents named by references to other files,
+/// such as `./common.yaml#/components/schemas/Error`.
+pub trait ReferenceResolver {
+    /// Returns the raw contents of the document at `location`. The location
+    /// has already been resolved against that of the referring document.
+    fn load(&self, location: &str) -> io::Result<String>;
+}
+
+/// Resolves locations as paths on the local filesystem.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FileSystemResolver;
+
+impl ReferenceResolver for FileSystemResolver {
+    fn load(&self, location: &str) -> io::Result<String> {
+        fs::read_to_string(location)
+    }
+}
+
+/// Serves documents from memory, by location, for tests.
+#[cfg(test)]
+pub(crate) struct InMemory(pub(crate) std::collections::BTreeMap<String, String>);
+
+#[cfg(test)]
+impl<L: Into<String>, C: Into<String>> std::iter::FromIterator<(L, C)> for InMemory {
+    fn from_iter<I: IntoIterator<Item = (L, C)>>(documents: I) -> Self {
+        InMemory(
+            documents
+                .into_iter()
+                .map(|(location, contents)| (location.into(), contents.into()))
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+impl ReferenceResolver for InMemory {
+    fn load(&self, location: &str) -> io::Result<String> {
+        self.0
+            .get(location)
+            .cloned()
+            .ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))
+    }
+}
+
+/// The reasons an external reference can fail to be resolved.
+#[derive(Debug)]
+pub enum ExternalReferenceError {
+    /// The referenced document could not be loaded.
+    Load { location: String, source: io::Error },
+    /// The referenced document is neither valid JSON nor YAML,
+    /// or the referenced part of it is not the expected kind of object.
+    Parse {
+        reference: String,
+        source: serde_yaml::Error,
+    },
+    /// The fragment of the reference does not exist in the document.
+    NotFound(String),
+    /// The reference leads back to itself, possibly through other files.
+    /// Holds the chain of (absolute) references, ending with the one that
+    /// was revisited.
+    Cycle(Vec<String>),
+}
+
+impl fmt::Display for ExternalReferenceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExternalReferenceError::Load { location, source } => {
+                write!(f, "could not load `{}`: {}", location, source)
+            }
+            ExternalReferenceError::Parse { reference, source } => {
+                write!(f, "could not parse `{}`: {}", reference, source)
+            }
+            ExternalReferenceError::NotFound(r) => write!(f, "reference `{}` not found", r),
+            ExternalReferenceError::Cycle(chain) => {
+                write!(f, "circular reference: {}", chain.join(" -> "))
+            }
+        }
+    }
+}
+
+impl Error for ExternalReferenceError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ExternalReferenceError::Load { source, .. } => Some(source),
+            ExternalReferenceError::Parse { source, .. } => Some(source),
+            ExternalReferenceError::NotFound(_) | ExternalReferenceError::Cycle(_) => None,
+        }
+    }
+}
+
+impl OpenAPI {
+    /// Replaces every reference to another document with the object it
+    /// points to, loaded through `resolver`.
+    ///
+    /// `location` is where this document itself was loaded from, and is what
+    /// relative references are resolved against. References inside loaded
+    /// documents are followed as well, relative to their own document;
+    /// references within this document are left untouched.
+    pub fn resolve_external_references<R: ReferenceResolver>(
+        &mut self,
+        location: &str,
+        resolver: &R,
+    ) -> Result<(), ExternalReferenceError> {
+        let mut inliner = ExternalInliner {
+            documents: Documents::new(resolver),
+            bases: vec![location.to_owned()],
+            stack: Vec::new(),
+        };
+        self.walk(&mut inliner)
+    }
+}
+
+/// Splits a reference into the document part and the fragment,
+/// without the separating `#`.
+pub(crate) fn split_reference(reference: &str) -> (&str, &str) {
+    match reference.find('#') {
+        Some(i) => (&reference[..i], &reference[i + 1..]),
+        None => (reference, ""),
+    }
+}
+
+/// Resolves a relative document location against the location of the
+/// document it appears in, as a URI reference would be.
+pub(crate) fn join_location(base: &str, relative: &str) -> String {
+    if relative.is_empty() {
+        return base.to_owned();
+    }
+    if relative.starts_with('/') || relative.contains("://") {
+        return relative.to_owned();
+    }
+    let mut segments: Vec<&str> = match base.rfind('/') {
+        Some(i) => base[..i].split('/').collect(),
+        None => Vec::new(),
+    };
+    for segment in relative.split('/') {
+        match segment {
+            "." => {}
+            ".." if segments.last().is_some_and(|s| !s.is_empty() && *s != "..") => {
+                segments.pop();
+            }
+            _ => segments.push(segment),
+        }
+    }
+    segments.join("/")
+}
+
+/// Finds the node a JSON Pointer refers to within a parsed document.
+pub(crate) fn lookup_pointer<'a>(document: &'a Value, pointer: &str) -> Option<&'a Value> {
+    if pointer.is_empty() {
+        return Some(document);
+    }
+    if !pointer.starts_with('/') {
+        return None;
+    }
+    pointer[1..].split('/').try_fold(document, |node, segment| {
+        let segment = unescape_pointer_segment(segment);
+        match node {
+            Value::Mapping(mapping) => mapping.get(&Value::String(segment.clone())).or_else(|| {
+                let number: u64 = segment.parse().ok()?;
+                mapping.get(&Value::Number(number.into()))
+            }),
+            Value::Sequence(sequence) => sequence.get(segment.parse::<usize>().ok()?),
+            _ => None,
+        }
+    })
+}
+
+/// The documents loaded so far, by location.
+pub(crate) struct Documents<'r, R> {
+    resolver: &'r R,
+    documents: HashMap<String, Value>,
+}
+
+impl<'r, R: ReferenceResolver> Documents<'r, R> {
+    pub(crate) fn new(resolver: &'r R) -> Self {
+        Documents {
+            resolver,
+            documents: HashMap::new(),
+        }
+    }
+
+    fn document(&mut self, location: &str) -> Result<&Value, ExternalReferenceError> {
+        if !self.documents.contains_key(location) {
+            let contents =
+                self.resolver
+                    .load(location)
+                    .map_err(|source| ExternalReferenceError::Load {
+                        location: location.to_owned(),
+                        source,
+                    })?;
+            let document = serde_yaml::from_str(&contents).map_err(|source| {
+                ExternalReferenceError::Parse {
+                    reference: location.to_owned(),
+                    source,
+                }
+            })?;
+            self.documents.insert(location.to_owned(), document);
+        }
+        Ok(&self.documents[location])
+    }
+
+    /// Loads the object at `fragment` within the document at `location`.
+    /// `absolute` is the combination of both, for error messages.
+    pub(crate) fn load<T: DeserializeOwned>(
+        &mut self,
+        location: &str,
+        fragment: &str,
+        absolute: &str,
+    ) -> Result<T, ExternalReferenceError> {
+        let node = lookup_pointer(self.document(location)?, fragment)
+            .ok_or_else(|| ExternalReferenceError::NotFound(absolute.to_owned()))?
+            .clone();
+        serde_yaml::from_value(node).map_err(|source| ExternalReferenceError::Parse {
+            reference: absolute.to_owned(),
+            source,
+        })
+    }
+}
+
+struct ExternalInliner<'r, R> {
+    documents: Documents<'r, R>,
+    /// The locations of the documents being walked; the first is the root.
+    bases: Vec<String>,
+    /// The absolute references currently being expanded, outermost first.
+    stack: Vec<String>,
+}
+
+impl<'r, R: ReferenceResolver> VisitRefs for ExternalInliner<'r, R> {
+    type Error = ExternalReferenceError;
+
+    fn reference<T: Referenceable>(
+        &mut self,
+        value: &mut ReferenceOr<T>,
+    ) -> Result<(), ExternalReferenceError> {
+        let reference = match value {
+            ReferenceOr::Item(item) => return item.walk(self),
+            ReferenceOr::Reference { reference } => reference.to_string(),
+        };
+        let (document, fragment) = split_reference(&reference);
+        if document.is_empty() && self.bases.len() == 1 {
+            // An internal reference of the root document.
+            return Ok(());
+        }
+        let location = join_location(self.bases.last().unwrap(), document);
+        let absolute = format!("{}#{}", location, fragment);
+        if let Some(start) = self.stack.iter().position(|r| *r == absolute) {
+            let mut chain = self.stack[start..].to_vec();
+            chain.push(absolute);
+            return Err(ExternalReferenceError::Cycle(chain));
+        }
+
+        let mut item: T = self.documents.load(&location, fragment, &absolute)?;
+
+        self.stack.push(absolute);
+        self.bases.push(location);
+        let result = item.walk(self);
+        self.bases.pop();
+        self.stack.pop();
+        result?;
+
+        *value = ReferenceOr::Item(item);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn joins_locations() {
+        assert_eq!(
+            join_location("specs/main.yaml", "./common.yaml"),
+            "specs/common.yaml"
+        );
+        assert_eq!(
+            join_location("specs/main.yaml", "../common.yaml"),
+            "common.yaml"
+        );
+        assert_eq!(
+            join_location("/srv/a/main.yaml", "b/c.yaml"),
+            "/srv/a/b/c.yaml"
+        );
+        assert_eq!(join_location("main.yaml", "common.yaml"), "common.yaml");
+        assert_eq!(join_location("main.yaml", ""), "main.yaml");
+    }
+
+    #[test]
+    fn inlines_across_files() {
+        let resolver: InMemory = vec![
+            (
+                "specs/common.yaml",
+                "components:\n  schemas:\n    Error:\n      type: object\n      properties:\n        code:\n          $ref: '#/components/schemas/Code'\n    Code:\n      type: integer\n",
+            ),
+            ("specs/loop.yaml", "type: array\nitems:\n  $ref: './loop.yaml'\n"),
+        ]
+        .into_iter()
+        .collect();
+
+        let mut api: OpenAPI = serde_yaml::from_str(
+            "openapi: 3.0.0\ninfo: {title: t, version: '1'}\npaths: {}\ncomponents:\n  schemas:\n    Error:\n      $ref: './common.yaml#/components/schemas/Error'\n    Local:\n      $ref: '#/components/schemas/Error'\n",
+        )
+        .unwrap();
+        api.resolve_external_references("specs/main.yaml", &resolver)
+            .unwrap();
+        let schemas = &api.components.as_ref().unwrap().schemas;
+        assert_eq!(
+            schemas["Local"],
+            ReferenceOr::ref_("#/components/schemas/Error")
+        );
+        match &schemas["Error"] {
+            ReferenceOr::Item(Schema {
+                schema_kind: SchemaKind::Type(Type::Object(object)),
+                ..
+            }) => match &object.properties["code"] {
+                ReferenceOr::Item(code) => assert_eq!(
+                    code.schema_kind,
+                    SchemaKind::Type(Type::Integer(Default::default()))
+                ),
+                reference => panic!("unexpected {:?}", reference),
+            },
+            other => panic!("unexpected {:?}", other),
+        }
+
+        let mut looping: OpenAPI = serde_yaml::from_str(
+            "openapi: 3.0.0\ninfo: {title: t, version: '1'}\npaths: {}\ncomponents:\n  schemas:\n    Loop:\n      $ref: './loop.yaml'\n",
+        )
+        .unwrap();
+        match looping.resolve_external_references("specs/main.yaml", &resolver) {
+            Err(ExternalReferenceError::Cycle(chain)) => {
+                assert_eq!(chain, vec!["specs/loop.yaml#", "specs/loop.yaml#"])
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+}
@@ -1,7 +1,8 @@
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 
 /// Allows referencing an external resource for extended documentation.
-#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
 pub struct ExternalDocumentation {
     /// A short description of the target documentation.
     /// CommonMark syntax MAY be used for rich text representation.
@@ -10,4 +11,7 @@ pub struct ExternalDocumentation {
     /// REQUIRED. The URL for the target documentation.
     /// Value MUST be in the format of a URL.
     pub url: String,
+    /// Inline extensions to this object.
+    #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
+    pub extensions: IndexMap<String, serde_json::Value>,
 }
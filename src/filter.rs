@@ -0,0 +1,176 @@
+use crate::*;
+use std::collections::BTreeSet;
+
+/// Selects operations of a document; see [`OpenAPI::filter`].
+///
+/// An operation is selected if it has one of the `tags`, one of the
+/// `operation_ids` or one of the `paths`, or if all three are empty,
+/// unless it has one of the `excluded_tags`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FilterSpec {
+    pub tags: BTreeSet<String>,
+    pub operation_ids: BTreeSet<String>,
+    /// Paths, such as `/pets/{id}`, selecting all their operations.
+    pub paths: BTreeSet<String>,
+    pub excluded_tags: BTreeSet<String>,
+}
+
+impl FilterSpec {
+    /// A filter selecting every operation.
+    pub fn new() -> Self {
+        FilterSpec::default()
+    }
+
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.tags.insert(tag.into());
+        self
+    }
+
+    pub fn operation_id(mut self, operation_id: impl Into<String>) -> Self {
+        self.operation_ids.insert(operation_id.into());
+        self
+    }
+
+    pub fn path(mut self, path: impl Into<String>) -> Self {
+        self.paths.insert(path.into());
+        self
+    }
+
+    pub fn exclude_tag(mut self, tag: impl Into<String>) -> Self {
+        self.excluded_tags.insert(tag.into());
+        self
+    }
+
+    fn selects_everything(&self) -> bool {
+        self.tags.is_empty() && self.operation_ids.is_empty() && self.paths.is_empty()
+    }
+
+    fn selects(&self, path: &str, operation: &Operation) -> bool {
+        let included = self.selects_everything()
+            || self.paths.contains(path)
+            || operation
+                .tags
+                .iter()
+                .any(|tag| self.tags.contains(tag.as_str()))
+            || operation
+                .operation_id
+                .as_ref()
+                .is_some_and(|id| self.operation_ids.contains(id));
+        included
+            && !operation
+                .tags
+                .iter()
+                .any(|tag| self.excluded_tags.contains(tag.as_str()))
+    }
+}
+
+impl OpenAPI {
+    /// A copy of the document with only the operations `spec` selects.
+    ///
+    /// Path items left without operations are removed, as are the
+    /// components and root tags that the remaining document does not use.
+    /// Path items given by reference are kept only if their path is one
+    /// of the `paths` of `spec`.
+    pub fn filter(&self, spec: &FilterSpec) -> OpenAPI {
+        let mut api = self.clone();
+        api.paths.retain(|path, item| {
+            let item = match item {
+                ReferenceOr::Item(item) => item,
+                ReferenceOr::Reference { .. } => return spec.paths.contains(path),
+            };
+            for method in Method::ALL {
+                let operation = item.operation_mut(method);
+                if operation
+                    .as_ref()
+                    .is_some_and(|operation| !spec.selects(path, operation))
+                {
+                    *operation = None;
+                }
+            }
+            item.operations().next().is_some()
+        });
+
+        let used: BTreeSet<&str> = api
+            .paths
+            .values()
+            .filter_map(|item| match item {
+                ReferenceOr::Item(item) => Some(item),
+                ReferenceOr::Reference { .. } => None,
+            })
+            .flat_map(PathItem::operations)
+            .flat_map(|(_, operation)| operation.tags.iter().map(Name::as_str))
+            .collect();
+        let tags = api
+            .tags
+            .iter()
+            .filter(|tag| used.contains(tag.name.as_str()))
+            .cloned()
+            .collect();
+        api.tags = tags;
+
+        api.remove_unused_components();
+        api
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn filters_operations() {
+        let api: OpenAPI = serde_yaml::from_str(
+            "
+openapi: 3.0.0
+info: {title: t, version: '1'}
+tags: [{name: public}, {name: internal}]
+paths:
+  /pets:
+    get:
+      tags: [public]
+      responses: {'200': {$ref: '#/components/responses/Pets'}}
+    post:
+      tags: [internal]
+      requestBody: {$ref: '#/components/requestBodies/Pet'}
+      responses: {'201': {description: created}}
+  /admin:
+    get:
+      operationId: admin
+      tags: [internal]
+      responses: {'200': {description: ok}}
+components:
+  responses:
+    Pets:
+      description: pets
+      content:
+        application/json: {schema: {type: array, items: {$ref: '#/components/schemas/Pet'}}}
+  requestBodies:
+    Pet:
+      content:
+        application/json: {schema: {$ref: '#/components/schemas/Pet'}}
+  schemas:
+    Pet: {type: object}
+",
+        )
+        .unwrap();
+
+        let public = api.filter(&FilterSpec::new().tag("public"));
+        assert_eq!(public.paths.keys().collect::<Vec<_>>(), vec!["/pets"]);
+        match &public.paths["/pets"] {
+            ReferenceOr::Item(item) => {
+                assert!(item.get.is_some());
+                assert!(item.post.is_none());
+            }
+            ReferenceOr::Reference { .. } => panic!("expected a path item"),
+        }
+        assert_eq!(public.tags.len(), 1);
+        let components = public.components.as_ref().unwrap();
+        assert!(components.request_bodies.is_empty());
+        assert!(components.schemas.contains_key("Pet"));
+
+        let filtered = api.filter(&FilterSpec::new().operation_id("admin").path("/pets"));
+        assert_eq!(filtered.paths.len(), 2);
+        let filtered = api.filter(&FilterSpec::new().exclude_tag("internal"));
+        assert_eq!(filtered.paths.keys().collect::<Vec<_>>(), vec!["/pets"]);
+    }
+}
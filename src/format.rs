@@ -0,0 +1,198 @@
+use crate::*;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt;
+
+type Check = Box<dyn Fn(&Value) -> bool + Send + Sync>;
+type Make = Box<dyn Fn(&mut dyn Rng) -> Value + Send + Sync>;
+
+/// Formats of strings and numbers beyond those this crate knows, each
+/// with how to check values of it and how to make them up.
+///
+/// The crate checks the `date`, `date-time`, `byte`, `int32` and `int64`
+/// formats and makes up values of those and of others such as `uuid` and
+/// `email`; formats registered here take precedence, and others are not
+/// constrained. Pass a registry to [`Schema::validate_value_with`],
+/// [`Schema::example_value_with`] and [`Schema::generate_with`].
+///
+/// ```
+/// use openapiv3::{Components, FormatRegistry, Rng, Schema};
+/// use serde_json::{json, Value};
+///
+/// let formats = FormatRegistry::new().format(
+///     "hex",
+///     |value: &Value| {
+///         value
+///             .as_str()
+///             .is_some_and(|s| s.chars().all(|c| c.is_ascii_hexdigit()))
+///     },
+///     |rng: &mut dyn Rng| json!(format!("{:x}", rng.next_u64())),
+/// );
+/// let schema: Schema = serde_yaml::from_str("{type: string, format: hex}").unwrap();
+/// let components = Components::default();
+/// assert!(schema
+///     .validate_value_with(&components, &formats, &json!("c0ffee"))
+///     .is_ok());
+/// assert!(schema
+///     .validate_value_with(&components, &formats, &json!("coffee"))
+///     .is_err());
+/// let example = schema.example_value_with(&components, &formats);
+/// assert!(schema
+///     .validate_value_with(&components, &formats, &example)
+///     .is_ok());
+/// ```
+#[derive(Default)]
+pub struct FormatRegistry {
+    checks: BTreeMap<String, Check>,
+    makers: BTreeMap<String, Make>,
+}
+
+/// The formats this crate knows, and no others.
+pub(crate) static BUILT_IN_FORMATS: FormatRegistry = FormatRegistry::new();
+
+impl FormatRegistry {
+    /// A registry of only the formats this crate knows.
+    pub const fn new() -> Self {
+        FormatRegistry {
+            checks: BTreeMap::new(),
+            makers: BTreeMap::new(),
+        }
+    }
+
+    /// Registers a format with how to check and make up its values.
+    pub fn format(
+        self,
+        name: &str,
+        check: impl Fn(&Value) -> bool + Send + Sync + 'static,
+        make: impl Fn(&mut dyn Rng) -> Value + Send + Sync + 'static,
+    ) -> Self {
+        self.validator(name, check).generator(name, make)
+    }
+
+    /// Checks values of the format with `check`, which is given those of
+    /// the type of the schema, such as strings for `type: string`.
+    pub fn validator(
+        mut self,
+        name: &str,
+        check: impl Fn(&Value) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.checks.insert(name.to_owned(), Box::new(check));
+        self
+    }
+
+    /// Makes up values of the format with `make`. Example values are made
+    /// with a random number generator of a fixed seed.
+    pub fn generator(
+        mut self,
+        name: &str,
+        make: impl Fn(&mut dyn Rng) -> Value + Send + Sync + 'static,
+    ) -> Self {
+        self.makers.insert(name.to_owned(), Box::new(make));
+        self
+    }
+
+    /// Whether `value` is of the format, or `None` if no check is
+    /// registered for it.
+    pub fn check(&self, format: &str, value: &Value) -> Option<bool> {
+        self.checks.get(format).map(|check| check(value))
+    }
+
+    /// A value of the format, or `None` if no way to make one up is
+    /// registered for it.
+    pub fn make(&self, format: &str, rng: &mut dyn Rng) -> Option<Value> {
+        self.makers.get(format).map(|make| make(rng))
+    }
+
+    pub(crate) fn checks(&self, format: &str) -> bool {
+        self.checks.contains_key(format)
+    }
+}
+
+impl fmt::Debug for FormatRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FormatRegistry")
+            .field("validators", &self.checks.keys().collect::<Vec<_>>())
+            .field("generators", &self.makers.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl Schema {
+    /// The `format` of the schema, if it has one and no `enum`, whose
+    /// values take precedence when making up a value.
+    pub(crate) fn free_format(&self) -> Option<&str> {
+        match &self.schema_kind {
+            SchemaKind::Type(Type::String(string)) if string.enumeration.is_empty() => {
+                string.format.name(StringFormat::as_str)
+            }
+            SchemaKind::Type(Type::Number(number)) if number.enumeration.is_empty() => {
+                number.format.name(NumberFormat::as_str)
+            }
+            SchemaKind::Type(Type::Integer(integer)) if integer.enumeration.is_empty() => {
+                integer.format.name(IntegerFormat::as_str)
+            }
+            SchemaKind::Any(any) => any.format.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use serde_json::{json, Value};
+
+    #[test]
+    fn registers_formats() {
+        let formats = FormatRegistry::new()
+            .validator("even", |value: &Value| {
+                value.as_i64().is_some_and(|n| n % 2 == 0)
+            })
+            .generator("even", |rng: &mut dyn Rng| json!(rng.next_u64() % 50 * 2))
+            .format(
+                "date",
+                |value: &Value| value == "today",
+                |_: &mut dyn Rng| json!("today"),
+            );
+        let schema: Schema = serde_yaml::from_str(
+            "
+type: object
+properties:
+  n: {type: integer, format: even}
+  day: {type: string, format: date}
+",
+        )
+        .unwrap();
+        let components = Components::default();
+
+        let errors = schema
+            .validate_value_with(&components, &formats, &json!({"n": 3, "day": "2021-01-01"}))
+            .unwrap_err();
+        let errors: Vec<String> = errors.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            errors,
+            ["/day: expected format `date`", "/n: expected format `even`"]
+        );
+        assert!(schema
+            .validate_value_with(&components, &formats, &json!({"n": "x", "day": "today"}))
+            .is_err());
+        assert!(schema
+            .validate_value(&json!({"n": 3, "day": "2021-01-01"}))
+            .is_ok());
+
+        let example = schema.example_value_with(&components, &formats);
+        assert!(schema
+            .validate_value_with(&components, &formats, &example)
+            .is_ok());
+        let mut rng = SeededRng::new(1);
+        for _ in 0..10 {
+            let value =
+                schema.generate_with(&components, &mut rng, &GenOptions::default(), &formats);
+            assert!(value.get("n").is_none_or(|n| n.as_i64().unwrap() % 2 == 0));
+            assert!(value.get("day").is_none_or(|day| day == "today"));
+        }
+        let odd: Schema = serde_yaml::from_str("{type: integer, format: even, enum: [3]}").unwrap();
+        assert_eq!(odd.example_value_with(&components, &formats), 3);
+        assert_eq!(formats.check("odd", &json!(1)), None);
+    }
+}
@@ -1,4 +1,5 @@
 use crate::*;
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
@@ -7,7 +8,7 @@ use std::collections::BTreeMap;
 /// 1) name MUST NOT be specified, it is given in the corresponding headers map.
 /// 2) in MUST NOT be specified, it is implicitly in header.
 /// 3) All traits that are affected by the location MUST be applicable to a location of header (for example, style).
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct Header {
     /// A brief description of the parameter. This could
@@ -17,6 +18,10 @@ pub struct Header {
     pub description: Option<String>,
     #[serde(default)]
     pub style: HeaderStyle,
+    /// When true, the properties of object values are serialized as
+    /// `name=value` pairs. Defaults to false.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explode: Option<bool>,
     /// Determines whether this parameter is mandatory.
     /// If the parameter location is "path", this property
     /// is REQUIRED and its value MUST be true. Otherwise,
@@ -34,4 +39,62 @@ pub struct Header {
     pub example: Option<serde_json::Value>,
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub examples: BTreeMap<String, ReferenceOr<Example>>,
+    /// Inline extensions to this object.
+    #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
+    pub extensions: IndexMap<String, serde_json::Value>,
+}
+
+impl Header {
+    /// The header as a header parameter named `name`, as it appears in
+    /// the `headers` map of a response or encoding.
+    pub fn into_parameter(self, name: impl Into<String>) -> Parameter {
+        Parameter::Header {
+            parameter_data: ParameterData {
+                name: name.into(),
+                description: self.description,
+                required: self.required,
+                deprecated: self.deprecated,
+                explode: self.explode,
+                format: self.format,
+                example: self.example,
+                examples: self.examples,
+                extensions: self.extensions,
+            },
+            style: self.style,
+        }
+    }
+}
+
+impl Schemed for Header {
+    fn format(&self) -> &ParameterSchemaOrContent {
+        &self.format
+    }
+
+    fn required(&self) -> bool {
+        self.required
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_to_and_from_parameters() {
+        let header: Header = serde_yaml::from_str(
+            "{description: d, required: true, explode: true, schema: {type: object}, x-a: 1}",
+        )
+        .unwrap();
+        let parameter = header.clone().into_parameter("X-Rate");
+        assert_eq!(parameter.parameter_data_ref().name, "X-Rate");
+        assert!(parameter.effective_explode());
+        assert_eq!(parameter.schema(), header.schema());
+        assert_eq!(
+            parameter.into_header(),
+            Some(("X-Rate".to_owned(), header.clone()))
+        );
+
+        let query: Parameter = serde_yaml::from_str("{name: q, in: query, schema: {}}").unwrap();
+        assert_eq!(query.into_header(), None);
+    }
 }
@@ -0,0 +1,310 @@
+use crate::visit::Visitor;
+use crate::walk::{Referenceable, VisitRefs, Walk};
+use crate::*;
+use std::any::Any;
+use std::convert::Infallible;
+
+impl OpenAPI {
+    /// Moves the schemas declared more than once inline within `paths` into
+    /// `components.schemas`, replacing each declaration with a reference,
+    /// and returns the names of the components added.
+    ///
+    /// Schemas are the same if they are [structurally
+    /// equal](Schema::structurally_equal). Only objects with properties,
+    /// enums and compositions of several schemas are moved, and inline
+    /// copies of an existing component are replaced with references to it
+    /// even where declared once. Schemas within moved ones are moved first,
+    /// so that components refer to each other rather than repeat themselves.
+    ///
+    /// Components are named after where the schema is first declared, by
+    /// [`SchemaNamer::new`]; see [`OpenAPI::hoist_duplicate_schemas_with`].
+    ///
+    /// ```
+    /// use openapiv3::OpenAPI;
+    ///
+    /// let mut api: OpenAPI = serde_yaml::from_str(
+    ///     "
+    /// openapi: 3.0.0
+    /// info: {title: t, version: '1'}
+    /// paths:
+    ///   /pets:
+    ///     get:
+    ///       responses:
+    ///         '200':
+    ///           description: pets
+    ///           content:
+    ///             application/json:
+    ///               schema:
+    ///                 type: object
+    ///                 properties:
+    ///                   owner: {type: object, properties: {name: {type: string}}}
+    ///     post:
+    ///       requestBody:
+    ///         content:
+    ///           application/json:
+    ///             schema: {type: object, properties: {name: {type: string}}}
+    ///       responses: {}
+    /// ",
+    /// )
+    /// .unwrap();
+    /// assert_eq!(api.hoist_duplicate_schemas(), ["GetPetsResponse200Owner"]);
+    /// let text = serde_yaml::to_string(&api.paths).unwrap();
+    /// assert_eq!(text.matches("#/components/schemas/GetPetsResponse200Owner").count(), 2);
+    /// ```
+    pub fn hoist_duplicate_schemas(&mut self) -> Vec<String> {
+        self.hoist_duplicate_schemas_with(&mut SchemaNamer::new())
+    }
+
+    /// Moves duplicate schemas into components as
+    /// [`OpenAPI::hoist_duplicate_schemas`] does, naming the components
+    /// with `namer`, which is kept from giving the names of existing
+    /// ones.
+    pub fn hoist_duplicate_schemas_with(&mut self, namer: &mut SchemaNamer) -> Vec<String> {
+        let lookup = self.components.clone().unwrap_or_default();
+        let mut groups: Vec<Group> = lookup
+            .schemas
+            .iter()
+            .filter_map(|(name, schema)| match schema {
+                ReferenceOr::Item(schema) if worth_hoisting(schema) => Some(Group {
+                    schema: schema.clone(),
+                    name: Some(name.clone()),
+                    locations: Vec::new(),
+                }),
+                _ => None,
+            })
+            .collect();
+        self.visit(&mut Counter {
+            groups: &mut groups,
+            components: &lookup,
+        });
+
+        for name in lookup.schemas.keys() {
+            namer.reserve(name.clone());
+        }
+        for group in &mut groups {
+            if group.name.is_none() && group.locations.len() > 1 {
+                group.name = Some(namer.name(self, &group.locations[0]));
+            }
+        }
+        let mut hoister = Hoister {
+            groups,
+            lookup,
+            hoisted: Vec::new(),
+        };
+        let _ = self.paths.walk(&mut hoister);
+        let components = self.components.get_or_insert_with(Components::default);
+        for name in &hoister.hoisted {
+            let schema = hoister.lookup.schemas[name].clone();
+            components.schemas.insert(name.clone(), schema);
+        }
+        hoister.hoisted
+    }
+}
+
+/// Whether a schema is substantial enough to be worth a component of its
+/// own.
+fn worth_hoisting(schema: &Schema) -> bool {
+    match &schema.schema_kind {
+        SchemaKind::Type(Type::Object(object)) => !object.properties.is_empty(),
+        SchemaKind::Type(Type::String(string)) => !string.enumeration.is_empty(),
+        SchemaKind::Type(Type::Integer(integer)) => !integer.enumeration.is_empty(),
+        SchemaKind::Type(Type::Number(number)) => !number.enumeration.is_empty(),
+        SchemaKind::OneOf { one_of: schemas }
+        | SchemaKind::AllOf { all_of: schemas }
+        | SchemaKind::AnyOf { any_of: schemas } => schemas.len() > 1,
+        SchemaKind::Any(any) => !any.properties.is_empty(),
+        SchemaKind::Type(_) => false,
+    }
+}
+
+/// Schemas that are structurally equal to each other.
+struct Group {
+    schema: Schema,
+    /// The component the schemas are, or are to be, moved to, if they are
+    /// declared more than once or are copies of a component.
+    name: Option<String>,
+    /// Where the schema is declared inline.
+    locations: Vec<String>,
+}
+
+/// Counts the inline declarations of each schema.
+struct Counter<'g> {
+    groups: &'g mut Vec<Group>,
+    components: &'g Components,
+}
+
+impl<'a, 'g> Visitor<'a> for Counter<'g> {
+    fn visit_schema(&mut self, location: &str, schema: &'a Schema) {
+        if !location.starts_with("/paths/") || !worth_hoisting(schema) {
+            return;
+        }
+        let components = self.components;
+        match self
+            .groups
+            .iter_mut()
+            .find(|group| group.schema.structurally_equal(schema, components))
+        {
+            Some(group) => group.locations.push(location.to_owned()),
+            None => self.groups.push(Group {
+                schema: schema.clone(),
+                name: None,
+                locations: vec![location.to_owned()],
+            }),
+        }
+    }
+}
+
+/// Replaces inline declarations with references, innermost first.
+struct Hoister {
+    groups: Vec<Group>,
+    /// The schemas of the document and those moved so far, against which
+    /// references in partly moved schemas resolve.
+    lookup: Components,
+    hoisted: Vec<String>,
+}
+
+impl Hoister {
+    fn hoist(&mut self, schema: &Schema) -> Option<Reference> {
+        if !worth_hoisting(schema) {
+            return None;
+        }
+        let lookup = &self.lookup;
+        let name = self
+            .groups
+            .iter()
+            .filter(|group| group.name.is_some())
+            .find(|group| group.schema.structurally_equal(schema, lookup))?
+            .name
+            .clone()?;
+        if !self.lookup.schemas.contains_key(&name) {
+            self.lookup
+                .schemas
+                .insert(name.clone(), ReferenceOr::Item(schema.clone()));
+            self.hoisted.push(name.clone());
+        }
+        Some(Reference::component(ComponentKind::Schemas, &name))
+    }
+}
+
+impl VisitRefs for Hoister {
+    type Error = Infallible;
+
+    fn reference<T: Referenceable>(
+        &mut self,
+        value: &mut ReferenceOr<T>,
+    ) -> Result<(), Infallible> {
+        if let ReferenceOr::Item(item) = value {
+            item.walk(self)?;
+        }
+        if let Some(value) = (value as &mut dyn Any).downcast_mut::<ReferenceOr<Schema>>() {
+            if let ReferenceOr::Item(schema) = value {
+                if let Some(reference) = self.hoist(schema) {
+                    *value = ReferenceOr::Reference { reference };
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn hoists_duplicates() {
+        let mut api: OpenAPI = serde_yaml::from_str(
+            "
+openapi: 3.0.0
+info: {title: t, version: '1'}
+paths:
+  /pets:
+    get:
+      parameters:
+        - {name: kind, in: query, schema: {type: string, enum: [cat, dog]}}
+      responses:
+        '200':
+          description: pets
+          content:
+            application/json:
+              schema:
+                type: array
+                items:
+                  type: object
+                  required: [id, kind]
+                  properties:
+                    id: {type: integer}
+                    kind: {type: string, enum: [cat, dog]}
+    post:
+      requestBody:
+        content:
+          application/json:
+            schema:
+              type: object
+              required: [kind, id]
+              properties:
+                kind: {type: string, enum: [cat, dog]}
+                id: {type: integer}
+      responses:
+        default:
+          description: error
+          content:
+            application/json:
+              schema: {type: object, properties: {message: {type: string}}}
+components:
+  schemas:
+    Kind: {type: integer}
+    Error: {type: object, properties: {message: {type: string}}}
+",
+        )
+        .unwrap();
+        assert_eq!(
+            api.hoist_duplicate_schemas(),
+            ["GetPetsKind", "GetPetsResponse200Item"]
+        );
+
+        let expected: OpenAPI = serde_yaml::from_str(
+            "
+openapi: 3.0.0
+info: {title: t, version: '1'}
+paths:
+  /pets:
+    get:
+      parameters:
+        - {name: kind, in: query, schema: {$ref: '#/components/schemas/GetPetsKind'}}
+      responses:
+        '200':
+          description: pets
+          content:
+            application/json:
+              schema:
+                type: array
+                items: {$ref: '#/components/schemas/GetPetsResponse200Item'}
+    post:
+      requestBody:
+        content:
+          application/json:
+            schema: {$ref: '#/components/schemas/GetPetsResponse200Item'}
+      responses:
+        default:
+          description: error
+          content:
+            application/json:
+              schema: {$ref: '#/components/schemas/Error'}
+components:
+  schemas:
+    Kind: {type: integer}
+    Error: {type: object, properties: {message: {type: string}}}
+    GetPetsKind: {type: string, enum: [cat, dog]}
+    GetPetsResponse200Item:
+      type: object
+      required: [id, kind]
+      properties:
+        id: {type: integer}
+        kind: {$ref: '#/components/schemas/GetPetsKind'}
+",
+        )
+        .unwrap();
+        assert_eq!(api, expected);
+    }
+}
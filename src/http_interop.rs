@@ -0,0 +1,247 @@
+//! Conversions between the types of the crate and those of the `http`
+//! crate, with the `http` feature.
+
+use crate::validation::{request, response};
+use crate::*;
+use std::convert::TryFrom;
+
+impl From<Method> for http::Method {
+    fn from(method: Method) -> Self {
+        match method {
+            Method::Get => http::Method::GET,
+            Method::Put => http::Method::PUT,
+            Method::Post => http::Method::POST,
+            Method::Delete => http::Method::DELETE,
+            Method::Options => http::Method::OPTIONS,
+            Method::Head => http::Method::HEAD,
+            Method::Patch => http::Method::PATCH,
+            Method::Trace => http::Method::TRACE,
+        }
+    }
+}
+
+/// Fails for methods that a Path Item Object has no field for, such as
+/// `CONNECT`.
+impl TryFrom<&http::Method> for Method {
+    type Error = ParseMethodError;
+
+    fn try_from(method: &http::Method) -> Result<Self, Self::Error> {
+        method.as_str().parse()
+    }
+}
+
+impl TryFrom<http::Method> for Method {
+    type Error = ParseMethodError;
+
+    fn try_from(method: http::Method) -> Result<Self, Self::Error> {
+        Method::try_from(&method)
+    }
+}
+
+impl From<http::StatusCode> for StatusCode {
+    fn from(status: http::StatusCode) -> Self {
+        StatusCode::Code(status.as_u16())
+    }
+}
+
+impl StatusCode {
+    /// The status code, or `None` for a range of them.
+    pub fn to_http(&self) -> Option<http::StatusCode> {
+        match *self {
+            StatusCode::Code(code) => http::StatusCode::from_u16(code).ok(),
+            StatusCode::Range(_) => None,
+        }
+    }
+}
+
+impl Parameter {
+    /// The name of a header parameter, or `None` for parameters in other
+    /// locations and names that are not valid header names.
+    pub fn header_name(&self) -> Option<http::HeaderName> {
+        match self {
+            Parameter::Header { parameter_data, .. } => {
+                http::HeaderName::from_bytes(parameter_data.name.as_bytes()).ok()
+            }
+            _ => None,
+        }
+    }
+}
+
+impl crate::Response {
+    /// The names of the headers the response describes, leaving out those
+    /// that are not valid header names.
+    pub fn header_names(&self) -> Vec<http::HeaderName> {
+        self.headers
+            .keys()
+            .filter_map(|name| http::HeaderName::from_bytes(name.as_bytes()).ok())
+            .collect()
+    }
+}
+
+/// A message of the `http` crate, as the validation APIs take it.
+///
+/// Headers whose values are not visible ASCII are left out. The path
+/// parameters of a request are not part of it, and are added from the
+/// [`RouteMatch`] of the request.
+///
+/// ```
+/// use openapiv3::validation::request;
+/// use openapiv3::{HttpMessage, Method, OpenAPI};
+/// use std::convert::TryFrom;
+///
+/// let api: OpenAPI = serde_yaml::from_str(
+///     "
+/// openapi: 3.0.0
+/// info: {title: t, version: '1'}
+/// paths:
+///   /pets/{id}:
+///     get:
+///       parameters:
+///         - {name: id, in: path, required: true, schema: {type: integer}}
+///         - {name: limit, in: query, schema: {type: integer}}
+///       responses: {}
+/// ",
+/// )
+/// .unwrap();
+/// let http_request = http::Request::get("/pets/7?limit=ten").body(Vec::new()).unwrap();
+///
+/// let method = Method::try_from(http_request.method()).unwrap();
+/// let route = api.router().route(method, http_request.uri().path()).unwrap();
+/// let mut request = http_request.to_validated();
+/// for (name, value) in &route.parameters {
+///     request = request.path_param(name, value);
+/// }
+/// let errors = request::validate(route.operation, route.path_item, &Default::default(), &request)
+///     .unwrap_err();
+/// assert_eq!(errors[0].to_string(), "query parameter `limit`: expected integer");
+/// ```
+pub trait HttpMessage {
+    type Validated<'r>
+    where
+        Self: 'r;
+
+    fn to_validated(&self) -> Self::Validated<'_>;
+}
+
+impl<B: AsRef<[u8]>> HttpMessage for http::Request<B> {
+    type Validated<'r>
+        = request::Request<'r>
+    where
+        Self: 'r;
+
+    fn to_validated(&self) -> request::Request<'_> {
+        let (headers, content_type) = headers(self.headers());
+        request::Request {
+            path_params: Vec::new(),
+            query: self.uri().query().unwrap_or_default(),
+            headers,
+            content_type,
+            body: self.body().as_ref(),
+        }
+    }
+}
+
+impl<B: AsRef<[u8]>> HttpMessage for http::Response<B> {
+    type Validated<'r>
+        = response::Response<'r>
+    where
+        Self: 'r;
+
+    fn to_validated(&self) -> response::Response<'_> {
+        let (headers, content_type) = headers(self.headers());
+        response::Response {
+            status: self.status().as_u16(),
+            headers,
+            content_type,
+            body: self.body().as_ref(),
+        }
+    }
+}
+
+/// The headers that are visible ASCII, by name and value, and the value of
+/// the `Content-Type` header, if there is one.
+fn headers(map: &http::HeaderMap) -> (Vec<(&str, &str)>, Option<&str>) {
+    let headers: Vec<(&str, &str)> = map
+        .iter()
+        .filter_map(|(name, value)| Some((name.as_str(), value.to_str().ok()?)))
+        .collect();
+    let content_type = map
+        .get(http::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok());
+    (headers, content_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_methods_and_status_codes() {
+        for &method in &Method::ALL {
+            assert_eq!(Method::try_from(http::Method::from(method)), Ok(method));
+        }
+        assert!(Method::try_from(&http::Method::CONNECT).is_err());
+
+        let status = StatusCode::from(http::StatusCode::NOT_FOUND);
+        assert_eq!(status, StatusCode::Code(404));
+        assert_eq!(status.to_http(), Some(http::StatusCode::NOT_FOUND));
+        assert_eq!(StatusCode::Range(4).to_http(), None);
+    }
+
+    #[test]
+    fn validates_http_messages() {
+        let api: OpenAPI = serde_yaml::from_str(
+            "
+openapi: 3.0.0
+info: {title: t, version: '1'}
+paths:
+  /pets:
+    post:
+      parameters: [{name: X-Request-Id, in: header, required: true, schema: {type: string}}]
+      requestBody:
+        content: {application/json: {schema: {type: object, required: [name]}}}
+      responses:
+        201:
+          description: created
+          headers: {Location: {required: true, schema: {type: string}}}
+",
+        )
+        .unwrap();
+        let route = api.router().route(Method::Post, "/pets").unwrap();
+        let (operation, path_item) = (route.operation, route.path_item);
+        let components = Components::default();
+        assert_eq!(
+            operation.effective_parameters(path_item, &components)[0].header_name(),
+            Some(http::HeaderName::from_static("x-request-id"))
+        );
+
+        let valid = http::Request::post("/pets")
+            .header("x-request-id", "1")
+            .header("content-type", "application/json")
+            .body(r#"{"name": "Rex"}"#)
+            .unwrap();
+        let request = valid.to_validated();
+        assert!(request::validate(operation, path_item, &components, &request).is_ok());
+        let invalid = http::Request::post("/pets")
+            .header("content-type", "application/json")
+            .body("{}")
+            .unwrap();
+        let request = invalid.to_validated();
+        let errors = request::validate(operation, path_item, &components, &request).unwrap_err();
+        assert_eq!(errors.len(), 2);
+
+        let created = http::Response::builder()
+            .status(201)
+            .header("location", "/pets/1")
+            .body(Vec::new())
+            .unwrap();
+        assert!(response::validate(operation, &components, &created.to_validated()).is_ok());
+        let described = operation.responses.get_for(201).unwrap();
+        let described = components.resolve(described).unwrap();
+        assert_eq!(described.header_names(), [http::header::LOCATION]);
+        let missing = http::Response::builder().status(201).body("").unwrap();
+        let errors =
+            response::validate(operation, &components, &missing.to_validated()).unwrap_err();
+        assert_eq!(errors.len(), 1);
+    }
+}
@@ -0,0 +1,292 @@
+use crate::discriminator::target_reference;
+use crate::reference::{escape_pointer_segment, unescape_pointer_segment};
+use crate::walk::{FindRefs, Location};
+use crate::*;
+use std::collections::{BTreeMap, BTreeSet};
+
+impl OpenAPI {
+    /// Maps every component that is used within the document to the
+    /// locations that use it.
+    ///
+    /// Keys are references of the form `#/components/{kind}/{name}`. The
+    /// locations are JSON Pointers to the objects holding each `$ref`, such
+    /// as `/paths/~1pets/get/responses/200/content/application~1json/schema`,
+    /// to the discriminator mappings naming each schema, such as
+    /// `/components/schemas/Pet/discriminator/mapping/cat`, or to the
+    /// security requirements naming each security scheme, such as
+    /// `/paths/~1pets/get/security/0`. References to other documents are
+    /// not included.
+    pub fn reference_index(&self) -> BTreeMap<Reference, BTreeSet<String>> {
+        let mut index: BTreeMap<Reference, BTreeSet<String>> = BTreeMap::new();
+        let mut at = Location::default();
+        self.find_refs(&mut at, &mut |at: &Location, reference: &Reference| {
+            if reference.is_external() {
+                return;
+            }
+            if let (Some(kind), Some(name)) = (reference.component_kind(), reference.name()) {
+                index
+                    .entry(Reference::component(kind, &name))
+                    .or_default()
+                    .insert(at.as_str().to_owned());
+            }
+        });
+
+        for (location, schema) in self.all_schemas() {
+            let mapping = schema
+                .schema_data
+                .discriminator
+                .iter()
+                .flat_map(|discriminator| &discriminator.mapping);
+            for (value, target) in mapping {
+                let reference = match target_reference(target).parse::<Reference>() {
+                    Ok(reference) if !reference.is_external() => reference,
+                    _ => continue,
+                };
+                if let (Some(kind), Some(name)) = (reference.component_kind(), reference.name()) {
+                    index
+                        .entry(Reference::component(kind, &name))
+                        .or_default()
+                        .insert(format!(
+                            "{}/discriminator/mapping/{}",
+                            location,
+                            escape_pointer_segment(value)
+                        ));
+                }
+            }
+        }
+
+        let mut requirements = |location: &str, security: &[SecurityRequirement]| {
+            for (i, requirement) in security.iter().enumerate() {
+                for scheme in requirement.keys() {
+                    index
+                        .entry(Reference::component(ComponentKind::SecuritySchemes, scheme))
+                        .or_default()
+                        .insert(format!("{}/security/{}", location, i));
+                }
+            }
+        };
+        requirements("", &self.security);
+        at.with("paths", |at| {
+            for (path, item) in &self.paths {
+                let item = match item {
+                    ReferenceOr::Item(item) => item,
+                    ReferenceOr::Reference { .. } => continue,
+                };
+                at.with(path, |at| {
+                    for (method, operation) in item.operations() {
+                        at.with(method.as_str(), |at| {
+                            requirements(
+                                at.as_str(),
+                                operation.security.as_deref().unwrap_or_default(),
+                            )
+                        });
+                    }
+                });
+            }
+        });
+        for (location, _, operation) in self.callback_operations() {
+            requirements(&location, operation.security.as_deref().unwrap_or_default());
+        }
+        index
+    }
+
+    /// Removes the components that are not used by any path or security
+    /// requirement, directly or through other components, and returns
+    /// references to them.
+    ///
+    /// Components only used by other unused components are removed too,
+    /// as are groups of components only referring to each other.
+    pub fn remove_unused_components(&mut self) -> Vec<Reference> {
+        let index = self.reference_index();
+        let mut uses: BTreeMap<Reference, Vec<&Reference>> = BTreeMap::new();
+        let mut used = BTreeSet::new();
+        let mut pending = Vec::new();
+        for (target, locations) in &index {
+            for location in locations {
+                match containing_component(location) {
+                    Some(source) => uses.entry(source).or_default().push(target),
+                    None => {
+                        if used.insert(target) {
+                            pending.push(target);
+                        }
+                    }
+                }
+            }
+        }
+        while let Some(source) = pending.pop() {
+            for &target in uses.get(source).into_iter().flatten() {
+                if used.insert(target) {
+                    pending.push(target);
+                }
+            }
+        }
+
+        let components = match &mut self.components {
+            Some(components) => components,
+            None => return Vec::new(),
+        };
+        let mut unused = Vec::new();
+        for kind in ComponentKind::ALL.iter().copied() {
+            for name in components.names(kind) {
+                let reference = Reference::component(kind, name);
+                if !used.contains(&reference) {
+                    unused.push((kind, name.to_owned(), reference));
+                }
+            }
+        }
+        unused
+            .into_iter()
+            .map(|(kind, name, reference)| {
+                components.remove(kind, &name);
+                reference
+            })
+            .collect()
+    }
+}
+
+/// The component a location lies within, if any.
+fn containing_component(location: &str) -> Option<Reference> {
+    let mut segments = location.split('/');
+    match (
+        segments.next(),
+        segments.next(),
+        segments.next(),
+        segments.next(),
+    ) {
+        (Some(""), Some("components"), Some(kind), Some(name)) => Some(Reference::component(
+            kind.parse().ok()?,
+            &unescape_pointer_segment(name),
+        )),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn indexes_references() {
+        let api: OpenAPI = serde_yaml::from_str(
+            "
+openapi: 3.0.0
+info: {title: t, version: '1'}
+security: [{key: []}]
+paths:
+  /pets:
+    get:
+      security: [{}, {key: []}]
+      parameters: [{$ref: '#/components/parameters/limit'}]
+      responses:
+        '200':
+          description: pets
+          content:
+            application/json:
+              schema: {type: array, items: {$ref: '#/components/schemas/Pet'}}
+components:
+  schemas:
+    Pet:
+      type: object
+      properties:
+        parent: {$ref: '#/components/schemas/Pet'}
+        tag: {$ref: 'common.yaml#/components/schemas/Tag'}
+    Animal:
+      oneOf: [{$ref: '#/components/schemas/Pet'}]
+      discriminator:
+        propertyName: kind
+        mapping: {pet: '#/components/schemas/Pet', cat: Cat, dog: 'common.yaml#/Dog'}
+    Cat: {type: object}
+  parameters:
+    limit: {name: limit, in: query, schema: {type: integer}}
+  securitySchemes:
+    key: {type: apiKey, name: key, in: header}
+",
+        )
+        .unwrap();
+
+        let index = api.reference_index();
+        let locations = |reference: &str| -> Vec<&str> {
+            index[&reference.parse::<Reference>().unwrap()]
+                .iter()
+                .map(String::as_str)
+                .collect()
+        };
+        assert_eq!(index.len(), 4);
+        assert_eq!(
+            locations("#/components/schemas/Pet"),
+            vec![
+                "/components/schemas/Animal/discriminator/mapping/pet",
+                "/components/schemas/Animal/oneOf/0",
+                "/components/schemas/Pet/properties/parent",
+                "/paths/~1pets/get/responses/200/content/application~1json/schema/items",
+            ]
+        );
+        assert_eq!(
+            locations("#/components/schemas/Cat"),
+            vec!["/components/schemas/Animal/discriminator/mapping/cat"]
+        );
+        assert_eq!(
+            locations("#/components/parameters/limit"),
+            vec!["/paths/~1pets/get/parameters/0"]
+        );
+        assert_eq!(
+            locations("#/components/securitySchemes/key"),
+            vec!["/paths/~1pets/get/security/1", "/security/0"]
+        );
+    }
+
+    #[test]
+    fn removes_unused_components() {
+        let mut api: OpenAPI = serde_yaml::from_str(
+            "
+openapi: 3.0.0
+info: {title: t, version: '1'}
+paths:
+  /pets:
+    get:
+      security: [{key: []}]
+      responses:
+        '200': {$ref: '#/components/responses/Pets'}
+components:
+  responses:
+    Pets:
+      description: pets
+      content:
+        application/json:
+          schema: {type: array, items: {$ref: '#/components/schemas/Pet'}}
+  schemas:
+    Cat: {type: object}
+    Dog: {type: object}
+    Pet:
+      type: object
+      properties: {tag: {$ref: '#/components/schemas/Tag'}}
+      discriminator:
+        propertyName: kind
+        mapping: {cat: '#/components/schemas/Cat', dog: Dog}
+    Tag: {type: string}
+    Orphan: {type: object, properties: {back: {$ref: '#/components/schemas/Unused'}}}
+    Unused: {type: object, properties: {other: {$ref: '#/components/schemas/Orphan'}}}
+  securitySchemes:
+    key: {type: apiKey, name: key, in: header}
+    oauth: {type: http, scheme: basic}
+",
+        )
+        .unwrap();
+
+        assert_eq!(
+            api.remove_unused_components(),
+            vec![
+                "#/components/securitySchemes/oauth",
+                "#/components/schemas/Orphan",
+                "#/components/schemas/Unused",
+            ]
+        );
+        let components = api.components.as_ref().unwrap();
+        assert_eq!(
+            components.schemas.keys().collect::<Vec<_>>(),
+            vec!["Cat", "Dog", "Pet", "Tag"]
+        );
+        assert!(components.responses.contains_key("Pets"));
+        assert!(api.remove_unused_components().is_empty());
+    }
+}
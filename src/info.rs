@@ -1,10 +1,11 @@
 use crate::*;
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 
 /// The object provides metadata about the API.
 /// The metadata MAY be used by the clients if needed,
 /// and MAY be presented in editing or documentation generation tools for convenience.
-#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
 pub struct Info {
     /// REQUIRED. The title of the application.
     pub title: String,
@@ -25,4 +26,7 @@ pub struct Info {
     /// REQUIRED. The version of the OpenAPI document (which is distinct from
     /// the OpenAPI Specification version or the API implementation version).
     pub version: String,
+    /// Inline extensions to this object.
+    #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
+    pub extensions: IndexMap<String, serde_json::Value>,
 }
@@ -0,0 +1,1225 @@
+//! Synthesis of values that conform to schemas, whether plausible or
+//! random.
+
+use crate::*;
+use serde_json::Value;
+
+impl Schema {
+    /// A plausible value of the schema, such as a mock server might return,
+    /// resolving references in `components`.
+    ///
+    /// The schema's `example` is used if it has one, or else its `default`
+    /// or the first value of its `enum`. Otherwise the value is made up:
+    /// strings fit their `format` and lengths, numbers their bounds and
+    /// `multipleOf`, arrays have one item or as many as `minItems` asks for,
+    /// and objects have every property they declare. The first branch of a
+    /// `oneOf` or `anyOf` is taken, and an `allOf` is merged as by
+    /// [`Schema::flatten_all_of`]. Properties and items that would recurse
+    /// into a schema already being made up are left out where they may be,
+    /// and are otherwise `null`, as are references that do not resolve.
+    ///
+    /// Patterns are not taken into account.
+    ///
+    /// ```
+    /// use openapiv3::{Components, Schema};
+    /// use serde_json::json;
+    ///
+    /// let schema: Schema = serde_yaml::from_str(
+    ///     "
+    /// type: object
+    /// properties:
+    ///   id: {type: string, format: uuid}
+    ///   born: {type: string, format: date}
+    ///   kind: {type: string, enum: [dog, cat]}
+    ///   age: {type: integer, minimum: 1}
+    ///   tags: {type: array, minItems: 2, items: {type: string, example: good}}
+    /// ",
+    /// )
+    /// .unwrap();
+    /// assert_eq!(
+    ///     schema.example_value(&Components::default()),
+    ///     json!({
+    ///         "id": "3fa85f64-5717-4562-b3fc-2c963f66afa6",
+    ///         "born": "2021-01-01",
+    ///         "kind": "dog",
+    ///         "age": 1,
+    ///         "tags": ["good", "good"],
+    ///     })
+    /// );
+    /// ```
+    pub fn example_value(&self, components: &Components) -> Value {
+        self.example_value_with(components, &crate::format::BUILT_IN_FORMATS)
+    }
+
+    /// Like [`Schema::example_value`], making up values of the formats
+    /// registered in `formats` with their generators.
+    pub fn example_value_with(&self, components: &Components, formats: &FormatRegistry) -> Value {
+        Synthesizer {
+            components,
+            formats,
+            stack: Vec::new(),
+        }
+        .schema(self)
+    }
+}
+
+struct Synthesizer<'c> {
+    components: &'c Components,
+    formats: &'c FormatRegistry,
+    /// The references whose schemas are being made up, outermost first.
+    stack: Vec<String>,
+}
+
+impl Synthesizer<'_> {
+    /// Whether making up a value of the schema would recurse.
+    fn recurses(&self, schema: &ReferenceOr<Box<Schema>>) -> bool {
+        match schema {
+            ReferenceOr::Reference { reference } => {
+                self.stack.iter().any(|r| r == reference.as_str())
+            }
+            ReferenceOr::Item(_) => false,
+        }
+    }
+
+    fn boxed(&mut self, schema: &ReferenceOr<Box<Schema>>) -> Value {
+        let reference = match schema {
+            ReferenceOr::Item(schema) => return self.schema(schema),
+            ReferenceOr::Reference { reference } => reference.to_string(),
+        };
+        if self.recurses(schema) {
+            return Value::Null;
+        }
+        let components = self.components;
+        match components.resolve_boxed(schema) {
+            Ok(schema) => {
+                self.stack.push(reference);
+                let value = self.schema(schema);
+                self.stack.pop();
+                value
+            }
+            Err(_) => Value::Null,
+        }
+    }
+
+    fn schema(&mut self, schema: &Schema) -> Value {
+        let data = &schema.schema_data;
+        if let Some(value) = data.example.as_ref().or(data.default.as_ref()) {
+            return value.clone();
+        }
+        if let Some(value) = schema
+            .free_format()
+            .and_then(|format| self.formats.make(format, &mut SeededRng::new(0)))
+        {
+            return value;
+        }
+        match &schema.schema_kind {
+            SchemaKind::Type(kind) => self.typed(kind),
+            SchemaKind::OneOf { one_of: branches } | SchemaKind::AnyOf { any_of: branches } => {
+                match branches.first() {
+                    Some(branch) => self.boxed(&branch.clone().map(Box::new)),
+                    None => Value::Null,
+                }
+            }
+            SchemaKind::AllOf { all_of } => match schema.flatten_all_of(self.components) {
+                Ok(merged) => self.schema(&merged),
+                // Branches that cannot be merged may still give an object
+                // with the properties of each.
+                Err(_) => all_of.iter().fold(Value::Null, |merged, branch| {
+                    match (merged, self.boxed(&branch.clone().map(Box::new))) {
+                        (Value::Object(mut merged), Value::Object(value)) => {
+                            merged.extend(value);
+                            Value::Object(merged)
+                        }
+                        (merged, Value::Null) => merged,
+                        (_, value) => value,
+                    }
+                }),
+            },
+            SchemaKind::Any(any) => self.any(any),
+        }
+    }
+
+    fn typed(&mut self, kind: &Type) -> Value {
+        match kind {
+            Type::String(string) => match string.enumeration.first() {
+                Some(value) => Value::from(value.as_str()),
+                None => Value::from(fake_string(
+                    string.format.name(StringFormat::as_str),
+                    string.min_length,
+                    string.max_length,
+                )),
+            },
+            Type::Number(number) => match number.enumeration.first() {
+                Some(value) => Value::from(*value),
+                None => Value::from(fake_number(
+                    (number.minimum, number.exclusive_minimum),
+                    (number.maximum, number.exclusive_maximum),
+                    number.multiple_of,
+                )),
+            },
+            Type::Integer(integer) => match integer.enumeration.first() {
+                Some(value) => Value::from(*value),
+                None => Value::from(fake_integer(
+                    (integer.minimum, integer.exclusive_minimum),
+                    (integer.maximum, integer.exclusive_maximum),
+                    integer.multiple_of,
+                )),
+            },
+            Type::Object(object) => self.object(&object.properties, &object.required),
+            Type::Array(array) => self.array(&array.items, array.min_items, array.max_items),
+            Type::Boolean {} => Value::Bool(true),
+        }
+    }
+
+    /// A value of an untyped schema, of the type its keywords suggest.
+    fn any(&mut self, any: &AnySchema) -> Value {
+        if !any.properties.is_empty() || !any.required.is_empty() {
+            self.object(&any.properties, &any.required)
+        } else if let Some(items) = &any.items {
+            self.array(items, any.min_items, any.max_items)
+        } else if any.format.is_some()
+            || any.pattern.is_some()
+            || any.min_length.is_some()
+            || any.max_length.is_some()
+        {
+            Value::from(fake_string(
+                any.format.as_deref(),
+                any.min_length,
+                any.max_length,
+            ))
+        } else if any.minimum.is_some() || any.maximum.is_some() || any.multiple_of.is_some() {
+            Value::from(fake_number(
+                (any.minimum, any.exclusive_minimum.unwrap_or(false)),
+                (any.maximum, any.exclusive_maximum.unwrap_or(false)),
+                any.multiple_of,
+            ))
+        } else {
+            Value::Null
+        }
+    }
+
+    fn object(
+        &mut self,
+        properties: &Map<Name, ReferenceOr<Box<Schema>>>,
+        required: &[Name],
+    ) -> Value {
+        let mut object = serde_json::Map::new();
+        for (name, schema) in properties {
+            let value = if !self.recurses(schema) {
+                self.boxed(schema)
+            } else if required.contains(name) {
+                Value::Null
+            } else {
+                continue;
+            };
+            object.insert(name.to_string(), value);
+        }
+        for name in required {
+            if !object.contains_key(name.as_str()) {
+                object.insert(name.to_string(), Value::Null);
+            }
+        }
+        Value::Object(object)
+    }
+
+    fn array(
+        &mut self,
+        items: &ReferenceOr<Box<Schema>>,
+        min_items: Option<usize>,
+        max_items: Option<usize>,
+    ) -> Value {
+        let len = match (min_items.unwrap_or(1), max_items) {
+            (len, Some(max)) => len.min(max),
+            (len, None) => len,
+        };
+        if len == 0 || (min_items.unwrap_or(0) == 0 && self.recurses(items)) {
+            return Value::Array(Vec::new());
+        }
+        let item = self.boxed(items);
+        Value::Array(vec![item; len])
+    }
+}
+
+/// A string of a format, or else of the lengths given.
+fn fake_string(
+    format: Option<&str>,
+    min_length: Option<usize>,
+    max_length: Option<usize>,
+) -> String {
+    let formatted = match format {
+        Some("date") => "2021-01-01",
+        Some("date-time") => "2021-01-01T00:00:00Z",
+        Some("time") => "00:00:00",
+        Some("byte") => "c3RyaW5n",
+        Some("password") => "password",
+        Some("uuid") => "3fa85f64-5717-4562-b3fc-2c963f66afa6",
+        Some("email") => "user@example.com",
+        Some("uri") | Some("url") => "https://example.com/",
+        Some("hostname") => "example.com",
+        Some("ipv4") => "192.0.2.1",
+        Some("ipv6") => "2001:db8::1",
+        _ => "",
+    };
+    if !formatted.is_empty() {
+        return formatted.to_owned();
+    }
+    let min = min_length.unwrap_or(0);
+    let len = match max_length {
+        Some(max) => "string".len().max(min).min(max),
+        None => "string".len().max(min),
+    };
+    "string".chars().cycle().take(len).collect()
+}
+
+/// A number within bounds, each given with whether it is exclusive, and a
+/// multiple of `multiple_of` if there is one: the one closest to zero.
+fn fake_number(
+    minimum: (Option<f64>, bool),
+    maximum: (Option<f64>, bool),
+    multiple_of: Option<f64>,
+) -> f64 {
+    let mut n = 0.0_f64;
+    if let (Some(min), exclusive) = minimum {
+        if n < min || (exclusive && n <= min) {
+            n = match maximum.0 {
+                Some(max) if exclusive => (min + max) / 2.0,
+                _ if exclusive => min + 1.0,
+                _ => min,
+            };
+        }
+    }
+    if let (Some(max), exclusive) = maximum {
+        if n > max || (exclusive && n >= max) {
+            n = match minimum.0 {
+                Some(min) if exclusive => (min + max) / 2.0,
+                _ if exclusive => max - 1.0,
+                _ => max,
+            };
+        }
+    }
+    // Bounds are on the side of zero that `n` moved to, if it moved.
+    match multiple_of {
+        Some(step) if step > 0.0 && n > 0.0 => (n / step).ceil() * step,
+        Some(step) if step > 0.0 => (n / step).floor() * step,
+        _ => n,
+    }
+}
+
+/// As [`fake_number`], for integers.
+fn fake_integer(
+    minimum: (Option<i64>, bool),
+    maximum: (Option<i64>, bool),
+    multiple_of: Option<i64>,
+) -> i64 {
+    let min = minimum.0.map(|min| {
+        if minimum.1 {
+            min.saturating_add(1)
+        } else {
+            min
+        }
+    });
+    let max = maximum.0.map(|max| {
+        if maximum.1 {
+            max.saturating_sub(1)
+        } else {
+            max
+        }
+    });
+    let mut n = 0_i64;
+    if let Some(min) = min {
+        n = n.max(min);
+    }
+    if let Some(max) = max {
+        n = n.min(max);
+    }
+    match multiple_of {
+        Some(step) if step > 0 && n > 0 => n.saturating_add(step - 1) / step * step,
+        Some(step) if step > 0 => n - n.rem_euclid(step),
+        _ => n,
+    }
+}
+
+/// A source of randomness for [`Schema::generate`], so that any generator
+/// can be used; [`SeededRng`] is a small one that can be seeded.
+pub trait Rng {
+    /// The next of a sequence of uniformly distributed numbers.
+    fn next_u64(&mut self) -> u64;
+}
+
+impl<R: Rng + ?Sized> Rng for &mut R {
+    fn next_u64(&mut self) -> u64 {
+        (**self).next_u64()
+    }
+}
+
+/// A SplitMix64 generator: fast and reproducible from its seed, but not
+/// for cryptography.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeededRng {
+    state: u64,
+}
+
+impl SeededRng {
+    pub fn new(seed: u64) -> Self {
+        SeededRng { state: seed }
+    }
+}
+
+impl Rng for SeededRng {
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+/// Limits and odds for [`Schema::generate`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct GenOptions {
+    /// The most items arrays have beyond their `minItems`, unless their
+    /// `maxItems` says otherwise.
+    pub max_items: usize,
+    /// The most characters strings have beyond their `minLength`, unless
+    /// their `maxLength` says otherwise.
+    pub max_length: usize,
+    /// The chance, from 0 to 1, that an optional property is present.
+    pub optional_properties: f64,
+    /// The chance, from 0 to 1, that a nullable value is `null`.
+    pub nulls: f64,
+    /// How deeply arrays and objects nest before they are given only the
+    /// items and properties they require.
+    pub max_depth: usize,
+}
+
+impl Default for GenOptions {
+    fn default() -> Self {
+        GenOptions {
+            max_items: 3,
+            max_length: 12,
+            optional_properties: 0.5,
+            nulls: 0.1,
+            max_depth: 4,
+        }
+    }
+}
+
+impl Schema {
+    /// A random value of the schema, resolving references in `components`,
+    /// for tests that want more variety than [`Schema::example_value`].
+    ///
+    /// Values respect `enum`, `format`, the bounds of numbers, strings,
+    /// arrays and objects, `multipleOf`, `uniqueItems`, `required` and
+    /// `nullable`, and patterns of the common regular expression syntax:
+    /// literals, `.`, classes such as `[a-z]` and `\d`, groups,
+    /// alternatives and quantifiers. A random branch of a `oneOf` or `anyOf`
+    /// is taken, and an `allOf` is merged as by [`Schema::flatten_all_of`].
+    /// Examples and defaults are not used. Constraints that cannot be met
+    /// together, such as patterns of other syntax along with lengths, are
+    /// met where possible.
+    ///
+    /// ```
+    /// use openapiv3::{Components, GenOptions, Schema, SeededRng};
+    ///
+    /// let schema: Schema = serde_yaml::from_str(
+    ///     "
+    /// type: object
+    /// required: [id, code]
+    /// properties:
+    ///   id: {type: integer, minimum: 1, maximum: 9}
+    ///   code: {type: string, pattern: '^[A-Z]{3}-\\d{2,4}$'}
+    /// ",
+    /// )
+    /// .unwrap();
+    /// let components = Components::default();
+    /// let mut rng = SeededRng::new(7);
+    /// for _ in 0..20 {
+    ///     let value = schema.generate(&components, &mut rng, &GenOptions::default());
+    ///     assert!(schema.validate_value(&value).is_ok(), "{}", value);
+    /// }
+    /// ```
+    pub fn generate(
+        &self,
+        components: &Components,
+        rng: &mut impl Rng,
+        options: &GenOptions,
+    ) -> Value {
+        self.generate_with(components, rng, options, &crate::format::BUILT_IN_FORMATS)
+    }
+
+    /// Like [`Schema::generate`], making up values of the formats
+    /// registered in `formats` with their generators.
+    pub fn generate_with(
+        &self,
+        components: &Components,
+        rng: &mut impl Rng,
+        options: &GenOptions,
+        formats: &FormatRegistry,
+    ) -> Value {
+        Generator {
+            components,
+            formats,
+            rng,
+            options,
+            depth: 0,
+        }
+        .schema(self)
+    }
+}
+
+struct Generator<'a, R> {
+    components: &'a Components,
+    formats: &'a FormatRegistry,
+    rng: R,
+    options: &'a GenOptions,
+    /// How many arrays and objects the value being generated is nested in.
+    depth: usize,
+}
+
+impl<R: Rng> Generator<'_, R> {
+    /// A number from 0 up to, but not including, `n`, which must not be 0.
+    fn below(&mut self, n: u64) -> u64 {
+        self.rng.next_u64() % n
+    }
+
+    /// A number from 0 up to, but not including, 1.
+    fn unit(&mut self) -> f64 {
+        (self.rng.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn chance(&mut self, p: f64) -> bool {
+        self.unit() < p
+    }
+
+    /// A number from `lo` to `hi`, both included.
+    fn between(&mut self, lo: i64, hi: i64) -> i64 {
+        let span = (hi as i128 - lo as i128 + 1) as u128;
+        let offset = if span > u64::MAX as u128 {
+            self.rng.next_u64() as u128
+        } else {
+            self.below(span as u64) as u128
+        };
+        (lo as i128 + offset as i128) as i64
+    }
+
+    fn pick<'t, T>(&mut self, items: &'t [T]) -> Option<&'t T> {
+        match items.len() {
+            0 => None,
+            len => Some(&items[self.below(len as u64) as usize]),
+        }
+    }
+
+    /// Whether nested arrays and objects should have no more than they
+    /// require.
+    fn too_deep(&self) -> bool {
+        self.depth >= self.options.max_depth
+    }
+
+    fn boxed(&mut self, schema: &ReferenceOr<Box<Schema>>) -> Value {
+        // References nest no deeper than arrays and objects require.
+        if self.depth > self.options.max_depth * 2 {
+            return Value::Null;
+        }
+        let components = self.components;
+        match components.resolve_boxed(schema) {
+            Ok(schema) => self.schema(schema),
+            Err(_) => Value::Null,
+        }
+    }
+
+    fn schema(&mut self, schema: &Schema) -> Value {
+        let nulls = self.options.nulls;
+        if schema.schema_data.nullable && self.chance(nulls) {
+            return Value::Null;
+        }
+        if let Some(format) = schema.free_format() {
+            if let Some(value) = self.formats.make(format, &mut self.rng) {
+                return value;
+            }
+        }
+        match &schema.schema_kind {
+            SchemaKind::Type(kind) => self.typed(kind),
+            SchemaKind::OneOf { one_of: branches } | SchemaKind::AnyOf { any_of: branches } => {
+                match self.pick(branches) {
+                    Some(branch) => self.boxed(&branch.clone().map(Box::new)),
+                    None => Value::Null,
+                }
+            }
+            SchemaKind::AllOf { all_of } => match schema.flatten_all_of(self.components) {
+                Ok(merged) => self.schema(&merged),
+                Err(_) => all_of.iter().fold(Value::Null, |merged, branch| {
+                    match (merged, self.boxed(&branch.clone().map(Box::new))) {
+                        (Value::Object(mut merged), Value::Object(value)) => {
+                            merged.extend(value);
+                            Value::Object(merged)
+                        }
+                        (merged, Value::Null) => merged,
+                        (_, value) => value,
+                    }
+                }),
+            },
+            SchemaKind::Any(any) => self.any(any),
+        }
+    }
+
+    fn typed(&mut self, kind: &Type) -> Value {
+        match kind {
+            Type::String(string) => match self.pick(&string.enumeration) {
+                Some(value) => Value::from(value.as_str()),
+                None => Value::from(self.string(
+                    string.format.name(StringFormat::as_str),
+                    string.pattern.as_deref(),
+                    string.min_length,
+                    string.max_length,
+                )),
+            },
+            Type::Number(number) => match self.pick(&number.enumeration) {
+                Some(value) => Value::from(*value),
+                None => Value::from(self.number(
+                    (number.minimum, number.exclusive_minimum),
+                    (number.maximum, number.exclusive_maximum),
+                    number.multiple_of,
+                )),
+            },
+            Type::Integer(integer) => match self.pick(&integer.enumeration) {
+                Some(value) => Value::from(*value),
+                None => {
+                    let (lo, hi) = match integer.format.name(IntegerFormat::as_str) {
+                        Some("int32") => (i32::MIN as i64, i32::MAX as i64),
+                        _ => (i64::MIN, i64::MAX),
+                    };
+                    let bound = |bound: Option<i64>| bound.map(|bound| bound.clamp(lo, hi));
+                    Value::from(self.integer(
+                        (bound(integer.minimum), integer.exclusive_minimum),
+                        (bound(integer.maximum), integer.exclusive_maximum),
+                        integer.multiple_of,
+                    ))
+                }
+            },
+            Type::Object(object) => self.object(&object.properties, &object.required),
+            Type::Array(array) => self.array(
+                &array.items,
+                array.min_items,
+                array.max_items,
+                array.unique_items,
+            ),
+            Type::Boolean {} => Value::Bool(self.chance(0.5)),
+        }
+    }
+
+    fn any(&mut self, any: &AnySchema) -> Value {
+        if !any.properties.is_empty() || !any.required.is_empty() {
+            self.object(&any.properties, &any.required)
+        } else if let Some(items) = &any.items {
+            self.array(
+                items,
+                any.min_items,
+                any.max_items,
+                any.unique_items.unwrap_or(false),
+            )
+        } else if any.format.is_some()
+            || any.pattern.is_some()
+            || any.min_length.is_some()
+            || any.max_length.is_some()
+        {
+            Value::from(self.string(
+                any.format.as_deref(),
+                any.pattern.as_deref(),
+                any.min_length,
+                any.max_length,
+            ))
+        } else if any.minimum.is_some() || any.maximum.is_some() || any.multiple_of.is_some() {
+            Value::from(self.number(
+                (any.minimum, any.exclusive_minimum.unwrap_or(false)),
+                (any.maximum, any.exclusive_maximum.unwrap_or(false)),
+                any.multiple_of,
+            ))
+        } else {
+            Value::Null
+        }
+    }
+
+    fn string(
+        &mut self,
+        format: Option<&str>,
+        pattern: Option<&str>,
+        min_length: Option<usize>,
+        max_length: Option<usize>,
+    ) -> String {
+        let min = min_length.unwrap_or(0);
+        let max = max_length.unwrap_or(min + self.options.max_length).max(min);
+        let fits = |s: &str| (min..=max).contains(&s.chars().count());
+        if let Some(pattern) = pattern {
+            if let (Ok(regex), Some(parsed)) = (regex::Regex::new(pattern), parse_pattern(pattern))
+            {
+                for _ in 0..20 {
+                    let mut s = String::new();
+                    self.pattern(&parsed, &mut s);
+                    if fits(&s) && regex.is_match(&s) {
+                        return s;
+                    }
+                }
+            }
+        }
+        let hex = |generator: &mut Self, len| -> String {
+            (0..len)
+                .map(|_| char::from(b"0123456789abcdef"[generator.below(16) as usize]))
+                .collect()
+        };
+        let formatted = match format {
+            Some("date") => Some(self.date()),
+            Some("date-time") => Some(format!("{}T{}Z", self.date(), self.time())),
+            Some("time") => Some(self.time()),
+            Some("uuid") => Some(format!(
+                "{}-{}-4{}-{}{}-{}",
+                hex(self, 8),
+                hex(self, 4),
+                hex(self, 3),
+                char::from(b"89ab"[self.below(4) as usize]),
+                hex(self, 3),
+                hex(self, 12)
+            )),
+            Some("email") => Some(format!("{}@example.com", self.word(3, 10))),
+            Some("uri") | Some("url") => Some(format!("https://example.com/{}", self.word(1, 10))),
+            Some("hostname") => Some(format!("{}.example.com", self.word(1, 10))),
+            Some("ipv4") => Some(format!("192.0.2.{}", self.below(256))),
+            Some("ipv6") => Some(format!("2001:db8::{:x}", self.below(0x10000))),
+            Some("byte") => {
+                let groups = self.between(min.div_ceil(4) as i64, (max / 4).max(1) as i64);
+                const BASE64: &[u8] =
+                    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+                let encoded = (0..groups * 4)
+                    .map(|_| char::from(BASE64[self.below(64) as usize]))
+                    .collect();
+                Some(encoded)
+            }
+            _ => None,
+        };
+        if let Some(formatted) = formatted {
+            return formatted;
+        }
+        self.word(min, max)
+    }
+
+    /// Letters and digits, from `min` to `max` of them.
+    fn word(&mut self, min: usize, max: usize) -> String {
+        const CHARACTERS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+        let len = self.between(min as i64, max as i64);
+        (0..len)
+            .map(|_| char::from(CHARACTERS[self.below(CHARACTERS.len() as u64) as usize]))
+            .collect()
+    }
+
+    fn date(&mut self) -> String {
+        format!(
+            "{:04}-{:02}-{:02}",
+            self.between(1970, 2037),
+            self.between(1, 12),
+            self.between(1, 28)
+        )
+    }
+
+    fn time(&mut self) -> String {
+        format!(
+            "{:02}:{:02}:{:02}",
+            self.below(24),
+            self.below(60),
+            self.below(60)
+        )
+    }
+
+    fn pattern(&mut self, nodes: &[PatternNode], out: &mut String) {
+        for node in nodes {
+            match node {
+                PatternNode::Literal(c) => out.push(*c),
+                PatternNode::Class { ranges, negated } => out.push(self.class(ranges, *negated)),
+                PatternNode::Group(alternatives) => {
+                    if let Some(alternative) = self.pick(alternatives) {
+                        self.pattern(alternative, out);
+                    }
+                }
+                PatternNode::Repeat { node, min, max } => {
+                    let max = max.unwrap_or(min + 3);
+                    for _ in 0..self.between(*min as i64, max as i64) {
+                        self.pattern(std::slice::from_ref(node), out);
+                    }
+                }
+            }
+        }
+    }
+
+    /// A character in one of `ranges`, or if `negated`, a printable ASCII
+    /// character in none of them.
+    fn class(&mut self, ranges: &[(char, char)], negated: bool) -> char {
+        if negated {
+            let allowed: Vec<char> = (' '..='~')
+                .filter(|c| !ranges.iter().any(|(lo, hi)| (lo..=hi).contains(&c)))
+                .collect();
+            return self.pick(&allowed).copied().unwrap_or(' ');
+        }
+        match self.pick(ranges) {
+            Some(&(lo, hi)) => {
+                let c = self.between(lo as i64, hi as i64);
+                char::from_u32(c as u32).unwrap_or(lo)
+            }
+            None => ' ',
+        }
+    }
+
+    fn number(
+        &mut self,
+        minimum: (Option<f64>, bool),
+        maximum: (Option<f64>, bool),
+        multiple_of: Option<f64>,
+    ) -> f64 {
+        let lo = minimum
+            .0
+            .unwrap_or_else(|| maximum.0.map_or(-100.0, |max| max - 100.0));
+        let hi = maximum.0.unwrap_or(lo + 100.0).max(lo);
+        let within = |n: f64| {
+            (if minimum.1 { n > lo } else { n >= lo }) && (if maximum.1 { n < hi } else { n <= hi })
+        };
+        if let Some(step) = multiple_of.filter(|step| *step > 0.0) {
+            let first = (lo / step).ceil() as i64;
+            let last = (hi / step).floor() as i64;
+            for _ in 0..10 {
+                if first > last {
+                    break;
+                }
+                let n = self.between(first, last) as f64 * step;
+                if within(n) {
+                    return n;
+                }
+            }
+            return fake_number(minimum, maximum, multiple_of);
+        }
+        for _ in 0..10 {
+            let n = lo + self.unit() * (hi - lo);
+            if within(n) {
+                return n;
+            }
+        }
+        (lo + hi) / 2.0
+    }
+
+    fn integer(
+        &mut self,
+        minimum: (Option<i64>, bool),
+        maximum: (Option<i64>, bool),
+        multiple_of: Option<i64>,
+    ) -> i64 {
+        let min = minimum.0.map(|min| {
+            if minimum.1 {
+                min.saturating_add(1)
+            } else {
+                min
+            }
+        });
+        let max = maximum.0.map(|max| {
+            if maximum.1 {
+                max.saturating_sub(1)
+            } else {
+                max
+            }
+        });
+        let (lo, hi) = match (min, max) {
+            (Some(lo), Some(hi)) => (lo, hi.max(lo)),
+            (Some(lo), None) => (lo, lo.saturating_add(100)),
+            (None, Some(hi)) => (hi.saturating_sub(100), hi),
+            (None, None) => (-100, 100),
+        };
+        match multiple_of.filter(|step| *step > 0) {
+            Some(step) => {
+                let first = lo.div_euclid(step) + i64::from(lo.rem_euclid(step) != 0);
+                let last = hi.div_euclid(step);
+                if first > last {
+                    fake_integer(minimum, maximum, multiple_of)
+                } else {
+                    self.between(first, last).saturating_mul(step)
+                }
+            }
+            None => self.between(lo, hi),
+        }
+    }
+
+    fn object(
+        &mut self,
+        properties: &Map<Name, ReferenceOr<Box<Schema>>>,
+        required: &[Name],
+    ) -> Value {
+        let optional_properties = self.options.optional_properties;
+        let too_deep = self.too_deep();
+        self.depth += 1;
+        let mut object = serde_json::Map::new();
+        for (name, schema) in properties {
+            if required.contains(name) || (!too_deep && self.chance(optional_properties)) {
+                let value = self.boxed(schema);
+                object.insert(name.to_string(), value);
+            }
+        }
+        for name in required {
+            if !object.contains_key(name.as_str()) {
+                object.insert(name.to_string(), Value::Null);
+            }
+        }
+        self.depth -= 1;
+        Value::Object(object)
+    }
+
+    fn array(
+        &mut self,
+        items: &ReferenceOr<Box<Schema>>,
+        min_items: Option<usize>,
+        max_items: Option<usize>,
+        unique_items: bool,
+    ) -> Value {
+        let min = min_items.unwrap_or(0);
+        let max = match max_items {
+            _ if self.too_deep() => min,
+            Some(max) => max.max(min),
+            None => min + self.options.max_items,
+        };
+        let len = self.between(min as i64, max as i64) as usize;
+        self.depth += 1;
+        let mut array = Vec::with_capacity(len);
+        // Items that repeat others are tried again a few times.
+        let mut attempts = len * 10;
+        while array.len() < len && attempts > 0 {
+            let item = self.boxed(items);
+            attempts -= 1;
+            if !unique_items || !array.contains(&item) {
+                array.push(item);
+            }
+        }
+        self.depth -= 1;
+        Value::Array(array)
+    }
+}
+
+/// A part of a regular expression, as much of their syntax as
+/// [`Schema::generate`] generates strings for.
+#[derive(Debug, Clone, PartialEq)]
+enum PatternNode {
+    Literal(char),
+    /// Any character in one of the ranges, or if `negated`, in none.
+    Class {
+        ranges: Vec<(char, char)>,
+        negated: bool,
+    },
+    /// Alternatives, one of which is chosen.
+    Group(Vec<Vec<PatternNode>>),
+    Repeat {
+        node: Box<PatternNode>,
+        min: usize,
+        max: Option<usize>,
+    },
+}
+
+/// Parses a pattern into the parts to generate strings from, or `None` if
+/// it uses other syntax. Anchors are dropped, as generated strings are
+/// matched as a whole.
+fn parse_pattern(pattern: &str) -> Option<Vec<PatternNode>> {
+    let mut chars = pattern.chars().peekable();
+    let alternatives = parse_alternatives(&mut chars)?;
+    if chars.next().is_some() {
+        return None;
+    }
+    Some(vec![PatternNode::Group(alternatives)])
+}
+
+type Chars<'a> = std::iter::Peekable<std::str::Chars<'a>>;
+
+fn parse_alternatives(chars: &mut Chars<'_>) -> Option<Vec<Vec<PatternNode>>> {
+    let mut alternatives = vec![Vec::new()];
+    while let Some(&c) = chars.peek() {
+        let node = match c {
+            ')' => break,
+            '|' => {
+                chars.next();
+                alternatives.push(Vec::new());
+                continue;
+            }
+            '^' | '$' => {
+                chars.next();
+                continue;
+            }
+            '(' => {
+                chars.next();
+                if chars.peek() == Some(&'?') {
+                    chars.next();
+                    if chars.next() != Some(':') {
+                        return None;
+                    }
+                }
+                let group = parse_alternatives(chars)?;
+                if chars.next() != Some(')') {
+                    return None;
+                }
+                PatternNode::Group(group)
+            }
+            '[' => {
+                chars.next();
+                parse_class(chars)?
+            }
+            '.' => {
+                chars.next();
+                PatternNode::Class {
+                    ranges: vec![('\n', '\n')],
+                    negated: true,
+                }
+            }
+            '\\' => {
+                chars.next();
+                parse_escape(chars.next()?)?
+            }
+            '*' | '+' | '?' | '{' | ']' | '}' => return None,
+            _ => {
+                chars.next();
+                PatternNode::Literal(c)
+            }
+        };
+        let node = parse_quantifier(chars, node)?;
+        alternatives.last_mut()?.push(node);
+    }
+    Some(alternatives)
+}
+
+fn parse_quantifier(chars: &mut Chars<'_>, node: PatternNode) -> Option<PatternNode> {
+    let (min, max) = match chars.peek() {
+        Some('*') => (0, None),
+        Some('+') => (1, None),
+        Some('?') => (0, Some(1)),
+        Some('{') => {
+            chars.next();
+            let mut bounds = String::new();
+            loop {
+                match chars.next()? {
+                    '}' => break,
+                    c => bounds.push(c),
+                }
+            }
+            let (min, max) = match bounds.split_once(',') {
+                Some((min, "")) => (min.parse().ok()?, None),
+                Some((min, max)) => (min.parse().ok()?, Some(max.parse().ok()?)),
+                None => {
+                    let n = bounds.parse().ok()?;
+                    (n, Some(n))
+                }
+            };
+            return parse_quantifier_end(
+                chars,
+                PatternNode::Repeat {
+                    node: Box::new(node),
+                    min,
+                    max,
+                },
+            );
+        }
+        _ => return Some(node),
+    };
+    chars.next();
+    parse_quantifier_end(
+        chars,
+        PatternNode::Repeat {
+            node: Box::new(node),
+            min,
+            max,
+        },
+    )
+}
+
+/// Skips the `?` of a lazy quantifier, which matches the same strings.
+fn parse_quantifier_end(chars: &mut Chars<'_>, node: PatternNode) -> Option<PatternNode> {
+    if chars.peek() == Some(&'?') {
+        chars.next();
+    }
+    match chars.peek() {
+        Some('*') | Some('+') | Some('?') | Some('{') => None,
+        _ => Some(node),
+    }
+}
+
+fn parse_escape(c: char) -> Option<PatternNode> {
+    let class = |ranges: &[(char, char)], negated| PatternNode::Class {
+        ranges: ranges.to_vec(),
+        negated,
+    };
+    const DIGIT: &[(char, char)] = &[('0', '9')];
+    const WORD: &[(char, char)] = &[('a', 'z'), ('A', 'Z'), ('0', '9'), ('_', '_')];
+    const SPACE: &[(char, char)] = &[(' ', ' '), ('\t', '\r')];
+    Some(match c {
+        'd' => class(DIGIT, false),
+        'D' => class(DIGIT, true),
+        'w' => class(WORD, false),
+        'W' => class(WORD, true),
+        's' => class(SPACE, false),
+        'S' => class(SPACE, true),
+        'n' => PatternNode::Literal('\n'),
+        't' => PatternNode::Literal('\t'),
+        'r' => PatternNode::Literal('\r'),
+        c if c.is_ascii_alphanumeric() => return None,
+        c => PatternNode::Literal(c),
+    })
+}
+
+/// Parses a class, following its `[`.
+fn parse_class(chars: &mut Chars<'_>) -> Option<PatternNode> {
+    let negated = chars.peek() == Some(&'^');
+    if negated {
+        chars.next();
+    }
+    let mut ranges = Vec::new();
+    let mut first = true;
+    loop {
+        let c = chars.next()?;
+        let lo = match c {
+            ']' if !first => break,
+            '\\' => match parse_escape(chars.next()?)? {
+                PatternNode::Literal(c) => c,
+                PatternNode::Class {
+                    ranges: escaped,
+                    negated: false,
+                } => {
+                    ranges.extend(escaped);
+                    first = false;
+                    continue;
+                }
+                _ => return None,
+            },
+            '[' => return None,
+            c => c,
+        };
+        first = false;
+        let mut ahead = chars.clone();
+        let hi = match (ahead.next(), ahead.next()) {
+            (Some('-'), Some(hi)) if hi != ']' => {
+                chars.next();
+                chars.next();
+                match hi {
+                    '\\' => match parse_escape(chars.next()?)? {
+                        PatternNode::Literal(hi) => hi,
+                        _ => return None,
+                    },
+                    hi => hi,
+                }
+            }
+            _ => lo,
+        };
+        if hi < lo {
+            return None;
+        }
+        ranges.push((lo, hi));
+    }
+    Some(PatternNode::Class { ranges, negated })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn makes_up_values() {
+        let components: Components = serde_yaml::from_str(
+            "
+schemas:
+  Node:
+    type: object
+    required: [name]
+    properties:
+      name: {type: string, minLength: 8, maxLength: 9}
+      code: {type: string, maxLength: 3}
+      parent: {$ref: '#/components/schemas/Node'}
+      children: {type: array, items: {$ref: '#/components/schemas/Node'}}
+  Pet:
+    allOf:
+      - {$ref: '#/components/schemas/Node'}
+      - type: object
+        required: [weight]
+        properties:
+          weight: {type: number, minimum: 0, exclusiveMinimum: true, maximum: 10, multipleOf: 0.5}
+          legs: {type: integer, minimum: 1, multipleOf: 2}
+          cost: {type: integer, maximum: -3, multipleOf: 2}
+          kind: {oneOf: [{type: boolean}, {type: string}]}
+          owner: {default: nobody}
+          extra: {}
+",
+        )
+        .unwrap();
+        let pet = components.resolve_reference::<Schema>("#/components/schemas/Pet");
+        assert_eq!(
+            pet.unwrap().example_value(&components),
+            json!({
+                "name": "stringst",
+                "code": "str",
+                "parent": {"name": "stringst", "code": "str", "children": []},
+                "children": [{"name": "stringst", "code": "str", "children": []}],
+                "weight": 5.0,
+                "legs": 2,
+                "cost": -4,
+                "kind": true,
+                "owner": "nobody",
+                "extra": null,
+            })
+        );
+
+        let schema: Schema =
+            serde_yaml::from_str("{type: array, maxItems: 0, items: {type: string}}").unwrap();
+        assert_eq!(schema.example_value(&components), json!([]));
+    }
+
+    #[test]
+    fn generates_values() {
+        let components: Components = serde_yaml::from_str(
+            "
+schemas:
+  Node:
+    type: object
+    required: [id, tags]
+    properties:
+      id: {type: string, format: uuid}
+      code: {type: string, pattern: '^(ab|c[x-z]+)\\.\\d{2}$', maxLength: 8}
+      word: {type: string, minLength: 2, maxLength: 4}
+      born: {type: string, format: date-time, nullable: true}
+      bytes: {type: string, format: byte}
+      weight: {type: number, minimum: 0, exclusiveMinimum: true, maximum: 1}
+      count: {type: integer, format: int32, maximum: 20, multipleOf: 5}
+      kind: {type: string, enum: [dog, cat]}
+      pick: {oneOf: [{type: boolean}, {type: integer, minimum: 3, maximum: 3}]}
+      tags: {type: array, minItems: 1, maxItems: 3, uniqueItems: true, items: {type: integer, minimum: 0, maximum: 2}}
+      children: {type: array, items: {$ref: '#/components/schemas/Node'}}
+",
+        )
+        .unwrap();
+        let node = components
+            .resolve_reference::<Schema>("#/components/schemas/Node")
+            .unwrap();
+        let mut rng = SeededRng::new(42);
+        let options = GenOptions::default();
+        let values: Vec<Value> = (0..50)
+            .map(|_| node.generate(&components, &mut rng, &options))
+            .collect();
+        for value in &values {
+            assert_eq!(
+                node.validate_value_in(&components, value),
+                Ok(()),
+                "{}",
+                value
+            );
+        }
+        assert!(values.windows(2).any(|pair| pair[0] != pair[1]));
+        assert!(values.iter().any(|value| value.get("children").is_some()));
+
+        let mut again = SeededRng::new(42);
+        assert_eq!(node.generate(&components, &mut again, &options), values[0]);
+    }
+
+    #[test]
+    fn parses_patterns() {
+        assert!(parse_pattern("^[^a-c]*?x{2,}(?:y|z)?[\\d.-]$").is_some());
+        for unsupported in &["a**", "(?=a)", "\\1", "[a", "(a", "a{x}"] {
+            assert_eq!(parse_pattern(unsupported), None, "{}", unsupported);
+        }
+    }
+}
@@ -0,0 +1,250 @@
+//! The strings that documents repeat: those of [`Reference`](crate::Reference)s,
+//! and [`Name`]s of properties and tags. With the `intern` feature, equal
+//! strings read on the same thread share one copy, rather than each holding
+//! their own.
+//!
+//! Each thread keeps a table of the strings it has shared, so that threads
+//! reading documents side by side do not wait on one another. Strings no
+//! longer used outside the table are dropped from it whenever it has doubled
+//! in size since it was last cleared of them.
+
+use serde::de::{Deserializer, Visitor};
+use serde::{Deserialize, Serialize, Serializer};
+use std::borrow::Borrow;
+use std::fmt;
+use std::ops::Deref;
+
+#[cfg(feature = "intern")]
+pub(crate) type Interned = std::sync::Arc<str>;
+#[cfg(not(feature = "intern"))]
+pub(crate) type Interned = String;
+
+#[cfg(feature = "intern")]
+mod table {
+    use super::Interned;
+    use std::cell::RefCell;
+    use std::collections::HashSet;
+    use std::sync::Arc;
+
+    /// The size below which the table is not cleared.
+    const MIN_SWEEP: usize = 1024;
+
+    #[derive(Default)]
+    pub(super) struct Table {
+        pub(super) strings: HashSet<Interned>,
+        /// The size at which unused strings are next dropped.
+        sweep_at: usize,
+    }
+
+    thread_local! {
+        pub(super) static TABLE: RefCell<Table> = RefCell::new(Table::default());
+    }
+
+    impl Table {
+        pub(super) fn intern(&mut self, s: &str) -> Interned {
+            if let Some(shared) = self.strings.get(s) {
+                return shared.clone();
+            }
+            if self.strings.len() >= self.sweep_at {
+                self.strings.retain(|shared| Arc::strong_count(shared) > 1);
+                self.sweep_at = (self.strings.len() * 2).max(MIN_SWEEP);
+            }
+            let shared: Interned = s.into();
+            self.strings.insert(shared.clone());
+            shared
+        }
+    }
+}
+
+/// The shared copy of `s`, made if there is none yet.
+#[cfg(feature = "intern")]
+pub(crate) fn intern(s: &str) -> Interned {
+    table::TABLE
+        .try_with(|table| table.borrow_mut().intern(s))
+        // The thread is exiting, and its table is gone.
+        .unwrap_or_else(|_| s.into())
+}
+
+#[cfg(not(feature = "intern"))]
+pub(crate) fn intern(s: &str) -> Interned {
+    s.to_owned()
+}
+
+#[cfg(feature = "intern")]
+pub(crate) fn into_string(interned: Interned) -> String {
+    String::from(&*interned)
+}
+
+#[cfg(not(feature = "intern"))]
+pub(crate) fn into_string(interned: Interned) -> String {
+    interned
+}
+
+/// A name that documents tend to repeat, such as that of a property,
+/// which is shared with the `intern` feature.
+///
+/// It dereferences to `str`, compares with strings, and converts from them,
+/// so that maps keyed by names are looked up with a `&str`.
+///
+/// ```
+/// use openapiv3::Name;
+///
+/// let name = Name::from("id");
+/// assert_eq!(name, "id");
+/// assert_eq!(name.len(), 2);
+/// ```
+#[derive(Clone, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Name(Interned);
+
+impl Name {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Deref for Name {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Borrow<str> for Name {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for Name {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Debug for Name {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&*self.0, f)
+    }
+}
+
+impl fmt::Display for Name {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<&str> for Name {
+    fn from(s: &str) -> Self {
+        Name(intern(s))
+    }
+}
+
+impl From<&String> for Name {
+    fn from(s: &String) -> Self {
+        Name(intern(s))
+    }
+}
+
+impl From<String> for Name {
+    fn from(s: String) -> Self {
+        Name(intern(&s))
+    }
+}
+
+impl From<Name> for String {
+    fn from(name: Name) -> Self {
+        into_string(name.0)
+    }
+}
+
+impl PartialEq<str> for Name {
+    fn eq(&self, other: &str) -> bool {
+        *self.0 == *other
+    }
+}
+
+impl PartialEq<&str> for Name {
+    fn eq(&self, other: &&str) -> bool {
+        *self.0 == **other
+    }
+}
+
+impl PartialEq<String> for Name {
+    fn eq(&self, other: &String) -> bool {
+        *self.0 == **other
+    }
+}
+
+impl PartialEq<Name> for str {
+    fn eq(&self, other: &Name) -> bool {
+        *self == *other.0
+    }
+}
+
+impl PartialEq<Name> for &str {
+    fn eq(&self, other: &Name) -> bool {
+        **self == *other.0
+    }
+}
+
+impl PartialEq<Name> for String {
+    fn eq(&self, other: &Name) -> bool {
+        **self == *other.0
+    }
+}
+
+impl Serialize for Name {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Name {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct NameVisitor;
+
+        impl<'de> Visitor<'de> for NameVisitor {
+            type Value = Name;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a string")
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Name, E> {
+                Ok(Name::from(value))
+            }
+        }
+
+        deserializer.deserialize_str(NameVisitor)
+    }
+}
+
+#[cfg(all(test, feature = "intern"))]
+mod tests {
+    use super::*;
+
+    fn table_len() -> usize {
+        table::TABLE.with(|table| table.borrow().strings.len())
+    }
+
+    #[test]
+    fn shares_names() {
+        let a = Name::from("petId");
+        let b: Name = serde_json::from_str("\"petId\"").unwrap();
+        assert!(std::sync::Arc::ptr_eq(&a.0, &b.0));
+    }
+
+    #[test]
+    fn drops_unused_strings() {
+        for i in 0..100_000 {
+            let _ = Name::from(format!("name{}", i));
+        }
+        let kept: Vec<Name> = (0..10).map(|i| Name::from(format!("kept{}", i))).collect();
+        for i in 0..100_000 {
+            let _ = Name::from(format!("other{}", i));
+        }
+        assert!(table_len() < 3000, "{}", table_len());
+        assert!(std::sync::Arc::ptr_eq(&kept[3].0, &Name::from("kept3").0));
+    }
+}
@@ -0,0 +1,426 @@
+//! Translation of schemas to and from standard JSON Schema.
+
+use crate::*;
+use serde_json::{Map as JsonMap, Value};
+
+/// A version of JSON Schema.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Draft {
+    Draft4,
+    Draft6,
+    Draft7,
+    Draft2019_09,
+    Draft2020_12,
+}
+
+impl Draft {
+    /// The meta-schema of the draft, as given by `$schema`.
+    pub fn uri(self) -> &'static str {
+        match self {
+            Draft::Draft4 => "http://json-schema.org/draft-04/schema#",
+            Draft::Draft6 => "http://json-schema.org/draft-06/schema#",
+            Draft::Draft7 => "http://json-schema.org/draft-07/schema#",
+            Draft::Draft2019_09 => "https://json-schema.org/draft/2019-09/schema",
+            Draft::Draft2020_12 => "https://json-schema.org/draft/2020-12/schema",
+        }
+    }
+
+    /// Where the draft keeps reusable schemas, as a JSON Pointer.
+    pub fn definitions(self) -> &'static str {
+        if self >= Draft::Draft2019_09 {
+            "/$defs"
+        } else {
+            "/definitions"
+        }
+    }
+}
+
+impl Schema {
+    /// The schema as a JSON Schema document of `draft`, for validators of
+    /// JSON Schema.
+    ///
+    /// `nullable` becomes `null` among the allowed types, `example` becomes
+    /// `examples`, and exclusive bounds take the form of the draft, a
+    /// number from draft 6 on. Keywords that the draft does not know are
+    /// left out, as are those only OpenAPI defines, such as `discriminator`,
+    /// `xml` and extensions. In drafts before 2019-09, which ignore the
+    /// keywords alongside a `$ref`, such a reference is moved into an
+    /// `allOf`.
+    ///
+    /// References to the schemas of the components point to schemas of the
+    /// same names in the [`definitions`](Draft::definitions) of the
+    /// document, where the caller is to put them.
+    ///
+    /// ```
+    /// use openapiv3::{Draft, Schema};
+    /// use serde_json::json;
+    ///
+    /// let schema: Schema =
+    ///     serde_yaml::from_str("{type: integer, minimum: 0, exclusiveMinimum: true, nullable: true}")
+    ///         .unwrap();
+    /// assert_eq!(
+    ///     schema.to_json_schema(Draft::Draft7),
+    ///     json!({
+    ///         "$schema": "http://json-schema.org/draft-07/schema#",
+    ///         "type": ["integer", "null"],
+    ///         "exclusiveMinimum": 0,
+    ///     })
+    /// );
+    /// ```
+    pub fn to_json_schema(&self, draft: Draft) -> Value {
+        let upgraded = crate::upgrade::schema_item(self.clone());
+        let mut value = serde_json::to_value(upgraded).unwrap_or(Value::Bool(true));
+        translate(&mut value, draft);
+        match &mut value {
+            Value::Object(object) => {
+                object.insert("$schema".to_owned(), Value::from(draft.uri()));
+            }
+            // A boolean schema cannot name its draft; drafts before 6 have
+            // no boolean schemas.
+            Value::Bool(true) if draft < Draft::Draft6 => value = Value::Object(JsonMap::new()),
+            _ => {}
+        }
+        value
+    }
+}
+
+/// Rewrites a 2020-12 schema, with the OpenAPI vocabulary, for `draft`.
+fn translate(value: &mut Value, draft: Draft) {
+    let object = match value {
+        Value::Object(object) => object,
+        Value::Bool(accepts) if draft < Draft::Draft6 => {
+            *value = if *accepts {
+                Value::Object(JsonMap::new())
+            } else {
+                serde_json::json!({"not": {}})
+            };
+            return;
+        }
+        _ => return,
+    };
+
+    object.retain(|keyword, _| match keyword.as_str() {
+        "discriminator" | "xml" | "externalDocs" => false,
+        keyword if keyword.starts_with("x-") => false,
+        "examples" | "const" => draft >= Draft::Draft6,
+        "readOnly" | "writeOnly" | "$comment" => draft >= Draft::Draft7,
+        "deprecated" | "$defs" => draft >= Draft::Draft2019_09,
+        _ => true,
+    });
+
+    if draft < Draft::Draft6 {
+        for (exclusive, inclusive) in &[
+            ("exclusiveMinimum", "minimum"),
+            ("exclusiveMaximum", "maximum"),
+        ] {
+            if let Some(bound) = object.remove(*exclusive) {
+                object.insert((*inclusive).to_owned(), bound);
+                object.insert((*exclusive).to_owned(), Value::Bool(true));
+            }
+        }
+    }
+
+    for keyword in &["items", "additionalProperties", "not"] {
+        if let Some(schema) = object.get_mut(*keyword) {
+            translate(schema, draft);
+        }
+    }
+    for keyword in &["allOf", "anyOf", "oneOf"] {
+        if let Some(Value::Array(schemas)) = object.get_mut(*keyword) {
+            schemas
+                .iter_mut()
+                .for_each(|schema| translate(schema, draft));
+        }
+    }
+    if let Some(Value::Object(properties)) = object.get_mut("properties") {
+        properties
+            .values_mut()
+            .for_each(|schema| translate(schema, draft));
+    }
+
+    if let Some(Value::String(reference)) = object.get_mut("$ref") {
+        if let Some(name) = reference.strip_prefix("#/components/schemas/") {
+            *reference = format!("#{}/{}", draft.definitions(), name);
+        }
+        if draft < Draft::Draft2019_09 && object.len() > 1 {
+            let reference = object.remove("$ref").unwrap_or_default();
+            let mut all_of = vec![serde_json::json!({ "$ref": reference })];
+            if let Some(Value::Array(others)) = object.remove("allOf") {
+                all_of.extend(others);
+            }
+            object.insert("allOf".to_owned(), Value::Array(all_of));
+        }
+    }
+}
+
+/// A JSON Schema document read as a schema of this crate; see
+/// [`Schema::from_json_schema`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedSchema {
+    pub schema: ReferenceOr<Schema>,
+    /// The schemas of the document's `$defs`, or `definitions`, by name,
+    /// for the schemas of the components. References to them point there.
+    pub definitions: Map<String, ReferenceOr<Schema>>,
+    /// What could not be expressed, at locations within the document.
+    pub issues: Vec<DowngradeIssue>,
+}
+
+impl Schema {
+    /// Reads a JSON Schema document of draft 4 to 2020-12, such as one
+    /// generated for a Rust type, reporting keywords that schemas of
+    /// OpenAPI 3.0 cannot hold; those are left out.
+    ///
+    /// The forms of earlier drafts, such as boolean exclusive bounds and
+    /// `definitions`, are read as well as those of later ones. The
+    /// definitions of the document are returned alongside the schema, to be
+    /// added to the components.
+    ///
+    /// Schemas of types deriving `schemars::JsonSchema` are read by way of
+    /// their JSON, as in
+    /// `Schema::from_json_schema(serde_json::to_value(schemars::schema_for!(Pet))?)`;
+    /// the reverse is deserializing the result of
+    /// [`to_json_schema`](Schema::to_json_schema) for draft 7.
+    ///
+    /// ```
+    /// use openapiv3::{Components, ReferenceOr, Schema};
+    /// use serde_json::json;
+    ///
+    /// let imported = Schema::from_json_schema(json!({
+    ///     "$schema": "http://json-schema.org/draft-07/schema#",
+    ///     "type": "object",
+    ///     "required": ["owner"],
+    ///     "properties": {"owner": {"$ref": "#/definitions/Person"}},
+    ///     "definitions": {"Person": {"type": ["string", "null"]}},
+    /// }))
+    /// .unwrap();
+    /// assert!(imported.issues.is_empty());
+    ///
+    /// let mut components = Components::default();
+    /// components.schemas.extend(imported.definitions);
+    /// components.schemas.insert("Pet".to_owned(), imported.schema);
+    /// let person = components.resolve_reference::<Schema>("#/components/schemas/Person");
+    /// assert!(person.unwrap().schema_data.nullable);
+    /// ```
+    pub fn from_json_schema(mut document: Value) -> Result<ImportedSchema, serde_json::Error> {
+        upgrade_keywords(&mut document);
+        let mut definitions = Map::new();
+        let mut issues = Vec::new();
+        if let Value::Object(object) = &mut document {
+            object.remove("$schema");
+            if let Some(Value::Object(defs)) = object.remove("$defs") {
+                for (name, definition) in defs {
+                    let (definition, found) =
+                        crate::downgrade::downgrade_schema(serde_json::from_value(definition)?);
+                    let at = format!("/$defs/{}", crate::reference::escape_pointer_segment(&name));
+                    issues.extend(found.into_iter().map(|mut issue| {
+                        issue.location.insert_str(0, &at);
+                        issue
+                    }));
+                    definitions.insert(name, definition);
+                }
+            }
+        }
+        let (schema, found) = crate::downgrade::downgrade_schema(serde_json::from_value(document)?);
+        issues.extend(found);
+        Ok(ImportedSchema {
+            schema,
+            definitions,
+            issues,
+        })
+    }
+}
+
+/// Rewrites the keywords of earlier drafts in a schema, and those of its
+/// subschemas, in the form of draft 2020-12, and points references to
+/// definitions at the components.
+fn upgrade_keywords(value: &mut Value) {
+    let object = match value {
+        Value::Object(object) => object,
+        _ => return,
+    };
+
+    if !object.contains_key("$defs") {
+        if let Some(definitions) = object.remove("definitions") {
+            object.insert("$defs".to_owned(), definitions);
+        }
+    }
+    for (exclusive, inclusive) in &[
+        ("exclusiveMinimum", "minimum"),
+        ("exclusiveMaximum", "maximum"),
+    ] {
+        if let Some(Value::Bool(flag)) = object.get(*exclusive) {
+            let bound = if *flag {
+                object.remove(*inclusive)
+            } else {
+                None
+            };
+            object.remove(*exclusive);
+            if let Some(bound) = bound {
+                object.insert((*exclusive).to_owned(), bound);
+            }
+        }
+    }
+    if let Some(Value::Array(_)) = object.get("items") {
+        let items = object.remove("items").unwrap_or_default();
+        object.insert("prefixItems".to_owned(), items);
+        if let Some(additional) = object.remove("additionalItems") {
+            object.insert("items".to_owned(), additional);
+        }
+    }
+    if let Some(Value::Object(dependencies)) = object.remove("dependencies") {
+        let (required, schemas): (JsonMap<_, _>, JsonMap<_, _>) = dependencies
+            .into_iter()
+            .partition(|(_, dependency)| dependency.is_array());
+        let mut insert = |keyword: &str, dependencies: JsonMap<_, _>| {
+            if !dependencies.is_empty() {
+                object.insert(keyword.to_owned(), Value::Object(dependencies));
+            }
+        };
+        insert("dependentRequired", required);
+        insert("dependentSchemas", schemas);
+    }
+    if let Some(Value::String(reference)) = object.get_mut("$ref") {
+        let name = reference
+            .strip_prefix("#/$defs/")
+            .or_else(|| reference.strip_prefix("#/definitions/"));
+        if let Some(name) = name {
+            *reference = format!("#/components/schemas/{}", name);
+        }
+    }
+
+    for (keyword, subschemas) in object.iter_mut() {
+        match keyword.as_str() {
+            "items"
+            | "additionalProperties"
+            | "not"
+            | "if"
+            | "then"
+            | "else"
+            | "contains"
+            | "propertyNames"
+            | "unevaluatedItems"
+            | "unevaluatedProperties"
+            | "contentSchema" => upgrade_keywords(subschemas),
+            "allOf" | "anyOf" | "oneOf" | "prefixItems" => {
+                if let Value::Array(subschemas) = subschemas {
+                    subschemas.iter_mut().for_each(upgrade_keywords);
+                }
+            }
+            "properties" | "patternProperties" | "dependentSchemas" | "$defs" => {
+                if let Value::Object(subschemas) = subschemas {
+                    subschemas.values_mut().for_each(upgrade_keywords);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn translates_schemas() {
+        let schema: Schema = serde_yaml::from_str(
+            "
+type: object
+description: a pet
+example: {name: Rex}
+x-internal: true
+externalDocs: {url: 'https://example.com'}
+discriminator: {propertyName: kind}
+properties:
+  name: {type: string, readOnly: true, deprecated: true}
+  weight: {type: number, maximum: 10, exclusiveMaximum: true}
+  owner: {$ref: '#/components/schemas/Person'}
+  tags:
+    type: array
+    items: {type: string, enum: [a, b], nullable: true}
+  extra: {additionalProperties: false}
+",
+        )
+        .unwrap();
+        assert_eq!(
+            schema.to_json_schema(Draft::Draft2020_12),
+            json!({
+                "$schema": "https://json-schema.org/draft/2020-12/schema",
+                "type": "object",
+                "description": "a pet",
+                "examples": [{"name": "Rex"}],
+                "properties": {
+                    "name": {"type": "string", "readOnly": true, "deprecated": true},
+                    "weight": {"type": "number", "exclusiveMaximum": 10.0},
+                    "owner": {"$ref": "#/$defs/Person"},
+                    "tags": {
+                        "type": "array",
+                        "items": {"type": ["string", "null"], "enum": ["a", "b", null]},
+                    },
+                    "extra": {"additionalProperties": false},
+                },
+            })
+        );
+        assert_eq!(
+            schema.to_json_schema(Draft::Draft4),
+            json!({
+                "$schema": "http://json-schema.org/draft-04/schema#",
+                "type": "object",
+                "description": "a pet",
+                "properties": {
+                    "name": {"type": "string"},
+                    "weight": {"type": "number", "maximum": 10.0, "exclusiveMaximum": true},
+                    "owner": {"$ref": "#/definitions/Person"},
+                    "tags": {
+                        "type": "array",
+                        "items": {"type": ["string", "null"], "enum": ["a", "b", null]},
+                    },
+                    "extra": {"additionalProperties": {"not": {}}},
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn imports_schemas() {
+        let imported = Schema::from_json_schema(json!({
+            "$schema": "http://json-schema.org/draft-04/schema#",
+            "type": "object",
+            "properties": {
+                "age": {"type": "integer", "minimum": 0, "exclusiveMinimum": true},
+                "pair": {"type": "array", "items": [{"type": "string"}], "additionalItems": false},
+                "tag": {"$ref": "#/definitions/Tag"},
+            },
+            "dependencies": {"age": ["pair"]},
+            "definitions": {"Tag": {"type": "string", "not": {"const": ""}}},
+        }))
+        .unwrap();
+        let expected: Schema = serde_yaml::from_str(
+            "
+type: object
+properties:
+  age: {type: integer, minimum: 0, exclusiveMinimum: true}
+  pair: {type: array, items: {}}
+  tag: {$ref: '#/components/schemas/Tag'}
+",
+        )
+        .unwrap();
+        assert_eq!(imported.schema, ReferenceOr::Item(expected));
+        assert_eq!(
+            imported.definitions.get("Tag"),
+            Some(&ReferenceOr::Item(
+                serde_yaml::from_str("{type: string}").unwrap()
+            ))
+        );
+        let issues: Vec<String> = imported.issues.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            issues,
+            vec![
+                "/$defs/Tag: `not` is not supported in 3.0",
+                ": `dependentRequired` is not supported in 3.0",
+                "/properties/pair: `prefixItems` is not supported in 3.0",
+                "/properties/pair/items: the `false` schema is not supported in 3.0",
+            ]
+        );
+    }
+}
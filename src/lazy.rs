@@ -0,0 +1,184 @@
+use crate::*;
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// A document whose paths and schemas are kept as they were read, and
+/// only deserialized one at a time as they are asked for, so that reading
+/// the `info` of a large document or one of its operations does not cost
+/// deserializing every schema.
+///
+/// The rest of the document is read as [`OpenAPI`] reads it. Entries that
+/// do not match the specification are only reported on when they are
+/// materialized.
+///
+/// ```
+/// use openapiv3::{LazyOpenAPI, Method};
+///
+/// let api: LazyOpenAPI = serde_yaml::from_str(
+///     "
+/// openapi: 3.0.0
+/// info: {title: Pets, version: '1'}
+/// paths:
+///   /pets:
+///     get: {operationId: listPets, responses: {}}
+/// components:
+///   schemas:
+///     Pet: {type: object}
+///     Broken: [not, a, schema]
+/// ",
+/// )
+/// .unwrap();
+/// assert_eq!(api.info.title, "Pets");
+///
+/// let operation = api.operation("/pets", Method::Get).unwrap().unwrap();
+/// assert_eq!(operation.operation_id.as_deref(), Some("listPets"));
+/// assert!(api.schema("Pet").unwrap().is_ok());
+/// assert!(api.schema("Broken").unwrap().is_err());
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct LazyOpenAPI {
+    pub openapi: String,
+    pub info: Info,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub servers: Vec<Server>,
+    /// The path items, by path, as they were read, along with the
+    /// extensions of the Paths Object.
+    pub paths: Map<String, Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub components: Option<LazyComponents>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub security: Vec<SecurityRequirement>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<Tag>,
+    #[serde(rename = "externalDocs")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_docs: Option<ExternalDocumentation>,
+    #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
+    pub extensions: IndexMap<String, Value>,
+}
+
+/// The components of a [`LazyOpenAPI`]: its schemas as they were read, and
+/// the other components as [`Components`] reads them.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct LazyComponents {
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub schemas: Map<String, Value>,
+    /// The other components. Its `schemas` are always empty.
+    #[serde(flatten)]
+    pub rest: Components,
+}
+
+impl LazyOpenAPI {
+    /// Deserializes the path item of `path`, if the document has one.
+    pub fn path_item(
+        &self,
+        path: &str,
+    ) -> Option<Result<ReferenceOr<PathItem>, serde_json::Error>> {
+        self.paths.get(path).map(ReferenceOr::deserialize)
+    }
+
+    /// Deserializes the operation of `path` and `method`, without the rest
+    /// of its path item, if the document has one. The operations of path
+    /// items that are references are not looked up.
+    pub fn operation(
+        &self,
+        path: &str,
+        method: Method,
+    ) -> Option<Result<Operation, serde_json::Error>> {
+        let operation = self.paths.get(path)?.get(method.as_str())?;
+        Some(Operation::deserialize(operation))
+    }
+
+    /// Deserializes the schema of the components named `name`, if the
+    /// document has one.
+    pub fn schema(&self, name: &str) -> Option<Result<ReferenceOr<Schema>, serde_json::Error>> {
+        let schemas = &self.components.as_ref()?.schemas;
+        schemas.get(name).map(ReferenceOr::deserialize)
+    }
+
+    /// Deserializes every path item and schema, into the document as
+    /// [`OpenAPI`] would have read it.
+    pub fn into_openapi(self) -> Result<OpenAPI, serde_json::Error> {
+        let mut paths = Paths::new();
+        for (path, item) in self.paths {
+            if path.starts_with("x-") {
+                paths.extensions.insert(path, item);
+            } else {
+                paths.insert(path, serde_json::from_value(item)?);
+            }
+        }
+        let components = match self.components {
+            Some(components) => {
+                let mut rest = components.rest;
+                rest.schemas = components
+                    .schemas
+                    .into_iter()
+                    .map(|(name, schema)| Ok((name, serde_json::from_value(schema)?)))
+                    .collect::<Result<_, serde_json::Error>>()?;
+                Some(rest)
+            }
+            None => None,
+        };
+        Ok(OpenAPI {
+            openapi: self.openapi,
+            info: self.info,
+            servers: self.servers,
+            paths,
+            components,
+            security: self.security,
+            tags: self.tags,
+            external_docs: self.external_docs,
+            extensions: self.extensions,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn materializes_on_demand() {
+        let text = "
+openapi: 3.0.0
+info: {title: t, version: '1'}
+x-owner: pets team
+paths:
+  /pets/{id}:
+    parameters: [{name: id, in: path, required: true, schema: {type: integer}}]
+    get:
+      responses:
+        200:
+          description: a pet
+          content:
+            application/json: {schema: {$ref: '#/components/schemas/Pet'}}
+  /owners: {$ref: 'owners.yaml'}
+components:
+  schemas:
+    Pet: {type: object, properties: {name: {type: string}}}
+  responses:
+    NotFound: {description: not found}
+";
+        let lazy: LazyOpenAPI = serde_yaml::from_str(text).unwrap();
+        let components = lazy.components.as_ref().unwrap();
+        assert!(components.rest.schemas.is_empty());
+        assert!(components.rest.responses.contains_key("NotFound"));
+
+        let operation = lazy.operation("/pets/{id}", Method::Get).unwrap().unwrap();
+        assert!(operation.responses.get_for(200).is_some());
+        assert!(lazy.operation("/pets/{id}", Method::Put).is_none());
+        assert!(lazy.operation("/owners", Method::Get).is_none());
+        assert!(matches!(
+            lazy.path_item("/owners"),
+            Some(Ok(ReferenceOr::Reference { .. }))
+        ));
+        assert!(lazy.schema("Owner").is_none());
+
+        let api: OpenAPI = serde_yaml::from_str(text).unwrap();
+        assert_eq!(lazy.into_openapi().unwrap(), api);
+    }
+}
@@ -1,57 +1,187 @@
+#[cfg(feature = "proptest")]
+mod arbitrary;
+mod base_path;
+pub mod borrowed;
+mod builder;
+mod bundle;
 mod callback;
+mod canonicalize;
+mod comments;
+pub mod compat;
 mod components;
 mod contact;
+mod cycles;
+mod deprecations;
+mod dereference;
+pub mod diff;
 mod discriminator;
+mod downgrade;
+pub mod dsl;
 mod encoding;
+mod equivalence;
 mod example;
+mod examples;
+pub mod extensions;
+mod external;
 mod external_documentation;
+mod filter;
+mod format;
 mod header;
+mod hoist;
+#[cfg(feature = "http")]
+mod http_interop;
+mod index;
 mod info;
+mod instance;
+mod intern;
+mod json_schema;
+mod lazy;
 mod license;
+mod limits;
 mod link;
+pub mod lint;
+mod map;
 mod media_type;
+mod media_type_range;
+mod merge;
+mod method;
+pub mod mock;
+mod multipart;
+mod namer;
+mod numeric;
 mod openapi;
+mod openapi_schema;
 mod operation;
+mod operation_ids;
+mod operation_index;
+#[cfg(feature = "parallel")]
+mod parallel;
 mod parameter;
+mod parse_error;
+mod path_template;
 mod paths;
+mod pointer;
+mod project;
+mod read;
 mod reference;
+mod rename;
+pub mod render;
 mod request_body;
+mod request_example;
+mod resolve;
 mod responses;
+mod router;
+mod runtime_expression;
+mod satisfiable;
 mod schema;
 mod security_requirement;
 mod security_scheme;
 mod server;
 mod server_variable;
+mod source_map;
+mod split;
 mod status_code;
+mod strict;
+mod style;
+mod summary;
+#[cfg(feature = "swagger2")]
+pub mod swagger2;
 mod tag;
+mod tags;
+pub mod testing;
+mod type_shape;
+mod upgrade;
 mod util;
+pub mod v3_1;
+mod validate;
+pub mod validation;
+mod validator;
+mod value;
 mod variant_or;
+mod versioned;
+pub mod visit;
+mod walk;
+mod xml;
+mod yaml;
 
+pub use self::base_path::*;
+pub use self::builder::*;
 pub use self::callback::*;
+pub use self::comments::*;
 pub use self::components::*;
 pub use self::contact::*;
+pub use self::deprecations::*;
+pub use self::dereference::*;
 pub use self::discriminator::*;
+pub use self::downgrade::*;
 pub use self::encoding::*;
 pub use self::example::*;
+pub use self::examples::*;
+pub use self::external::*;
 pub use self::external_documentation::*;
+pub use self::filter::*;
+pub use self::format::*;
 pub use self::header::*;
+#[cfg(feature = "http")]
+pub use self::http_interop::*;
 pub use self::info::*;
+pub use self::instance::*;
+pub use self::intern::Name;
+pub use self::json_schema::*;
+pub use self::lazy::*;
 pub use self::license::*;
+pub use self::limits::*;
 pub use self::link::*;
+pub use self::map::*;
 pub use self::media_type::*;
+pub use self::media_type_range::*;
+pub use self::merge::*;
+pub use self::method::*;
+pub use self::multipart::*;
+pub use self::namer::*;
+pub use self::numeric::*;
 pub use self::openapi::*;
+pub use self::openapi_schema::*;
 pub use self::operation::*;
+pub use self::operation_ids::*;
+pub use self::operation_index::*;
 pub use self::parameter::*;
+pub use self::parse_error::*;
+pub use self::path_template::*;
 pub use self::paths::*;
+pub use self::pointer::*;
+pub use self::project::*;
+pub use self::read::*;
 pub use self::reference::*;
+pub use self::rename::*;
 pub use self::request_body::*;
+pub use self::request_example::*;
+pub use self::resolve::*;
 pub use self::responses::*;
+pub use self::router::*;
+pub use self::runtime_expression::*;
+pub use self::satisfiable::*;
 pub use self::schema::*;
 pub use self::security_requirement::*;
 pub use self::security_scheme::*;
 pub use self::server::*;
 pub use self::server_variable::*;
+pub use self::source_map::*;
+pub use self::split::*;
 pub use self::status_code::*;
+pub use self::strict::*;
+pub use self::style::*;
 pub use self::tag::*;
+pub use self::tags::*;
+pub use self::type_shape::*;
 pub use self::util::*;
+pub use self::validate::*;
+pub use self::validator::*;
+pub use self::value::*;
 pub use self::variant_or::*;
+pub use self::versioned::*;
+pub use self::xml::*;
+pub use self::yaml::*;
+
+#[cfg(feature = "derive")]
+pub use openapiv3_derive::OpenApiSchema;
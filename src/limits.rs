@@ -0,0 +1,292 @@
+use crate::read::Format;
+use crate::*;
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::fmt;
+use yaml_rust::parser::{Event, MarkedEventReceiver, Parser};
+use yaml_rust::scanner::Marker;
+
+/// Bounds on the size of a document, for reading documents from untrusted
+/// sources without exhausting the stack or memory.
+///
+/// The document is checked against the limits as it is scanned, before it
+/// is deserialized, so that neither deeply nested values nor aliases that
+/// expand to many more values than they take up get that far.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReadLimits {
+    /// How deeply mappings and sequences may nest.
+    pub max_depth: usize,
+    /// How many values the document may have, counting those of aliases
+    /// each time they are used.
+    pub max_nodes: usize,
+    /// How long, in bytes, each string and key may be.
+    pub max_string_length: usize,
+}
+
+impl Default for ReadLimits {
+    fn default() -> Self {
+        ReadLimits {
+            max_depth: 64,
+            max_nodes: 1_000_000,
+            max_string_length: 1 << 20,
+        }
+    }
+}
+
+/// A limit of [`ReadLimits`] that a document exceeds, at the value where
+/// it does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LimitExceeded {
+    pub limit: Limit,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Limit {
+    Depth(usize),
+    Nodes(usize),
+    StringLength(usize),
+}
+
+impl fmt::Display for LimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.limit {
+            Limit::Depth(n) => write!(f, "values nest more than {} deep", n)?,
+            Limit::Nodes(n) => write!(f, "document has more than {} values", n)?,
+            Limit::StringLength(n) => write!(f, "string is longer than {} bytes", n)?,
+        }
+        write!(f, " at {}", self.span)
+    }
+}
+
+impl std::error::Error for LimitExceeded {}
+
+/// Reads a JSON or YAML document of any supported version as [`from_str`]
+/// does, failing if it exceeds `limits`.
+///
+/// ```
+/// use openapiv3::{from_str_with_limits, ReadError, ReadLimits};
+///
+/// let limits = ReadLimits {
+///     max_nodes: 100,
+///     ..ReadLimits::default()
+/// };
+/// let bomb = "
+/// openapi: 3.0.0
+/// info: {title: t, version: '1'}
+/// x-a: &a [1, 2, 3, 4, 5, 6, 7, 8, 9, 10]
+/// x-b: &b [*a, *a, *a, *a, *a, *a, *a, *a, *a, *a]
+/// x-c: [*b, *b, *b, *b, *b, *b, *b, *b, *b, *b]
+/// paths: {}
+/// ";
+/// match from_str_with_limits(bomb, &limits).unwrap_err() {
+///     ReadError::Limit(error) => {
+///         assert_eq!(error.to_string(), "document has more than 100 values at line 5, column 38")
+///     }
+///     error => panic!("{}", error),
+/// }
+/// ```
+pub fn from_str_with_limits(
+    text: &str,
+    limits: &ReadLimits,
+) -> Result<VersionedDocument, ReadError> {
+    check_limits(text, limits)?;
+    from_str(text)
+}
+
+/// Reads a JSON or YAML document as `T`, as [`parse`] does, failing if it
+/// exceeds `limits`.
+pub fn parse_with_limits<T: DeserializeOwned>(
+    text: &str,
+    limits: &ReadLimits,
+) -> Result<T, ReadError> {
+    check_limits(text, limits)?;
+    Format::of(text).parse(text)
+}
+
+/// Scans a document for values beyond `limits`. A document that cannot be
+/// scanned is left for deserialization to report on, unless it nests
+/// deeper than `limits` allow before it goes wrong.
+fn check_limits(text: &str, limits: &ReadLimits) -> Result<(), ReadError> {
+    let mut checker = Checker {
+        limits: *limits,
+        nodes: 0,
+        frames: Vec::new(),
+        anchors: HashMap::new(),
+        exceeded: None,
+    };
+    if let Err(error) = Parser::new(text.chars()).load(&mut checker, true) {
+        // The scanner reads ahead of the events it gives, and gives up on
+        // flow collections nested more than 255 deep before giving those
+        // of any of them, so this is the only sign of such a document.
+        let too_deep = error.to_string().starts_with("recursion limit exceeded");
+        if checker.exceeded.is_none() && too_deep && limits.max_depth < 256 {
+            checker.exceeded = Some(LimitExceeded {
+                limit: Limit::Depth(limits.max_depth),
+                span: Span {
+                    line: error.marker().line(),
+                    column: error.marker().col() + 1,
+                },
+            });
+        }
+    }
+    match checker.exceeded {
+        Some(exceeded) => Err(ReadError::Limit(exceeded)),
+        None => Ok(()),
+    }
+}
+
+/// The size of a value, as aliases of it repeat it.
+#[derive(Clone, Copy, Default)]
+struct Size {
+    nodes: usize,
+    /// How deeply mappings and sequences nest within it, itself included.
+    depth: usize,
+}
+
+/// A mapping or sequence being scanned.
+struct Frame {
+    anchor: usize,
+    /// The count of nodes before the mapping or sequence.
+    start: usize,
+    /// The deepest nesting within it, as [`Size::depth`].
+    depth: usize,
+}
+
+struct Checker {
+    limits: ReadLimits,
+    nodes: usize,
+    frames: Vec<Frame>,
+    anchors: HashMap<usize, Size>,
+    exceeded: Option<LimitExceeded>,
+}
+
+impl Checker {
+    /// Counts a value of `size` within the current mapping or sequence.
+    fn add(&mut self, size: Size) -> Option<Limit> {
+        self.nodes = self.nodes.saturating_add(size.nodes);
+        if let Some(parent) = self.frames.last_mut() {
+            parent.depth = parent.depth.max(size.depth + 1);
+        }
+        if self.nodes > self.limits.max_nodes {
+            Some(Limit::Nodes(self.limits.max_nodes))
+        } else if self.frames.len() + size.depth > self.limits.max_depth {
+            Some(Limit::Depth(self.limits.max_depth))
+        } else {
+            None
+        }
+    }
+}
+
+impl MarkedEventReceiver for Checker {
+    fn on_event(&mut self, event: Event, mark: Marker) {
+        if self.exceeded.is_some() {
+            return;
+        }
+        let exceeded = match event {
+            Event::Scalar(value, _, anchor, _) => {
+                let size = Size { nodes: 1, depth: 0 };
+                if anchor > 0 {
+                    self.anchors.insert(anchor, size);
+                }
+                if value.len() > self.limits.max_string_length {
+                    Some(Limit::StringLength(self.limits.max_string_length))
+                } else {
+                    self.add(size)
+                }
+            }
+            Event::Alias(anchor) => {
+                let size = self.anchors.get(&anchor).copied().unwrap_or_default();
+                self.add(size)
+            }
+            Event::MappingStart(anchor) | Event::SequenceStart(anchor) => {
+                let exceeded = self.add(Size { nodes: 1, depth: 1 });
+                self.frames.push(Frame {
+                    anchor,
+                    start: self.nodes - 1,
+                    depth: 1,
+                });
+                exceeded
+            }
+            Event::MappingEnd | Event::SequenceEnd => {
+                if let Some(frame) = self.frames.pop() {
+                    if let Some(parent) = self.frames.last_mut() {
+                        parent.depth = parent.depth.max(frame.depth + 1);
+                    }
+                    if frame.anchor > 0 {
+                        let size = Size {
+                            nodes: self.nodes - frame.start,
+                            depth: frame.depth,
+                        };
+                        self.anchors.insert(frame.anchor, size);
+                    }
+                }
+                None
+            }
+            _ => None,
+        };
+        self.exceeded = exceeded.map(|limit| LimitExceeded {
+            limit,
+            span: Span {
+                line: mark.line(),
+                column: mark.col() + 1,
+            },
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn enforces_limits() {
+        let limits = ReadLimits {
+            max_depth: 4,
+            max_nodes: 20,
+            max_string_length: 8,
+        };
+        let limit = |text: &str| match parse_with_limits::<serde_json::Value>(text, &limits) {
+            Err(ReadError::Limit(exceeded)) => Some(exceeded.limit),
+            _ => None,
+        };
+        assert_eq!(limit("[[[[1]]]]"), None);
+        assert_eq!(limit("[[[[[1]]]]]"), Some(Limit::Depth(4)));
+        assert_eq!(limit("{a: &a [[1]], b: [[*a]]}"), Some(Limit::Depth(4)));
+        assert_eq!(limit("{a: &a [[1]], b: [*a]}"), None);
+        assert_eq!(limit("[12345678]"), None);
+        assert_eq!(limit("[123456789]"), Some(Limit::StringLength(8)));
+        assert_eq!(limit(&format!("{:?}", vec![0; 19])), None);
+        assert_eq!(limit(&format!("{:?}", vec![0; 20])), Some(Limit::Nodes(20)));
+        assert!(matches!(
+            parse_with_limits::<serde_json::Value>("[", &limits),
+            Err(ReadError::Yaml(_))
+        ));
+    }
+
+    #[test]
+    fn limits_depth_past_what_the_scanner_reads() {
+        let deep = format!("{}{}", "[".repeat(1000), "]".repeat(1000));
+        match parse_with_limits::<serde_json::Value>(&deep, &ReadLimits::default()) {
+            Err(ReadError::Limit(exceeded)) => {
+                assert_eq!(exceeded.limit, Limit::Depth(64));
+                assert_eq!(
+                    exceeded.span,
+                    Span {
+                        line: 1,
+                        column: 256
+                    }
+                );
+            }
+            result => panic!("{:?}", result.map(|_| ())),
+        }
+        let deep = format!("a:\n  b: {}{}", "{c: ".repeat(300), "}".repeat(300));
+        assert!(matches!(
+            parse_with_limits::<serde_json::Value>(&deep, &ReadLimits::default()),
+            Err(ReadError::Limit(LimitExceeded {
+                limit: Limit::Depth(64),
+                ..
+            }))
+        ));
+    }
+}
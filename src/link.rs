@@ -1,4 +1,5 @@
 use crate::*;
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
@@ -13,8 +14,8 @@ use std::collections::BTreeMap;
 /// For computing links, and providing instructions to execute them,
 /// a runtime expression is used for accessing values in an operation
 /// and using them as parameters while invoking the linked operation.
-#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
-#[serde(rename = "camelCase")]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
 pub struct Link {
     /// A description of the link.
     /// CommonMark syntax MAY be used for rich text representation.
@@ -43,9 +44,12 @@ pub struct Link {
     /// to the linked operation. The parameter name can be qualified
     /// using the parameter location [{in}.]{name} for operations
     /// that use the same parameter name in different locations (e.g. path.id).
-    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub parameters: BTreeMap<String, String>,
     /// A server object to be used by the target operation.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub server: Option<Server>,
+    /// Inline extensions to this object.
+    #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
+    pub extensions: IndexMap<String, serde_json::Value>,
 }
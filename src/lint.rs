@@ -0,0 +1,364 @@
+//! Style checks for API descriptions, in the manner of Spectral.
+//!
+//! A [`Linter`] runs a set of [`Rule`]s over a document, each at its own
+//! [`Severity`]. [`Linter::recommended`] provides a pack of built-in rules;
+//! custom rules are added by implementing [`Rule`].
+
+use crate::reference::escape_pointer_segment;
+use crate::{OpenAPI, Operation, ReferenceOr, Severity, StatusCode};
+use std::fmt;
+
+/// A problem reported by a lint rule.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    /// The name of the rule that reported the problem.
+    pub rule: &'static str,
+    pub severity: Severity,
+    /// A JSON Pointer to the offending part of the document.
+    pub location: String,
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} [{}] {}: {}",
+            self.severity, self.rule, self.location, self.message
+        )
+    }
+}
+
+/// What a rule is being run with.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LintContext {
+    rule: &'static str,
+    severity: Severity,
+}
+
+impl LintContext {
+    /// The severity the rule is being run at.
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    /// Creates a diagnostic for the rule being run.
+    pub fn diagnostic(
+        &self,
+        location: impl Into<String>,
+        message: impl Into<String>,
+    ) -> Diagnostic {
+        Diagnostic {
+            rule: self.rule,
+            severity: self.severity,
+            location: location.into(),
+            message: message.into(),
+        }
+    }
+}
+
+/// A check of a document.
+pub trait Rule {
+    /// The name identifying the rule in diagnostics, such as `kebab-case-paths`.
+    fn name(&self) -> &'static str;
+
+    /// The severity the rule is run at unless configured otherwise.
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, ctx: &LintContext, doc: &OpenAPI) -> Vec<Diagnostic>;
+}
+
+/// Runs a set of rules over documents.
+#[derive(Default)]
+pub struct Linter {
+    rules: Vec<(Box<dyn Rule>, Severity)>,
+}
+
+impl fmt::Debug for Linter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(
+                self.rules
+                    .iter()
+                    .map(|(rule, severity)| (rule.name(), severity)),
+            )
+            .finish()
+    }
+}
+
+impl Linter {
+    /// A linter without any rules.
+    pub fn new() -> Self {
+        Linter::default()
+    }
+
+    /// A linter with all the built-in rules, at their default severities.
+    pub fn recommended() -> Self {
+        Linter::new()
+            .rule(KebabCasePaths)
+            .rule(PluralResources)
+            .rule(SatisfiableSchemas)
+            .rule(SuccessResponse)
+            .rule(TagsDefined)
+    }
+
+    /// Adds a rule, run at its default severity.
+    pub fn rule<R: Rule + 'static>(self, rule: R) -> Self {
+        let severity = rule.default_severity();
+        self.rule_with_severity(rule, severity)
+    }
+
+    /// Adds a rule, run at `severity`.
+    pub fn rule_with_severity<R: Rule + 'static>(mut self, rule: R, severity: Severity) -> Self {
+        self.rules.push((Box::new(rule), severity));
+        self
+    }
+
+    /// Runs every rule over `doc`, returning the diagnostics ordered by
+    /// location, and rule for the same location.
+    pub fn lint(&self, doc: &OpenAPI) -> Vec<Diagnostic> {
+        let mut diagnostics: Vec<Diagnostic> = self
+            .rules
+            .iter()
+            .flat_map(|(rule, severity)| {
+                let ctx = LintContext {
+                    rule: rule.name(),
+                    severity: *severity,
+                };
+                rule.check(&ctx, doc)
+            })
+            .collect();
+        diagnostics.sort_by(|a, b| (&a.location, a.rule).cmp(&(&b.location, b.rule)));
+        diagnostics
+    }
+}
+
+fn path_location(path: &str) -> String {
+    format!("/paths/{}", escape_pointer_segment(path))
+}
+
+/// Calls `f` with the location of every operation and the operation,
+/// those of callbacks included.
+fn for_each_operation<F: FnMut(String, &Operation)>(doc: &OpenAPI, mut f: F) {
+    for (path, method, operation) in doc.operations() {
+        f(
+            format!("{}/{}", path_location(path), method.as_str()),
+            operation,
+        );
+    }
+    for (location, _, operation) in doc.callback_operations() {
+        f(location, operation);
+    }
+}
+
+/// The fixed, non-template segments of a path.
+fn literal_segments(path: &str) -> impl Iterator<Item = &str> {
+    path.split('/')
+        .filter(|segment| !segment.is_empty() && !segment.starts_with('{'))
+}
+
+/// Path segments are lowercase words separated by hyphens, as in
+/// `/pet-owners/{id}`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct KebabCasePaths;
+
+impl Rule for KebabCasePaths {
+    fn name(&self) -> &'static str {
+        "kebab-case-paths"
+    }
+
+    fn check(&self, ctx: &LintContext, doc: &OpenAPI) -> Vec<Diagnostic> {
+        doc.paths
+            .keys()
+            .filter_map(|path| {
+                let offending = literal_segments(path).find(|segment| {
+                    segment.starts_with('-')
+                        || segment.ends_with('-')
+                        || segment.contains("--")
+                        || !segment
+                            .chars()
+                            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+                })?;
+                Some(ctx.diagnostic(
+                    path_location(path),
+                    format!("path segment `{}` is not kebab-case", offending),
+                ))
+            })
+            .collect()
+    }
+}
+
+/// A segment that is followed by a path parameter names a collection,
+/// and so is plural, as in `/pets/{id}` rather than `/pet/{id}`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PluralResources;
+
+impl Rule for PluralResources {
+    fn name(&self) -> &'static str {
+        "plural-resources"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Info
+    }
+
+    fn check(&self, ctx: &LintContext, doc: &OpenAPI) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        for path in doc.paths.keys() {
+            let segments: Vec<&str> = path.split('/').collect();
+            for pair in segments.windows(2) {
+                let (resource, next) = (pair[0], pair[1]);
+                if !resource.is_empty()
+                    && !resource.starts_with('{')
+                    && next.starts_with('{')
+                    && !resource.ends_with('s')
+                {
+                    diagnostics.push(ctx.diagnostic(
+                        path_location(path),
+                        format!("resource `{}` should be plural", resource),
+                    ));
+                }
+            }
+        }
+        diagnostics
+    }
+}
+
+/// The schemas of the components accept some value, as checked by
+/// [`Schema::is_satisfiable`](crate::Schema::is_satisfiable).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SatisfiableSchemas;
+
+impl Rule for SatisfiableSchemas {
+    fn name(&self) -> &'static str {
+        "satisfiable-schemas"
+    }
+
+    fn check(&self, ctx: &LintContext, doc: &OpenAPI) -> Vec<Diagnostic> {
+        let components = match &doc.components {
+            Some(components) => components,
+            None => return Vec::new(),
+        };
+        let mut diagnostics = Vec::new();
+        for (name, schema) in &components.schemas {
+            if let ReferenceOr::Item(schema) = schema {
+                for problem in schema.is_satisfiable(components).problems {
+                    diagnostics.push(ctx.diagnostic(
+                        format!(
+                            "/components/schemas/{}{}",
+                            escape_pointer_segment(name),
+                            problem.location
+                        ),
+                        problem.kind.to_string(),
+                    ));
+                }
+            }
+        }
+        diagnostics
+    }
+}
+
+/// Every operation documents at least one successful (2XX) response.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SuccessResponse;
+
+impl Rule for SuccessResponse {
+    fn name(&self) -> &'static str {
+        "success-response"
+    }
+
+    fn check(&self, ctx: &LintContext, doc: &OpenAPI) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        for_each_operation(doc, |location, operation| {
+            let success = operation
+                .responses
+                .responses
+                .keys()
+                .any(StatusCode::is_success);
+            if !success {
+                diagnostics.push(ctx.diagnostic(
+                    format!("{}/responses", location),
+                    "operation has no 2XX response",
+                ));
+            }
+        });
+        diagnostics
+    }
+}
+
+/// Every tag used by an operation is declared in the root `tags`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TagsDefined;
+
+impl Rule for TagsDefined {
+    fn name(&self) -> &'static str {
+        "tags-defined"
+    }
+
+    fn check(&self, ctx: &LintContext, doc: &OpenAPI) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        for_each_operation(doc, |location, operation| {
+            for (i, tag) in operation.tags.iter().enumerate() {
+                if !doc.tags.iter().any(|declared| declared.name == *tag) {
+                    diagnostics.push(ctx.diagnostic(
+                        format!("{}/tags/{}", location, i),
+                        format!("tag `{}` is not declared", tag),
+                    ));
+                }
+            }
+        });
+        diagnostics
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn runs_recommended_rules() {
+        let doc: OpenAPI = serde_yaml::from_str(
+            "
+openapi: 3.0.0
+info: {title: t, version: '1'}
+tags: [{name: pets}]
+paths:
+  /pets/{id}:
+    get:
+      tags: [pets]
+      responses: {'200': {description: a pet}}
+  /petOwner/{id}:
+    get:
+      tags: [owners]
+      responses: {default: {description: failure}}
+components:
+  schemas:
+    Code: {type: string, minLength: 3, maxLength: 2}
+",
+        )
+        .unwrap();
+
+        let diagnostics: Vec<String> = Linter::recommended()
+            .lint(&doc)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            diagnostics,
+            vec![
+                "warning [satisfiable-schemas] /components/schemas/Code: \
+                 minLength 3 is above maxLength 2",
+                "warning [kebab-case-paths] /paths/~1petOwner~1{id}: \
+                 path segment `petOwner` is not kebab-case",
+                "info [plural-resources] /paths/~1petOwner~1{id}: \
+                 resource `petOwner` should be plural",
+                "warning [success-response] /paths/~1petOwner~1{id}/get/responses: \
+                 operation has no 2XX response",
+                "warning [tags-defined] /paths/~1petOwner~1{id}/get/tags/0: \
+                 tag `owners` is not declared",
+            ]
+        );
+    }
+}
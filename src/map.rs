@@ -0,0 +1,34 @@
+/// The map of the objects whose keys are chosen by the author of a
+/// document: `paths`, the `components`, the `properties` of schemas and
+/// `responses`.
+///
+/// This is a [`BTreeMap`](std::collections::BTreeMap), ordered by key,
+/// unless the `preserve_order` feature is enabled, in which case it is an
+/// [`IndexMap`](indexmap::IndexMap) keeping the order of the source
+/// document.
+#[cfg(not(feature = "preserve_order"))]
+pub type Map<K, V> = std::collections::BTreeMap<K, V>;
+
+#[cfg(feature = "preserve_order")]
+pub type Map<K, V> = indexmap::IndexMap<K, V>;
+
+/// Removes `key` from `map`, keeping the order of the other entries.
+#[cfg(not(feature = "preserve_order"))]
+pub(crate) fn remove_entry<V>(map: &mut Map<String, V>, key: &str) -> Option<V> {
+    map.remove(key)
+}
+
+#[cfg(feature = "preserve_order")]
+pub(crate) fn remove_entry<V>(map: &mut Map<String, V>, key: &str) -> Option<V> {
+    map.shift_remove(key)
+}
+
+/// Orders the entries of `map` by key, as they always are without the
+/// `preserve_order` feature.
+#[cfg(not(feature = "preserve_order"))]
+pub(crate) fn sort_keys<K: Ord, V>(_: &mut Map<K, V>) {}
+
+#[cfg(feature = "preserve_order")]
+pub(crate) fn sort_keys<K: Ord + std::hash::Hash, V>(map: &mut Map<K, V>) {
+    map.sort_keys()
+}
@@ -1,4 +1,5 @@
 use crate::*;
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
@@ -12,4 +13,87 @@ pub struct MediaType {
     pub examples: BTreeMap<String, ReferenceOr<Example>>,
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub encoding: BTreeMap<String, Encoding>,
+    /// Inline extensions to this object.
+    #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
+    pub extensions: IndexMap<String, serde_json::Value>,
+}
+
+/// Content negotiation over a `content` map, keyed by media type or media
+/// type range.
+///
+/// ```
+/// use openapiv3::{Content, MediaType, Negotiate};
+///
+/// let content: Content = ["application/json", "application/*", "*/*"]
+///     .iter()
+///     .map(|key| (key.parse().unwrap(), MediaType::default()))
+///     .collect();
+/// let key = |content_type| content.negotiate(content_type).map(|(key, _)| key.as_str());
+/// assert_eq!(key("application/json; charset=utf-8"), Some("application/json"));
+/// assert_eq!(key("application/vnd.pet+json"), Some("application/json"));
+/// assert_eq!(key("application/xml"), Some("application/*"));
+/// assert_eq!(key("image/png"), Some("*/*"));
+/// ```
+pub trait Negotiate<T> {
+    /// The entry that best describes a concrete content type, such as the
+    /// `Content-Type` of a message. From best to worst, keys match:
+    ///
+    /// - the same type, such as `application/vnd.pet+json`;
+    /// - a range of the same suffix, such as `application/*+json`;
+    /// - the type of the suffix, such as `application/json`;
+    /// - a range of the same top-level type, such as `application/*`;
+    /// - `*/*`.
+    ///
+    /// Types are compared in any case. A key with parameters, such as
+    /// `text/plain; charset=utf-8`, matches only content types with the same
+    /// parameters, and is preferred over the same key without them.
+    fn negotiate(&self, content_type: &str) -> Option<(&MediaTypeRange, &T)>;
+}
+
+impl<T> Negotiate<T> for BTreeMap<MediaTypeRange, T> {
+    fn negotiate(&self, content_type: &str) -> Option<(&MediaTypeRange, &T)> {
+        let concrete = MediaTypeRange::parse(content_type).ok()?;
+        let mut best = None;
+        for (key, value) in self {
+            let score = match key.specificity(&concrete) {
+                Some(score) => score,
+                None => continue,
+            };
+            if best.as_ref().is_none_or(|(best, _, _)| score > *best) {
+                best = Some((score, key, value));
+            }
+        }
+        best.map(|(_, key, value)| (key, value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn negotiates_content_types() {
+        let content: BTreeMap<MediaTypeRange, ()> = [
+            "Text/Plain",
+            "text/plain; charset=utf-8",
+            "application/*+json",
+            "application/json",
+            "application/*",
+        ]
+        .iter()
+        .map(|key| (MediaTypeRange::from(*key), ()))
+        .collect();
+        let key = |content_type| content.negotiate(content_type).map(|(key, _)| key.as_str());
+        assert_eq!(key("text/plain"), Some("Text/Plain"));
+        assert_eq!(
+            key("text/plain;charset=\"UTF-8\""),
+            Some("text/plain; charset=utf-8")
+        );
+        assert_eq!(key("text/plain; charset=ascii"), Some("Text/Plain"));
+        assert_eq!(key("application/vnd.pet+json"), Some("application/*+json"));
+        assert_eq!(key("APPLICATION/JSON"), Some("application/json"));
+        assert_eq!(key("application/vnd.pet+xml"), Some("application/*"));
+        assert_eq!(key("text/html"), None);
+        assert_eq!(key("json"), None);
+    }
 }
@@ -0,0 +1,411 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
+/// A media type, such as `application/json; charset=utf-8`, or a range of
+/// them, such as `image/*`: the keys of `content` maps.
+///
+/// Ranges compare in any case of their type, subtype, parameter names and
+/// parameter values, and in any order of their parameters. They order by specificity: media
+/// types first, then ranges of a suffix such as `application/*+json`, then
+/// ranges of a top-level type such as `application/*`, then `*/*`. A map
+/// keyed by them thus lists its most specific entries first.
+///
+/// Any string deserializes as a range, so that documents with malformed
+/// keys still load; those keys are [`is_valid`](MediaTypeRange::is_valid)
+/// `false`, never match a content type, and order last. A range serializes
+/// as written.
+///
+/// ```
+/// use openapiv3::MediaTypeRange;
+///
+/// let range: MediaTypeRange = "Application/Vnd.Pet+JSON; Version=2".parse().unwrap();
+/// assert_eq!(range.type_(), "application");
+/// assert_eq!(range.subtype(), "vnd.pet+json");
+/// assert_eq!(range.suffix(), Some("json"));
+/// assert_eq!(range.parameter("version"), Some("2"));
+/// assert_eq!(range, "application/vnd.pet+json;version=2");
+/// assert_eq!(range.to_string(), "Application/Vnd.Pet+JSON; Version=2");
+///
+/// let mut ranges: Vec<MediaTypeRange> = vec!["*/*".into(), "text/*".into(), "text/plain".into()];
+/// ranges.sort();
+/// assert_eq!(ranges, vec!["text/plain", "text/*", "*/*"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MediaTypeRange {
+    raw: String,
+    /// The top-level type in lower case, or the whole of `raw` if it is not
+    /// a media type or range.
+    type_: String,
+    /// The subtype in lower case, empty if `raw` is not a media type or
+    /// range.
+    subtype: String,
+    /// The parameters with their names in lower case, ordered by name and
+    /// then by value in any case.
+    parameters: Vec<(String, String)>,
+}
+
+impl MediaTypeRange {
+    /// Parses a media type or range. It must have a top-level type and a
+    /// subtype, and a range must not give a subtype for the `*` type.
+    pub fn parse(media_type: &str) -> Result<Self, ParseMediaTypeRangeError> {
+        let range = MediaTypeRange::from(media_type);
+        if range.is_valid() {
+            Ok(range)
+        } else {
+            Err(ParseMediaTypeRangeError(media_type.to_owned()))
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.raw
+    }
+
+    /// Whether the range is well-formed; see [`parse`](MediaTypeRange::parse).
+    pub fn is_valid(&self) -> bool {
+        !self.subtype.is_empty()
+    }
+
+    /// The top-level type in lower case, such as `application`, or `*`.
+    pub fn type_(&self) -> &str {
+        &self.type_
+    }
+
+    /// The subtype in lower case, such as `vnd.pet+json`, `*+json` or `*`.
+    pub fn subtype(&self) -> &str {
+        &self.subtype
+    }
+
+    /// The structured syntax suffix of the subtype, such as `json` for
+    /// `application/vnd.pet+json`.
+    pub fn suffix(&self) -> Option<&str> {
+        self.subtype.rsplit_once('+').map(|(_, suffix)| suffix)
+    }
+
+    /// The value of a parameter, by its name in any case, without quotes.
+    pub fn parameter(&self, name: &str) -> Option<&str> {
+        self.parameters
+            .iter()
+            .find(|(parameter, _)| parameter.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// The parameters, with their names in lower case, ordered by name.
+    pub fn parameters(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.parameters
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    /// Whether this is JSON, such as `application/json` or
+    /// `application/vnd.pet+json`.
+    pub fn is_json(&self) -> bool {
+        self.subtype == "json" || self.suffix() == Some("json")
+    }
+
+    /// Whether this is a range rather than a single media type.
+    pub fn is_range(&self) -> bool {
+        self.type_ == "*" || self.subtype == "*" || self.subtype.starts_with("*+")
+    }
+
+    /// How closely this range matches a media type, if it matches it at
+    /// all; greater is closer. See [`Negotiate`](crate::Negotiate) for the
+    /// order.
+    pub(crate) fn specificity(&self, media_type: &MediaTypeRange) -> Option<(u8, usize)> {
+        if !self.is_valid() || (self.type_ != "*" && self.type_ != media_type.type_) {
+            return None;
+        }
+        let subtype = if self.type_ == "*" {
+            0
+        } else if self.subtype == media_type.subtype {
+            4
+        } else if self.subtype.starts_with("*+") && self.suffix() == media_type.suffix() {
+            3
+        } else if media_type.suffix() == Some(self.subtype.as_str()) {
+            2
+        } else if self.subtype == "*" {
+            1
+        } else {
+            return None;
+        };
+        let matched = self.parameters.iter().all(|(name, value)| {
+            media_type
+                .parameter(name)
+                .is_some_and(|other| other.eq_ignore_ascii_case(value))
+        });
+        matched.then_some((subtype, self.parameters.len()))
+    }
+
+    /// How broad the range is, from media types at 0 to malformed ranges.
+    fn breadth(&self) -> u8 {
+        if !self.is_valid() {
+            4
+        } else if self.type_ == "*" {
+            3
+        } else if self.subtype == "*" {
+            2
+        } else if self.subtype.starts_with("*+") {
+            1
+        } else {
+            0
+        }
+    }
+}
+
+/// Reads any string as a range; see [`MediaTypeRange::is_valid`].
+impl From<&str> for MediaTypeRange {
+    fn from(media_type: &str) -> Self {
+        MediaTypeRange::from(media_type.to_owned())
+    }
+}
+
+impl From<String> for MediaTypeRange {
+    fn from(raw: String) -> Self {
+        let mut parts = raw.split(';');
+        let essence = parts.next().unwrap_or_default().trim().to_ascii_lowercase();
+        let (type_, subtype) = match essence.split_once('/') {
+            Some((type_, subtype))
+                if !type_.is_empty()
+                    && !subtype.is_empty()
+                    && !subtype.contains('/')
+                    && (type_ != "*" || subtype == "*") =>
+            {
+                (type_.to_owned(), subtype.to_owned())
+            }
+            _ => (raw.trim().to_ascii_lowercase(), String::new()),
+        };
+        let mut parameters: Vec<(String, String)> = parts
+            .filter_map(|parameter| {
+                let (name, value) = parameter.split_once('=')?;
+                let value = value.trim();
+                let value = value
+                    .strip_prefix('"')
+                    .and_then(|value| value.strip_suffix('"'))
+                    .unwrap_or(value);
+                Some((name.trim().to_ascii_lowercase(), value.to_owned()))
+            })
+            .collect();
+        parameters.sort_by(cmp_parameters);
+        MediaTypeRange {
+            raw,
+            type_,
+            subtype,
+            parameters,
+        }
+    }
+}
+
+impl FromStr for MediaTypeRange {
+    type Err = ParseMediaTypeRangeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        MediaTypeRange::parse(s)
+    }
+}
+
+impl AsRef<str> for MediaTypeRange {
+    fn as_ref(&self) -> &str {
+        &self.raw
+    }
+}
+
+impl fmt::Display for MediaTypeRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.raw)
+    }
+}
+
+/// Compares parameter values in any case, as matching does.
+impl PartialEq for MediaTypeRange {
+    fn eq(&self, other: &Self) -> bool {
+        self.type_ == other.type_
+            && self.subtype == other.subtype
+            && self.parameters.len() == other.parameters.len()
+            && self
+                .parameters
+                .iter()
+                .zip(&other.parameters)
+                .all(|(a, b)| cmp_parameters(a, b).is_eq())
+    }
+}
+
+impl Eq for MediaTypeRange {}
+
+impl PartialEq<str> for MediaTypeRange {
+    fn eq(&self, other: &str) -> bool {
+        let other = MediaTypeRange::from(other);
+        *self == other
+    }
+}
+
+impl PartialEq<&str> for MediaTypeRange {
+    fn eq(&self, other: &&str) -> bool {
+        *self == **other
+    }
+}
+
+impl Hash for MediaTypeRange {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.type_.hash(state);
+        self.subtype.hash(state);
+        for (name, value) in &self.parameters {
+            name.hash(state);
+            folded(value).for_each(|byte| state.write_u8(byte));
+            state.write_u8(0xff);
+        }
+    }
+}
+
+/// Orders parameters by name, then by value in any case.
+fn cmp_parameters(
+    (name, value): &(String, String),
+    (other, other_value): &(String, String),
+) -> Ordering {
+    name.cmp(other)
+        .then_with(|| folded(value).cmp(folded(other_value)))
+}
+
+/// The bytes of `s` in lower case.
+fn folded(s: &str) -> impl Iterator<Item = u8> + '_ {
+    s.bytes().map(|byte| byte.to_ascii_lowercase())
+}
+
+impl Ord for MediaTypeRange {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.breadth()
+            .cmp(&other.breadth())
+            .then_with(|| self.type_.cmp(&other.type_))
+            .then_with(|| self.subtype.cmp(&other.subtype))
+            .then_with(|| other.parameters.len().cmp(&self.parameters.len()))
+            .then_with(|| {
+                self.parameters
+                    .iter()
+                    .zip(&other.parameters)
+                    .map(|(a, b)| cmp_parameters(a, b))
+                    .find(|ordering| ordering.is_ne())
+                    .unwrap_or(Ordering::Equal)
+            })
+    }
+}
+
+impl PartialOrd for MediaTypeRange {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Serialize for MediaTypeRange {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.raw)
+    }
+}
+
+impl<'de> Deserialize<'de> for MediaTypeRange {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(MediaTypeRange::from)
+    }
+}
+
+/// A string that is not a media type or range.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseMediaTypeRangeError(pub String);
+
+impl fmt::Display for ParseMediaTypeRangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}` is not a media type or media type range", self.0)
+    }
+}
+
+impl Error for ParseMediaTypeRangeError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn compares_ranges() {
+        assert_eq!(
+            MediaTypeRange::from("text/plain; charset=utf-8; format=flowed"),
+            "TEXT/Plain;Format=flowed;charset=\"utf-8\""
+        );
+        assert_ne!(
+            MediaTypeRange::from("text/plain; charset=utf-8"),
+            "text/plain"
+        );
+        assert_ne!(MediaTypeRange::from("text"), "plain");
+
+        let map: BTreeMap<MediaTypeRange, ()> = [
+            "*/*",
+            "json",
+            "application/*",
+            "application/*+json",
+            "text/plain",
+            "text/plain; charset=utf-8",
+            "application/json",
+        ]
+        .iter()
+        .map(|key| (MediaTypeRange::from(*key), ()))
+        .collect();
+        let keys: Vec<&str> = map.keys().map(MediaTypeRange::as_str).collect();
+        assert_eq!(
+            keys,
+            vec![
+                "application/json",
+                "text/plain; charset=utf-8",
+                "text/plain",
+                "application/*+json",
+                "application/*",
+                "*/*",
+                "json",
+            ]
+        );
+    }
+
+    #[test]
+    fn compares_parameter_values_in_any_case() {
+        use std::collections::HashSet;
+
+        let upper = MediaTypeRange::from("text/plain; charset=UTF-8");
+        let lower = MediaTypeRange::from("text/plain; charset=utf-8");
+        assert_eq!(upper, lower);
+        assert_eq!(upper.cmp(&lower), Ordering::Equal);
+        assert_eq!(upper.parameter("charset"), Some("UTF-8"));
+        let set: HashSet<MediaTypeRange> = vec![upper.clone(), lower].into_iter().collect();
+        assert_eq!(set.len(), 1);
+        assert_eq!(
+            upper.specificity(&"text/plain; charset=utf-8".into()),
+            Some((4, 1))
+        );
+
+        assert_ne!(upper, "text/plain; charset=utf-16");
+        let utf16 = MediaTypeRange::from("text/plain; charset=UTF-16");
+        assert_eq!(upper.cmp(&utf16), Ordering::Greater);
+        assert_eq!(
+            MediaTypeRange::from("a/b; x=B; y=1"),
+            MediaTypeRange::from("a/b; Y=1; X=b")
+        );
+    }
+
+    #[test]
+    fn parses_ranges() {
+        for valid in &["image/png", "image/*", "*/*", "application/*+xml"] {
+            assert!(MediaTypeRange::parse(valid).is_ok(), "{}", valid);
+        }
+        for invalid in &["json", "/json", "image/", "*/png", "a/b/c", ""] {
+            assert_eq!(
+                MediaTypeRange::parse(invalid),
+                Err(ParseMediaTypeRangeError(invalid.to_string()))
+            );
+        }
+        assert!(MediaTypeRange::from("image/*").is_range());
+        assert!(!MediaTypeRange::from("image/png").is_range());
+
+        let range: MediaTypeRange = serde_json::from_str("\"Text/HTML\"").unwrap();
+        assert_eq!(range, "text/html");
+        assert_eq!(serde_json::to_string(&range).unwrap(), "\"Text/HTML\"");
+    }
+}
@@ -0,0 +1,662 @@
+use crate::*;
+use std::error::Error;
+use std::fmt;
+
+/// Why the branches of an `allOf` could not be merged into one schema.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergeError {
+    /// A JSON Pointer, relative to the merged schema, to where the branches
+    /// disagree, such as `/properties/id`; empty for the schema itself.
+    pub location: String,
+    pub kind: MergeErrorKind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MergeErrorKind {
+    /// A branch is a reference that does not resolve.
+    Unresolved(ResolveError),
+    /// A branch refers back to a schema being merged, through the chain of
+    /// references given.
+    Cycle(Vec<String>),
+    /// A branch is a `oneOf` or an `anyOf`, which has no single shape to
+    /// merge.
+    Composition(&'static str),
+    /// The branches give different values for a keyword that cannot be
+    /// combined, such as two `type`s or two `pattern`s.
+    Conflict(&'static str),
+    /// The combined constraints of a keyword admit no value, such as a
+    /// `minLength` above the `maxLength`, or disjoint `enum`s.
+    Unsatisfiable(&'static str),
+}
+
+impl fmt::Display for MergeErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MergeErrorKind::Unresolved(error) => error.fmt(f),
+            MergeErrorKind::Cycle(chain) => {
+                write!(f, "circular reference: {}", chain.join(" -> "))
+            }
+            MergeErrorKind::Composition(keyword) => {
+                write!(f, "a branch with `{}` cannot be merged", keyword)
+            }
+            MergeErrorKind::Conflict(keyword) => {
+                write!(f, "the branches have conflicting `{}`", keyword)
+            }
+            MergeErrorKind::Unsatisfiable(keyword) => {
+                write!(f, "the branches together make `{}` unsatisfiable", keyword)
+            }
+        }
+    }
+}
+
+impl fmt::Display for MergeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.location.is_empty() {
+            self.kind.fmt(f)
+        } else {
+            write!(f, "{}: {}", self.location, self.kind)
+        }
+    }
+}
+
+impl Error for MergeError {}
+
+impl Schema {
+    /// Merges the branches of an `allOf`, nested ones included, into a
+    /// single schema that accepts the same values; any other schema is
+    /// returned as it is.
+    ///
+    /// Properties and `required` lists are combined, as are the bounds of
+    /// numbers, lengths and sizes, taking the tighter of each, and `enum`s,
+    /// taking the values common to them. A property that branches declare
+    /// differently is merged in the same way. The annotations of the schema
+    /// itself, such as its `description`, take precedence over those of its
+    /// branches. Keywords that do not apply to the type of the merged schema
+    /// are dropped.
+    ///
+    /// ```
+    /// use openapiv3::{Components, Schema, SchemaKind, Type};
+    ///
+    /// let components: Components = serde_yaml::from_str(
+    ///     "
+    /// schemas:
+    ///   Pet: {type: object, required: [name], properties: {name: {type: string}}}
+    /// ",
+    /// )
+    /// .unwrap();
+    /// let dog: Schema = serde_yaml::from_str(
+    ///     "
+    /// description: a dog
+    /// allOf:
+    ///   - $ref: '#/components/schemas/Pet'
+    ///   - {type: object, required: [bark], properties: {bark: {type: boolean}}}
+    /// ",
+    /// )
+    /// .unwrap();
+    /// let merged = dog.flatten_all_of(&components).unwrap();
+    /// assert_eq!(merged.schema_data.description.as_deref(), Some("a dog"));
+    /// match merged.schema_kind {
+    ///     SchemaKind::Type(Type::Object(object)) => {
+    ///         assert_eq!(object.required, ["name", "bark"]);
+    ///         assert_eq!(object.properties.len(), 2);
+    ///     }
+    ///     _ => unreachable!(),
+    /// }
+    /// ```
+    pub fn flatten_all_of(&self, components: &Components) -> Result<Schema, MergeError> {
+        Merger {
+            components,
+            location: String::new(),
+            stack: Vec::new(),
+        }
+        .flatten(self)
+    }
+}
+
+struct Merger<'c> {
+    components: &'c Components,
+    location: String,
+    /// The references being merged, outermost first.
+    stack: Vec<String>,
+}
+
+type Merged<T> = Result<T, MergeError>;
+
+impl Merger<'_> {
+    fn error(&self, kind: MergeErrorKind) -> MergeError {
+        MergeError {
+            location: self.location.clone(),
+            kind,
+        }
+    }
+
+    fn nested<T>(&mut self, segment: &str, f: impl FnOnce(&mut Self) -> T) -> T {
+        let len = self.location.len();
+        self.location.push('/');
+        self.location
+            .push_str(&crate::reference::escape_pointer_segment(segment));
+        let result = f(self);
+        self.location.truncate(len);
+        result
+    }
+
+    fn flatten(&mut self, schema: &Schema) -> Merged<Schema> {
+        let all_of = match &schema.schema_kind {
+            SchemaKind::AllOf { all_of } => all_of,
+            _ => return Ok(schema.clone()),
+        };
+        let mut branches = Vec::with_capacity(all_of.len());
+        for branch in all_of {
+            let resolved = self
+                .components
+                .resolve(branch)
+                .map_err(|error| self.error(MergeErrorKind::Unresolved(error)))?;
+            let reference = match branch {
+                ReferenceOr::Reference { reference } => reference.to_string(),
+                ReferenceOr::Item(_) => {
+                    branches.push(self.flatten(resolved)?);
+                    continue;
+                }
+            };
+            if let Some(start) = self.stack.iter().position(|r| *r == reference) {
+                let mut chain = self.stack[start..].to_vec();
+                chain.push(reference);
+                return Err(self.error(MergeErrorKind::Cycle(chain)));
+            }
+            self.stack.push(reference);
+            let flattened = self.flatten(resolved);
+            self.stack.pop();
+            branches.push(flattened?);
+        }
+
+        let mut merged = Schema::default();
+        for branch in &branches {
+            merged.schema_kind = self.kind(&merged.schema_kind, &branch.schema_kind)?;
+        }
+        merged.schema_data = data(&schema.schema_data, &branches);
+        Ok(merged)
+    }
+
+    /// Merges two schemas, as though they were the branches of an `allOf`.
+    fn schema(
+        &mut self,
+        a: &ReferenceOr<Box<Schema>>,
+        b: &ReferenceOr<Box<Schema>>,
+    ) -> Merged<ReferenceOr<Box<Schema>>> {
+        if a == b {
+            return Ok(a.clone());
+        }
+        // References are kept, so that merging them can tell cycles.
+        let all_of = vec![a.clone().unbox(), b.clone().unbox()];
+        let merged = self.flatten(&Schema {
+            schema_data: SchemaData::default(),
+            schema_kind: SchemaKind::AllOf { all_of },
+        })?;
+        Ok(ReferenceOr::Item(Box::new(merged)))
+    }
+
+    fn kind(&mut self, a: &SchemaKind, b: &SchemaKind) -> Merged<SchemaKind> {
+        let typed = |kind| Ok(SchemaKind::Type(kind));
+        match (a, b) {
+            (SchemaKind::OneOf { .. }, _) | (_, SchemaKind::OneOf { .. }) => {
+                Err(self.error(MergeErrorKind::Composition("oneOf")))
+            }
+            (SchemaKind::AnyOf { .. }, _) | (_, SchemaKind::AnyOf { .. }) => {
+                Err(self.error(MergeErrorKind::Composition("anyOf")))
+            }
+            // Nested `allOf`s are flattened before they are merged.
+            (SchemaKind::AllOf { .. }, _) | (_, SchemaKind::AllOf { .. }) => {
+                unreachable!("allOf is flattened before merging")
+            }
+            (SchemaKind::Any(a), SchemaKind::Any(b)) => Ok(SchemaKind::Any(self.any(a, b)?)),
+            (SchemaKind::Any(any), SchemaKind::Type(kind))
+            | (SchemaKind::Type(kind), SchemaKind::Any(any)) => {
+                let constraints = typed_constraints(any, kind);
+                typed(self.types(kind, &constraints)?)
+            }
+            (SchemaKind::Type(a), SchemaKind::Type(b)) => typed(self.types(a, b)?),
+        }
+    }
+
+    fn types(&mut self, a: &Type, b: &Type) -> Merged<Type> {
+        Ok(match (a, b) {
+            (Type::String(a), Type::String(b)) => Type::String(StringType {
+                format: self.format(&a.format, &b.format)?,
+                pattern: self.same("pattern", &a.pattern, &b.pattern)?,
+                enumeration: self.enumeration(&a.enumeration, &b.enumeration)?,
+                min_length: max(a.min_length, b.min_length),
+                max_length: min(a.max_length, b.max_length),
+            }),
+            (Type::Number(a), Type::Number(b)) => {
+                let (minimum, exclusive_minimum) = self.bound(
+                    (a.minimum, a.exclusive_minimum),
+                    (b.minimum, b.exclusive_minimum),
+                    |a, b| a > b,
+                );
+                let (maximum, exclusive_maximum) = self.bound(
+                    (a.maximum, a.exclusive_maximum),
+                    (b.maximum, b.exclusive_maximum),
+                    |a, b| a < b,
+                );
+                Type::Number(NumberType {
+                    format: self.format(&a.format, &b.format)?,
+                    multiple_of: self.same("multipleOf", &a.multiple_of, &b.multiple_of)?,
+                    exclusive_minimum,
+                    exclusive_maximum,
+                    minimum,
+                    maximum,
+                    enumeration: self.enumeration(&a.enumeration, &b.enumeration)?,
+                })
+            }
+            (Type::Integer(a), Type::Integer(b)) => {
+                let (minimum, exclusive_minimum) = self.bound(
+                    (a.minimum, a.exclusive_minimum),
+                    (b.minimum, b.exclusive_minimum),
+                    |a, b| a > b,
+                );
+                let (maximum, exclusive_maximum) = self.bound(
+                    (a.maximum, a.exclusive_maximum),
+                    (b.maximum, b.exclusive_maximum),
+                    |a, b| a < b,
+                );
+                Type::Integer(IntegerType {
+                    format: self.format(&a.format, &b.format)?,
+                    multiple_of: self.same("multipleOf", &a.multiple_of, &b.multiple_of)?,
+                    exclusive_minimum,
+                    exclusive_maximum,
+                    minimum,
+                    maximum,
+                    enumeration: self.enumeration(&a.enumeration, &b.enumeration)?,
+                })
+            }
+            (Type::Object(a), Type::Object(b)) => Type::Object(ObjectType {
+                properties: self.properties(&a.properties, &b.properties)?,
+                required: union(&a.required, &b.required),
+                additional_properties: self
+                    .additional_properties(&a.additional_properties, &b.additional_properties)?,
+                min_properties: max(a.min_properties, b.min_properties),
+                max_properties: min(a.max_properties, b.max_properties),
+            }),
+            (Type::Array(a), Type::Array(b)) => Type::Array(ArrayType {
+                items: self.nested("items", |merger| merger.schema(&a.items, &b.items))?,
+                min_items: max(a.min_items, b.min_items),
+                max_items: min(a.max_items, b.max_items),
+                unique_items: a.unique_items || b.unique_items,
+            }),
+            (Type::Boolean {}, Type::Boolean {}) => Type::Boolean {},
+            _ => return Err(self.error(MergeErrorKind::Conflict("type"))),
+        })
+        .and_then(|merged| self.check_bounds(merged))
+    }
+
+    fn any(&mut self, a: &AnySchema, b: &AnySchema) -> Merged<AnySchema> {
+        let (minimum, exclusive_minimum) = self.bound(
+            (a.minimum, a.exclusive_minimum.unwrap_or(false)),
+            (b.minimum, b.exclusive_minimum.unwrap_or(false)),
+            |a, b| a > b,
+        );
+        let (maximum, exclusive_maximum) = self.bound(
+            (a.maximum, a.exclusive_maximum.unwrap_or(false)),
+            (b.maximum, b.exclusive_maximum.unwrap_or(false)),
+            |a, b| a < b,
+        );
+        let items = match (&a.items, &b.items) {
+            (Some(a), Some(b)) => Some(self.nested("items", |merger| merger.schema(a, b))?),
+            (items, None) | (None, items) => items.clone(),
+        };
+        Ok(AnySchema {
+            pattern: self.same("pattern", &a.pattern, &b.pattern)?,
+            multiple_of: self.same("multipleOf", &a.multiple_of, &b.multiple_of)?,
+            exclusive_minimum: Some(exclusive_minimum).filter(|exclusive| *exclusive),
+            exclusive_maximum: Some(exclusive_maximum).filter(|exclusive| *exclusive),
+            minimum,
+            maximum,
+            properties: self.properties(&a.properties, &b.properties)?,
+            required: union(&a.required, &b.required),
+            additional_properties: self
+                .additional_properties(&a.additional_properties, &b.additional_properties)?,
+            min_propeties: max(a.min_propeties, b.min_propeties),
+            max_properties: min(a.max_properties, b.max_properties),
+            items,
+            min_items: max(a.min_items, b.min_items),
+            max_items: min(a.max_items, b.max_items),
+            unique_items: match (a.unique_items, b.unique_items) {
+                (None, None) => None,
+                (a, b) => Some(a.unwrap_or(false) || b.unwrap_or(false)),
+            },
+            format: self.same("format", &a.format, &b.format)?,
+            min_length: max(a.min_length, b.min_length),
+            max_length: min(a.max_length, b.max_length),
+        })
+    }
+
+    fn properties(
+        &mut self,
+        a: &Map<Name, ReferenceOr<Box<Schema>>>,
+        b: &Map<Name, ReferenceOr<Box<Schema>>>,
+    ) -> Merged<Map<Name, ReferenceOr<Box<Schema>>>> {
+        let mut merged = a.clone();
+        for (name, schema) in b {
+            let property = match merged.get(name) {
+                Some(existing) => self.nested("properties", |merger| {
+                    merger.nested(name, |merger| merger.schema(existing, schema))
+                })?,
+                None => schema.clone(),
+            };
+            merged.insert(name.clone(), property);
+        }
+        Ok(merged)
+    }
+
+    fn additional_properties(
+        &mut self,
+        a: &Option<AdditionalProperties>,
+        b: &Option<AdditionalProperties>,
+    ) -> Merged<Option<AdditionalProperties>> {
+        Ok(match (a, b) {
+            (a, b) if a == b => a.clone(),
+            (None, other) | (other, None) => other.clone(),
+            (Some(AdditionalProperties::Any), other) | (other, Some(AdditionalProperties::Any)) => {
+                other.clone()
+            }
+            (Some(AdditionalProperties::None), _) | (_, Some(AdditionalProperties::None)) => {
+                Some(AdditionalProperties::None)
+            }
+            (Some(AdditionalProperties::Schema(a)), Some(AdditionalProperties::Schema(b))) => {
+                let (a, b) = ((**a).clone().map(Box::new), (**b).clone().map(Box::new));
+                let merged = self.nested("additionalProperties", |merger| merger.schema(&a, &b))?;
+                Some(AdditionalProperties::Schema(Box::new(merged.unbox())))
+            }
+        })
+    }
+
+    fn format<T: Clone + PartialEq>(
+        &self,
+        a: &VariantOrUnknownOrEmpty<T>,
+        b: &VariantOrUnknownOrEmpty<T>,
+    ) -> Merged<VariantOrUnknownOrEmpty<T>> {
+        match (a, b) {
+            (VariantOrUnknownOrEmpty::Empty, other) | (other, VariantOrUnknownOrEmpty::Empty) => {
+                Ok(other.clone())
+            }
+            (a, b) if a == b => Ok(a.clone()),
+            _ => Err(self.error(MergeErrorKind::Conflict("format"))),
+        }
+    }
+
+    /// The value of a keyword that only one branch may give, or that all
+    /// branches giving it must agree on.
+    fn same<T: Clone + PartialEq>(
+        &self,
+        keyword: &'static str,
+        a: &Option<T>,
+        b: &Option<T>,
+    ) -> Merged<Option<T>> {
+        match (a, b) {
+            (Some(a), Some(b)) if a != b => Err(self.error(MergeErrorKind::Conflict(keyword))),
+            (Some(value), _) | (None, Some(value)) => Ok(Some(value.clone())),
+            (None, None) => Ok(None),
+        }
+    }
+
+    /// The values common to two `enum`s, an empty one allowing any value.
+    fn enumeration<T: Clone + PartialEq>(&self, a: &[T], b: &[T]) -> Merged<Vec<T>> {
+        if a.is_empty() || b.is_empty() {
+            return Ok(if a.is_empty() { b } else { a }.to_vec());
+        }
+        let common: Vec<T> = a
+            .iter()
+            .filter(|value| b.contains(value))
+            .cloned()
+            .collect();
+        if common.is_empty() {
+            return Err(self.error(MergeErrorKind::Unsatisfiable("enum")));
+        }
+        Ok(common)
+    }
+
+    /// The tighter of two bounds and whether it is exclusive, where
+    /// `tighter(a, b)` tells whether `a` is tighter than `b`.
+    fn bound<T: PartialEq + Copy>(
+        &self,
+        a: (Option<T>, bool),
+        b: (Option<T>, bool),
+        tighter: impl Fn(T, T) -> bool,
+    ) -> (Option<T>, bool) {
+        match (a, b) {
+            ((Some(x), x_exclusive), (Some(y), y_exclusive)) => {
+                if x == y {
+                    (Some(x), x_exclusive || y_exclusive)
+                } else if tighter(x, y) {
+                    (Some(x), x_exclusive)
+                } else {
+                    (Some(y), y_exclusive)
+                }
+            }
+            ((Some(x), exclusive), (None, _)) | ((None, _), (Some(x), exclusive)) => {
+                (Some(x), exclusive)
+            }
+            ((None, _), (None, _)) => (None, false),
+        }
+    }
+
+    /// Checks that lower bounds do not exceed upper ones.
+    fn check_bounds(&self, merged: Type) -> Merged<Type> {
+        let above = |min: Option<usize>, max: Option<usize>| {
+            min.zip(max).is_some_and(|(min, max)| min > max)
+        };
+        let keyword = match &merged {
+            Type::String(string) if above(string.min_length, string.max_length) => "maxLength",
+            Type::Object(object) if above(object.min_properties, object.max_properties) => {
+                "maxProperties"
+            }
+            Type::Array(array) if above(array.min_items, array.max_items) => "maxItems",
+            Type::Number(number) => match number.minimum.zip(number.maximum) {
+                Some((min, max))
+                    if min > max
+                        || (min == max
+                            && (number.exclusive_minimum || number.exclusive_maximum)) =>
+                {
+                    "maximum"
+                }
+                _ => return Ok(merged),
+            },
+            Type::Integer(integer) => match integer.minimum.zip(integer.maximum) {
+                Some((min, max))
+                    if min > max
+                        || (min == max
+                            && (integer.exclusive_minimum || integer.exclusive_maximum)) =>
+                {
+                    "maximum"
+                }
+                _ => return Ok(merged),
+            },
+            _ => return Ok(merged),
+        };
+        Err(self.error(MergeErrorKind::Unsatisfiable(keyword)))
+    }
+}
+
+/// The annotations of a merged schema: those of the schema itself, with
+/// those of its branches filling in what it leaves out. A merged schema is
+/// nullable only if every branch is, and read-only, write-only or
+/// deprecated if any is.
+fn data(own: &SchemaData, branches: &[Schema]) -> SchemaData {
+    let mut data = own.clone();
+    let all = || branches.iter().map(|branch| &branch.schema_data);
+    data.nullable |= !branches.is_empty() && all().all(|branch| branch.nullable);
+    data.read_only |= all().any(|branch| branch.read_only);
+    data.write_only |= all().any(|branch| branch.write_only);
+    data.deprecated |= all().any(|branch| branch.deprecated);
+    for branch in all() {
+        data.external_docs = data.external_docs.or_else(|| branch.external_docs.clone());
+        data.example = data.example.or_else(|| branch.example.clone());
+        data.title = data.title.or_else(|| branch.title.clone());
+        data.description = data.description.or_else(|| branch.description.clone());
+        data.discriminator = data.discriminator.or_else(|| branch.discriminator.clone());
+        data.default = data.default.or_else(|| branch.default.clone());
+        data.xml = data.xml.or_else(|| branch.xml.clone());
+        for (name, value) in &branch.extensions {
+            data.extensions
+                .entry(name.clone())
+                .or_insert_with(|| value.clone());
+        }
+    }
+    data
+}
+
+/// The keywords of an untyped schema that apply to values of a type, as a
+/// schema of that type.
+fn typed_constraints(any: &AnySchema, kind: &Type) -> Type {
+    match kind {
+        Type::String(_) => Type::String(StringType {
+            format: any
+                .format
+                .as_ref()
+                .map_or(VariantOrUnknownOrEmpty::Empty, |format| {
+                    serde_json::from_value(serde_json::Value::from(format.as_str()))
+                        .unwrap_or(VariantOrUnknownOrEmpty::Unknown(format.clone()))
+                }),
+            pattern: any.pattern.clone(),
+            enumeration: Vec::new(),
+            min_length: any.min_length,
+            max_length: any.max_length,
+        }),
+        Type::Number(_) => Type::Number(NumberType {
+            multiple_of: any.multiple_of,
+            exclusive_minimum: any.exclusive_minimum.unwrap_or(false),
+            exclusive_maximum: any.exclusive_maximum.unwrap_or(false),
+            minimum: any.minimum,
+            maximum: any.maximum,
+            ..NumberType::default()
+        }),
+        Type::Integer(_) => Type::Integer(IntegerType {
+            // Bounds and multiples that are not integers are left out.
+            multiple_of: any.multiple_of.and_then(integer),
+            exclusive_minimum: any.exclusive_minimum.unwrap_or(false),
+            exclusive_maximum: any.exclusive_maximum.unwrap_or(false),
+            minimum: any.minimum.and_then(integer),
+            maximum: any.maximum.and_then(integer),
+            ..IntegerType::default()
+        }),
+        Type::Object(_) => Type::Object(ObjectType {
+            properties: any.properties.clone(),
+            required: any.required.clone(),
+            additional_properties: any.additional_properties.clone(),
+            min_properties: any.min_propeties,
+            max_properties: any.max_properties,
+        }),
+        Type::Array(_) => Type::Array(ArrayType {
+            items: any
+                .items
+                .clone()
+                .unwrap_or_else(|| ReferenceOr::Item(Box::default())),
+            min_items: any.min_items,
+            max_items: any.max_items,
+            unique_items: any.unique_items.unwrap_or(false),
+        }),
+        Type::Boolean {} => Type::Boolean {},
+    }
+}
+
+fn integer(value: f64) -> Option<i64> {
+    Some(value as i64).filter(|integer| *integer as f64 == value)
+}
+
+fn max<T: Ord>(a: Option<T>, b: Option<T>) -> Option<T> {
+    a.into_iter().chain(b).max()
+}
+
+fn min<T: Ord>(a: Option<T>, b: Option<T>) -> Option<T> {
+    a.into_iter().chain(b).min()
+}
+
+fn union(a: &[Name], b: &[Name]) -> Vec<Name> {
+    let mut union = a.to_vec();
+    union.extend(b.iter().filter(|name| !a.contains(name)).cloned());
+    union
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn merge(yaml: &str) -> Result<Schema, String> {
+        let components: Components = serde_yaml::from_str(
+            "
+schemas:
+  Named: {type: object, required: [name], properties: {name: {type: string, maxLength: 10}}}
+  Loop: {allOf: [{$ref: '#/components/schemas/Loop'}]}
+",
+        )
+        .unwrap();
+        let schema: Schema = serde_yaml::from_str(yaml).unwrap();
+        schema
+            .flatten_all_of(&components)
+            .map_err(|error| error.to_string())
+    }
+
+    #[test]
+    fn merges_branches() {
+        let merged = merge(
+            "
+allOf:
+  - $ref: '#/components/schemas/Named'
+  - allOf:
+      - {maxProperties: 5, properties: {name: {minLength: 2}}}
+      - type: object
+        nullable: true
+        properties:
+          age: {type: integer, minimum: 0, maximum: 100, enum: [1, 2, 3]}
+  - {properties: {age: {type: integer, exclusiveMinimum: true, minimum: 0, enum: [0, 1, 2]}}}
+",
+        )
+        .unwrap();
+        let expected: Schema = serde_yaml::from_str(
+            "
+type: object
+required: [name]
+maxProperties: 5
+properties:
+  name: {type: string, minLength: 2, maxLength: 10}
+  age: {type: integer, exclusiveMinimum: true, minimum: 0, maximum: 100, enum: [1, 2]}
+",
+        )
+        .unwrap();
+        assert_eq!(merged, expected);
+
+        let plain: Schema = serde_yaml::from_str("{type: string}").unwrap();
+        assert_eq!(merge("{type: string}"), Ok(plain));
+    }
+
+    #[test]
+    fn reports_conflicts() {
+        assert_eq!(
+            merge("allOf: [{type: string}, {type: integer}]"),
+            Err("the branches have conflicting `type`".to_owned())
+        );
+        assert_eq!(
+            merge("allOf: [{$ref: '#/components/schemas/Named'}, {properties: {name: {maxLength: 20, pattern: a}}}, {properties: {name: {pattern: b}}}]"),
+            Err("/properties/name: the branches have conflicting `pattern`".to_owned())
+        );
+        assert_eq!(
+            merge("allOf: [{type: string, minLength: 5}, {maxLength: 4}]"),
+            Err("the branches together make `maxLength` unsatisfiable".to_owned())
+        );
+        assert_eq!(
+            merge("allOf: [{type: string, enum: [a]}, {type: string, enum: [b]}]"),
+            Err("the branches together make `enum` unsatisfiable".to_owned())
+        );
+        assert_eq!(
+            merge("allOf: [{oneOf: [{type: string}]}]"),
+            Err("a branch with `oneOf` cannot be merged".to_owned())
+        );
+        assert_eq!(
+            merge("allOf: [{$ref: '#/components/schemas/Loop'}]"),
+            Err(
+                "circular reference: #/components/schemas/Loop -> #/components/schemas/Loop"
+                    .to_owned()
+            )
+        );
+    }
+}
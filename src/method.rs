@@ -0,0 +1,91 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// An HTTP method that a Path Item Object can describe an operation for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Method {
+    Get,
+    Put,
+    Post,
+    Delete,
+    Options,
+    Head,
+    Patch,
+    Trace,
+}
+
+impl Method {
+    /// Every method, in the order of the fields of [`PathItem`](crate::PathItem).
+    pub const ALL: [Method; 8] = [
+        Method::Get,
+        Method::Put,
+        Method::Post,
+        Method::Delete,
+        Method::Options,
+        Method::Head,
+        Method::Patch,
+        Method::Trace,
+    ];
+
+    /// The name of the method as a field of a Path Item Object, such as
+    /// `get`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Method::Get => "get",
+            Method::Put => "put",
+            Method::Post => "post",
+            Method::Delete => "delete",
+            Method::Options => "options",
+            Method::Head => "head",
+            Method::Patch => "patch",
+            Method::Trace => "trace",
+        }
+    }
+}
+
+/// Formats the method as it appears in requests, such as `GET`.
+impl fmt::Display for Method {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.as_str().to_ascii_uppercase())
+    }
+}
+
+/// An error parsing a [`Method`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseMethodError(pub String);
+
+impl fmt::Display for ParseMethodError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}` is not a method of a Path Item Object", self.0)
+    }
+}
+
+impl Error for ParseMethodError {}
+
+/// Parses a method in any case, such as `get` or `GET`.
+impl FromStr for Method {
+    type Err = ParseMethodError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Method::ALL
+            .iter()
+            .copied()
+            .find(|method| method.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| ParseMethodError(s.to_owned()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_and_formats() {
+        assert_eq!("GET".parse(), Ok(Method::Get));
+        assert_eq!("patch".parse(), Ok(Method::Patch));
+        assert!("connect".parse::<Method>().is_err());
+        assert_eq!(Method::Delete.to_string(), "DELETE");
+        assert_eq!(Method::Delete.as_str(), "delete");
+    }
+}
@@ -0,0 +1,281 @@
+//! Responses made up from a document, for standing in for the API it
+//! describes before it exists.
+//!
+//! A [`MockServer`] answers requests by their method, path and headers,
+//! and leaves receiving and sending them to whatever server it is mounted
+//! in.
+//!
+//! ```
+//! use openapiv3::mock::MockServer;
+//! use openapiv3::{Method, OpenAPI};
+//!
+//! let api: OpenAPI = serde_yaml::from_str(
+//!     "
+//! openapi: 3.0.0
+//! info: {title: t, version: '1'}
+//! paths:
+//!   /pets/{id}:
+//!     get:
+//!       responses:
+//!         200:
+//!           description: a pet
+//!           content:
+//!             application/json: {example: {name: Rex}}
+//!         404: {description: no such pet}
+//! ",
+//! )
+//! .unwrap();
+//! let server = MockServer::new(&api);
+//!
+//! let response = server.respond(Method::Get, "/pets/7", &[]);
+//! assert_eq!(response.status, 200);
+//! assert_eq!(response.body, b"{\n  \"name\": \"Rex\"\n}");
+//!
+//! let response = server.respond(Method::Get, "/pets/7", &[("Prefer", "code=404")]);
+//! assert_eq!(response.status, 404);
+//! assert!(response.body.is_empty());
+//!
+//! assert_eq!(server.respond(Method::Get, "/owners", &[]).status, 404);
+//! assert_eq!(server.respond(Method::Post, "/pets/7", &[]).status, 405);
+//! ```
+
+use crate::request_example::{sample, serialize_body};
+use crate::*;
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+/// Answers requests with the responses operations describe.
+#[derive(Debug, Clone)]
+pub struct MockServer<'a> {
+    router: OpenApiRouter<'a>,
+    components: Cow<'a, Components>,
+}
+
+/// A response made up by a [`MockServer`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MockResponse {
+    pub status: u16,
+    /// The headers, by name and value, `Content-Type` included.
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl<'a> MockServer<'a> {
+    pub fn new(api: &'a OpenAPI) -> Self {
+        MockServer {
+            router: api.router(),
+            components: match &api.components {
+                Some(components) => Cow::Borrowed(components),
+                None => Cow::Owned(Components::default()),
+            },
+        }
+    }
+
+    /// The response to a request with the given method, path and headers.
+    ///
+    /// The operation is found as by an [`OpenApiRouter`], and a response
+    /// of 404 or 405 made if there is none. The response is the one for the
+    /// code a `Prefer: code=...` header asks for, if the operation
+    /// describes it; else that of the lowest success code, then of `2XX`
+    /// as 200, then the default as 200, then the lowest code described.
+    /// An operation with no responses is answered with 501.
+    ///
+    /// The body is of the media type the `Accept` header asks for, else
+    /// the first described, and made up as
+    /// [`Operation::example_request`] makes up bodies, as are the headers
+    /// the response describes.
+    pub fn respond(&self, method: Method, path: &str, headers: &[(&str, &str)]) -> MockResponse {
+        let operation = match self.router.route(method, path) {
+            Ok(route) => route.operation,
+            Err(RouteError::NotFound) => return status(404),
+            Err(RouteError::MethodNotAllowed(allowed)) => {
+                let allowed: Vec<String> = allowed.iter().map(Method::to_string).collect();
+                let mut response = status(405);
+                response
+                    .headers
+                    .push(("Allow".to_owned(), allowed.join(", ")));
+                return response;
+            }
+        };
+        let header = |name: &str| {
+            headers
+                .iter()
+                .find(|(actual, _)| actual.eq_ignore_ascii_case(name))
+                .map(|(_, value)| *value)
+        };
+
+        let (code, described) = match choose(&operation.responses, header("Prefer")) {
+            Some(chosen) => chosen,
+            None => return status(501),
+        };
+        let components = self.components.as_ref();
+        let described = match components.resolve(described) {
+            Ok(described) => described,
+            Err(_) => return status(code),
+        };
+
+        let mut response = status(code);
+        for (name, header) in &described.headers {
+            if name.eq_ignore_ascii_case("content-type") {
+                continue;
+            }
+            let header = match components.resolve(header) {
+                Ok(header) => header,
+                Err(_) => continue,
+            };
+            let schema = match &header.format {
+                ParameterSchemaOrContent::Schema(schema) => Some(schema),
+                ParameterSchemaOrContent::Content(_) => None,
+            };
+            if let Some(value) = sample(components, &header.example, &header.examples, schema) {
+                let value = header.clone().into_parameter(name.clone()).encode(&value);
+                response.headers.push((name.clone(), value));
+            }
+        }
+
+        let media_type = accepted(&described.content, header("Accept"));
+        if let Some((content_type, ReferenceOr::Item(media_type))) = media_type {
+            let value = sample(
+                components,
+                &media_type.example,
+                &media_type.examples,
+                media_type.schema.as_ref(),
+            );
+            let (content_type, body) = serialize_body(components, content_type, media_type, value);
+            response
+                .headers
+                .push(("Content-Type".to_owned(), content_type));
+            response.body = body.into_bytes();
+        }
+        response
+    }
+}
+
+fn status(status: u16) -> MockResponse {
+    MockResponse {
+        status,
+        ..MockResponse::default()
+    }
+}
+
+/// The status code to respond with and its response.
+fn choose<'r>(
+    responses: &'r Responses,
+    prefer: Option<&str>,
+) -> Option<(u16, &'r ReferenceOr<Response>)> {
+    let preferred = prefer.and_then(|prefer| {
+        prefer
+            .split([',', ';'])
+            .filter_map(|preference| preference.trim().strip_prefix("code="))
+            .find_map(|code| code.trim_matches('"').parse::<u16>().ok())
+    });
+    if let Some(code) = preferred {
+        if let Some(response) = responses.get_for(code) {
+            return Some((code, response));
+        }
+    }
+    let codes = || {
+        responses
+            .responses
+            .iter()
+            .filter_map(|(code, response)| match code {
+                StatusCode::Code(code) => Some((*code, response)),
+                StatusCode::Range(_) => None,
+            })
+    };
+    codes()
+        .filter(|(code, _)| (200..300).contains(code))
+        .min_by_key(|(code, _)| *code)
+        .or_else(|| {
+            let success = responses.responses.get(&StatusCode::Range(2));
+            success.map(|response| (200, response))
+        })
+        .or_else(|| responses.default.as_ref().map(|response| (200, response)))
+        .or_else(|| codes().min_by_key(|(code, _)| *code))
+}
+
+/// The media type that an `Accept` header asks for first, or the first
+/// described.
+fn accepted<'c>(
+    content: &'c BTreeMap<MediaTypeRange, ReferenceOr<MediaType>>,
+    accept: Option<&str>,
+) -> Option<(&'c MediaTypeRange, &'c ReferenceOr<MediaType>)> {
+    let accepted = accept.into_iter().flat_map(|accept| accept.split(','));
+    for range in accepted.filter_map(|range| MediaTypeRange::parse(range.trim()).ok()) {
+        let found = content.iter().find(|(content_type, _)| {
+            without_parameters(&range)
+                .specificity(content_type)
+                .is_some()
+        });
+        if found.is_some() {
+            return found;
+        }
+    }
+    content.iter().next()
+}
+
+/// A range of `Accept` without parameters such as its weight, `q`.
+fn without_parameters(range: &MediaTypeRange) -> MediaTypeRange {
+    MediaTypeRange::from(format!("{}/{}", range.type_(), range.subtype()).as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mocks_responses() {
+        let api: OpenAPI = serde_yaml::from_str(
+            "
+openapi: 3.0.0
+info: {title: t, version: '1'}
+paths:
+  /pets:
+    get:
+      responses:
+        2XX:
+          description: pets
+          headers:
+            X-Total: {schema: {type: integer, example: 3}}
+          content:
+            text/plain: {example: Rex}
+            application/json:
+              schema: {type: array, items: {type: string, example: Tom}}
+        default: {$ref: '#/components/responses/Error'}
+    put: {responses: {}}
+components:
+  responses:
+    Error:
+      description: an error
+      content:
+        application/json: {example: {message: oops}}
+",
+        )
+        .unwrap();
+        let server = MockServer::new(&api);
+
+        let response = server.respond(Method::Get, "/pets", &[("accept", "application/*;q=0.9")]);
+        assert_eq!(response.status, 200);
+        assert_eq!(
+            response.headers,
+            [
+                ("X-Total".to_owned(), "3".to_owned()),
+                ("Content-Type".to_owned(), "application/json".to_owned()),
+            ]
+        );
+        assert_eq!(response.body, b"[\n  \"Tom\"\n]");
+        let response = server.respond(Method::Get, "/pets", &[("Accept", "image/png, text/*")]);
+        assert_eq!(response.body, b"Rex");
+
+        let response = server.respond(Method::Get, "/pets", &[("Prefer", "code=500")]);
+        assert_eq!(response.status, 500);
+        assert_eq!(response.body, b"{\n  \"message\": \"oops\"\n}");
+
+        assert_eq!(server.respond(Method::Put, "/pets", &[]).status, 501);
+        let response = server.respond(Method::Delete, "/pets", &[]);
+        assert_eq!(
+            response.headers,
+            [("Allow".to_owned(), "GET, PUT".to_owned())]
+        );
+    }
+}
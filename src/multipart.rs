@@ -0,0 +1,496 @@
+use crate::style::coerce;
+use crate::*;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+
+/// A part of a `multipart/form-data` body: the value of a property, or a
+/// file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Part {
+    /// The name of the property, from the `Content-Disposition` header.
+    pub name: String,
+    /// The name of the file, for a part that is one.
+    pub filename: Option<String>,
+    /// The `Content-Type` header, which is `text/plain` if absent.
+    pub content_type: Option<String>,
+    /// The other headers, but for `Content-Disposition`.
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+/// A `multipart/form-data` body, as built by [`MediaType::encode_multipart`]
+/// or parsed with [`Multipart::parse`].
+///
+/// ```
+/// use openapiv3::{Multipart, Part};
+///
+/// let multipart = Multipart {
+///     boundary: "xyz".to_owned(),
+///     parts: vec![Part {
+///         name: "note".to_owned(),
+///         body: b"hello".to_vec(),
+///         ..Part::default()
+///     }],
+/// };
+/// let body = multipart.to_bytes();
+/// assert_eq!(
+///     body,
+///     b"--xyz\r\nContent-Disposition: form-data; name=\"note\"\r\n\r\nhello\r\n--xyz--\r\n"
+/// );
+/// assert_eq!(Multipart::parse(&multipart.content_type(), &body), Ok(multipart));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Multipart {
+    pub boundary: String,
+    pub parts: Vec<Part>,
+}
+
+/// The reasons a multipart body cannot be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MultipartError {
+    /// The content type is not a `multipart` one with a `boundary`.
+    MissingBoundary,
+    /// The body does not begin with the boundary, or does not end with it.
+    MissingDelimiter,
+    /// The headers of a part are not `Name: value` lines.
+    MalformedHeaders,
+    /// A part has no `Content-Disposition` header naming it.
+    MissingName,
+}
+
+impl fmt::Display for MultipartError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            MultipartError::MissingBoundary => "the content type has no multipart boundary",
+            MultipartError::MissingDelimiter => "the body is not delimited by its boundary",
+            MultipartError::MalformedHeaders => "the headers of a part are malformed",
+            MultipartError::MissingName => "a part has no name",
+        })
+    }
+}
+
+impl Error for MultipartError {}
+
+impl Multipart {
+    /// The `Content-Type` of the body, with its boundary.
+    pub fn content_type(&self) -> String {
+        format!("multipart/form-data; boundary={}", self.boundary)
+    }
+
+    /// The body, each part preceded by the boundary and its headers.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        for part in &self.parts {
+            out.extend_from_slice(format!("--{}\r\n", self.boundary).as_bytes());
+            let mut disposition = format!("form-data; name=\"{}\"", quote(&part.name));
+            if let Some(filename) = &part.filename {
+                disposition.push_str(&format!("; filename=\"{}\"", quote(filename)));
+            }
+            let headers = std::iter::once(("Content-Disposition", disposition.as_str()))
+                .chain(part.content_type.as_deref().map(|t| ("Content-Type", t)))
+                .chain(part.headers.iter().map(|(n, v)| (n.as_str(), v.as_str())));
+            for (name, value) in headers {
+                out.extend_from_slice(format!("{}: {}\r\n", name, value).as_bytes());
+            }
+            out.extend_from_slice(b"\r\n");
+            out.extend_from_slice(&part.body);
+            out.extend_from_slice(b"\r\n");
+        }
+        out.extend_from_slice(format!("--{}--\r\n", self.boundary).as_bytes());
+        out
+    }
+
+    /// Parses a body of the given `Content-Type`, which names its boundary.
+    /// Anything before the first boundary and after the last is ignored.
+    pub fn parse(content_type: &str, body: &[u8]) -> Result<Multipart, MultipartError> {
+        let range =
+            MediaTypeRange::parse(content_type).map_err(|_| MultipartError::MissingBoundary)?;
+        let boundary = match range.parameter("boundary") {
+            Some(boundary) if range.type_() == "multipart" && !boundary.is_empty() => boundary,
+            _ => return Err(MultipartError::MissingBoundary),
+        };
+        let delimiter = format!("\r\n--{}", boundary).into_bytes();
+
+        // The first delimiter may begin the body, without the line break
+        // before the others.
+        let start = if body.starts_with(&delimiter[2..]) {
+            delimiter.len() - 2
+        } else {
+            find(body, &delimiter).ok_or(MultipartError::MissingDelimiter)? + delimiter.len()
+        };
+        let mut rest = &body[start..];
+        let mut parts = Vec::new();
+        loop {
+            if rest.starts_with(b"--") {
+                break;
+            }
+            rest = rest
+                .strip_prefix(b"\r\n")
+                .ok_or(MultipartError::MissingDelimiter)?;
+            let end = find(rest, &delimiter).ok_or(MultipartError::MissingDelimiter)?;
+            parts.push(parse_part(&rest[..end])?);
+            rest = &rest[end + delimiter.len()..];
+        }
+        Ok(Multipart {
+            boundary: boundary.to_owned(),
+            parts,
+        })
+    }
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+/// Escapes a name for a quoted `Content-Disposition` parameter as browsers
+/// do, by percent-encoding quotes and line breaks.
+fn quote(name: &str) -> String {
+    name.replace('"', "%22")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+fn parse_part(raw: &[u8]) -> Result<Part, MultipartError> {
+    let (head, body) = match find(raw, b"\r\n\r\n") {
+        Some(end) => (&raw[..end], &raw[end + 4..]),
+        None if raw.starts_with(b"\r\n") => (&raw[..0], &raw[2..]),
+        None => return Err(MultipartError::MalformedHeaders),
+    };
+    let head = std::str::from_utf8(head).map_err(|_| MultipartError::MalformedHeaders)?;
+    let mut part = Part {
+        body: body.to_vec(),
+        ..Part::default()
+    };
+    let mut named = false;
+    for line in head.split("\r\n").filter(|line| !line.is_empty()) {
+        let (name, value) = line
+            .split_once(':')
+            .ok_or(MultipartError::MalformedHeaders)?;
+        let (name, value) = (name.trim(), value.trim());
+        if name.eq_ignore_ascii_case("content-disposition") {
+            for (key, value) in disposition_parameters(value) {
+                match key.to_ascii_lowercase().as_str() {
+                    "name" => {
+                        part.name = value;
+                        named = true;
+                    }
+                    "filename" => part.filename = Some(value),
+                    _ => {}
+                }
+            }
+        } else if name.eq_ignore_ascii_case("content-type") {
+            part.content_type = Some(value.to_owned());
+        } else {
+            part.headers.push((name.to_owned(), value.to_owned()));
+        }
+    }
+    if named {
+        Ok(part)
+    } else {
+        Err(MultipartError::MissingName)
+    }
+}
+
+/// The parameters of a `Content-Disposition` value such as
+/// `form-data; name="a"`, unquoted.
+fn disposition_parameters(value: &str) -> Vec<(String, String)> {
+    let mut parameters = Vec::new();
+    let mut rest = value.split_once(';').map_or("", |(_, rest)| rest);
+    loop {
+        rest = rest.trim_start_matches([' ', '\t', ';']);
+        let (key, after) = match rest.split_once('=') {
+            Some(split) => split,
+            None => break,
+        };
+        let key = key.trim().to_owned();
+        let after = after.trim_start();
+        if let Some(quoted) = after.strip_prefix('"') {
+            let mut value = String::new();
+            let mut chars = quoted.char_indices();
+            let mut end = quoted.len();
+            while let Some((i, c)) = chars.next() {
+                match c {
+                    '\\' => value.extend(chars.next().map(|(_, c)| c)),
+                    '"' => {
+                        end = i + 1;
+                        break;
+                    }
+                    c => value.push(c),
+                }
+            }
+            parameters.push((key, value));
+            rest = &quoted[end..];
+        } else {
+            let end = after.find(';').unwrap_or(after.len());
+            parameters.push((key, after[..end].trim().to_owned()));
+            rest = &after[end..];
+        }
+    }
+    parameters
+}
+
+fn property_schema<'a>(
+    components: &'a Components,
+    object: Option<&'a Schema>,
+    name: &str,
+) -> Option<&'a Schema> {
+    let properties = match &object?.schema_kind {
+        SchemaKind::Type(Type::Object(object)) => &object.properties,
+        SchemaKind::Any(any) => &any.properties,
+        _ => return None,
+    };
+    components.resolve_boxed(properties.get(name)?).ok()
+}
+
+fn array_items<'a>(
+    components: &'a Components,
+    schema: Option<&'a Schema>,
+) -> Option<Option<&'a Schema>> {
+    match &schema?.schema_kind {
+        SchemaKind::Type(Type::Array(array)) => Some(components.resolve_boxed(&array.items).ok()),
+        _ => None,
+    }
+}
+
+impl MediaType {
+    /// Builds a `multipart/form-data` body of the properties of `value`,
+    /// followed by `files`.
+    ///
+    /// Each property is encoded as its [encoding](MediaType::property_encodings)
+    /// gives: as JSON for JSON content types, else as text, with arrays
+    /// of other content types sent as one part for each item. Files without
+    /// a content type of their own are given that of their property. The
+    /// boundary is chosen so as not to appear in any part.
+    ///
+    /// ```
+    /// use openapiv3::{Components, MediaType, Multipart, Part};
+    /// use serde_json::json;
+    ///
+    /// let media_type: MediaType = serde_yaml::from_str(
+    ///     "
+    /// schema:
+    ///   type: object
+    ///   properties:
+    ///     id: {type: integer}
+    ///     tags: {type: array, items: {type: string}}
+    ///     photo: {type: string, format: binary}
+    /// ",
+    /// )
+    /// .unwrap();
+    /// let components = Components::default();
+    /// let photo = Part {
+    ///     name: "photo".to_owned(),
+    ///     filename: Some("cat.png".to_owned()),
+    ///     body: vec![0x89, 0x50],
+    ///     ..Part::default()
+    /// };
+    /// let multipart =
+    ///     media_type.encode_multipart(&components, &json!({"id": 3, "tags": ["a", "b"]}), vec![photo]);
+    /// assert_eq!(multipart.parts.len(), 4);
+    /// assert_eq!(
+    ///     multipart.parts[3].content_type.as_deref(),
+    ///     Some("application/octet-stream")
+    /// );
+    ///
+    /// let body = multipart.to_bytes();
+    /// let parsed = Multipart::parse(&multipart.content_type(), &body).unwrap();
+    /// assert_eq!(
+    ///     media_type.decode_multipart(&components, &parsed),
+    ///     json!({"id": 3, "tags": ["a", "b"], "photo": "\u{fffd}P"})
+    /// );
+    /// ```
+    pub fn encode_multipart(
+        &self,
+        components: &Components,
+        value: &Value,
+        files: Vec<Part>,
+    ) -> Multipart {
+        let encodings = self
+            .property_encodings("multipart/form-data", components)
+            .unwrap_or_default();
+        let content_type = |name: &str| {
+            encodings
+                .get(name)
+                .map_or("text/plain", |encoding| encoding.content_type.as_str())
+                .to_owned()
+        };
+        let mut parts = Vec::new();
+        for (name, value) in value.as_object().into_iter().flatten() {
+            let content_type = content_type(name);
+            let json = MediaTypeRange::from(content_type.as_str()).is_json();
+            let values = match value {
+                Value::Array(items) if !json => items.iter().collect(),
+                value => vec![value],
+            };
+            for value in values {
+                let body = match value {
+                    Value::String(text) if !json => text.clone().into_bytes(),
+                    value => value.to_string().into_bytes(),
+                };
+                parts.push(Part {
+                    name: name.clone(),
+                    content_type: Some(content_type.clone()).filter(|t| t != "text/plain"),
+                    body,
+                    ..Part::default()
+                });
+            }
+        }
+        for mut file in files {
+            if file.content_type.is_none() {
+                file.content_type = Some(content_type(&file.name));
+            }
+            parts.push(file);
+        }
+
+        let mut boundary = "openapiv3-boundary".to_owned();
+        let mut suffix = 1;
+        while parts
+            .iter()
+            .any(|part| find(&part.body, boundary.as_bytes()).is_some())
+        {
+            boundary = format!("openapiv3-boundary-{}", suffix);
+            suffix += 1;
+        }
+        Multipart { boundary, parts }
+    }
+
+    /// Reads the parts of a multipart body as an object, for validation
+    /// against the schema.
+    ///
+    /// Parts of JSON content types are parsed as JSON, and others read as
+    /// text, lossily where not UTF-8, then as the type their property
+    /// schema gives, as parameters are. The parts of an array property, or
+    /// of a property given more than once, are collected into an array.
+    pub fn decode_multipart(&self, components: &Components, multipart: &Multipart) -> Value {
+        let schema = self
+            .schema
+            .as_ref()
+            .and_then(|schema| components.resolve(schema).ok());
+        decode_parts(components, schema, &multipart.parts)
+    }
+}
+
+/// Reads parts as an object of the schema; see
+/// [`MediaType::decode_multipart`].
+pub(crate) fn decode_parts(
+    components: &Components,
+    schema: Option<&Schema>,
+    parts: &[Part],
+) -> Value {
+    let object = schema.and_then(|schema| schema.flatten_all_of(components).ok());
+    let mut values: BTreeMap<&str, Vec<Value>> = BTreeMap::new();
+    let mut order = Vec::new();
+    for part in parts {
+        let property = property_schema(components, object.as_ref(), &part.name);
+        let items = array_items(components, property);
+        let text = String::from_utf8_lossy(&part.body);
+        let json = part
+            .content_type
+            .as_deref()
+            .is_some_and(|t| MediaTypeRange::from(t).is_json());
+        let value = match (json, items.flatten().or(property)) {
+            (true, _) => serde_json::from_str(&text).unwrap_or_else(|_| Value::from(text)),
+            (false, Some(schema)) => coerce(components, schema, &text),
+            (false, None) => Value::from(text),
+        };
+        if !values.contains_key(part.name.as_str()) {
+            order.push(part.name.as_str());
+        }
+        values.entry(&part.name).or_default().push(value);
+    }
+    let object = order
+        .into_iter()
+        .map(|name| {
+            let property = property_schema(components, object.as_ref(), name);
+            let mut values = values.remove(name).unwrap_or_default();
+            let array = array_items(components, property).is_some();
+            let value = match (array, values.len()) {
+                (true, 1) if values[0].is_array() => values.remove(0),
+                (false, 1) => values.remove(0),
+                _ => Value::Array(values),
+            };
+            (name.to_owned(), value)
+        })
+        .collect();
+    Value::Object(object)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use serde_json::json;
+
+    #[test]
+    fn encodes_and_decodes_multipart() {
+        let media_type: MediaType = serde_yaml::from_str(
+            "
+schema:
+  type: object
+  properties:
+    meta: {type: object, properties: {size: {type: integer}}}
+    flags: {type: array, items: {type: boolean}}
+    note: {type: string}
+encoding:
+  flags: {contentType: text/plain}
+",
+        )
+        .unwrap();
+        let components = Components::default();
+        let value =
+            json!({"meta": {"size": 2}, "flags": [true, false], "note": "--openapiv3-boundary"});
+        let multipart = media_type.encode_multipart(&components, &value, Vec::new());
+        assert_eq!(multipart.boundary, "openapiv3-boundary-1");
+        let parsed = Multipart::parse(&multipart.content_type(), &multipart.to_bytes()).unwrap();
+        assert_eq!(parsed, multipart);
+        assert_eq!(media_type.decode_multipart(&components, &parsed), value);
+
+        let body = "preamble\r\n--b\r\n\
+            Content-Disposition: form-data; name=\"a\\\"b\"; filename=x.txt\r\n\
+            X-Trace: 1\r\n\r\nline\r\n\r\n--b\r\n\
+            Content-Disposition: form-data; name=a\"b\r\n\r\n\r\n--b--\r\nepilogue";
+        let parsed =
+            Multipart::parse("multipart/form-data; boundary=\"b\"", body.as_bytes()).unwrap();
+        assert_eq!(parsed.parts.len(), 2);
+        assert_eq!(parsed.parts[0].name, "a\"b");
+        assert_eq!(parsed.parts[0].filename.as_deref(), Some("x.txt"));
+        assert_eq!(
+            parsed.parts[0].headers,
+            [("X-Trace".to_owned(), "1".to_owned())]
+        );
+        assert_eq!(parsed.parts[0].body, b"line\r\n");
+        assert_eq!(parsed.parts[1].body, b"");
+        assert_eq!(
+            media_type.decode_multipart(&components, &parsed),
+            json!({"a\"b": ["line\r\n", ""]})
+        );
+
+        let errors: Vec<MultipartError> = [
+            ("multipart/form-data", "--b--"),
+            ("multipart/form-data; boundary=b", "--c--"),
+            (
+                "multipart/form-data; boundary=b",
+                "--b\r\nbroken\r\n\r\n\r\n--b--",
+            ),
+            (
+                "multipart/form-data; boundary=b",
+                "--b\r\nX: 1\r\n\r\n\r\n--b--",
+            ),
+        ]
+        .iter()
+        .map(|(content_type, body)| Multipart::parse(content_type, body.as_bytes()).unwrap_err())
+        .collect();
+        assert_eq!(
+            errors,
+            [
+                MultipartError::MissingBoundary,
+                MultipartError::MissingDelimiter,
+                MultipartError::MalformedHeaders,
+                MultipartError::MissingName,
+            ]
+        );
+    }
+}
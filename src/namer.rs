@@ -0,0 +1,278 @@
+use crate::reference::unescape_pointer_segment;
+use crate::*;
+use std::collections::HashSet;
+
+/// Names schemas declared inline after where they are declared, such as
+/// `ListPetsResponse200Item` for the items of the response of the
+/// `listPets` operation with status 200, never giving the same name twice.
+///
+/// A name is made of the operation, by its `operationId` or else its
+/// method and path, or of the component the schema is declared in,
+/// followed by `Request` for a request body, `Response` and the status
+/// for a response, the name of a parameter, header or property, `Item`
+/// for the items of an array, `Value` for `additionalProperties` and
+/// `Variant` followed by the position of a branch of `oneOf`, `anyOf` or
+/// `allOf`. Names are made unique with a numeric suffix.
+///
+/// ```
+/// use openapiv3::{OpenAPI, SchemaNamer};
+///
+/// let api: OpenAPI = serde_yaml::from_str(
+///     "
+/// openapi: 3.0.0
+/// info: {title: t, version: '1'}
+/// paths:
+///   /pets/{id}:
+///     get:
+///       responses:
+///         '200':
+///           description: a pet
+///           content:
+///             application/json:
+///               schema: {type: object, properties: {tags: {type: array, items: {type: object}}}}
+/// ",
+/// )
+/// .unwrap();
+/// let location = "/paths/~1pets~1{id}/get/responses/200/content/application~1json/schema";
+/// let mut namer = SchemaNamer::new().status_codes(false);
+/// assert_eq!(namer.name(&api, location), "GetPetsIdResponse");
+/// assert_eq!(
+///     namer.name(&api, &format!("{}/properties/tags/items", location)),
+///     "GetPetsIdResponseTagsItem"
+/// );
+/// assert_eq!(namer.name(&api, location), "GetPetsIdResponse_2");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaNamer {
+    request_suffix: String,
+    response_suffix: String,
+    status_codes: bool,
+    taken: HashSet<String>,
+}
+
+impl Default for SchemaNamer {
+    fn default() -> Self {
+        SchemaNamer::new()
+    }
+}
+
+impl SchemaNamer {
+    /// A namer that has given no names, naming request bodies `Request`
+    /// and responses `Response` with their status.
+    pub fn new() -> Self {
+        SchemaNamer {
+            request_suffix: "Request".to_owned(),
+            response_suffix: "Response".to_owned(),
+            status_codes: true,
+            taken: HashSet::new(),
+        }
+    }
+
+    /// What to name request bodies after their operation.
+    pub fn request_suffix(mut self, suffix: impl Into<String>) -> Self {
+        self.request_suffix = suffix.into();
+        self
+    }
+
+    /// What to name responses after their operation.
+    pub fn response_suffix(mut self, suffix: impl Into<String>) -> Self {
+        self.response_suffix = suffix.into();
+        self
+    }
+
+    /// Whether to name responses after their status too.
+    pub fn status_codes(mut self, status_codes: bool) -> Self {
+        self.status_codes = status_codes;
+        self
+    }
+
+    /// Keeps `name` from being given, as it is taken by something else.
+    pub fn reserve(&mut self, name: impl Into<String>) {
+        self.taken.insert(name.into());
+    }
+
+    /// Names the schema at `location`, a JSON Pointer within `api` such as
+    /// those [`OpenAPI::all_schemas`] gives.
+    pub fn name(&mut self, api: &OpenAPI, location: &str) -> String {
+        let mut base: String = self
+            .words(api, location)
+            .iter()
+            .map(|w| pascal(w))
+            .collect();
+        if base.is_empty() {
+            base = "Schema".to_owned();
+        }
+
+        let mut name = base.clone();
+        let mut suffix = 2;
+        while !self.taken.insert(name.clone()) {
+            name = format!("{}_{}", base, suffix);
+            suffix += 1;
+        }
+        name
+    }
+
+    /// The words describing where `location` is.
+    fn words(&self, api: &OpenAPI, location: &str) -> Vec<String> {
+        let raw: Vec<&str> = location.split('/').collect();
+        let segments: Vec<String> = raw.iter().map(|s| unescape_pointer_segment(s)).collect();
+        let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+        // The pointer to what the segments up to `end` lead to.
+        let lookup = |end: usize| api.lookup(&raw[..end].join("/"));
+
+        let mut words = Vec::new();
+        let mut rest = segments.get(1..).unwrap_or_default();
+        loop {
+            let at = segments.len() - rest.len();
+            rest = match rest {
+                ["paths", path, method, tail @ ..] if method.parse::<Method>().is_ok() => {
+                    words.extend(operation_words(lookup(at + 3), method, path));
+                    tail
+                }
+                [method, tail @ ..] if method.parse::<Method>().is_ok() => {
+                    words.extend(operation_words(lookup(at + 1), method, ""));
+                    tail
+                }
+                ["paths", path, tail @ ..] => {
+                    words.push(path.to_string());
+                    tail
+                }
+                ["components", _, name, tail @ ..] | ["callbacks", name, _, tail @ ..] => {
+                    words.push(name.to_string());
+                    tail
+                }
+                ["requestBody", tail @ ..] => {
+                    words.push(self.request_suffix.clone());
+                    tail
+                }
+                ["responses", status, tail @ ..] => {
+                    words.push(self.response_suffix.clone());
+                    if self.status_codes {
+                        words.push(status.to_string());
+                    }
+                    tail
+                }
+                ["parameters", _, tail @ ..] => {
+                    if let Some(AnyObject::Parameter(parameter)) = lookup(at + 2) {
+                        words.push(parameter.parameter_data_ref().name.clone());
+                    }
+                    tail
+                }
+                ["headers", name, tail @ ..] | ["properties", name, tail @ ..] => {
+                    words.push(name.to_string());
+                    tail
+                }
+                ["items", tail @ ..] => {
+                    words.push("Item".to_owned());
+                    tail
+                }
+                ["additionalProperties", tail @ ..] => {
+                    words.push("Value".to_owned());
+                    tail
+                }
+                ["oneOf" | "anyOf" | "allOf", index, tail @ ..] => {
+                    let position = index.parse::<usize>().map_or(0, |i| i + 1);
+                    words.push(format!("Variant{}", position));
+                    tail
+                }
+                ["content", _, tail @ ..] | [_, tail @ ..] => tail,
+                [] => break,
+            };
+        }
+        words
+    }
+}
+
+/// The words naming an operation: its `operationId`, or its method and
+/// path.
+fn operation_words(operation: Option<AnyObject<'_>>, method: &str, path: &str) -> Vec<String> {
+    match operation {
+        Some(AnyObject::Operation(Operation {
+            operation_id: Some(id),
+            ..
+        })) => vec![id.clone()],
+        _ => vec![method.to_owned(), path.to_owned()],
+    }
+}
+
+/// `words` in PascalCase, without characters other than ASCII letters and
+/// digits, which component names may not all contain.
+fn pascal(words: &str) -> String {
+    words
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .flat_map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase())
+                .into_iter()
+                .chain(chars)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn names_schemas() {
+        let api: OpenAPI = serde_yaml::from_str(
+            "
+openapi: 3.0.0
+info: {title: t, version: '1'}
+paths:
+  /pets:
+    parameters:
+      - {name: page-size, in: query, schema: {type: integer}}
+    post:
+      operationId: createPet
+      parameters:
+        - {name: kind, in: query, schema: {type: string}}
+      requestBody:
+        content:
+          application/json:
+            schema:
+              oneOf: [{type: object}, {type: object, additionalProperties: {type: object}}]
+      responses:
+        default:
+          description: error
+          headers:
+            rate-limit: {schema: {type: integer}}
+components:
+  schemas:
+    Pet:
+      type: object
+      properties:
+        owner: {type: object}
+",
+        )
+        .unwrap();
+        let mut namer = SchemaNamer::new().request_suffix("Body");
+        namer.reserve("CreatePetBody");
+        let names: Vec<String> = [
+            "/paths/~1pets/parameters/0/schema",
+            "/paths/~1pets/post/parameters/0/schema",
+            "/paths/~1pets/post/requestBody/content/application~1json/schema",
+            "/paths/~1pets/post/requestBody/content/application~1json/schema/oneOf/1/additionalProperties",
+            "/paths/~1pets/post/responses/default/headers/rate-limit/schema",
+            "/components/schemas/Pet/properties/owner",
+            "",
+        ]
+        .iter()
+        .map(|location| namer.name(&api, location))
+        .collect();
+        assert_eq!(
+            names,
+            [
+                "PetsPageSize",
+                "CreatePetKind",
+                "CreatePetBody_2",
+                "CreatePetBodyVariant2Value",
+                "CreatePetResponseDefaultRateLimit",
+                "PetOwner",
+                "Schema",
+            ]
+        );
+    }
+}
@@ -0,0 +1,290 @@
+use crate::*;
+use std::error::Error;
+use std::fmt;
+
+/// A bound on numbers, which the bound itself meets unless it is
+/// exclusive.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bound {
+    pub value: f64,
+    pub exclusive: bool,
+}
+
+impl Bound {
+    pub fn inclusive(value: f64) -> Self {
+        Bound {
+            value,
+            exclusive: false,
+        }
+    }
+
+    pub fn exclusive(value: f64) -> Self {
+        Bound {
+            value,
+            exclusive: true,
+        }
+    }
+}
+
+/// The constraints of a schema on numbers, whichever version of the
+/// specification it is written in: 3.0 marks a `minimum` or `maximum` as
+/// exclusive with a boolean, while 3.1 gives `exclusiveMinimum` and
+/// `exclusiveMaximum` bounds of their own.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NumericConstraints {
+    pub multiple_of: Option<f64>,
+    pub minimum: Option<Bound>,
+    pub maximum: Option<Bound>,
+    /// Whether only integers are allowed, as by `type: integer`.
+    pub integer: bool,
+}
+
+/// Why [`NumericConstraints`] contradict themselves.
+#[derive(Debug, Clone, PartialEq)]
+pub enum NumericConstraintError {
+    /// `multipleOf` is not greater than 0.
+    MultipleOf(f64),
+    /// No number lies between the bounds.
+    EmptyRange { minimum: Bound, maximum: Bound },
+    /// No number between the bounds is a multiple of `multipleOf`, or an
+    /// integer where integers are required.
+    NoMultiple {
+        multiple_of: f64,
+        minimum: Bound,
+        maximum: Bound,
+    },
+}
+
+fn write_range(f: &mut fmt::Formatter<'_>, minimum: &Bound, maximum: &Bound) -> fmt::Result {
+    write!(
+        f,
+        "{} {} and {} {}",
+        if minimum.exclusive {
+            "greater than"
+        } else {
+            "at least"
+        },
+        minimum.value,
+        if maximum.exclusive {
+            "less than"
+        } else {
+            "at most"
+        },
+        maximum.value
+    )
+}
+
+impl fmt::Display for NumericConstraintError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NumericConstraintError::MultipleOf(multiple_of) => {
+                write!(f, "multipleOf must be greater than 0, not {}", multiple_of)
+            }
+            NumericConstraintError::EmptyRange { minimum, maximum } => {
+                f.write_str("no number is ")?;
+                write_range(f, minimum, maximum)
+            }
+            NumericConstraintError::NoMultiple {
+                multiple_of,
+                minimum,
+                maximum,
+            } => {
+                write!(f, "no multiple of {} is ", multiple_of)?;
+                write_range(f, minimum, maximum)
+            }
+        }
+    }
+}
+
+impl Error for NumericConstraintError {}
+
+impl NumericConstraints {
+    /// Whether `n` meets the constraints.
+    pub fn contains(&self, n: f64) -> bool {
+        let above = |bound: &Bound| n > bound.value || (!bound.exclusive && n == bound.value);
+        let below = |bound: &Bound| n < bound.value || (!bound.exclusive && n == bound.value);
+        self.minimum.as_ref().is_none_or(above)
+            && self.maximum.as_ref().is_none_or(below)
+            && self.multiple_of.is_none_or(|m| is_multiple(n, m))
+            && (!self.integer || n.fract() == 0.0)
+    }
+
+    /// Checks that the constraints admit some number: that `multipleOf` is
+    /// positive, that the minimum is below the maximum, and that a multiple
+    /// of `multipleOf` lies between them.
+    pub fn check(&self) -> Result<(), NumericConstraintError> {
+        if let Some(multiple_of) = self.multiple_of.filter(|&m| m <= 0.0 || m.is_nan()) {
+            return Err(NumericConstraintError::MultipleOf(multiple_of));
+        }
+        let (minimum, maximum) = match (self.minimum, self.maximum) {
+            (Some(minimum), Some(maximum)) => (minimum, maximum),
+            _ => return Ok(()),
+        };
+        if minimum.value > maximum.value
+            || (minimum.value == maximum.value && (minimum.exclusive || maximum.exclusive))
+        {
+            return Err(NumericConstraintError::EmptyRange { minimum, maximum });
+        }
+        let step = match (self.multiple_of, self.integer) {
+            (Some(multiple_of), _) => multiple_of,
+            (None, true) => 1.0,
+            (None, false) => return Ok(()),
+        };
+        // The least multiple from the minimum on, and the next should that
+        // be excluded or not an integer where integers are required.
+        let first = (minimum.value / step).ceil();
+        let found = (0..3)
+            .map(|i| (first + i as f64) * step)
+            .take_while(|&n| n <= maximum.value)
+            .any(|n| self.contains(n));
+        if found {
+            Ok(())
+        } else {
+            Err(NumericConstraintError::NoMultiple {
+                multiple_of: step,
+                minimum,
+                maximum,
+            })
+        }
+    }
+}
+
+fn is_multiple(n: f64, of: f64) -> bool {
+    let quotient = n / of;
+    of <= 0.0 || (quotient - quotient.round()).abs() <= 1e-9
+}
+
+fn bound(value: Option<f64>, exclusive: bool) -> Option<Bound> {
+    value.map(|value| Bound { value, exclusive })
+}
+
+impl Schema {
+    /// The constraints of the schema on numbers, or `None` unless it is of
+    /// `type: number` or `integer`, or is untyped with such constraints.
+    ///
+    /// ```
+    /// use openapiv3::{Bound, Schema};
+    ///
+    /// let schema: Schema =
+    ///     serde_yaml::from_str("{type: integer, minimum: 5, maximum: 3}").unwrap();
+    /// let constraints = schema.numeric_constraints().unwrap();
+    /// assert_eq!(constraints.minimum, Some(Bound::inclusive(5.0)));
+    /// assert_eq!(
+    ///     constraints.check().unwrap_err().to_string(),
+    ///     "no number is at least 5 and at most 3"
+    /// );
+    /// ```
+    pub fn numeric_constraints(&self) -> Option<NumericConstraints> {
+        match &self.schema_kind {
+            SchemaKind::Type(Type::Number(number)) => Some(NumericConstraints {
+                multiple_of: number.multiple_of,
+                minimum: bound(number.minimum, number.exclusive_minimum),
+                maximum: bound(number.maximum, number.exclusive_maximum),
+                integer: false,
+            }),
+            SchemaKind::Type(Type::Integer(integer)) => Some(NumericConstraints {
+                multiple_of: integer.multiple_of.map(|m| m as f64),
+                minimum: bound(integer.minimum.map(|n| n as f64), integer.exclusive_minimum),
+                maximum: bound(integer.maximum.map(|n| n as f64), integer.exclusive_maximum),
+                integer: true,
+            }),
+            SchemaKind::Any(any)
+                if any.multiple_of.is_some() || any.minimum.is_some() || any.maximum.is_some() =>
+            {
+                Some(NumericConstraints {
+                    multiple_of: any.multiple_of,
+                    minimum: bound(any.minimum, any.exclusive_minimum.unwrap_or(false)),
+                    maximum: bound(any.maximum, any.exclusive_maximum.unwrap_or(false)),
+                    integer: false,
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+impl v3_1::SchemaObject {
+    /// The constraints of the schema on numbers, taking the tighter of
+    /// `minimum` and `exclusiveMinimum`, and of `maximum` and
+    /// `exclusiveMaximum`, where both are given.
+    pub fn numeric_constraints(&self) -> NumericConstraints {
+        let integer = self.schema_type.as_ref().is_some_and(|types| {
+            types.contains(v3_1::SchemaType::Integer) && !types.contains(v3_1::SchemaType::Number)
+        });
+        let value =
+            |n: &Option<serde_json::Number>| n.as_ref().and_then(serde_json::Number::as_f64);
+        NumericConstraints {
+            multiple_of: value(&self.multiple_of),
+            minimum: match (value(&self.minimum), value(&self.exclusive_minimum)) {
+                (Some(minimum), Some(exclusive)) if minimum > exclusive => {
+                    Some(Bound::inclusive(minimum))
+                }
+                (_, Some(exclusive)) => Some(Bound::exclusive(exclusive)),
+                (minimum, None) => minimum.map(Bound::inclusive),
+            },
+            maximum: match (value(&self.maximum), value(&self.exclusive_maximum)) {
+                (Some(maximum), Some(exclusive)) if maximum < exclusive => {
+                    Some(Bound::inclusive(maximum))
+                }
+                (_, Some(exclusive)) => Some(Bound::exclusive(exclusive)),
+                (maximum, None) => maximum.map(Bound::inclusive),
+            },
+            integer,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn resolves_constraints() {
+        let v3_0: Schema = serde_yaml::from_str(
+            "{type: number, minimum: 1, exclusiveMinimum: true, maximum: 10, multipleOf: 2.5}",
+        )
+        .unwrap();
+        let v3_1: v3_1::SchemaObject = serde_yaml::from_str(
+            "{type: number, minimum: 0, exclusiveMinimum: 1, maximum: 10, multipleOf: 2.5}",
+        )
+        .unwrap();
+        let constraints = v3_0.numeric_constraints().unwrap();
+        assert_eq!(constraints, v3_1.numeric_constraints());
+        assert_eq!(constraints.minimum, Some(Bound::exclusive(1.0)));
+        assert_eq!(constraints.check(), Ok(()));
+        assert!(constraints.contains(7.5) && !constraints.contains(1.0));
+
+        let errors: Vec<String> = [
+            "{type: integer, minimum: 3, maximum: 3, exclusiveMaximum: true}",
+            "{type: integer, minimum: 3, maximum: 4, multipleOf: 5}",
+            "{type: number, minimum: 1.2, maximum: 1.8, multipleOf: 1}",
+            "{minimum: 0, multipleOf: -1}",
+        ]
+        .iter()
+        .map(|source| {
+            let schema: Schema = serde_yaml::from_str(source).unwrap();
+            schema
+                .numeric_constraints()
+                .unwrap()
+                .check()
+                .unwrap_err()
+                .to_string()
+        })
+        .collect();
+        assert_eq!(
+            errors,
+            [
+                "no number is at least 3 and less than 3",
+                "no multiple of 5 is at least 3 and at most 4",
+                "no multiple of 1 is at least 1.2 and at most 1.8",
+                "multipleOf must be greater than 0, not -1",
+            ]
+        );
+
+        let integer: v3_1::SchemaObject =
+            serde_yaml::from_str("{type: integer, exclusiveMinimum: 1, maximum: 1.5}").unwrap();
+        assert!(integer.numeric_constraints().check().is_err());
+        let string: Schema = serde_yaml::from_str("{type: string}").unwrap();
+        assert_eq!(string.numeric_constraints(), None);
+    }
+}
@@ -0,0 +1,374 @@
+use crate::*;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+/// A Rust type whose values have a schema, as serialized by serde.
+///
+/// With the `derive` feature, `#[derive(OpenApiSchema)]` implements it for
+/// structs and enums: a type becomes a schema of the components, named
+/// after it or its `#[serde(rename)]`, described by its doc comment. Field
+/// and variant names follow `#[serde(rename)]` and `#[serde(rename_all)]`,
+/// and enums are represented as `#[serde(tag)]`, `#[serde(content)]` and
+/// `#[serde(untagged)]` have them. Fields with `#[serde(skip)]` are left
+/// out, and those that are `Option`s or have `#[serde(default)]` or
+/// `#[serde(skip_serializing_if)]` are not required. Generic types,
+/// `#[serde(flatten)]` and tuple variants of more than one field are not
+/// supported.
+///
+/// ```
+/// # #[cfg(feature = "derive")] {
+/// use openapiv3::{Components, OpenApiSchema};
+///
+/// /// A pet.
+/// #[derive(OpenApiSchema)]
+/// #[serde(rename_all = "camelCase")]
+/// struct Pet {
+///     pet_name: String,
+///     owner: Option<Box<Pet>>,
+/// }
+///
+/// let mut components = Components::default();
+/// let pet = components.register::<Pet>();
+/// assert_eq!(pet, openapiv3::ReferenceOr::ref_("#/components/schemas/Pet"));
+/// let schema = components.resolve(&pet).unwrap();
+/// assert_eq!(schema.schema_data.description.as_deref(), Some("A pet."));
+/// # }
+/// ```
+pub trait OpenApiSchema {
+    /// The schema of the type, adding those of the types it is made of to
+    /// `components` where they are components themselves.
+    fn schema_in(components: &mut Components) -> ReferenceOr<Schema>;
+
+    /// Whether a field of the type need not be present, as is the case for
+    /// `Option`s.
+    fn optional() -> bool {
+        false
+    }
+}
+
+impl Components {
+    /// Adds the schema of a type, and those of the types it is made of, to
+    /// the schemas of the components where they are components, returning
+    /// the schema to use for the type.
+    pub fn register<T: OpenApiSchema + ?Sized>(&mut self) -> ReferenceOr<Schema> {
+        T::schema_in(self)
+    }
+}
+
+fn typed(kind: Type) -> ReferenceOr<Schema> {
+    ReferenceOr::Item(Schema {
+        schema_data: SchemaData::default(),
+        schema_kind: SchemaKind::Type(kind),
+    })
+}
+
+macro_rules! integer_schema {
+    ($($ty:ty => $format:expr, $minimum:expr;)*) => {
+        $(impl OpenApiSchema for $ty {
+            fn schema_in(_: &mut Components) -> ReferenceOr<Schema> {
+                typed(Type::Integer(IntegerType {
+                    format: $format,
+                    minimum: $minimum,
+                    ..IntegerType::default()
+                }))
+            }
+        })*
+    };
+}
+
+integer_schema! {
+    i8 => VariantOrUnknownOrEmpty::Empty, None;
+    i16 => VariantOrUnknownOrEmpty::Empty, None;
+    i32 => VariantOrUnknownOrEmpty::Item(IntegerFormat::Int32), None;
+    i64 => VariantOrUnknownOrEmpty::Item(IntegerFormat::Int64), None;
+    isize => VariantOrUnknownOrEmpty::Item(IntegerFormat::Int64), None;
+    u8 => VariantOrUnknownOrEmpty::Empty, Some(0);
+    u16 => VariantOrUnknownOrEmpty::Empty, Some(0);
+    u32 => VariantOrUnknownOrEmpty::Item(IntegerFormat::Int32), Some(0);
+    u64 => VariantOrUnknownOrEmpty::Item(IntegerFormat::Int64), Some(0);
+    usize => VariantOrUnknownOrEmpty::Item(IntegerFormat::Int64), Some(0);
+}
+
+impl OpenApiSchema for f32 {
+    fn schema_in(_: &mut Components) -> ReferenceOr<Schema> {
+        typed(Type::Number(NumberType {
+            format: VariantOrUnknownOrEmpty::Item(NumberFormat::Float),
+            ..NumberType::default()
+        }))
+    }
+}
+
+impl OpenApiSchema for f64 {
+    fn schema_in(_: &mut Components) -> ReferenceOr<Schema> {
+        typed(Type::Number(NumberType {
+            format: VariantOrUnknownOrEmpty::Item(NumberFormat::Double),
+            ..NumberType::default()
+        }))
+    }
+}
+
+impl OpenApiSchema for bool {
+    fn schema_in(_: &mut Components) -> ReferenceOr<Schema> {
+        typed(Type::Boolean {})
+    }
+}
+
+impl OpenApiSchema for String {
+    fn schema_in(_: &mut Components) -> ReferenceOr<Schema> {
+        typed(Type::String(StringType::default()))
+    }
+}
+
+impl OpenApiSchema for str {
+    fn schema_in(components: &mut Components) -> ReferenceOr<Schema> {
+        String::schema_in(components)
+    }
+}
+
+impl OpenApiSchema for char {
+    fn schema_in(_: &mut Components) -> ReferenceOr<Schema> {
+        typed(Type::String(StringType {
+            min_length: Some(1),
+            max_length: Some(1),
+            ..StringType::default()
+        }))
+    }
+}
+
+impl OpenApiSchema for serde_json::Value {
+    fn schema_in(_: &mut Components) -> ReferenceOr<Schema> {
+        ReferenceOr::Item(Schema::default())
+    }
+}
+
+impl<T: OpenApiSchema> OpenApiSchema for Option<T> {
+    fn schema_in(components: &mut Components) -> ReferenceOr<Schema> {
+        let mut schema = __private::schema_of(T::schema_in(components));
+        schema.schema_data.nullable = true;
+        ReferenceOr::Item(schema)
+    }
+
+    fn optional() -> bool {
+        true
+    }
+}
+
+macro_rules! forward_schema {
+    ($($ty:ty),*) => {
+        $(impl<T: OpenApiSchema + ?Sized> OpenApiSchema for $ty {
+            fn schema_in(components: &mut Components) -> ReferenceOr<Schema> {
+                T::schema_in(components)
+            }
+
+            fn optional() -> bool {
+                T::optional()
+            }
+        })*
+    };
+}
+
+forward_schema!(Box<T>, std::rc::Rc<T>, std::sync::Arc<T>, &T);
+
+fn array<T: OpenApiSchema>(components: &mut Components, unique_items: bool) -> ReferenceOr<Schema> {
+    typed(Type::Array(ArrayType {
+        items: T::schema_in(components).map(Box::new),
+        unique_items,
+        ..ArrayType::default()
+    }))
+}
+
+impl<T: OpenApiSchema> OpenApiSchema for Vec<T> {
+    fn schema_in(components: &mut Components) -> ReferenceOr<Schema> {
+        array::<T>(components, false)
+    }
+}
+
+impl<T: OpenApiSchema> OpenApiSchema for [T] {
+    fn schema_in(components: &mut Components) -> ReferenceOr<Schema> {
+        array::<T>(components, false)
+    }
+}
+
+impl<T: OpenApiSchema, S> OpenApiSchema for HashSet<T, S> {
+    fn schema_in(components: &mut Components) -> ReferenceOr<Schema> {
+        array::<T>(components, true)
+    }
+}
+
+impl<T: OpenApiSchema> OpenApiSchema for BTreeSet<T> {
+    fn schema_in(components: &mut Components) -> ReferenceOr<Schema> {
+        array::<T>(components, true)
+    }
+}
+
+fn map<T: OpenApiSchema>(components: &mut Components) -> ReferenceOr<Schema> {
+    let values = T::schema_in(components);
+    typed(Type::Object(ObjectType {
+        additional_properties: Some(AdditionalProperties::Schema(Box::new(values))),
+        ..ObjectType::default()
+    }))
+}
+
+impl<K, T: OpenApiSchema, S> OpenApiSchema for HashMap<K, T, S> {
+    fn schema_in(components: &mut Components) -> ReferenceOr<Schema> {
+        map::<T>(components)
+    }
+}
+
+impl<K, T: OpenApiSchema> OpenApiSchema for BTreeMap<K, T> {
+    fn schema_in(components: &mut Components) -> ReferenceOr<Schema> {
+        map::<T>(components)
+    }
+}
+
+/// What the code of `#[derive(OpenApiSchema)]` is made of. Not part of the
+/// API.
+#[doc(hidden)]
+pub mod __private {
+    use crate::*;
+
+    /// A field: its name, its schema, and whether it need not be present.
+    pub type Field = (&'static str, ReferenceOr<Schema>, bool);
+
+    /// The reference to the schema of a component, which `f` makes if it
+    /// has not been added yet. The name is taken before `f` runs, so that
+    /// types that contain themselves refer to themselves.
+    pub fn component(
+        components: &mut Components,
+        name: &str,
+        f: impl FnOnce(&mut Components) -> Schema,
+    ) -> ReferenceOr<Schema> {
+        if !components.schemas.contains_key(name) {
+            components
+                .schemas
+                .insert(name.to_owned(), ReferenceOr::Item(Schema::default()));
+            let schema = f(components);
+            components
+                .schemas
+                .insert(name.to_owned(), ReferenceOr::Item(schema));
+        }
+        ReferenceOr::Reference {
+            reference: Reference::component(ComponentKind::Schemas, name),
+        }
+    }
+
+    /// A schema that is the one given, wrapping a reference in an `allOf`.
+    pub fn schema_of(schema: ReferenceOr<Schema>) -> Schema {
+        match schema {
+            ReferenceOr::Item(schema) => schema,
+            reference => all_of(vec![reference]),
+        }
+    }
+
+    /// The schema with a description, if there is one; references, whose
+    /// siblings 3.0 ignores, are wrapped in an `allOf`.
+    pub fn described(
+        schema: ReferenceOr<Schema>,
+        description: Option<&str>,
+    ) -> ReferenceOr<Schema> {
+        match description {
+            Some(description) => {
+                let mut schema = schema_of(schema);
+                schema.schema_data.description = Some(description.to_owned());
+                ReferenceOr::Item(schema)
+            }
+            None => schema,
+        }
+    }
+
+    pub fn object(fields: Vec<Field>, deny_unknown_fields: bool) -> ReferenceOr<Schema> {
+        let mut object = ObjectType::default();
+        for (name, schema, optional) in fields {
+            if !optional {
+                object.required.push(name.into());
+            }
+            object.properties.insert(name.into(), schema.map(Box::new));
+        }
+        if deny_unknown_fields {
+            object.additional_properties = Some(AdditionalProperties::None);
+        }
+        ReferenceOr::Item(Schema {
+            schema_data: SchemaData::default(),
+            schema_kind: SchemaKind::Type(Type::Object(object)),
+        })
+    }
+
+    pub fn strings(values: &[&str]) -> ReferenceOr<Schema> {
+        ReferenceOr::Item(Schema {
+            schema_data: SchemaData::default(),
+            schema_kind: SchemaKind::Type(Type::String(StringType {
+                enumeration: values.iter().map(|value| (*value).to_owned()).collect(),
+                ..StringType::default()
+            })),
+        })
+    }
+
+    /// The schema of `null`, as serde serializes unit variants of untagged
+    /// enums.
+    pub fn null() -> ReferenceOr<Schema> {
+        ReferenceOr::Item(Schema {
+            schema_data: SchemaData {
+                nullable: true,
+                ..SchemaData::default()
+            },
+            schema_kind: SchemaKind::Type(Type::Object(ObjectType {
+                max_properties: Some(0),
+                ..ObjectType::default()
+            })),
+        })
+    }
+
+    pub fn one_of(one_of: Vec<ReferenceOr<Schema>>) -> ReferenceOr<Schema> {
+        ReferenceOr::Item(Schema {
+            schema_data: SchemaData::default(),
+            schema_kind: SchemaKind::OneOf { one_of },
+        })
+    }
+
+    pub fn all_of(all_of: Vec<ReferenceOr<Schema>>) -> Schema {
+        Schema {
+            schema_data: SchemaData::default(),
+            schema_kind: SchemaKind::AllOf { all_of },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn describes_types() {
+        let mut components = Components::default();
+        let schema = components.register::<Option<Vec<std::collections::HashMap<String, u8>>>>();
+        let expected: Schema = serde_yaml::from_str(
+            "
+type: array
+nullable: true
+items:
+  type: object
+  additionalProperties: {type: integer, minimum: 0}
+",
+        )
+        .unwrap();
+        assert_eq!(schema, ReferenceOr::Item(expected));
+        assert!(components.schemas.is_empty());
+
+        let tag = __private::component(&mut components, "Tag", |components| {
+            let name = String::schema_in(components);
+            __private::schema_of(__private::object(vec![("name", name, false)], true))
+        });
+        let tags = components.register::<Option<Vec<Box<i64>>>>();
+        let tagged = __private::described(tag.clone(), Some("a tag"));
+        let expected: Schema = serde_yaml::from_str(
+            "
+description: a tag
+allOf: [{$ref: '#/components/schemas/Tag'}]
+",
+        )
+        .unwrap();
+        assert_eq!(tagged, ReferenceOr::Item(expected));
+        assert_eq!(components.schemas.len(), 1);
+        assert!(<Option<bool>>::optional() && !<Box<str>>::optional());
+        assert!(matches!(tags, ReferenceOr::Item(_)));
+    }
+}
@@ -1,5 +1,7 @@
 use crate::*;
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// Describes a single API operation on a path.
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
@@ -10,7 +12,7 @@ pub struct Operation {
     /// by resources or any other qualifier.
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
-    pub tags: Vec<String>,
+    pub tags: Vec<Name>,
     /// A short summary of what the operation does.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub summary: Option<String>,
@@ -48,6 +50,12 @@ pub struct Operation {
     /// REQUIRED. The list of possible responses as they are returned
     /// from executing this operation.
     pub responses: Responses,
+    /// A map of possible out-of band callbacks related to the parent operation.
+    /// The key is a unique identifier for the Callback Object. Each value in
+    /// the map is a Callback Object that describes a request that may be
+    /// initiated by the API provider and the expected responses.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub callbacks: BTreeMap<String, ReferenceOr<Callback>>,
     /// Declares this operation to be deprecated.Default value is false.
     #[serde(default, skip_serializing_if = "is_false")]
     pub deprecated: bool,
@@ -56,22 +64,70 @@ pub struct Operation {
     /// be used. Only one of the security requirement objects need to be satisfied to
     /// authorize a request. This definition overrides any declared top-level security.
     /// To remove a top-level security declaration, an empty array can be used.
-    #[serde(default)]
-    #[serde(skip_serializing_if = "Vec::is_empty")]
-    pub security: Vec<SecurityRequirement>,
+    /// `None` if the operation does not declare its own security, so that
+    /// the top-level one applies; see [`Operation::effective_security`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub security: Option<Vec<SecurityRequirement>>,
     /// An alternative server array to service this operation.
     /// If an alternative server object is specified at the
     /// Path Item Object or Root level, it will be overridden by this value.
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub servers: Vec<Server>,
+    /// Inline extensions to this object.
+    #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
+    pub extensions: IndexMap<String, serde_json::Value>,
+}
+
+impl Operation {
+    /// The parameters that apply to this operation: its own, along with
+    /// those of its path item that it does not override.
+    ///
+    /// A parameter is identified by its name and location, and the
+    /// operation's declaration of it takes the place of the path item's.
+    /// Those of the path item come first, then those of the operation,
+    /// each in the order declared. References are resolved against
+    /// `components`, and left out if they cannot be.
+    pub fn effective_parameters<'a>(
+        &'a self,
+        path_item: &'a PathItem,
+        components: &'a Components,
+    ) -> Vec<&'a Parameter> {
+        merge_parameters(components, &path_item.parameters, &self.parameters)
+    }
+}
+
+/// Merges the parameters of a path item, `shared`, with those of one of
+/// its operations, `own`; see [`Operation::effective_parameters`].
+pub(crate) fn merge_parameters<'a>(
+    components: &'a Components,
+    shared: &'a [ReferenceOr<Parameter>],
+    own: &'a [ReferenceOr<Parameter>],
+) -> Vec<&'a Parameter> {
+    let resolve = |parameters: &'a [ReferenceOr<Parameter>]| -> Vec<&'a Parameter> {
+        parameters
+            .iter()
+            .filter_map(|parameter| components.resolve(parameter).ok())
+            .collect()
+    };
+    let own = resolve(own);
+    let mut all: Vec<&Parameter> = resolve(shared)
+        .into_iter()
+        .filter(|shared| {
+            !own.iter().any(|own| {
+                own.location() == shared.location()
+                    && own.parameter_data_ref().name == shared.parameter_data_ref().name
+            })
+        })
+        .collect();
+    all.extend(own);
+    all
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{Operation, ReferenceOr, Responses, StatusCode};
+    use crate::{Map, Operation, ReferenceOr, Responses, StatusCode};
     use serde_yaml::from_str;
-    use std::collections::BTreeMap;
 
     #[test]
     fn deserialize_responses() {
@@ -80,10 +136,11 @@ mod tests {
                 responses: Responses {
                     default: None,
                     responses: {
-                        let mut map = BTreeMap::new();
+                        let mut map = Map::new();
                         map.insert(StatusCode::Code(200), ReferenceOr::ref_("test"));
                         map
-                    }
+                    },
+                    ..Default::default()
                 },
                 ..Default::default()
             },
@@ -95,10 +152,11 @@ mod tests {
                 responses: Responses {
                     default: None,
                     responses: {
-                        let mut map = BTreeMap::new();
+                        let mut map = Map::new();
                         map.insert(StatusCode::Code(666), ReferenceOr::ref_("demo"));
                         map
-                    }
+                    },
+                    ..Default::default()
                 },
                 ..Default::default()
             },
@@ -110,15 +168,73 @@ mod tests {
                 responses: Responses {
                     default: Some(ReferenceOr::ref_("def")),
                     responses: {
-                        let mut map = BTreeMap::new();
+                        let mut map = Map::new();
                         map.insert(StatusCode::Code(666), ReferenceOr::ref_("demo"));
                         map.insert(StatusCode::Code(418), ReferenceOr::ref_("demo"));
                         map
-                    }
+                    },
+                    ..Default::default()
                 },
                 ..Default::default()
             },
             from_str("{ responses: { default: { $ref: 'def' }, \"666\": { $ref: 'demo' }, 418: { $ref: 'demo' } } }").unwrap(),
         );
     }
+
+    #[test]
+    fn merges_parameters() {
+        let api: crate::OpenAPI = from_str(
+            "
+openapi: 3.0.0
+info: {title: t, version: '1'}
+paths:
+  /pets/{id}:
+    parameters:
+      - {name: id, in: path, required: true, schema: {type: string}}
+      - {name: limit, in: query, schema: {type: integer}}
+      - {$ref: '#/components/parameters/Trace'}
+    get:
+      parameters:
+        - {name: limit, in: query, description: own, schema: {type: integer}}
+        - {name: limit, in: header, schema: {type: integer}}
+        - {$ref: '#/components/parameters/Missing'}
+      responses: {}
+components:
+  parameters:
+    Trace: {name: trace, in: header, schema: {type: string}}
+",
+        )
+        .unwrap();
+        let item = match &api.paths["/pets/{id}"] {
+            ReferenceOr::Item(item) => item,
+            ReferenceOr::Reference { .. } => unreachable!(),
+        };
+        let parameters = item
+            .get
+            .as_ref()
+            .unwrap()
+            .effective_parameters(item, api.components.as_ref().unwrap());
+        let names: Vec<_> = parameters
+            .iter()
+            .map(|parameter| {
+                (
+                    parameter.location(),
+                    parameter.parameter_data_ref().name.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("path", "id"),
+                ("header", "trace"),
+                ("query", "limit"),
+                ("header", "limit")
+            ]
+        );
+        assert_eq!(
+            parameters[2].parameter_data_ref().description.as_deref(),
+            Some("own")
+        );
+    }
 }
@@ -0,0 +1,212 @@
+use crate::*;
+use std::collections::HashSet;
+
+/// How [`OpenAPI::assign_operation_ids`] names operations.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OperationIdStrategy {
+    pub case: OperationIdCase,
+    /// Whether `operationId`s not already in `case` are rewritten in it,
+    /// rather than kept as they are.
+    pub rewrite: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OperationIdCase {
+    /// `getPetsIdToys`.
+    #[default]
+    Camel,
+    /// `get_pets_id_toys`.
+    Snake,
+}
+
+/// An `operationId` [`OpenAPI::assign_operation_ids`] gave.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OperationIdChange {
+    pub path: String,
+    pub method: Method,
+    /// The `operationId` the operation had, if it had one.
+    pub old: Option<String>,
+    pub new: String,
+}
+
+impl OpenAPI {
+    /// Gives each operation of the paths without an `operationId` one made
+    /// of its method and path, such as `getPetsId` for `GET /pets/{id}`,
+    /// and with [`OperationIdStrategy::rewrite`], rewrites those not in the
+    /// case of the strategy in it, such as `list_pets` as `listPets`. Ids
+    /// made are kept unique with a numeric suffix, such as `getPets2`.
+    ///
+    /// Returns what changed, in the order of the operations.
+    ///
+    /// ```
+    /// use openapiv3::{Method, OpenAPI, OperationIdCase, OperationIdStrategy};
+    ///
+    /// let mut api: OpenAPI = serde_yaml::from_str(
+    ///     "
+    /// openapi: 3.0.0
+    /// info: {title: Pets, version: '1'}
+    /// paths:
+    ///   /pets/{id}:
+    ///     get: {responses: {}}
+    ///     delete: {operationId: removePet, responses: {}}
+    /// ",
+    /// )
+    /// .unwrap();
+    /// let strategy = OperationIdStrategy {
+    ///     case: OperationIdCase::Snake,
+    ///     rewrite: true,
+    /// };
+    /// let changes = api.assign_operation_ids(strategy);
+    /// let ids: Vec<_> = changes.iter().map(|change| change.new.as_str()).collect();
+    /// assert_eq!(ids, ["get_pets_id", "remove_pet"]);
+    /// assert_eq!(changes[1].old.as_deref(), Some("removePet"));
+    /// ```
+    pub fn assign_operation_ids(
+        &mut self,
+        strategy: OperationIdStrategy,
+    ) -> Vec<OperationIdChange> {
+        let conforms = |id: &str| !strategy.rewrite || join(&words(id), strategy.case) == id;
+        let mut taken: HashSet<String> = self
+            .operations()
+            .filter_map(|(_, _, operation)| operation.operation_id.clone())
+            .filter(|id| conforms(id))
+            .collect();
+
+        let mut changes = Vec::new();
+        for (path, method, operation) in self.operations_mut() {
+            let base = match operation.operation_id.as_deref() {
+                Some(id) if conforms(id) => continue,
+                Some(id) if !words(id).is_empty() => join(&words(id), strategy.case),
+                _ => {
+                    let mut words = vec![method.as_str().to_owned()];
+                    words.extend(path.split('/').flat_map(self::words));
+                    join(&words, strategy.case)
+                }
+            };
+            let mut id = base.clone();
+            let mut suffix = 2;
+            while !taken.insert(id.clone()) {
+                id = match strategy.case {
+                    OperationIdCase::Camel => format!("{}{}", base, suffix),
+                    OperationIdCase::Snake => format!("{}_{}", base, suffix),
+                };
+                suffix += 1;
+            }
+            changes.push(OperationIdChange {
+                path: path.to_owned(),
+                method,
+                old: operation.operation_id.replace(id.clone()),
+                new: id,
+            });
+        }
+        changes
+    }
+}
+
+/// The words of `s` in lower case, split at characters other than ASCII
+/// letters and digits, and where a lower case letter or digit is followed
+/// by an upper case letter.
+fn words(s: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    for part in s.split(|c: char| !c.is_ascii_alphanumeric()) {
+        let mut word = String::new();
+        let mut previous = None::<char>;
+        for c in part.chars() {
+            let hump = c.is_ascii_uppercase()
+                && previous.is_some_and(|p| p.is_ascii_lowercase() || p.is_ascii_digit());
+            if hump {
+                words.push(std::mem::take(&mut word));
+            }
+            word.push(c.to_ascii_lowercase());
+            previous = Some(c);
+        }
+        if !word.is_empty() {
+            words.push(word);
+        }
+    }
+    words
+}
+
+fn join(words: &[String], case: OperationIdCase) -> String {
+    match case {
+        OperationIdCase::Camel => words
+            .iter()
+            .enumerate()
+            .map(|(i, word)| {
+                let mut chars = word.chars();
+                match chars.next() {
+                    Some(first) if i > 0 => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                    _ => word.clone(),
+                }
+            })
+            .collect(),
+        OperationIdCase::Snake => words.join("_"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn assigns_operation_ids() {
+        let text = "
+openapi: 3.0.0
+info: {title: Pets, version: '1'}
+paths:
+  /pet-owners/{ownerId}/pets:
+    get: {responses: {}}
+    post: {operationId: add_pet, responses: {}}
+  /petOwners/{owner_id}/pets:
+    get: {responses: {}}
+  /pets:
+    get: {operationId: getPetOwnersOwnerIdPets, responses: {}}
+";
+        let mut api: OpenAPI = serde_yaml::from_str(text).unwrap();
+        let changes = api.assign_operation_ids(OperationIdStrategy::default());
+        let changed: Vec<_> = changes
+            .iter()
+            .map(|c| (c.path.as_str(), c.method, c.old.as_deref(), c.new.as_str()))
+            .collect();
+        assert_eq!(
+            changed,
+            [
+                (
+                    "/pet-owners/{ownerId}/pets",
+                    Method::Get,
+                    None,
+                    "getPetOwnersOwnerIdPets2"
+                ),
+                (
+                    "/petOwners/{owner_id}/pets",
+                    Method::Get,
+                    None,
+                    "getPetOwnersOwnerIdPets3"
+                ),
+            ]
+        );
+        assert!(api
+            .assign_operation_ids(OperationIdStrategy::default())
+            .is_empty());
+
+        let mut api: OpenAPI = serde_yaml::from_str(text).unwrap();
+        let changes = api.assign_operation_ids(OperationIdStrategy {
+            case: OperationIdCase::Camel,
+            rewrite: true,
+        });
+        let ids: Vec<_> = api
+            .operations()
+            .map(|(_, _, operation)| operation.operation_id.as_deref().unwrap())
+            .collect();
+        assert_eq!(
+            ids,
+            [
+                "getPetOwnersOwnerIdPets2",
+                "addPet",
+                "getPetOwnersOwnerIdPets3",
+                "getPetOwnersOwnerIdPets"
+            ]
+        );
+        assert_eq!(changes.len(), 3);
+    }
+}
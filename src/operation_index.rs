@@ -0,0 +1,128 @@
+use crate::reference::unescape_pointer_segment;
+use crate::*;
+use std::collections::HashMap;
+
+/// The operations of the paths of a document by their `operationId`, for
+/// looking up many of them. Where several operations have the same id,
+/// the first is kept.
+///
+/// ```
+/// use openapiv3::{Link, Method, OpenAPI, OperationIndex};
+///
+/// let api: OpenAPI = serde_yaml::from_str(
+///     "
+/// openapi: 3.0.0
+/// info: {title: Pets, version: '1'}
+/// paths:
+///   /pets: {post: {operationId: addPet, responses: {}}}
+///   /pets/{id}: {get: {operationId: getPet, responses: {}}}
+/// ",
+/// )
+/// .unwrap();
+/// let index = OperationIndex::new(&api);
+/// let (path, method, _) = index.get("getPet").unwrap();
+/// assert_eq!((path, method), ("/pets/{id}", Method::Get));
+/// assert!(index.get("deletePet").is_none());
+///
+/// let link: Link = serde_yaml::from_str("operationRef: '#/paths/~1pets/post'").unwrap();
+/// let (_, _, operation) = index.link_target(&link).unwrap();
+/// assert_eq!(operation.operation_id.as_deref(), Some("addPet"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct OperationIndex<'a> {
+    api: &'a OpenAPI,
+    operations: HashMap<&'a str, (&'a str, Method, &'a Operation)>,
+}
+
+impl<'a> OperationIndex<'a> {
+    pub fn new(api: &'a OpenAPI) -> Self {
+        let mut operations = HashMap::new();
+        for (path, method, operation) in api.operations() {
+            if let Some(id) = &operation.operation_id {
+                operations
+                    .entry(id.as_str())
+                    .or_insert((path, method, operation));
+            }
+        }
+        OperationIndex { api, operations }
+    }
+
+    /// The path, method and operation of `id`.
+    pub fn get(&self, id: &str) -> Option<(&'a str, Method, &'a Operation)> {
+        self.operations.get(id).copied()
+    }
+
+    /// The operation a link is to, by its `operationId`, or by an
+    /// `operationRef` to an operation of the paths of the document, such
+    /// as `#/paths/~1pets/get`.
+    pub fn link_target(&self, link: &Link) -> Option<(&'a str, Method, &'a Operation)> {
+        if let Some(id) = &link.operation_id {
+            return self.get(id);
+        }
+        let pointer = link.operation_ref.as_deref()?.strip_prefix("#/paths/")?;
+        let (path, method) = pointer.rsplit_once('/')?;
+        let path = unescape_pointer_segment(path);
+        let method: Method = method.parse().ok()?;
+        let (path, item) = self.api.paths.get_key_value(&path)?;
+        match item {
+            ReferenceOr::Item(item) => Some((path, method, item.operation(method)?)),
+            ReferenceOr::Reference { .. } => None,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.operations.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.operations.is_empty()
+    }
+}
+
+impl OpenAPI {
+    /// The path, method and operation of the operation of the paths whose
+    /// `operationId` is `id`. An [`OperationIndex`] finds many faster.
+    pub fn operation_by_id(&self, id: &str) -> Option<(&str, Method, &Operation)> {
+        self.operations()
+            .find(|(_, _, operation)| operation.operation_id.as_deref() == Some(id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_operations_by_id() {
+        let api: OpenAPI = serde_yaml::from_str(
+            "
+openapi: 3.0.0
+info: {title: Pets, version: '1'}
+paths:
+  /a: {get: {operationId: same, responses: {}}}
+  /b:
+    get: {operationId: same, responses: {}}
+    put: {operationId: putB, responses: {}}
+    delete: {responses: {}}
+",
+        )
+        .unwrap();
+        let index = OperationIndex::new(&api);
+        assert_eq!(index.len(), 2);
+        assert_eq!(index.get("same").unwrap().0, "/a");
+        assert_eq!(api.operation_by_id("same").unwrap().0, "/a");
+        assert_eq!(api.operation_by_id("putB").unwrap().1, Method::Put);
+        assert!(api.operation_by_id("deleteB").is_none());
+
+        let link = |text| serde_yaml::from_str::<Link>(text).unwrap();
+        let target = index.link_target(&link("operationRef: '#/paths/~1b/delete'"));
+        assert_eq!(
+            target.map(|(path, method, _)| (path, method)),
+            Some(("/b", Method::Delete))
+        );
+        assert!(index
+            .link_target(&link("operationRef: '#/paths/~1b/post'"))
+            .is_none());
+        assert!(index.link_target(&link("operationId: missing")).is_none());
+    }
+}
@@ -0,0 +1,115 @@
+//! Spreading work between threads, for the `par_` variants of validation,
+//! example checking and dereferencing.
+
+use std::num::NonZeroUsize;
+use std::panic;
+use std::thread;
+
+/// Splits `items` into one run for each available thread, and applies `f`
+/// to each run on its own thread, returning the results in order.
+pub(crate) fn map_chunks<T, R, F>(items: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&[T]) -> R + Sync,
+{
+    let f = &f;
+    thread::scope(|scope| {
+        let handles: Vec<_> = items
+            .chunks(chunk_size(items.len()))
+            .map(|chunk| scope.spawn(move || f(chunk)))
+            .collect();
+        handles.into_iter().map(join).collect()
+    })
+}
+
+/// Like [`map_chunks`], handing each thread its run of `items` to modify.
+pub(crate) fn map_chunks_mut<T, R, F>(items: &mut [T], f: F) -> Vec<R>
+where
+    T: Send,
+    R: Send,
+    F: Fn(&mut [T]) -> R + Sync,
+{
+    let f = &f;
+    let size = chunk_size(items.len());
+    thread::scope(|scope| {
+        let handles: Vec<_> = items
+            .chunks_mut(size)
+            .map(|chunk| scope.spawn(move || f(chunk)))
+            .collect();
+        handles.into_iter().map(join).collect()
+    })
+}
+
+fn chunk_size(len: usize) -> usize {
+    // Tests split the work even on a single processor, so that combining
+    // the results of several threads is exercised.
+    let threads = if cfg!(test) {
+        4
+    } else {
+        thread::available_parallelism().map_or(1, NonZeroUsize::get)
+    };
+    len.div_ceil(threads).max(1)
+}
+
+/// Waits for a thread, passing on its panic if it had one.
+fn join<R>(handle: thread::ScopedJoinHandle<'_, R>) -> R {
+    handle
+        .join()
+        .unwrap_or_else(|payload| panic::resume_unwind(payload))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn matches_sequential_results() {
+        let mut paths = String::new();
+        for i in 0..64 {
+            // Every fourth operation repeats an `operationId`, and every
+            // fifth has an example that its schema rejects.
+            let id = if i % 4 == 0 { 0 } else { i };
+            let example = if i % 5 == 0 { "x" } else { "1" };
+            paths.push_str(&format!(
+                "
+  /p{i}:
+    get:
+      operationId: op{id}
+      responses:
+        '200':
+          description: ok
+          content:
+            application/json:
+              schema: {{$ref: '#/components/schemas/Count'}}
+              example: {example}
+",
+                i = i,
+                id = id,
+                example = example,
+            ));
+        }
+        let api: OpenAPI = serde_yaml::from_str(&format!(
+            "
+openapi: 3.0.0
+info: {{title: t, version: '1'}}
+paths:{}
+components:
+  schemas:
+    Count: {{type: integer}}
+",
+            paths
+        ))
+        .unwrap();
+
+        let errors = api.validate();
+        assert_eq!(errors.len(), 15);
+        assert_eq!(api.par_validate(), errors);
+        let validator = Validator::new();
+        assert_eq!(validator.par_validate(&api), validator.validate(&api));
+        let examples = api.validate_examples();
+        assert_eq!(examples.len(), 13);
+        assert_eq!(api.par_validate_examples(), examples);
+        assert_eq!(api.clone().par_into_dereferenced(), api.into_dereferenced());
+    }
+}
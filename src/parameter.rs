@@ -1,11 +1,13 @@
 use crate::*;
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
+use std::fmt;
 
 /// Describes a single operation parameter.
 ///
 /// A unique parameter is defined by a combination of a name and location.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
 pub struct ParameterData {
     /// REQUIRED. The name of the parameter. Parameter names are case sensitive.
     /// If in is "path", the name field MUST correspond to the associated path
@@ -34,14 +36,23 @@ pub struct ParameterData {
     /// be transitioned out of usage.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub deprecated: Option<bool>,
+    /// When true, array and object values generate a separate parameter
+    /// for each item or property. Defaults to true for the `form` style,
+    /// and false for every other.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explode: Option<bool>,
     #[serde(flatten)]
     pub format: ParameterSchemaOrContent,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub example: Option<serde_json::Value>,
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub examples: BTreeMap<String, ReferenceOr<Example>>,
+    /// Inline extensions to this object.
+    #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
+    pub extensions: IndexMap<String, serde_json::Value>,
 }
 
+#[allow(clippy::large_enum_variant)]
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub enum ParameterSchemaOrContent {
     #[serde(rename = "schema")]
@@ -50,7 +61,78 @@ pub enum ParameterSchemaOrContent {
     Content(Content),
 }
 
-pub type Content = BTreeMap<String, MediaType>;
+/// A schema that allows any value.
+impl Default for ParameterSchemaOrContent {
+    fn default() -> Self {
+        ParameterSchemaOrContent::Schema(ReferenceOr::Item(Schema::default()))
+    }
+}
+
+pub type Content = BTreeMap<MediaTypeRange, MediaType>;
+
+/// What parameters and headers share: how their values are described, and
+/// whether they are required.
+///
+/// ```
+/// use openapiv3::{Header, Parameter, Schemed};
+///
+/// fn describe(item: &impl Schemed) -> String {
+///     let requirement = if item.required() { "required" } else { "optional" };
+///     match item.content() {
+///         Some(content) => format!("{}, as {}", requirement, content.keys().next().unwrap()),
+///         None => requirement.to_owned(),
+///     }
+/// }
+///
+/// let header: Header = serde_yaml::from_str("{required: true, schema: {type: string}}").unwrap();
+/// let parameter: Parameter =
+///     serde_yaml::from_str("{name: q, in: query, content: {application/json: {}}}").unwrap();
+/// assert_eq!(describe(&header), "required");
+/// assert_eq!(describe(&parameter), "optional, as application/json");
+/// ```
+pub trait Schemed {
+    /// The schema or the media type that describes the value.
+    fn format(&self) -> &ParameterSchemaOrContent;
+
+    fn required(&self) -> bool;
+
+    /// The schema of the value, unless a media type describes it.
+    fn schema(&self) -> Option<&ReferenceOr<Schema>> {
+        match self.format() {
+            ParameterSchemaOrContent::Schema(schema) => Some(schema),
+            ParameterSchemaOrContent::Content(_) => None,
+        }
+    }
+
+    /// The map holding the single media type that describes the value,
+    /// unless a schema describes it.
+    fn content(&self) -> Option<&Content> {
+        match self.format() {
+            ParameterSchemaOrContent::Schema(_) => None,
+            ParameterSchemaOrContent::Content(content) => Some(content),
+        }
+    }
+}
+
+impl Schemed for ParameterData {
+    fn format(&self) -> &ParameterSchemaOrContent {
+        &self.format
+    }
+
+    fn required(&self) -> bool {
+        self.required
+    }
+}
+
+impl Schemed for Parameter {
+    fn format(&self) -> &ParameterSchemaOrContent {
+        &self.parameter_data_ref().format
+    }
+
+    fn required(&self) -> bool {
+        self.parameter_data_ref().required
+    }
+}
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(tag = "in")]
@@ -95,24 +177,155 @@ pub enum Parameter {
     },
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+impl Parameter {
+    /// Returns the data common to parameters in every location.
+    pub fn parameter_data_ref(&self) -> &ParameterData {
+        match self {
+            Parameter::Query { parameter_data, .. }
+            | Parameter::Header { parameter_data, .. }
+            | Parameter::Path { parameter_data, .. }
+            | Parameter::Cookie { parameter_data, .. } => parameter_data,
+        }
+    }
+
+    /// Returns a mutable reference to the data common to parameters
+    /// in every location.
+    pub fn parameter_data_mut(&mut self) -> &mut ParameterData {
+        match self {
+            Parameter::Query { parameter_data, .. }
+            | Parameter::Header { parameter_data, .. }
+            | Parameter::Path { parameter_data, .. }
+            | Parameter::Cookie { parameter_data, .. } => parameter_data,
+        }
+    }
+
+    /// The parameter as a Header Object and the name it goes by, if it is
+    /// a header parameter.
+    pub fn into_header(self) -> Option<(String, Header)> {
+        match self {
+            Parameter::Header {
+                parameter_data,
+                style,
+            } => Some((
+                parameter_data.name,
+                Header {
+                    description: parameter_data.description,
+                    style,
+                    explode: parameter_data.explode,
+                    required: parameter_data.required,
+                    deprecated: parameter_data.deprecated,
+                    format: parameter_data.format,
+                    example: parameter_data.example,
+                    examples: parameter_data.examples,
+                    extensions: parameter_data.extensions,
+                },
+            )),
+            _ => None,
+        }
+    }
+
+    /// The value of `in`, such as `query`.
+    pub(crate) fn location(&self) -> &'static str {
+        match self {
+            Parameter::Query { .. } => "query",
+            Parameter::Header { .. } => "header",
+            Parameter::Path { .. } => "path",
+            Parameter::Cookie { .. } => "cookie",
+        }
+    }
+
+    /// The style of the parameter, whichever of those of its location it
+    /// declares, else the default of its location: `form` for query and
+    /// cookie parameters, `simple` for path and header ones.
+    pub fn effective_style(&self) -> ParameterStyle {
+        match self {
+            Parameter::Query { style, .. } => match style {
+                QueryStyle::Form => ParameterStyle::Form,
+                QueryStyle::SpaceDelimited => ParameterStyle::SpaceDelimited,
+                QueryStyle::PipeDelimited => ParameterStyle::PipeDelimited,
+                QueryStyle::DeepObject => ParameterStyle::DeepObject,
+            },
+            Parameter::Header {
+                style: HeaderStyle::Simple,
+                ..
+            } => ParameterStyle::Simple,
+            Parameter::Path { style, .. } => match style {
+                PathStyle::Matrix => ParameterStyle::Matrix,
+                PathStyle::Label => ParameterStyle::Label,
+                PathStyle::Simple => ParameterStyle::Simple,
+            },
+            Parameter::Cookie {
+                style: CookieStyle::Form,
+                ..
+            } => ParameterStyle::Form,
+        }
+    }
+
+    /// Whether array and object values of the parameter are exploded: as
+    /// declared, else true for the `form` and `deepObject` styles and false
+    /// for every other.
+    ///
+    /// The specification has no other value for `deepObject`, which only
+    /// exists exploded.
+    pub fn effective_explode(&self) -> bool {
+        self.parameter_data_ref().explode.unwrap_or_else(|| {
+            matches!(
+                self.effective_style(),
+                ParameterStyle::Form | ParameterStyle::DeepObject
+            )
+        })
+    }
+}
+
+/// The style of a parameter in any location, as given by
+/// [`Parameter::effective_style`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ParameterStyle {
+    Matrix,
+    Label,
+    Form,
+    Simple,
+    SpaceDelimited,
+    PipeDelimited,
+    DeepObject,
+}
+
+impl ParameterStyle {
+    /// The name of the style in a document, such as `deepObject`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ParameterStyle::Matrix => "matrix",
+            ParameterStyle::Label => "label",
+            ParameterStyle::Form => "form",
+            ParameterStyle::Simple => "simple",
+            ParameterStyle::SpaceDelimited => "spaceDelimited",
+            ParameterStyle::PipeDelimited => "pipeDelimited",
+            ParameterStyle::DeepObject => "deepObject",
+        }
+    }
+}
+
+impl fmt::Display for ParameterStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
 pub enum PathStyle {
     #[serde(rename = "matrix")]
     Matrix,
     #[serde(rename = "label")]
     Label,
     #[serde(rename = "simple")]
+    #[default]
     Simple,
 }
 
-impl Default for PathStyle {
-    fn default() -> Self {
-        PathStyle::Simple
-    }
-}
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
 pub enum QueryStyle {
     #[serde(rename = "form")]
+    #[default]
     Form,
     #[serde(rename = "spaceDelimited")]
     SpaceDelimited,
@@ -122,30 +335,74 @@ pub enum QueryStyle {
     DeepObject,
 }
 
-impl Default for QueryStyle {
-    fn default() -> Self {
-        QueryStyle::Form
-    }
-}
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
 pub enum CookieStyle {
     #[serde(rename = "form")]
+    #[default]
     Form,
 }
 
-impl Default for CookieStyle {
-    fn default() -> CookieStyle {
-        CookieStyle::Form
-    }
-}
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
 pub enum HeaderStyle {
     #[serde(rename = "simple")]
+    #[default]
     Simple,
 }
 
-impl Default for HeaderStyle {
-    fn default() -> HeaderStyle {
-        HeaderStyle::Simple
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn applies_style_defaults() {
+        let parameter = |yaml| -> Parameter { serde_yaml::from_str(yaml).unwrap() };
+        let cases = [
+            (
+                "{name: p, in: query, schema: {}}",
+                ParameterStyle::Form,
+                true,
+            ),
+            (
+                "{name: p, in: query, explode: false, schema: {}}",
+                ParameterStyle::Form,
+                false,
+            ),
+            (
+                "{name: p, in: query, style: deepObject, schema: {}}",
+                ParameterStyle::DeepObject,
+                true,
+            ),
+            (
+                "{name: p, in: query, style: pipeDelimited, schema: {}}",
+                ParameterStyle::PipeDelimited,
+                false,
+            ),
+            (
+                "{name: p, in: path, required: true, schema: {}}",
+                ParameterStyle::Simple,
+                false,
+            ),
+            (
+                "{name: p, in: path, required: true, style: label, explode: true, schema: {}}",
+                ParameterStyle::Label,
+                true,
+            ),
+            (
+                "{name: p, in: header, schema: {}}",
+                ParameterStyle::Simple,
+                false,
+            ),
+            (
+                "{name: p, in: cookie, schema: {}}",
+                ParameterStyle::Form,
+                true,
+            ),
+        ];
+        for (yaml, style, explode) in cases.iter() {
+            let parameter = parameter(yaml);
+            assert_eq!(parameter.effective_style(), *style, "{}", yaml);
+            assert_eq!(parameter.effective_explode(), *explode, "{}", yaml);
+        }
+        assert_eq!(ParameterStyle::SpaceDelimited.to_string(), "spaceDelimited");
     }
 }
@@ -0,0 +1,397 @@
+use crate::reference::escape_pointer_segment;
+use crate::*;
+use serde::de::{
+    self, DeserializeOwned, DeserializeSeed, EnumAccess, IntoDeserializer, MapAccess, SeqAccess,
+    VariantAccess, Visitor,
+};
+use serde::Deserialize;
+use serde_yaml::Value;
+use std::cell::RefCell;
+use std::fmt;
+
+/// An error deserializing a document, along with where in the document it
+/// happened.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// A JSON Pointer to the value that could not be read, such as
+    /// `/paths/~1pets/get/responses/200`.
+    pub location: String,
+    /// The position of the value in the source text.
+    pub span: Option<Span>,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.location.is_empty() {
+            write!(f, "{}: ", self.location)?;
+        }
+        f.write_str(&self.message)?;
+        if let Some(span) = self.span {
+            write!(f, " at {}", span)?;
+        }
+        Ok(())
+    }
+}
+
+/// Reads `text` again to find where it fails to deserialize as `T`, or
+/// `None` if it cannot be told.
+pub(crate) fn locate_error<T: DeserializeOwned>(text: &str) -> Option<ParseError> {
+    let value: Value = serde_yaml::from_str(text).ok()?;
+    STATE.with(|state| *state.borrow_mut() = Some(State::default()));
+    with_state(|state| state.entering = true);
+    let result = T::deserialize(Tracked(&value));
+    let state = STATE
+        .with(|state| state.borrow_mut().take())
+        .unwrap_or_default();
+    let message = result.err()?.0;
+    let location = state.failed_at.unwrap_or_default();
+    let span = SourceMap::parse(text)
+        .ok()
+        .and_then(|spans| spans.locate(&location));
+    Some(ParseError {
+        location,
+        span,
+        message,
+    })
+}
+
+/// Whether a value is being deserialized while locating an error, straight
+/// from the value being tracked, which is then no longer the case.
+///
+/// Types that read their input through a buffer, as [`ReferenceOr`] does,
+/// use this to go on tracking the buffer with [`deserialize_tracked`].
+pub(crate) fn entering() -> bool {
+    with_state(|state| std::mem::take(&mut state.entering)).unwrap_or(false)
+}
+
+/// Deserializes a buffered value that stands in for the tracked value, so
+/// that errors within it are located.
+pub(crate) fn deserialize_tracked<'de, T: Deserialize<'de>>(value: &Value) -> Result<T, String> {
+    with_state(|state| state.entering = true);
+    T::deserialize(Tracked(value)).map_err(|e| e.0)
+}
+
+thread_local! {
+    static STATE: RefCell<Option<State>> = const { RefCell::new(None) };
+}
+
+#[derive(Default)]
+struct State {
+    path: Vec<String>,
+    /// Set when handing the tracked value of a node to its type, until
+    /// the type asks for its contents.
+    entering: bool,
+    /// The innermost node where an error came from.
+    failed_at: Option<String>,
+}
+
+fn with_state<R>(f: impl FnOnce(&mut State) -> R) -> Option<R> {
+    STATE.with(|state| state.borrow_mut().as_mut().map(f))
+}
+
+/// Deserializes the node of a map or sequence named `segment`.
+///
+/// Types that read a map or sequence through a buffer, as flattened fields
+/// are, use this for each node to go on tracking it.
+pub(crate) fn descend<R, E>(segment: String, f: impl FnOnce() -> Result<R, E>) -> Result<R, E> {
+    with_state(|state| {
+        state.path.push(segment);
+        state.entering = true;
+    });
+    let result = f();
+    with_state(|state| {
+        state.entering = false;
+        if result.is_err() && state.failed_at.is_none() {
+            let mut location = String::new();
+            for segment in &state.path {
+                location.push('/');
+                location.push_str(&escape_pointer_segment(segment));
+            }
+            state.failed_at = Some(location);
+        }
+        state.path.pop();
+    });
+    result
+}
+
+fn segment(key: &Value) -> String {
+    match key {
+        Value::String(key) => key.clone(),
+        Value::Number(key) => key.to_string(),
+        Value::Bool(key) => key.to_string(),
+        Value::Null => "null".to_owned(),
+        Value::Sequence(_) | Value::Mapping(_) => "?".to_owned(),
+    }
+}
+
+#[derive(Debug)]
+struct Error(String);
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl de::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error(msg.to_string())
+    }
+}
+
+/// A deserializer of a YAML value that records the path to each node it
+/// descends into.
+struct Tracked<'a>(&'a Value);
+
+impl<'a> Tracked<'a> {
+    fn unexpected(&self) -> de::Unexpected<'a> {
+        match self.0 {
+            Value::Null => de::Unexpected::Unit,
+            Value::Bool(b) => de::Unexpected::Bool(*b),
+            Value::Number(_) => de::Unexpected::Other("number"),
+            Value::String(s) => de::Unexpected::Str(s),
+            Value::Sequence(_) => de::Unexpected::Seq,
+            Value::Mapping(_) => de::Unexpected::Map,
+        }
+    }
+}
+
+impl<'de, 'a> de::Deserializer<'de> for Tracked<'a> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        entering();
+        match self.0 {
+            Value::Null => visitor.visit_unit(),
+            Value::Bool(b) => visitor.visit_bool(*b),
+            Value::Number(n) => match (n.as_u64(), n.as_i64(), n.as_f64()) {
+                (Some(n), _, _) => visitor.visit_u64(n),
+                (None, Some(n), _) => visitor.visit_i64(n),
+                (None, None, n) => visitor.visit_f64(n.unwrap_or(f64::NAN)),
+            },
+            Value::String(s) => visitor.visit_str(s),
+            Value::Sequence(items) => visitor.visit_seq(Items(items.iter().enumerate())),
+            Value::Mapping(entries) => visitor.visit_map(Entries {
+                entries: entries.iter(),
+                value: None,
+            }),
+        }
+    }
+
+    /// Reads scalars as strings, as serde_yaml does for the fields of
+    /// types such as `version: 1`.
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.0 {
+            Value::Bool(_) | Value::Number(_) => {
+                entering();
+                visitor.visit_str(&segment(self.0))
+            }
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.0 {
+            Value::Null => {
+                entering();
+                visitor.visit_none()
+            }
+            _ => {
+                with_state(|state| state.entering = true);
+                visitor.visit_some(self)
+            }
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        with_state(|state| state.entering = true);
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        entering();
+        match self.0 {
+            Value::String(variant) => visitor.visit_enum(variant.as_str().into_deserializer()),
+            Value::Mapping(entries) if entries.len() == 1 => {
+                let (variant, value) = entries.iter().next().unwrap();
+                visitor.visit_enum(Variant { variant, value })
+            }
+            _ => Err(de::Error::invalid_type(
+                self.unexpected(),
+                &"an enum variant",
+            )),
+        }
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        entering();
+        visitor.visit_unit()
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char bytes byte_buf
+        unit unit_struct seq tuple tuple_struct map struct identifier
+    }
+}
+
+struct Items<'a>(std::iter::Enumerate<std::slice::Iter<'a, Value>>);
+
+impl<'de, 'a> SeqAccess<'de> for Items<'a> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Error> {
+        match self.0.next() {
+            Some((i, item)) => descend(i.to_string(), || seed.deserialize(Tracked(item))).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.0.len())
+    }
+}
+
+struct Entries<'a> {
+    entries: serde_yaml::mapping::Iter<'a>,
+    /// The key and value of the entry whose key was last read.
+    value: Option<(&'a Value, &'a Value)>,
+}
+
+impl<'de, 'a> MapAccess<'de> for Entries<'a> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        match self.entries.next() {
+            Some((key, value)) => {
+                self.value = Some((key, value));
+                descend(segment(key), || seed.deserialize(Tracked(key))).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        let (key, value) = self
+            .value
+            .take()
+            .ok_or_else(|| <Error as de::Error>::custom("value requested before key"))?;
+        descend(segment(key), || seed.deserialize(Tracked(value)))
+    }
+}
+
+struct Variant<'a> {
+    variant: &'a Value,
+    value: &'a Value,
+}
+
+impl<'de, 'a> EnumAccess<'de> for Variant<'a> {
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self), Error> {
+        Ok((seed.deserialize(Tracked(self.variant))?, self))
+    }
+}
+
+impl<'de, 'a> VariantAccess<'de> for Variant<'a> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        match self.value {
+            Value::Null => Ok(()),
+            _ => Err(de::Error::invalid_type(
+                Tracked(self.value).unexpected(),
+                &"a unit variant",
+            )),
+        }
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Error> {
+        descend(segment(self.variant), || {
+            seed.deserialize(Tracked(self.value))
+        })
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, Error> {
+        descend(segment(self.variant), || {
+            de::Deserializer::deserialize_any(Tracked(self.value), visitor)
+        })
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        descend(segment(self.variant), || {
+            de::Deserializer::deserialize_any(Tracked(self.value), visitor)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    fn error(text: &str) -> String {
+        match parse::<OpenAPI>(text) {
+            Err(ReadError::Parse(error)) => error.to_string(),
+            result => panic!("unexpected result {:?}", result.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn locates_errors() {
+        let yaml = "\
+openapi: 3.0.0
+info: {title: t, version: 1}
+paths:
+  /pets:
+    get:
+      parameters:
+        - {name: id, in: path, schema: {type: string}}
+        - {name: limit, in: query, schema: {}, style: bogus}
+      responses:
+        200: {content: {}}
+";
+        assert_eq!(
+            error(yaml),
+            "/paths/~1pets/get/parameters/1: unknown variant `bogus`, expected one of \
+             `form`, `spaceDelimited`, `pipeDelimited`, `deepObject` at line 8, column 11"
+        );
+        assert_eq!(
+            error(&yaml.replace("bogus", "form")),
+            "/paths/~1pets/get/responses/200: missing field `description` at line 10, column 9"
+        );
+        assert_eq!(
+            error(r#"{"openapi": "3.0.0", "paths": {}}"#),
+            "missing field `info` at line 1, column 1"
+        );
+        assert!(matches!(
+            parse::<OpenAPI>("openapi: [").unwrap_err(),
+            ReadError::Yaml(_)
+        ));
+    }
+}
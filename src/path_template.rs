@@ -0,0 +1,366 @@
+use crate::util::percent_encode;
+use crate::Method;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// A path of the Paths Object, such as `/pets/{petId}`, with the variables
+/// between braces that path parameters fill in.
+///
+/// ```
+/// use openapiv3::PathTemplate;
+///
+/// let template: PathTemplate = "/pets/{petId}/photos/{photoId}".parse().unwrap();
+/// assert_eq!(template.variables().collect::<Vec<_>>(), vec!["petId", "photoId"]);
+/// assert_eq!(
+///     template.matches("/pets/7/photos/front"),
+///     Some(vec![("petId", "7"), ("photoId", "front")])
+/// );
+/// assert_eq!(template.matches("/pets/7"), None);
+/// assert_eq!(
+///     template
+///         .render(|name| Some(if name == "petId" { "7" } else { "a b" }.to_owned()))
+///         .unwrap(),
+///     "/pets/7/photos/a%20b"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PathTemplate {
+    template: String,
+    pieces: Vec<Piece>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Piece {
+    Literal(String),
+    Variable(String),
+}
+
+impl PathTemplate {
+    /// Parses a path template. Braces must pair up, and each pair must hold
+    /// the name of a variable.
+    pub fn parse(template: &str) -> Result<Self, ParsePathTemplateError> {
+        let error = |kind| ParsePathTemplateError {
+            template: template.to_owned(),
+            kind,
+        };
+        let mut pieces = Vec::new();
+        let mut rest = template;
+        while !rest.is_empty() {
+            let open = rest.find('{').unwrap_or(rest.len());
+            let literal = &rest[..open];
+            if literal.contains('}') {
+                return Err(error(PathTemplateErrorKind::UnopenedBrace));
+            }
+            if !literal.is_empty() {
+                pieces.push(Piece::Literal(literal.to_owned()));
+            }
+            rest = &rest[open..];
+            if rest.is_empty() {
+                break;
+            }
+            let close = rest
+                .find('}')
+                .ok_or_else(|| error(PathTemplateErrorKind::UnclosedBrace))?;
+            let name = &rest[1..close];
+            if name.contains('{') || name.contains('/') {
+                return Err(error(PathTemplateErrorKind::UnclosedBrace));
+            }
+            if name.is_empty() {
+                return Err(error(PathTemplateErrorKind::EmptyVariable));
+            }
+            pieces.push(Piece::Variable(name.to_owned()));
+            rest = &rest[close + 1..];
+        }
+        Ok(PathTemplate {
+            template: template.to_owned(),
+            pieces,
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.template
+    }
+
+    /// The names of the variables of the template, in order.
+    pub fn variables(&self) -> impl Iterator<Item = &str> {
+        self.pieces.iter().filter_map(|piece| match piece {
+            Piece::Variable(name) => Some(name.as_str()),
+            Piece::Literal(_) => None,
+        })
+    }
+
+    /// Matches a concrete path, such as `/pets/7`, against the template,
+    /// returning the value of each variable in order if it matches.
+    ///
+    /// A value is never empty and never spans more than one segment of the
+    /// path. Values are as they appear in the path, without percent-decoding.
+    pub fn matches<'p>(&self, path: &'p str) -> Option<Vec<(&str, &'p str)>> {
+        let mut values = Vec::new();
+        if match_pieces(&self.pieces, path, &mut values) {
+            values.reverse();
+            Some(values)
+        } else {
+            None
+        }
+    }
+
+    /// Fills in the variables of the template with the values `value`
+    /// returns for them, percent-encoding each so that it stays within its
+    /// segment of the path.
+    ///
+    /// Fails with the name of the first variable without a value.
+    pub fn render<F>(&self, mut value: F) -> Result<String, MissingVariable>
+    where
+        F: FnMut(&str) -> Option<String>,
+    {
+        let mut path = String::with_capacity(self.template.len());
+        for piece in &self.pieces {
+            match piece {
+                Piece::Literal(literal) => path.push_str(literal),
+                Piece::Variable(name) => {
+                    let value = value(name).ok_or_else(|| MissingVariable(name.clone()))?;
+                    percent_encode(&value, &mut path);
+                }
+            }
+        }
+        Ok(path)
+    }
+
+    /// The template written as a route of a web framework, for matching
+    /// paths with the framework's router, or `None` if the framework cannot
+    /// express it.
+    ///
+    /// ```
+    /// use openapiv3::{PathTemplate, RouteSyntax};
+    ///
+    /// let template = PathTemplate::parse("/pets/{petId}/photos").unwrap();
+    /// assert_eq!(template.route(RouteSyntax::Axum).unwrap(), "/pets/:petId/photos");
+    /// assert_eq!(template.route(RouteSyntax::Actix).unwrap(), "/pets/{petId}/photos");
+    ///
+    /// let file = PathTemplate::parse("/files/{name}.{ext}").unwrap();
+    /// assert_eq!(file.route(RouteSyntax::Axum), None);
+    /// ```
+    pub fn route(&self, syntax: RouteSyntax) -> Option<String> {
+        let mut route = String::with_capacity(self.template.len());
+        for (i, piece) in self.pieces.iter().enumerate() {
+            match (piece, syntax) {
+                (Piece::Literal(literal), _) => route.push_str(literal),
+                (Piece::Variable(name), RouteSyntax::Axum) => {
+                    // Captures of axum span whole segments.
+                    let starts = route.is_empty() || route.ends_with('/');
+                    let ends = match self.pieces.get(i + 1) {
+                        Some(Piece::Literal(next)) => next.starts_with('/'),
+                        Some(Piece::Variable(_)) => false,
+                        None => true,
+                    };
+                    if !starts || !ends {
+                        return None;
+                    }
+                    route.push(':');
+                    route.push_str(name);
+                }
+                (Piece::Variable(name), RouteSyntax::Actix) => {
+                    route.push('{');
+                    route.push_str(name);
+                    route.push('}');
+                }
+            }
+        }
+        Some(route)
+    }
+}
+
+/// The syntax of the routes of a web framework, which
+/// [`PathTemplate::route`] writes templates in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RouteSyntax {
+    /// `/pets/:id`, as axum before 0.8 writes routes. Variables must span
+    /// whole segments.
+    Axum,
+    /// `/pets/{id}`, as actix-web, and axum since 0.8, write routes.
+    Actix,
+}
+
+impl fmt::Display for RouteSyntax {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            RouteSyntax::Axum => "axum",
+            RouteSyntax::Actix => "actix-web",
+        })
+    }
+}
+
+/// Matches `path` against `pieces`, pushing the values of their variables
+/// onto `values` in reverse.
+fn match_pieces<'t, 'p>(
+    pieces: &'t [Piece],
+    path: &'p str,
+    values: &mut Vec<(&'t str, &'p str)>,
+) -> bool {
+    match pieces.split_first() {
+        None => path.is_empty(),
+        Some((Piece::Literal(literal), rest)) => path
+            .strip_prefix(literal.as_str())
+            .is_some_and(|path| match_pieces(rest, path, values)),
+        Some((Piece::Variable(name), rest)) => {
+            let segment = path.find('/').unwrap_or(path.len());
+            if segment == 0 {
+                return false;
+            }
+            // The shortest value that lets the rest of the template match.
+            for (end, _) in path[..segment]
+                .char_indices()
+                .skip(1)
+                .chain(Some((segment, ' ')))
+            {
+                if match_pieces(rest, &path[end..], values) {
+                    values.push((name, &path[..end]));
+                    return true;
+                }
+            }
+            false
+        }
+    }
+}
+
+impl fmt::Display for PathTemplate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.template)
+    }
+}
+
+impl FromStr for PathTemplate {
+    type Err = ParsePathTemplateError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        PathTemplate::parse(s)
+    }
+}
+
+/// An error parsing a [`PathTemplate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsePathTemplateError {
+    pub template: String,
+    pub kind: PathTemplateErrorKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathTemplateErrorKind {
+    /// A `{` has no matching `}` within the same segment.
+    UnclosedBrace,
+    /// A `}` has no matching `{`.
+    UnopenedBrace,
+    /// A pair of braces holds no name.
+    EmptyVariable,
+}
+
+impl fmt::Display for ParsePathTemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let problem = match self.kind {
+            PathTemplateErrorKind::UnclosedBrace => "has an unclosed `{`",
+            PathTemplateErrorKind::UnopenedBrace => "has a `}` without a `{`",
+            PathTemplateErrorKind::EmptyVariable => "has a variable without a name",
+        };
+        write!(f, "path template `{}` {}", self.template, problem)
+    }
+}
+
+impl Error for ParsePathTemplateError {}
+
+/// A variable of a [`PathTemplate`] that was given no value to render.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingVariable(pub String);
+
+impl fmt::Display for MissingVariable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "no value for path template variable `{}`", self.0)
+    }
+}
+
+impl Error for MissingVariable {}
+
+/// An operation, by the route of its path in the syntax of a framework,
+/// as [`OpenAPI::routes`](crate::OpenAPI::routes) lists them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Route<'a> {
+    pub method: Method,
+    pub route: String,
+    pub operation_id: Option<&'a str>,
+}
+
+/// A path of the Paths Object that cannot be written as a route in the
+/// syntax of a framework, as it is not a valid template or uses variables
+/// the framework cannot express.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnroutablePath {
+    pub path: String,
+    pub syntax: RouteSyntax,
+}
+
+impl fmt::Display for UnroutablePath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "path `{}` cannot be written as a {} route",
+            self.path, self.syntax
+        )
+    }
+}
+
+impl Error for UnroutablePath {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_paths() {
+        let template = PathTemplate::parse("/files/{name}.{ext}").unwrap();
+        assert_eq!(
+            template.matches("/files/report.tar.gz"),
+            Some(vec![("name", "report"), ("ext", "tar.gz")])
+        );
+        assert_eq!(template.matches("/files/a/b.txt"), None);
+        assert_eq!(template.matches("/files/.txt"), None);
+        assert_eq!(template.variables().count(), 2);
+
+        let plain = PathTemplate::parse("/pets").unwrap();
+        assert_eq!(plain.matches("/pets"), Some(Vec::new()));
+        assert_eq!(plain.matches("/pets/"), None);
+        assert_eq!(plain.render(|_| None).unwrap(), "/pets",);
+        assert_eq!(
+            PathTemplate::parse("/pets/{id}")
+                .unwrap()
+                .render(|_| None)
+                .unwrap_err(),
+            MissingVariable("id".to_owned())
+        );
+    }
+
+    #[test]
+    fn writes_routes() {
+        let route = |template, syntax| PathTemplate::parse(template).unwrap().route(syntax);
+        assert_eq!(route("/{a}/{b}", RouteSyntax::Axum).unwrap(), "/:a/:b");
+        assert_eq!(route("/v{version}/pets", RouteSyntax::Axum), None);
+        assert_eq!(route("/{a}{b}", RouteSyntax::Axum), None);
+        assert_eq!(
+            route("/files/{name}.{ext}", RouteSyntax::Actix).unwrap(),
+            "/files/{name}.{ext}"
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_templates() {
+        let kind = |template| PathTemplate::parse(template).unwrap_err().kind;
+        assert_eq!(kind("/pets/{id"), PathTemplateErrorKind::UnclosedBrace);
+        assert_eq!(kind("/pets/{id/x}"), PathTemplateErrorKind::UnclosedBrace);
+        assert_eq!(kind("/pets/{{id}}"), PathTemplateErrorKind::UnclosedBrace);
+        assert_eq!(kind("/pets/id}"), PathTemplateErrorKind::UnopenedBrace);
+        assert_eq!(kind("/pets/{}"), PathTemplateErrorKind::EmptyVariable);
+        assert_eq!(
+            PathTemplate::parse("/pets/{}").unwrap_err().to_string(),
+            "path template `/pets/{}` has a variable without a name"
+        );
+    }
+}
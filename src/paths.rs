@@ -1,6 +1,7 @@
+use crate::reference::escape_pointer_segment;
 use crate::*;
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
-use std::collections::BTreeMap;
 
 /// Describes the operations available on a single path.
 /// A Path Item MAY be empty, due to ACL constraints.
@@ -39,10 +40,262 @@ pub struct PathItem {
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub parameters: Vec<ReferenceOr<Parameter>>,
+    /// Inline extensions to this object.
+    #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
+    pub extensions: IndexMap<String, serde_json::Value>,
+}
+
+impl PathItem {
+    /// The operation for `method`, if there is one.
+    pub fn operation(&self, method: Method) -> Option<&Operation> {
+        match method {
+            Method::Get => self.get.as_ref(),
+            Method::Put => self.put.as_ref(),
+            Method::Post => self.post.as_ref(),
+            Method::Delete => self.delete.as_ref(),
+            Method::Options => self.options.as_ref(),
+            Method::Head => self.head.as_ref(),
+            Method::Patch => self.patch.as_ref(),
+            Method::Trace => self.trace.as_ref(),
+        }
+    }
+
+    /// The field holding the operation for `method`.
+    pub fn operation_mut(&mut self, method: Method) -> &mut Option<Operation> {
+        match method {
+            Method::Get => &mut self.get,
+            Method::Put => &mut self.put,
+            Method::Post => &mut self.post,
+            Method::Delete => &mut self.delete,
+            Method::Options => &mut self.options,
+            Method::Head => &mut self.head,
+            Method::Patch => &mut self.patch,
+            Method::Trace => &mut self.trace,
+        }
+    }
+
+    /// The operations of the path item, each with its method, in the order
+    /// of [`Method::ALL`].
+    pub fn operations(&self) -> impl Iterator<Item = (Method, &Operation)> {
+        Method::ALL
+            .iter()
+            .filter_map(move |&method| self.operation(method).map(|operation| (method, operation)))
+    }
+
+    /// Like [`PathItem::operations`], for modification.
+    pub fn operations_mut(&mut self) -> impl Iterator<Item = (Method, &mut Operation)> {
+        vec![
+            (Method::Get, &mut self.get),
+            (Method::Put, &mut self.put),
+            (Method::Post, &mut self.post),
+            (Method::Delete, &mut self.delete),
+            (Method::Options, &mut self.options),
+            (Method::Head, &mut self.head),
+            (Method::Patch, &mut self.patch),
+            (Method::Trace, &mut self.trace),
+        ]
+        .into_iter()
+        .filter_map(|(method, operation)| operation.as_mut().map(|operation| (method, operation)))
+    }
+}
+
+impl OpenAPI {
+    /// Every operation of the document, with its path and method, in the
+    /// order of the paths. Path items given by reference are skipped.
+    ///
+    /// ```
+    /// use openapiv3::{Method, OpenAPI};
+    ///
+    /// let api: OpenAPI = serde_yaml::from_str(
+    ///     "
+    /// openapi: 3.0.0
+    /// info: {title: t, version: '1'}
+    /// paths:
+    ///   /pets: {get: {responses: {}}, post: {responses: {}}}
+    /// ",
+    /// )
+    /// .unwrap();
+    /// let operations: Vec<_> = api
+    ///     .operations()
+    ///     .map(|(path, method, _)| (path, method))
+    ///     .collect();
+    /// assert_eq!(operations, [("/pets", Method::Get), ("/pets", Method::Post)]);
+    /// ```
+    pub fn operations(&self) -> impl Iterator<Item = (&str, Method, &Operation)> {
+        self.paths.iter().flat_map(|(path, item)| {
+            let item = match item {
+                ReferenceOr::Item(item) => Some(item),
+                ReferenceOr::Reference { .. } => None,
+            };
+            item.into_iter().flat_map(move |item| {
+                item.operations()
+                    .map(move |(method, operation)| (path.as_str(), method, operation))
+            })
+        })
+    }
+
+    /// The method, route and `operationId` of each operation, with its path
+    /// written as a route of a web framework by [`PathTemplate::route`],
+    /// for registering a handler for each operation by its `operationId`.
+    ///
+    /// Fails with the first path the framework cannot express.
+    ///
+    /// ```
+    /// use openapiv3::{Method, OpenAPI, RouteSyntax};
+    ///
+    /// let api: OpenAPI = serde_yaml::from_str(
+    ///     "
+    /// openapi: 3.0.0
+    /// info: {title: t, version: '1'}
+    /// paths:
+    ///   /pets/{id}: {get: {operationId: showPet, responses: {}}, delete: {responses: {}}}
+    /// ",
+    /// )
+    /// .unwrap();
+    /// let routes: Vec<_> = api
+    ///     .routes(RouteSyntax::Axum)
+    ///     .unwrap()
+    ///     .into_iter()
+    ///     .map(|route| (route.method, route.route, route.operation_id))
+    ///     .collect();
+    /// assert_eq!(
+    ///     routes,
+    ///     [
+    ///         (Method::Get, "/pets/:id".to_owned(), Some("showPet")),
+    ///         (Method::Delete, "/pets/:id".to_owned(), None),
+    ///     ]
+    /// );
+    /// ```
+    pub fn routes(&self, syntax: RouteSyntax) -> Result<Vec<Route<'_>>, UnroutablePath> {
+        self.operations()
+            .map(|(path, method, operation)| {
+                let route = PathTemplate::parse(path)
+                    .ok()
+                    .and_then(|template| template.route(syntax))
+                    .ok_or_else(|| UnroutablePath {
+                        path: path.to_owned(),
+                        syntax,
+                    })?;
+                Ok(Route {
+                    method,
+                    route,
+                    operation_id: operation.operation_id.as_deref(),
+                })
+            })
+            .collect()
+    }
+
+    /// Like [`OpenAPI::operations`], for modification.
+    pub fn operations_mut(&mut self) -> impl Iterator<Item = (&str, Method, &mut Operation)> {
+        self.paths.iter_mut().flat_map(|(path, item)| {
+            let item = match item {
+                ReferenceOr::Item(item) => Some(item),
+                ReferenceOr::Reference { .. } => None,
+            };
+            item.into_iter().flat_map(move |item| {
+                item.operations_mut()
+                    .map(move |(method, operation)| (path.as_str(), method, operation))
+            })
+        })
+    }
+
+    /// Every operation of the callbacks of the document, nested ones
+    /// included, with a JSON Pointer to it and its method. Those of the
+    /// operations come first, in the order of the paths, then those of the
+    /// callbacks of the components. Callbacks and path items given by
+    /// reference are skipped.
+    ///
+    /// ```
+    /// use openapiv3::{Method, OpenAPI};
+    ///
+    /// let api: OpenAPI = serde_yaml::from_str(
+    ///     "
+    /// openapi: 3.0.0
+    /// info: {title: t, version: '1'}
+    /// paths:
+    ///   /streams:
+    ///     post:
+    ///       responses: {}
+    ///       callbacks:
+    ///         onData: {'{$request.query.url}': {post: {responses: {}}}}
+    /// ",
+    /// )
+    /// .unwrap();
+    /// let operations: Vec<_> = api
+    ///     .callback_operations()
+    ///     .map(|(location, method, _)| (location, method))
+    ///     .collect();
+    /// assert_eq!(
+    ///     operations,
+    ///     [(
+    ///         "/paths/~1streams/post/callbacks/onData/{$request.query.url}/post".to_owned(),
+    ///         Method::Post
+    ///     )]
+    /// );
+    /// ```
+    pub fn callback_operations(&self) -> impl Iterator<Item = (String, Method, &Operation)> {
+        let mut found = Vec::new();
+        for (path, method, operation) in self.operations() {
+            let location = format!(
+                "/paths/{}/{}/callbacks",
+                escape_pointer_segment(path),
+                method.as_str()
+            );
+            callback_operations(&operation.callbacks, &location, &mut found);
+        }
+        if let Some(components) = &self.components {
+            callback_operations(&components.callbacks, "/components/callbacks", &mut found);
+        }
+        found.into_iter()
+    }
+}
+
+/// Pushes the operations of `callbacks`, which are at `location`, and of
+/// their own callbacks onto `found`.
+fn callback_operations<'a>(
+    callbacks: impl IntoIterator<Item = (&'a String, &'a ReferenceOr<Callback>)>,
+    location: &str,
+    found: &mut Vec<(String, Method, &'a Operation)>,
+) {
+    for (name, callback) in callbacks {
+        let callback = match callback {
+            ReferenceOr::Item(callback) => callback,
+            ReferenceOr::Reference { .. } => continue,
+        };
+        for (expression, item) in callback {
+            for (method, operation) in item.operations() {
+                let at = format!(
+                    "{}/{}/{}/{}",
+                    location,
+                    escape_pointer_segment(name),
+                    escape_pointer_segment(expression),
+                    method.as_str()
+                );
+                let nested = format!("{}/callbacks", at);
+                found.push((at, method, operation));
+                callback_operations(&operation.callbacks, &nested, found);
+            }
+        }
+    }
 }
 
 /// Holds the relative paths to the individual endpoints and
 /// their operations. The path is appended to the URL from the
 /// Server Object in order to construct the full URL. The Paths
 /// MAY be empty, due to ACL constraints.
-pub type Paths = BTreeMap<String, ReferenceOr<PathItem>>;
+///
+/// Dereferences to the map of path items, so that it can be used as one.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct Paths {
+    /// The path items, by path.
+    #[serde(
+        flatten,
+        deserialize_with = "crate::util::deserialize_without_extensions"
+    )]
+    pub paths: Map<String, ReferenceOr<PathItem>>,
+    /// Inline extensions to this object.
+    #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
+    pub extensions: IndexMap<String, serde_json::Value>,
+}
+
+crate::util::entries_of!(Paths, paths: Map<String, ReferenceOr<PathItem>>, ReferenceOr<PathItem>);
@@ -0,0 +1,382 @@
+use crate::reference::unescape_pointer_segment;
+use crate::util::percent_decode;
+use crate::*;
+
+/// A borrowed node of an OpenAPI document, as found by [`OpenAPI::lookup`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AnyObject<'a> {
+    OpenAPI(&'a OpenAPI),
+    Info(&'a Info),
+    Contact(&'a Contact),
+    License(&'a License),
+    Server(&'a Server),
+    ServerVariable(&'a ServerVariable),
+    Paths(&'a Paths),
+    PathItem(&'a PathItem),
+    Operation(&'a Operation),
+    Parameter(&'a Parameter),
+    RequestBody(&'a RequestBody),
+    MediaType(&'a MediaType),
+    Encoding(&'a Encoding),
+    Responses(&'a Responses),
+    Response(&'a Response),
+    Header(&'a Header),
+    Example(&'a Example),
+    Link(&'a Link),
+    Callback(&'a Callback),
+    Schema(&'a Schema),
+    Discriminator(&'a Discriminator),
+    Components(&'a Components),
+    SecurityScheme(&'a SecurityScheme),
+    SecurityRequirement(&'a SecurityRequirement),
+    Tag(&'a Tag),
+    ExternalDocumentation(&'a ExternalDocumentation),
+    /// A `$ref` that was not followed.
+    Reference(&'a Reference),
+    /// A literal value, such as an example or a default.
+    Value(&'a serde_json::Value),
+    /// A string of a list, such as one of the tags of an operation.
+    Str(&'a str),
+}
+
+impl OpenAPI {
+    /// Finds the node a JSON Pointer refers to within this document, such as
+    /// `#/paths/~1pets/get/responses/200`. The leading `#` is optional;
+    /// when present, the pointer is percent-decoded as a URI fragment.
+    ///
+    /// Pointers are matched against the serialized names of fields.
+    /// References are not followed: a pointer ending at a reference returns
+    /// [`AnyObject::Reference`], and one that continues past it returns `None`.
+    pub fn lookup(&self, pointer: &str) -> Option<AnyObject<'_>> {
+        let pointer = match pointer.strip_prefix('#') {
+            Some(fragment) => percent_decode(fragment)?,
+            None => pointer.to_owned(),
+        };
+        let mut node = AnyObject::OpenAPI(self);
+        if pointer.is_empty() {
+            return Some(node);
+        }
+        let mut segments = pointer
+            .strip_prefix('/')?
+            .split('/')
+            .map(unescape_pointer_segment);
+        while let Some(segment) = segments.next() {
+            node = node.child(&segment, &mut segments)?;
+        }
+        Some(node)
+    }
+}
+
+fn reference_or<'a, T>(
+    value: &'a ReferenceOr<T>,
+    item: fn(&'a T) -> AnyObject<'a>,
+) -> AnyObject<'a> {
+    match value {
+        ReferenceOr::Reference { reference } => AnyObject::Reference(reference),
+        ReferenceOr::Item(value) => item(value),
+    }
+}
+
+fn schema(value: &ReferenceOr<Schema>) -> AnyObject<'_> {
+    reference_or(value, AnyObject::Schema)
+}
+
+fn boxed_schema(value: &ReferenceOr<Box<Schema>>) -> AnyObject<'_> {
+    match value {
+        ReferenceOr::Reference { reference } => AnyObject::Reference(reference),
+        ReferenceOr::Item(value) => AnyObject::Schema(value),
+    }
+}
+
+impl<'a> AnyObject<'a> {
+    /// Returns the child node named by `segment`. Nodes holding lists or
+    /// maps take the index or key from the following segment.
+    fn child<I>(self, segment: &str, rest: &mut I) -> Option<AnyObject<'a>>
+    where
+        I: Iterator<Item = String>,
+    {
+        use AnyObject as A;
+        let mut next = || rest.next();
+        let mut index = || next()?.parse::<usize>().ok();
+        Some(match self {
+            A::OpenAPI(api) => match segment {
+                "info" => A::Info(&api.info),
+                "servers" => A::Server(api.servers.get(index()?)?),
+                "paths" => A::Paths(&api.paths),
+                "components" => A::Components(api.components.as_ref()?),
+                "security" => A::SecurityRequirement(api.security.get(index()?)?),
+                "tags" => A::Tag(api.tags.get(index()?)?),
+                "externalDocs" => A::ExternalDocumentation(api.external_docs.as_ref()?),
+                _ => return None,
+            },
+            A::Info(info) => match segment {
+                "contact" => A::Contact(info.contact.as_ref()?),
+                "license" => A::License(info.license.as_ref()?),
+                _ => return None,
+            },
+            A::Server(server) => match segment {
+                "variables" => A::ServerVariable(server.variables.as_ref()?.get(&next()?)?),
+                _ => return None,
+            },
+            A::Paths(paths) => reference_or(paths.get(segment)?, A::PathItem),
+            A::PathItem(item) => match segment {
+                "servers" => A::Server(item.servers.get(index()?)?),
+                "parameters" => reference_or(item.parameters.get(index()?)?, A::Parameter),
+                method => A::Operation(
+                    match method {
+                        "get" => &item.get,
+                        "put" => &item.put,
+                        "post" => &item.post,
+                        "delete" => &item.delete,
+                        "options" => &item.options,
+                        "head" => &item.head,
+                        "patch" => &item.patch,
+                        "trace" => &item.trace,
+                        _ => return None,
+                    }
+                    .as_ref()?,
+                ),
+            },
+            A::Operation(operation) => match segment {
+                "tags" => A::Str(operation.tags.get(index()?)?),
+                "externalDocumentation" => {
+                    A::ExternalDocumentation(operation.external_documentation.as_ref()?)
+                }
+                "parameters" => reference_or(operation.parameters.get(index()?)?, A::Parameter),
+                "requestBody" => reference_or(operation.request_body.as_ref()?, A::RequestBody),
+                "responses" => A::Responses(&operation.responses),
+                "callbacks" => reference_or(operation.callbacks.get(&next()?)?, A::Callback),
+                "security" => A::SecurityRequirement(operation.security.as_ref()?.get(index()?)?),
+                "servers" => A::Server(operation.servers.get(index()?)?),
+                _ => return None,
+            },
+            A::Responses(responses) => match segment {
+                "default" => reference_or(responses.default.as_ref()?, A::Response),
+                code => {
+                    let (_, response) = responses
+                        .responses
+                        .iter()
+                        .find(|(status, _)| status.to_string().eq_ignore_ascii_case(code))?;
+                    reference_or(response, A::Response)
+                }
+            },
+            A::Response(response) => match segment {
+                "headers" => reference_or(response.headers.get(&next()?)?, A::Header),
+                "content" => reference_or(response.content.get(&next()?.into())?, A::MediaType),
+                "links" => reference_or(response.links.get(&next()?)?, A::Link),
+                _ => return None,
+            },
+            A::RequestBody(body) => match segment {
+                "content" => A::MediaType(body.content.get(&next()?.into())?),
+                _ => return None,
+            },
+            A::MediaType(media_type) => match segment {
+                "schema" => schema(media_type.schema.as_ref()?),
+                "example" => A::Value(media_type.example.as_ref()?),
+                "examples" => reference_or(media_type.examples.get(&next()?)?, A::Example),
+                "encoding" => A::Encoding(media_type.encoding.get(&next()?)?),
+                _ => return None,
+            },
+            A::Encoding(encoding) => match segment {
+                "headers" => reference_or(encoding.headers.get(&next()?)?, A::Header),
+                _ => return None,
+            },
+            A::Parameter(parameter) => {
+                let data = parameter.parameter_data_ref();
+                match segment {
+                    "example" => A::Value(data.example.as_ref()?),
+                    "examples" => reference_or(data.examples.get(&next()?)?, A::Example),
+                    _ => format_child(&data.format, segment, next)?,
+                }
+            }
+            A::Header(header) => match segment {
+                "example" => A::Value(header.example.as_ref()?),
+                "examples" => reference_or(header.examples.get(&next()?)?, A::Example),
+                _ => format_child(&header.format, segment, next)?,
+            },
+            A::Components(components) => {
+                let name = next()?;
+                match segment {
+                    "schemas" => schema(components.schemas.get(&name)?),
+                    "responses" => reference_or(components.responses.get(&name)?, A::Response),
+                    "parameters" => reference_or(components.parameters.get(&name)?, A::Parameter),
+                    "examples" => reference_or(components.examples.get(&name)?, A::Example),
+                    "requestBodies" => {
+                        reference_or(components.request_bodies.get(&name)?, A::RequestBody)
+                    }
+                    "headers" => reference_or(components.headers.get(&name)?, A::Header),
+                    "securitySchemes" => {
+                        reference_or(components.security_schemes.get(&name)?, A::SecurityScheme)
+                    }
+                    "links" => reference_or(components.links.get(&name)?, A::Link),
+                    "callbacks" => reference_or(components.callbacks.get(&name)?, A::Callback),
+                    _ => return None,
+                }
+            }
+            A::Callback(callback) => A::PathItem(callback.get(segment)?),
+            A::Schema(s) => match segment {
+                "properties" => {
+                    let properties = match &s.schema_kind {
+                        SchemaKind::Type(Type::Object(object)) => &object.properties,
+                        SchemaKind::Any(any) => &any.properties,
+                        _ => return None,
+                    };
+                    boxed_schema(properties.get(next()?.as_str())?)
+                }
+                "items" => match &s.schema_kind {
+                    SchemaKind::Type(Type::Array(array)) => boxed_schema(&array.items),
+                    SchemaKind::Any(any) => boxed_schema(any.items.as_ref()?),
+                    _ => return None,
+                },
+                "additionalProperties" => {
+                    let additional = match &s.schema_kind {
+                        SchemaKind::Type(Type::Object(object)) => &object.additional_properties,
+                        SchemaKind::Any(any) => &any.additional_properties,
+                        _ => return None,
+                    };
+                    match additional.as_ref()? {
+                        AdditionalProperties::Schema(s) => schema(s),
+                        AdditionalProperties::Any | AdditionalProperties::None => return None,
+                    }
+                }
+                "allOf" | "oneOf" | "anyOf" => {
+                    let schemas = match (&s.schema_kind, segment) {
+                        (SchemaKind::AllOf { all_of }, "allOf") => all_of,
+                        (SchemaKind::OneOf { one_of }, "oneOf") => one_of,
+                        (SchemaKind::AnyOf { any_of }, "anyOf") => any_of,
+                        _ => return None,
+                    };
+                    schema(schemas.get(index()?)?)
+                }
+                "discriminator" => A::Discriminator(s.schema_data.discriminator.as_ref()?),
+                "externalDocs" => A::ExternalDocumentation(s.schema_data.external_docs.as_ref()?),
+                "example" => A::Value(s.schema_data.example.as_ref()?),
+                "default" => A::Value(s.schema_data.default.as_ref()?),
+                _ => return None,
+            },
+            A::Tag(tag) => match segment {
+                "externalDocs" => A::ExternalDocumentation(tag.external_docs.as_ref()?),
+                _ => return None,
+            },
+            A::Link(link) => match segment {
+                "server" => A::Server(link.server.as_ref()?),
+                _ => return None,
+            },
+            A::Value(value) => A::Value(match value {
+                serde_json::Value::Object(object) => object.get(segment)?,
+                serde_json::Value::Array(array) => array.get(segment.parse::<usize>().ok()?)?,
+                _ => return None,
+            }),
+            A::Contact(_)
+            | A::License(_)
+            | A::ServerVariable(_)
+            | A::Example(_)
+            | A::Discriminator(_)
+            | A::SecurityScheme(_)
+            | A::SecurityRequirement(_)
+            | A::ExternalDocumentation(_)
+            | A::Reference(_)
+            | A::Str(_) => return None,
+        })
+    }
+}
+
+/// Looks up `schema` or `content/{media type}` in a parameter or header.
+fn format_child<'a, F>(
+    format: &'a ParameterSchemaOrContent,
+    segment: &str,
+    next: F,
+) -> Option<AnyObject<'a>>
+where
+    F: FnOnce() -> Option<String>,
+{
+    match (format, segment) {
+        (ParameterSchemaOrContent::Schema(s), "schema") => Some(schema(s)),
+        (ParameterSchemaOrContent::Content(content), "content") => {
+            content.get(&next()?.into()).map(AnyObject::MediaType)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn looks_up_nested_nodes() {
+        let api: OpenAPI = serde_yaml::from_str(
+            "
+openapi: 3.0.0
+info: {title: t, version: '1'}
+paths:
+  /pets/{id}:
+    get:
+      tags: [pets]
+      parameters:
+        - {name: id, in: path, required: true, schema: {type: string}}
+      callbacks:
+        onChange:
+          '{$request.body#/url}':
+            post: {responses: {'204': {description: ok}}}
+      responses:
+        200:
+          description: ok
+          content:
+            application/json:
+              schema: {$ref: '#/components/schemas/Pet'}
+        4XX: {$ref: '#/components/responses/Error'}
+",
+        )
+        .unwrap();
+
+        let item = match &api.paths["/pets/{id}"] {
+            ReferenceOr::Item(item) => item,
+            _ => unreachable!(),
+        };
+        let operation = item.get.as_ref().unwrap();
+        assert_eq!(
+            api.lookup("#/paths/~1pets~1%7Bid%7D/get"),
+            Some(AnyObject::Operation(operation))
+        );
+        assert_eq!(
+            api.lookup("/paths/~1pets~1{id}/get/responses/200/content/application~1json/schema"),
+            Some(AnyObject::Reference(
+                &"#/components/schemas/Pet".parse().unwrap()
+            ))
+        );
+        assert_eq!(
+            api.lookup("/paths/~1pets~1{id}/get/responses/4xx"),
+            Some(AnyObject::Reference(
+                &"#/components/responses/Error".parse().unwrap()
+            ))
+        );
+        match api.lookup("/paths/~1pets~1{id}/get/parameters/0/schema") {
+            Some(AnyObject::Schema(schema)) => assert_eq!(
+                schema.schema_kind,
+                SchemaKind::Type(Type::String(Default::default()))
+            ),
+            other => panic!("unexpected {:?}", other),
+        }
+        assert_eq!(api.lookup("/paths/~1pets~1{id}/get/parameters/1"), None);
+        assert_eq!(
+            api.lookup("/paths/~1pets~1{id}/get/tags/0"),
+            Some(AnyObject::Str("pets"))
+        );
+        let callback = match &operation.callbacks["onChange"] {
+            ReferenceOr::Item(callback) => callback,
+            _ => unreachable!(),
+        };
+        assert_eq!(
+            api.lookup("/paths/~1pets~1{id}/get/callbacks/onChange"),
+            Some(AnyObject::Callback(callback))
+        );
+        assert_eq!(
+            api.lookup("/paths/~1pets~1{id}/get/callbacks/onChange/{$request.body#~1url}/post"),
+            Some(AnyObject::Operation(
+                callback["{$request.body#/url}"].post.as_ref().unwrap()
+            ))
+        );
+        assert_eq!(api.lookup("/info/license"), None);
+    }
+}
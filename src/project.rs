@@ -0,0 +1,201 @@
+use crate::*;
+
+/// Which way a value is sent: by clients in requests, or by servers in
+/// responses.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum Direction {
+    Request,
+    Response,
+}
+
+impl Direction {
+    /// Whether a property with the annotations is left out of values sent
+    /// this way: `readOnly` ones of requests, `writeOnly` ones of
+    /// responses.
+    fn omits(self, data: &SchemaData) -> bool {
+        match self {
+            Direction::Request => data.read_only,
+            Direction::Response => data.write_only,
+        }
+    }
+}
+
+impl Schema {
+    /// The schema of values sent in `direction`, without the properties
+    /// that are `readOnly` for requests or `writeOnly` for responses, which
+    /// are no longer required either.
+    ///
+    /// Properties are left out wherever they are declared: in nested
+    /// objects and arrays, `additionalProperties` and the branches of
+    /// `allOf`, `oneOf` and `anyOf`. References are kept as they are, and
+    /// so only properties whose own schema, rather than one referred to,
+    /// says they are `readOnly` or `writeOnly` are left out.
+    ///
+    /// ```
+    /// use openapiv3::{Direction, Schema};
+    ///
+    /// let pet: Schema = serde_yaml::from_str(
+    ///     "
+    /// type: object
+    /// required: [id, name]
+    /// properties:
+    ///   id: {type: integer, readOnly: true}
+    ///   name: {type: string}
+    ///   password: {type: string, writeOnly: true}
+    /// ",
+    /// )
+    /// .unwrap();
+    /// let request: Schema = serde_yaml::from_str(
+    ///     "
+    /// type: object
+    /// required: [name]
+    /// properties:
+    ///   name: {type: string}
+    ///   password: {type: string, writeOnly: true}
+    /// ",
+    /// )
+    /// .unwrap();
+    /// assert_eq!(pet.project(Direction::Request), request);
+    /// ```
+    pub fn project(&self, direction: Direction) -> Schema {
+        let mut schema = self.clone();
+        project(&mut schema, direction);
+        schema
+    }
+}
+
+fn project(schema: &mut Schema, direction: Direction) {
+    match &mut schema.schema_kind {
+        SchemaKind::Type(Type::Object(object)) => project_object(
+            &mut object.properties,
+            &mut object.required,
+            object.additional_properties.as_mut(),
+            direction,
+        ),
+        SchemaKind::Type(Type::Array(array)) => project_boxed(&mut array.items, direction),
+        SchemaKind::OneOf { one_of: branches }
+        | SchemaKind::AllOf { all_of: branches }
+        | SchemaKind::AnyOf { any_of: branches } => {
+            for branch in branches {
+                if let ReferenceOr::Item(branch) = branch {
+                    project(branch, direction);
+                }
+            }
+        }
+        SchemaKind::Any(any) => {
+            project_object(
+                &mut any.properties,
+                &mut any.required,
+                any.additional_properties.as_mut(),
+                direction,
+            );
+            if let Some(items) = &mut any.items {
+                project_boxed(items, direction);
+            }
+        }
+        SchemaKind::Type(_) => {}
+    }
+}
+
+fn project_boxed(schema: &mut ReferenceOr<Box<Schema>>, direction: Direction) {
+    if let ReferenceOr::Item(schema) = schema {
+        project(schema, direction);
+    }
+}
+
+fn project_object(
+    properties: &mut Map<Name, ReferenceOr<Box<Schema>>>,
+    required: &mut Vec<Name>,
+    additional_properties: Option<&mut AdditionalProperties>,
+    direction: Direction,
+) {
+    properties.retain(|name, property| match property {
+        ReferenceOr::Item(schema) if direction.omits(&schema.schema_data) => {
+            required.retain(|required| required != name);
+            false
+        }
+        _ => true,
+    });
+    for property in properties.values_mut() {
+        project_boxed(property, direction);
+    }
+    if let Some(AdditionalProperties::Schema(schema)) = additional_properties {
+        if let ReferenceOr::Item(schema) = schema.as_mut() {
+            project(schema, direction);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn projects_schemas() {
+        let schema: Schema = serde_yaml::from_str(
+            "
+allOf:
+  - $ref: '#/components/schemas/Base'
+  - type: object
+    required: [created, secret]
+    properties:
+      created: {type: string, readOnly: true}
+      secret: {type: string, writeOnly: true}
+      pets:
+        type: array
+        items:
+          oneOf:
+            - type: object
+              properties: {id: {type: integer, readOnly: true}}
+      labels:
+        type: object
+        additionalProperties:
+          properties: {token: {type: string, writeOnly: true}, text: {type: string}}
+",
+        )
+        .unwrap();
+        let request: Schema = serde_yaml::from_str(
+            "
+allOf:
+  - $ref: '#/components/schemas/Base'
+  - type: object
+    required: [secret]
+    properties:
+      secret: {type: string, writeOnly: true}
+      pets:
+        type: array
+        items:
+          oneOf:
+            - type: object
+      labels:
+        type: object
+        additionalProperties:
+          properties: {token: {type: string, writeOnly: true}, text: {type: string}}
+",
+        )
+        .unwrap();
+        let response: Schema = serde_yaml::from_str(
+            "
+allOf:
+  - $ref: '#/components/schemas/Base'
+  - type: object
+    required: [created]
+    properties:
+      created: {type: string, readOnly: true}
+      pets:
+        type: array
+        items:
+          oneOf:
+            - type: object
+              properties: {id: {type: integer, readOnly: true}}
+      labels:
+        type: object
+        additionalProperties:
+          properties: {text: {type: string}}
+",
+        )
+        .unwrap();
+        assert_eq!(schema.project(Direction::Request), request);
+        assert_eq!(schema.project(Direction::Response), response);
+    }
+}
@@ -0,0 +1,223 @@
+use crate::*;
+use serde::de::DeserializeOwned;
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Read};
+
+/// A document of any version this crate can read, as returned by
+/// [`from_reader`] and [`from_str`].
+#[allow(clippy::large_enum_variant)]
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq)]
+pub enum VersionedDocument {
+    /// A Swagger 2.0 document.
+    #[cfg(feature = "swagger2")]
+    Swagger2(swagger2::Swagger),
+    /// An OpenAPI 3.0.x document.
+    V3_0(OpenAPI),
+    /// An OpenAPI 3.1.x document.
+    V3_1(v3_1::OpenAPI),
+}
+
+impl From<VersionedOpenAPI> for VersionedDocument {
+    fn from(api: VersionedOpenAPI) -> Self {
+        match api {
+            VersionedOpenAPI::V3_0(api) => VersionedDocument::V3_0(api),
+            VersionedOpenAPI::V3_1(api) => VersionedDocument::V3_1(api),
+        }
+    }
+}
+
+/// An error reading a document with [`from_reader`] or [`from_str`].
+#[derive(Debug)]
+pub enum ReadError {
+    Io(io::Error),
+    Json(serde_json::Error),
+    Yaml(serde_yaml::Error),
+    /// The document has neither an `openapi` nor a `swagger` field.
+    MissingVersion,
+    /// The document is of a version this crate cannot read, or whose
+    /// support is not enabled.
+    UnsupportedVersion(String),
+    /// The document has fields that are not part of the specification;
+    /// see [`OpenAPI::from_str_strict`].
+    UnknownFields(Vec<UnknownField>),
+    /// The document does not match the specification; see [`parse`].
+    Parse(ParseError),
+    /// The document is larger than allowed; see [`ReadLimits`].
+    Limit(LimitExceeded),
+}
+
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReadError::Io(e) => write!(f, "failed to read document: {}", e),
+            ReadError::Json(e) => write!(f, "invalid JSON document: {}", e),
+            ReadError::Yaml(e) => write!(f, "invalid YAML document: {}", e),
+            ReadError::MissingVersion => {
+                f.write_str("document has neither an `openapi` nor a `swagger` field")
+            }
+            ReadError::UnsupportedVersion(version) => {
+                write!(f, "unsupported specification version `{}`", version)
+            }
+            ReadError::UnknownFields(fields) => {
+                f.write_str("document has unknown fields:")?;
+                for field in fields {
+                    write!(f, " {}", field.location)?;
+                }
+                Ok(())
+            }
+            ReadError::Parse(e) => write!(f, "invalid document: {}", e),
+            ReadError::Limit(e) => write!(f, "document too large: {}", e),
+        }
+    }
+}
+
+impl Error for ReadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ReadError::Io(e) => Some(e),
+            ReadError::Json(e) => Some(e),
+            ReadError::Yaml(e) => Some(e),
+            ReadError::Limit(e) => Some(e),
+            ReadError::MissingVersion
+            | ReadError::UnsupportedVersion(_)
+            | ReadError::UnknownFields(_)
+            | ReadError::Parse(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for ReadError {
+    fn from(e: io::Error) -> Self {
+        ReadError::Io(e)
+    }
+}
+
+/// Reads a JSON or YAML document of any supported version; see [`from_str`].
+pub fn from_reader<R: Read>(mut reader: R) -> Result<VersionedDocument, ReadError> {
+    let mut text = String::new();
+    reader.read_to_string(&mut text)?;
+    from_str(&text)
+}
+
+/// Reads a JSON or YAML document of any supported version, told apart by
+/// its `openapi` or `swagger` field.
+///
+/// A document is read as JSON if it starts with `{`, and as YAML otherwise.
+/// Swagger 2.0 documents are only read with the `swagger2` feature.
+///
+/// ```
+/// let doc = openapiv3::from_str(r#"{
+///     "openapi": "3.1.0",
+///     "info": {"title": "Pets", "version": "1"}
+/// }"#)
+/// .unwrap();
+/// assert!(matches!(doc, openapiv3::VersionedDocument::V3_1(_)));
+/// ```
+pub fn from_str(text: &str) -> Result<VersionedDocument, ReadError> {
+    let format = Format::of(text);
+    let version = |value: &serde_yaml::Value, field: &str| {
+        value.get(field).map(|version| match version {
+            serde_yaml::Value::String(version) => version.clone(),
+            other => serde_yaml::to_string(other)
+                .unwrap_or_default()
+                .trim_start_matches("---")
+                .trim()
+                .to_owned(),
+        })
+    };
+    let value: serde_yaml::Value = format.parse(text)?;
+
+    if let Some(version) = version(&value, "openapi") {
+        return if version.starts_with("3.0.") {
+            format.parse(text).map(VersionedDocument::V3_0)
+        } else if version.starts_with("3.1.") {
+            format.parse(text).map(VersionedDocument::V3_1)
+        } else {
+            Err(ReadError::UnsupportedVersion(version))
+        };
+    }
+    match version(&value, "swagger") {
+        #[cfg(feature = "swagger2")]
+        Some(version) if version == "2.0" => format.parse(text).map(VersionedDocument::Swagger2),
+        Some(version) => Err(ReadError::UnsupportedVersion(version)),
+        None => Err(ReadError::MissingVersion),
+    }
+}
+
+/// Reads a JSON or YAML document as `T`.
+///
+/// Where the document does not match `T`, the error names the value at
+/// fault and its position, rather than only the object holding it:
+///
+/// ```
+/// let error = openapiv3::parse::<openapiv3::OpenAPI>(
+///     "openapi: 3.0.0\ninfo: {title: t, version: '1'}\npaths:\n  /pets:\n    get: {}\n",
+/// )
+/// .unwrap_err();
+/// assert_eq!(
+///     error.to_string(),
+///     "invalid document: /paths/~1pets/get: missing field `responses` at line 5, column 5"
+/// );
+/// ```
+pub fn parse<T: DeserializeOwned>(text: &str) -> Result<T, ReadError> {
+    Format::of(text).parse(text)
+}
+
+#[derive(Clone, Copy)]
+pub(crate) enum Format {
+    Json,
+    Yaml,
+}
+
+impl Format {
+    /// JSON if the text starts with `{`, and YAML otherwise.
+    pub(crate) fn of(text: &str) -> Self {
+        if text.trim_start().starts_with('{') {
+            Format::Json
+        } else {
+            Format::Yaml
+        }
+    }
+
+    /// Deserializes `text`, locating the value at fault on failure.
+    pub(crate) fn parse<T: DeserializeOwned>(self, text: &str) -> Result<T, ReadError> {
+        let result = match self {
+            Format::Json => serde_json::from_str(text).map_err(ReadError::Json),
+            Format::Yaml => serde_yaml::from_str(text).map_err(ReadError::Yaml),
+        };
+        result.map_err(|e| crate::parse_error::locate_error::<T>(text).map_or(e, ReadError::Parse))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_versions_and_formats() {
+        let yaml = "openapi: 3.0.0\ninfo: {title: t, version: '1'}\npaths: {}\n";
+        assert!(matches!(
+            from_reader(yaml.as_bytes()).unwrap(),
+            VersionedDocument::V3_0(_)
+        ));
+
+        let json = r#"{"openapi": "3.1.0", "info": {"title": "t", "version": "1"}}"#;
+        assert!(matches!(
+            from_str(json).unwrap(),
+            VersionedDocument::V3_1(_)
+        ));
+
+        let error = from_str(r#"{"openapi": "3.0.0", "info": {"title": "t"}}"#).unwrap_err();
+        assert!(matches!(error, ReadError::Parse(_)));
+        assert!(matches!(
+            from_str("swagger: 1.2").unwrap_err(),
+            ReadError::UnsupportedVersion(version) if version == "1.2"
+        ));
+        assert!(matches!(
+            from_str("info: {}").unwrap_err(),
+            ReadError::MissingVersion
+        ));
+    }
+}
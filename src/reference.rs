@@ -1,24 +1,60 @@
-use serde::{Deserialize, Serialize};
+use crate::intern::{intern, Interned};
+use crate::*;
+use serde::de::value::{BorrowedStrDeserializer, MapAccessDeserializer, SeqAccessDeserializer};
+use serde::de::{
+    self, DeserializeSeed, Error as _, IgnoredAny, IntoDeserializer, MapAccess, SeqAccess, Visitor,
+};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::borrow::Cow;
+use std::error::Error;
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::Deref;
+use std::str::FromStr;
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
 #[serde(untagged)]
 pub enum ReferenceOr<T> {
     Reference {
         #[serde(rename = "$ref")]
-        reference: String,
+        reference: Reference,
     },
     Item(T),
 }
 
 impl<T> ReferenceOr<T> {
+    /// Creates a reference, without checking that `r` is valid; see
+    /// [`ReferenceOr::try_ref`].
     pub fn ref_(r: &str) -> Self {
         ReferenceOr::Reference {
-            reference: r.to_owned(),
+            reference: Reference { raw: intern(r) },
         }
     }
+
+    /// Creates a reference, if `r` is valid, see [`Reference`].
+    pub fn try_ref(r: &str) -> Result<Self, ParseReferenceError> {
+        Ok(ReferenceOr::Reference {
+            reference: r.parse()?,
+        })
+    }
+
     pub fn boxed_item(item: T) -> ReferenceOr<Box<T>> {
         ReferenceOr::Item(Box::new(item))
     }
+
+    /// Converts the item with `f`, keeping any reference as it is.
+    pub fn map<U, F: FnOnce(T) -> U>(self, f: F) -> ReferenceOr<U> {
+        match self {
+            ReferenceOr::Reference { reference } => ReferenceOr::Reference { reference },
+            ReferenceOr::Item(item) => ReferenceOr::Item(f(item)),
+        }
+    }
+}
+
+impl<T> From<T> for ReferenceOr<T> {
+    fn from(item: T) -> Self {
+        ReferenceOr::Item(item)
+    }
 }
 
 impl<T> ReferenceOr<Box<T>> {
@@ -29,3 +65,537 @@ impl<T> ReferenceOr<Box<T>> {
         }
     }
 }
+
+impl<'de, T> Deserialize<'de> for ReferenceOr<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        // Any object with a `$ref` is a reference, whatever else it holds,
+        // so that a malformed reference is reported as such rather than
+        // being read as an item that happens to have an unknown field.
+        if crate::parse_error::entering() {
+            return deserialize_tracked(deserializer);
+        }
+        deserializer.deserialize_any(ReferenceOrVisitor(PhantomData))
+    }
+}
+
+/// Reads a [`ReferenceOr`] as the error of a document is being located,
+/// through a buffer that goes on being tracked.
+fn deserialize_tracked<'de, T, D>(deserializer: D) -> Result<ReferenceOr<T>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    use serde_yaml::Value;
+
+    let mut value = Value::deserialize(deserializer)?;
+    stringify_keys(&mut value);
+    if let Value::Mapping(mapping) = &value {
+        if let Some(reference) = mapping.get(&Value::String("$ref".to_owned())) {
+            return match reference {
+                Value::String(reference) => Ok(ReferenceOr::Reference {
+                    reference: reference.parse().map_err(D::Error::custom)?,
+                }),
+                _ => Err(D::Error::custom("`$ref` must be a string")),
+            };
+        }
+    }
+    crate::parse_error::deserialize_tracked(&value)
+        .map(ReferenceOr::Item)
+        .map_err(D::Error::custom)
+}
+
+/// Hands the value straight to `T`, unless it is a mapping with a `$ref`.
+///
+/// A mapping is read by `T` as it goes, with its `$ref` held back; once
+/// the `$ref` is found, the rest of the mapping is skipped, and what `T`
+/// made of it is dropped.
+struct ReferenceOrVisitor<T>(PhantomData<T>);
+
+impl<'de, T: Deserialize<'de>> Visitor<'de> for ReferenceOrVisitor<T> {
+    type Value = ReferenceOr<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a reference or an item")
+    }
+
+    fn visit_bool<E: de::Error>(self, v: bool) -> Result<Self::Value, E> {
+        T::deserialize(v.into_deserializer()).map(ReferenceOr::Item)
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+        T::deserialize(v.into_deserializer()).map(ReferenceOr::Item)
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+        T::deserialize(v.into_deserializer()).map(ReferenceOr::Item)
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<Self::Value, E> {
+        T::deserialize(v.into_deserializer()).map(ReferenceOr::Item)
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        T::deserialize(v.into_deserializer()).map(ReferenceOr::Item)
+    }
+
+    fn visit_borrowed_str<E: de::Error>(self, v: &'de str) -> Result<Self::Value, E> {
+        T::deserialize(BorrowedStrDeserializer::new(v)).map(ReferenceOr::Item)
+    }
+
+    fn visit_string<E: de::Error>(self, v: String) -> Result<Self::Value, E> {
+        T::deserialize(v.into_deserializer()).map(ReferenceOr::Item)
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+        T::deserialize(().into_deserializer()).map(ReferenceOr::Item)
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+        self.visit_unit()
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        Deserialize::deserialize(deserializer)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<Self::Value, A::Error> {
+        T::deserialize(SeqAccessDeserializer::new(seq)).map(ReferenceOr::Item)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+        let mut map = RefHeldBack {
+            map,
+            reference: None,
+        };
+        let item = T::deserialize(MapAccessDeserializer::new(&mut map));
+        match map.reference {
+            Some(reference) => Ok(ReferenceOr::Reference { reference }),
+            None => item.map(ReferenceOr::Item),
+        }
+    }
+}
+
+/// The entries of a mapping but its `$ref`, which is kept aside.
+struct RefHeldBack<A> {
+    map: A,
+    reference: Option<Reference>,
+}
+
+impl<'de, A: MapAccess<'de>> MapAccess<'de> for RefHeldBack<A> {
+    type Error = A::Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, A::Error> {
+        if self.reference.is_some() {
+            return Ok(None);
+        }
+        let key = match self.map.next_key::<Key<'de>>()? {
+            Some(Key(key)) => key,
+            None => return Ok(None),
+        };
+        if key != "$ref" {
+            let key = match key {
+                Cow::Borrowed(key) => seed.deserialize(BorrowedStrDeserializer::new(key)),
+                Cow::Owned(key) => seed.deserialize(key.into_deserializer()),
+            };
+            return key.map(Some);
+        }
+        let reference = match self.map.next_value::<serde_yaml::Value>()? {
+            serde_yaml::Value::String(reference) => reference.parse(),
+            _ => return Err(A::Error::custom("`$ref` must be a string")),
+        };
+        self.reference = Some(reference.map_err(A::Error::custom)?);
+        while self.map.next_key::<IgnoredAny>()?.is_some() {
+            self.map.next_value::<IgnoredAny>()?;
+        }
+        Ok(None)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, A::Error> {
+        self.map.next_value_seed(seed)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.map.size_hint()
+    }
+}
+
+/// A key of a mapping, borrowed from the input where it can be.
+struct Key<'de>(Cow<'de, str>);
+
+impl<'de> Deserialize<'de> for Key<'de> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct KeyVisitor;
+
+        impl<'de> Visitor<'de> for KeyVisitor {
+            type Value = Key<'de>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a string")
+            }
+
+            fn visit_borrowed_str<E>(self, v: &'de str) -> Result<Key<'de>, E> {
+                Ok(Key(Cow::Borrowed(v)))
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Key<'de>, E> {
+                Ok(Key(Cow::Owned(v.to_owned())))
+            }
+
+            fn visit_string<E>(self, v: String) -> Result<Key<'de>, E> {
+                Ok(Key(Cow::Owned(v)))
+            }
+        }
+
+        deserializer.deserialize_str(KeyVisitor)
+    }
+}
+
+/// Turns the scalar keys of all mappings into strings, as YAML keys such
+/// as `200` would otherwise not be accepted when deserializing from a
+/// buffered value.
+pub(crate) fn stringify_keys(value: &mut serde_yaml::Value) {
+    use serde_yaml::Value;
+
+    match value {
+        Value::Mapping(mapping) => {
+            if mapping.iter().any(|(key, _)| !key.is_string()) {
+                *mapping = std::mem::take(mapping)
+                    .into_iter()
+                    .map(|(key, value)| match key {
+                        Value::Number(n) => (Value::String(n.to_string()), value),
+                        Value::Bool(b) => (Value::String(b.to_string()), value),
+                        key => (key, value),
+                    })
+                    .collect();
+            }
+            for (_, value) in mapping.iter_mut() {
+                stringify_keys(value);
+            }
+        }
+        Value::Sequence(sequence) => sequence.iter_mut().for_each(stringify_keys),
+        _ => {}
+    }
+}
+
+/// The value of a `$ref`: a URI reference to a document, optionally
+/// followed by `#` and a JSON Pointer to a node within it.
+///
+/// References within the current document have no document part, e.g.
+/// `#/components/schemas/Pet`; references to other documents do, e.g.
+/// `./common.yaml#/components/schemas/Error`.
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Reference {
+    raw: Interned,
+}
+
+impl Reference {
+    /// Creates a reference to a component of the current document.
+    pub fn component(kind: ComponentKind, name: &str) -> Reference {
+        Reference {
+            raw: intern(&format!(
+                "#/components/{}/{}",
+                kind,
+                escape_pointer_segment(name)
+            )),
+        }
+    }
+
+    /// An empty reference, for briefly taking the place of another.
+    pub(crate) fn placeholder() -> Reference {
+        Reference { raw: "".into() }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.raw
+    }
+
+    /// The location of the referenced document; empty for a reference
+    /// within the current document.
+    pub fn document(&self) -> &str {
+        match self.raw.find('#') {
+            Some(i) => &self.raw[..i],
+            None => &self.raw,
+        }
+    }
+
+    /// The JSON Pointer following the `#`, still escaped, if there is one.
+    pub fn fragment(&self) -> Option<&str> {
+        self.raw.find('#').map(|i| &self.raw[i + 1..])
+    }
+
+    /// Whether the reference points into another document.
+    pub fn is_external(&self) -> bool {
+        !self.document().is_empty()
+    }
+
+    /// The kind of component pointed to, when the fragment has the
+    /// form `/components/{kind}/{name}`.
+    pub fn component_kind(&self) -> Option<ComponentKind> {
+        self.component_parts().map(|(kind, _)| kind)
+    }
+
+    /// The (unescaped) name of the component pointed to, when the fragment
+    /// has the form `/components/{kind}/{name}`.
+    pub fn name(&self) -> Option<Cow<'_, str>> {
+        self.component_parts().map(|(_, name)| name)
+    }
+
+    fn component_parts(&self) -> Option<(ComponentKind, Cow<'_, str>)> {
+        let mut segments = self.fragment()?.split('/');
+        match (
+            segments.next(),
+            segments.next(),
+            segments.next(),
+            segments.next(),
+            segments.next(),
+        ) {
+            (Some(""), Some("components"), Some(kind), Some(name), None) if !name.is_empty() => {
+                let name = if name.contains('~') {
+                    Cow::Owned(unescape_pointer_segment(name))
+                } else {
+                    Cow::Borrowed(name)
+                };
+                Some((kind.parse().ok()?, name))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Escapes `~` and `/` so that a key can be used as a JSON Pointer segment.
+pub(crate) fn escape_pointer_segment(segment: &str) -> String {
+    segment.replace('~', "~0").replace('/', "~1")
+}
+
+/// Reverses the JSON Pointer escaping of `~` and `/` in a single segment.
+pub(crate) fn unescape_pointer_segment(segment: &str) -> String {
+    segment.replace("~1", "/").replace("~0", "~")
+}
+
+/// The reasons a string is not a valid [`Reference`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseReferenceError {
+    /// References may not contain whitespace or control characters.
+    InvalidCharacter(String),
+    /// The fragment is neither empty, a JSON Pointer nor a plain name, such
+    /// as the `Pet` of `#Pet`.
+    InvalidFragment(String),
+    /// A `~` in the fragment is not followed by `0` or `1`.
+    InvalidEscape(String),
+}
+
+impl fmt::Display for ParseReferenceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseReferenceError::InvalidCharacter(r) => {
+                write!(f, "invalid character in reference `{}`", r)
+            }
+            ParseReferenceError::InvalidFragment(r) => {
+                write!(
+                    f,
+                    "fragment of reference `{}` is neither a JSON Pointer nor a plain name",
+                    r
+                )
+            }
+            ParseReferenceError::InvalidEscape(r) => {
+                write!(f, "invalid `~` escape in reference `{}`", r)
+            }
+        }
+    }
+}
+
+impl Error for ParseReferenceError {}
+
+impl FromStr for Reference {
+    type Err = ParseReferenceError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.chars().any(|c| c.is_whitespace() || c.is_control()) {
+            return Err(ParseReferenceError::InvalidCharacter(s.to_owned()));
+        }
+        let reference = Reference { raw: intern(s) };
+        if let Some(fragment) = reference.fragment() {
+            if !fragment.is_empty() && !fragment.starts_with('/') {
+                if is_plain_name(fragment) {
+                    return Ok(reference);
+                }
+                return Err(ParseReferenceError::InvalidFragment(s.to_owned()));
+            }
+            let mut escapes = fragment.split('~').skip(1);
+            if escapes.any(|rest| !(rest.starts_with('0') || rest.starts_with('1'))) {
+                return Err(ParseReferenceError::InvalidEscape(s.to_owned()));
+            }
+        }
+        Ok(reference)
+    }
+}
+
+/// Whether `fragment` is a plain name, as of an `$anchor`: a letter or
+/// `_` followed by letters, digits, `-`, `.` or `_`.
+fn is_plain_name(fragment: &str) -> bool {
+    let mut chars = fragment.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '_'))
+}
+
+impl Deref for Reference {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.raw
+    }
+}
+
+impl AsRef<str> for Reference {
+    fn as_ref(&self) -> &str {
+        &self.raw
+    }
+}
+
+impl fmt::Display for Reference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.raw)
+    }
+}
+
+impl PartialEq<str> for Reference {
+    fn eq(&self, other: &str) -> bool {
+        &*self.raw == other
+    }
+}
+
+impl PartialEq<&str> for Reference {
+    fn eq(&self, other: &&str) -> bool {
+        &*self.raw == *other
+    }
+}
+
+impl From<Reference> for String {
+    fn from(reference: Reference) -> String {
+        crate::intern::into_string(reference.raw)
+    }
+}
+
+impl Serialize for Reference {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.raw)
+    }
+}
+
+impl<'de> Deserialize<'de> for Reference {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let raw = String::deserialize(deserializer)?;
+        raw.parse().map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn parses_parts() {
+        let reference: Reference = "common.yaml#/components/schemas/a~1b".parse().unwrap();
+        assert_eq!(reference.document(), "common.yaml");
+        assert_eq!(reference.fragment(), Some("/components/schemas/a~1b"));
+        assert!(reference.is_external());
+        assert_eq!(reference.component_kind(), Some(ComponentKind::Schemas));
+        assert_eq!(reference.name().as_deref(), Some("a/b"));
+
+        let reference = Reference::component(ComponentKind::RequestBodies, "Pet");
+        assert_eq!(reference, "#/components/requestBodies/Pet");
+        assert!(!reference.is_external());
+
+        let reference: Reference = "./pet.yaml".parse().unwrap();
+        assert_eq!(reference.fragment(), None);
+        assert_eq!(reference.component_kind(), None);
+
+        let reference: Reference = "pet.yaml#Pet".parse().unwrap();
+        assert_eq!(reference.fragment(), Some("Pet"));
+        assert_eq!(reference.component_kind(), None);
+    }
+
+    #[test]
+    fn rejects_malformed_references() {
+        assert_eq!(
+            "#/a b".parse::<Reference>(),
+            Err(ParseReferenceError::InvalidCharacter("#/a b".to_owned()))
+        );
+        assert!("#components/schemas/Pet".parse::<Reference>().is_err());
+        assert!("#/components/schemas/P~2t".parse::<Reference>().is_err());
+        assert!("#1Pet".parse::<Reference>().is_err());
+        assert!(ReferenceOr::<Schema>::try_ref("#/a b").is_err());
+        match ReferenceOr::<Schema>::ref_("#/a b") {
+            ReferenceOr::Reference { reference } => assert_eq!(reference, "#/a b"),
+            ReferenceOr::Item(_) => panic!("not a reference"),
+        }
+
+        let error = serde_yaml::from_str::<ReferenceOr<Schema>>("{$ref: '#P=t', type: string}")
+            .unwrap_err();
+        assert!(
+            error.to_string().contains("neither a JSON Pointer"),
+            "{}",
+            error
+        );
+
+        let error = serde_yaml::from_str::<ReferenceOr<Response>>("{content: {}}").unwrap_err();
+        assert!(
+            error.to_string().contains("missing field `description`"),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn reads_references_anywhere_in_a_mapping() {
+        let schema: ReferenceOr<Schema> = serde_json::from_str(
+            r##"{"description": "A pet.", "$ref": "#/components/schemas/Pet", "type": 1}"##,
+        )
+        .unwrap();
+        assert_eq!(schema, ReferenceOr::ref_("#/components/schemas/Pet"));
+
+        let response: ReferenceOr<Response> =
+            serde_yaml::from_str("{$ref: '#/components/responses/NotFound'}").unwrap();
+        assert_eq!(
+            response,
+            ReferenceOr::ref_("#/components/responses/NotFound")
+        );
+
+        let schema: ReferenceOr<Schema> =
+            serde_json::from_str(r#"{"type": "string", "description": "A name."}"#).unwrap();
+        match schema {
+            ReferenceOr::Item(schema) => {
+                assert_eq!(schema.schema_data.description.as_deref(), Some("A name."))
+            }
+            ReferenceOr::Reference { .. } => panic!("not an item"),
+        }
+
+        let error = serde_json::from_str::<ReferenceOr<Schema>>(r#"{"$ref": 1}"#).unwrap_err();
+        assert!(error.to_string().contains("must be a string"), "{}", error);
+    }
+
+    #[cfg(feature = "intern")]
+    #[test]
+    fn shares_interned_references() {
+        let a: Reference = "#/components/schemas/Pet".parse().unwrap();
+        let b = Reference::component(ComponentKind::Schemas, "Pet");
+        assert!(std::sync::Arc::ptr_eq(&a.raw, &b.raw));
+    }
+}
@@ -0,0 +1,364 @@
+use crate::reference::unescape_pointer_segment;
+use crate::visit::VisitMut;
+use crate::*;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+
+/// Why [`OpenAPI::rename_component`] renamed nothing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RenameError {
+    /// There is no component by the old name.
+    NotFound(Reference),
+    /// There already is a component by the new name.
+    Exists(Reference),
+}
+
+impl fmt::Display for RenameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RenameError::NotFound(r) => write!(f, "there is no component `{}`", r),
+            RenameError::Exists(r) => write!(f, "there already is a component `{}`", r),
+        }
+    }
+}
+
+impl Error for RenameError {}
+
+impl OpenAPI {
+    /// Renames the component `old` of the given kind to `new`, keeping its
+    /// place among the others, and rewrites what refers to it: every
+    /// `$ref` to it or into it, those in callbacks, links and the
+    /// components included, along with the targets of the `mapping` of
+    /// discriminators, which may also name schemas, the `operationRef` of
+    /// links, and, for a security scheme, the requirements naming it.
+    ///
+    /// Returns the number of references rewritten. References to other
+    /// documents are left as they are.
+    ///
+    /// ```
+    /// use openapiv3::{ComponentKind, OpenAPI};
+    ///
+    /// let mut api: OpenAPI = serde_yaml::from_str(
+    ///     "
+    /// openapi: 3.0.0
+    /// info: {title: t, version: '1'}
+    /// paths:
+    ///   /pets:
+    ///     get:
+    ///       responses:
+    ///         200:
+    ///           description: pets
+    ///           content:
+    ///             application/json:
+    ///               schema: {type: array, items: {$ref: '#/components/schemas/Pet'}}
+    /// components:
+    ///   schemas:
+    ///     Pet: {type: object}
+    /// ",
+    /// )
+    /// .unwrap();
+    /// assert_eq!(api.rename_component(ComponentKind::Schemas, "Pet", "Animal"), Ok(1));
+    /// let text = serde_yaml::to_string(&api).unwrap();
+    /// assert!(text.contains("$ref: \"#/components/schemas/Animal\""));
+    /// assert!(api.components.unwrap().schemas.contains_key("Animal"));
+    /// ```
+    pub fn rename_component(
+        &mut self,
+        kind: ComponentKind,
+        old: &str,
+        new: &str,
+    ) -> Result<usize, RenameError> {
+        let not_found = || RenameError::NotFound(Reference::component(kind, old));
+        let components = self.components.as_mut().ok_or_else(not_found)?;
+        if !components.contains(kind, old) {
+            return Err(not_found());
+        }
+        if old == new {
+            return Ok(0);
+        }
+        if components.contains(kind, new) {
+            return Err(RenameError::Exists(Reference::component(kind, new)));
+        }
+        let renames = BTreeMap::from([(kind, BTreeMap::from([(old.to_owned(), new.to_owned())]))]);
+        Ok(self.rename_components(&renames))
+    }
+
+    /// Puts `prefix` before the name of every component, such as
+    /// `Billing_Invoice` for `Invoice`, for merging documents whose
+    /// components would otherwise clash, and rewrites what refers to them
+    /// as [`OpenAPI::rename_component`] does.
+    ///
+    /// Returns the number of references rewritten.
+    ///
+    /// ```
+    /// use openapiv3::OpenAPI;
+    ///
+    /// let mut api: OpenAPI = serde_yaml::from_str(
+    ///     "
+    /// openapi: 3.0.0
+    /// info: {title: t, version: '1'}
+    /// paths: {}
+    /// components:
+    ///   schemas:
+    ///     Invoice: {type: object, properties: {lines: {$ref: '#/components/schemas/Lines'}}}
+    ///     Lines: {type: array, items: {type: string}}
+    /// ",
+    /// )
+    /// .unwrap();
+    /// assert_eq!(api.prefix_components("Billing_"), 1);
+    /// let schemas = &api.components.as_ref().unwrap().schemas;
+    /// let names: Vec<_> = schemas.keys().map(String::as_str).collect();
+    /// assert_eq!(names, ["Billing_Invoice", "Billing_Lines"]);
+    /// ```
+    pub fn prefix_components(&mut self, prefix: &str) -> usize {
+        let components = match &self.components {
+            Some(components) => components,
+            None => return 0,
+        };
+        let renames = ComponentKind::ALL
+            .iter()
+            .map(|&kind| {
+                let names = components.names(kind).into_iter();
+                let names = names.map(|name| (name.to_owned(), format!("{}{}", prefix, name)));
+                (kind, names.collect())
+            })
+            .collect();
+        self.rename_components(&renames)
+    }
+
+    /// Renames the components `renames` has new names for, by kind and old
+    /// name, and rewrites what refers to them, returning the number of
+    /// references rewritten. The new names must not clash.
+    fn rename_components(&mut self, renames: &Renames) -> usize {
+        if let Some(components) = &mut self.components {
+            for (&kind, names) in renames {
+                components.rename(kind, names);
+            }
+        }
+        let mut renamer = Renamer { renames, count: 0 };
+        self.visit_mut(&mut renamer);
+        renamer.requirements(&mut self.security);
+        renamer.count
+    }
+}
+
+/// New names of components, by kind and old name.
+type Renames = BTreeMap<ComponentKind, BTreeMap<String, String>>;
+
+struct Renamer<'a> {
+    renames: &'a Renames,
+    count: usize,
+}
+
+impl Renamer<'_> {
+    /// `target` pointing to a renamed component by its new name, if it
+    /// pointed to it or within it by its old.
+    fn rewrite(&self, target: &str) -> Option<String> {
+        let mut segments = target.strip_prefix("#/components/")?.splitn(3, '/');
+        let kind: ComponentKind = segments.next()?.parse().ok()?;
+        let name = unescape_pointer_segment(segments.next()?);
+        let new = Reference::component(kind, self.renames.get(&kind)?.get(&name)?);
+        Some(match segments.next() {
+            Some(rest) => format!("{}/{}", new, rest),
+            None => new.to_string(),
+        })
+    }
+
+    fn requirements(&mut self, requirements: &mut [SecurityRequirement]) {
+        let schemes = match self.renames.get(&ComponentKind::SecuritySchemes) {
+            Some(schemes) => schemes,
+            None => return,
+        };
+        for requirement in requirements {
+            let renamed = requirement
+                .keys()
+                .filter(|&name| schemes.contains_key(name));
+            self.count += renamed.count();
+            *requirement = std::mem::take(requirement)
+                .into_iter()
+                .map(|(name, scopes)| match schemes.get(&name) {
+                    Some(new) => (new.clone(), scopes),
+                    None => (name, scopes),
+                })
+                .collect();
+        }
+    }
+}
+
+impl VisitMut for Renamer<'_> {
+    fn visit_operation_mut(&mut self, _: &str, operation: &mut Operation) {
+        if let Some(security) = &mut operation.security {
+            self.requirements(security);
+        }
+    }
+
+    fn visit_schema_mut(&mut self, _: &str, schema: &mut Schema) {
+        let mapping = schema
+            .schema_data
+            .discriminator
+            .iter_mut()
+            .flat_map(|discriminator| discriminator.mapping.values_mut());
+        for target in mapping {
+            // Targets without a `#` or `/` are names of schemas.
+            let renamed = if target.contains(['#', '/']) {
+                self.rewrite(target)
+            } else {
+                let schemas = self.renames.get(&ComponentKind::Schemas);
+                schemas
+                    .and_then(|schemas| schemas.get(target.as_str()))
+                    .cloned()
+            };
+            if let Some(renamed) = renamed {
+                *target = renamed;
+                self.count += 1;
+            }
+        }
+    }
+
+    fn visit_link_mut(&mut self, _: &str, link: &mut Link) {
+        let renamed = link.operation_ref.as_deref().and_then(|r| self.rewrite(r));
+        if let Some(renamed) = renamed {
+            link.operation_ref = Some(renamed);
+            self.count += 1;
+        }
+    }
+
+    fn visit_reference_mut(&mut self, _: &str, reference: &mut Reference) {
+        let renamed = self.rewrite(reference.as_str());
+        if let Some(renamed) = renamed.and_then(|r| r.parse().ok()) {
+            *reference = renamed;
+            self.count += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renames_components() {
+        let mut api: OpenAPI = serde_yaml::from_str(
+            "
+openapi: 3.0.0
+info: {title: t, version: '1'}
+security: [{key: []}]
+paths:
+  /hooks:
+    post:
+      security: [{key: [], other: []}]
+      responses: {}
+      callbacks:
+        onEvent:
+          '{$request.body#/url}':
+            post:
+              requestBody:
+                content:
+                  application/json: {schema: {$ref: '#/components/schemas/Event'}}
+              responses:
+                200:
+                  description: ok
+                  links:
+                    again: {operationRef: '#/components/callbacks/x/~1y/get'}
+components:
+  schemas:
+    Event:
+      type: object
+      properties:
+        kind: {$ref: '#/components/schemas/Event/properties/name'}
+        name: {type: string}
+    Events:
+      oneOf: [{$ref: '#/components/schemas/Event'}, {$ref: '#/components/schemas/Events'}]
+      discriminator:
+        propertyName: kind
+        mapping: {a: Event, b: '#/components/schemas/Event', c: Events}
+  securitySchemes:
+    key: {type: apiKey, in: header, name: X-Key}
+",
+        )
+        .unwrap();
+        assert_eq!(
+            api.rename_component(ComponentKind::Schemas, "Event", "Happening"),
+            Ok(5)
+        );
+        let text = serde_yaml::to_string(&api).unwrap();
+        assert!(!text.contains("schemas/Event\""));
+        assert!(text.contains("\"#/components/schemas/Happening/properties/name\""));
+        assert!(text.contains("a: Happening"));
+        assert!(text.contains("c: Events"));
+
+        assert_eq!(
+            api.rename_component(ComponentKind::SecuritySchemes, "key", "apiKey"),
+            Ok(2)
+        );
+        assert!(api.security[0].contains_key("apiKey"));
+
+        assert_eq!(
+            api.rename_component(ComponentKind::Schemas, "Events", "Happening"),
+            Err(RenameError::Exists(Reference::component(
+                ComponentKind::Schemas,
+                "Happening"
+            )))
+        );
+        assert_eq!(
+            api.rename_component(ComponentKind::Callbacks, "x", "y"),
+            Err(RenameError::NotFound(Reference::component(
+                ComponentKind::Callbacks,
+                "x"
+            )))
+        );
+    }
+
+    #[test]
+    fn prefixes_components() {
+        let mut api: OpenAPI = serde_yaml::from_str(
+            "
+openapi: 3.0.0
+info: {title: t, version: '1'}
+security: [{key: []}]
+paths:
+  /pets:
+    get:
+      responses:
+        default: {$ref: '#/components/responses/Error'}
+components:
+  responses:
+    Error: {description: failed}
+  schemas:
+    B_Cat: {type: object}
+    Cat: {type: object}
+    Pet:
+      oneOf: [{$ref: '#/components/schemas/Cat'}, {$ref: '#/components/schemas/B_Cat'}]
+      discriminator:
+        propertyName: kind
+        mapping: {cat: '#/components/schemas/Cat', big: B_Cat, other: ./other.yaml#/Cat}
+  securitySchemes:
+    key: {type: apiKey, in: header, name: X-Key}
+",
+        )
+        .unwrap();
+        assert_eq!(api.prefix_components("B_"), 6);
+        let components = api.components.as_ref().unwrap();
+        let schemas: Vec<_> = components.schemas.keys().map(String::as_str).collect();
+        assert_eq!(schemas, ["B_B_Cat", "B_Cat", "B_Pet"]);
+        let pet = serde_json::to_value(&components.schemas["B_Pet"]).unwrap();
+        assert_eq!(
+            pet["oneOf"],
+            serde_json::json!([
+                {"$ref": "#/components/schemas/B_Cat"},
+                {"$ref": "#/components/schemas/B_B_Cat"}
+            ])
+        );
+        assert_eq!(
+            pet["discriminator"]["mapping"],
+            serde_json::json!({
+                "big": "B_B_Cat",
+                "cat": "#/components/schemas/B_Cat",
+                "other": "./other.yaml#/Cat"
+            })
+        );
+        assert!(api.security[0].contains_key("B_key"));
+        assert!(components.responses.contains_key("B_Error"));
+    }
+}
@@ -0,0 +1,505 @@
+//! Markdown documentation of the operations of a document, one page per
+//! tag.
+//!
+//! Each page lists its operations in a table, then documents each with
+//! its parameters, request body and responses, with an example of each
+//! body, and ends with the properties of the schemas its operations use.
+//!
+//! ```
+//! use openapiv3::render::markdown;
+//! use openapiv3::OpenAPI;
+//!
+//! let api: OpenAPI = serde_yaml::from_str(
+//!     "
+//! openapi: 3.0.0
+//! info: {title: Pets, version: '1'}
+//! tags: [{name: pets, description: Everything about pets.}]
+//! paths:
+//!   /pets:
+//!     get:
+//!       tags: [pets]
+//!       summary: List pets
+//!       responses:
+//!         200:
+//!           description: The pets.
+//!           content:
+//!             application/json:
+//!               schema: {type: array, items: {$ref: '#/components/schemas/Pet'}}
+//! components:
+//!   schemas:
+//!     Pet:
+//!       type: object
+//!       required: [name]
+//!       properties:
+//!         name: {type: string, example: Rex}
+//! ",
+//! )
+//! .unwrap();
+//! let pages = markdown::render(&api);
+//! assert_eq!(pages[0].tag, "pets");
+//! assert!(pages[0].markdown.starts_with(
+//!     "\
+//! ## pets
+//!
+//! Everything about pets.
+//!
+//! | Method | Path | Summary |
+//! | --- | --- | --- |
+//! | GET | [/pets](#get-pets) | List pets |
+//! "
+//! ));
+//! assert!(pages[0].markdown.contains("| 200 | The pets. | `Pet[]` |"));
+//! assert!(pages[0].markdown.contains("| `name` | `string` | yes |  |"));
+//! ```
+
+use super::{anchor, Entry};
+use crate::request_example::{sample, serialize_body};
+use crate::summary::{boxed_name, content_schema, name, schema_name};
+use crate::walk::{FindRefs, Location};
+use crate::*;
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+/// The documentation of the operations of one tag.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Page {
+    /// The tag, or `default` for the page of operations without tags.
+    pub tag: String,
+    pub markdown: String,
+}
+
+/// A page for each tag of the document, in the order the tags are
+/// declared, then those only used by operations in the order they are
+/// first used, and last a `default` page of the operations without tags,
+/// if there are any.
+pub fn render(api: &OpenAPI) -> Vec<Page> {
+    let default_components = Components::default();
+    let components = api.components.as_ref().unwrap_or(&default_components);
+    let mut pages = Vec::new();
+    for group in super::by_tag(api) {
+        let mut out = String::new();
+        let tag = group.tag.unwrap_or("default");
+        write_page(
+            &mut out,
+            components,
+            tag,
+            group.description,
+            &group.operations,
+        );
+        out.truncate(out.trim_end().len());
+        out.push('\n');
+        pages.push(Page {
+            tag: tag.to_owned(),
+            markdown: out,
+        });
+    }
+    pages
+}
+
+fn write_page(
+    out: &mut String,
+    components: &Components,
+    tag: &str,
+    description: Option<&str>,
+    operations: &[Entry<'_>],
+) {
+    let _ = writeln!(out, "# {}\n", tag);
+    if let Some(description) = description {
+        let _ = writeln!(out, "{}\n", description.trim_end());
+    }
+    out.push_str("| Method | Path | Summary |\n| --- | --- | --- |\n");
+    for (path, method, _, operation) in operations {
+        let heading = format!("{} {}", method, path);
+        let _ = writeln!(
+            out,
+            "| {} | [{}](#{}) | {} |",
+            method,
+            cell(path),
+            anchor(&heading),
+            cell(operation.summary.as_deref().unwrap_or_default())
+        );
+    }
+    out.push('\n');
+    for (path, method, item, operation) in operations {
+        write_operation(out, components, path, *method, item, operation);
+    }
+
+    let schemas = used_schemas(components, operations);
+    if !schemas.is_empty() {
+        out.push_str("## Schemas\n\n");
+    }
+    for name in schemas {
+        if let Some(ReferenceOr::Item(schema)) = components.schemas.get(&name) {
+            write_schema(out, &name, schema);
+        }
+    }
+}
+
+fn write_operation(
+    out: &mut String,
+    components: &Components,
+    path: &str,
+    method: Method,
+    item: &PathItem,
+    operation: &Operation,
+) {
+    let _ = writeln!(out, "## {} {}\n", method, path);
+    if operation.deprecated {
+        out.push_str("**Deprecated.**\n\n");
+    }
+    for text in [&operation.summary, &operation.description]
+        .iter()
+        .copied()
+        .flatten()
+    {
+        let _ = writeln!(out, "{}\n", text.trim_end());
+    }
+
+    let parameters = operation.effective_parameters(item, components);
+    if !parameters.is_empty() {
+        out.push_str("### Parameters\n\n");
+        out.push_str("| Name | In | Type | Required | Description |\n");
+        out.push_str("| --- | --- | --- | --- | --- |\n");
+        for parameter in parameters {
+            let data = parameter.parameter_data_ref();
+            let schema = match &data.format {
+                ParameterSchemaOrContent::Schema(schema) => Some(schema),
+                ParameterSchemaOrContent::Content(content) => content_schema(content.iter()),
+            };
+            let _ = writeln!(
+                out,
+                "| `{}` | {} | {} | {} | {} |",
+                cell(&data.name),
+                parameter.location(),
+                schema.map(|s| code(&schema_name(s))).unwrap_or_default(),
+                yes_no(data.required),
+                cell(data.description.as_deref().unwrap_or_default())
+            );
+        }
+        out.push('\n');
+    }
+
+    if let Some(body) = &operation.request_body {
+        out.push_str("### Request body\n\n");
+        match components.resolve(body) {
+            Ok(body) => {
+                if let Some(description) = &body.description {
+                    let _ = writeln!(out, "{}\n", description.trim_end());
+                }
+                let _ = writeln!(out, "Required: {}\n", yes_no(body.required));
+                for (range, media_type) in &body.content {
+                    write_content(out, components, range, media_type, "Example");
+                }
+            }
+            Err(_) => {
+                if let ReferenceOr::Reference { reference } = body {
+                    let _ = writeln!(out, "`{}`\n", name(reference));
+                }
+            }
+        }
+    }
+
+    out.push_str("### Responses\n\n");
+    out.push_str("| Status | Description | Type |\n| --- | --- | --- |\n");
+    let responses: Vec<_> = operation
+        .responses
+        .responses
+        .iter()
+        .map(|(code, response)| (code.to_string(), response))
+        .chain(
+            operation
+                .responses
+                .default
+                .iter()
+                .map(|response| ("default".to_owned(), response)),
+        )
+        .filter_map(|(code, response)| Some((code, components.resolve(response).ok()?)))
+        .collect();
+    for (code, response) in &responses {
+        let schema = content_schema(media_types(response));
+        let _ = writeln!(
+            out,
+            "| {} | {} | {} |",
+            code,
+            cell(&response.description),
+            schema
+                .map(|s| code_cell(&schema_name(s)))
+                .unwrap_or_default()
+        );
+    }
+    out.push('\n');
+    for (code, response) in &responses {
+        for (range, media_type) in media_types(response) {
+            let title = format!("Example `{}` response", code);
+            write_content(out, components, range, media_type, &title);
+        }
+    }
+}
+
+/// The media types of a response, other than references, which cannot be
+/// to components.
+fn media_types(response: &Response) -> impl Iterator<Item = (&MediaTypeRange, &MediaType)> + Clone {
+    response
+        .content
+        .iter()
+        .filter_map(|(range, media_type)| match media_type {
+            ReferenceOr::Item(media_type) => Some((range, media_type)),
+            ReferenceOr::Reference { .. } => None,
+        })
+}
+
+/// Writes an example of the body of a media type, if there is one.
+fn write_content(
+    out: &mut String,
+    components: &Components,
+    range: &MediaTypeRange,
+    media_type: &MediaType,
+    title: &str,
+) {
+    let value = sample(
+        components,
+        &media_type.example,
+        &media_type.examples,
+        media_type.schema.as_ref(),
+    );
+    if value.is_none() {
+        return;
+    }
+    let (content_type, body) = serialize_body(components, range, media_type, value);
+    let language = if range.is_json() {
+        "json"
+    } else if range.subtype().ends_with("xml") {
+        "xml"
+    } else {
+        ""
+    };
+    let _ = writeln!(
+        out,
+        "{} (`{}`):\n\n```{}\n{}\n```\n",
+        title, content_type, language, body
+    );
+}
+
+fn write_schema(out: &mut String, name: &str, schema: &Schema) {
+    let _ = writeln!(out, "### {}\n", name);
+    if let Some(description) = &schema.schema_data.description {
+        let _ = writeln!(out, "{}\n", description.trim_end());
+    }
+    let (properties, required) = match &schema.schema_kind {
+        SchemaKind::Type(Type::Object(object)) => (&object.properties, &object.required),
+        SchemaKind::Any(any) => (&any.properties, &any.required),
+        _ => {
+            let _ = writeln!(out, "Type: {}\n", code(&schema.to_string()));
+            return;
+        }
+    };
+    if properties.is_empty() {
+        let _ = writeln!(out, "Type: {}\n", code(&schema.to_string()));
+        return;
+    }
+    out.push_str("| Property | Type | Required | Description |\n");
+    out.push_str("| --- | --- | --- | --- |\n");
+    for (property, schema) in properties {
+        let description = match schema {
+            ReferenceOr::Item(schema) => schema.schema_data.description.as_deref(),
+            ReferenceOr::Reference { .. } => None,
+        };
+        let _ = writeln!(
+            out,
+            "| `{}` | {} | {} | {} |",
+            cell(property),
+            code_cell(&boxed_name(schema)),
+            yes_no(required.contains(property)),
+            cell(description.unwrap_or_default())
+        );
+    }
+    out.push('\n');
+}
+
+/// The names of the schemas of the components that the operations use,
+/// directly or through other components.
+fn used_schemas(components: &Components, operations: &[Entry<'_>]) -> BTreeSet<String> {
+    let mut pending = Vec::new();
+    let mut collect = |_: &Location, reference: &Reference| pending.push(reference.clone());
+    for (_, _, item, operation) in operations {
+        item.parameters
+            .find_refs(&mut Location::default(), &mut collect);
+        operation.find_refs(&mut Location::default(), &mut collect);
+    }
+
+    let mut seen = BTreeSet::new();
+    let mut schemas = BTreeSet::new();
+    while let Some(reference) = pending.pop() {
+        if reference.is_external() || !seen.insert(reference.clone()) {
+            continue;
+        }
+        let (kind, name) = match (reference.component_kind(), reference.name()) {
+            (Some(kind), Some(name)) => (kind, name.into_owned()),
+            _ => continue,
+        };
+        let mut collect = |_: &Location, reference: &Reference| pending.push(reference.clone());
+        let at = &mut Location::default();
+        match kind {
+            ComponentKind::Schemas => {
+                if let Some(schema) = components.schemas.get(&name) {
+                    schema.find_refs(at, &mut collect);
+                    schemas.insert(name);
+                }
+            }
+            ComponentKind::Responses => {
+                if let Some(response) = components.responses.get(&name) {
+                    response.find_refs(at, &mut collect);
+                }
+            }
+            ComponentKind::Parameters => {
+                if let Some(parameter) = components.parameters.get(&name) {
+                    parameter.find_refs(at, &mut collect);
+                }
+            }
+            ComponentKind::RequestBodies => {
+                if let Some(body) = components.request_bodies.get(&name) {
+                    body.find_refs(at, &mut collect);
+                }
+            }
+            ComponentKind::Headers => {
+                if let Some(header) = components.headers.get(&name) {
+                    header.find_refs(at, &mut collect);
+                }
+            }
+            _ => {}
+        }
+    }
+    schemas
+}
+
+/// Text for a table cell, on one line and with its `|`s escaped.
+fn cell(text: &str) -> String {
+    text.trim().replace('|', "\\|").replace('\n', "<br>")
+}
+
+fn code(text: &str) -> String {
+    format!("`{}`", text)
+}
+
+fn code_cell(text: &str) -> String {
+    code(&cell(text))
+}
+
+fn yes_no(yes: bool) -> &'static str {
+    if yes {
+        "yes"
+    } else {
+        "no"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_pages_by_tag() {
+        let api: OpenAPI = serde_yaml::from_str(
+            "
+openapi: 3.0.0
+info: {title: Pets, version: '1'}
+paths:
+  /pets/{id}:
+    parameters:
+      - {name: id, in: path, required: true, description: The pet., schema: {type: integer}}
+    put:
+      tags: [pets]
+      deprecated: true
+      requestBody:
+        required: true
+        content:
+          application/json: {schema: {$ref: '#/components/schemas/Pet'}}
+      responses:
+        204: {description: Updated.}
+        default: {$ref: '#/components/responses/Error'}
+  /health:
+    get:
+      responses:
+        200: {description: Up | down}
+components:
+  responses:
+    Error:
+      description: An error.
+      content:
+        application/json: {schema: {$ref: '#/components/schemas/Error'}}
+  schemas:
+    Pet:
+      type: object
+      properties:
+        kind: {type: string, enum: [cat, dog]}
+        name: {type: string, description: Its name., example: Rex}
+    Error: {type: string, example: oops}
+    Unused: {type: integer}
+",
+        )
+        .unwrap();
+        let pages = render(&api);
+        let tags: Vec<_> = pages.iter().map(|page| page.tag.as_str()).collect();
+        assert_eq!(tags, ["pets", "default"]);
+        assert_eq!(
+            pages[0].markdown,
+            "\
+# pets
+
+| Method | Path | Summary |
+| --- | --- | --- |
+| PUT | [/pets/{id}](#put-petsid) |  |
+
+## PUT /pets/{id}
+
+**Deprecated.**
+
+### Parameters
+
+| Name | In | Type | Required | Description |
+| --- | --- | --- | --- | --- |
+| `id` | path | `integer` | yes | The pet. |
+
+### Request body
+
+Required: yes
+
+Example (`application/json`):
+
+```json
+{
+  \"kind\": \"cat\",
+  \"name\": \"Rex\"
+}
+```
+
+### Responses
+
+| Status | Description | Type |
+| --- | --- | --- |
+| 204 | Updated. |  |
+| default | An error. | `Error` |
+
+Example `default` response (`application/json`):
+
+```json
+\"oops\"
+```
+
+## Schemas
+
+### Error
+
+Type: `string`
+
+### Pet
+
+| Property | Type | Required | Description |
+| --- | --- | --- | --- |
+| `kind` | `\"cat\" \\| \"dog\"` | no |  |
+| `name` | `string` | no | Its name. |
+"
+        );
+        assert!(pages[1].markdown.contains("| 200 | Up \\| down |  |"));
+    }
+}
@@ -0,0 +1,50 @@
+//! Documentation of the APIs documents describe.
+
+pub mod markdown;
+pub mod model;
+
+use crate::*;
+
+/// An operation with the path and path item it is of.
+pub(crate) type Entry<'a> = (&'a str, Method, &'a PathItem, &'a Operation);
+
+/// The operations of a tag, or of no tag, with their path items.
+pub(crate) struct Section<'a> {
+    pub tag: Option<&'a str>,
+    pub description: Option<&'a str>,
+    pub operations: Vec<Entry<'a>>,
+}
+
+/// The [`OpenAPI::operations_by_tag`], with the path item of each
+/// operation.
+pub(crate) fn by_tag(api: &OpenAPI) -> Vec<Section<'_>> {
+    api.operations_by_tag()
+        .into_iter()
+        .map(|group| Section {
+            tag: group.name,
+            description: group.tag.and_then(|tag| tag.description.as_deref()),
+            operations: group
+                .operations
+                .into_iter()
+                .filter_map(|(path, method, operation)| match api.paths.get(path)? {
+                    ReferenceOr::Item(item) => Some((path, method, item, operation)),
+                    ReferenceOr::Reference { .. } => None,
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+/// The anchor GitHub gives a heading: lowercase, without punctuation
+/// other than `-` and `_`, and with spaces as `-`.
+pub(crate) fn anchor(heading: &str) -> String {
+    heading
+        .to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            ' ' => Some('-'),
+            c if c.is_alphanumeric() || c == '-' || c == '_' => Some(c),
+            _ => None,
+        })
+        .collect()
+}
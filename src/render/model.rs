@@ -0,0 +1,589 @@
+//! A view of a document for templates, such as those of Tera or Askama,
+//! to render documentation from.
+//!
+//! The view is flat and owned: operations are grouped by tag, their
+//! parameters merged with those of their path items, and references
+//! followed, with the schemas of bodies and parameters inlined as trees.
+//! Schemas that are components carry their names and the anchors of
+//! their entries in [`Documentation::schemas`], so that templates may
+//! link to them rather than repeat them. Every field is serialized, as
+//! `null` if it is absent, so that templates need not check for it.
+//!
+//! ```
+//! use openapiv3::render::model::Documentation;
+//! use openapiv3::OpenAPI;
+//!
+//! let api: OpenAPI = serde_yaml::from_str(
+//!     "
+//! openapi: 3.0.0
+//! info: {title: Pets, version: '1'}
+//! paths:
+//!   /pets:
+//!     get:
+//!       tags: [pets]
+//!       responses:
+//!         200:
+//!           description: The pets.
+//!           content:
+//!             application/json:
+//!               schema: {type: array, items: {$ref: '#/components/schemas/Pet'}}
+//! components:
+//!   schemas:
+//!     Pet:
+//!       type: object
+//!       properties:
+//!         name: {type: string}
+//! ",
+//! )
+//! .unwrap();
+//! let documentation = Documentation::new(&api);
+//! let operation = &documentation.tags[0].operations[0];
+//! assert_eq!(operation.anchor, "get-pets");
+//! let schema = operation.responses[0].content[0].schema.as_ref().unwrap();
+//! let pet = schema.items.as_ref().unwrap();
+//! assert_eq!(pet.anchor.as_deref(), Some("schema-pet"));
+//! assert_eq!(pet.properties[0].name, "name");
+//! assert_eq!(documentation.schemas[0].anchor, pet.anchor);
+//!
+//! let context = serde_json::to_value(&documentation).unwrap();
+//! assert_eq!(context["tags"][0]["operations"][0]["method"], "GET");
+//! ```
+
+use super::anchor;
+use crate::request_example::{sample, serialize_body};
+use crate::summary::{content_schema, name};
+use crate::*;
+use serde::Serialize;
+use serde_json::Value;
+
+/// The documentation of a document.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Documentation {
+    pub title: String,
+    pub version: String,
+    pub description: Option<String>,
+    /// The tags in the order the document declares them, then those only
+    /// used by operations, and last `default` for the operations without
+    /// tags. An operation with several tags is in each.
+    pub tags: Vec<TagDoc>,
+    /// The schemas of the components, each inlined.
+    pub schemas: Vec<SchemaDoc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TagDoc {
+    pub name: String,
+    pub description: Option<String>,
+    pub operations: Vec<OperationDoc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OperationDoc {
+    /// The method in upper case, such as `GET`.
+    pub method: String,
+    pub path: String,
+    /// The anchor of the operation, made from its method and path as
+    /// GitHub makes them from headings, such as `get-petsid`.
+    pub anchor: String,
+    pub operation_id: Option<String>,
+    pub summary: Option<String>,
+    pub description: Option<String>,
+    pub deprecated: bool,
+    /// The parameters of the operation and of its path item.
+    pub parameters: Vec<ParameterDoc>,
+    pub request_body: Option<BodyDoc>,
+    /// The responses by status, then the default response.
+    pub responses: Vec<ResponseDoc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ParameterDoc {
+    pub name: String,
+    /// Where the parameter is: `query`, `header`, `path` or `cookie`.
+    #[serde(rename = "in")]
+    pub location: String,
+    pub required: bool,
+    pub deprecated: bool,
+    pub description: Option<String>,
+    /// The schema of the parameter, or of its JSON content.
+    pub schema: Option<SchemaDoc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BodyDoc {
+    pub description: Option<String>,
+    pub required: bool,
+    pub content: Vec<ContentDoc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ResponseDoc {
+    /// The status code or range, such as `200` or `4XX`, or `default`.
+    pub status: String,
+    pub description: String,
+    pub content: Vec<ContentDoc>,
+}
+
+/// A body of one media type.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ContentDoc {
+    pub media_type: String,
+    pub schema: Option<SchemaDoc>,
+    /// An example of the body as it is sent; see
+    /// [`Operation::example_request`].
+    pub example: Option<String>,
+}
+
+/// A schema with the schemas it is made of inlined.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SchemaDoc {
+    /// The name of the component the schema is, if it is one.
+    pub name: Option<String>,
+    /// The anchor of the entry of the component in
+    /// [`Documentation::schemas`], such as `schema-pet`.
+    pub anchor: Option<String>,
+    /// The schema as a type expression, such as `Pet[]`; see the
+    /// [`Display`](std::fmt::Display) of [`Schema`].
+    pub type_name: String,
+    /// One of `string`, `number`, `integer`, `boolean`, `object`, `array`,
+    /// `oneOf`, `anyOf`, `allOf` and `any`. References that cannot be
+    /// followed are `any`.
+    pub kind: &'static str,
+    pub format: Option<String>,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub nullable: bool,
+    pub deprecated: bool,
+    pub read_only: bool,
+    pub write_only: bool,
+    #[serde(rename = "enum")]
+    pub enumeration: Vec<Value>,
+    pub default: Option<Value>,
+    pub example: Option<Value>,
+    pub properties: Vec<PropertyDoc>,
+    pub additional_properties: Option<Box<SchemaDoc>>,
+    pub items: Option<Box<SchemaDoc>>,
+    /// The branches of a `oneOf`, `anyOf` or `allOf`.
+    pub variants: Vec<SchemaDoc>,
+    /// Whether the schema is a component already being inlined, such as
+    /// the children of a tree, which is then not inlined again.
+    pub recursive: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PropertyDoc {
+    pub name: String,
+    pub required: bool,
+    pub schema: SchemaDoc,
+}
+
+impl Documentation {
+    pub fn new(api: &OpenAPI) -> Documentation {
+        let default_components = Components::default();
+        let components = api.components.as_ref().unwrap_or(&default_components);
+        let tags = super::by_tag(api)
+            .into_iter()
+            .map(|group| TagDoc {
+                name: group.tag.unwrap_or("default").to_owned(),
+                description: group.description.map(str::to_owned),
+                operations: group
+                    .operations
+                    .iter()
+                    .map(|&(path, method, item, operation)| {
+                        operation_doc(components, path, method, item, operation)
+                    })
+                    .collect(),
+            })
+            .collect();
+        let schemas = components
+            .schemas
+            .iter()
+            .filter_map(|(name, schema)| {
+                let schema = components.resolve(schema).ok()?;
+                Some(component_doc(components, name, schema, &mut Vec::new()))
+            })
+            .collect();
+        Documentation {
+            title: api.info.title.clone(),
+            version: api.info.version.clone(),
+            description: api.info.description.clone(),
+            tags,
+            schemas,
+        }
+    }
+}
+
+fn operation_doc(
+    components: &Components,
+    path: &str,
+    method: Method,
+    item: &PathItem,
+    operation: &Operation,
+) -> OperationDoc {
+    let parameters = operation
+        .effective_parameters(item, components)
+        .into_iter()
+        .map(|parameter| {
+            let data = parameter.parameter_data_ref();
+            let schema = match &data.format {
+                ParameterSchemaOrContent::Schema(schema) => Some(schema),
+                ParameterSchemaOrContent::Content(content) => content_schema(content.iter()),
+            };
+            ParameterDoc {
+                name: data.name.clone(),
+                location: parameter.location().to_owned(),
+                required: data.required,
+                deprecated: data.deprecated.unwrap_or(false),
+                description: data.description.clone(),
+                schema: schema.map(|schema| schema_doc(components, schema, &mut Vec::new())),
+            }
+        })
+        .collect();
+    let request_body = operation
+        .request_body
+        .as_ref()
+        .and_then(|body| components.resolve(body).ok())
+        .map(|body| BodyDoc {
+            description: body.description.clone(),
+            required: body.required,
+            content: body
+                .content
+                .iter()
+                .map(|(range, media_type)| content_doc(components, range, media_type))
+                .collect(),
+        });
+    let responses = operation
+        .responses
+        .responses
+        .iter()
+        .map(|(status, response)| (status.to_string(), response))
+        .chain(
+            operation
+                .responses
+                .default
+                .iter()
+                .map(|response| ("default".to_owned(), response)),
+        )
+        .filter_map(|(status, response)| {
+            let response = components.resolve(response).ok()?;
+            Some(ResponseDoc {
+                status,
+                description: response.description.clone(),
+                content: response
+                    .content
+                    .iter()
+                    .filter_map(|(range, media_type)| match media_type {
+                        ReferenceOr::Item(media_type) => {
+                            Some(content_doc(components, range, media_type))
+                        }
+                        ReferenceOr::Reference { .. } => None,
+                    })
+                    .collect(),
+            })
+        })
+        .collect();
+    OperationDoc {
+        method: method.to_string(),
+        path: path.to_owned(),
+        anchor: anchor(&format!("{} {}", method, path)),
+        operation_id: operation.operation_id.clone(),
+        summary: operation.summary.clone(),
+        description: operation.description.clone(),
+        deprecated: operation.deprecated,
+        parameters,
+        request_body,
+        responses,
+    }
+}
+
+fn content_doc(
+    components: &Components,
+    range: &MediaTypeRange,
+    media_type: &MediaType,
+) -> ContentDoc {
+    let value = sample(
+        components,
+        &media_type.example,
+        &media_type.examples,
+        media_type.schema.as_ref(),
+    );
+    let example = value.map(|value| serialize_body(components, range, media_type, Some(value)).1);
+    ContentDoc {
+        media_type: range.to_string(),
+        schema: media_type
+            .schema
+            .as_ref()
+            .map(|schema| schema_doc(components, schema, &mut Vec::new())),
+        example,
+    }
+}
+
+/// The component schema `name`, inlined unless it is in `inlining`, the
+/// components whose schemas are being inlined.
+fn component_doc(
+    components: &Components,
+    name: &str,
+    schema: &Schema,
+    inlining: &mut Vec<String>,
+) -> SchemaDoc {
+    let recursive = inlining.iter().any(|n| n == name);
+    let mut doc = if recursive {
+        SchemaDoc {
+            recursive: true,
+            ..summary_doc(schema)
+        }
+    } else {
+        inlining.push(name.to_owned());
+        let doc = item_doc(components, schema, inlining);
+        inlining.pop();
+        doc
+    };
+    doc.name = Some(name.to_owned());
+    doc.anchor = Some(anchor(&format!("schema {}", name)));
+    doc.type_name = name.to_owned();
+    doc
+}
+
+fn schema_doc(
+    components: &Components,
+    schema: &ReferenceOr<Schema>,
+    inlining: &mut Vec<String>,
+) -> SchemaDoc {
+    match schema {
+        ReferenceOr::Item(schema) => item_doc(components, schema, inlining),
+        ReferenceOr::Reference { reference } => reference_doc(components, reference, inlining),
+    }
+}
+
+fn boxed_doc(
+    components: &Components,
+    schema: &ReferenceOr<Box<Schema>>,
+    inlining: &mut Vec<String>,
+) -> SchemaDoc {
+    match schema {
+        ReferenceOr::Item(schema) => item_doc(components, schema, inlining),
+        ReferenceOr::Reference { reference } => reference_doc(components, reference, inlining),
+    }
+}
+
+fn reference_doc(
+    components: &Components,
+    reference: &Reference,
+    inlining: &mut Vec<String>,
+) -> SchemaDoc {
+    let resolved = components.resolve_reference::<Schema>(reference);
+    match (reference.name(), resolved) {
+        (Some(component), Ok(schema)) if !reference.is_external() => {
+            component_doc(components, &component, schema, inlining)
+        }
+        _ => SchemaDoc {
+            type_name: name(reference),
+            ..summary_doc(&Schema {
+                schema_data: SchemaData::default(),
+                schema_kind: SchemaKind::Any(AnySchema::default()),
+            })
+        },
+    }
+}
+
+/// The schema without the schemas it is made of.
+fn summary_doc(schema: &Schema) -> SchemaDoc {
+    let data = &schema.schema_data;
+    let (kind, format, enumeration) = match &schema.schema_kind {
+        SchemaKind::Type(Type::String(string)) => (
+            "string",
+            string.format.name(StringFormat::as_str),
+            string
+                .enumeration
+                .iter()
+                .map(|v| Value::from(v.as_str()))
+                .collect(),
+        ),
+        SchemaKind::Type(Type::Number(number)) => (
+            "number",
+            number.format.name(NumberFormat::as_str),
+            number.enumeration.iter().map(|&v| Value::from(v)).collect(),
+        ),
+        SchemaKind::Type(Type::Integer(integer)) => (
+            "integer",
+            integer.format.name(IntegerFormat::as_str),
+            integer
+                .enumeration
+                .iter()
+                .map(|&v| Value::from(v))
+                .collect(),
+        ),
+        SchemaKind::Type(Type::Boolean {}) => ("boolean", None, Vec::new()),
+        SchemaKind::Type(Type::Object(_)) => ("object", None, Vec::new()),
+        SchemaKind::Type(Type::Array(_)) => ("array", None, Vec::new()),
+        SchemaKind::OneOf { .. } => ("oneOf", None, Vec::new()),
+        SchemaKind::AnyOf { .. } => ("anyOf", None, Vec::new()),
+        SchemaKind::AllOf { .. } => ("allOf", None, Vec::new()),
+        SchemaKind::Any(any) => ("any", any.format.as_deref(), Vec::new()),
+    };
+    SchemaDoc {
+        name: None,
+        anchor: None,
+        type_name: schema.to_string(),
+        kind,
+        format: format.map(str::to_owned),
+        title: data.title.clone(),
+        description: data.description.clone(),
+        nullable: data.nullable,
+        deprecated: data.deprecated,
+        read_only: data.read_only,
+        write_only: data.write_only,
+        enumeration,
+        default: data.default.clone(),
+        example: data.example.clone(),
+        properties: Vec::new(),
+        additional_properties: None,
+        items: None,
+        variants: Vec::new(),
+        recursive: false,
+    }
+}
+
+fn item_doc(components: &Components, schema: &Schema, inlining: &mut Vec<String>) -> SchemaDoc {
+    let mut doc = summary_doc(schema);
+    let (properties, required, additional, items, variants) = match &schema.schema_kind {
+        SchemaKind::Type(Type::Object(object)) => (
+            Some(&object.properties),
+            &object.required[..],
+            object.additional_properties.as_ref(),
+            None,
+            &[][..],
+        ),
+        SchemaKind::Type(Type::Array(array)) => (None, &[][..], None, Some(&array.items), &[][..]),
+        SchemaKind::OneOf { one_of: variants }
+        | SchemaKind::AnyOf { any_of: variants }
+        | SchemaKind::AllOf { all_of: variants } => (None, &[][..], None, None, &variants[..]),
+        SchemaKind::Any(any) => (
+            Some(&any.properties),
+            &any.required[..],
+            any.additional_properties.as_ref(),
+            any.items.as_ref(),
+            &[][..],
+        ),
+        SchemaKind::Type(_) => (None, &[][..], None, None, &[][..]),
+    };
+    doc.properties = properties
+        .into_iter()
+        .flatten()
+        .map(|(name, schema)| PropertyDoc {
+            name: name.to_string(),
+            required: required.contains(name),
+            schema: boxed_doc(components, schema, inlining),
+        })
+        .collect();
+    if let Some(AdditionalProperties::Schema(values)) = additional {
+        doc.additional_properties = Some(Box::new(schema_doc(components, values, inlining)));
+    }
+    doc.items = items.map(|items| Box::new(boxed_doc(components, items, inlining)));
+    doc.variants = variants
+        .iter()
+        .map(|variant| schema_doc(components, variant, inlining))
+        .collect();
+    doc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inlines_schemas_for_templates() {
+        let api: OpenAPI = serde_yaml::from_str(
+            "
+openapi: 3.0.0
+info: {title: Trees, version: '1'}
+paths:
+  /trees/{id}:
+    parameters:
+      - {name: id, in: path, required: true, schema: {type: integer, format: int64}}
+    put:
+      operationId: putTree
+      parameters:
+        - {name: dry, in: query, deprecated: true, schema: {type: boolean}}
+      requestBody:
+        required: true
+        content:
+          application/json:
+            schema: {$ref: '#/components/schemas/Tree'}
+            example: {name: oak}
+      responses:
+        204: {description: Saved.}
+        default: {$ref: '#/components/responses/Error'}
+components:
+  responses:
+    Error:
+      description: Failed.
+      content:
+        text/plain: {schema: {type: string, enum: [bad, worse]}}
+  schemas:
+    Tree:
+      type: object
+      required: [name]
+      properties:
+        children: {type: array, items: {$ref: '#/components/schemas/Tree'}}
+        name: {type: string}
+",
+        )
+        .unwrap();
+        let documentation = Documentation::new(&api);
+        assert_eq!(documentation.tags.len(), 1);
+        assert_eq!(documentation.tags[0].name, "default");
+        let operation = &documentation.tags[0].operations[0];
+        assert_eq!(operation.anchor, "put-treesid");
+
+        let parameters: Vec<_> = operation
+            .parameters
+            .iter()
+            .map(|p| {
+                (
+                    p.name.as_str(),
+                    p.location.as_str(),
+                    p.required,
+                    p.deprecated,
+                )
+            })
+            .collect();
+        assert_eq!(
+            parameters,
+            [("id", "path", true, false), ("dry", "query", false, true)]
+        );
+        let id = operation.parameters[0].schema.as_ref().unwrap();
+        assert_eq!((id.kind, id.format.as_deref()), ("integer", Some("int64")));
+
+        let body = operation.request_body.as_ref().unwrap();
+        assert!(body.required);
+        assert_eq!(
+            body.content[0].example.as_deref(),
+            Some("{\n  \"name\": \"oak\"\n}")
+        );
+        let tree = body.content[0].schema.as_ref().unwrap();
+        assert_eq!(tree.type_name, "Tree");
+        assert!(!tree.recursive);
+        let children = &tree.properties[0];
+        assert_eq!(
+            (children.name.as_str(), children.required),
+            ("children", false)
+        );
+        assert_eq!(children.schema.type_name, "Tree[]");
+        let child = children.schema.items.as_ref().unwrap();
+        assert!(child.recursive);
+        assert_eq!(child.anchor.as_deref(), Some("schema-tree"));
+        assert!(child.properties.is_empty());
+
+        let statuses: Vec<_> = operation.responses.iter().map(|r| &r.status[..]).collect();
+        assert_eq!(statuses, ["204", "default"]);
+        let error = operation.responses[1].content[0].schema.as_ref().unwrap();
+        assert_eq!(
+            error.enumeration,
+            [Value::from("bad"), Value::from("worse")]
+        );
+
+        assert_eq!(documentation.schemas.len(), 1);
+        assert_eq!(&documentation.schemas[0], tree);
+    }
+}
@@ -1,4 +1,5 @@
 use crate::*;
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
@@ -15,9 +16,12 @@ pub struct RequestBody {
     /// multiple keys, only the most specific key is applicable.
     ///  e.g. text/plain overrides text/*
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
-    pub content: BTreeMap<String, MediaType>,
+    pub content: BTreeMap<MediaTypeRange, MediaType>,
     /// Determines if the request body is required in the
     /// request. Defaults to false.
     #[serde(default, skip_serializing_if = "is_false")]
     pub required: bool,
+    /// Inline extensions to this object.
+    #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
+    pub extensions: IndexMap<String, serde_json::Value>,
 }
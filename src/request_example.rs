@@ -0,0 +1,389 @@
+use crate::util::percent_encode_except;
+use crate::*;
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// What [`Operation::example_request`] needs to know of the operation
+/// beyond the operation itself, and what to include in the request.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExampleRequestOptions<'a> {
+    pub method: Method,
+    /// The path of the operation, such as `/pets/{id}`.
+    pub path: &'a str,
+    /// The path item of the operation, whose parameters apply to it too.
+    pub path_item: Option<&'a PathItem>,
+    /// The components that references resolve against.
+    pub components: Option<&'a Components>,
+    /// The media type of the body, if the operation accepts several;
+    /// otherwise JSON is preferred, then the first declared.
+    pub content_type: Option<&'a str>,
+    /// Whether optional parameters are given values too, rather than
+    /// only required ones.
+    pub optional_parameters: bool,
+}
+
+impl<'a> ExampleRequestOptions<'a> {
+    pub fn new(method: Method, path: &'a str) -> Self {
+        ExampleRequestOptions {
+            method,
+            path,
+            path_item: None,
+            components: None,
+            content_type: None,
+            optional_parameters: false,
+        }
+    }
+}
+
+/// A request to an operation with sample values, as documentation shows
+/// them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpRequestExample {
+    pub method: Method,
+    /// The URL of the server, followed by the path and query string.
+    pub url: String,
+    /// The headers, by name and value, `Content-Type` and `Cookie`
+    /// included.
+    pub headers: Vec<(String, String)>,
+    pub body: Option<String>,
+}
+
+impl HttpRequestExample {
+    /// The request as a `curl` command, its arguments quoted for POSIX
+    /// shells and one to a line.
+    ///
+    /// ```
+    /// use openapiv3::{HttpRequestExample, Method};
+    ///
+    /// let request = HttpRequestExample {
+    ///     method: Method::Post,
+    ///     url: "https://example.com/pets".to_owned(),
+    ///     headers: vec![("Content-Type".to_owned(), "text/plain".to_owned())],
+    ///     body: Some("Rex's".to_owned()),
+    /// };
+    /// assert_eq!(
+    ///     request.to_curl(),
+    ///     "curl -X POST 'https://example.com/pets' \\\n  \
+    ///      -H 'Content-Type: text/plain' \\\n  \
+    ///      --data-raw 'Rex'\\''s'"
+    /// );
+    /// ```
+    pub fn to_curl(&self) -> String {
+        let mut curl = format!("curl -X {} {}", self.method, quote(&self.url));
+        for (name, value) in &self.headers {
+            curl.push_str(" \\\n  -H ");
+            curl.push_str(&quote(&format!("{}: {}", name, value)));
+        }
+        if let Some(body) = &self.body {
+            curl.push_str(" \\\n  --data-raw ");
+            curl.push_str(&quote(body));
+        }
+        curl
+    }
+}
+
+/// `text` in single quotes, as POSIX shells read it literally.
+fn quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "'\\''"))
+}
+
+impl Operation {
+    /// A request to the operation at `server`, with sample values for its
+    /// parameters and body.
+    ///
+    /// A value is the example of the parameter or media type, its first
+    /// example, or else one made up from its schema by
+    /// [`Schema::example_value`]. Values are serialized as the parameters
+    /// describe; bodies as JSON, forms, multipart forms, XML or text, as
+    /// their media type says. Server variables take their defaults.
+    /// Parameters that cannot be resolved, or have no sample value, are
+    /// left out.
+    ///
+    /// ```
+    /// use openapiv3::{ExampleRequestOptions, Method, OpenAPI};
+    ///
+    /// let api: OpenAPI = serde_yaml::from_str(
+    ///     "
+    /// openapi: 3.0.0
+    /// info: {title: t, version: '1'}
+    /// servers: [{url: 'https://example.com/v1'}]
+    /// paths:
+    ///   /pets/{id}:
+    ///     put:
+    ///       parameters:
+    ///         - {name: id, in: path, required: true, schema: {type: integer}}
+    ///         - {name: dry-run, in: query, required: true, schema: {type: boolean}}
+    ///         - {name: X-Trace, in: header, example: abc, schema: {type: string}}
+    ///       requestBody:
+    ///         content:
+    ///           application/json:
+    ///             example: {name: Rex}
+    ///       responses: {}
+    /// ",
+    /// )
+    /// .unwrap();
+    /// let (_, _, operation) = api.operations().next().unwrap();
+    /// let mut options = ExampleRequestOptions::new(Method::Put, "/pets/{id}");
+    /// options.optional_parameters = true;
+    /// let request = operation.example_request(&api.servers[0], &options);
+    /// assert_eq!(request.url, "https://example.com/v1/pets/0?dry-run=true");
+    /// assert_eq!(
+    ///     request.headers,
+    ///     [
+    ///         ("X-Trace".to_owned(), "abc".to_owned()),
+    ///         ("Content-Type".to_owned(), "application/json".to_owned()),
+    ///     ]
+    /// );
+    /// assert_eq!(request.body.as_deref(), Some("{\n  \"name\": \"Rex\"\n}"));
+    /// ```
+    pub fn example_request(
+        &self,
+        server: &Server,
+        options: &ExampleRequestOptions<'_>,
+    ) -> HttpRequestExample {
+        let default_components = Components::default();
+        let components = options.components.unwrap_or(&default_components);
+        let no_path_item = PathItem::default();
+        let path_item = options.path_item.unwrap_or(&no_path_item);
+
+        let mut path = options.path.to_owned();
+        let mut query = Vec::new();
+        let mut headers = Vec::new();
+        let mut cookies = Vec::new();
+        for parameter in self.effective_parameters(path_item, components) {
+            let data = parameter.parameter_data_ref();
+            if !data.required && !options.optional_parameters {
+                continue;
+            }
+            let value = match parameter_sample(components, data) {
+                Some(value) => value,
+                None => continue,
+            };
+            let encoded = parameter.encode(&value);
+            match parameter {
+                Parameter::Path { .. } => {
+                    path = path.replace(&format!("{{{}}}", data.name), &encoded);
+                }
+                Parameter::Query { .. } => query.push(encoded),
+                Parameter::Header { .. } => headers.push((data.name.clone(), encoded)),
+                Parameter::Cookie { .. } => cookies.push(encoded),
+            }
+        }
+        if !cookies.is_empty() {
+            headers.push(("Cookie".to_owned(), cookies.join("; ")));
+        }
+
+        let base = server.default_url().unwrap_or_else(|_| server.url.clone());
+        let mut url = format!("{}{}", base.trim_end_matches('/'), path);
+        if !query.is_empty() {
+            url.push('?');
+            url.push_str(&query.join("&"));
+        }
+
+        let mut body = None;
+        let request_body = self
+            .request_body
+            .as_ref()
+            .and_then(|body| components.resolve(body).ok());
+        if let Some((content_type, media_type)) =
+            request_body.and_then(|body| pick_media_type(&body.content, options.content_type))
+        {
+            let value = sample(
+                components,
+                &media_type.example,
+                &media_type.examples,
+                media_type.schema.as_ref(),
+            );
+            let (content_type, text) = serialize_body(components, content_type, media_type, value);
+            headers.push(("Content-Type".to_owned(), content_type));
+            body = Some(text);
+        }
+
+        HttpRequestExample {
+            method: options.method,
+            url,
+            headers,
+            body,
+        }
+    }
+}
+
+/// The media type to send a body as: the one asked for, else JSON, else
+/// the first declared.
+fn pick_media_type<'c>(
+    content: &'c Content,
+    wanted: Option<&str>,
+) -> Option<(&'c MediaTypeRange, &'c MediaType)> {
+    let find = |found: &dyn Fn(&MediaTypeRange) -> bool| {
+        content.iter().find(|(content_type, _)| found(content_type))
+    };
+    wanted
+        .and_then(|wanted| find(&|content_type| content_type.as_str().eq_ignore_ascii_case(wanted)))
+        .or_else(|| find(&MediaTypeRange::is_json))
+        .or_else(|| content.iter().next())
+}
+
+fn parameter_sample(components: &Components, data: &ParameterData) -> Option<Value> {
+    match &data.format {
+        ParameterSchemaOrContent::Schema(schema) => {
+            sample(components, &data.example, &data.examples, Some(schema))
+        }
+        ParameterSchemaOrContent::Content(content) => {
+            let (_, media_type) = content.iter().next()?;
+            sample(components, &data.example, &data.examples, None).or_else(|| {
+                sample(
+                    components,
+                    &media_type.example,
+                    &media_type.examples,
+                    media_type.schema.as_ref(),
+                )
+            })
+        }
+    }
+}
+
+/// The example, the value of the first of the examples, or a value made up
+/// from the schema.
+pub(crate) fn sample(
+    components: &Components,
+    example: &Option<Value>,
+    examples: &BTreeMap<String, ReferenceOr<Example>>,
+    schema: Option<&ReferenceOr<Schema>>,
+) -> Option<Value> {
+    example
+        .clone()
+        .or_else(|| {
+            examples
+                .values()
+                .find_map(|example| components.resolve(example).ok()?.value.clone())
+        })
+        .or_else(|| {
+            let schema = components.resolve(schema?).ok()?;
+            Some(schema.example_value(components))
+        })
+}
+
+/// The content type and text of a body holding `value`.
+pub(crate) fn serialize_body(
+    components: &Components,
+    range: &MediaTypeRange,
+    media_type: &MediaType,
+    value: Option<Value>,
+) -> (String, String) {
+    let value = match value {
+        Some(value) => value,
+        None => return (range.to_string(), String::new()),
+    };
+    let value = &value;
+    let essence = format!("{}/{}", range.type_(), range.subtype()).to_ascii_lowercase();
+    let schema = media_type
+        .schema
+        .as_ref()
+        .and_then(|schema| components.resolve(schema).ok());
+    let text = if range.is_json() {
+        serde_json::to_string_pretty(value).unwrap_or_default()
+    } else if essence == "application/x-www-form-urlencoded" {
+        form(value)
+    } else if essence.starts_with("multipart/") {
+        let multipart = media_type.encode_multipart(components, value, Vec::new());
+        let text = String::from_utf8_lossy(&multipart.to_bytes()).into_owned();
+        return (multipart.content_type(), text);
+    } else if essence.ends_with("/xml") || essence.ends_with("+xml") {
+        let default_schema = Schema::default();
+        schema
+            .unwrap_or(&default_schema)
+            .to_xml(components, "root", value)
+    } else {
+        match value {
+            Value::String(text) => text.clone(),
+            value => value.to_string(),
+        }
+    };
+    (range.to_string(), text)
+}
+
+/// The properties of an object as a form, arrays repeating their key.
+fn form(value: &Value) -> String {
+    let mut pairs = Vec::new();
+    for (name, value) in value.as_object().into_iter().flatten() {
+        let values = match value {
+            Value::Array(items) => items.iter().collect(),
+            value => vec![value],
+        };
+        for value in values {
+            let text = match value {
+                Value::String(text) => text.clone(),
+                value => value.to_string(),
+            };
+            let mut pair = String::new();
+            percent_encode_except(name, |_| false, &mut pair);
+            pair.push('=');
+            percent_encode_except(&text, |_| false, &mut pair);
+            pairs.push(pair);
+        }
+    }
+    pairs.join("&")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn renders_example_requests() {
+        let api: OpenAPI = serde_yaml::from_str(
+            "
+openapi: 3.0.0
+info: {title: t, version: '1'}
+paths:
+  /pets:
+    parameters:
+      - {name: session, in: cookie, required: true, schema: {type: string, example: s1}}
+    post:
+      parameters:
+        - {name: tags, in: query, schema: {type: array, items: {type: string}}}
+        - {name: page, in: query, required: true, examples: {one: {value: 2}}, schema: {type: integer}}
+      requestBody:
+        $ref: '#/components/requestBodies/Pet'
+      responses: {}
+components:
+  requestBodies:
+    Pet:
+      content:
+        application/x-www-form-urlencoded:
+          schema:
+            type: object
+            properties:
+              name: {type: string, example: Rex T}
+              tags: {type: array, minItems: 2, items: {type: string, example: a}}
+        text/plain: {}
+",
+        )
+        .unwrap();
+        let path_item = match &api.paths["/pets"] {
+            ReferenceOr::Item(path_item) => path_item,
+            ReferenceOr::Reference { .. } => unreachable!(),
+        };
+        let operation = path_item.post.as_ref().unwrap();
+        let server: Server = serde_yaml::from_str(
+            "url: 'http://localhost:{port}/'\nvariables: {port: {default: '80'}}",
+        )
+        .unwrap();
+        let mut options = ExampleRequestOptions::new(Method::Post, "/pets");
+        options.path_item = Some(path_item);
+        options.components = api.components.as_ref();
+
+        let request = operation.example_request(&server, &options);
+        assert_eq!(
+            request.to_curl(),
+            "curl -X POST 'http://localhost:80/pets?page=2' \\\n  \
+             -H 'Cookie: session=s1' \\\n  \
+             -H 'Content-Type: application/x-www-form-urlencoded' \\\n  \
+             --data-raw 'name=Rex%20T&tags=a&tags=a'"
+        );
+
+        options.content_type = Some("text/plain");
+        let request = operation.example_request(&server, &options);
+        assert_eq!(request.body.as_deref(), Some(""));
+    }
+}
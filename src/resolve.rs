@@ -0,0 +1,403 @@
+use crate::reference::unescape_pointer_segment;
+use crate::*;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// The sections of the Components Object that may be the target of a
+/// reference such as `#/components/schemas/Pet`.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ComponentKind {
+    Schemas,
+    Responses,
+    Parameters,
+    Examples,
+    RequestBodies,
+    Headers,
+    SecuritySchemes,
+    Links,
+    Callbacks,
+}
+
+impl ComponentKind {
+    /// Every kind of component, in the order they are declared in the
+    /// Components Object.
+    pub const ALL: [ComponentKind; 9] = [
+        ComponentKind::SecuritySchemes,
+        ComponentKind::Responses,
+        ComponentKind::Parameters,
+        ComponentKind::Examples,
+        ComponentKind::RequestBodies,
+        ComponentKind::Headers,
+        ComponentKind::Schemas,
+        ComponentKind::Links,
+        ComponentKind::Callbacks,
+    ];
+
+    /// The name of the section as it appears in a reference.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ComponentKind::Schemas => "schemas",
+            ComponentKind::Responses => "responses",
+            ComponentKind::Parameters => "parameters",
+            ComponentKind::Examples => "examples",
+            ComponentKind::RequestBodies => "requestBodies",
+            ComponentKind::Headers => "headers",
+            ComponentKind::SecuritySchemes => "securitySchemes",
+            ComponentKind::Links => "links",
+            ComponentKind::Callbacks => "callbacks",
+        }
+    }
+}
+
+impl fmt::Display for ComponentKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ComponentKind {
+    type Err = ResolveError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ComponentKind::ALL
+            .iter()
+            .copied()
+            .find(|kind| kind.as_str() == s)
+            .ok_or_else(|| ResolveError::Unsupported(s.to_owned()))
+    }
+}
+
+/// An object that can be declared in the Components Object
+/// and referenced from elsewhere in the document.
+pub trait Component: Sized {
+    /// The section of the Components Object holding objects of this type.
+    const KIND: ComponentKind;
+
+    /// Returns the map of reusable objects of this type.
+    fn section(components: &Components) -> &Map<String, ReferenceOr<Self>>;
+
+    /// Returns the map of reusable objects of this type, for modification.
+    fn section_mut(components: &mut Components) -> &mut Map<String, ReferenceOr<Self>>;
+}
+
+macro_rules! impl_component {
+    ($ty:ty, $kind:ident, $field:ident) => {
+        impl Component for $ty {
+            const KIND: ComponentKind = ComponentKind::$kind;
+
+            fn section(components: &Components) -> &Map<String, ReferenceOr<Self>> {
+                &components.$field
+            }
+
+            fn section_mut(components: &mut Components) -> &mut Map<String, ReferenceOr<Self>> {
+                &mut components.$field
+            }
+        }
+    };
+}
+
+impl_component!(Schema, Schemas, schemas);
+impl_component!(Response, Responses, responses);
+impl_component!(Parameter, Parameters, parameters);
+impl_component!(Example, Examples, examples);
+impl_component!(RequestBody, RequestBodies, request_bodies);
+impl_component!(Header, Headers, headers);
+impl_component!(SecurityScheme, SecuritySchemes, security_schemes);
+impl_component!(Link, Links, links);
+impl_component!(Callback, Callbacks, callbacks);
+
+/// Calls a function generic over [`Component`] with the type
+/// corresponding to a runtime `ComponentKind`.
+macro_rules! with_component_type {
+    ($kind:expr, $f:ident ( $($arg:expr),* )) => {
+        match $kind {
+            ComponentKind::Schemas => $f::<Schema>($($arg),*),
+            ComponentKind::Responses => $f::<Response>($($arg),*),
+            ComponentKind::Parameters => $f::<Parameter>($($arg),*),
+            ComponentKind::Examples => $f::<Example>($($arg),*),
+            ComponentKind::RequestBodies => $f::<RequestBody>($($arg),*),
+            ComponentKind::Headers => $f::<Header>($($arg),*),
+            ComponentKind::SecuritySchemes => $f::<SecurityScheme>($($arg),*),
+            ComponentKind::Links => $f::<Link>($($arg),*),
+            ComponentKind::Callbacks => $f::<Callback>($($arg),*),
+        }
+    };
+}
+
+impl Components {
+    /// The names of the components of the given kind, in order.
+    pub(crate) fn names(&self, kind: ComponentKind) -> Vec<&str> {
+        fn names<'a, T: Component + 'a>(components: &'a Components) -> Vec<&'a str> {
+            T::section(components).keys().map(String::as_str).collect()
+        }
+        with_component_type!(kind, names(self))
+    }
+
+    /// Whether there is a component of the given kind and name.
+    pub(crate) fn contains(&self, kind: ComponentKind, name: &str) -> bool {
+        fn contains<T: Component>(components: &Components, name: &str) -> bool {
+            T::section(components).contains_key(name)
+        }
+        with_component_type!(kind, contains(self, name))
+    }
+
+    /// Removes the component of the given kind and name, returning
+    /// whether there was one.
+    pub(crate) fn remove(&mut self, kind: ComponentKind, name: &str) -> bool {
+        fn remove<T: Component>(components: &mut Components, name: &str) -> bool {
+            remove_entry(T::section_mut(components), name).is_some()
+        }
+        with_component_type!(kind, remove(self, name))
+    }
+
+    /// Renames the components of the given kind that `renames` has new
+    /// names for, keeping their places.
+    pub(crate) fn rename(&mut self, kind: ComponentKind, renames: &BTreeMap<String, String>) {
+        fn rename<T: Component>(components: &mut Components, renames: &BTreeMap<String, String>) {
+            let section = T::section_mut(components);
+            *section = std::mem::take(section)
+                .into_iter()
+                .map(|(name, component)| match renames.get(&name) {
+                    Some(new) => (new.clone(), component),
+                    None => (name, component),
+                })
+                .collect();
+        }
+        with_component_type!(kind, rename(self, renames))
+    }
+}
+
+/// The reasons a reference can fail to resolve.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ResolveError {
+    /// The reference points outside of the current document.
+    External(String),
+    /// The reference is not of the form `#/components/{kind}/{name}`.
+    Unsupported(String),
+    /// The reference points at a different kind of component
+    /// than the one being resolved.
+    KindMismatch {
+        reference: String,
+        expected: ComponentKind,
+    },
+    /// No component exists under the referenced name.
+    NotFound(String),
+    /// The reference leads back to itself through other references.
+    Cycle(String),
+}
+
+impl fmt::Display for ResolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ResolveError::External(r) => write!(f, "external reference `{}` cannot be resolved", r),
+            ResolveError::Unsupported(r) => write!(f, "unsupported reference `{}`", r),
+            ResolveError::KindMismatch {
+                reference,
+                expected,
+            } => write!(
+                f,
+                "reference `{}` does not point into `#/components/{}`",
+                reference, expected
+            ),
+            ResolveError::NotFound(r) => write!(f, "reference `{}` not found", r),
+            ResolveError::Cycle(r) => write!(f, "reference `{}` is circular", r),
+        }
+    }
+}
+
+impl Error for ResolveError {}
+
+/// Splits a reference of the form `#/components/{kind}/{name}`
+/// into the kind of component and the (unescaped) name.
+pub fn parse_component_reference(reference: &str) -> Result<(ComponentKind, String), ResolveError> {
+    let pointer = match reference.find('#') {
+        Some(0) => &reference[1..],
+        Some(_) | None => return Err(ResolveError::External(reference.to_owned())),
+    };
+    let unsupported = || ResolveError::Unsupported(reference.to_owned());
+    let mut segments = pointer.split('/');
+    match (
+        segments.next(),
+        segments.next(),
+        segments.next(),
+        segments.next(),
+        segments.next(),
+    ) {
+        (Some(""), Some("components"), Some(kind), Some(name), None) if !name.is_empty() => {
+            let kind = kind.parse().map_err(|_| unsupported())?;
+            Ok((kind, unescape_pointer_segment(name)))
+        }
+        _ => Err(unsupported()),
+    }
+}
+
+impl Components {
+    /// Returns the object a reference points to, following any
+    /// chain of references between components along the way.
+    pub fn resolve<'a, T: Component>(
+        &'a self,
+        reference: &'a ReferenceOr<T>,
+    ) -> Result<&'a T, ResolveError> {
+        match reference {
+            ReferenceOr::Reference { reference } => self.resolve_reference(reference),
+            ReferenceOr::Item(item) => Ok(item),
+        }
+    }
+
+    /// Like [`Components::resolve`], for the boxed references used by nested schemas.
+    pub fn resolve_boxed<'a, T: Component>(
+        &'a self,
+        reference: &'a ReferenceOr<Box<T>>,
+    ) -> Result<&'a T, ResolveError> {
+        match reference {
+            ReferenceOr::Reference { reference } => self.resolve_reference(reference),
+            ReferenceOr::Item(item) => Ok(item),
+        }
+    }
+
+    /// Looks up the component a `$ref` string points to.
+    pub fn resolve_reference<T: Component>(&self, reference: &str) -> Result<&T, ResolveError> {
+        let mut seen: Vec<&str> = Vec::new();
+        let mut current = reference;
+        loop {
+            if seen.contains(&current) {
+                return Err(ResolveError::Cycle(reference.to_owned()));
+            }
+            seen.push(current);
+            let (kind, name) = parse_component_reference(current)?;
+            if kind != T::KIND {
+                return Err(ResolveError::KindMismatch {
+                    reference: current.to_owned(),
+                    expected: T::KIND,
+                });
+            }
+            match T::section(self).get(&name) {
+                Some(ReferenceOr::Item(item)) => return Ok(item),
+                Some(ReferenceOr::Reference { reference }) => current = reference,
+                None => return Err(ResolveError::NotFound(current.to_owned())),
+            }
+        }
+    }
+}
+
+impl OpenAPI {
+    /// Returns the object a reference points to. Only references into
+    /// `#/components` of this document can be resolved.
+    pub fn resolve<'a, T: Component>(
+        &'a self,
+        reference: &'a ReferenceOr<T>,
+    ) -> Result<&'a T, ResolveError> {
+        match reference {
+            ReferenceOr::Reference { reference } => self.resolve_reference(reference),
+            ReferenceOr::Item(item) => Ok(item),
+        }
+    }
+
+    /// Like [`OpenAPI::resolve`], for the boxed references used by nested schemas.
+    pub fn resolve_boxed<'a, T: Component>(
+        &'a self,
+        reference: &'a ReferenceOr<Box<T>>,
+    ) -> Result<&'a T, ResolveError> {
+        match reference {
+            ReferenceOr::Reference { reference } => self.resolve_reference(reference),
+            ReferenceOr::Item(item) => Ok(item),
+        }
+    }
+
+    /// Looks up the component a `$ref` string points to.
+    pub fn resolve_reference<T: Component>(&self, reference: &str) -> Result<&T, ResolveError> {
+        match &self.components {
+            Some(components) => components.resolve_reference(reference),
+            None => {
+                // Still report malformed references as such.
+                parse_component_reference(reference)?;
+                Err(ResolveError::NotFound(reference.to_owned()))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    fn components() -> Components {
+        let mut components = Components::default();
+        components.parameters.insert(
+            "limit".to_owned(),
+            ReferenceOr::Item(Parameter::Query {
+                parameter_data: ParameterData {
+                    name: "limit".to_owned(),
+                    description: None,
+                    required: false,
+                    deprecated: None,
+                    explode: None,
+                    format: ParameterSchemaOrContent::Content(Default::default()),
+                    example: None,
+                    examples: Default::default(),
+                    extensions: Default::default(),
+                },
+                allow_reserved: false,
+                style: QueryStyle::Form,
+                allow_empty_value: None,
+            }),
+        );
+        components.parameters.insert(
+            "alias".to_owned(),
+            ReferenceOr::ref_("#/components/parameters/limit"),
+        );
+        components.parameters.insert(
+            "loop".to_owned(),
+            ReferenceOr::ref_("#/components/parameters/loop"),
+        );
+        components
+    }
+
+    #[test]
+    fn resolves_items_and_chains() {
+        let components = components();
+        let param: &Parameter = components
+            .resolve_reference("#/components/parameters/alias")
+            .unwrap();
+        match param {
+            Parameter::Query { parameter_data, .. } => assert_eq!(parameter_data.name, "limit"),
+            _ => panic!("expected a query parameter"),
+        }
+    }
+
+    #[test]
+    fn reports_errors() {
+        let components = components();
+        assert_eq!(
+            components.resolve_reference::<Parameter>("#/components/parameters/loop"),
+            Err(ResolveError::Cycle(
+                "#/components/parameters/loop".to_owned()
+            ))
+        );
+        assert_eq!(
+            components.resolve_reference::<Parameter>("#/components/parameters/missing"),
+            Err(ResolveError::NotFound(
+                "#/components/parameters/missing".to_owned()
+            ))
+        );
+        assert_eq!(
+            components.resolve_reference::<Schema>("#/components/parameters/limit"),
+            Err(ResolveError::KindMismatch {
+                reference: "#/components/parameters/limit".to_owned(),
+                expected: ComponentKind::Schemas,
+            })
+        );
+        assert_eq!(
+            components.resolve_reference::<Schema>("common.yaml#/components/schemas/Error"),
+            Err(ResolveError::External(
+                "common.yaml#/components/schemas/Error".to_owned()
+            ))
+        );
+        assert_eq!(
+            components.resolve_reference::<Schema>("#/paths/~1pets"),
+            Err(ResolveError::Unsupported("#/paths/~1pets".to_owned()))
+        );
+    }
+}
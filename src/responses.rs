@@ -1,6 +1,11 @@
 use crate::*;
+use indexmap::IndexMap;
+use serde::de::{self, Deserializer, IgnoredAny, IntoDeserializer, MapAccess, Visitor};
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
+use std::fmt;
+use std::iter::FromIterator;
+use std::marker::PhantomData;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
 pub struct Responses {
@@ -23,9 +28,138 @@ pub struct Responses {
     /// If a response range is defined using an explicit code, the
     /// explicit code definition takes precedence over the range
     /// definition for that code.
-    #[serde(flatten)]
+    #[serde(flatten, deserialize_with = "deserialize_responses")]
     #[serde(default)]
-    pub responses: BTreeMap<StatusCode, ReferenceOr<Response>>,
+    pub responses: Map<StatusCode, ReferenceOr<Response>>,
+    /// Inline extensions to this object.
+    #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
+    pub extensions: IndexMap<String, serde_json::Value>,
+}
+
+impl Responses {
+    /// The response for a status code: the one for the code itself, else
+    /// the one for its range, such as `2XX`, else the default.
+    ///
+    /// ```
+    /// use openapiv3::{ReferenceOr, Responses, StatusCode};
+    ///
+    /// let responses: Responses = serde_yaml::from_str(
+    ///     "{204: {$ref: '#/components/responses/Empty'}, 2XX: {$ref: '#/components/responses/Ok'}}",
+    /// )
+    /// .unwrap();
+    /// assert_eq!(
+    ///     responses.get_for(204),
+    ///     Some(&ReferenceOr::ref_("#/components/responses/Empty"))
+    /// );
+    /// assert_eq!(
+    ///     responses.get_for(200),
+    ///     Some(&ReferenceOr::ref_("#/components/responses/Ok"))
+    /// );
+    /// assert_eq!(responses.get_for(404), None);
+    /// ```
+    pub fn get_for(&self, code: u16) -> Option<&ReferenceOr<Response>> {
+        self.responses
+            .get(&StatusCode::Code(code))
+            .or_else(|| self.responses.get(&StatusCode::Range(code / 100)))
+            .or(self.default.as_ref())
+    }
+}
+
+/// Collects the responses for specific status codes, without a default.
+impl FromIterator<(StatusCode, ReferenceOr<Response>)> for Responses {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (StatusCode, ReferenceOr<Response>)>,
+    {
+        Responses {
+            default: None,
+            responses: iter.into_iter().collect(),
+            extensions: IndexMap::new(),
+        }
+    }
+}
+
+/// Deserializes the responses of a Responses Object, leaving out its
+/// extensions, which have no status code.
+pub(crate) fn deserialize_responses<'de, D, T>(
+    deserializer: D,
+) -> Result<Map<StatusCode, T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    struct ResponsesVisitor<T>(PhantomData<T>);
+
+    impl<'de, T: Deserialize<'de>> Visitor<'de> for ResponsesVisitor<T> {
+        type Value = Map<StatusCode, T>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a map from status codes to responses")
+        }
+
+        fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+        where
+            A: MapAccess<'de>,
+        {
+            let mut responses = Map::new();
+            while let Some(key) = map.next_key()? {
+                match key {
+                    ResponseKey::Status(code) => {
+                        let response =
+                            crate::parse_error::descend(code.to_string(), || map.next_value())?;
+                        responses.insert(code, response);
+                    }
+                    ResponseKey::Extension => {
+                        map.next_value::<IgnoredAny>()?;
+                    }
+                }
+            }
+            Ok(responses)
+        }
+    }
+
+    deserializer.deserialize_map(ResponsesVisitor(PhantomData))
+}
+
+/// A key of a Responses Object other than `default`.
+enum ResponseKey {
+    Status(StatusCode),
+    Extension,
+}
+
+impl<'de> Deserialize<'de> for ResponseKey {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct ResponseKeyVisitor;
+
+        impl<'de> Visitor<'de> for ResponseKeyVisitor {
+            type Value = ResponseKey;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a status code or an extension")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<ResponseKey, E> {
+                if value.starts_with("x-") {
+                    Ok(ResponseKey::Extension)
+                } else {
+                    StatusCode::deserialize(value.into_deserializer()).map(ResponseKey::Status)
+                }
+            }
+
+            fn visit_i64<E: de::Error>(self, value: i64) -> Result<ResponseKey, E> {
+                StatusCode::deserialize(value.into_deserializer()).map(ResponseKey::Status)
+            }
+
+            fn visit_u64<E: de::Error>(self, value: u64) -> Result<ResponseKey, E> {
+                StatusCode::deserialize(value.into_deserializer()).map(ResponseKey::Status)
+            }
+        }
+
+        deserializer.deserialize_any(ResponseKeyVisitor)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
@@ -47,11 +181,15 @@ pub struct Response {
     /// only the most specific key is applicable. e.g. text/plain
     /// overrides text/*
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
-    pub content: BTreeMap<String, ReferenceOr<MediaType>>,
+    pub content: BTreeMap<MediaTypeRange, ReferenceOr<MediaType>>,
 
     /// A map of operations links that can be followed from the response.
     /// The key of the map is a short name for the link, following
     /// the naming constraints of the names for Component Objects.
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub links: BTreeMap<String, ReferenceOr<Link>>,
+
+    /// Inline extensions to this object.
+    #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
+    pub extensions: IndexMap<String, serde_json::Value>,
 }
@@ -0,0 +1,219 @@
+use crate::util::percent_decode;
+use crate::*;
+use std::error::Error;
+use std::fmt;
+
+/// Finds the operation of a document that handles a request, by its method
+/// and path.
+///
+/// Paths are matched as [`PathTemplate`]s, with concrete segments taking
+/// precedence over templated ones, so that `/pets/mine` is matched before
+/// `/pets/{id}` whatever their order in the document. Paths are matched
+/// as declared, relative to the servers of the document: the path of a
+/// request for `https://example.com/v1/pets` is `/pets` given a server
+/// `https://example.com/v1`.
+///
+/// ```
+/// use openapiv3::{Method, OpenAPI, OpenApiRouter};
+///
+/// let api: OpenAPI = serde_yaml::from_str(
+///     "
+/// openapi: 3.0.0
+/// info: {title: t, version: '1'}
+/// paths:
+///   /pets/{id}: {get: {operationId: getPet, responses: {}}}
+///   /pets/mine: {get: {operationId: getMine, responses: {}}}
+/// ",
+/// )
+/// .unwrap();
+/// let router = OpenApiRouter::new(&api);
+///
+/// let route = router.route(Method::Get, "/pets/a%20b").unwrap();
+/// assert_eq!(route.operation.operation_id.as_deref(), Some("getPet"));
+/// assert_eq!(route.parameters, vec![("id", "a b".to_owned())]);
+///
+/// let route = router.route(Method::Get, "/pets/mine").unwrap();
+/// assert_eq!(route.operation.operation_id.as_deref(), Some("getMine"));
+/// assert!(router.route(Method::Get, "/owners").is_err());
+/// ```
+#[derive(Debug, Clone)]
+pub struct OpenApiRouter<'a> {
+    /// The routes, most specific first.
+    routes: Vec<Route<'a>>,
+}
+
+#[derive(Debug, Clone)]
+struct Route<'a> {
+    path: &'a str,
+    template: PathTemplate,
+    /// The variables of the template, borrowed from the document.
+    variables: Vec<&'a str>,
+    item: &'a PathItem,
+}
+
+/// The operation that handles a request, as found by an [`OpenApiRouter`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct RouteMatch<'a> {
+    /// The path of the Paths Object that matched, such as `/pets/{id}`.
+    pub path: &'a str,
+    pub path_item: &'a PathItem,
+    pub method: Method,
+    pub operation: &'a Operation,
+    /// The value of each variable of the path, in order, percent-decoded.
+    pub parameters: Vec<(&'a str, String)>,
+}
+
+/// Why an [`OpenApiRouter`] found no operation for a request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RouteError {
+    /// No path of the document matches.
+    NotFound,
+    /// Paths match, but none has an operation for the method. Holds the
+    /// methods that they do have operations for.
+    MethodNotAllowed(Vec<Method>),
+}
+
+impl fmt::Display for RouteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RouteError::NotFound => f.write_str("no path matches the request"),
+            RouteError::MethodNotAllowed(allowed) => {
+                f.write_str("method not allowed, expected one of")?;
+                for (i, method) in allowed.iter().enumerate() {
+                    let separator = if i == 0 { " " } else { ", " };
+                    write!(f, "{}{}", separator, method)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Error for RouteError {}
+
+impl<'a> OpenApiRouter<'a> {
+    /// Compiles the paths of a document. Path items that are references,
+    /// and paths that are not well-formed templates, are left out.
+    pub fn new(api: &'a OpenAPI) -> Self {
+        let mut routes: Vec<Route<'a>> = api
+            .paths
+            .iter()
+            .filter_map(|(path, item)| match item {
+                ReferenceOr::Item(item) => Some(Route {
+                    path,
+                    template: PathTemplate::parse(path).ok()?,
+                    variables: path
+                        .split('{')
+                        .skip(1)
+                        .filter_map(|rest| rest.find('}').map(|end| &rest[..end]))
+                        .collect(),
+                    item,
+                }),
+                ReferenceOr::Reference { .. } => None,
+            })
+            .collect();
+        routes.sort_by_cached_key(|route| precedence(route.path));
+        OpenApiRouter { routes }
+    }
+
+    /// Finds the operation for a request with the given method and path.
+    /// The path should not include a query string.
+    pub fn route(&self, method: Method, path: &str) -> Result<RouteMatch<'a>, RouteError> {
+        let mut allowed = Vec::new();
+        for route in &self.routes {
+            let values = match route.template.matches(path) {
+                Some(values) => values,
+                None => continue,
+            };
+            match route.item.operation(method) {
+                Some(operation) => {
+                    return Ok(RouteMatch {
+                        path: route.path,
+                        path_item: route.item,
+                        method,
+                        operation,
+                        parameters: route
+                            .variables
+                            .iter()
+                            .zip(values)
+                            .map(|(name, (_, value))| {
+                                let value =
+                                    percent_decode(value).unwrap_or_else(|| value.to_owned());
+                                (*name, value)
+                            })
+                            .collect(),
+                    })
+                }
+                None => allowed.extend(route.item.operations().map(|(method, _)| method)),
+            }
+        }
+        if allowed.is_empty() {
+            Err(RouteError::NotFound)
+        } else {
+            allowed.sort();
+            allowed.dedup();
+            Err(RouteError::MethodNotAllowed(allowed))
+        }
+    }
+}
+
+impl OpenAPI {
+    /// Compiles the paths of the document into a router; see
+    /// [`OpenApiRouter`].
+    pub fn router(&self) -> OpenApiRouter<'_> {
+        OpenApiRouter::new(self)
+    }
+}
+
+/// Orders paths so that, segment by segment, concrete segments come before
+/// templated ones.
+fn precedence(path: &str) -> Vec<bool> {
+    path.split('/')
+        .map(|segment| segment.contains('{'))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn routes_requests() {
+        let api: OpenAPI = serde_yaml::from_str(
+            "
+openapi: 3.0.0
+info: {title: t, version: '1'}
+paths:
+  /pets/{id}/photos/{photo}: {get: {operationId: getPhoto, responses: {}}}
+  /pets/{id}/photos/latest: {put: {operationId: putLatest, responses: {}}}
+  /pets/{id: {get: {operationId: malformed, responses: {}}}
+  /pets: {get: {responses: {}}, post: {responses: {}}}
+",
+        )
+        .unwrap();
+        let router = api.router();
+
+        let route = router.route(Method::Get, "/pets/7/photos/latest").unwrap();
+        assert_eq!(route.path, "/pets/{id}/photos/{photo}");
+        assert_eq!(
+            route.parameters,
+            vec![("id", "7".to_owned()), ("photo", "latest".to_owned())]
+        );
+        let route = router.route(Method::Put, "/pets/7/photos/latest").unwrap();
+        assert_eq!(route.operation.operation_id.as_deref(), Some("putLatest"));
+        assert_eq!(route.parameters, vec![("id", "7".to_owned())]);
+
+        assert_eq!(
+            router.route(Method::Delete, "/pets").unwrap_err(),
+            RouteError::MethodNotAllowed(vec![Method::Get, Method::Post])
+        );
+        assert_eq!(
+            router.route(Method::Get, "/pets/{id").unwrap_err(),
+            RouteError::NotFound
+        );
+        assert_eq!(
+            router.route(Method::Get, "/pets/").unwrap_err(),
+            RouteError::NotFound
+        );
+    }
+}
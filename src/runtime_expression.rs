@@ -0,0 +1,536 @@
+use crate::style::form_pairs;
+use crate::validation::request::Request;
+use crate::validation::response::Response;
+use crate::*;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// A runtime expression, such as `$request.path.id` or
+/// `$response.body#/owner/id`: how links and callbacks refer to a part of
+/// the request and response of an operation.
+///
+/// ```
+/// use openapiv3::{ExpressionSource, RuntimeExpression};
+///
+/// let expression: RuntimeExpression = "$response.body#/owner/id".parse().unwrap();
+/// assert_eq!(
+///     expression,
+///     RuntimeExpression::Response(ExpressionSource::Body(Some("/owner/id".to_owned())))
+/// );
+/// assert_eq!(expression.to_string(), "$response.body#/owner/id");
+/// assert!("$request.cookie.id".parse::<RuntimeExpression>().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RuntimeExpression {
+    /// `$url`: the full URL of the request.
+    Url,
+    /// `$method`: the method of the request.
+    Method,
+    /// `$statusCode`: the status code of the response.
+    StatusCode,
+    /// `$request.`: a part of the request.
+    Request(ExpressionSource),
+    /// `$response.`: a part of the response.
+    Response(ExpressionSource),
+}
+
+/// The part of a request or response a [`RuntimeExpression`] refers to.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ExpressionSource {
+    /// `header.{name}`: a header, by its name in any case.
+    Header(String),
+    /// `query.{name}`: a parameter of the query string.
+    Query(String),
+    /// `path.{name}`: a variable of the path.
+    Path(String),
+    /// `body`, or `body#{pointer}`: the body, or the part of it a JSON
+    /// Pointer such as `/id` refers to.
+    Body(Option<String>),
+}
+
+impl RuntimeExpression {
+    pub fn parse(expression: &str) -> Result<Self, ParseRuntimeExpressionError> {
+        let error = || ParseRuntimeExpressionError(expression.to_owned());
+        let parsed = match expression {
+            "$url" => RuntimeExpression::Url,
+            "$method" => RuntimeExpression::Method,
+            "$statusCode" => RuntimeExpression::StatusCode,
+            _ => {
+                if let Some(source) = expression.strip_prefix("$request.") {
+                    RuntimeExpression::Request(ExpressionSource::parse(source).ok_or_else(error)?)
+                } else if let Some(source) = expression.strip_prefix("$response.") {
+                    RuntimeExpression::Response(ExpressionSource::parse(source).ok_or_else(error)?)
+                } else {
+                    return Err(error());
+                }
+            }
+        };
+        Ok(parsed)
+    }
+
+    /// The value of the expression in an exchange, if it has one.
+    ///
+    /// Headers, query parameters and path variables are strings; several
+    /// headers of the same name are joined with `, `. A body is parsed as
+    /// JSON, or taken as a string if it is not JSON and no pointer is given.
+    /// Responses have neither query parameters nor path variables.
+    pub fn evaluate(&self, exchange: &Exchange<'_>) -> Option<Value> {
+        match self {
+            RuntimeExpression::Url => Some(Value::from(exchange.url)),
+            RuntimeExpression::Method => Some(Value::from(exchange.method.to_string())),
+            RuntimeExpression::StatusCode => Some(Value::from(exchange.response.status)),
+            RuntimeExpression::Request(source) => match source {
+                ExpressionSource::Header(name) => header(&exchange.request.headers, name),
+                ExpressionSource::Query(name) => form_pairs(exchange.request.query)
+                    .into_iter()
+                    .find(|(actual, _)| actual == name)
+                    .map(|(_, value)| Value::from(value)),
+                ExpressionSource::Path(name) => exchange
+                    .request
+                    .path_params
+                    .iter()
+                    .find(|(actual, _)| actual == name)
+                    .map(|(_, value)| Value::from(*value)),
+                ExpressionSource::Body(pointer) => body(exchange.request.body, pointer),
+            },
+            RuntimeExpression::Response(source) => match source {
+                ExpressionSource::Header(name) => header(&exchange.response.headers, name),
+                ExpressionSource::Query(_) | ExpressionSource::Path(_) => None,
+                ExpressionSource::Body(pointer) => body(exchange.response.body, pointer),
+            },
+        }
+    }
+}
+
+impl ExpressionSource {
+    fn parse(source: &str) -> Option<Self> {
+        let nonempty = |name: &str| Some(name.to_owned()).filter(|name| !name.is_empty());
+        if let Some(name) = source.strip_prefix("header.") {
+            let token = name
+                .bytes()
+                .all(|byte| byte.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&byte));
+            nonempty(name)
+                .filter(|_| token)
+                .map(ExpressionSource::Header)
+        } else if let Some(name) = source.strip_prefix("query.") {
+            nonempty(name).map(ExpressionSource::Query)
+        } else if let Some(name) = source.strip_prefix("path.") {
+            nonempty(name).map(ExpressionSource::Path)
+        } else if source == "body" {
+            Some(ExpressionSource::Body(None))
+        } else {
+            let pointer = source.strip_prefix("body#")?;
+            if !pointer.is_empty() && !pointer.starts_with('/') {
+                return None;
+            }
+            Some(ExpressionSource::Body(Some(pointer.to_owned())))
+        }
+    }
+}
+
+fn header(headers: &[(&str, &str)], name: &str) -> Option<Value> {
+    let values: Vec<&str> = headers
+        .iter()
+        .filter(|(actual, _)| actual.eq_ignore_ascii_case(name))
+        .map(|(_, value)| *value)
+        .collect();
+    if values.is_empty() {
+        None
+    } else {
+        Some(Value::from(values.join(", ")))
+    }
+}
+
+fn body(body: &[u8], pointer: &Option<String>) -> Option<Value> {
+    if body.is_empty() {
+        return None;
+    }
+    match (serde_json::from_slice::<Value>(body), pointer) {
+        (Ok(value), Some(pointer)) => value.pointer(pointer).cloned(),
+        (Ok(value), None) => Some(value),
+        (Err(_), Some(_)) => None,
+        (Err(_), None) => Some(Value::from(String::from_utf8_lossy(body).into_owned())),
+    }
+}
+
+impl fmt::Display for RuntimeExpression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RuntimeExpression::Url => f.write_str("$url"),
+            RuntimeExpression::Method => f.write_str("$method"),
+            RuntimeExpression::StatusCode => f.write_str("$statusCode"),
+            RuntimeExpression::Request(source) => write!(f, "$request.{}", source),
+            RuntimeExpression::Response(source) => write!(f, "$response.{}", source),
+        }
+    }
+}
+
+impl fmt::Display for ExpressionSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExpressionSource::Header(name) => write!(f, "header.{}", name),
+            ExpressionSource::Query(name) => write!(f, "query.{}", name),
+            ExpressionSource::Path(name) => write!(f, "path.{}", name),
+            ExpressionSource::Body(None) => f.write_str("body"),
+            ExpressionSource::Body(Some(pointer)) => write!(f, "body#{}", pointer),
+        }
+    }
+}
+
+impl FromStr for RuntimeExpression {
+    type Err = ParseRuntimeExpressionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        RuntimeExpression::parse(s)
+    }
+}
+
+/// A string that is not a runtime expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseRuntimeExpressionError(pub String);
+
+impl fmt::Display for ParseRuntimeExpressionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}` is not a runtime expression", self.0)
+    }
+}
+
+impl Error for ParseRuntimeExpressionError {}
+
+/// A string with runtime expressions embedded between braces, such as
+/// `https://{$request.header.host}/pets/{$response.body#/id}`. Braces that do
+/// not open with `$` are part of the surrounding text.
+///
+/// ```
+/// use openapiv3::ExpressionTemplate;
+///
+/// let template: ExpressionTemplate = "{$url}?id={$response.body#/id}".parse().unwrap();
+/// let expressions: Vec<String> = template.expressions().map(ToString::to_string).collect();
+/// assert_eq!(expressions, vec!["$url", "$response.body#/id"]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ExpressionTemplate {
+    template: String,
+    pieces: Vec<TemplatePiece>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum TemplatePiece {
+    Literal(String),
+    Expression(RuntimeExpression),
+}
+
+impl ExpressionTemplate {
+    /// Parses a template. Each `{$` must be closed by a `}`, and hold a
+    /// runtime expression.
+    pub fn parse(template: &str) -> Result<Self, ParseRuntimeExpressionError> {
+        let mut pieces = Vec::new();
+        let mut literal = String::new();
+        let mut rest = template;
+        while let Some(open) = rest.find("{$") {
+            literal.push_str(&rest[..open]);
+            let close = rest[open..]
+                .find('}')
+                .ok_or_else(|| ParseRuntimeExpressionError(rest[open..].to_owned()))?;
+            let expression = RuntimeExpression::parse(&rest[open + 1..open + close])?;
+            if !literal.is_empty() {
+                pieces.push(TemplatePiece::Literal(std::mem::take(&mut literal)));
+            }
+            pieces.push(TemplatePiece::Expression(expression));
+            rest = &rest[open + close + 1..];
+        }
+        literal.push_str(rest);
+        if !literal.is_empty() {
+            pieces.push(TemplatePiece::Literal(literal));
+        }
+        Ok(ExpressionTemplate {
+            template: template.to_owned(),
+            pieces,
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.template
+    }
+
+    /// The expressions of the template, in order.
+    pub fn expressions(&self) -> impl Iterator<Item = &RuntimeExpression> {
+        self.pieces.iter().filter_map(|piece| match piece {
+            TemplatePiece::Expression(expression) => Some(expression),
+            TemplatePiece::Literal(_) => None,
+        })
+    }
+
+    /// Fills in the expressions of the template with their values in an
+    /// exchange. Strings are written as they are, other values as JSON.
+    ///
+    /// Fails with the first expression without a value.
+    pub fn render(&self, exchange: &Exchange<'_>) -> Result<String, RuntimeExpression> {
+        let mut rendered = String::with_capacity(self.template.len());
+        for piece in &self.pieces {
+            match piece {
+                TemplatePiece::Literal(literal) => rendered.push_str(literal),
+                TemplatePiece::Expression(expression) => match expression.evaluate(exchange) {
+                    Some(Value::String(value)) => rendered.push_str(&value),
+                    Some(value) => rendered.push_str(&value.to_string()),
+                    None => return Err(expression.clone()),
+                },
+            }
+        }
+        Ok(rendered)
+    }
+}
+
+impl fmt::Display for ExpressionTemplate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.template)
+    }
+}
+
+impl FromStr for ExpressionTemplate {
+    type Err = ParseRuntimeExpressionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ExpressionTemplate::parse(s)
+    }
+}
+
+/// A request and the response to it, as seen by a client or a server, that
+/// runtime expressions are evaluated in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Exchange<'r> {
+    /// The full URL of the request, such as `https://example.com/pets?limit=5`.
+    pub url: &'r str,
+    pub method: Method,
+    pub request: Request<'r>,
+    pub response: Response<'r>,
+}
+
+/// The parameters and body to invoke the target of a [`Link`] with, as
+/// evaluated by [`Link::evaluate`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LinkRequest {
+    /// The value of each parameter, by its name as given in the link, which
+    /// may be qualified by its location, such as `path.id`.
+    pub parameters: BTreeMap<String, Value>,
+    pub request_body: Option<Value>,
+}
+
+/// Why a [`Link`] could not be evaluated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EvaluateLinkError {
+    /// The parameter, by its name as given in the link, or `None` for the
+    /// request body.
+    pub parameter: Option<String>,
+    pub kind: EvaluateLinkErrorKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EvaluateLinkErrorKind {
+    /// The value is not a well-formed expression or template.
+    Malformed(ParseRuntimeExpressionError),
+    /// The exchange has no value for an expression.
+    Unavailable(RuntimeExpression),
+}
+
+impl fmt::Display for EvaluateLinkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.parameter {
+            Some(name) => write!(f, "parameter `{}`: ", name)?,
+            None => f.write_str("request body: ")?,
+        }
+        match &self.kind {
+            EvaluateLinkErrorKind::Malformed(error) => error.fmt(f),
+            EvaluateLinkErrorKind::Unavailable(expression) => {
+                write!(f, "`{}` has no value", expression)
+            }
+        }
+    }
+}
+
+impl Error for EvaluateLinkError {}
+
+impl Link {
+    /// Evaluates the parameters and request body of the link in the
+    /// exchange that returned it.
+    ///
+    /// A value that starts with `$` is a runtime expression, and takes the
+    /// value it refers to, keeping its JSON type. A value with expressions
+    /// embedded between braces is rendered as a string, unless it is a
+    /// single embedded expression, which is taken as a bare one. Any other
+    /// value is a string constant.
+    ///
+    /// ```
+    /// use openapiv3::validation::{request::Request, response::Response};
+    /// use openapiv3::{Exchange, Link, Method};
+    /// use serde_json::json;
+    ///
+    /// let link: Link = serde_yaml::from_str(
+    ///     "
+    /// operationId: getOwner
+    /// parameters:
+    ///   ownerId: $response.body#/owner
+    ///   path.petId: $request.path.id
+    ///   label: 'pet {$request.path.id} of {$response.body#/owner}'
+    ///   verbose: 'true'
+    /// ",
+    /// )
+    /// .unwrap();
+    /// let exchange = Exchange {
+    ///     url: "https://example.com/pets/7",
+    ///     method: Method::Get,
+    ///     request: Request::new().path_param("id", "7"),
+    ///     response: Response::new(200).body("application/json", br#"{"owner": 3}"#),
+    /// };
+    /// let evaluated = link.evaluate(&exchange).unwrap();
+    /// assert_eq!(evaluated.parameters["ownerId"], json!(3));
+    /// assert_eq!(evaluated.parameters["path.petId"], json!("7"));
+    /// assert_eq!(evaluated.parameters["label"], json!("pet 7 of 3"));
+    /// assert_eq!(evaluated.parameters["verbose"], json!("true"));
+    /// ```
+    pub fn evaluate(&self, exchange: &Exchange<'_>) -> Result<LinkRequest, EvaluateLinkError> {
+        let mut parameters = BTreeMap::new();
+        for (name, value) in &self.parameters {
+            let value = evaluate_value(value, exchange).map_err(|kind| EvaluateLinkError {
+                parameter: Some(name.clone()),
+                kind,
+            })?;
+            parameters.insert(name.clone(), value);
+        }
+        let request_body = match &self.request_body {
+            Some(value) => {
+                Some(
+                    evaluate_value(value, exchange).map_err(|kind| EvaluateLinkError {
+                        parameter: None,
+                        kind,
+                    })?,
+                )
+            }
+            None => None,
+        };
+        Ok(LinkRequest {
+            parameters,
+            request_body,
+        })
+    }
+}
+
+fn evaluate_value(value: &str, exchange: &Exchange<'_>) -> Result<Value, EvaluateLinkErrorKind> {
+    let expression = if value.starts_with('$') {
+        RuntimeExpression::parse(value).map_err(EvaluateLinkErrorKind::Malformed)?
+    } else {
+        let template =
+            ExpressionTemplate::parse(value).map_err(EvaluateLinkErrorKind::Malformed)?;
+        match template.pieces.as_slice() {
+            [TemplatePiece::Expression(expression)] => expression.clone(),
+            _ => {
+                return template
+                    .render(exchange)
+                    .map(Value::from)
+                    .map_err(EvaluateLinkErrorKind::Unavailable)
+            }
+        }
+    };
+    expression
+        .evaluate(exchange)
+        .ok_or(EvaluateLinkErrorKind::Unavailable(expression))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parses_expressions() {
+        for expression in &[
+            "$url",
+            "$method",
+            "$statusCode",
+            "$request.header.X-Request-Id",
+            "$request.query.q",
+            "$request.path.id",
+            "$request.body",
+            "$request.body#",
+            "$response.body#/a~1b/0",
+        ] {
+            let parsed = RuntimeExpression::parse(expression).unwrap();
+            assert_eq!(parsed.to_string(), *expression);
+        }
+        for malformed in &[
+            "url",
+            "$status",
+            "$request",
+            "$request.path.",
+            "$request.header.a b",
+            "$response.body#id",
+        ] {
+            assert_eq!(
+                RuntimeExpression::parse(malformed),
+                Err(ParseRuntimeExpressionError(malformed.to_string()))
+            );
+        }
+
+        let template = ExpressionTemplate::parse("{\"id\": {$request.path.id}}").unwrap();
+        assert_eq!(template.expressions().count(), 1);
+        assert!(ExpressionTemplate::parse("{$request.path.id").is_err());
+        assert!(ExpressionTemplate::parse("{$request.id}").is_err());
+    }
+
+    #[test]
+    fn evaluates_links() {
+        let exchange = Exchange {
+            url: "https://example.com/pets?limit=5",
+            method: Method::Post,
+            request: Request::new()
+                .query("limit=5&name=a%20b")
+                .body("text/plain", b"Rex"),
+            response: Response::new(201)
+                .header("Location", "/pets/7")
+                .body("application/json", br#"{"id": 7, "tags": ["a"]}"#),
+        };
+        let evaluated = |value: &str| {
+            let link = Link {
+                parameters: vec![("p".to_owned(), value.to_owned())]
+                    .into_iter()
+                    .collect(),
+                ..Link::default()
+            };
+            link.evaluate(&exchange)
+                .map(|evaluated| evaluated.parameters["p"].clone())
+                .map_err(|error| error.to_string())
+        };
+        assert_eq!(evaluated("$method"), Ok(json!("POST")));
+        assert_eq!(evaluated("$statusCode"), Ok(json!(201)));
+        assert_eq!(evaluated("$request.query.name"), Ok(json!("a b")));
+        assert_eq!(evaluated("$request.body"), Ok(json!("Rex")));
+        assert_eq!(evaluated("$response.header.location"), Ok(json!("/pets/7")));
+        assert_eq!(evaluated("$response.body#/tags"), Ok(json!(["a"])));
+        assert_eq!(evaluated("{$response.body#/id}"), Ok(json!(7)));
+        assert_eq!(
+            evaluated("{$url}&id={$response.body#/id}"),
+            Ok(json!("https://example.com/pets?limit=5&id=7"))
+        );
+        assert_eq!(evaluated("{}"), Ok(json!("{}")));
+        assert_eq!(
+            evaluated("$response.query.limit"),
+            Err("parameter `p`: `$response.query.limit` has no value".to_owned())
+        );
+        assert_eq!(
+            evaluated("$request.cookie.id"),
+            Err("parameter `p`: `$request.cookie.id` is not a runtime expression".to_owned())
+        );
+
+        let link = Link {
+            request_body: Some("$request.body#/id".to_owned()),
+            ..Link::default()
+        };
+        assert_eq!(
+            link.evaluate(&exchange).unwrap_err().to_string(),
+            "request body: `$request.body#/id` has no value"
+        );
+    }
+}
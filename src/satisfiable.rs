@@ -0,0 +1,416 @@
+use crate::*;
+use std::fmt;
+
+/// What [`Schema::is_satisfiable`] found.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SatReport {
+    /// Why the schema accepts no value; empty if it may accept some.
+    pub problems: Vec<Unsatisfiable>,
+}
+
+impl SatReport {
+    /// Whether the schema may accept some value: no contradiction was
+    /// found, though there may be ones the checks do not catch.
+    pub fn is_satisfiable(&self) -> bool {
+        self.problems.is_empty()
+    }
+}
+
+/// A contradiction that leaves a schema, and so any schema requiring a
+/// value of it, with no value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Unsatisfiable {
+    /// A JSON Pointer, relative to the schema checked, to the schema that
+    /// contradicts itself, such as `/properties/id`; empty for the schema
+    /// itself.
+    pub location: String,
+    pub kind: UnsatisfiableKind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum UnsatisfiableKind {
+    /// `minLength` is above `maxLength`.
+    Lengths { min: usize, max: usize },
+    /// `minItems` is above `maxItems`.
+    Items { min: usize, max: usize },
+    /// `minProperties`, or the number of required properties, is above
+    /// `maxProperties`.
+    Properties { min: usize, max: usize },
+    /// The bounds and `multipleOf` of numbers admit none.
+    Numbers(NumericConstraintError),
+    /// No value of the `enum` meets the other constraints.
+    Enum,
+    /// A required property is neither declared nor allowed by
+    /// `additionalProperties`.
+    Required(Name),
+    /// No branch of a `oneOf` or `anyOf` accepts a value.
+    Branches(&'static str),
+    /// The branches of an `allOf` contradict each other.
+    AllOf(MergeError),
+}
+
+impl fmt::Display for UnsatisfiableKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UnsatisfiableKind::Lengths { min, max } => {
+                write!(f, "minLength {} is above maxLength {}", min, max)
+            }
+            UnsatisfiableKind::Items { min, max } => {
+                write!(f, "minItems {} is above maxItems {}", min, max)
+            }
+            UnsatisfiableKind::Properties { min, max } => write!(
+                f,
+                "at least {} properties are required, but maxProperties is {}",
+                min, max
+            ),
+            UnsatisfiableKind::Numbers(error) => error.fmt(f),
+            UnsatisfiableKind::Enum => {
+                f.write_str("no value of the enum meets the other constraints")
+            }
+            UnsatisfiableKind::Required(name) => write!(
+                f,
+                "required property `{}` is not allowed by additionalProperties",
+                name
+            ),
+            UnsatisfiableKind::Branches(keyword) => {
+                write!(f, "no branch of `{}` accepts a value", keyword)
+            }
+            UnsatisfiableKind::AllOf(error) => {
+                write!(f, "the branches of allOf contradict each other: {}", error)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Unsatisfiable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.location.is_empty() {
+            self.kind.fmt(f)
+        } else {
+            write!(f, "{}: {}", self.location, self.kind)
+        }
+    }
+}
+
+impl Schema {
+    /// Looks for contradictions that leave the schema with no value,
+    /// resolving references in `components`: bounds on lengths, sizes and
+    /// numbers that admit none, `enum`s none of whose values meet the
+    /// other constraints, required properties that `additionalProperties:
+    /// false` forbids, and `allOf`s whose branches cannot be merged as by
+    /// [`Schema::flatten_all_of`].
+    ///
+    /// Schemas that require a value of an unsatisfiable one, through
+    /// required properties, non-empty arrays or every branch of a `oneOf`
+    /// or `anyOf`, are unsatisfiable too; the report gives the contradiction
+    /// at the bottom. `nullable` schemas always accept `null`, and
+    /// references that do not resolve or recurse are assumed satisfiable.
+    ///
+    /// ```
+    /// use openapiv3::{Components, Schema};
+    ///
+    /// let schema: Schema = serde_yaml::from_str(
+    ///     "
+    /// type: object
+    /// required: [code]
+    /// properties:
+    ///   code: {type: string, minLength: 5, maxLength: 3}
+    /// ",
+    /// )
+    /// .unwrap();
+    /// let report = schema.is_satisfiable(&Components::default());
+    /// assert!(!report.is_satisfiable());
+    /// assert_eq!(
+    ///     report.problems[0].to_string(),
+    ///     "/properties/code: minLength 5 is above maxLength 3"
+    /// );
+    /// ```
+    pub fn is_satisfiable(&self, components: &Components) -> SatReport {
+        let mut checker = SatChecker {
+            components,
+            location: String::new(),
+            stack: Vec::new(),
+            problems: Vec::new(),
+        };
+        checker.schema(self);
+        SatReport {
+            problems: checker.problems,
+        }
+    }
+}
+
+struct SatChecker<'c> {
+    components: &'c Components,
+    location: String,
+    /// The references being checked, outermost first.
+    stack: Vec<String>,
+    problems: Vec<Unsatisfiable>,
+}
+
+impl SatChecker<'_> {
+    fn problem(&mut self, kind: UnsatisfiableKind) {
+        self.problems.push(Unsatisfiable {
+            location: self.location.clone(),
+            kind,
+        });
+    }
+
+    fn nested<T>(&mut self, segments: &[&str], f: impl FnOnce(&mut Self) -> T) -> T {
+        let len = self.location.len();
+        for segment in segments {
+            self.location.push('/');
+            self.location
+                .push_str(&crate::reference::escape_pointer_segment(segment));
+        }
+        let result = f(self);
+        self.location.truncate(len);
+        result
+    }
+
+    /// Whether the schema may accept a value, reporting why not.
+    fn boxed<T: std::borrow::Borrow<Schema>>(&mut self, schema: &ReferenceOr<T>) -> bool {
+        let reference = match schema {
+            ReferenceOr::Item(schema) => return self.schema(schema.borrow()),
+            ReferenceOr::Reference { reference } => reference.as_str(),
+        };
+        if self.stack.iter().any(|r| r == reference) {
+            return true;
+        }
+        let components = self.components;
+        match components.resolve_reference::<Schema>(reference) {
+            Ok(schema) => {
+                self.stack.push(reference.to_owned());
+                let satisfiable = self.schema(schema);
+                self.stack.pop();
+                satisfiable
+            }
+            Err(_) => true,
+        }
+    }
+
+    /// Whether some branch may accept a value, reporting the problems of
+    /// every branch if none does.
+    fn branches(&mut self, keyword: &'static str, branches: &[ReferenceOr<Schema>]) -> bool {
+        let len = self.problems.len();
+        let mut satisfiable = branches.is_empty();
+        for (i, branch) in branches.iter().enumerate() {
+            satisfiable |= self.nested(&[keyword, &i.to_string()], |checker| checker.boxed(branch));
+        }
+        if satisfiable {
+            self.problems.truncate(len);
+        } else {
+            self.problem(UnsatisfiableKind::Branches(keyword));
+        }
+        satisfiable
+    }
+
+    fn schema(&mut self, schema: &Schema) -> bool {
+        if schema.schema_data.nullable {
+            return true;
+        }
+        let len = self.problems.len();
+        if let Some(error) = schema
+            .numeric_constraints()
+            .and_then(|constraints| constraints.check().err())
+        {
+            self.problem(UnsatisfiableKind::Numbers(error));
+        }
+        match &schema.schema_kind {
+            SchemaKind::AllOf { .. } => match schema.flatten_all_of(self.components) {
+                Ok(merged) => {
+                    if let SchemaKind::AllOf { .. } = merged.schema_kind {
+                        return true;
+                    }
+                    return self.schema(&merged);
+                }
+                Err(error) => match error.kind {
+                    MergeErrorKind::Conflict(_) | MergeErrorKind::Unsatisfiable(_) => {
+                        self.problem(UnsatisfiableKind::AllOf(error))
+                    }
+                    _ => return true,
+                },
+            },
+            SchemaKind::OneOf { one_of } => {
+                self.branches("oneOf", one_of);
+            }
+            SchemaKind::AnyOf { any_of } => {
+                self.branches("anyOf", any_of);
+            }
+            SchemaKind::Type(Type::String(string)) => {
+                self.lengths(string.min_length, string.max_length);
+            }
+            SchemaKind::Type(Type::Array(array)) => {
+                self.array(Some(&array.items), array.min_items, array.max_items);
+            }
+            SchemaKind::Type(Type::Object(object)) => self.object(
+                &object.properties,
+                &object.required,
+                object.additional_properties.as_ref(),
+                (object.min_properties, object.max_properties),
+            ),
+            SchemaKind::Any(any) => {
+                self.lengths(any.min_length, any.max_length);
+                self.array(any.items.as_ref(), any.min_items, any.max_items);
+                self.object(
+                    &any.properties,
+                    &any.required,
+                    any.additional_properties.as_ref(),
+                    (any.min_propeties, any.max_properties),
+                );
+            }
+            SchemaKind::Type(_) => {}
+        }
+        if self.problems.len() == len && !self.enumeration(schema) {
+            self.problem(UnsatisfiableKind::Enum);
+        }
+        self.problems.len() == len
+    }
+
+    /// Whether some value of the `enum` of the schema, if it has one,
+    /// meets its other constraints.
+    fn enumeration(&self, schema: &Schema) -> bool {
+        let values: Vec<serde_json::Value> = match &schema.schema_kind {
+            SchemaKind::Type(Type::String(string)) => {
+                string.enumeration.iter().cloned().map(Into::into).collect()
+            }
+            SchemaKind::Type(Type::Number(number)) => {
+                number.enumeration.iter().map(|&n| n.into()).collect()
+            }
+            SchemaKind::Type(Type::Integer(integer)) => {
+                integer.enumeration.iter().map(|&n| n.into()).collect()
+            }
+            _ => return true,
+        };
+        values.is_empty()
+            || values
+                .iter()
+                .any(|value| schema.validate_value_in(self.components, value).is_ok())
+    }
+
+    fn lengths(&mut self, min: Option<usize>, max: Option<usize>) {
+        if let Some((min, max)) = min.zip(max).filter(|(min, max)| min > max) {
+            self.problem(UnsatisfiableKind::Lengths { min, max });
+        }
+    }
+
+    fn array(
+        &mut self,
+        items: Option<&ReferenceOr<Box<Schema>>>,
+        min: Option<usize>,
+        max: Option<usize>,
+    ) {
+        if let Some((min, max)) = min.zip(max).filter(|(min, max)| min > max) {
+            self.problem(UnsatisfiableKind::Items { min, max });
+        } else if let Some(items) = items.filter(|_| min.unwrap_or(0) > 0) {
+            self.nested(&["items"], |checker| checker.boxed(items));
+        }
+    }
+
+    fn object(
+        &mut self,
+        properties: &Map<Name, ReferenceOr<Box<Schema>>>,
+        required: &[Name],
+        additional_properties: Option<&AdditionalProperties>,
+        (min, max): (Option<usize>, Option<usize>),
+    ) {
+        let least = min.unwrap_or(0).max(required.len());
+        if let Some(max) = max.filter(|&max| least > max) {
+            self.problem(UnsatisfiableKind::Properties { min: least, max });
+        }
+        for name in required {
+            match properties.get(name) {
+                Some(property) => {
+                    self.nested(&["properties", name], |checker| checker.boxed(property));
+                }
+                None => match additional_properties {
+                    Some(AdditionalProperties::None) => {
+                        self.problem(UnsatisfiableKind::Required(name.clone()))
+                    }
+                    Some(AdditionalProperties::Schema(schema)) => {
+                        self.nested(&["additionalProperties"], |checker| checker.boxed(schema));
+                    }
+                    _ => {}
+                },
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn finds_contradictions() {
+        let components: Components = serde_yaml::from_str(
+            "
+schemas:
+  Node:
+    type: object
+    required: [next]
+    properties: {next: {$ref: '#/components/schemas/Node'}}
+  Empty: {type: array, minItems: 2, maxItems: 1}
+",
+        )
+        .unwrap();
+        let problems = |source: &str| -> Vec<String> {
+            let schema: Schema = serde_yaml::from_str(source).unwrap();
+            let report = schema.is_satisfiable(&components);
+            assert_eq!(report.is_satisfiable(), report.problems.is_empty());
+            report.problems.iter().map(ToString::to_string).collect()
+        };
+
+        assert_eq!(
+            problems(
+                "
+type: object
+required: [a, b, c, d]
+maxProperties: 3
+additionalProperties: false
+properties:
+  a: {type: integer, minimum: 3, maximum: 1}
+  b: {type: string, enum: [x, yy], minLength: 3}
+  c: {type: array, minItems: 1, items: {$ref: '#/components/schemas/Empty'}}
+  optional: {type: string, minLength: 2, maxLength: 1}
+"
+            ),
+            [
+                "at least 4 properties are required, but maxProperties is 3",
+                "/properties/a: no number is at least 3 and at most 1",
+                "/properties/b: no value of the enum meets the other constraints",
+                "/properties/c/items: minItems 2 is above maxItems 1",
+                "required property `d` is not allowed by additionalProperties",
+            ]
+        );
+        assert_eq!(
+            problems(
+                "
+allOf:
+  - {type: string}
+  - {type: integer}
+"
+            ),
+            ["the branches of allOf contradict each other: the branches have conflicting `type`"]
+        );
+        assert_eq!(
+            problems(
+                "
+oneOf:
+  - {type: string, minLength: 2, maxLength: 1}
+  - {$ref: '#/components/schemas/Empty'}
+"
+            ),
+            [
+                "/oneOf/0: minLength 2 is above maxLength 1",
+                "/oneOf/1: minItems 2 is above maxItems 1",
+                "no branch of `oneOf` accepts a value",
+            ]
+        );
+        assert!(
+            problems("{required: [n], properties: {n: {$ref: '#/components/schemas/Node'}}}")
+                .is_empty()
+        );
+        assert!(problems("{anyOf: [{type: string, maxLength: 1}, {type: integer}]}").is_empty());
+        assert!(problems("{type: integer, nullable: true, minimum: 2, maximum: 1}").is_empty());
+    }
+}
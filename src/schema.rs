@@ -1,8 +1,8 @@
 use crate::*;
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
-use std::collections::BTreeMap;
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct SchemaData {
     #[serde(default, skip_serializing_if = "is_false")]
@@ -25,9 +25,16 @@ pub struct SchemaData {
     pub discriminator: Option<Discriminator>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub default: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub xml: Option<Xml>,
+    /// Inline extensions to this object.
+    #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
+    pub extensions: IndexMap<String, serde_json::Value>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+/// Schemas, and the objects that may contain them, are `PartialEq` but
+/// not `Eq`, as numeric keywords such as `minimum` are floating point.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
 pub struct Schema {
     #[serde(flatten)]
     pub schema_data: SchemaData,
@@ -54,6 +61,13 @@ pub enum SchemaKind {
     Any(AnySchema),
 }
 
+/// A schema without any constraints, which accepts every value.
+impl Default for SchemaKind {
+    fn default() -> Self {
+        SchemaKind::Any(AnySchema::default())
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(tag = "type", rename_all = "lowercase")]
 pub enum Type {
@@ -65,13 +79,98 @@ pub enum Type {
     Boolean {},
 }
 
+/// What `additionalProperties` allows of the properties of an object not
+/// named in `properties`.
+///
+/// ```
+/// use openapiv3::{AdditionalProperties, Schema};
+///
+/// let closed: AdditionalProperties = serde_json::from_str("false").unwrap();
+/// assert_eq!(closed, AdditionalProperties::None);
+/// assert!(!closed.allows_extra_keys());
+/// assert_eq!(serde_json::to_string(&AdditionalProperties::Any).unwrap(), "true");
+///
+/// let typed: AdditionalProperties = serde_json::from_str(r#"{"type": "string"}"#).unwrap();
+/// assert!(typed.allows_extra_keys() && typed.schema().is_some());
+/// ```
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-#[serde(untagged)]
+#[serde(from = "AdditionalPropertiesRepr", into = "AdditionalPropertiesRepr")]
 pub enum AdditionalProperties {
-    Any(bool),
+    /// Any additional properties are allowed, written `true`.
+    Any,
+    /// No additional properties are allowed, written `false`.
+    None,
+    /// Additional properties are allowed if they match the schema.
+    Schema(Box<ReferenceOr<Schema>>),
+}
+
+/// Additional properties are allowed, as when the keyword is absent.
+impl Default for AdditionalProperties {
+    fn default() -> Self {
+        AdditionalProperties::Any
+    }
+}
+
+impl AdditionalProperties {
+    /// Whether any property not named in `properties` may be present, if
+    /// only with a value matching the schema.
+    pub fn allows_extra_keys(&self) -> bool {
+        !matches!(self, AdditionalProperties::None)
+    }
+
+    /// The schema additional properties must match, if one is given.
+    pub fn schema(&self) -> Option<&ReferenceOr<Schema>> {
+        match self {
+            AdditionalProperties::Schema(schema) => Some(schema),
+            _ => None,
+        }
+    }
+}
+
+/// `true` allows any additional properties, and `false` none.
+impl From<bool> for AdditionalProperties {
+    fn from(allowed: bool) -> Self {
+        if allowed {
+            AdditionalProperties::Any
+        } else {
+            AdditionalProperties::None
+        }
+    }
+}
+
+impl From<ReferenceOr<Schema>> for AdditionalProperties {
+    fn from(schema: ReferenceOr<Schema>) -> Self {
+        AdditionalProperties::Schema(Box::new(schema))
+    }
+}
+
+/// How `additionalProperties` is written: as a boolean or a schema.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+enum AdditionalPropertiesRepr {
+    Bool(bool),
     Schema(Box<ReferenceOr<Schema>>),
 }
 
+impl From<AdditionalPropertiesRepr> for AdditionalProperties {
+    fn from(repr: AdditionalPropertiesRepr) -> Self {
+        match repr {
+            AdditionalPropertiesRepr::Bool(allowed) => allowed.into(),
+            AdditionalPropertiesRepr::Schema(schema) => AdditionalProperties::Schema(schema),
+        }
+    }
+}
+
+impl From<AdditionalProperties> for AdditionalPropertiesRepr {
+    fn from(additional: AdditionalProperties) -> Self {
+        match additional {
+            AdditionalProperties::Any => AdditionalPropertiesRepr::Bool(true),
+            AdditionalProperties::None => AdditionalPropertiesRepr::Bool(false),
+            AdditionalProperties::Schema(schema) => AdditionalPropertiesRepr::Schema(schema),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct AnySchema {
@@ -87,10 +186,10 @@ pub struct AnySchema {
     pub minimum: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub maximum: Option<f64>,
-    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
-    pub properties: BTreeMap<String, ReferenceOr<Box<Schema>>>,
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub properties: Map<Name, ReferenceOr<Box<Schema>>>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
-    pub required: Vec<String>,
+    pub required: Vec<Name>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub additional_properties: Option<AdditionalProperties>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -107,6 +206,10 @@ pub struct AnySchema {
     pub unique_items: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub format: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_length: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<usize>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
@@ -118,6 +221,10 @@ pub struct StringType {
     pub pattern: Option<String>,
     #[serde(rename = "enum", default, skip_serializing_if = "Vec::is_empty")]
     pub enumeration: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_length: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<usize>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
@@ -161,10 +268,10 @@ pub struct IntegerType {
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct ObjectType {
-    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
-    pub properties: BTreeMap<String, ReferenceOr<Box<Schema>>>,
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub properties: Map<Name, ReferenceOr<Box<Schema>>>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
-    pub required: Vec<String>,
+    pub required: Vec<Name>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub additional_properties: Option<AdditionalProperties>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -185,21 +292,51 @@ pub struct ArrayType {
     pub unique_items: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+/// An array of any items.
+impl Default for ArrayType {
+    fn default() -> Self {
+        ArrayType {
+            items: ReferenceOr::boxed_item(Schema::default()),
+            min_items: None,
+            max_items: None,
+            unique_items: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum NumberFormat {
     Float,
     Double,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+impl NumberFormat {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            NumberFormat::Float => "float",
+            NumberFormat::Double => "double",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum IntegerFormat {
     Int32,
     Int64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+impl IntegerFormat {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            IntegerFormat::Int32 => "int32",
+            IntegerFormat::Int64 => "int64",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum StringFormat {
     Date,
@@ -208,3 +345,16 @@ pub enum StringFormat {
     Byte,
     Binary,
 }
+
+impl StringFormat {
+    /// The name of the format as written in documents, such as `date-time`.
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            StringFormat::Date => "date",
+            StringFormat::DateTime => "date-time",
+            StringFormat::Password => "password",
+            StringFormat::Byte => "byte",
+            StringFormat::Binary => "binary",
+        }
+    }
+}
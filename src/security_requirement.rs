@@ -1,4 +1,7 @@
+use crate::*;
 use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
 
 /// Lists the required security schemes to execute this operation.
 /// The name used for each property MUST correspond to a security
@@ -14,3 +17,147 @@ use std::collections::BTreeMap;
 /// Security Requirement Objects in the list needs to be satisfied
 /// to authorize the request.
 pub type SecurityRequirement = BTreeMap<String, Vec<String>>;
+
+/// A security requirement with its schemes looked up in the components, as
+/// returned by [`Operation::effective_security`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedSecurityRequirement<'a> {
+    pub requirement: &'a SecurityRequirement,
+    /// Each scheme of the requirement, by name, along with the scopes it
+    /// requires. Empty for a requirement of no schemes, `{}`, which makes
+    /// authorization optional.
+    pub schemes: Vec<(&'a str, &'a SecurityScheme, &'a [String])>,
+}
+
+/// A security requirement names a scheme that is not declared, or whose
+/// reference does not resolve, in the components.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownSecurityScheme(pub String);
+
+impl fmt::Display for UnknownSecurityScheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "security scheme `{}` is not declared", self.0)
+    }
+}
+
+impl Error for UnknownSecurityScheme {}
+
+impl Operation {
+    /// The security requirements that apply to the operation, any one of
+    /// which authorizes a request: its own if it declares any, otherwise
+    /// those of the document. An operation that declares an empty list
+    /// requires no authorization, whatever the document declares.
+    ///
+    /// ```
+    /// use openapiv3::{Method, OpenAPI};
+    ///
+    /// let api: OpenAPI = serde_yaml::from_str(
+    ///     "
+    /// openapi: 3.0.0
+    /// info: {title: t, version: '1'}
+    /// security: [{key: []}]
+    /// paths:
+    ///   /pets: {get: {responses: {}}, post: {security: [], responses: {}}}
+    /// components:
+    ///   securitySchemes:
+    ///     key: {type: apiKey, name: key, in: header}
+    /// ",
+    /// )
+    /// .unwrap();
+    /// let operation = |method| api.router().route(method, "/pets").unwrap().operation;
+    ///
+    /// let get = operation(Method::Get).effective_security(&api).unwrap();
+    /// assert_eq!(get[0].schemes[0].0, "key");
+    /// let post = operation(Method::Post).effective_security(&api).unwrap();
+    /// assert!(post.is_empty());
+    /// ```
+    pub fn effective_security<'a>(
+        &'a self,
+        root: &'a OpenAPI,
+    ) -> Result<Vec<ResolvedSecurityRequirement<'a>>, UnknownSecurityScheme> {
+        let security = self.security.as_ref().unwrap_or(&root.security);
+        security
+            .iter()
+            .map(|requirement| {
+                let schemes = requirement
+                    .iter()
+                    .map(|(name, scopes)| {
+                        let scheme = root
+                            .components
+                            .as_ref()
+                            .and_then(|components| {
+                                let scheme = components.security_schemes.get(name)?;
+                                components.resolve(scheme).ok()
+                            })
+                            .ok_or_else(|| UnknownSecurityScheme(name.clone()))?;
+                        Ok((name.as_str(), scheme, scopes.as_slice()))
+                    })
+                    .collect::<Result<_, _>>()?;
+                Ok(ResolvedSecurityRequirement {
+                    requirement,
+                    schemes,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_security() {
+        let api: OpenAPI = serde_yaml::from_str(
+            "
+openapi: 3.0.0
+info: {title: t, version: '1'}
+security: [{oauth: [read]}, {}]
+paths:
+  /pets:
+    get: {responses: {}}
+    put: {security: [{oauth: [write], key: []}], responses: {}}
+    delete: {security: [{admin: []}], responses: {}}
+components:
+  securitySchemes:
+    key: {$ref: '#/components/securitySchemes/Key'}
+    Key: {type: apiKey, name: key, in: query}
+    oauth: {type: http, scheme: bearer}
+",
+        )
+        .unwrap();
+        let names = |method| {
+            let route = api.router().route(method, "/pets").unwrap();
+            route
+                .operation
+                .effective_security(&api)
+                .map(|requirements| {
+                    requirements
+                        .iter()
+                        .map(|requirement| {
+                            requirement
+                                .schemes
+                                .iter()
+                                .map(|(name, _, scopes)| format!("{}{:?}", name, scopes))
+                                .collect::<Vec<_>>()
+                        })
+                        .collect::<Vec<_>>()
+                })
+        };
+        assert_eq!(
+            names(Method::Get),
+            Ok(vec![vec!["oauth[\"read\"]".to_owned()], vec![]])
+        );
+        assert_eq!(
+            names(Method::Put),
+            Ok(vec![vec![
+                "key[]".to_owned(),
+                "oauth[\"write\"]".to_owned()
+            ]])
+        );
+        assert_eq!(
+            names(Method::Delete),
+            Err(UnknownSecurityScheme("admin".to_owned()))
+        );
+    }
+}
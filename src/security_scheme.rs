@@ -1,11 +1,16 @@
+use crate::util::percent_encode_except;
+use indexmap::IndexMap;
+use serde::de::{Deserializer, Error as _};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::collections::BTreeMap;
 
 /// Defines a security scheme that can be used by the operations.
 /// Supported schemes are HTTP authentication, an API key (either as a
 /// header or as a query parameter), OAuth2's common flows (implicit, password,
 /// application and access code) as defined in RFC6749, and OpenID Connect Discovery.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(tag = "type")]
 pub enum SecurityScheme {
     #[serde(rename = "apiKey")]
@@ -13,23 +18,37 @@ pub enum SecurityScheme {
         #[serde(rename = "in")]
         location: APIKeyLocation,
         name: String,
+        /// Inline extensions to this object.
+        #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
+        extensions: IndexMap<String, serde_json::Value>,
     },
     #[serde(rename = "http")]
     HTTP {
         scheme: String,
         #[serde(rename = "bearerFormat")]
         bearer_format: Option<String>,
+        /// Inline extensions to this object.
+        #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
+        extensions: IndexMap<String, serde_json::Value>,
     },
     #[serde(rename = "oauth2")]
-    OAuth2 { flows: OAuth2Flows },
+    OAuth2 {
+        flows: OAuth2Flows,
+        /// Inline extensions to this object.
+        #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
+        extensions: IndexMap<String, serde_json::Value>,
+    },
     #[serde(rename = "openIdConnect")]
     OpenIDConnect {
         #[serde(rename = "openIdConnectUrl")]
         open_id_connect_url: String,
+        /// Inline extensions to this object.
+        #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
+        extensions: IndexMap<String, serde_json::Value>,
     },
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum APIKeyLocation {
     #[serde(rename = "query")]
     Query,
@@ -39,7 +58,9 @@ pub enum APIKeyLocation {
     Cookie,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+/// The flows of an OAuth2 security scheme. Each is held by the field of its
+/// kind; the fields are filled by the name of each flow when deserializing.
+#[derive(Debug, Clone, Serialize, Default, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct OAuth2Flows {
     #[serde(flatten)]
@@ -50,9 +71,12 @@ pub struct OAuth2Flows {
     pub client_credentials: Option<OAuth2Flow>,
     #[serde(flatten)]
     pub authorization_code: Option<OAuth2Flow>,
+    /// Inline extensions to this object.
+    #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
+    pub extensions: IndexMap<String, serde_json::Value>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum OAuth2Flow {
     #[serde(rename = "implicit")]
     Implicit {
@@ -62,6 +86,9 @@ pub enum OAuth2Flow {
         refresh_url: Option<String>,
         #[serde(default)]
         scopes: BTreeMap<String, String>,
+        /// Inline extensions to this object.
+        #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
+        extensions: IndexMap<String, serde_json::Value>,
     },
     #[serde(rename = "password")]
     Password {
@@ -71,6 +98,9 @@ pub enum OAuth2Flow {
         token_url: String,
         #[serde(default)]
         scopes: BTreeMap<String, String>,
+        /// Inline extensions to this object.
+        #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
+        extensions: IndexMap<String, serde_json::Value>,
     },
     #[serde(rename = "clientCredentials")]
     ClientCredentials {
@@ -80,6 +110,9 @@ pub enum OAuth2Flow {
         token_url: String,
         #[serde(default)]
         scopes: BTreeMap<String, String>,
+        /// Inline extensions to this object.
+        #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
+        extensions: IndexMap<String, serde_json::Value>,
     },
     #[serde(rename = "authorizationCode")]
     AuthorizationCode {
@@ -91,5 +124,289 @@ pub enum OAuth2Flow {
         refresh_url: Option<String>,
         #[serde(default)]
         scopes: BTreeMap<String, String>,
+        /// Inline extensions to this object.
+        #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
+        extensions: IndexMap<String, serde_json::Value>,
     },
 }
+
+impl<'de> Deserialize<'de> for OAuth2Flows {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // The flows are externally tagged by their names, so each is read
+        // from its own field rather than from the flattened object.
+        let mut fields = IndexMap::<String, Value>::deserialize(deserializer)?;
+        let mut flow = |name: &str| match fields.shift_remove(name) {
+            Some(flow) => {
+                let tagged = Value::Object(std::iter::once((name.to_owned(), flow)).collect());
+                serde_json::from_value(tagged)
+                    .map(Some)
+                    .map_err(D::Error::custom)
+            }
+            None => Ok(None),
+        };
+        Ok(OAuth2Flows {
+            implicit: flow("implicit")?,
+            password: flow("password")?,
+            client_credentials: flow("clientCredentials")?,
+            authorization_code: flow("authorizationCode")?,
+            extensions: fields
+                .into_iter()
+                .filter(|(name, _)| name.starts_with("x-"))
+                .collect(),
+        })
+    }
+}
+
+impl SecurityScheme {
+    /// The scopes the scheme declares, by name, with their descriptions:
+    /// those of every flow of an OAuth2 scheme, and none for other schemes.
+    pub fn scopes(&self) -> BTreeMap<&str, &str> {
+        match self {
+            SecurityScheme::OAuth2 { flows, .. } => flows.scopes(),
+            _ => BTreeMap::new(),
+        }
+    }
+}
+
+impl OAuth2Flows {
+    /// The flows that are declared, in the order of the fields.
+    pub fn iter(&self) -> impl Iterator<Item = &OAuth2Flow> {
+        self.implicit
+            .iter()
+            .chain(&self.password)
+            .chain(&self.client_credentials)
+            .chain(&self.authorization_code)
+    }
+
+    /// The scopes of every flow, by name, with their descriptions. A scope
+    /// declared by several flows has the description of the first.
+    pub fn scopes(&self) -> BTreeMap<&str, &str> {
+        let mut scopes = BTreeMap::new();
+        for flow in self.iter() {
+            for (name, description) in flow.scopes() {
+                scopes.entry(name.as_str()).or_insert(description.as_str());
+            }
+        }
+        scopes
+    }
+
+    /// The flows that declare a scope.
+    pub fn supporting(&self, scope: &str) -> impl Iterator<Item = &OAuth2Flow> + '_ {
+        let scope = scope.to_owned();
+        self.iter()
+            .filter(move |flow| flow.scopes().contains_key(&scope))
+    }
+}
+
+/// A request to the token endpoint of an OAuth2 flow, as made by a client.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenRequestTemplate<'a> {
+    pub token_url: &'a str,
+    /// The `application/x-www-form-urlencoded` body of the request, with
+    /// placeholders for the values the client provides.
+    pub body: String,
+}
+
+impl OAuth2Flow {
+    /// The name of the kind of flow, such as `authorizationCode`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            OAuth2Flow::Implicit { .. } => "implicit",
+            OAuth2Flow::Password { .. } => "password",
+            OAuth2Flow::ClientCredentials { .. } => "clientCredentials",
+            OAuth2Flow::AuthorizationCode { .. } => "authorizationCode",
+        }
+    }
+
+    /// The scopes of the flow, by name, with their descriptions.
+    pub fn scopes(&self) -> &BTreeMap<String, String> {
+        match self {
+            OAuth2Flow::Implicit { scopes, .. }
+            | OAuth2Flow::Password { scopes, .. }
+            | OAuth2Flow::ClientCredentials { scopes, .. }
+            | OAuth2Flow::AuthorizationCode { scopes, .. } => scopes,
+        }
+    }
+
+    pub fn authorization_url(&self) -> Option<&str> {
+        match self {
+            OAuth2Flow::Implicit {
+                authorization_url, ..
+            }
+            | OAuth2Flow::AuthorizationCode {
+                authorization_url, ..
+            } => Some(authorization_url),
+            OAuth2Flow::Password { .. } | OAuth2Flow::ClientCredentials { .. } => None,
+        }
+    }
+
+    pub fn token_url(&self) -> Option<&str> {
+        match self {
+            OAuth2Flow::Password { token_url, .. }
+            | OAuth2Flow::ClientCredentials { token_url, .. }
+            | OAuth2Flow::AuthorizationCode { token_url, .. } => Some(token_url),
+            OAuth2Flow::Implicit { .. } => None,
+        }
+    }
+
+    pub fn refresh_url(&self) -> Option<&str> {
+        match self {
+            OAuth2Flow::Implicit { refresh_url, .. }
+            | OAuth2Flow::Password { refresh_url, .. }
+            | OAuth2Flow::ClientCredentials { refresh_url, .. }
+            | OAuth2Flow::AuthorizationCode { refresh_url, .. } => refresh_url.as_deref(),
+        }
+    }
+
+    /// Whether a client should protect the flow with PKCE (RFC 7636), as
+    /// the authorization code flow should be.
+    pub fn uses_pkce(&self) -> bool {
+        matches!(self, OAuth2Flow::AuthorizationCode { .. })
+    }
+
+    /// The URL a client sends a user to for authorization with the given
+    /// scopes, for the flows that have one. The values the client provides
+    /// are placeholders in braces: `{client_id}`, `{redirect_uri}` and
+    /// `{state}`, along with `{code_challenge}`, by the `S256` method, for
+    /// flows that [use PKCE](OAuth2Flow::uses_pkce).
+    ///
+    /// ```
+    /// use openapiv3::OAuth2Flows;
+    ///
+    /// let flows: OAuth2Flows = serde_yaml::from_str(
+    ///     "
+    /// authorizationCode:
+    ///   authorizationUrl: https://example.com/authorize
+    ///   tokenUrl: https://example.com/token
+    ///   scopes: {read:pets: read your pets}
+    /// ",
+    /// )
+    /// .unwrap();
+    /// let flow = flows.supporting("read:pets").next().unwrap();
+    /// assert_eq!(
+    ///     flow.authorization_url_template(&["read:pets"]).unwrap(),
+    ///     "https://example.com/authorize?response_type=code&client_id={client_id}\
+    ///      &redirect_uri={redirect_uri}&scope=read%3Apets&state={state}\
+    ///      &code_challenge={code_challenge}&code_challenge_method=S256"
+    /// );
+    /// ```
+    pub fn authorization_url_template(&self, scopes: &[&str]) -> Option<String> {
+        let authorization_url = self.authorization_url()?;
+        let response_type = match self {
+            OAuth2Flow::Implicit { .. } => "token",
+            _ => "code",
+        };
+        let separator = if authorization_url.contains('?') {
+            '&'
+        } else {
+            '?'
+        };
+        let mut url = format!(
+            "{}{}response_type={}&client_id={{client_id}}&redirect_uri={{redirect_uri}}",
+            authorization_url, separator, response_type
+        );
+        push_scopes(scopes, &mut url);
+        url.push_str("&state={state}");
+        if self.uses_pkce() {
+            url.push_str("&code_challenge={code_challenge}&code_challenge_method=S256");
+        }
+        Some(url)
+    }
+
+    /// The request a client makes for a token with the given scopes, for
+    /// the flows that have a token endpoint. As with
+    /// [`authorization_url_template`](OAuth2Flow::authorization_url_template),
+    /// the values the client provides are placeholders in braces, among
+    /// them `{code_verifier}` for flows that use PKCE.
+    pub fn token_request_template(&self, scopes: &[&str]) -> Option<TokenRequestTemplate<'_>> {
+        let token_url = self.token_url()?;
+        let mut body = match self {
+            OAuth2Flow::Password { .. } => {
+                "grant_type=password&username={username}&password={password}".to_owned()
+            }
+            OAuth2Flow::ClientCredentials { .. } => "grant_type=client_credentials".to_owned(),
+            _ => "grant_type=authorization_code&code={code}&redirect_uri={redirect_uri}\
+                  &client_id={client_id}&code_verifier={code_verifier}"
+                .to_owned(),
+        };
+        // Scopes were granted with the code.
+        if !self.uses_pkce() {
+            push_scopes(scopes, &mut body);
+        }
+        Some(TokenRequestTemplate { token_url, body })
+    }
+}
+
+/// Appends the `scope` parameter of a request, if there are any scopes.
+fn push_scopes(scopes: &[&str], out: &mut String) {
+    if scopes.is_empty() {
+        return;
+    }
+    out.push_str("&scope=");
+    for (i, scope) in scopes.iter().enumerate() {
+        if i > 0 {
+            out.push_str("%20");
+        }
+        percent_encode_except(scope, |_| false, out);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_flows() {
+        let scheme: SecurityScheme = serde_yaml::from_str(
+            "
+type: oauth2
+flows:
+  implicit:
+    authorizationUrl: https://example.com/authorize?tenant=a
+    scopes: {read: read pets}
+  password:
+    tokenUrl: https://example.com/token
+    scopes: {read: read all pets, write: write pets}
+  x-flow: custom
+",
+        )
+        .unwrap();
+        let flows = match &scheme {
+            SecurityScheme::OAuth2 { flows, .. } => flows,
+            _ => unreachable!(),
+        };
+        assert!(matches!(flows.implicit, Some(OAuth2Flow::Implicit { .. })));
+        assert!(matches!(flows.password, Some(OAuth2Flow::Password { .. })));
+        assert!(flows.authorization_code.is_none());
+        assert_eq!(flows.extensions["x-flow"], "custom");
+
+        let scopes: Vec<(&str, &str)> = scheme.scopes().into_iter().collect();
+        assert_eq!(scopes, vec![("read", "read pets"), ("write", "write pets")]);
+        let supporting: Vec<&str> = flows.supporting("read").map(OAuth2Flow::name).collect();
+        assert_eq!(supporting, vec!["implicit", "password"]);
+
+        let implicit = flows.implicit.as_ref().unwrap();
+        assert_eq!(
+            implicit.authorization_url_template(&[]).unwrap(),
+            "https://example.com/authorize?tenant=a&response_type=token\
+             &client_id={client_id}&redirect_uri={redirect_uri}&state={state}"
+        );
+        assert!(implicit.token_request_template(&[]).is_none());
+        let password = flows.password.as_ref().unwrap();
+        assert_eq!(
+            password.token_request_template(&["read", "write"]),
+            Some(TokenRequestTemplate {
+                token_url: "https://example.com/token",
+                body: "grant_type=password&username={username}&password={password}\
+                       &scope=read%20write"
+                    .to_owned(),
+            })
+        );
+
+        let value = serde_json::to_value(&scheme).unwrap();
+        assert_eq!(
+            serde_json::from_value::<SecurityScheme>(value).unwrap(),
+            scheme
+        );
+    }
+}
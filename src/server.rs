@@ -1,9 +1,13 @@
 use crate::*;
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
-use std::collections::BTreeMap;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::fmt;
 
 /// An object representing a Server.
-#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
 pub struct Server {
     /// REQUIRED. A URL to the target host.
     /// This URL supports Server Variables and MAY be relative,
@@ -21,4 +25,500 @@ pub struct Server {
     /// The value is used for substitution in the server's URL template.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub variables: Option<BTreeMap<String, ServerVariable>>,
+    /// Inline extensions to this object.
+    #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
+    pub extensions: IndexMap<String, serde_json::Value>,
+}
+
+impl Server {
+    /// The names of the variables between braces in the URL, in order of
+    /// first use, each of which must be declared in `variables`.
+    ///
+    /// ```
+    /// use openapiv3::{Server, ServerUrlError};
+    ///
+    /// let server: Server = serde_yaml::from_str(
+    ///     "
+    /// url: 'https://{region}.example.com:{port}/{region}'
+    /// variables: {region: {default: eu}}
+    /// ",
+    /// )
+    /// .unwrap();
+    /// assert_eq!(
+    ///     server.variables_in_url(),
+    ///     Err(ServerUrlError::UndeclaredVariable("port".to_owned()))
+    /// );
+    /// ```
+    pub fn variables_in_url(&self) -> Result<Vec<&str>, ServerUrlError> {
+        let mut names = Vec::new();
+        for piece in self.pieces()? {
+            if let UrlPiece::Variable(name) = piece {
+                if self.variable(name).is_none() {
+                    return Err(ServerUrlError::UndeclaredVariable(name.to_owned()));
+                }
+                if !names.contains(&name) {
+                    names.push(name);
+                }
+            }
+        }
+        Ok(names)
+    }
+
+    /// The URL with each variable replaced by its value in `vars`, or by its
+    /// default if it has none there. Values must be among those enumerated
+    /// for their variable, if any are. Values are substituted as they are,
+    /// so that a variable may stand for several segments of a path.
+    ///
+    /// ```
+    /// use openapiv3::Server;
+    /// use std::collections::HashMap;
+    ///
+    /// let server: Server = serde_yaml::from_str(
+    ///     "
+    /// url: 'https://{region}.example.com/{version}'
+    /// variables:
+    ///   region: {default: eu, enum: [eu, us]}
+    ///   version: {default: v1}
+    /// ",
+    /// )
+    /// .unwrap();
+    /// assert_eq!(server.default_url().unwrap(), "https://eu.example.com/v1");
+    ///
+    /// let vars: HashMap<&str, &str> = vec![("region", "us")].into_iter().collect();
+    /// assert_eq!(server.url_with(&vars).unwrap(), "https://us.example.com/v1");
+    ///
+    /// let vars: HashMap<&str, &str> = vec![("region", "ap")].into_iter().collect();
+    /// assert_eq!(
+    ///     server.url_with(&vars).unwrap_err().to_string(),
+    ///     "`ap` is not one of the values of server variable `region`: `eu`, `us`"
+    /// );
+    /// ```
+    pub fn url_with(&self, vars: &HashMap<&str, &str>) -> Result<String, ServerUrlError> {
+        let mut url = String::with_capacity(self.url.len());
+        for piece in self.pieces()? {
+            match piece {
+                UrlPiece::Literal(literal) => url.push_str(literal),
+                UrlPiece::Variable(name) => {
+                    let variable = self
+                        .variable(name)
+                        .ok_or_else(|| ServerUrlError::UndeclaredVariable(name.to_owned()))?;
+                    let value = vars.get(name).copied().unwrap_or(&variable.default);
+                    if !variable.enumeration.is_empty()
+                        && !variable.enumeration.iter().any(|allowed| allowed == value)
+                    {
+                        return Err(ServerUrlError::NotInEnum {
+                            variable: name.to_owned(),
+                            value: value.to_owned(),
+                            allowed: variable.enumeration.clone(),
+                        });
+                    }
+                    url.push_str(value);
+                }
+            }
+        }
+        Ok(url)
+    }
+
+    /// The URL with each variable replaced by its default.
+    pub fn default_url(&self) -> Result<String, ServerUrlError> {
+        self.url_with(&HashMap::new())
+    }
+
+    /// The URL with each variable replaced by its value in `vars` or its
+    /// default, as by [`url_with`](Server::url_with), and resolved against
+    /// `base` if it is relative, as a URI reference is (RFC 3986). The base is
+    /// the URL the document was retrieved from.
+    ///
+    /// ```
+    /// use openapiv3::Server;
+    ///
+    /// let server = |url: &str| Server {
+    ///     url: url.to_owned(),
+    ///     ..Server::default()
+    /// };
+    /// let base = "https://example.com/docs/openapi.yaml";
+    /// assert_eq!(server("/api/v2").resolve(base, &Default::default()).unwrap(), "https://example.com/api/v2");
+    /// assert_eq!(server("../v1").resolve(base, &Default::default()).unwrap(), "https://example.com/v1");
+    /// assert_eq!(server("//cdn.example.com").resolve(base, &Default::default()).unwrap(), "https://cdn.example.com");
+    /// ```
+    pub fn resolve(
+        &self,
+        base: &str,
+        vars: &HashMap<&str, &str>,
+    ) -> Result<String, ServerUrlError> {
+        Ok(resolve_url(base, &self.url_with(vars)?))
+    }
+
+    fn variable(&self, name: &str) -> Option<&ServerVariable> {
+        self.variables.as_ref()?.get(name)
+    }
+
+    fn pieces(&self) -> Result<Vec<UrlPiece<'_>>, ServerUrlError> {
+        let malformed = || ServerUrlError::Malformed(self.url.clone());
+        let mut pieces = Vec::new();
+        let mut rest = self.url.as_str();
+        while let Some(open) = rest.find('{') {
+            if rest[..open].contains('}') {
+                return Err(malformed());
+            }
+            let close = rest[open..].find('}').ok_or_else(malformed)? + open;
+            let name = &rest[open + 1..close];
+            if name.is_empty() || name.contains('{') {
+                return Err(malformed());
+            }
+            pieces.push(UrlPiece::Literal(&rest[..open]));
+            pieces.push(UrlPiece::Variable(name));
+            rest = &rest[close + 1..];
+        }
+        if rest.contains('}') {
+            return Err(malformed());
+        }
+        pieces.push(UrlPiece::Literal(rest));
+        Ok(pieces)
+    }
+}
+
+impl Operation {
+    /// The servers of the operation: its own if it has any, otherwise those
+    /// of its path item, otherwise those of the document, otherwise a single
+    /// server at `/`, as the specification defaults to.
+    pub fn effective_servers<'a>(
+        &'a self,
+        item: &'a PathItem,
+        root: &'a OpenAPI,
+    ) -> Cow<'a, [Server]> {
+        [&self.servers, &item.servers, &root.servers]
+            .iter()
+            .find(|servers| !servers.is_empty())
+            .map_or_else(
+                || Cow::Owned(vec![root_server()]),
+                |servers| Cow::Borrowed(servers.as_slice()),
+            )
+    }
+
+    /// The URLs of the [effective servers](Operation::effective_servers) of
+    /// the operation, with their variables at their defaults, resolved
+    /// against `base`, the URL the document was retrieved from.
+    ///
+    /// ```
+    /// use openapiv3::{Method, OpenAPI};
+    ///
+    /// let api: OpenAPI = serde_yaml::from_str(
+    ///     "
+    /// openapi: 3.0.0
+    /// info: {title: t, version: '1'}
+    /// servers: [{url: /v1}]
+    /// paths:
+    ///   /pets:
+    ///     servers: [{url: 'https://pets.example.com'}]
+    ///     get: {responses: {}}
+    ///     put: {servers: [{url: ../beta}], responses: {}}
+    ///   /owners: {get: {responses: {}}}
+    /// ",
+    /// )
+    /// .unwrap();
+    /// let base = "https://example.com/docs/openapi.yaml";
+    /// let urls = |method, path| {
+    ///     let route = api.router().route(method, path).unwrap();
+    ///     route.operation.server_urls(route.path_item, &api, base).unwrap()
+    /// };
+    /// assert_eq!(urls(Method::Get, "/pets"), ["https://pets.example.com"]);
+    /// assert_eq!(urls(Method::Put, "/pets"), ["https://example.com/beta"]);
+    /// assert_eq!(urls(Method::Get, "/owners"), ["https://example.com/v1"]);
+    /// ```
+    pub fn server_urls(
+        &self,
+        item: &PathItem,
+        root: &OpenAPI,
+        base: &str,
+    ) -> Result<Vec<String>, ServerUrlError> {
+        let vars = HashMap::new();
+        self.effective_servers(item, root)
+            .iter()
+            .map(|server| server.resolve(base, &vars))
+            .collect()
+    }
+}
+
+impl OpenAPI {
+    /// The URLs of the servers of the document, or of a single server at
+    /// `/` if it has none, resolved as by [`Operation::server_urls`].
+    pub fn server_urls(&self, base: &str) -> Result<Vec<String>, ServerUrlError> {
+        let vars = HashMap::new();
+        if self.servers.is_empty() {
+            return root_server().resolve(base, &vars).map(|url| vec![url]);
+        }
+        self.servers
+            .iter()
+            .map(|server| server.resolve(base, &vars))
+            .collect()
+    }
+}
+
+/// The server of a document that declares none.
+fn root_server() -> Server {
+    Server {
+        url: "/".to_owned(),
+        ..Server::default()
+    }
+}
+
+/// The parts of a URI reference: its scheme, authority, path, query and
+/// fragment, each without its delimiters.
+struct UriParts<'a> {
+    scheme: Option<&'a str>,
+    authority: Option<&'a str>,
+    path: &'a str,
+    query: Option<&'a str>,
+    fragment: Option<&'a str>,
+}
+
+impl<'a> UriParts<'a> {
+    fn parse(uri: &'a str) -> Self {
+        let (rest, fragment) = match uri.split_once('#') {
+            Some((rest, fragment)) => (rest, Some(fragment)),
+            None => (uri, None),
+        };
+        let (rest, query) = match rest.split_once('?') {
+            Some((rest, query)) => (rest, Some(query)),
+            None => (rest, None),
+        };
+        let (scheme, rest) = match rest.split_once(':') {
+            Some((scheme, rest))
+                if scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                    && scheme
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+                    && !scheme.contains('/') =>
+            {
+                (Some(scheme), rest)
+            }
+            _ => (None, rest),
+        };
+        let (authority, path) = match rest.strip_prefix("//") {
+            Some(rest) => {
+                let end = rest.find('/').unwrap_or(rest.len());
+                (Some(&rest[..end]), &rest[end..])
+            }
+            None => (None, rest),
+        };
+        UriParts {
+            scheme,
+            authority,
+            path,
+            query,
+            fragment,
+        }
+    }
+}
+
+/// Resolves a URI reference against a base URI, as by RFC 3986, section 5.2.
+fn resolve_url(base: &str, reference: &str) -> String {
+    let base = UriParts::parse(base);
+    let reference = UriParts::parse(reference);
+    let (scheme, authority, path, query) = if reference.scheme.is_some() {
+        (
+            reference.scheme,
+            reference.authority,
+            remove_dot_segments(reference.path),
+            reference.query,
+        )
+    } else if reference.authority.is_some() {
+        (
+            base.scheme,
+            reference.authority,
+            remove_dot_segments(reference.path),
+            reference.query,
+        )
+    } else if reference.path.is_empty() {
+        (
+            base.scheme,
+            base.authority,
+            base.path.to_owned(),
+            reference.query.or(base.query),
+        )
+    } else if reference.path.starts_with('/') {
+        (
+            base.scheme,
+            base.authority,
+            remove_dot_segments(reference.path),
+            reference.query,
+        )
+    } else {
+        let merged = if base.authority.is_some() && base.path.is_empty() {
+            format!("/{}", reference.path)
+        } else {
+            let directory = base.path.rfind('/').map_or("", |i| &base.path[..=i]);
+            format!("{}{}", directory, reference.path)
+        };
+        (
+            base.scheme,
+            base.authority,
+            remove_dot_segments(&merged),
+            reference.query,
+        )
+    };
+
+    let mut uri = String::new();
+    if let Some(scheme) = scheme {
+        uri.push_str(scheme);
+        uri.push(':');
+    }
+    if let Some(authority) = authority {
+        uri.push_str("//");
+        uri.push_str(authority);
+    }
+    uri.push_str(&path);
+    if let Some(query) = query {
+        uri.push('?');
+        uri.push_str(query);
+    }
+    if let Some(fragment) = reference.fragment {
+        uri.push('#');
+        uri.push_str(fragment);
+    }
+    uri
+}
+
+/// Removes the `.` and `..` segments of a path, as by RFC 3986, section
+/// 5.2.4.
+fn remove_dot_segments(path: &str) -> String {
+    let mut output: Vec<&str> = Vec::new();
+    let segments: Vec<&str> = path.split('/').collect();
+    // The empty segment before the leading slash of an absolute path.
+    let root = usize::from(path.starts_with('/'));
+    for (i, segment) in segments.iter().enumerate() {
+        let last = i + 1 == segments.len();
+        match *segment {
+            "." | ".." => {
+                if *segment == ".." && output.len() > root {
+                    output.pop();
+                }
+                // A path ending in a dot segment still ends in a slash.
+                if last {
+                    output.push("");
+                }
+            }
+            _ => output.push(segment),
+        }
+    }
+    output.join("/")
+}
+
+enum UrlPiece<'a> {
+    Literal(&'a str),
+    Variable(&'a str),
+}
+
+/// Why the URL of a [`Server`] could not be made concrete.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ServerUrlError {
+    /// The braces of the URL do not pair up around variable names.
+    Malformed(String),
+    /// A variable of the URL is not declared in `variables`.
+    UndeclaredVariable(String),
+    /// The value of a variable is not among those it enumerates.
+    NotInEnum {
+        variable: String,
+        value: String,
+        allowed: Vec<String>,
+    },
+}
+
+impl fmt::Display for ServerUrlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ServerUrlError::Malformed(url) => {
+                write!(f, "server URL `{}` has unpaired braces", url)
+            }
+            ServerUrlError::UndeclaredVariable(name) => {
+                write!(f, "server variable `{}` is not declared", name)
+            }
+            ServerUrlError::NotInEnum {
+                variable,
+                value,
+                allowed,
+            } => {
+                write!(
+                    f,
+                    "`{}` is not one of the values of server variable `{}`",
+                    value, variable
+                )?;
+                for (i, allowed) in allowed.iter().enumerate() {
+                    let separator = if i == 0 { ": " } else { ", " };
+                    write!(f, "{}`{}`", separator, allowed)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Error for ServerUrlError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_urls() {
+        let base = "http://a/b/c/d;p?q";
+        for (reference, resolved) in &[
+            ("g:h", "g:h"),
+            ("g", "http://a/b/c/g"),
+            ("./g", "http://a/b/c/g"),
+            ("g/", "http://a/b/c/g/"),
+            ("/g", "http://a/g"),
+            ("//g", "http://g"),
+            ("?y", "http://a/b/c/d;p?y"),
+            ("g?y#s", "http://a/b/c/g?y#s"),
+            ("", "http://a/b/c/d;p?q"),
+            (".", "http://a/b/c/"),
+            ("..", "http://a/b/"),
+            ("../..", "http://a/"),
+            ("../../../g", "http://a/g"),
+            ("/./g", "http://a/g"),
+            ("g/../h", "http://a/b/c/h"),
+        ] {
+            assert_eq!(resolve_url(base, reference), *resolved, "{}", reference);
+        }
+        assert_eq!(resolve_url("specs/openapi.yaml", "../v1"), "v1");
+        assert_eq!(
+            resolve_url("https://example.com", "v1"),
+            "https://example.com/v1"
+        );
+    }
+
+    #[test]
+    fn substitutes_variables() {
+        let server = |url: &str| Server {
+            url: url.to_owned(),
+            variables: Some(
+                vec![(
+                    "base".to_owned(),
+                    ServerVariable {
+                        default: "/api/v2".to_owned(),
+                        ..ServerVariable::default()
+                    },
+                )]
+                .into_iter()
+                .collect(),
+            ),
+            ..Server::default()
+        };
+        assert_eq!(
+            server("https://example.com{base}").default_url(),
+            Ok("https://example.com/api/v2".to_owned())
+        );
+        assert_eq!(server("/plain").variables_in_url(), Ok(Vec::new()));
+        assert_eq!(server("{base}/{base}").variables_in_url(), Ok(vec!["base"]));
+        for malformed in &["{base", "base}", "{}", "{{base}}", "{base}}"] {
+            assert_eq!(
+                server(malformed).default_url(),
+                Err(ServerUrlError::Malformed(malformed.to_string())),
+                "{}",
+                malformed
+            );
+        }
+    }
 }
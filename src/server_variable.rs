@@ -1,8 +1,9 @@
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 
 /// An object representing a Server Variable
 /// for server URL template substitution.
-#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
 pub struct ServerVariable {
     /// An enumeration of string values to be
     /// used if the substitution options are from a limited set.
@@ -19,4 +20,7 @@ pub struct ServerVariable {
     /// variable. CommonMark syntax MAY be used
     /// for rich text representation.
     pub description: Option<String>,
+    /// Inline extensions to this object.
+    #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
+    pub extensions: IndexMap<String, serde_json::Value>,
 }
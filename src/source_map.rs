@@ -0,0 +1,243 @@
+use crate::read::Format;
+use crate::reference::escape_pointer_segment;
+use crate::*;
+use serde::de::DeserializeOwned;
+use std::collections::BTreeMap;
+use std::fmt;
+use yaml_rust::parser::{Event, MarkedEventReceiver, Parser};
+use yaml_rust::scanner::Marker;
+
+/// A position in the source text of a document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Span {
+    /// The line, counting from 1.
+    pub line: usize,
+    /// The column, counting characters from 1.
+    pub column: usize,
+}
+
+impl fmt::Display for Span {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+/// The positions of the values of a document in its source text, by JSON
+/// Pointer.
+///
+/// The position of a field is that of its name, and the position of an
+/// array item that of the item itself. This pairs with the locations that
+/// validation and linting report:
+///
+/// ```
+/// let text = "openapi: 3.0.0\ninfo: {title: t, version: '1'}\npaths:\n  /pets:\n    get:\n      responses: {}\n";
+/// let (api, spans) = openapiv3::OpenAPI::from_str_with_spans(text).unwrap();
+/// let error = &api.validate()[0];
+/// assert_eq!(error.location, "/paths/~1pets/get/responses");
+/// assert_eq!(spans.locate(&error.location).unwrap().to_string(), "line 6, column 7");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SourceMap {
+    spans: BTreeMap<String, Span>,
+}
+
+impl SourceMap {
+    /// Records the position of every value of a JSON or YAML document.
+    pub fn parse(text: &str) -> Result<SourceMap, ReadError> {
+        let mut recorder = Recorder {
+            text: text.chars().collect(),
+            ..Recorder::default()
+        };
+        if Parser::new(text.chars())
+            .load(&mut recorder, false)
+            .is_err()
+        {
+            // serde_yaml reads documents with the same parser, and so
+            // reports the same error.
+            serde_yaml::from_str::<serde_yaml::Value>(text).map_err(ReadError::Yaml)?;
+        }
+        Ok(SourceMap {
+            spans: recorder.spans,
+        })
+    }
+
+    /// The position of the value at `pointer`, such as `/paths/~1pets/get`.
+    pub fn get(&self, pointer: &str) -> Option<Span> {
+        self.spans.get(pointer).copied()
+    }
+
+    /// The position of the value at `pointer`, or of its nearest enclosing
+    /// value that is in the source text, such as when `pointer` names a
+    /// missing field or a value within an alias.
+    pub fn locate(&self, pointer: &str) -> Option<Span> {
+        let mut pointer = pointer;
+        loop {
+            if let Some(span) = self.get(pointer) {
+                return Some(span);
+            }
+            pointer = &pointer[..pointer.rfind('/')?];
+        }
+    }
+
+    /// The recorded positions, ordered by pointer.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, Span)> {
+        self.spans
+            .iter()
+            .map(|(pointer, span)| (pointer.as_str(), *span))
+    }
+}
+
+impl OpenAPI {
+    /// Reads a JSON or YAML document along with the position of each of its
+    /// values; see [`SourceMap`].
+    pub fn from_str_with_spans(text: &str) -> Result<(OpenAPI, SourceMap), ReadError> {
+        with_spans(text)
+    }
+}
+
+impl v3_1::OpenAPI {
+    /// Reads a JSON or YAML document along with the position of each of its
+    /// values; see [`SourceMap`].
+    pub fn from_str_with_spans(text: &str) -> Result<(v3_1::OpenAPI, SourceMap), ReadError> {
+        with_spans(text)
+    }
+}
+
+fn with_spans<T: DeserializeOwned>(text: &str) -> Result<(T, SourceMap), ReadError> {
+    let api = Format::of(text).parse(text)?;
+    Ok((api, SourceMap::parse(text)?))
+}
+
+enum Frame {
+    /// A mapping, with the pointer to it and the key of the value being
+    /// read, if its key has been read.
+    Mapping(String, Option<String>),
+    /// A sequence, with the pointer to it and the index of the next item.
+    Sequence(String, usize),
+}
+
+#[derive(Default)]
+struct Recorder {
+    /// The source text, indexed by the character offsets of markers.
+    text: Vec<char>,
+    spans: BTreeMap<String, Span>,
+    frames: Vec<Frame>,
+    /// The depth of a complex mapping key being skipped, if any.
+    skipping: usize,
+}
+
+impl Recorder {
+    /// Handles the start of a node at `span`, returning its pointer, or
+    /// `None` if it is a key. A node without a span takes that of its
+    /// first key, if it has one.
+    fn node(&mut self, key: Option<&str>, span: Option<Span>) -> Option<String> {
+        let pointer = match self.frames.last_mut() {
+            None => String::new(),
+            Some(Frame::Mapping(at, pending @ None)) => {
+                let span = span?;
+                self.spans.entry(at.clone()).or_insert(span);
+                let key = key.unwrap_or_default();
+                let pointer = format!("{}/{}", at, escape_pointer_segment(key));
+                self.spans.entry(pointer.clone()).or_insert(span);
+                *pending = Some(pointer);
+                return None;
+            }
+            Some(Frame::Mapping(_, pending @ Some(_))) => pending.take().unwrap(),
+            Some(Frame::Sequence(at, index)) => {
+                *index += 1;
+                format!("{}/{}", at, *index - 1)
+            }
+        };
+        if let Some(span) = span {
+            self.spans.entry(pointer.clone()).or_insert(span);
+        }
+        Some(pointer)
+    }
+}
+
+impl MarkedEventReceiver for Recorder {
+    fn on_event(&mut self, event: Event, mark: Marker) {
+        if self.skipping > 0 {
+            match event {
+                Event::MappingStart(_) | Event::SequenceStart(_) => self.skipping += 1,
+                Event::MappingEnd | Event::SequenceEnd => self.skipping -= 1,
+                _ => {}
+            }
+            return;
+        }
+        let span = Span {
+            line: mark.line(),
+            column: mark.col() + 1,
+        };
+        match event {
+            Event::Scalar(value, ..) => {
+                self.node(Some(&value), Some(span));
+            }
+            Event::Alias(_) => {
+                self.node(None, Some(span));
+            }
+            Event::MappingStart(_) => {
+                // The parser marks a block mapping after its first key,
+                // which then gives its position instead.
+                let flow = self.text.get(mark.index()) == Some(&'{');
+                match self.node(None, Some(span).filter(|_| flow)) {
+                    Some(pointer) => self.frames.push(Frame::Mapping(pointer, None)),
+                    None => self.skipping = 1,
+                }
+            }
+            Event::SequenceStart(_) => match self.node(None, Some(span)) {
+                Some(pointer) => self.frames.push(Frame::Sequence(pointer, 0)),
+                None => self.skipping = 1,
+            },
+            Event::MappingEnd | Event::SequenceEnd => {
+                self.frames.pop();
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_positions() {
+        let yaml = "\
+openapi: 3.0.0
+info: {title: t, version: '1'}
+paths:
+  /pets/{id}:
+    get:
+      parameters:
+        - name: id
+          in: path
+        - {name: limit, in: query}
+      responses: {}
+";
+        let spans = SourceMap::parse(yaml).unwrap();
+        let at = |pointer| spans.get(pointer).map(|span| (span.line, span.column));
+        assert_eq!(at(""), Some((1, 1)));
+        assert_eq!(at("/info/version"), Some((2, 18)));
+        assert_eq!(at("/paths/~1pets~1{id}/get"), Some((5, 5)));
+        assert_eq!(at("/paths/~1pets~1{id}/get/parameters/0"), Some((7, 11)));
+        assert_eq!(at("/paths/~1pets~1{id}/get/parameters/1/in"), Some((9, 25)));
+        assert_eq!(at("/paths/~1pets~1{id}/get/responses"), Some((10, 7)));
+        assert_eq!(at("/paths/~1pets~1{id}/get/summary"), None);
+        assert_eq!(
+            spans.locate("/paths/~1pets~1{id}/get/summary"),
+            spans.get("/paths/~1pets~1{id}/get")
+        );
+
+        let json = "{\n  \"openapi\": \"3.0.0\",\n  \"paths\": {\"/pets\": {}}\n}";
+        let spans = SourceMap::parse(json).unwrap();
+        assert_eq!(
+            spans.get("/paths/~1pets"),
+            Some(Span {
+                line: 3,
+                column: 13
+            })
+        );
+        assert!(SourceMap::parse("paths: [").is_err());
+    }
+}
@@ -0,0 +1,291 @@
+use crate::discriminator::target_reference;
+use crate::walk::{Referenceable, VisitRefs, Walk};
+use crate::*;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::convert::Infallible;
+
+/// The name of the root document produced by [`OpenAPI::split`].
+pub const SPLIT_ROOT: &str = "openapi.yaml";
+
+impl OpenAPI {
+    /// Partitions the document into a tree of YAML files, the inverse of
+    /// [`OpenAPI::bundle`], returning the contents of each file by its
+    /// relative path.
+    ///
+    /// Every path item is moved to `paths/{path}.yaml` and every component
+    /// to `components/{kind}/{name}.yaml`, leaving the root document,
+    /// [`SPLIT_ROOT`], with references to them. References between the
+    /// objects are rewritten to relative file references; those to anything
+    /// else in the document are rewritten to point into the root document.
+    pub fn split(&self) -> Result<BTreeMap<String, String>, serde_yaml::Error> {
+        let mut files = BTreeMap::new();
+        let mut root = self.clone();
+
+        let mut taken = HashSet::new();
+        let mut component_files = HashMap::new();
+        if let Some(components) = &self.components {
+            for kind in ComponentKind::ALL.iter().copied() {
+                for name in components.names(kind) {
+                    let file = unique_file(
+                        &mut taken,
+                        &format!("components/{}/", kind),
+                        file_stem(name),
+                    );
+                    component_files.insert((kind, name.to_owned()), file);
+                }
+            }
+        }
+
+        for (path, item) in root.paths.iter_mut() {
+            let stem = file_stem(path.trim_start_matches('/'));
+            let file = unique_file(&mut taken, "paths/", stem);
+            let mut relocator = Relocator {
+                prefix: "../",
+                files: &component_files,
+            };
+            let mut moved = std::mem::replace(item, file_reference(&file));
+            let _ = moved.walk(&mut relocator);
+            files.insert(file, serde_yaml::to_string(&moved)?);
+        }
+
+        if let Some(components) = &mut root.components {
+            split_section::<SecurityScheme>(components, &component_files, &mut files)?;
+            split_section::<Response>(components, &component_files, &mut files)?;
+            split_section::<Parameter>(components, &component_files, &mut files)?;
+            split_section::<Example>(components, &component_files, &mut files)?;
+            split_section::<RequestBody>(components, &component_files, &mut files)?;
+            split_section::<Header>(components, &component_files, &mut files)?;
+            split_section::<Schema>(components, &component_files, &mut files)?;
+            split_section::<Link>(components, &component_files, &mut files)?;
+            split_section::<Callback>(components, &component_files, &mut files)?;
+        }
+
+        files.insert(SPLIT_ROOT.to_owned(), serde_yaml::to_string(&root)?);
+        Ok(files)
+    }
+}
+
+fn split_section<T: Component + Referenceable + Serialize>(
+    components: &mut Components,
+    component_files: &HashMap<(ComponentKind, String), String>,
+    files: &mut BTreeMap<String, String>,
+) -> Result<(), serde_yaml::Error> {
+    for (name, item) in <T as Component>::section_mut(components).iter_mut() {
+        let file = &component_files[&(<T as Component>::KIND, name.clone())];
+        let mut relocator = Relocator {
+            prefix: "../../",
+            files: component_files,
+        };
+        let mut moved = std::mem::replace(item, file_reference(file));
+        let _ = moved.walk(&mut relocator);
+        files.insert(file.clone(), serde_yaml::to_string(&moved)?);
+    }
+    Ok(())
+}
+
+/// Turns a path or component name into something safe to use as
+/// (part of) a file name.
+fn file_stem(name: &str) -> String {
+    let stem: String = name
+        .chars()
+        .filter_map(|c| match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '.' | '-' | '_' => Some(c),
+            '/' => Some('_'),
+            _ => None,
+        })
+        .collect();
+    if stem.is_empty() {
+        "root".to_owned()
+    } else {
+        stem
+    }
+}
+
+/// Picks a file name within `directory` that no other file has.
+fn unique_file(taken: &mut HashSet<String>, directory: &str, stem: String) -> String {
+    let mut file = format!("{}{}.yaml", directory, stem);
+    let mut suffix = 2;
+    while !taken.insert(file.clone()) {
+        file = format!("{}{}_{}.yaml", directory, stem, suffix);
+        suffix += 1;
+    }
+    file
+}
+
+/// A reference, from the root document, to the whole of `file`.
+fn file_reference<T>(file: &str) -> ReferenceOr<T> {
+    ReferenceOr::Reference {
+        reference: format!("./{}", file).parse().unwrap(),
+    }
+}
+
+/// Rewrites the references of an object that is moved to its own file.
+struct Relocator<'a> {
+    /// The way from the file back to the directory of the root document.
+    prefix: &'a str,
+    files: &'a HashMap<(ComponentKind, String), String>,
+}
+
+impl<'a> Relocator<'a> {
+    /// The reference from the new file to what `reference` points to; none
+    /// if it is absolute, and so needs no rewriting.
+    fn relocate(&self, reference: &Reference) -> Option<Reference> {
+        let rewritten = if reference.is_external() {
+            let document = reference.document();
+            if document.starts_with('/') || document.contains("://") {
+                return None;
+            }
+            format!("{}{}", self.prefix, reference)
+        } else {
+            let file = match (reference.component_kind(), reference.name()) {
+                (Some(kind), Some(name)) => self.files.get(&(kind, name.into_owned())),
+                _ => None,
+            };
+            match file {
+                Some(file) => format!("{}{}", self.prefix, file),
+                None => format!("{}{}{}", self.prefix, SPLIT_ROOT, reference),
+            }
+        };
+        Some(rewritten.parse().unwrap())
+    }
+}
+
+impl<'a> VisitRefs for Relocator<'a> {
+    type Error = Infallible;
+
+    fn reference<T: Referenceable>(
+        &mut self,
+        value: &mut ReferenceOr<T>,
+    ) -> Result<(), Infallible> {
+        let reference = match value {
+            ReferenceOr::Item(item) => return item.walk(self),
+            ReferenceOr::Reference { reference } => reference,
+        };
+        if let Some(rewritten) = self.relocate(reference) {
+            *reference = rewritten;
+        }
+        Ok(())
+    }
+
+    fn mapping(&mut self, target: &mut String) -> Result<(), Infallible> {
+        let rewritten = target_reference(target)
+            .parse()
+            .ok()
+            .and_then(|reference| self.relocate(&reference));
+        if let Some(rewritten) = rewritten {
+            *target = rewritten.into();
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn splits_into_files() {
+        let api: OpenAPI = serde_yaml::from_str(
+            "
+openapi: 3.0.0
+info: {title: t, version: '1'}
+paths:
+  /pets/{id}:
+    get:
+      responses:
+        '200':
+          description: a pet
+          content:
+            application/json:
+              schema: {$ref: '#/components/schemas/Pet'}
+components:
+  schemas:
+    Pet:
+      type: object
+      properties:
+        owner: {$ref: 'people.yaml#/Person'}
+        siblings: {type: array, items: {$ref: '#/components/schemas/Pet'}}
+    Animal:
+      oneOf: [{$ref: '#/components/schemas/Pet'}, {$ref: '#/components/schemas/Cat'}]
+      discriminator:
+        propertyName: kind
+        mapping: {pet: '#/components/schemas/Pet', cat: Cat}
+    Cat: {type: object}
+",
+        )
+        .unwrap();
+        let files = api.split().unwrap();
+        assert_eq!(
+            files.keys().collect::<Vec<_>>(),
+            vec![
+                "components/schemas/Animal.yaml",
+                "components/schemas/Cat.yaml",
+                "components/schemas/Pet.yaml",
+                "openapi.yaml",
+                "paths/pets_id.yaml"
+            ]
+        );
+
+        let root: OpenAPI = serde_yaml::from_str(&files["openapi.yaml"]).unwrap();
+        assert_eq!(
+            root.paths["/pets/{id}"],
+            ReferenceOr::ref_("./paths/pets_id.yaml")
+        );
+        assert_eq!(
+            root.components.unwrap().schemas["Pet"],
+            ReferenceOr::ref_("./components/schemas/Pet.yaml")
+        );
+
+        let path = &files["paths/pets_id.yaml"];
+        assert!(path.contains("../components/schemas/Pet.yaml"), "{}", path);
+        let pet = &files["components/schemas/Pet.yaml"];
+        assert!(pet.contains("../../people.yaml#/Person"), "{}", pet);
+        assert!(pet.contains("../../components/schemas/Pet.yaml"), "{}", pet);
+        let animal: Schema =
+            serde_yaml::from_str(&files["components/schemas/Animal.yaml"]).unwrap();
+        let mapping = &animal.schema_data.discriminator.as_ref().unwrap().mapping;
+        assert_eq!(mapping["pet"], "../../components/schemas/Pet.yaml");
+        assert_eq!(mapping["cat"], "../../components/schemas/Cat.yaml");
+
+        // Bundling the files puts the document back together.
+        let mut files = crate::external::InMemory(files);
+        files.0.insert(
+            "people.yaml".to_owned(),
+            "Person: {type: string}".to_owned(),
+        );
+        let mut bundled: OpenAPI = serde_yaml::from_str(&files.0["openapi.yaml"]).unwrap();
+        bundled.bundle("openapi.yaml", &files).unwrap();
+        let expected: OpenAPI = serde_yaml::from_str(
+            "
+openapi: 3.0.0
+info: {title: t, version: '1'}
+paths:
+  /pets/{id}:
+    get:
+      responses:
+        '200':
+          description: a pet
+          content:
+            application/json:
+              schema: {$ref: '#/components/schemas/Pet'}
+components:
+  schemas:
+    Pet:
+      type: object
+      properties:
+        owner: {$ref: '#/components/schemas/Person'}
+        siblings: {type: array, items: {$ref: '#/components/schemas/Pet'}}
+    Animal:
+      oneOf: [{$ref: '#/components/schemas/Pet'}, {$ref: '#/components/schemas/Cat'}]
+      discriminator:
+        propertyName: kind
+        mapping: {pet: '#/components/schemas/Pet', cat: '#/components/schemas/Cat'}
+    Cat: {type: object}
+    Person: {type: string}
+",
+        )
+        .unwrap();
+        assert_eq!(bundled, expected);
+    }
+}
@@ -1,12 +1,64 @@
 use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+use std::error::Error;
 use std::fmt;
+use std::str::FromStr;
 
+/// The status code, or range of them, that a response is described for.
+///
+/// Codes order before ranges, so that iterating over a map keyed by status
+/// code, such as [`Responses::responses`](crate::Responses::responses),
+/// comes across `404` before `2XX`.
 #[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub enum StatusCode {
     Code(u16),
+    /// The codes of a class, such as `2` for `2XX`.
     Range(u16),
 }
 
+impl StatusCode {
+    /// Whether the status code of a response is this one, or in this range.
+    pub fn matches(&self, code: u16) -> bool {
+        match *self {
+            StatusCode::Code(n) => n == code,
+            StatusCode::Range(n) => n == code / 100,
+        }
+    }
+
+    /// The class of the code, such as `2` for `204` and `2XX`.
+    pub fn class(&self) -> u16 {
+        match *self {
+            StatusCode::Code(n) => n / 100,
+            StatusCode::Range(n) => n,
+        }
+    }
+
+    /// Whether the code is, or the range holds, `1XX` codes.
+    pub fn is_informational(&self) -> bool {
+        self.class() == 1
+    }
+
+    /// Whether the code is, or the range holds, `2XX` codes.
+    pub fn is_success(&self) -> bool {
+        self.class() == 2
+    }
+
+    /// Whether the code is, or the range holds, `3XX` codes.
+    pub fn is_redirection(&self) -> bool {
+        self.class() == 3
+    }
+
+    /// Whether the code is, or the range holds, `4XX` codes.
+    pub fn is_client_error(&self) -> bool {
+        self.class() == 4
+    }
+
+    /// Whether the code is, or the range holds, `5XX` codes.
+    pub fn is_server_error(&self) -> bool {
+        self.class() == 5
+    }
+}
+
 impl fmt::Display for StatusCode {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -16,6 +68,67 @@ impl fmt::Display for StatusCode {
     }
 }
 
+/// Parses a code such as `200`, or a range such as `2XX` in either case,
+/// as in the keys of a Responses Object.
+///
+/// ```
+/// use openapiv3::StatusCode;
+///
+/// assert_eq!("404".parse(), Ok(StatusCode::Code(404)));
+/// assert_eq!("5xx".parse(), Ok(StatusCode::Range(5)));
+/// assert!("2XY".parse::<StatusCode>().is_err());
+/// ```
+impl FromStr for StatusCode {
+    type Err = InvalidStatusCode;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || InvalidStatusCode(s.to_owned());
+        match s.as_bytes() {
+            [n, x, y]
+                if n.is_ascii_digit()
+                    && x.eq_ignore_ascii_case(&b'X')
+                    && y.eq_ignore_ascii_case(&b'X') =>
+            {
+                Ok(StatusCode::Range(u16::from(n - b'0')))
+            }
+            [_, _, _] if s.bytes().all(|b| b.is_ascii_digit()) => {
+                StatusCode::try_from(s.parse::<u16>().map_err(|_| invalid())?)
+                    .map_err(|_| invalid())
+            }
+            _ => Err(invalid()),
+        }
+    }
+}
+
+/// Accepts codes between 100 and 999.
+impl TryFrom<u16> for StatusCode {
+    type Error = InvalidStatusCode;
+
+    fn try_from(code: u16) -> Result<Self, Self::Error> {
+        if (100..1000).contains(&code) {
+            Ok(StatusCode::Code(code))
+        } else {
+            Err(InvalidStatusCode(code.to_string()))
+        }
+    }
+}
+
+/// A value that is neither a status code nor a range of them, as written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidStatusCode(pub String);
+
+impl fmt::Display for InvalidStatusCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "`{}` is not a status code between 100 and 999 nor a range such as `2XX`",
+            self.0
+        )
+    }
+}
+
+impl Error for InvalidStatusCode {}
+
 impl<'de> Deserialize<'de> for StatusCode {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -36,7 +149,7 @@ impl<'de> Deserialize<'de> for StatusCode {
             where
                 E: de::Error,
             {
-                if value >= 100 && value < 1000 {
+                if (100..1000).contains(&value) {
                     Ok(StatusCode::Code(value as u16))
                 } else {
                     Err(E::invalid_value(Unexpected::Signed(value), &self))
@@ -47,7 +160,7 @@ impl<'de> Deserialize<'de> for StatusCode {
             where
                 E: de::Error,
             {
-                if value >= 100 && value < 1000 {
+                if (100..1000).contains(&value) {
                     Ok(StatusCode::Code(value as u16))
                 } else {
                     Err(E::invalid_value(Unexpected::Unsigned(value), &self))
@@ -67,7 +180,10 @@ impl<'de> Deserialize<'de> for StatusCode {
                 }
 
                 if !value.is_ascii() {
-                    return Err(E::invalid_value(Unexpected::Str(value), &"ascii, format `\\dXX`"));
+                    return Err(E::invalid_value(
+                        Unexpected::Str(value),
+                        &"ascii, format `\\dXX`",
+                    ));
                 }
 
                 let v = value.as_bytes().to_ascii_uppercase();
@@ -96,7 +212,7 @@ impl Serialize for StatusCode {
 
 #[cfg(test)]
 mod tests {
-    use super::StatusCode;
+    use super::*;
     use serde_yaml::from_str;
 
     #[test]
@@ -122,4 +238,52 @@ mod tests {
     fn deserialize_invalid_range() {
         let _: StatusCode = from_str("2XY").unwrap();
     }
+
+    #[test]
+    fn matches_codes() {
+        assert!(StatusCode::Range(2).matches(204));
+        assert!(!StatusCode::Range(2).matches(304));
+        assert!(StatusCode::Code(404).matches(404));
+        assert!(!StatusCode::Code(404).matches(400));
+        assert!(StatusCode::Code(503).is_server_error());
+        assert!(StatusCode::Range(4).is_client_error());
+        assert!(!StatusCode::Range(4).is_success());
+
+        let mut codes = vec![
+            StatusCode::Range(2),
+            StatusCode::Code(500),
+            StatusCode::Code(200),
+        ];
+        codes.sort();
+        assert_eq!(
+            codes,
+            vec![
+                StatusCode::Code(200),
+                StatusCode::Code(500),
+                StatusCode::Range(2)
+            ]
+        );
+    }
+
+    #[test]
+    fn converts_codes() {
+        assert_eq!(StatusCode::try_from(204), Ok(StatusCode::Code(204)));
+        assert_eq!(
+            StatusCode::try_from(99),
+            Err(InvalidStatusCode("99".to_owned()))
+        );
+        assert_eq!(
+            "099".parse::<StatusCode>(),
+            Err(InvalidStatusCode("099".to_owned()))
+        );
+        assert!("20".parse::<StatusCode>().is_err());
+        assert!("+20".parse::<StatusCode>().is_err());
+        assert_eq!(
+            "２XX".parse::<StatusCode>().unwrap_err().to_string(),
+            "`２XX` is not a status code between 100 and 999 nor a range such as `2XX`"
+        );
+        for code in &[StatusCode::Code(418), StatusCode::Range(3)] {
+            assert_eq!(code.to_string().parse::<StatusCode>().as_ref(), Ok(code));
+        }
+    }
 }
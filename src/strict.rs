@@ -0,0 +1,192 @@
+use crate::read::Format;
+use crate::walk::Location;
+use crate::*;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use std::fmt;
+
+/// A field of a document that is not part of the specification, and so
+/// is dropped when the document is read.
+///
+/// Extensions, whose names start with `x-`, are never unknown.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownField {
+    /// A JSON Pointer to the field, such as
+    /// `/paths/~1pets/get/requestBodies`.
+    pub location: String,
+}
+
+impl fmt::Display for UnknownField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: unknown field", self.location)
+    }
+}
+
+impl OpenAPI {
+    /// Reads a JSON or YAML document, failing with
+    /// [`ReadError::UnknownFields`] if it has fields that are not part of
+    /// the specification, such as a misspelt `requestBody` of an operation.
+    ///
+    /// ```
+    /// let error = openapiv3::OpenAPI::from_str_strict(
+    ///     "openapi: 3.0.0\ninfo: {title: t, version: '1', summary: s}\npaths: {}",
+    /// )
+    /// .unwrap_err();
+    /// assert_eq!(
+    ///     error.to_string(),
+    ///     "document has unknown fields: /info/summary"
+    /// );
+    /// ```
+    pub fn from_str_strict(text: &str) -> Result<OpenAPI, ReadError> {
+        strict(text)
+    }
+
+    /// Reads a JSON or YAML document, along with the fields it has that
+    /// are not part of the specification and are dropped.
+    ///
+    /// Telling an unknown field from a known one left at its default takes
+    /// reading the document again for each field the model does not write
+    /// back, so this is slower than deserializing the document directly.
+    pub fn from_str_lenient(text: &str) -> Result<(OpenAPI, Vec<UnknownField>), ReadError> {
+        lenient(text)
+    }
+}
+
+impl v3_1::OpenAPI {
+    /// Reads a JSON or YAML document, failing with
+    /// [`ReadError::UnknownFields`] if it has fields that are not part of
+    /// the specification; see [`OpenAPI::from_str_strict`].
+    ///
+    /// The unknown keywords of schemas are kept rather than reported.
+    pub fn from_str_strict(text: &str) -> Result<v3_1::OpenAPI, ReadError> {
+        strict(text)
+    }
+
+    /// Reads a JSON or YAML document, along with the fields it has that
+    /// are not part of the specification; see [`OpenAPI::from_str_lenient`].
+    pub fn from_str_lenient(text: &str) -> Result<(v3_1::OpenAPI, Vec<UnknownField>), ReadError> {
+        lenient(text)
+    }
+}
+
+fn strict<T>(text: &str) -> Result<T, ReadError>
+where
+    T: DeserializeOwned + Serialize + PartialEq,
+{
+    let (api, unknown) = lenient(text)?;
+    if unknown.is_empty() {
+        Ok(api)
+    } else {
+        Err(ReadError::UnknownFields(unknown))
+    }
+}
+
+fn lenient<T>(text: &str) -> Result<(T, Vec<UnknownField>), ReadError>
+where
+    T: DeserializeOwned + Serialize + PartialEq,
+{
+    let format = Format::of(text);
+    let api: T = format.parse(text)?;
+    // Going through YAML values allows the keys that YAML does not quote,
+    // such as status codes, which JSON values do not.
+    let source: serde_yaml::Value = format.parse(text)?;
+    let source = serde_json::to_value(source).map_err(ReadError::Json)?;
+    let written = serde_json::to_value(&api).map_err(ReadError::Json)?;
+
+    let mut candidates = Vec::new();
+    missing_fields(&source, &written, &mut Location::default(), &mut candidates);
+    if candidates.is_empty() {
+        return Ok((api, Vec::new()));
+    }
+
+    // A field the model does not write back is either unknown or left at
+    // its default. Only an unknown field can hold a value of the wrong
+    // type without that changing what is read.
+    let read = serde_json::from_value::<T>(source.clone()).map_err(ReadError::Json)?;
+    let unknown = candidates
+        .into_iter()
+        .filter(|location| {
+            let mut changed = source.clone();
+            if let Some(value) = changed.pointer_mut(location) {
+                *value = Value::from(0.5);
+            }
+            serde_json::from_value::<T>(changed).is_ok_and(|changed| changed == read)
+        })
+        .map(|location| UnknownField { location })
+        .collect();
+    Ok((api, unknown))
+}
+
+/// Collects the fields of `source`, other than extensions, without a
+/// counterpart in `written`.
+fn missing_fields(source: &Value, written: &Value, at: &mut Location, out: &mut Vec<String>) {
+    match (source, written) {
+        (Value::Object(source), Value::Object(written)) => {
+            for (key, value) in source {
+                if key.starts_with("x-") {
+                    continue;
+                }
+                at.with(key, |at| match written.get(key) {
+                    Some(written) => missing_fields(value, written, at, out),
+                    None => out.push(at.as_str().to_owned()),
+                });
+            }
+        }
+        (Value::Array(source), Value::Array(written)) if source.len() == written.len() => {
+            for (i, (value, written)) in source.iter().zip(written).enumerate() {
+                at.with(&i.to_string(), |at| missing_fields(value, written, at, out));
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    const DOCUMENT: &str = "
+openapi: 3.0.0
+info: {title: t, version: '1', x-audience: public}
+paths:
+  /pets:
+    post:
+      deprecated: false
+      requestBodies: {}
+      responses:
+        200:
+          description: ok
+          content:
+            application/json:
+              schema: {type: object, required: [], tpye: string}
+";
+
+    #[test]
+    fn reports_unknown_fields() {
+        let (api, unknown) = OpenAPI::from_str_lenient(DOCUMENT).unwrap();
+        assert!(api.paths.contains_key("/pets"));
+        assert_eq!(
+            unknown.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            vec![
+                "/paths/~1pets/post/requestBodies: unknown field",
+                "/paths/~1pets/post/responses/200/content/application~1json/schema/tpye: \
+                 unknown field",
+            ]
+        );
+
+        match OpenAPI::from_str_strict(DOCUMENT).unwrap_err() {
+            ReadError::UnknownFields(fields) => assert_eq!(fields, unknown),
+            error => panic!("unexpected error {}", error),
+        }
+        assert!(
+            OpenAPI::from_str_strict(&DOCUMENT.replace("      requestBodies: {}\n", "")).is_err()
+        );
+        assert!(OpenAPI::from_str_strict(
+            &DOCUMENT
+                .replace("      requestBodies: {}\n", "")
+                .replace(", tpye: string", "")
+        )
+        .is_ok());
+    }
+}
@@ -0,0 +1,783 @@
+use crate::util::{percent_decode, percent_encode_except};
+use crate::*;
+use serde_json::Value;
+use std::error::Error;
+use std::fmt;
+
+/// The serialization of parameter values in their `style`, as RFC 6570
+/// expands them.
+///
+/// ```
+/// use openapiv3::Parameter;
+/// use serde_json::json;
+///
+/// let parameter: Parameter = serde_yaml::from_str(
+///     "{name: id, in: path, required: true, style: matrix, explode: true, schema: {type: array, items: {type: integer}}}",
+/// )
+/// .unwrap();
+/// assert_eq!(parameter.encode(&json!([3, 4])), ";id=3;id=4");
+/// assert_eq!(parameter.decode(";id=3;id=4"), Ok(json!([3, 4])));
+///
+/// let parameter: Parameter = serde_yaml::from_str(
+///     "{name: color, in: query, style: deepObject, schema: {type: object}}",
+/// )
+/// .unwrap();
+/// assert_eq!(
+///     parameter.encode(&json!({"R": 100, "G": "a b"})),
+///     "color[G]=a%20b&color[R]=100"
+/// );
+/// assert_eq!(
+///     parameter.decode("color[R]=100&color[G]=a%20b&page=2"),
+///     Ok(json!({"R": "100", "G": "a b"}))
+/// );
+/// ```
+impl Parameter {
+    /// Serializes a value as the parameter appears in a request: the
+    /// variable of a path, such as `.3,4` in the `label` style; the pairs of
+    /// a query string, such as `id=3&id=4`; the value of a header; or the
+    /// pairs of a `Cookie` header, such as `id=3; id=4`.
+    ///
+    /// Values are percent-encoded, except in headers and in query
+    /// parameters that allow reserved characters. Arrays and objects nested
+    /// in others are serialized as JSON, which the styles have no way of
+    /// expressing. A parameter described by `content` is serialized as its
+    /// media type, JSON or else text, then as a single value.
+    pub fn encode(&self, value: &Value) -> String {
+        let data = self.parameter_data_ref();
+        let escape = |text: &str| -> String {
+            let mut out = String::new();
+            match self {
+                Parameter::Header { .. } => out.push_str(text),
+                Parameter::Query {
+                    allow_reserved: true,
+                    ..
+                } => percent_encode_except(text, |byte| RESERVED.contains(&byte), &mut out),
+                _ => percent_encode_except(text, |_| false, &mut out),
+            }
+            out
+        };
+        let separator = match self {
+            Parameter::Cookie { .. } => "; ",
+            _ => "&",
+        };
+        match &data.format {
+            ParameterSchemaOrContent::Schema(_) => {
+                encode(self.style(), &data.name, value, &escape, separator)
+            }
+            ParameterSchemaOrContent::Content(content) => {
+                let text = match content.keys().next() {
+                    Some(media_type) if media_type.is_json() => value.to_string(),
+                    _ => text(value),
+                };
+                let text = Value::String(text);
+                encode(self.style(), &data.name, &text, &escape, separator)
+            }
+        }
+    }
+
+    /// Deserializes the value of the parameter from a request, as
+    /// [`encode`](Parameter::encode) serializes it. See
+    /// [`decode_in`](Parameter::decode_in).
+    pub fn decode(&self, raw: &str) -> Result<Value, DecodeParameterError> {
+        self.decode_in(&Components::default(), raw)
+    }
+
+    /// Deserializes the value of the parameter from a request, resolving
+    /// the references of its schema in `components`.
+    ///
+    /// `raw` is the variable of a path, the whole of a query string
+    /// without its `?`, the value of a header, or the value of a `Cookie`
+    /// header; the parameter is picked out of a query string or cookies by
+    /// its name. An exploded `form` object spreads its properties across
+    /// the query string, so every pair not otherwise claimed is taken for
+    /// one: `raw` should hold only the pairs of the parameter.
+    ///
+    /// Strings are read as the types the schema gives them, so that `5` is
+    /// the number 5 given an integer schema, and are left strings where
+    /// they are not of that type, for validation to report. Values of
+    /// schemas that cannot be resolved are left strings.
+    pub fn decode_in(
+        &self,
+        components: &Components,
+        raw: &str,
+    ) -> Result<Value, DecodeParameterError> {
+        let data = self.parameter_data_ref();
+        let spread: &[&str] = &[];
+        let spread = Some(spread).filter(|_| self.spreads(components));
+        let raw = match self {
+            Parameter::Path { .. } => {
+                Raw::One(percent_decode(raw).unwrap_or_else(|| raw.to_owned()))
+            }
+            Parameter::Header { .. } => Raw::One(raw.to_owned()),
+            Parameter::Query { .. } => query_raw(&form_pairs(raw), &data.name, spread)
+                .ok_or(DecodeParameterError::Missing)?,
+            Parameter::Cookie { .. } => query_raw(&cookie_pairs(raw), &data.name, spread)
+                .ok_or(DecodeParameterError::Missing)?,
+        };
+        match &data.format {
+            ParameterSchemaOrContent::Schema(schema) => match components.resolve(schema) {
+                Ok(schema) => decode(components, self.style(), &data.name, schema, raw)
+                    .map_err(|style| DecodeParameterError::Malformed { style }),
+                Err(_) => Ok(Value::String(raw.first())),
+            },
+            ParameterSchemaOrContent::Content(content) => match content.keys().next() {
+                Some(media_type) if media_type.is_json() => serde_json::from_str(&raw.first())
+                    .map_err(|e| DecodeParameterError::Unparsable(e.to_string())),
+                _ => Ok(Value::String(raw.first())),
+            },
+        }
+    }
+
+    /// Whether the parameter is an object in the exploded `form` style,
+    /// which spreads its properties across the query string or cookies as
+    /// pairs of their own.
+    pub(crate) fn spreads(&self, components: &Components) -> bool {
+        let style = self.style();
+        style.name == "form"
+            && style.explode
+            && match &self.parameter_data_ref().format {
+                ParameterSchemaOrContent::Schema(schema) => components
+                    .resolve(schema)
+                    .is_ok_and(|schema| matches!(shape(schema), Shape::Object)),
+                ParameterSchemaOrContent::Content(_) => false,
+            }
+    }
+
+    /// How the parameter is serialized, applying the defaults of its
+    /// location.
+    pub(crate) fn style(&self) -> Style {
+        let style = self.effective_style();
+        Style {
+            name: style.as_str(),
+            explode: self.effective_explode(),
+            delimiter: match style {
+                ParameterStyle::SpaceDelimited => ' ',
+                ParameterStyle::PipeDelimited => '|',
+                _ => ',',
+            },
+        }
+    }
+}
+
+impl Header {
+    /// How the header is serialized, as a parameter in the `simple` style.
+    pub(crate) fn style(&self) -> Style {
+        Style {
+            name: ParameterStyle::Simple.as_str(),
+            explode: self.explode.unwrap_or(false),
+            delimiter: ',',
+        }
+    }
+}
+
+/// Why a parameter could not be read by [`Parameter::decode`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodeParameterError {
+    /// The query string or cookies hold no value for the parameter.
+    Missing,
+    /// The value is not serialized in the style of the parameter, such as
+    /// a `label` value without its leading `.`.
+    Malformed { style: &'static str },
+    /// The value cannot be parsed as the media type of the parameter, for
+    /// the reason given.
+    Unparsable(String),
+}
+
+impl fmt::Display for DecodeParameterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeParameterError::Missing => f.write_str("parameter is missing"),
+            DecodeParameterError::Malformed { style } => {
+                write!(f, "parameter is not serialized in the `{}` style", style)
+            }
+            DecodeParameterError::Unparsable(reason) => {
+                write!(f, "parameter cannot be parsed: {}", reason)
+            }
+        }
+    }
+}
+
+impl Error for DecodeParameterError {}
+
+/// The reserved characters of RFC 3986, which parameters that allow them
+/// leave unencoded.
+const RESERVED: &[u8] = b":/?#[]@!$&'()*+,;=";
+
+/// A primitive value as text; strings are unquoted, and null is empty.
+fn text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Null => String::new(),
+        value => value.to_string(),
+    }
+}
+
+/// Serializes `value` as the parameter `name` in a style, escaping names
+/// and values with `escape` and separating pairs with `separator`.
+fn encode(
+    style: Style,
+    name: &str,
+    value: &Value,
+    escape: &dyn Fn(&str) -> String,
+    separator: &str,
+) -> String {
+    let Style {
+        name: style,
+        explode,
+        delimiter,
+    } = style;
+    let name = escape(name);
+    let delimiter = match delimiter {
+        ' ' => "%20".to_owned(),
+        delimiter => delimiter.to_string(),
+    };
+    let pairs = |pairs: Vec<(String, &String)>| -> String {
+        pairs
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect::<Vec<_>>()
+            .join(separator)
+    };
+    match value {
+        Value::Array(items) => {
+            let items: Vec<String> = items.iter().map(|item| escape(&text(item))).collect();
+            match style {
+                "simple" => items.join(","),
+                "label" => format!(".{}", items.join(if explode { "." } else { "," })),
+                "matrix" if explode => items
+                    .iter()
+                    .map(|item| format!(";{}={}", name, item))
+                    .collect(),
+                "matrix" if items.is_empty() => format!(";{}", name),
+                "matrix" => format!(";{}={}", name, items.join(",")),
+                _ if explode => pairs(items.iter().map(|item| (name.clone(), item)).collect()),
+                _ => format!("{}={}", name, items.join(&delimiter)),
+            }
+        }
+        Value::Object(object) => {
+            let properties: Vec<(String, String)> = object
+                .iter()
+                .map(|(key, value)| (escape(key), escape(&text(value))))
+                .collect();
+            let assigned = |separator: &str| -> String {
+                properties
+                    .iter()
+                    .map(|(key, value)| format!("{}={}", key, value))
+                    .collect::<Vec<_>>()
+                    .join(separator)
+            };
+            let flat = |delimiter: &str| -> String {
+                properties
+                    .iter()
+                    .flat_map(|(key, value)| vec![key.as_str(), value.as_str()])
+                    .collect::<Vec<_>>()
+                    .join(delimiter)
+            };
+            match style {
+                "simple" if explode => assigned(","),
+                "simple" => flat(","),
+                "label" if explode => format!(".{}", assigned(".")),
+                "label" => format!(".{}", flat(",")),
+                "matrix" if explode => properties
+                    .iter()
+                    .map(|(key, value)| format!(";{}={}", key, value))
+                    .collect(),
+                "matrix" => format!(";{}={}", name, flat(",")),
+                "deepObject" => pairs(
+                    properties
+                        .iter()
+                        .map(|(key, value)| (format!("{}[{}]", name, key), value))
+                        .collect(),
+                ),
+                _ if explode => assigned(separator),
+                _ => format!("{}={}", name, flat(&delimiter)),
+            }
+        }
+        value => {
+            let value = escape(&text(value));
+            match style {
+                "simple" => value,
+                "label" => format!(".{}", value),
+                "matrix" if value.is_empty() => format!(";{}", name),
+                "matrix" => format!(";{}={}", name, value),
+                _ => format!("{}={}", name, value),
+            }
+        }
+    }
+}
+
+/// The decoded names and values of a `Cookie` header.
+fn cookie_pairs(cookies: &str) -> Vec<(String, String)> {
+    cookies
+        .split(';')
+        .filter_map(|cookie| cookie.trim().split_once('='))
+        .map(|(name, value)| {
+            let value = percent_decode(value).unwrap_or_else(|| value.to_owned());
+            (name.to_owned(), value)
+        })
+        .collect()
+}
+
+/// The decoded names and values of a query string or form body.
+pub(crate) fn form_pairs(form: &str) -> Vec<(String, String)> {
+    let decode = |s: &str| {
+        let s = s.replace('+', " ");
+        percent_decode(&s).unwrap_or(s)
+    };
+    form.split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (decode(name), decode(value))
+        })
+        .collect()
+}
+
+/// The strings a parameter is read from, before they are decoded according
+/// to its style.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Raw {
+    /// The value of a path variable or header.
+    One(String),
+    /// The values of each occurrence of a query parameter or cookie.
+    Many(Vec<String>),
+    /// The properties of an object query parameter that spreads them across
+    /// the query string, in the `deepObject`, or exploded `form`, style.
+    Properties(Vec<(String, String)>),
+}
+
+impl Raw {
+    pub(crate) fn is_empty(&self) -> bool {
+        match self {
+            Raw::One(_) => false,
+            Raw::Many(values) => values.is_empty(),
+            Raw::Properties(properties) => properties.is_empty(),
+        }
+    }
+
+    pub(crate) fn is_empty_value(&self) -> bool {
+        match self {
+            Raw::One(value) => value.is_empty(),
+            Raw::Many(values) => values.iter().all(String::is_empty),
+            Raw::Properties(_) => false,
+        }
+    }
+
+    pub(crate) fn first(self) -> String {
+        match self {
+            Raw::One(value) => value,
+            Raw::Many(values) => values.into_iter().next().unwrap_or_default(),
+            Raw::Properties(properties) => properties
+                .into_iter()
+                .next()
+                .map(|(_, value)| value)
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// What a parameter's schema makes of its value.
+pub(crate) enum Shape<'s> {
+    Primitive,
+    Array(&'s ReferenceOr<Box<Schema>>),
+    Object,
+}
+
+pub(crate) fn shape(schema: &Schema) -> Shape<'_> {
+    match &schema.schema_kind {
+        SchemaKind::Type(Type::Array(array)) => Shape::Array(&array.items),
+        SchemaKind::Type(Type::Object(_)) => Shape::Object,
+        SchemaKind::Any(any) => match &any.items {
+            Some(items) => Shape::Array(items),
+            None if !any.properties.is_empty() => Shape::Object,
+            None => Shape::Primitive,
+        },
+        _ => Shape::Primitive,
+    }
+}
+
+/// How a parameter or header is serialized: the name of its style, whether
+/// it is exploded, and what separates the items of arrays and objects.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Style {
+    pub(crate) name: &'static str,
+    pub(crate) explode: bool,
+    pub(crate) delimiter: char,
+}
+
+/// Decodes the strings of a parameter or header `name` into a value,
+/// according to its style and schema, or fails with the name of the style
+/// they do not follow.
+pub(crate) fn decode(
+    components: &Components,
+    style: Style,
+    name: &str,
+    schema: &Schema,
+    raw: Raw,
+) -> Result<Value, &'static str> {
+    let Style {
+        name: style,
+        explode,
+        delimiter,
+    } = style;
+
+    // Strip what the style puts before the value, leaving a string to split.
+    let raw = match (style, raw) {
+        ("label", Raw::One(value)) => {
+            let value = value.strip_prefix('.').ok_or(style)?;
+            // Only the items of arrays and objects are separated by dots;
+            // a primitive, such as `1.5`, keeps its own.
+            let items = matches!(shape(schema), Shape::Array(_) | Shape::Object);
+            Raw::One(if explode && items {
+                value.replace('.', ",")
+            } else {
+                value.to_owned()
+            })
+        }
+        ("matrix", Raw::One(value)) => {
+            let value = value.strip_prefix(';').ok_or(style)?;
+            let prefix = format!("{}=", name);
+            match shape(schema) {
+                Shape::Object if explode => Raw::One(value.replace(';', ",")),
+                Shape::Array(_) if explode => Raw::One(
+                    value
+                        .split(';')
+                        .map(|item| item.strip_prefix(&prefix))
+                        .collect::<Option<Vec<_>>>()
+                        .ok_or(style)?
+                        .join(","),
+                ),
+                // An empty value leaves out its `=`.
+                _ if value == name => Raw::One(String::new()),
+                _ => Raw::One(value.strip_prefix(&prefix).ok_or(style)?.to_owned()),
+            }
+        }
+        (_, raw) => raw,
+    };
+
+    let split = |value: &str| -> Vec<String> {
+        if value.is_empty() {
+            Vec::new()
+        } else if style == "simple" {
+            // Headers allow whitespace around the items of a list.
+            value
+                .split(delimiter)
+                .map(|item| item.trim().to_owned())
+                .collect()
+        } else {
+            value.split(delimiter).map(str::to_owned).collect()
+        }
+    };
+    match shape(schema) {
+        Shape::Primitive => Ok(coerce(components, schema, &raw.first())),
+        Shape::Array(items) => {
+            let values = match raw {
+                Raw::One(value) => split(&value),
+                Raw::Many(values) if explode && style == "form" => values,
+                Raw::Many(values) => values.iter().flat_map(|value| split(value)).collect(),
+                Raw::Properties(_) => return Err(style),
+            };
+            let items = components.resolve_boxed(items).ok();
+            Ok(Value::Array(
+                values
+                    .iter()
+                    .map(|value| match items {
+                        Some(items) => coerce(components, items, value),
+                        None => Value::String(value.clone()),
+                    })
+                    .collect(),
+            ))
+        }
+        Shape::Object => {
+            let properties = match raw {
+                Raw::Properties(properties) => properties,
+                raw => {
+                    let items = split(&raw.first());
+                    if explode {
+                        items
+                            .iter()
+                            .map(|item| {
+                                let (key, value) = item.split_once('=').ok_or(style)?;
+                                Ok((key.to_owned(), value.to_owned()))
+                            })
+                            .collect::<Result<_, &str>>()?
+                    } else if items.len() % 2 == 0 {
+                        items
+                            .chunks(2)
+                            .map(|pair| (pair[0].clone(), pair[1].clone()))
+                            .collect()
+                    } else {
+                        return Err(style);
+                    }
+                }
+            };
+            decode_pairs(components, schema, properties).ok_or(style)
+        }
+    }
+}
+
+/// Decodes the properties of an object, each according to its schema.
+pub(crate) fn decode_pairs(
+    components: &Components,
+    schema: &Schema,
+    pairs: Vec<(String, String)>,
+) -> Option<Value> {
+    let (properties, additional) = match &schema.schema_kind {
+        SchemaKind::Type(Type::Object(object)) => {
+            (&object.properties, object.additional_properties.as_ref())
+        }
+        SchemaKind::Any(any) => (&any.properties, any.additional_properties.as_ref()),
+        _ => return None,
+    };
+    let object = pairs
+        .into_iter()
+        .map(|(name, value)| {
+            let schema = match (properties.get(name.as_str()), additional) {
+                (Some(property), _) => components.resolve_boxed(property).ok(),
+                (None, Some(AdditionalProperties::Schema(schema))) => {
+                    components.resolve(schema).ok()
+                }
+                (None, _) => None,
+            };
+            let value = match schema {
+                Some(schema) => coerce(components, schema, &value),
+                None => Value::String(value),
+            };
+            (name, value)
+        })
+        .collect();
+    Some(Value::Object(object))
+}
+
+/// Reads a string of a request as a value of the type `schema` gives it,
+/// leaving it a string where it is not of that type, for validation to
+/// report.
+pub(crate) fn coerce(components: &Components, schema: &Schema, raw: &str) -> Value {
+    match &schema.schema_kind {
+        SchemaKind::Type(Type::Integer(_)) => match raw.parse::<i64>() {
+            Ok(n) => Value::from(n),
+            Err(_) => Value::from(raw),
+        },
+        SchemaKind::Type(Type::Number(_)) => raw
+            .parse::<f64>()
+            .ok()
+            .and_then(serde_json::Number::from_f64)
+            .map_or_else(|| Value::from(raw), Value::Number),
+        SchemaKind::Type(Type::Boolean {}) => match raw {
+            "true" => Value::Bool(true),
+            "false" => Value::Bool(false),
+            _ => Value::from(raw),
+        },
+        SchemaKind::OneOf { one_of: schemas }
+        | SchemaKind::AllOf { all_of: schemas }
+        | SchemaKind::AnyOf { any_of: schemas } => schemas
+            .iter()
+            .filter_map(|schema| components.resolve(schema).ok())
+            .map(|schema| coerce(components, schema, raw))
+            .find(|value| !value.is_string())
+            .unwrap_or_else(|| Value::from(raw)),
+        _ => Value::from(raw),
+    }
+}
+
+/// Finds the strings of the query parameter `name`. For an exploded object,
+/// `spread` holds the names of every query parameter of the operation,
+/// which its properties cannot be.
+pub(crate) fn query_raw(
+    query: &[(String, String)],
+    name: &str,
+    spread: Option<&[&str]>,
+) -> Option<Raw> {
+    let values: Vec<String> = query
+        .iter()
+        .filter(|(key, _)| key == name)
+        .map(|(_, value)| value.clone())
+        .collect();
+    let prefix = format!("{}[", name);
+    let deep: Vec<(String, String)> = query
+        .iter()
+        .filter_map(|(key, value)| {
+            let property = key.strip_prefix(&prefix)?.strip_suffix(']')?;
+            Some((property.to_owned(), value.clone()))
+        })
+        .collect();
+    if !values.is_empty() {
+        return Some(Raw::Many(values));
+    }
+    if !deep.is_empty() {
+        return Some(Raw::Properties(deep));
+    }
+    let others = spread?;
+    let spread: Vec<(String, String)> = query
+        .iter()
+        .filter(|(key, _)| !others.contains(&key.as_str()) && !key.contains('['))
+        .cloned()
+        .collect();
+    Some(Raw::Properties(spread)).filter(|raw| !raw.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn parameter(yaml: &str) -> Parameter {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn encodes_styles() {
+        let primitive = json!(5);
+        let array = json!([3, 4, 5]);
+        let object = json!({"role": "admin", "firstName": "Alex"});
+        let cases = [
+            (
+                "in: path, style: simple",
+                ["5", "3,4,5", "firstName,Alex,role,admin"],
+            ),
+            (
+                "in: path, style: simple, explode: true",
+                ["5", "3,4,5", "firstName=Alex,role=admin"],
+            ),
+            (
+                "in: path, style: label",
+                [".5", ".3,4,5", ".firstName,Alex,role,admin"],
+            ),
+            (
+                "in: path, style: label, explode: true",
+                [".5", ".3.4.5", ".firstName=Alex.role=admin"],
+            ),
+            (
+                "in: path, style: matrix",
+                [";id=5", ";id=3,4,5", ";id=firstName,Alex,role,admin"],
+            ),
+            (
+                "in: path, style: matrix, explode: true",
+                [";id=5", ";id=3;id=4;id=5", ";firstName=Alex;role=admin"],
+            ),
+            (
+                "in: query, explode: false",
+                ["id=5", "id=3,4,5", "id=firstName,Alex,role,admin"],
+            ),
+            (
+                "in: query",
+                ["id=5", "id=3&id=4&id=5", "firstName=Alex&role=admin"],
+            ),
+            (
+                "in: query, style: spaceDelimited",
+                ["id=5", "id=3%204%205", "id=firstName%20Alex%20role%20admin"],
+            ),
+            (
+                "in: query, style: pipeDelimited",
+                ["id=5", "id=3|4|5", "id=firstName|Alex|role|admin"],
+            ),
+            (
+                "in: query, style: deepObject",
+                [
+                    "id=5",
+                    "id=3&id=4&id=5",
+                    "id[firstName]=Alex&id[role]=admin",
+                ],
+            ),
+            (
+                "in: cookie",
+                ["id=5", "id=3; id=4; id=5", "firstName=Alex; role=admin"],
+            ),
+        ];
+        for (location, expected) in cases.iter() {
+            for (schema, (value, expected)) in ["integer", "array", "object"]
+                .iter()
+                .zip([&primitive, &array, &object].iter().zip(expected.iter()))
+            {
+                let parameter = parameter(&format!(
+                    "{{name: id, {}, schema: {{type: {}, items: {{type: integer}}}}}}",
+                    location, schema
+                ));
+                assert_eq!(
+                    parameter.encode(value),
+                    *expected,
+                    "{} {}",
+                    location,
+                    schema
+                );
+                let decoded = parameter.decode(expected).unwrap();
+                let decoded = match &decoded {
+                    Value::Object(_) => {
+                        json!({"role": decoded["role"], "firstName": decoded["firstName"]})
+                    }
+                    _ => decoded,
+                };
+                assert_eq!(&decoded, *value, "{} {}", location, schema);
+            }
+        }
+    }
+
+    #[test]
+    fn keeps_the_dots_of_exploded_label_primitives() {
+        let label = |schema| {
+            parameter(&format!(
+                "{{name: v, in: path, style: label, explode: true, schema: {}}}",
+                schema
+            ))
+        };
+        assert_eq!(label("{type: number}").decode(".1.5"), Ok(json!(1.5)));
+        assert_eq!(
+            label("{type: string}").decode(".example.com"),
+            Ok(json!("example.com"))
+        );
+        assert_eq!(
+            label("{type: array, items: {type: string}}").decode(".a.b"),
+            Ok(json!(["a", "b"]))
+        );
+    }
+
+    #[test]
+    fn escapes_values() {
+        let value = json!("a b/c&d");
+        let encode = |yaml| parameter(yaml).encode(&value);
+        assert_eq!(
+            encode("{name: q, in: query, schema: {}}"),
+            "q=a%20b%2Fc%26d"
+        );
+        assert_eq!(
+            encode("{name: q, in: query, allowReserved: true, schema: {}}"),
+            "q=a%20b/c&d"
+        );
+        assert_eq!(encode("{name: X-Q, in: header, schema: {}}"), "a b/c&d");
+        assert_eq!(
+            encode("{name: q, in: query, content: {application/json: {}}}"),
+            "q=%22a%20b%2Fc%26d%22"
+        );
+        assert_eq!(
+            parameter("{name: q, in: query, content: {application/json: {}}}")
+                .decode("q=%22a%20b%2Fc%26d%22"),
+            Ok(value)
+        );
+    }
+
+    #[test]
+    fn reports_malformed_values() {
+        let ids = parameter(
+            "{name: ids, in: path, style: label, schema: {type: array, items: {type: integer}}}",
+        );
+        assert_eq!(
+            ids.decode("1,2"),
+            Err(DecodeParameterError::Malformed { style: "label" })
+        );
+        assert_eq!(ids.decode(".1,a"), Ok(json!([1, "a"])));
+        let matrix = parameter("{name: id, in: path, style: matrix, schema: {type: string}}");
+        assert_eq!(matrix.encode(&json!("")), ";id");
+        assert_eq!(matrix.decode(";id"), Ok(json!("")));
+        assert_eq!(
+            parameter("{name: page, in: query, schema: {type: integer}}").decode("size=2"),
+            Err(DecodeParameterError::Missing)
+        );
+        assert_eq!(
+            parameter("{name: q, in: query, content: {application/json: {}}}")
+                .decode("q={")
+                .unwrap_err()
+                .to_string(),
+            "parameter cannot be parsed: EOF while parsing an object at line 1 column 1"
+        );
+    }
+}
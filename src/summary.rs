@@ -0,0 +1,365 @@
+use crate::*;
+use std::fmt;
+
+/// The schema as a type expression, such as `{name: string, tag?: string}`
+/// or `(Cat | Dog)[]`, in the manner of its [`TypeShape`], but with an
+/// `allOf` written as its branches joined by `&` rather than merged, and
+/// references as the names of the schemas they refer to.
+///
+/// ```
+/// use openapiv3::Schema;
+///
+/// let schema: Schema = serde_yaml::from_str(
+///     "
+/// type: object
+/// required: [name]
+/// properties:
+///   born: {type: string, format: date}
+///   name: {type: string}
+///   owners: {type: array, items: {$ref: '#/components/schemas/Owner'}}
+/// ",
+/// )
+/// .unwrap();
+/// assert_eq!(
+///     schema.to_string(),
+///     "{born?: string(date), name: string, owners?: Owner[]}"
+/// );
+/// ```
+impl fmt::Display for Schema {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let nullable = self.schema_data.nullable && !matches!(self.schema_kind, SchemaKind::Any(_));
+        match &self.schema_kind {
+            SchemaKind::Type(Type::String(string)) if !string.enumeration.is_empty() => join(
+                f,
+                string.enumeration.iter().map(|v| format!("{:?}", v)),
+                " | ",
+            )?,
+            SchemaKind::Type(Type::String(string)) => {
+                with_format(f, "string", string.format.name(StringFormat::as_str))?
+            }
+            SchemaKind::Type(Type::Number(number)) if !number.enumeration.is_empty() => {
+                join(f, number.enumeration.iter().map(f64::to_string), " | ")?
+            }
+            SchemaKind::Type(Type::Number(number)) => {
+                with_format(f, "number", number.format.name(NumberFormat::as_str))?
+            }
+            SchemaKind::Type(Type::Integer(integer)) if !integer.enumeration.is_empty() => {
+                join(f, integer.enumeration.iter().map(i64::to_string), " | ")?
+            }
+            SchemaKind::Type(Type::Integer(integer)) => {
+                with_format(f, "integer", integer.format.name(IntegerFormat::as_str))?
+            }
+            SchemaKind::Type(Type::Boolean {}) => f.write_str("boolean")?,
+            SchemaKind::Type(Type::Object(object)) => object_type(
+                f,
+                &object.properties,
+                &object.required,
+                object.additional_properties.as_ref(),
+            )?,
+            SchemaKind::Type(Type::Array(array)) => write!(f, "{}[]", item(&array.items))?,
+            SchemaKind::OneOf { one_of: branches } | SchemaKind::AnyOf { any_of: branches } => {
+                join(f, branches.iter().map(branch), " | ")?
+            }
+            SchemaKind::AllOf { all_of } if all_of.len() == 1 => {
+                f.write_str(&schema_name(&all_of[0]))?
+            }
+            SchemaKind::AllOf { all_of } => join(f, all_of.iter().map(branch), " & ")?,
+            SchemaKind::Any(any) => match (&any.items, &any.additional_properties) {
+                (Some(items), _) => write!(f, "{}[]", item(items))?,
+                (None, _) if !any.properties.is_empty() => object_type(
+                    f,
+                    &any.properties,
+                    &any.required,
+                    any.additional_properties.as_ref(),
+                )?,
+                (None, Some(AdditionalProperties::Schema(values))) => {
+                    write!(f, "map<{}>", schema_name(values))?
+                }
+                (None, _) => f.write_str("any")?,
+            },
+        }
+        if nullable {
+            f.write_str(" | null")?;
+        }
+        Ok(())
+    }
+}
+
+fn join(
+    f: &mut fmt::Formatter<'_>,
+    items: impl Iterator<Item = String>,
+    separator: &str,
+) -> fmt::Result {
+    for (i, item) in items.enumerate() {
+        if i > 0 {
+            f.write_str(separator)?;
+        }
+        f.write_str(&item)?;
+    }
+    Ok(())
+}
+
+fn with_format(f: &mut fmt::Formatter<'_>, name: &str, format: Option<&str>) -> fmt::Result {
+    match format {
+        Some(format) => write!(f, "{}({})", name, format),
+        None => f.write_str(name),
+    }
+}
+
+fn object_type(
+    f: &mut fmt::Formatter<'_>,
+    properties: &Map<Name, ReferenceOr<Box<Schema>>>,
+    required: &[Name],
+    additional_properties: Option<&AdditionalProperties>,
+) -> fmt::Result {
+    let additional = match additional_properties {
+        Some(AdditionalProperties::Schema(values)) => Some(schema_name(values)),
+        Some(AdditionalProperties::Any) => Some("any".to_owned()),
+        _ => None,
+    };
+    if properties.is_empty() {
+        return write!(f, "map<{}>", additional.as_deref().unwrap_or("any"));
+    }
+    f.write_str("{")?;
+    for (i, (name, schema)) in properties.iter().enumerate() {
+        if i > 0 {
+            f.write_str(", ")?;
+        }
+        let optional = if required.contains(name) { "" } else { "?" };
+        write!(f, "{}{}: {}", name, optional, boxed_name(schema))?;
+    }
+    if let Some(additional) = additional {
+        write!(f, ", ..: {}", additional)?;
+    }
+    f.write_str("}")
+}
+
+/// The item of an array, in parentheses if it is of several types.
+fn item(items: &ReferenceOr<Box<Schema>>) -> String {
+    match items {
+        ReferenceOr::Item(schema) if is_compound(schema) => format!("({})", schema),
+        items => boxed_name(items),
+    }
+}
+
+/// A branch of a `oneOf`, `anyOf` or `allOf`, in parentheses if it is
+/// itself of several types.
+fn branch(schema: &ReferenceOr<Schema>) -> String {
+    match schema {
+        ReferenceOr::Item(schema) if is_compound(schema) => format!("({})", schema),
+        schema => schema_name(schema),
+    }
+}
+
+/// Whether a schema is written with `|` or `&`.
+fn is_compound(schema: &Schema) -> bool {
+    let enumerated = match &schema.schema_kind {
+        SchemaKind::Type(Type::String(string)) => string.enumeration.len(),
+        SchemaKind::Type(Type::Number(number)) => number.enumeration.len(),
+        SchemaKind::Type(Type::Integer(integer)) => integer.enumeration.len(),
+        SchemaKind::OneOf { .. } | SchemaKind::AnyOf { .. } => 2,
+        SchemaKind::AllOf { all_of } => all_of.len(),
+        _ => 0,
+    };
+    enumerated > 1
+        || schema.schema_data.nullable && !matches!(schema.schema_kind, SchemaKind::Any(_))
+}
+
+/// The request body and responses of the operation, such as
+/// `NewPet → 201: Pet, 400: Error`, or `→ 204` for an operation without a
+/// body. Each is written as the schema of its JSON content, or of its first
+/// content if none is JSON, and references as the names of the components
+/// they refer to.
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.request_body {
+            Some(ReferenceOr::Reference { reference }) => write!(f, "{} ", name(reference))?,
+            Some(ReferenceOr::Item(body)) => {
+                let optional = if body.required { "" } else { "?" };
+                match content_schema(body.content.iter()) {
+                    Some(schema) => write!(f, "{}{} ", schema_name(schema), optional)?,
+                    None => write!(f, "body{} ", optional)?,
+                }
+            }
+            None => {}
+        }
+        f.write_str("→")?;
+        let responses = self
+            .responses
+            .responses
+            .iter()
+            .map(|(code, response)| (code.to_string(), response))
+            .chain(
+                self.responses
+                    .default
+                    .iter()
+                    .map(|response| ("default".to_owned(), response)),
+            );
+        for (i, (code, response)) in responses.enumerate() {
+            f.write_str(if i > 0 { ", " } else { " " })?;
+            f.write_str(&code)?;
+            let schema = match response {
+                ReferenceOr::Reference { reference } => Some(name(reference)),
+                ReferenceOr::Item(response) => {
+                    let content =
+                        response.content.iter().filter_map(
+                            |(range, media_type)| match media_type {
+                                ReferenceOr::Item(media_type) => Some((range, media_type)),
+                                ReferenceOr::Reference { .. } => None,
+                            },
+                        );
+                    content_schema(content).map(schema_name)
+                }
+            };
+            if let Some(schema) = schema {
+                write!(f, ": {}", schema)?;
+            }
+        }
+        if self.deprecated {
+            f.write_str(" (deprecated)")?;
+        }
+        Ok(())
+    }
+}
+
+/// The operations of the path item, one per line, such as
+/// `GET → 200: Pet[]`.
+impl fmt::Display for PathItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (method, operation)) in self.operations().enumerate() {
+            if i > 0 {
+                f.write_str("\n")?;
+            }
+            write!(f, "{} {}", method, operation)?;
+        }
+        Ok(())
+    }
+}
+
+/// The title and version of the document, followed by its operations,
+/// one per line.
+///
+/// ```
+/// use openapiv3::OpenAPI;
+///
+/// let api: OpenAPI = serde_yaml::from_str(
+///     "
+/// openapi: 3.0.0
+/// info: {title: Pets, version: '1.0'}
+/// paths:
+///   /pets:
+///     get:
+///       responses:
+///         200:
+///           description: pets
+///           content:
+///             application/json:
+///               schema: {type: array, items: {$ref: '#/components/schemas/Pet'}}
+///         404: {$ref: '#/components/responses/NotFound'}
+/// ",
+/// )
+/// .unwrap();
+/// assert_eq!(
+///     api.to_string(),
+///     "Pets 1.0\nGET /pets → 200: Pet[], 404: NotFound"
+/// );
+/// ```
+impl fmt::Display for OpenAPI {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.info.title, self.info.version)?;
+        for (path, method, operation) in self.operations() {
+            write!(f, "\n{} {} {}", method, path, operation)?;
+        }
+        Ok(())
+    }
+}
+
+/// The schema of the JSON media type of some content, or of its first
+/// media type if none is JSON.
+pub(crate) fn content_schema<'c>(
+    content: impl Iterator<Item = (&'c MediaTypeRange, &'c MediaType)> + Clone,
+) -> Option<&'c ReferenceOr<Schema>> {
+    let (_, media_type) = content
+        .clone()
+        .find(|(range, _)| range.is_json())
+        .or_else(|| content.clone().next())?;
+    media_type.schema.as_ref()
+}
+
+pub(crate) fn schema_name(schema: &ReferenceOr<Schema>) -> String {
+    match schema {
+        ReferenceOr::Reference { reference } => name(reference),
+        ReferenceOr::Item(schema) => schema.to_string(),
+    }
+}
+
+pub(crate) fn boxed_name(schema: &ReferenceOr<Box<Schema>>) -> String {
+    match schema {
+        ReferenceOr::Reference { reference } => name(reference),
+        ReferenceOr::Item(schema) => schema.to_string(),
+    }
+}
+
+/// The name of the component a reference refers to, or the reference as it
+/// is written if it is not to a component of the document.
+pub(crate) fn name(reference: &Reference) -> String {
+    match reference.name() {
+        Some(name) if !reference.is_external() => name.into_owned(),
+        _ => reference.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summarizes_operations() {
+        let item: PathItem = serde_yaml::from_str(
+            "
+post:
+  deprecated: true
+  requestBody:
+    content:
+      text/plain: {schema: {type: string}}
+      application/json: {schema: {$ref: '#/components/schemas/NewPet'}}
+  responses:
+    201:
+      description: created
+      content:
+        application/xml: {schema: {type: object, properties: {id: {type: integer, format: int64}}}}
+    4XX: {description: failed}
+    default:
+      description: error
+      content:
+        application/json:
+          schema:
+            oneOf: [{$ref: '#/components/schemas/Error'}, {type: string, enum: [a, b]}]
+delete:
+  requestBody: {$ref: '#/components/requestBodies/Delete'}
+  responses:
+    204: {description: deleted}
+",
+        )
+        .unwrap();
+        assert_eq!(
+            item.to_string(),
+            "POST NewPet? → 201: {id?: integer(int64)}, 4XX, \
+             default: Error | (\"a\" | \"b\") (deprecated)\nDELETE Delete → 204"
+        );
+
+        let schema: Schema = serde_yaml::from_str(
+            "
+type: array
+nullable: true
+items:
+  nullable: true
+  allOf:
+    - $ref: '#/components/schemas/Pet'
+    - {type: object, additionalProperties: {type: boolean}}
+",
+        )
+        .unwrap();
+        assert_eq!(schema.to_string(), "(Pet & map<boolean> | null)[] | null");
+    }
+}
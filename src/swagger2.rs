@@ -0,0 +1,1223 @@
+//! Swagger 2.0 documents, and their conversion to OpenAPI 3.0.
+//!
+//! ```
+//! use openapiv3::swagger2::Swagger;
+//! use openapiv3::OpenAPI;
+//!
+//! let swagger: Swagger = serde_yaml::from_str(
+//!     "
+//! swagger: '2.0'
+//! info: {title: Pets, version: '1'}
+//! host: pets.example.com
+//! basePath: /v1
+//! paths: {}
+//! ",
+//! )
+//! .unwrap();
+//! let api: OpenAPI = swagger.into_openapi();
+//! assert_eq!(api.servers[0].url, "//pets.example.com/v1");
+//! ```
+//!
+//! The Schema Object of 2.0 is a subset of that of 3.0, so schemas are
+//! read as [`Schema`]s directly; references to `#/definitions`,
+//! `#/parameters` and `#/responses` are moved to the Components Object.
+
+use crate::walk::{Referenceable, VisitRefs, Walk};
+use crate::{
+    APIKeyLocation, Components, ExternalDocumentation, HeaderStyle, Info, Map, MediaType, Name,
+    OAuth2Flow, OAuth2Flows, ObjectType, OpenAPI, ParameterData, ParameterSchemaOrContent,
+    PathStyle, QueryStyle, Reference, ReferenceOr, RequestBody, Schema, SchemaKind,
+    SecurityRequirement, Server, StatusCode, Tag, Type,
+};
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::convert::Infallible;
+
+/// The root object of a Swagger 2.0 document.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Swagger {
+    /// REQUIRED. The version of the specification, which MUST be `2.0`.
+    pub swagger: String,
+    /// REQUIRED. Provides metadata about the API.
+    pub info: Info,
+    /// The host (name or ip) serving the API, which MAY include a port.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
+    /// The base path on which the API is served, relative to the host.
+    /// It MUST start with a leading slash.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_path: Option<String>,
+    /// The transfer protocols of the API.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub schemes: Vec<Scheme>,
+    /// The MIME types the APIs can consume, unless overridden by an operation.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub consumes: Vec<String>,
+    /// The MIME types the APIs can produce, unless overridden by an operation.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub produces: Vec<String>,
+    /// REQUIRED. The available paths and operations for the API, without
+    /// the extensions of the Paths Object.
+    #[serde(
+        default,
+        deserialize_with = "crate::util::deserialize_without_extensions"
+    )]
+    pub paths: Map<String, ReferenceOr<PathItem>>,
+    /// Data types produced and consumed by operations.
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub definitions: Map<String, ReferenceOr<Schema>>,
+    /// Parameters that can be used across operations.
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub parameters: Map<String, Parameter>,
+    /// Responses that can be used across operations.
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub responses: Map<String, Response>,
+    /// Security schemes that can be used across the specification.
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub security_definitions: Map<String, SecurityScheme>,
+    /// The security schemes applied to the whole API, unless overridden.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub security: Vec<SecurityRequirement>,
+    /// A list of tags used by the specification with additional metadata.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<Tag>,
+    /// Additional external documentation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_docs: Option<ExternalDocumentation>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Scheme {
+    Http,
+    Https,
+    Ws,
+    Wss,
+}
+
+impl Scheme {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Scheme::Http => "http",
+            Scheme::Https => "https",
+            Scheme::Ws => "ws",
+            Scheme::Wss => "wss",
+        }
+    }
+}
+
+/// Describes the operations available on a single path.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct PathItem {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub get: Option<Operation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub put: Option<Operation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post: Option<Operation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delete: Option<Operation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub options: Option<Operation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub head: Option<Operation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub patch: Option<Operation>,
+    /// A list of parameters that are applicable for all the operations
+    /// described under this path.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parameters: Vec<ReferenceOr<Parameter>>,
+}
+
+/// Describes a single API operation on a path.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Operation {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_docs: Option<ExternalDocumentation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub operation_id: Option<String>,
+    /// The MIME types the operation can consume, overriding those of the
+    /// document. An empty list clears them.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub consumes: Option<Vec<String>>,
+    /// The MIME types the operation can produce, overriding those of the
+    /// document. An empty list clears them.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub produces: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parameters: Vec<ReferenceOr<Parameter>>,
+    /// REQUIRED. The list of possible responses as they are returned
+    /// from executing this operation.
+    pub responses: Responses,
+    /// The transfer protocols of the operation, overriding those of the document.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schemes: Option<Vec<Scheme>>,
+    #[serde(default, skip_serializing_if = "crate::is_false")]
+    pub deprecated: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub security: Option<Vec<SecurityRequirement>>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ParameterLocation {
+    Query,
+    Header,
+    Path,
+    FormData,
+    Body,
+}
+
+/// Describes a single operation parameter.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Parameter {
+    /// REQUIRED. The name of the parameter. Parameter names are case sensitive.
+    pub name: String,
+    /// REQUIRED. The location of the parameter.
+    #[serde(rename = "in")]
+    pub location: ParameterLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "crate::is_false")]
+    pub required: bool,
+    /// The schema of a `body` parameter.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema: Option<ReferenceOr<Schema>>,
+    /// Whether a `query` or `formData` parameter may be sent empty.
+    #[serde(default, skip_serializing_if = "crate::is_false")]
+    pub allow_empty_value: bool,
+    /// The type of any other parameter.
+    #[serde(flatten)]
+    pub items: Items,
+}
+
+/// The type of a parameter or header, or of the items of an array one.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Items {
+    /// `string`, `number`, `integer`, `boolean`, `array` or, for
+    /// `formData` parameters, `file`.
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub item_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    /// REQUIRED if the type is `array`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub items: Option<Box<Items>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub collection_format: Option<CollectionFormat>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maximum: Option<f64>,
+    #[serde(default, skip_serializing_if = "crate::is_false")]
+    pub exclusive_maximum: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minimum: Option<f64>,
+    #[serde(default, skip_serializing_if = "crate::is_false")]
+    pub exclusive_minimum: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_length: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_items: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_items: Option<usize>,
+    #[serde(default, skip_serializing_if = "crate::is_false")]
+    pub unique_items: bool,
+    #[serde(rename = "enum", default, skip_serializing_if = "Vec::is_empty")]
+    pub enumeration: Vec<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multiple_of: Option<f64>,
+}
+
+/// How the values of an array are separated.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CollectionFormat {
+    Csv,
+    Ssv,
+    Tsv,
+    Pipes,
+    /// Separate parameters for each value, for `query` and `formData`
+    /// parameters only.
+    Multi,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct Responses {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<ReferenceOr<Response>>,
+    #[serde(flatten)]
+    #[serde(default)]
+    pub responses: Map<StatusCode, ReferenceOr<Response>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct Response {
+    /// REQUIRED. A short description of the response.
+    pub description: String,
+    /// The structure of the response, if there is a body.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema: Option<ReferenceOr<Schema>>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, Header>,
+    /// Examples of the response, by MIME type.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub examples: BTreeMap<String, Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct Header {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(flatten)]
+    pub items: Items,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type")]
+pub enum SecurityScheme {
+    #[serde(rename = "basic")]
+    Basic,
+    #[serde(rename = "apiKey")]
+    APIKey {
+        name: String,
+        #[serde(rename = "in")]
+        location: APIKeyLocation,
+    },
+    #[serde(rename = "oauth2")]
+    OAuth2 {
+        flow: OAuth2FlowType,
+        #[serde(rename = "authorizationUrl", skip_serializing_if = "Option::is_none")]
+        authorization_url: Option<String>,
+        #[serde(rename = "tokenUrl", skip_serializing_if = "Option::is_none")]
+        token_url: Option<String>,
+        #[serde(default)]
+        scopes: BTreeMap<String, String>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum OAuth2FlowType {
+    Implicit,
+    Password,
+    Application,
+    AccessCode,
+}
+
+impl From<Swagger> for OpenAPI {
+    fn from(swagger: Swagger) -> Self {
+        swagger.into_openapi()
+    }
+}
+
+impl Swagger {
+    /// Converts the document to OpenAPI 3.0.
+    ///
+    /// `host`, `basePath` and `schemes` become servers, and `body` and
+    /// `formData` parameters become request bodies, with a media type for
+    /// each MIME type the operation consumes. Response schemas likewise
+    /// get a media type for each MIME type the operation produces.
+    pub fn into_openapi(self) -> OpenAPI {
+        let converter = Converter {
+            host: self.host.as_deref(),
+            base_path: self.base_path.as_deref(),
+            consumes: &self.consumes,
+            produces: &self.produces,
+            parameters: &self.parameters,
+        };
+
+        let mut components = Components {
+            schemas: self.definitions.clone(),
+            security_schemes: self
+                .security_definitions
+                .iter()
+                .map(|(name, scheme)| (name.clone(), ReferenceOr::Item(security_scheme(scheme))))
+                .collect(),
+            responses: self
+                .responses
+                .iter()
+                .map(|(name, response)| {
+                    let response = converter.response(response, &self.produces);
+                    (name.clone(), ReferenceOr::Item(response))
+                })
+                .collect(),
+            ..Components::default()
+        };
+        for (name, parameter) in &self.parameters {
+            match parameter.location {
+                ParameterLocation::Body => {
+                    let body = converter.body(parameter, &self.consumes);
+                    components
+                        .request_bodies
+                        .insert(name.clone(), ReferenceOr::Item(body));
+                }
+                // 3.0 has no form parameters, so these are inlined wherever used.
+                ParameterLocation::FormData => {}
+                _ => {
+                    components.parameters.insert(
+                        name.clone(),
+                        ReferenceOr::Item(convert_parameter(parameter)),
+                    );
+                }
+            }
+        }
+
+        let paths = self
+            .paths
+            .iter()
+            .map(|(path, item)| {
+                let item = match item {
+                    ReferenceOr::Reference { reference } => ReferenceOr::Reference {
+                        reference: reference.clone(),
+                    },
+                    ReferenceOr::Item(item) => ReferenceOr::Item(converter.path_item(item)),
+                };
+                (path.clone(), item)
+            })
+            .collect();
+
+        let mut api = OpenAPI {
+            openapi: "3.0.3".to_owned(),
+            info: self.info.clone(),
+            servers: converter.servers(&self.schemes),
+            paths,
+            components: Some(components).filter(|c| *c != Components::default()),
+            security: self.security.clone(),
+            tags: self.tags.clone(),
+            external_docs: self.external_docs.clone(),
+            extensions: IndexMap::new(),
+        };
+        let _ = api.walk(&mut Relocator);
+        api
+    }
+}
+
+struct Converter<'a> {
+    host: Option<&'a str>,
+    base_path: Option<&'a str>,
+    consumes: &'a [String],
+    produces: &'a [String],
+    parameters: &'a Map<String, Parameter>,
+}
+
+impl<'a> Converter<'a> {
+    fn servers(&self, schemes: &[Scheme]) -> Vec<Server> {
+        let base_path = self.base_path.unwrap_or_default();
+        let urls = match self.host {
+            Some(host) if schemes.is_empty() => vec![format!("//{}{}", host, base_path)],
+            Some(host) => schemes
+                .iter()
+                .map(|scheme| format!("{}://{}{}", scheme.as_str(), host, base_path))
+                .collect(),
+            None if base_path.is_empty() => Vec::new(),
+            None => vec![base_path.to_owned()],
+        };
+        urls.into_iter()
+            .map(|url| Server {
+                url,
+                ..Server::default()
+            })
+            .collect()
+    }
+
+    fn path_item(&self, item: &PathItem) -> crate::PathItem {
+        let operation = |operation: &Option<Operation>| {
+            operation
+                .as_ref()
+                .map(|operation| self.operation(operation))
+        };
+        crate::PathItem {
+            get: operation(&item.get),
+            put: operation(&item.put),
+            post: operation(&item.post),
+            delete: operation(&item.delete),
+            options: operation(&item.options),
+            head: operation(&item.head),
+            patch: operation(&item.patch),
+            trace: None,
+            servers: Vec::new(),
+            parameters: self.parameters(&item.parameters).0,
+            extensions: IndexMap::new(),
+        }
+    }
+
+    /// Converts parameters, setting aside the request body that `body` and
+    /// `formData` parameters describe in 3.0.
+    fn parameters(
+        &self,
+        parameters: &[ReferenceOr<Parameter>],
+    ) -> (Vec<ReferenceOr<crate::Parameter>>, BodyParameters) {
+        let mut converted = Vec::new();
+        let mut body = BodyParameters::default();
+        for parameter in parameters {
+            match parameter {
+                ReferenceOr::Reference { reference } => {
+                    let name = reference.as_str().strip_prefix("#/parameters/");
+                    match name.and_then(|name| Some((name, self.parameters.get(name)?))) {
+                        Some((name, parameter))
+                            if parameter.location == ParameterLocation::Body =>
+                        {
+                            body.body = Some(ReferenceOr::Reference {
+                                reference: Reference::component(
+                                    crate::ComponentKind::RequestBodies,
+                                    name,
+                                ),
+                            });
+                        }
+                        Some((_, parameter))
+                            if parameter.location == ParameterLocation::FormData =>
+                        {
+                            body.form.push(parameter.clone());
+                        }
+                        _ => converted.push(ReferenceOr::Reference {
+                            reference: reference.clone(),
+                        }),
+                    }
+                }
+                ReferenceOr::Item(parameter) => match parameter.location {
+                    ParameterLocation::Body => {
+                        body.body = Some(ReferenceOr::Item(parameter.clone()));
+                    }
+                    ParameterLocation::FormData => body.form.push(parameter.clone()),
+                    _ => converted.push(ReferenceOr::Item(convert_parameter(parameter))),
+                },
+            }
+        }
+        (converted, body)
+    }
+
+    fn operation(&self, operation: &Operation) -> crate::Operation {
+        let consumes = media_types(operation.consumes.as_deref().unwrap_or(self.consumes));
+        let produces = media_types(operation.produces.as_deref().unwrap_or(self.produces));
+
+        // Path-level body parameters are not carried over, as 3.0 path
+        // items have no request body.
+        let (parameters, body) = self.parameters(&operation.parameters);
+        let request_body = match body.body {
+            Some(ReferenceOr::Reference { reference }) => {
+                Some(ReferenceOr::Reference { reference })
+            }
+            Some(ReferenceOr::Item(parameter)) => {
+                Some(ReferenceOr::Item(self.body(&parameter, &consumes)))
+            }
+            None if !body.form.is_empty() => {
+                Some(ReferenceOr::Item(form_body(&body.form, &consumes)))
+            }
+            None => None,
+        };
+
+        let response = |response: &ReferenceOr<Response>| match response {
+            ReferenceOr::Reference { reference } => ReferenceOr::Reference {
+                reference: reference.clone(),
+            },
+            ReferenceOr::Item(response) => ReferenceOr::Item(self.response(response, &produces)),
+        };
+        crate::Operation {
+            tags: operation.tags.iter().map(Name::from).collect(),
+            summary: operation.summary.clone(),
+            description: operation.description.clone(),
+            external_documentation: operation.external_docs.clone(),
+            operation_id: operation.operation_id.clone(),
+            parameters,
+            request_body,
+            responses: crate::Responses {
+                default: operation.responses.default.as_ref().map(response),
+                responses: operation
+                    .responses
+                    .responses
+                    .iter()
+                    .map(|(code, r)| (code.clone(), response(r)))
+                    .collect(),
+                extensions: IndexMap::new(),
+            },
+            callbacks: BTreeMap::new(),
+            deprecated: operation.deprecated,
+            security: operation.security.clone(),
+            servers: operation
+                .schemes
+                .as_ref()
+                .map(|schemes| self.servers(schemes))
+                .unwrap_or_default(),
+            extensions: IndexMap::new(),
+        }
+    }
+
+    fn body(&self, parameter: &Parameter, consumes: &[String]) -> RequestBody {
+        let media_type = MediaType {
+            schema: parameter.schema.clone(),
+            ..MediaType::default()
+        };
+        RequestBody {
+            description: parameter.description.clone(),
+            content: media_types(consumes)
+                .into_iter()
+                .map(|name| (name.into(), media_type.clone()))
+                .collect(),
+            required: parameter.required,
+            extensions: IndexMap::new(),
+        }
+    }
+
+    fn response(&self, response: &Response, produces: &[String]) -> crate::Response {
+        let mut content = BTreeMap::new();
+        if let Some(schema) = &response.schema {
+            for name in media_types(produces) {
+                content.insert(
+                    name.into(),
+                    MediaType {
+                        schema: Some(schema.clone()),
+                        ..MediaType::default()
+                    },
+                );
+            }
+        }
+        for (name, example) in &response.examples {
+            let media_type = content
+                .entry(name.as_str().into())
+                .or_insert_with(|| MediaType {
+                    schema: response.schema.clone(),
+                    ..MediaType::default()
+                });
+            media_type.example = Some(example.clone());
+        }
+        crate::Response {
+            description: response.description.clone(),
+            headers: response
+                .headers
+                .iter()
+                .map(|(name, header)| {
+                    let header = crate::Header {
+                        description: header.description.clone(),
+                        style: HeaderStyle::Simple,
+                        explode: None,
+                        required: false,
+                        deprecated: None,
+                        format: ParameterSchemaOrContent::Schema(ReferenceOr::Item(items_schema(
+                            &header.items,
+                        ))),
+                        example: None,
+                        examples: BTreeMap::new(),
+                        extensions: IndexMap::new(),
+                    };
+                    (name.clone(), ReferenceOr::Item(header))
+                })
+                .collect(),
+            content: content
+                .into_iter()
+                .map(|(name, media_type)| (name, ReferenceOr::Item(media_type)))
+                .collect(),
+            links: BTreeMap::new(),
+            extensions: IndexMap::new(),
+        }
+    }
+}
+
+/// The parameters of an operation that form its request body in 3.0.
+#[derive(Default)]
+struct BodyParameters {
+    body: Option<ReferenceOr<Parameter>>,
+    form: Vec<Parameter>,
+}
+
+/// The MIME types to describe a body with, defaulting to JSON.
+fn media_types(types: &[String]) -> Vec<String> {
+    if types.is_empty() {
+        vec!["application/json".to_owned()]
+    } else {
+        types.to_vec()
+    }
+}
+
+/// A request body for `formData` parameters, as an object with a property
+/// for each of them.
+fn form_body(form: &[Parameter], consumes: &[String]) -> RequestBody {
+    let mut properties = Map::new();
+    let mut required = Vec::new();
+    for parameter in form {
+        let mut schema = items_schema(&parameter.items);
+        schema.schema_data.description = parameter.description.clone();
+        properties.insert(
+            parameter.name.as_str().into(),
+            ReferenceOr::boxed_item(schema),
+        );
+        if parameter.required {
+            required.push(parameter.name.as_str().into());
+        }
+    }
+    let schema = Schema {
+        schema_data: Default::default(),
+        schema_kind: SchemaKind::Type(Type::Object(ObjectType {
+            properties,
+            required,
+            ..ObjectType::default()
+        })),
+    };
+
+    let has_file = form
+        .iter()
+        .any(|parameter| parameter.items.item_type.as_deref() == Some("file"));
+    let mut names: Vec<String> = consumes
+        .iter()
+        .filter(|name| {
+            name.starts_with("multipart/form-data")
+                || name.starts_with("application/x-www-form-urlencoded")
+        })
+        .cloned()
+        .collect();
+    if names.is_empty() {
+        names.push(if has_file {
+            "multipart/form-data".to_owned()
+        } else {
+            "application/x-www-form-urlencoded".to_owned()
+        });
+    }
+    let media_type = MediaType {
+        schema: Some(ReferenceOr::Item(schema)),
+        ..MediaType::default()
+    };
+    RequestBody {
+        description: None,
+        content: names
+            .into_iter()
+            .map(|name| (name.into(), media_type.clone()))
+            .collect(),
+        required: form.iter().any(|parameter| parameter.required),
+        extensions: IndexMap::new(),
+    }
+}
+
+fn convert_parameter(parameter: &Parameter) -> crate::Parameter {
+    let parameter_data = ParameterData {
+        name: parameter.name.clone(),
+        description: parameter.description.clone(),
+        required: parameter.required,
+        deprecated: None,
+        // Comma-separated arrays are the `form` style without its default
+        // of exploding, which only `multi` keeps.
+        explode: match (&parameter.location, parameter.items.collection_format) {
+            (
+                ParameterLocation::Query,
+                None | Some(CollectionFormat::Csv | CollectionFormat::Tsv),
+            ) if parameter.items.item_type.as_deref() == Some("array") => Some(false),
+            _ => None,
+        },
+        format: ParameterSchemaOrContent::Schema(ReferenceOr::Item(items_schema(&parameter.items))),
+        example: None,
+        examples: BTreeMap::new(),
+        extensions: IndexMap::new(),
+    };
+    match parameter.location {
+        ParameterLocation::Header => crate::Parameter::Header {
+            parameter_data,
+            style: HeaderStyle::Simple,
+        },
+        ParameterLocation::Path => crate::Parameter::Path {
+            parameter_data,
+            style: PathStyle::Simple,
+        },
+        _ => crate::Parameter::Query {
+            parameter_data,
+            allow_reserved: false,
+            style: match parameter.items.collection_format {
+                Some(CollectionFormat::Ssv) => QueryStyle::SpaceDelimited,
+                Some(CollectionFormat::Pipes) => QueryStyle::PipeDelimited,
+                _ => QueryStyle::Form,
+            },
+            allow_empty_value: Some(true).filter(|_| parameter.allow_empty_value),
+        },
+    }
+}
+
+/// The schema of a parameter or header type. `file` becomes a binary string.
+fn items_schema(items: &Items) -> Schema {
+    fn convert(value: &mut Value) {
+        if let Value::Object(object) = value {
+            object.remove("collectionFormat");
+            // Bounds are read as numbers, which an integer schema would
+            // not accept in the form `1.0`.
+            if object.get("type").and_then(Value::as_str) == Some("integer") {
+                for key in ["minimum", "maximum", "multipleOf"] {
+                    if let Some(n) = object.get(key).and_then(Value::as_f64) {
+                        if n.fract() == 0.0 {
+                            object.insert(key.to_owned(), (n as i64).into());
+                        }
+                    }
+                }
+            }
+            if object.get("type").and_then(Value::as_str) == Some("file") {
+                object.insert("type".to_owned(), "string".into());
+                object.insert("format".to_owned(), "binary".into());
+            }
+            if let Some(items) = object.get_mut("items") {
+                convert(items);
+            }
+        }
+    }
+
+    let mut value = serde_json::to_value(items).unwrap();
+    convert(&mut value);
+    serde_json::from_value(value).unwrap_or_else(|_| Schema {
+        schema_data: Default::default(),
+        schema_kind: SchemaKind::Any(Default::default()),
+    })
+}
+
+fn security_scheme(scheme: &SecurityScheme) -> crate::SecurityScheme {
+    match scheme {
+        SecurityScheme::Basic => crate::SecurityScheme::HTTP {
+            scheme: "basic".to_owned(),
+            bearer_format: None,
+            extensions: IndexMap::new(),
+        },
+        SecurityScheme::APIKey { name, location } => crate::SecurityScheme::APIKey {
+            location: location.clone(),
+            name: name.clone(),
+            extensions: IndexMap::new(),
+        },
+        SecurityScheme::OAuth2 {
+            flow,
+            authorization_url,
+            token_url,
+            scopes,
+        } => {
+            let authorization_url = authorization_url.clone().unwrap_or_default();
+            let token_url = token_url.clone().unwrap_or_default();
+            let scopes = scopes.clone();
+            let mut flows = OAuth2Flows::default();
+            match flow {
+                OAuth2FlowType::Implicit => {
+                    flows.implicit = Some(OAuth2Flow::Implicit {
+                        authorization_url,
+                        refresh_url: None,
+                        scopes,
+                        extensions: IndexMap::new(),
+                    })
+                }
+                OAuth2FlowType::Password => {
+                    flows.password = Some(OAuth2Flow::Password {
+                        refresh_url: None,
+                        token_url,
+                        scopes,
+                        extensions: IndexMap::new(),
+                    })
+                }
+                OAuth2FlowType::Application => {
+                    flows.client_credentials = Some(OAuth2Flow::ClientCredentials {
+                        refresh_url: None,
+                        token_url,
+                        scopes,
+                        extensions: IndexMap::new(),
+                    })
+                }
+                OAuth2FlowType::AccessCode => {
+                    flows.authorization_code = Some(OAuth2Flow::AuthorizationCode {
+                        authorization_url,
+                        token_url,
+                        refresh_url: None,
+                        scopes,
+                        extensions: IndexMap::new(),
+                    })
+                }
+            }
+            crate::SecurityScheme::OAuth2 {
+                flows,
+                extensions: IndexMap::new(),
+            }
+        }
+    }
+}
+
+/// Moves references to the sections of a 2.0 document to those of the
+/// Components Object.
+struct Relocator;
+
+impl VisitRefs for Relocator {
+    type Error = Infallible;
+
+    fn reference<T: Referenceable>(
+        &mut self,
+        value: &mut ReferenceOr<T>,
+    ) -> Result<(), Infallible> {
+        let reference = match value {
+            ReferenceOr::Item(item) => return item.walk(self),
+            ReferenceOr::Reference { reference } => reference,
+        };
+        let sections = [
+            ("#/definitions/", "#/components/schemas/"),
+            ("#/parameters/", "#/components/parameters/"),
+            ("#/responses/", "#/components/responses/"),
+        ];
+        for (from, to) in &sections {
+            if let Some(name) = reference.as_str().strip_prefix(from) {
+                if let Ok(relocated) = format!("{}{}", to, name).parse() {
+                    *reference = relocated;
+                }
+                break;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn converts_documents() {
+        let swagger: Swagger = serde_yaml::from_str(
+            "
+swagger: '2.0'
+info: {title: Pets, version: '1'}
+host: pets.example.com
+basePath: /v1
+schemes: [https]
+consumes: [application/json]
+produces: [application/json, application/xml]
+securityDefinitions:
+  oauth: {type: oauth2, flow: accessCode, authorizationUrl: 'https://a', tokenUrl: 'https://t'}
+parameters:
+  limit: {name: limit, in: query, type: integer, format: int32, minimum: 1, default: 3}
+  pet: {name: pet, in: body, required: true, schema: {$ref: '#/definitions/Pet'}}
+paths:
+  /pets:
+    get:
+      parameters:
+        - $ref: '#/parameters/limit'
+        - {name: tags, in: query, type: array, items: {type: string}, collectionFormat: pipes}
+      responses:
+        '200':
+          description: pets
+          schema: {type: array, items: {$ref: '#/definitions/Pet'}}
+          headers:
+            X-Rate-Limit: {type: integer}
+    post:
+      parameters: [{$ref: '#/parameters/pet'}]
+      responses: {'201': {description: created}}
+  /pets/{id}/photo:
+    put:
+      consumes: [multipart/form-data]
+      parameters:
+        - {name: id, in: path, required: true, type: string}
+        - {name: photo, in: formData, required: true, type: file}
+        - {name: caption, in: formData, type: string, description: shown below}
+      responses: {'204': {description: uploaded}}
+definitions:
+  Pet:
+    type: object
+    properties:
+      owner: {$ref: '#/definitions/Owner'}
+  Owner: {type: string}
+",
+        )
+        .unwrap();
+
+        let api = swagger.into_openapi();
+        let value = serde_json::to_value(&api).unwrap();
+        assert_eq!(
+            value["servers"],
+            json!([{"url": "https://pets.example.com/v1"}])
+        );
+        assert_eq!(
+            value["components"]["schemas"]["Pet"]["properties"]["owner"],
+            json!({"$ref": "#/components/schemas/Owner"})
+        );
+        assert_eq!(
+            value["components"]["requestBodies"]["pet"],
+            json!({
+                "content": {"application/json": {"schema": {"$ref": "#/components/schemas/Pet"}}},
+                "required": true,
+            })
+        );
+        assert_eq!(
+            value["components"]["securitySchemes"]["oauth"]["flows"],
+            json!({"authorizationCode": {
+                "authorizationUrl": "https://a",
+                "tokenUrl": "https://t",
+                "refreshUrl": null,
+                "scopes": {},
+            }})
+        );
+
+        assert_eq!(
+            value["components"]["parameters"]["limit"]["schema"],
+            json!({"type": "integer", "format": "int32", "minimum": 1, "default": 3})
+        );
+
+        let pets = &value["paths"]["/pets"];
+        assert_eq!(
+            pets["get"]["parameters"],
+            json!([
+                {"$ref": "#/components/parameters/limit"},
+                {
+                    "in": "query",
+                    "name": "tags",
+                    "schema": {"type": "array", "items": {"type": "string"}},
+                    "style": "pipeDelimited",
+                },
+            ])
+        );
+        let ok = &pets["get"]["responses"]["200"];
+        assert_eq!(
+            ok["content"]["application/xml"]["schema"]["items"],
+            json!({"$ref": "#/components/schemas/Pet"})
+        );
+        assert_eq!(
+            ok["headers"]["X-Rate-Limit"]["schema"],
+            json!({"type": "integer"})
+        );
+        assert_eq!(
+            pets["post"]["requestBody"],
+            json!({"$ref": "#/components/requestBodies/pet"})
+        );
+
+        let upload = &value["paths"]["/pets/{id}/photo"]["put"];
+        assert_eq!(upload["parameters"][0]["in"], "path");
+        assert_eq!(
+            upload["requestBody"],
+            json!({
+                "content": {"multipart/form-data": {"schema": {
+                    "type": "object",
+                    "properties": {
+                        "photo": {"type": "string", "format": "binary"},
+                        "caption": {"type": "string", "description": "shown below"},
+                    },
+                    "required": ["photo"],
+                }}},
+                "required": true,
+            })
+        );
+    }
+
+    fn convert(yaml: &str) -> Value {
+        let swagger: Swagger = serde_yaml::from_str(yaml).unwrap();
+        serde_json::to_value(swagger.into_openapi()).unwrap()
+    }
+
+    #[test]
+    fn converts_body_and_form_parameters() {
+        let value = convert(
+            "
+swagger: '2.0'
+info: {title: t, version: '1'}
+paths:
+  /pets:
+    parameters: [{name: ignored, in: body, schema: {type: string}}]
+    post:
+      consumes: [application/json, application/xml]
+      parameters:
+        - {name: pet, in: body, description: the pet, schema: {type: object}}
+      responses: {'201': {description: created}}
+    put:
+      parameters:
+        - {name: name, in: formData, required: true, type: string}
+        - {name: age, in: formData, type: integer}
+      responses: {'204': {description: updated}}
+  /photos:
+    post:
+      consumes: [application/json]
+      parameters: [{name: photo, in: formData, type: file}]
+      responses: {'204': {description: uploaded}}
+",
+        );
+        let pets = &value["paths"]["/pets"];
+        assert_eq!(pets.get("parameters"), None);
+        assert_eq!(
+            pets["post"]["requestBody"],
+            json!({
+                "description": "the pet",
+                "content": {
+                    "application/json": {"schema": {"type": "object"}},
+                    "application/xml": {"schema": {"type": "object"}},
+                },
+            })
+        );
+        assert_eq!(pets["post"].get("parameters"), None);
+        assert_eq!(
+            pets["put"]["requestBody"],
+            json!({
+                "content": {"application/x-www-form-urlencoded": {"schema": {
+                    "type": "object",
+                    "properties": {"name": {"type": "string"}, "age": {"type": "integer"}},
+                    "required": ["name"],
+                }}},
+                "required": true,
+            })
+        );
+        assert_eq!(
+            value["paths"]["/photos"]["post"]["requestBody"]["content"],
+            json!({"multipart/form-data": {"schema": {
+                "type": "object",
+                "properties": {"photo": {"type": "string", "format": "binary"}},
+            }}})
+        );
+    }
+
+    #[test]
+    fn describes_responses_in_what_operations_produce() {
+        let value = convert(
+            "
+swagger: '2.0'
+info: {title: t, version: '1'}
+produces: [application/xml]
+paths:
+  /a:
+    get:
+      responses: {'200': {description: a, schema: {type: string}}}
+  /b:
+    get:
+      produces: [text/plain, text/csv]
+      responses:
+        '200':
+          description: b
+          schema: {type: string}
+          examples: {text/plain: hello, application/yaml: hi}
+  /c:
+    get:
+      produces: []
+      responses: {'200': {description: c, schema: {type: string}}}
+",
+        );
+        let content =
+            |path: &str| value["paths"][path]["get"]["responses"]["200"]["content"].clone();
+        assert_eq!(
+            content("/a"),
+            json!({"application/xml": {"schema": {"type": "string"}}})
+        );
+        assert_eq!(
+            content("/b"),
+            json!({
+                "application/yaml": {"schema": {"type": "string"}, "example": "hi"},
+                "text/csv": {"schema": {"type": "string"}},
+                "text/plain": {"schema": {"type": "string"}, "example": "hello"},
+            })
+        );
+        assert_eq!(
+            content("/c"),
+            json!({"application/json": {"schema": {"type": "string"}}})
+        );
+    }
+
+    #[test]
+    fn converts_security_definitions() {
+        let value = convert(
+            "
+swagger: '2.0'
+info: {title: t, version: '1'}
+securityDefinitions:
+  basic: {type: basic}
+  key: {type: apiKey, name: X-Key, in: header}
+  implicit: {type: oauth2, flow: implicit, authorizationUrl: 'https://a', scopes: {read: reads}}
+  password: {type: oauth2, flow: password, tokenUrl: 'https://t'}
+  application: {type: oauth2, flow: application, tokenUrl: 'https://t'}
+security: [{key: []}]
+paths: {}
+",
+        );
+        let schemes = &value["components"]["securitySchemes"];
+        assert_eq!(
+            schemes["basic"],
+            json!({"type": "http", "scheme": "basic", "bearerFormat": null})
+        );
+        assert_eq!(
+            schemes["key"],
+            json!({"type": "apiKey", "in": "header", "name": "X-Key"})
+        );
+        assert_eq!(
+            schemes["implicit"]["flows"],
+            json!({"implicit": {
+                "authorizationUrl": "https://a",
+                "refreshUrl": null,
+                "scopes": {"read": "reads"},
+            }})
+        );
+        assert_eq!(
+            schemes["password"]["flows"]["password"]["tokenUrl"],
+            "https://t"
+        );
+        assert_eq!(
+            schemes["application"]["flows"]["clientCredentials"]["tokenUrl"],
+            "https://t"
+        );
+        assert_eq!(value["security"], json!([{"key": []}]));
+    }
+
+    #[test]
+    fn relocates_references_to_definitions() {
+        let value = convert(
+            "
+swagger: '2.0'
+info: {title: t, version: '1'}
+parameters:
+  id: {name: id, in: path, required: true, type: string}
+responses:
+  NotFound: {description: not found, schema: {$ref: '#/definitions/Error'}}
+paths:
+  /pets/{id}:
+    get:
+      parameters: [{$ref: '#/parameters/id'}]
+      responses:
+        '200':
+          description: a pet
+          schema:
+            type: object
+            properties: {pet: {allOf: [{$ref: '#/definitions/Pet'}]}}
+            additionalProperties: {$ref: '#/definitions/Tag'}
+        '404': {$ref: '#/responses/NotFound'}
+        default: {description: other, schema: {$ref: 'errors.yaml#/definitions/Error'}}
+definitions:
+  Pet: {type: object, properties: {tags: {type: array, items: {$ref: '#/definitions/Tag'}}}}
+  Tag: {type: string}
+  Error: {type: object}
+",
+        );
+        assert_eq!(
+            value["components"]["schemas"]["Pet"]["properties"]["tags"]["items"],
+            json!({"$ref": "#/components/schemas/Tag"})
+        );
+        assert_eq!(
+            value["components"]["responses"]["NotFound"]["content"]["application/json"]["schema"],
+            json!({"$ref": "#/components/schemas/Error"})
+        );
+        let get = &value["paths"]["/pets/{id}"]["get"];
+        assert_eq!(
+            get["parameters"],
+            json!([{"$ref": "#/components/parameters/id"}])
+        );
+        let schema = &get["responses"]["200"]["content"]["application/json"]["schema"];
+        assert_eq!(
+            schema["properties"]["pet"]["allOf"],
+            json!([{"$ref": "#/components/schemas/Pet"}])
+        );
+        assert_eq!(
+            schema["additionalProperties"],
+            json!({"$ref": "#/components/schemas/Tag"})
+        );
+        assert_eq!(
+            get["responses"]["404"],
+            json!({"$ref": "#/components/responses/NotFound"})
+        );
+        assert_eq!(
+            get["responses"]["default"]["content"]["application/json"]["schema"],
+            json!({"$ref": "errors.yaml#/definitions/Error"})
+        );
+    }
+}
@@ -0,0 +1,319 @@
+use crate::*;
+use serde_json::Value;
+
+/// The operations of a tag, in the order of the paths.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TagGroup<'a> {
+    /// The name of the tag, or `None` for the operations without tags.
+    pub name: Option<&'a str>,
+    /// The declaration of the tag in the root `tags`, if it is declared.
+    pub tag: Option<&'a Tag>,
+    pub operations: Vec<(&'a str, Method, &'a Operation)>,
+}
+
+impl OpenAPI {
+    /// The names of the tags of the document: those declared in the root
+    /// `tags`, in order, then those only used by operations, in the order
+    /// they are first used.
+    pub fn tag_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.tags.iter().map(|tag| tag.name.as_str()).collect();
+        for (_, _, operation) in self.operations() {
+            for tag in &operation.tags {
+                if !names.contains(&tag.as_str()) {
+                    names.push(tag);
+                }
+            }
+        }
+        names
+    }
+
+    /// The operations of each tag of [`OpenAPI::tag_names`], declared tags
+    /// that no operation uses included, then those without tags, if there
+    /// are any. An operation with several tags is in the group of each.
+    ///
+    /// ```
+    /// use openapiv3::OpenAPI;
+    ///
+    /// let api: OpenAPI = serde_yaml::from_str(
+    ///     "
+    /// openapi: 3.0.0
+    /// info: {title: Pets, version: '1'}
+    /// tags: [{name: pets}, {name: stores}]
+    /// paths:
+    ///   /health: {get: {responses: {}}}
+    ///   /pets: {get: {tags: [pets, owners], responses: {}}}
+    /// ",
+    /// )
+    /// .unwrap();
+    /// let groups: Vec<_> = api
+    ///     .operations_by_tag()
+    ///     .into_iter()
+    ///     .map(|group| (group.name, group.operations.len()))
+    ///     .collect();
+    /// assert_eq!(
+    ///     groups,
+    ///     [(Some("pets"), 1), (Some("stores"), 0), (Some("owners"), 1), (None, 1)]
+    /// );
+    /// assert_eq!(api.undeclared_tags(), ["owners"]);
+    /// ```
+    pub fn operations_by_tag(&self) -> Vec<TagGroup<'_>> {
+        let mut groups: Vec<TagGroup<'_>> = self
+            .tag_names()
+            .into_iter()
+            .map(|name| TagGroup {
+                name: Some(name),
+                tag: self.tags.iter().find(|tag| tag.name == name),
+                operations: self
+                    .operations()
+                    .filter(|(_, _, operation)| operation.tags.iter().any(|tag| tag == name))
+                    .collect(),
+            })
+            .collect();
+        let untagged: Vec<_> = self
+            .operations()
+            .filter(|(_, _, operation)| operation.tags.is_empty())
+            .collect();
+        if !untagged.is_empty() {
+            groups.push(TagGroup {
+                name: None,
+                tag: None,
+                operations: untagged,
+            });
+        }
+        groups
+    }
+
+    /// The tags used by operations but not declared in the root `tags`, in
+    /// the order they are first used.
+    pub fn undeclared_tags(&self) -> Vec<&str> {
+        self.tag_names().split_off(self.tags.len())
+    }
+
+    /// Declares each of the [`OpenAPI::undeclared_tags`] in the root `tags`,
+    /// after those already declared.
+    pub fn declare_tags(&mut self) {
+        let undeclared: Vec<Name> = self.undeclared_tags().into_iter().map(Name::from).collect();
+        self.tags.extend(undeclared.into_iter().map(|name| Tag {
+            name,
+            ..Tag::default()
+        }));
+    }
+
+    /// Renames the tag `from` to `to` wherever it is used: in the root
+    /// `tags`, the operations of the paths and of callbacks, and the
+    /// `x-tagGroups` extension of the root. If `to` is already a tag, the
+    /// two are merged: operations keep one of them, and the declaration of
+    /// `to` keeps its place, taking the description, external
+    /// documentation and extensions of `from` that it lacks.
+    ///
+    /// Returns whether `from` was used or declared.
+    ///
+    /// ```
+    /// use openapiv3::OpenAPI;
+    ///
+    /// let mut api: OpenAPI = serde_yaml::from_str(
+    ///     "
+    /// openapi: 3.0.0
+    /// info: {title: Pets, version: '1'}
+    /// tags: [{name: pets}, {name: animals, description: Animals.}]
+    /// paths:
+    ///   /pets: {get: {tags: [animals, pets], responses: {}}}
+    /// ",
+    /// )
+    /// .unwrap();
+    /// assert!(api.rename_tag("animals", "pets"));
+    /// assert_eq!(api.tags.len(), 1);
+    /// assert_eq!(api.tags[0].description.as_deref(), Some("Animals."));
+    /// assert_eq!(api.operations().next().unwrap().2.tags, ["pets"]);
+    /// ```
+    pub fn rename_tag(&mut self, from: &str, to: &str) -> bool {
+        if from == to {
+            return self.tag_names().contains(&from);
+        }
+        let mut renamed = false;
+        each_operation_mut(self, &mut |operation| {
+            renamed |= rename(&mut operation.tags, from, to);
+        });
+
+        if let Some(i) = self.tags.iter().position(|tag| tag.name == from) {
+            renamed = true;
+            let mut declaration = self.tags.remove(i);
+            match self.tags.iter_mut().find(|tag| tag.name == to) {
+                Some(tag) => {
+                    if tag.description.is_none() {
+                        tag.description = declaration.description;
+                    }
+                    if tag.external_docs.is_none() {
+                        tag.external_docs = declaration.external_docs;
+                    }
+                    for (key, value) in declaration.extensions {
+                        tag.extensions.entry(key).or_insert(value);
+                    }
+                }
+                None => {
+                    declaration.name = to.into();
+                    self.tags.insert(i, declaration);
+                }
+            }
+        }
+
+        let groups = self.extensions.get_mut("x-tagGroups");
+        for group in groups.and_then(Value::as_array_mut).into_iter().flatten() {
+            let tags = group.get_mut("tags").and_then(Value::as_array_mut);
+            let tags = match tags {
+                Some(tags) => tags,
+                None => continue,
+            };
+            let mut names: Vec<Name> = tags
+                .iter()
+                .filter_map(|tag| Some(tag.as_str()?.into()))
+                .collect();
+            if names.len() == tags.len() && rename(&mut names, from, to) {
+                renamed = true;
+                *tags = names
+                    .iter()
+                    .map(|name| Value::from(name.as_str()))
+                    .collect();
+            }
+        }
+        renamed
+    }
+
+    /// Merges each of the tags `from` into `into` with
+    /// [`OpenAPI::rename_tag`], returning whether any was used or declared.
+    pub fn merge_tags(&mut self, from: &[&str], into: &str) -> bool {
+        let mut merged = false;
+        for tag in from {
+            merged |= self.rename_tag(tag, into);
+        }
+        merged
+    }
+
+    /// Orders the tags of each operation, those of callbacks included, as
+    /// the root `tags` declares them, with those not declared last in the
+    /// order they were in.
+    pub fn order_tags(&mut self) {
+        let declared: Vec<Name> = self.tags.iter().map(|tag| tag.name.clone()).collect();
+        each_operation_mut(self, &mut |operation| {
+            operation.tags.sort_by_key(|tag| {
+                declared
+                    .iter()
+                    .position(|name| name == tag)
+                    .unwrap_or(declared.len())
+            });
+        });
+    }
+}
+
+/// Replaces `from` with `to` in `tags`, keeping only the first of them if
+/// `to` was already there. Returns whether `from` was there.
+fn rename(tags: &mut Vec<Name>, from: &str, to: &str) -> bool {
+    if !tags.iter().any(|tag| tag == from) {
+        return false;
+    }
+    let mut seen = false;
+    tags.retain_mut(|tag| {
+        if tag == from {
+            *tag = to.into();
+        }
+        let first = !(tag == to && seen);
+        seen |= tag == to;
+        first
+    });
+    true
+}
+
+/// Calls `f` with each operation of the paths and of callbacks, nested
+/// ones included. Path items and callbacks given by reference are skipped.
+fn each_operation_mut(api: &mut OpenAPI, f: &mut dyn FnMut(&mut Operation)) {
+    for item in api.paths.values_mut() {
+        if let ReferenceOr::Item(item) = item {
+            item.operations_mut()
+                .for_each(|(_, operation)| each_nested_mut(operation, f));
+        }
+    }
+    if let Some(components) = &mut api.components {
+        components
+            .callbacks
+            .values_mut()
+            .for_each(|callback| each_callback_mut(callback, f));
+    }
+}
+
+fn each_nested_mut(operation: &mut Operation, f: &mut dyn FnMut(&mut Operation)) {
+    f(operation);
+    operation
+        .callbacks
+        .values_mut()
+        .for_each(|callback| each_callback_mut(callback, f));
+}
+
+fn each_callback_mut(callback: &mut ReferenceOr<Callback>, f: &mut dyn FnMut(&mut Operation)) {
+    if let ReferenceOr::Item(callback) = callback {
+        for item in callback.values_mut() {
+            item.operations_mut()
+                .for_each(|(_, operation)| each_nested_mut(operation, f));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renames_and_orders_tags() {
+        let mut api: OpenAPI = serde_yaml::from_str(
+            "
+openapi: 3.0.0
+info: {title: Pets, version: '1'}
+tags: [{name: pets}, {name: stores, description: Stores.}]
+paths:
+  /pets:
+    post:
+      tags: [shops, pets]
+      responses: {}
+      callbacks:
+        onAdopted: {'{$request.body#/url}': {post: {tags: [shops], responses: {}}}}
+x-tagGroups:
+  - {name: Shopping, tags: [shops, stores]}
+",
+        )
+        .unwrap();
+        assert_eq!(api.tag_names(), ["pets", "stores", "shops"]);
+        assert!(api.rename_tag("shops", "stores"));
+        assert!(!api.rename_tag("shops", "stores"));
+        assert!(api.undeclared_tags().is_empty());
+
+        let (_, _, operation) = api.operations().next().unwrap();
+        assert_eq!(operation.tags, ["stores", "pets"]);
+        let (_, _, callback) = api.callback_operations().next().unwrap();
+        assert_eq!(callback.tags, ["stores"]);
+        assert_eq!(
+            api.extensions["x-tagGroups"],
+            serde_json::json!([{"name": "Shopping", "tags": ["stores"]}])
+        );
+
+        api.order_tags();
+        let (_, _, operation) = api.operations().next().unwrap();
+        assert_eq!(operation.tags, ["pets", "stores"]);
+
+        assert!(api.rename_tag("stores", "shops"));
+        assert_eq!(api.tags[1].name, "shops");
+        assert_eq!(api.tags[1].description.as_deref(), Some("Stores."));
+        assert!(api.merge_tags(&["pets", "shops"], "all"));
+        let names: Vec<_> = api.tags.iter().map(|tag| tag.name.as_str()).collect();
+        assert_eq!(names, ["all"]);
+        let (_, _, operation) = api.operations().next().unwrap();
+        assert_eq!(operation.tags, ["all"]);
+
+        api.operations_mut()
+            .next()
+            .unwrap()
+            .2
+            .tags
+            .push("new".into());
+        api.declare_tags();
+        assert_eq!(api.tags[1].name, "new");
+    }
+}
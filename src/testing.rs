@@ -0,0 +1,191 @@
+//! Assertions for contract tests, which check that the responses of a
+//! running API conform to its document.
+//!
+//! ```should_panic
+//! use openapiv3::{assert_response_conforms, OpenAPI};
+//!
+//! let api: OpenAPI = serde_yaml::from_str(
+//!     "
+//! openapi: 3.0.0
+//! info: {title: t, version: '1'}
+//! paths:
+//!   /pets/{id}:
+//!     get:
+//!       operationId: getPet
+//!       responses:
+//!         200:
+//!           description: a pet
+//!           content:
+//!             application/json:
+//!               schema: {type: object, properties: {name: {type: string}}}
+//! ",
+//! )
+//! .unwrap();
+//! let headers = [("Content-Type", "application/json")];
+//! assert_response_conforms!(api, "getPet", 200, &headers, br#"{"name": "Rex"}"#);
+//!
+//! // Panics with:
+//! //
+//! // response 200 of `getPet` does not conform to the document:
+//! //   body: /name: expected string
+//! //     found: 7
+//! assert_response_conforms!(api, "getPet", 200, &headers, br#"{"name": 7}"#);
+//! ```
+
+use crate::validation::response::{self, ResponseError, ResponseErrorKind, ResponsePart};
+use crate::*;
+use serde_json::Value;
+use std::error::Error;
+use std::fmt;
+
+/// Why a response fails [`check_response`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConformanceError {
+    /// The document has no operation of the `operationId`.
+    UnknownOperation(String),
+    /// The response does not conform to its operation.
+    Nonconforming {
+        operation_id: String,
+        status: u16,
+        errors: Vec<ResponseError>,
+        /// The body, if it is JSON, for showing the values found where
+        /// they do not conform.
+        body: Option<Value>,
+    },
+}
+
+impl fmt::Display for ConformanceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConformanceError::UnknownOperation(operation_id) => {
+                write!(f, "the document has no operation `{}`", operation_id)
+            }
+            ConformanceError::Nonconforming {
+                operation_id,
+                status,
+                errors,
+                body,
+            } => {
+                write!(
+                    f,
+                    "response {} of `{}` does not conform to the document:",
+                    status, operation_id
+                )?;
+                for error in errors {
+                    write!(f, "\n  {}", error)?;
+                    let found = match (&error.part, &error.kind, body) {
+                        (ResponsePart::Body, ResponseErrorKind::Schema(violation), Some(body)) => {
+                            body.pointer(&violation.path)
+                        }
+                        _ => None,
+                    };
+                    if let Some(found) = found {
+                        let found = serde_json::to_string_pretty(found).unwrap_or_default();
+                        write!(f, "\n    found: {}", found.replace('\n', "\n    "))?;
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Error for ConformanceError {}
+
+/// Checks a response to the operation of `operation_id` as
+/// [`response::validate`] does, with the content type of the body taken
+/// from its `Content-Type` header.
+pub fn check_response(
+    api: &OpenAPI,
+    operation_id: &str,
+    status: u16,
+    headers: &[(&str, &str)],
+    body: &[u8],
+) -> Result<(), ConformanceError> {
+    let (_, _, operation) = api
+        .operation_by_id(operation_id)
+        .ok_or_else(|| ConformanceError::UnknownOperation(operation_id.to_owned()))?;
+    let default_components = Components::default();
+    let components = api.components.as_ref().unwrap_or(&default_components);
+
+    let mut actual = response::Response::new(status);
+    actual.headers = headers.to_vec();
+    actual.content_type = headers
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case("content-type"))
+        .map(|(_, value)| *value);
+    actual.body = body;
+    response::validate(operation, components, &actual).map_err(|errors| {
+        ConformanceError::Nonconforming {
+            operation_id: operation_id.to_owned(),
+            status,
+            errors,
+            body: serde_json::from_slice(body).ok(),
+        }
+    })
+}
+
+/// Asserts that a response to an operation, by its `operationId`, conforms
+/// to the document, panicking with each way it does not otherwise; see
+/// [`testing::check_response`](crate::testing::check_response).
+///
+/// Takes the document, the `operationId`, the status code, the headers as
+/// a slice of name and value pairs, and the body as bytes.
+#[macro_export]
+macro_rules! assert_response_conforms {
+    ($api:expr, $operation_id:expr, $status:expr, $headers:expr, $body:expr $(,)?) => {
+        if let Err(error) =
+            $crate::testing::check_response(&$api, $operation_id, $status, $headers, $body)
+        {
+            panic!("{}", error);
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_nonconformance() {
+        let api: OpenAPI = serde_yaml::from_str(
+            "
+openapi: 3.0.0
+info: {title: t, version: '1'}
+paths:
+  /pets:
+    get:
+      operationId: listPets
+      responses:
+        200:
+          description: pets
+          headers: {X-Total: {required: true, schema: {type: integer}}}
+          content:
+            application/json:
+              schema:
+                type: array
+                items: {type: object, required: [name], properties: {name: {type: string}}}
+",
+        )
+        .unwrap();
+        let headers = [("content-type", "application/json")];
+        let body = br#"[{"name": "Rex"}, {"name": {"first": "Tom"}}]"#;
+        let error = check_response(&api, "listPets", 200, &headers, body).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "response 200 of `listPets` does not conform to the document:\
+             \n  header `X-Total`: is required\
+             \n  body: /1/name: expected string\
+             \n    found: {\
+             \n      \"first\": \"Tom\"\
+             \n    }"
+        );
+        assert_eq!(
+            check_response(&api, "getPet", 200, &[], b""),
+            Err(ConformanceError::UnknownOperation("getPet".to_owned()))
+        );
+
+        let headers = [("X-Total", "1"), ("Content-Type", "application/json")];
+        assert_response_conforms!(api, "listPets", 200, &headers, br#"[{"name": "Rex"}]"#);
+    }
+}
@@ -0,0 +1,337 @@
+use crate::*;
+use serde_json::Value;
+
+/// The shape of the values of a schema, as a code generator would model
+/// them with types. See [`Schema::to_type_shape`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypeShape {
+    /// Any value at all, for schemas without a type.
+    Any,
+    Boolean,
+    String {
+        format: Option<String>,
+    },
+    Integer {
+        format: Option<String>,
+    },
+    Number {
+        format: Option<String>,
+    },
+    /// One of the values of an `enum`.
+    Enum {
+        variants: Vec<Value>,
+    },
+    /// An object with declared properties, and the shape of any others it
+    /// may have if its `additionalProperties` give one or are `true`.
+    Struct {
+        fields: Vec<FieldShape>,
+        additional: Option<Box<TypeShape>>,
+    },
+    /// An object with no declared properties, whose values have the shape.
+    Map(Box<TypeShape>),
+    Vec(Box<TypeShape>),
+    /// The branches of a `oneOf` or an `anyOf`, with the property that
+    /// tells them apart if there is a discriminator.
+    OneOf {
+        variants: Vec<TypeShape>,
+        discriminator: Option<String>,
+    },
+    /// The shape, or `null`.
+    Nullable(Box<TypeShape>),
+    /// A schema of the components, by name, or another reference as it is
+    /// written.
+    Ref(String),
+}
+
+/// A property of a [`TypeShape::Struct`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldShape {
+    pub name: String,
+    pub shape: TypeShape,
+    pub required: bool,
+    pub description: Option<String>,
+}
+
+impl TypeShape {
+    fn of_reference(reference: &Reference) -> TypeShape {
+        match (reference.component_kind(), reference.name()) {
+            (Some(ComponentKind::Schemas), Some(name)) if !reference.is_external() => {
+                TypeShape::Ref(name.into_owned())
+            }
+            _ => TypeShape::Ref(reference.as_str().to_owned()),
+        }
+    }
+
+    fn nullable(self, nullable: bool) -> TypeShape {
+        match self {
+            TypeShape::Any | TypeShape::Nullable(_) => self,
+            shape if nullable => TypeShape::Nullable(Box::new(shape)),
+            shape => shape,
+        }
+    }
+}
+
+impl Schema {
+    /// The shape of the values of the schema, for code generators.
+    ///
+    /// References to schemas are kept as [`TypeShape::Ref`]s, except within
+    /// an `allOf` of several branches, which is merged as by
+    /// [`Schema::flatten_all_of`]; an `allOf` of a single branch, as used to
+    /// give a reference siblings, has the shape of that branch. An `anyOf`
+    /// has the shape of a `oneOf`. Objects without properties are
+    /// [`TypeShape::Map`]s, and strings and numbers with an `enum` are
+    /// [`TypeShape::Enum`]s. `nullable` schemas are
+    /// [`TypeShape::Nullable`], unless they accept any value already.
+    ///
+    /// ```
+    /// use openapiv3::{Components, Schema, TypeShape};
+    ///
+    /// let schema: Schema = serde_yaml::from_str(
+    ///     "
+    /// type: array
+    /// items:
+    ///   nullable: true
+    ///   allOf: [{$ref: '#/components/schemas/Pet'}]
+    /// ",
+    /// )
+    /// .unwrap();
+    /// assert_eq!(
+    ///     schema.to_type_shape(&Components::default()).unwrap(),
+    ///     TypeShape::Vec(Box::new(TypeShape::Nullable(Box::new(TypeShape::Ref(
+    ///         "Pet".to_owned()
+    ///     )))))
+    /// );
+    /// ```
+    pub fn to_type_shape(&self, components: &Components) -> Result<TypeShape, MergeError> {
+        let nullable = self.schema_data.nullable;
+        let shape = match &self.schema_kind {
+            SchemaKind::AllOf { all_of } if all_of.len() == 1 => shape_of(&all_of[0], components)?,
+            SchemaKind::AllOf { .. } => {
+                let merged = self.flatten_all_of(components)?;
+                if let SchemaKind::AllOf { .. } = merged.schema_kind {
+                    TypeShape::Any
+                } else {
+                    merged.to_type_shape(components)?
+                }
+            }
+            SchemaKind::OneOf { one_of: branches } | SchemaKind::AnyOf { any_of: branches } => {
+                TypeShape::OneOf {
+                    variants: branches
+                        .iter()
+                        .map(|branch| shape_of(branch, components))
+                        .collect::<Result<_, _>>()?,
+                    discriminator: self
+                        .schema_data
+                        .discriminator
+                        .as_ref()
+                        .map(|discriminator| discriminator.property_name.clone()),
+                }
+            }
+            SchemaKind::Type(Type::Boolean {}) => TypeShape::Boolean,
+            SchemaKind::Type(Type::String(string)) if !string.enumeration.is_empty() => {
+                TypeShape::Enum {
+                    variants: string
+                        .enumeration
+                        .iter()
+                        .cloned()
+                        .map(Value::from)
+                        .collect(),
+                }
+            }
+            SchemaKind::Type(Type::String(string)) => TypeShape::String {
+                format: string.format.name(StringFormat::as_str).map(str::to_owned),
+            },
+            SchemaKind::Type(Type::Integer(integer)) if !integer.enumeration.is_empty() => {
+                TypeShape::Enum {
+                    variants: integer
+                        .enumeration
+                        .iter()
+                        .map(|&n| Value::from(n))
+                        .collect(),
+                }
+            }
+            SchemaKind::Type(Type::Integer(integer)) => TypeShape::Integer {
+                format: integer
+                    .format
+                    .name(IntegerFormat::as_str)
+                    .map(str::to_owned),
+            },
+            SchemaKind::Type(Type::Number(number)) if !number.enumeration.is_empty() => {
+                TypeShape::Enum {
+                    variants: number.enumeration.iter().map(|&n| Value::from(n)).collect(),
+                }
+            }
+            SchemaKind::Type(Type::Number(number)) => TypeShape::Number {
+                format: number.format.name(NumberFormat::as_str).map(str::to_owned),
+            },
+            SchemaKind::Type(Type::Array(array)) => {
+                TypeShape::Vec(Box::new(shape_of(&array.items, components)?))
+            }
+            SchemaKind::Type(Type::Object(object)) => object_shape(
+                &object.properties,
+                &object.required,
+                object.additional_properties.as_ref(),
+                components,
+            )?,
+            SchemaKind::Any(any) if !any.properties.is_empty() => object_shape(
+                &any.properties,
+                &any.required,
+                any.additional_properties.as_ref(),
+                components,
+            )?,
+            SchemaKind::Any(AnySchema {
+                items: Some(items), ..
+            }) => TypeShape::Vec(Box::new(shape_of(items, components)?)),
+            SchemaKind::Any(AnySchema {
+                additional_properties: Some(AdditionalProperties::Schema(values)),
+                ..
+            }) => TypeShape::Map(Box::new(shape_of(values, components)?)),
+            SchemaKind::Any(_) => TypeShape::Any,
+        };
+        Ok(shape.nullable(nullable))
+    }
+}
+
+fn shape_of<T: std::borrow::Borrow<Schema>>(
+    schema: &ReferenceOr<T>,
+    components: &Components,
+) -> Result<TypeShape, MergeError> {
+    match schema {
+        ReferenceOr::Reference { reference } => Ok(TypeShape::of_reference(reference)),
+        ReferenceOr::Item(schema) => schema.borrow().to_type_shape(components),
+    }
+}
+
+fn object_shape(
+    properties: &Map<Name, ReferenceOr<Box<Schema>>>,
+    required: &[Name],
+    additional_properties: Option<&AdditionalProperties>,
+    components: &Components,
+) -> Result<TypeShape, MergeError> {
+    let additional = match additional_properties {
+        Some(AdditionalProperties::Schema(values)) => Some(shape_of(values, components)?),
+        Some(AdditionalProperties::Any) => Some(TypeShape::Any),
+        _ => None,
+    };
+    if properties.is_empty() {
+        return Ok(TypeShape::Map(Box::new(
+            additional.unwrap_or(TypeShape::Any),
+        )));
+    }
+    let mut fields = Vec::new();
+    for (name, schema) in properties {
+        fields.push(FieldShape {
+            name: name.to_string(),
+            shape: shape_of(schema, components)?,
+            required: required.contains(name),
+            description: match schema {
+                ReferenceOr::Item(schema) => schema.schema_data.description.clone(),
+                ReferenceOr::Reference { .. } => None,
+            },
+        });
+    }
+    Ok(TypeShape::Struct {
+        fields,
+        additional: additional.map(Box::new),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use serde_json::json;
+
+    #[test]
+    fn shapes_schemas() {
+        let components: Components = serde_yaml::from_str(
+            "
+schemas:
+  Named: {type: object, required: [name], properties: {name: {type: string}}}
+",
+        )
+        .unwrap();
+        let schema: Schema = serde_yaml::from_str(
+            "
+type: object
+required: [id]
+properties:
+  id: {type: integer, format: int64, description: the id}
+  kind: {type: string, enum: [dog, cat]}
+  named:
+    allOf:
+      - $ref: '#/components/schemas/Named'
+      - {type: object, properties: {nick: {type: number, nullable: true}}}
+  pet:
+    oneOf: [{$ref: '#/components/schemas/Named'}, {type: boolean}]
+    discriminator: {propertyName: name}
+  tags: {type: object, additionalProperties: {type: string, format: uuid}}
+",
+        )
+        .unwrap();
+        let field = |name: &str, shape, required, description: Option<&str>| FieldShape {
+            name: name.to_owned(),
+            shape,
+            required,
+            description: description.map(str::to_owned),
+        };
+        let expected = vec![
+            field(
+                "id",
+                TypeShape::Integer {
+                    format: Some("int64".to_owned()),
+                },
+                true,
+                Some("the id"),
+            ),
+            field(
+                "kind",
+                TypeShape::Enum {
+                    variants: vec![json!("dog"), json!("cat")],
+                },
+                false,
+                None,
+            ),
+            field(
+                "named",
+                TypeShape::Struct {
+                    fields: vec![
+                        field("name", TypeShape::String { format: None }, true, None),
+                        field(
+                            "nick",
+                            TypeShape::Nullable(Box::new(TypeShape::Number { format: None })),
+                            false,
+                            None,
+                        ),
+                    ],
+                    additional: None,
+                },
+                false,
+                None,
+            ),
+            field(
+                "pet",
+                TypeShape::OneOf {
+                    variants: vec![TypeShape::Ref("Named".to_owned()), TypeShape::Boolean],
+                    discriminator: Some("name".to_owned()),
+                },
+                false,
+                None,
+            ),
+            field(
+                "tags",
+                TypeShape::Map(Box::new(TypeShape::String {
+                    format: Some("uuid".to_owned()),
+                })),
+                false,
+                None,
+            ),
+        ];
+        assert_eq!(
+            schema.to_type_shape(&components).unwrap(),
+            TypeShape::Struct {
+                fields: expected,
+                additional: None,
+            }
+        );
+    }
+}
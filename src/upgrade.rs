@@ -0,0 +1,512 @@
+use crate::v3_1::{self, SchemaObject, SchemaType, SchemaTypes};
+use crate::*;
+use serde_json::{Number, Value};
+use std::iter::FromIterator;
+
+impl OpenAPI {
+    /// Converts the document to version 3.1 of the specification.
+    ///
+    /// Schemas are rewritten in terms of JSON Schema 2020-12: `nullable`
+    /// becomes a `null` type (or alternative, for schemas without a type),
+    /// `example` becomes `examples`, and boolean `exclusiveMinimum` and
+    /// `exclusiveMaximum` become the exclusive bound itself. References
+    /// are kept as they are, as components live at the same locations.
+    pub fn upgrade_to_3_1(self) -> v3_1::OpenAPI {
+        v3_1::OpenAPI {
+            openapi: "3.1.0".to_owned(),
+            info: info(self.info),
+            json_schema_dialect: None,
+            servers: self.servers,
+            paths: v3_1::Paths {
+                paths: map_values(self.paths.paths, |item| item.map(path_item)),
+                extensions: self.paths.extensions,
+            },
+            webhooks: Map::new(),
+            components: self.components.map(components),
+            security: self.security,
+            tags: self.tags,
+            external_docs: self.external_docs,
+            extensions: self.extensions,
+        }
+    }
+}
+
+fn map_values<K, T, U, M, F>(map: impl IntoIterator<Item = (K, T)>, mut f: F) -> M
+where
+    M: FromIterator<(K, U)>,
+    F: FnMut(T) -> U,
+{
+    map.into_iter()
+        .map(|(key, value)| (key, f(value)))
+        .collect()
+}
+
+fn info(info: Info) -> v3_1::Info {
+    v3_1::Info {
+        title: info.title,
+        summary: None,
+        description: info.description,
+        terms_of_service: info.terms_of_service,
+        contact: info.contact,
+        license: info.license.map(|license| v3_1::License {
+            name: license.name,
+            identifier: None,
+            url: license.url,
+            extensions: license.extensions,
+        }),
+        version: info.version,
+        extensions: info.extensions,
+    }
+}
+
+fn components(components: Components) -> v3_1::Components {
+    v3_1::Components {
+        schemas: map_values(components.schemas, schema),
+        responses: map_values(components.responses, |r| r.map(response)),
+        parameters: map_values(components.parameters, |p| p.map(parameter)),
+        examples: components.examples,
+        request_bodies: map_values(components.request_bodies, |r| r.map(request_body)),
+        headers: map_values(components.headers, |h| h.map(header)),
+        security_schemes: components.security_schemes,
+        links: components.links,
+        callbacks: map_values(components.callbacks, |c| c.map(callback)),
+        path_items: Map::new(),
+        extensions: components.extensions,
+    }
+}
+
+fn path_item(item: PathItem) -> v3_1::PathItem {
+    v3_1::PathItem {
+        summary: None,
+        description: None,
+        get: item.get.map(operation),
+        put: item.put.map(operation),
+        post: item.post.map(operation),
+        delete: item.delete.map(operation),
+        options: item.options.map(operation),
+        head: item.head.map(operation),
+        patch: item.patch.map(operation),
+        trace: item.trace.map(operation),
+        servers: item.servers,
+        parameters: parameters(item.parameters),
+        extensions: item.extensions,
+    }
+}
+
+fn callback(callback: Callback) -> v3_1::Callback {
+    v3_1::Callback {
+        paths: map_values(callback.paths, path_item),
+        extensions: callback.extensions,
+    }
+}
+
+fn operation(operation: Operation) -> v3_1::Operation {
+    v3_1::Operation {
+        tags: operation.tags,
+        summary: operation.summary,
+        description: operation.description,
+        external_documentation: operation.external_documentation,
+        operation_id: operation.operation_id,
+        parameters: parameters(operation.parameters),
+        request_body: operation.request_body.map(|body| body.map(request_body)),
+        responses: Some(v3_1::Responses {
+            default: operation.responses.default.map(|r| r.map(response)),
+            responses: operation
+                .responses
+                .responses
+                .into_iter()
+                .map(|(code, r)| (code, r.map(response)))
+                .collect(),
+            extensions: operation.responses.extensions,
+        }),
+        callbacks: map_values(operation.callbacks, |c| c.map(callback)),
+        deprecated: operation.deprecated,
+        security: operation.security,
+        servers: operation.servers,
+        extensions: operation.extensions,
+    }
+}
+
+fn parameters(parameters: Vec<ReferenceOr<Parameter>>) -> Vec<ReferenceOr<v3_1::Parameter>> {
+    parameters.into_iter().map(|p| p.map(parameter)).collect()
+}
+
+fn parameter(parameter: Parameter) -> v3_1::Parameter {
+    match parameter {
+        Parameter::Query {
+            parameter_data,
+            allow_reserved,
+            style,
+            allow_empty_value,
+        } => v3_1::Parameter::Query {
+            parameter_data: data(parameter_data),
+            allow_reserved,
+            style,
+            allow_empty_value,
+        },
+        Parameter::Header {
+            parameter_data,
+            style,
+        } => v3_1::Parameter::Header {
+            parameter_data: data(parameter_data),
+            style,
+        },
+        Parameter::Path {
+            parameter_data,
+            style,
+        } => v3_1::Parameter::Path {
+            parameter_data: data(parameter_data),
+            style,
+        },
+        Parameter::Cookie {
+            parameter_data,
+            style,
+        } => v3_1::Parameter::Cookie {
+            parameter_data: data(parameter_data),
+            style,
+        },
+    }
+}
+
+fn data(data: ParameterData) -> v3_1::ParameterData {
+    v3_1::ParameterData {
+        name: data.name,
+        description: data.description,
+        required: data.required,
+        deprecated: data.deprecated,
+        explode: data.explode,
+        format: format(data.format),
+        example: data.example,
+        examples: data.examples,
+        extensions: data.extensions,
+    }
+}
+
+fn format(format: ParameterSchemaOrContent) -> v3_1::ParameterSchemaOrContent {
+    match format {
+        ParameterSchemaOrContent::Schema(s) => v3_1::ParameterSchemaOrContent::Schema(schema(s)),
+        ParameterSchemaOrContent::Content(content) => {
+            v3_1::ParameterSchemaOrContent::Content(map_values(content, media_type))
+        }
+    }
+}
+
+fn header(header: Header) -> v3_1::Header {
+    v3_1::Header {
+        description: header.description,
+        style: header.style,
+        explode: header.explode,
+        required: header.required,
+        deprecated: header.deprecated,
+        format: format(header.format),
+        example: header.example,
+        examples: header.examples,
+        extensions: header.extensions,
+    }
+}
+
+fn request_body(body: RequestBody) -> v3_1::RequestBody {
+    v3_1::RequestBody {
+        description: body.description,
+        content: map_values(body.content, media_type),
+        required: body.required,
+        extensions: body.extensions,
+    }
+}
+
+fn response(response: Response) -> v3_1::Response {
+    v3_1::Response {
+        description: response.description,
+        headers: map_values(response.headers, |h| h.map(header)),
+        content: map_values(response.content, |m| m.map(media_type)),
+        links: response.links,
+        extensions: response.extensions,
+    }
+}
+
+fn media_type(media_type: MediaType) -> v3_1::MediaType {
+    v3_1::MediaType {
+        schema: media_type.schema.map(schema),
+        example: media_type.example,
+        examples: media_type.examples,
+        encoding: map_values(media_type.encoding, |encoding| v3_1::Encoding {
+            content_type: encoding.content_type,
+            headers: map_values(encoding.headers, |h| h.map(header)),
+            style: encoding.style,
+            explode: encoding.explode.unwrap_or(false),
+            allow_reserved: encoding.allow_reserved,
+            extensions: encoding.extensions,
+        }),
+        extensions: media_type.extensions,
+    }
+}
+
+fn schema(schema: ReferenceOr<Schema>) -> v3_1::Schema {
+    match schema {
+        ReferenceOr::Reference { reference } => SchemaObject {
+            reference: Some(reference.as_str().to_owned()),
+            ..SchemaObject::default()
+        }
+        .into(),
+        ReferenceOr::Item(item) => schema_item(item),
+    }
+}
+
+fn boxed(schema: ReferenceOr<Box<Schema>>) -> v3_1::Schema {
+    self::schema(schema.unbox())
+}
+
+fn additional(additional: AdditionalProperties) -> v3_1::Schema {
+    match additional {
+        AdditionalProperties::Any => v3_1::Schema::Bool(true),
+        AdditionalProperties::None => v3_1::Schema::Bool(false),
+        AdditionalProperties::Schema(s) => schema(*s),
+    }
+}
+
+/// The lower or upper bound of a number, in the 3.1 form where an
+/// exclusive bound replaces the inclusive one.
+fn bound(value: Option<Number>, exclusive: bool) -> (Option<Number>, Option<Number>) {
+    if exclusive {
+        (None, value)
+    } else {
+        (value, None)
+    }
+}
+
+pub(crate) fn schema_item(schema: Schema) -> v3_1::Schema {
+    let data = schema.schema_data;
+    let mut object = SchemaObject {
+        read_only: data.read_only,
+        write_only: data.write_only,
+        deprecated: data.deprecated,
+        external_docs: data.external_docs,
+        examples: data.example.into_iter().collect(),
+        title: data.title,
+        description: data.description,
+        discriminator: data.discriminator,
+        default: data.default,
+        other: data.extensions.into_iter().collect(),
+        ..SchemaObject::default()
+    };
+    if let Some(xml) = data.xml {
+        let xml = serde_json::to_value(xml).expect("XML objects serialize");
+        object.other.insert("xml".to_owned(), xml);
+    }
+
+    let mut ty = None;
+    match schema.schema_kind {
+        SchemaKind::Type(Type::String(string)) => {
+            ty = Some(SchemaType::String);
+            object.format = string.format.name(StringFormat::as_str).map(str::to_owned);
+            object.pattern = string.pattern;
+            object.min_length = string.min_length;
+            object.max_length = string.max_length;
+            if !string.enumeration.is_empty() {
+                object.enumeration =
+                    Some(string.enumeration.into_iter().map(Value::from).collect());
+            }
+        }
+        SchemaKind::Type(Type::Number(number)) => {
+            ty = Some(SchemaType::Number);
+            object.format = number.format.name(NumberFormat::as_str).map(str::to_owned);
+            object.multiple_of = number.multiple_of.and_then(Number::from_f64);
+            let minimum = number.minimum.and_then(Number::from_f64);
+            let (minimum, exclusive) = bound(minimum, number.exclusive_minimum);
+            object.minimum = minimum;
+            object.exclusive_minimum = exclusive;
+            let maximum = number.maximum.and_then(Number::from_f64);
+            let (maximum, exclusive) = bound(maximum, number.exclusive_maximum);
+            object.maximum = maximum;
+            object.exclusive_maximum = exclusive;
+            if !number.enumeration.is_empty() {
+                object.enumeration =
+                    Some(number.enumeration.into_iter().map(Value::from).collect());
+            }
+        }
+        SchemaKind::Type(Type::Integer(integer)) => {
+            ty = Some(SchemaType::Integer);
+            object.format = integer
+                .format
+                .name(IntegerFormat::as_str)
+                .map(str::to_owned);
+            object.multiple_of = integer.multiple_of.map(Number::from);
+            let minimum = integer.minimum.map(Number::from);
+            let (minimum, exclusive) = bound(minimum, integer.exclusive_minimum);
+            object.minimum = minimum;
+            object.exclusive_minimum = exclusive;
+            let maximum = integer.maximum.map(Number::from);
+            let (maximum, exclusive) = bound(maximum, integer.exclusive_maximum);
+            object.maximum = maximum;
+            object.exclusive_maximum = exclusive;
+            if !integer.enumeration.is_empty() {
+                object.enumeration =
+                    Some(integer.enumeration.into_iter().map(Value::from).collect());
+            }
+        }
+        SchemaKind::Type(Type::Object(obj)) => {
+            ty = Some(SchemaType::Object);
+            object.properties = map_values(obj.properties, boxed);
+            object.required = obj.required;
+            object.additional_properties = obj.additional_properties.map(additional);
+            object.min_properties = obj.min_properties;
+            object.max_properties = obj.max_properties;
+        }
+        SchemaKind::Type(Type::Array(array)) => {
+            ty = Some(SchemaType::Array);
+            object.items = Some(boxed(array.items));
+            object.min_items = array.min_items;
+            object.max_items = array.max_items;
+            object.unique_items = array.unique_items;
+        }
+        SchemaKind::Type(Type::Boolean {}) => ty = Some(SchemaType::Boolean),
+        SchemaKind::OneOf { one_of } => {
+            object.one_of = one_of.into_iter().map(self::schema).collect();
+        }
+        SchemaKind::AllOf { all_of } => {
+            object.all_of = all_of.into_iter().map(self::schema).collect();
+        }
+        SchemaKind::AnyOf { any_of } => {
+            object.any_of = any_of.into_iter().map(self::schema).collect();
+        }
+        SchemaKind::Any(any) => {
+            object.pattern = any.pattern;
+            object.multiple_of = any.multiple_of.and_then(Number::from_f64);
+            let minimum = any.minimum.and_then(Number::from_f64);
+            let (minimum, exclusive) = bound(minimum, any.exclusive_minimum == Some(true));
+            object.minimum = minimum;
+            object.exclusive_minimum = exclusive;
+            let maximum = any.maximum.and_then(Number::from_f64);
+            let (maximum, exclusive) = bound(maximum, any.exclusive_maximum == Some(true));
+            object.maximum = maximum;
+            object.exclusive_maximum = exclusive;
+            object.properties = map_values(any.properties, boxed);
+            object.required = any.required;
+            object.additional_properties = any.additional_properties.map(additional);
+            object.min_properties = any.min_propeties;
+            object.max_properties = any.max_properties;
+            object.items = any.items.map(boxed);
+            object.min_items = any.min_items;
+            object.max_items = any.max_items;
+            object.unique_items = any.unique_items.unwrap_or_default();
+            object.format = any.format;
+            object.min_length = any.min_length;
+            object.max_length = any.max_length;
+        }
+    }
+
+    if data.nullable {
+        if let Some(values) = &mut object.enumeration {
+            if !values.contains(&Value::Null) {
+                values.push(Value::Null);
+            }
+        }
+    }
+    match ty {
+        Some(ty) if data.nullable => {
+            object.schema_type = Some(SchemaTypes::Multiple(vec![ty, SchemaType::Null]))
+        }
+        Some(ty) => object.schema_type = Some(SchemaTypes::Single(ty)),
+        // A schema without a type already allows null, unless it is
+        // made of alternatives or parts that each need not.
+        None if data.nullable && !object.one_of.is_empty() => object.one_of.push(null()),
+        None if data.nullable && !object.any_of.is_empty() => object.any_of.push(null()),
+        None if data.nullable && !object.all_of.is_empty() => {
+            let all_of = std::mem::take(&mut object.all_of);
+            object.any_of = vec![
+                SchemaObject {
+                    all_of,
+                    ..SchemaObject::default()
+                }
+                .into(),
+                null(),
+            ];
+        }
+        None => {}
+    }
+    object.into()
+}
+
+fn null() -> v3_1::Schema {
+    SchemaObject {
+        schema_type: Some(SchemaTypes::Single(SchemaType::Null)),
+        ..SchemaObject::default()
+    }
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use serde_json::json;
+
+    #[test]
+    fn upgrades_schemas() {
+        let api: OpenAPI = serde_yaml::from_str(
+            "
+openapi: 3.0.3
+info: {title: t, version: '1', license: {name: MIT}}
+paths:
+  /pets:
+    get:
+      responses:
+        '200':
+          description: pets
+          content:
+            application/json:
+              schema: {type: array, items: {$ref: '#/components/schemas/Pet'}}
+components:
+  schemas:
+    Pet:
+      type: object
+      properties:
+        age: {type: integer, minimum: 0, exclusiveMinimum: true, maximum: 30}
+        name: {type: string, nullable: true, example: Rex}
+        owner:
+          nullable: true
+          allOf: [{$ref: '#/components/schemas/Owner'}]
+    Owner: {type: string, enum: [alice, bob], nullable: true}
+",
+        )
+        .unwrap();
+
+        let upgraded = api.upgrade_to_3_1();
+        assert_eq!(upgraded.openapi, "3.1.0");
+        let schemas = serde_json::to_value(&upgraded.components.unwrap().schemas).unwrap();
+        assert_eq!(
+            schemas,
+            json!({
+                "Owner": {"type": ["string", "null"], "enum": ["alice", "bob", null]},
+                "Pet": {
+                    "type": "object",
+                    "properties": {
+                        "age": {"type": "integer", "exclusiveMinimum": 0, "maximum": 30},
+                        "name": {"type": ["string", "null"], "examples": ["Rex"]},
+                        "owner": {
+                            "anyOf": [
+                                {"allOf": [{"$ref": "#/components/schemas/Owner"}]},
+                                {"type": "null"},
+                            ]
+                        },
+                    },
+                },
+            })
+        );
+        let response = &upgraded.paths["/pets"];
+        let yaml = serde_yaml::to_string(response).unwrap();
+        assert!(yaml.contains("#/components/schemas/Pet"));
+    }
+
+    #[test]
+    fn keeps_integer_bounds_exact() {
+        let schema: Schema = serde_yaml::from_str(
+            "{type: integer, minimum: 9007199254740993, maximum: 9223372036854775807}",
+        )
+        .unwrap();
+        let upgraded = serde_json::to_value(super::schema_item(schema)).unwrap();
+        assert_eq!(
+            upgraded,
+            json!({"type": "integer", "minimum": 9007199254740993i64, "maximum": i64::MAX})
+        );
+    }
+}
@@ -1,5 +1,242 @@
+use indexmap::IndexMap;
+use serde::de::{Deserializer, IgnoredAny, MapAccess, Visitor};
+use serde::Deserialize;
+use serde_json::Value;
+use std::fmt;
+use std::marker::PhantomData;
+
 #[allow(clippy::trivially_copy_pass_by_ref)] // needs to match signature for use in serde attribute
 #[inline]
 pub const fn is_false(v: &bool) -> bool {
     !(*v)
 }
+
+/// Deserializes the fields of an object whose names start with `x-`, for
+/// use on a flattened map of extensions.
+pub(crate) fn deserialize_extensions<'de, D>(
+    deserializer: D,
+) -> Result<IndexMap<String, Value>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    struct ExtensionsVisitor;
+
+    impl<'de> Visitor<'de> for ExtensionsVisitor {
+        type Value = IndexMap<String, Value>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("an object")
+        }
+
+        fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+        where
+            A: MapAccess<'de>,
+        {
+            let mut extensions = IndexMap::new();
+            while let Some(FieldName(name)) = map.next_key()? {
+                match name {
+                    Some(name) if name.starts_with("x-") => {
+                        extensions.insert(name, map.next_value()?);
+                    }
+                    _ => {
+                        map.next_value::<IgnoredAny>()?;
+                    }
+                }
+            }
+            Ok(extensions)
+        }
+    }
+
+    deserializer.deserialize_map(ExtensionsVisitor)
+}
+
+/// Deserializes the entries of an object other than its extensions, for
+/// use on a flattened map alongside [`deserialize_extensions`].
+pub(crate) fn deserialize_without_extensions<'de, D, M, V>(deserializer: D) -> Result<M, D::Error>
+where
+    D: Deserializer<'de>,
+    M: Default + Extend<(String, V)>,
+    V: Deserialize<'de>,
+{
+    struct EntriesVisitor<M, V>(PhantomData<(M, V)>);
+
+    impl<'de, M, V> Visitor<'de> for EntriesVisitor<M, V>
+    where
+        M: Default + Extend<(String, V)>,
+        V: Deserialize<'de>,
+    {
+        type Value = M;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("an object")
+        }
+
+        fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+        where
+            A: MapAccess<'de>,
+        {
+            let mut entries = M::default();
+            while let Some(name) = map.next_key::<String>()? {
+                if name.starts_with("x-") {
+                    map.next_value::<IgnoredAny>()?;
+                } else {
+                    let value = crate::parse_error::descend(name.clone(), || map.next_value())?;
+                    entries.extend(Some((name, value)));
+                }
+            }
+            Ok(entries)
+        }
+    }
+
+    deserializer.deserialize_map(EntriesVisitor(PhantomData))
+}
+
+/// Gives a struct holding a map of `$entries`, such as the Paths Object,
+/// the map's methods and the ways to iterate over and collect it.
+macro_rules! entries_of {
+    ($ty:ident, $entries:ident: $map:ty, $item:ty) => {
+        impl $ty {
+            pub fn new() -> Self {
+                Self::default()
+            }
+        }
+
+        impl std::ops::Deref for $ty {
+            type Target = $map;
+
+            fn deref(&self) -> &$map {
+                &self.$entries
+            }
+        }
+
+        impl std::ops::DerefMut for $ty {
+            fn deref_mut(&mut self) -> &mut $map {
+                &mut self.$entries
+            }
+        }
+
+        impl std::iter::FromIterator<(String, $item)> for $ty {
+            fn from_iter<I: IntoIterator<Item = (String, $item)>>(iter: I) -> Self {
+                $ty {
+                    $entries: iter.into_iter().collect(),
+                    extensions: Default::default(),
+                }
+            }
+        }
+
+        impl IntoIterator for $ty {
+            type Item = (String, $item);
+            type IntoIter = <$map as IntoIterator>::IntoIter;
+
+            fn into_iter(self) -> Self::IntoIter {
+                self.$entries.into_iter()
+            }
+        }
+
+        impl<'a> IntoIterator for &'a $ty {
+            type Item = (&'a String, &'a $item);
+            type IntoIter = <&'a $map as IntoIterator>::IntoIter;
+
+            fn into_iter(self) -> Self::IntoIter {
+                self.$entries.iter()
+            }
+        }
+
+        impl<'a> IntoIterator for &'a mut $ty {
+            type Item = (&'a String, &'a mut $item);
+            type IntoIter = <&'a mut $map as IntoIterator>::IntoIter;
+
+            fn into_iter(self) -> Self::IntoIter {
+                self.$entries.iter_mut()
+            }
+        }
+    };
+}
+
+pub(crate) use entries_of;
+
+/// The name of a field, if it is a string rather than, say, a status code
+/// that YAML leaves unquoted.
+struct FieldName(Option<String>);
+
+impl<'de> Deserialize<'de> for FieldName {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct FieldNameVisitor;
+
+        impl<'de> Visitor<'de> for FieldNameVisitor {
+            type Value = FieldName;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a field name")
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<FieldName, E> {
+                Ok(FieldName(Some(value.to_owned())))
+            }
+
+            fn visit_bool<E>(self, _: bool) -> Result<FieldName, E> {
+                Ok(FieldName(None))
+            }
+
+            fn visit_i64<E>(self, _: i64) -> Result<FieldName, E> {
+                Ok(FieldName(None))
+            }
+
+            fn visit_u64<E>(self, _: u64) -> Result<FieldName, E> {
+                Ok(FieldName(None))
+            }
+
+            fn visit_f64<E>(self, _: f64) -> Result<FieldName, E> {
+                Ok(FieldName(None))
+            }
+
+            fn visit_unit<E>(self) -> Result<FieldName, E> {
+                Ok(FieldName(None))
+            }
+        }
+
+        deserializer.deserialize_any(FieldNameVisitor)
+    }
+}
+
+/// Appends `value` to `out`, percent-encoding every byte other than the
+/// unreserved characters and sub-delimiters of RFC 3986, `:` and `@`.
+pub(crate) fn percent_encode(value: &str, out: &mut String) {
+    percent_encode_except(value, |byte| b"!$&'()*+,;=:@".contains(&byte), out)
+}
+
+/// Appends `value` to `out`, percent-encoding every byte other than the
+/// unreserved characters of RFC 3986 and those `keep` accepts.
+pub(crate) fn percent_encode_except(value: &str, keep: impl Fn(u8) -> bool, out: &mut String) {
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                out.push(byte as char)
+            }
+            _ if keep(byte) => out.push(byte as char),
+            _ => out.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+}
+
+/// Decodes the `%XX` escapes of `s`, or returns `None` if one is malformed
+/// or the result is not UTF-8.
+pub(crate) fn percent_decode(s: &str) -> Option<String> {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = s.get(i + 1..i + 3)?;
+            out.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            out.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(out).ok()
+}
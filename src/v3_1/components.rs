@@ -0,0 +1,47 @@
+use super::*;
+use crate::{Example, Link, Map, ReferenceOr, SecurityScheme};
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+/// Holds a set of reusable objects for different aspects of the OAS.
+/// All objects defined within the components object will have no effect
+/// on the API unless they are explicitly referenced from properties
+/// outside the components object.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Components {
+    /// An object to hold reusable Schema Objects. Schemas may themselves
+    /// be references, through `$ref`.
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub schemas: Map<String, Schema>,
+    /// An object to hold reusable Response Objects.
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub responses: Map<String, ReferenceOr<Response>>,
+    /// An object to hold reusable Parameter Objects.
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub parameters: Map<String, ReferenceOr<Parameter>>,
+    /// An object to hold reusable Example Objects.
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub examples: Map<String, ReferenceOr<Example>>,
+    /// An object to hold reusable Request Body Objects.
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub request_bodies: Map<String, ReferenceOr<RequestBody>>,
+    /// An object to hold reusable Header Objects.
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub headers: Map<String, ReferenceOr<Header>>,
+    /// An object to hold reusable Security Scheme Objects.
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub security_schemes: Map<String, ReferenceOr<SecurityScheme>>,
+    /// An object to hold reusable Link Objects.
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub links: Map<String, ReferenceOr<Link>>,
+    /// An object to hold reusable Callback Objects.
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub callbacks: Map<String, ReferenceOr<Callback>>,
+    /// An object to hold reusable Path Item Objects.
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub path_items: Map<String, ReferenceOr<PathItem>>,
+    /// Inline extensions to this object.
+    #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
+    pub extensions: IndexMap<String, serde_json::Value>,
+}
@@ -0,0 +1,54 @@
+use crate::Contact;
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+/// The object provides metadata about the API.
+/// The metadata MAY be used by the clients if needed,
+/// and MAY be presented in editing or documentation generation tools for convenience.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Info {
+    /// REQUIRED. The title of the application.
+    pub title: String,
+    /// A short summary of the API.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    /// A short description of the application.
+    /// CommonMark syntax MAY be used for rich text representation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// A URL to the Terms of Service for the API.
+    /// MUST be in the format of a URL.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub terms_of_service: Option<String>,
+    /// The contact information for the exposed API.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contact: Option<Contact>,
+    /// The license information for the exposed API.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub license: Option<License>,
+    /// REQUIRED. The version of the OpenAPI document (which is distinct from
+    /// the OpenAPI Specification version or the API implementation version).
+    pub version: String,
+    /// Inline extensions to this object.
+    #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
+    pub extensions: IndexMap<String, serde_json::Value>,
+}
+
+/// License information for the exposed API.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct License {
+    /// REQUIRED. The license name used for the API.
+    pub name: String,
+    /// An SPDX license expression for the API.
+    /// The identifier field is mutually exclusive of the url field.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identifier: Option<String>,
+    /// A URL to the license used for the API. MUST be in the format of a URL.
+    /// The url field is mutually exclusive of the identifier field.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Inline extensions to this object.
+    #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
+    pub extensions: IndexMap<String, serde_json::Value>,
+}
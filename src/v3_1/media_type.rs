@@ -0,0 +1,69 @@
+use super::*;
+use crate::{Example, MediaTypeRange, QueryStyle, ReferenceOr};
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct MediaType {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema: Option<Schema>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub example: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub examples: BTreeMap<String, ReferenceOr<Example>>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub encoding: BTreeMap<String, Encoding>,
+    /// Inline extensions to this object.
+    #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
+    pub extensions: IndexMap<String, serde_json::Value>,
+}
+
+/// A single encoding definition applied to a single schema property.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Encoding {
+    /// The Content-Type for encoding a specific property.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    /// A map allowing additional information to be provided as headers,
+    /// for example Content-Disposition.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, ReferenceOr<Header>>,
+    /// Describes how a specific property value will be serialized depending
+    /// on its type. See Parameter Object for details on the style property.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<QueryStyle>,
+    /// When this is true, property values of type array or object generate
+    /// separate parameters for each value of the array, or key-value-pair
+    /// of the map.
+    #[serde(default, skip_serializing_if = "crate::is_false")]
+    pub explode: bool,
+    /// Determines whether the parameter value SHOULD allow reserved characters,
+    /// as defined by RFC3986 :/?#[]@!$&'()*+,;= to be included without percent-encoding.
+    #[serde(default, skip_serializing_if = "crate::is_false")]
+    pub allow_reserved: bool,
+    /// Inline extensions to this object.
+    #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
+    pub extensions: IndexMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct RequestBody {
+    /// A brief description of the request body.
+    /// CommonMark syntax MAY be used for rich text representation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// REQUIRED. The content of the request body.
+    /// The key is a media type or media type range and
+    /// the value describes it.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub content: BTreeMap<MediaTypeRange, MediaType>,
+    /// Determines if the request body is required in the
+    /// request. Defaults to false.
+    #[serde(default, skip_serializing_if = "crate::is_false")]
+    pub required: bool,
+    /// Inline extensions to this object.
+    #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
+    pub extensions: IndexMap<String, serde_json::Value>,
+}
@@ -0,0 +1,28 @@
+//! The document model of version 3.1 of the OpenAPI Specification.
+//!
+//! 3.1 adds webhooks and reusable path items, and replaces the Schema
+//! Object with JSON Schema 2020-12. The objects that embed schemas are
+//! defined here again; those that 3.1 leaves unchanged, such as
+//! [`Server`](crate::Server) and [`SecurityScheme`](crate::SecurityScheme),
+//! are shared with the 3.0 model.
+//!
+//! Use [`VersionedOpenAPI`](crate::VersionedOpenAPI) to read a document of
+//! either version.
+
+mod components;
+mod info;
+mod media_type;
+mod openapi;
+mod parameter;
+mod paths;
+mod responses;
+mod schema;
+
+pub use self::components::*;
+pub use self::info::*;
+pub use self::media_type::*;
+pub use self::openapi::*;
+pub use self::parameter::*;
+pub use self::paths::*;
+pub use self::responses::*;
+pub use self::schema::*;
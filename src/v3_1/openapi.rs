@@ -0,0 +1,55 @@
+use super::*;
+use crate::{ExternalDocumentation, Map, ReferenceOr, SecurityRequirement, Server, Tag};
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenAPI {
+    /// REQUIRED. This string MUST be the version number of the OpenAPI
+    /// Specification that the OpenAPI document uses, such as `3.1.0`.
+    pub openapi: String,
+    /// REQUIRED. Provides metadata about the API.
+    /// The metadata MAY be used by tooling as required.
+    pub info: Info,
+    /// The default value for the `$schema` keyword within Schema Objects
+    /// contained within this OAS document. This MUST be in the form of a URI.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub json_schema_dialect: Option<String>,
+    /// An array of Server Objects, which provide connectivity information to a
+    /// target server. If the servers property is not provided, or is an empty
+    /// array, the default value would be a Server Object with a url value of /.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub servers: Vec<Server>,
+    /// The available paths and operations for the API.
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub paths: Paths,
+    /// The incoming webhooks that MAY be received as part of this API and
+    /// that the API consumer MAY choose to implement. The key name is a
+    /// unique string to refer to each webhook.
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub webhooks: Map<String, ReferenceOr<PathItem>>,
+    /// An element to hold various schemas for the specification.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub components: Option<Components>,
+    /// A declaration of which security mechanisms can be used across the API.
+    /// The list of values includes alternative security requirement objects
+    /// that can be used. Only one of the security requirement objects need to
+    /// be satisfied to authorize a request. Individual operations can override
+    /// this definition.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub security: Vec<SecurityRequirement>,
+    /// A list of tags used by the specification with additional metadata.
+    /// Each tag name in the list MUST be unique.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<Tag>,
+    /// Additional external documentation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_docs: Option<ExternalDocumentation>,
+    /// Inline extensions to this object.
+    #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
+    pub extensions: IndexMap<String, serde_json::Value>,
+}
@@ -0,0 +1,158 @@
+use super::*;
+use crate::{
+    CookieStyle, Example, HeaderStyle, MediaTypeRange, PathStyle, QueryStyle, ReferenceOr,
+};
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Describes a single operation parameter.
+///
+/// A unique parameter is defined by a combination of a name and location.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct ParameterData {
+    /// REQUIRED. The name of the parameter. Parameter names are case sensitive.
+    pub name: String,
+    /// A brief description of the parameter. This could
+    /// contain examples of use. CommonMark syntax MAY be
+    /// used for rich text representation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Determines whether this parameter is mandatory.
+    /// If the parameter location is "path", this property
+    /// is REQUIRED and its value MUST be true.
+    #[serde(default, skip_serializing_if = "crate::is_false")]
+    pub required: bool,
+    /// Specifies that a parameter is deprecated and SHOULD
+    /// be transitioned out of usage.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
+    /// When true, array and object values generate a separate parameter
+    /// for each item or property.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explode: Option<bool>,
+    #[serde(flatten)]
+    pub format: ParameterSchemaOrContent,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub example: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub examples: BTreeMap<String, ReferenceOr<Example>>,
+    /// Inline extensions to this object.
+    #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
+    pub extensions: IndexMap<String, serde_json::Value>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum ParameterSchemaOrContent {
+    #[serde(rename = "schema")]
+    Schema(Schema),
+    #[serde(rename = "content")]
+    Content(Content),
+}
+
+/// A schema that allows any value.
+impl Default for ParameterSchemaOrContent {
+    fn default() -> Self {
+        ParameterSchemaOrContent::Schema(Schema::default())
+    }
+}
+
+pub type Content = BTreeMap<MediaTypeRange, MediaType>;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "in")]
+pub enum Parameter {
+    #[serde(rename = "query")]
+    Query {
+        #[serde(flatten)]
+        parameter_data: ParameterData,
+        #[serde(default)]
+        #[serde(rename = "allowReserved", skip_serializing_if = "crate::is_false")]
+        allow_reserved: bool,
+        #[serde(default)]
+        style: QueryStyle,
+        /// Sets the ability to pass empty-valued parameters. This is
+        /// valid only for query parameters and allows sending a parameter
+        /// with an empty value.
+        #[serde(rename = "allowEmptyValue", skip_serializing_if = "Option::is_none")]
+        allow_empty_value: Option<bool>,
+    },
+    #[serde(rename = "header")]
+    Header {
+        #[serde(flatten)]
+        parameter_data: ParameterData,
+        #[serde(default)]
+        style: HeaderStyle,
+    },
+    #[serde(rename = "path")]
+    Path {
+        #[serde(flatten)]
+        parameter_data: ParameterData,
+        #[serde(default)]
+        style: PathStyle,
+    },
+    #[serde(rename = "cookie")]
+    Cookie {
+        #[serde(flatten)]
+        parameter_data: ParameterData,
+        #[serde(default)]
+        style: CookieStyle,
+    },
+}
+
+impl Parameter {
+    /// Returns the data common to parameters in every location.
+    pub fn parameter_data_ref(&self) -> &ParameterData {
+        match self {
+            Parameter::Query { parameter_data, .. }
+            | Parameter::Header { parameter_data, .. }
+            | Parameter::Path { parameter_data, .. }
+            | Parameter::Cookie { parameter_data, .. } => parameter_data,
+        }
+    }
+
+    /// Returns a mutable reference to the data common to parameters
+    /// in every location.
+    pub fn parameter_data_mut(&mut self) -> &mut ParameterData {
+        match self {
+            Parameter::Query { parameter_data, .. }
+            | Parameter::Header { parameter_data, .. }
+            | Parameter::Path { parameter_data, .. }
+            | Parameter::Cookie { parameter_data, .. } => parameter_data,
+        }
+    }
+}
+
+/// The Header Object follows the structure of the Parameter Object,
+/// without `name` and `in`.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Header {
+    /// A brief description of the header. This could
+    /// contain examples of use. CommonMark syntax MAY be
+    /// used for rich text representation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub style: HeaderStyle,
+    /// When true, the properties of object values are serialized as
+    /// `name=value` pairs. Defaults to false.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explode: Option<bool>,
+    /// Determines whether this header is mandatory.
+    #[serde(default, skip_serializing_if = "crate::is_false")]
+    pub required: bool,
+    /// Specifies that a header is deprecated and SHOULD
+    /// be transitioned out of usage.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
+    #[serde(flatten)]
+    pub format: ParameterSchemaOrContent,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub example: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub examples: BTreeMap<String, ReferenceOr<Example>>,
+    /// Inline extensions to this object.
+    #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
+    pub extensions: IndexMap<String, serde_json::Value>,
+}
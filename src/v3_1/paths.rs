@@ -0,0 +1,153 @@
+use super::*;
+use crate::{ExternalDocumentation, Map, Name, ReferenceOr, SecurityRequirement, Server};
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Describes the operations available on a single path.
+/// A Path Item MAY be empty, due to ACL constraints.
+/// The path itself is still exposed to the documentation
+/// viewer but they will not know which operations and
+/// parameters are available.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct PathItem {
+    /// An optional, string summary, intended to apply to all operations in this path.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    /// An optional, string description, intended to apply to all operations in this path.
+    /// CommonMark syntax MAY be used for rich text representation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub get: Option<Operation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub put: Option<Operation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post: Option<Operation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delete: Option<Operation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub options: Option<Operation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub head: Option<Operation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub patch: Option<Operation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trace: Option<Operation>,
+    /// An alternative server array to service all operations in this path.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub servers: Vec<Server>,
+    /// A list of parameters that are applicable for all the
+    /// operations described under this path. These parameters
+    /// can be overridden at the operation level, but cannot be
+    /// removed there. The list MUST NOT include duplicated parameters.
+    /// A unique parameter is defined by a combination of a name and location.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub parameters: Vec<ReferenceOr<Parameter>>,
+    /// Inline extensions to this object.
+    #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
+    pub extensions: IndexMap<String, serde_json::Value>,
+}
+
+/// Holds the relative paths to the individual endpoints and
+/// their operations. The path is appended to the URL from the
+/// Server Object in order to construct the full URL. The Paths
+/// MAY be empty, due to ACL constraints.
+///
+/// Dereferences to the map of path items, so that it can be used as one.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct Paths {
+    /// The path items, by path.
+    #[serde(
+        flatten,
+        deserialize_with = "crate::util::deserialize_without_extensions"
+    )]
+    pub paths: Map<String, ReferenceOr<PathItem>>,
+    /// Inline extensions to this object.
+    #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
+    pub extensions: IndexMap<String, serde_json::Value>,
+}
+
+crate::util::entries_of!(Paths, paths: Map<String, ReferenceOr<PathItem>>, ReferenceOr<PathItem>);
+
+/// A map of possible out-of band callbacks related to the parent operation.
+/// The key value used to identify the callback object is an expression,
+/// evaluated at runtime, that identifies a URL to use for the callback operation.
+///
+/// Dereferences to the map of path items, so that it can be used as one.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct Callback {
+    /// The path items, by the expression of their URL.
+    #[serde(
+        flatten,
+        deserialize_with = "crate::util::deserialize_without_extensions"
+    )]
+    pub paths: BTreeMap<String, PathItem>,
+    /// Inline extensions to this object.
+    #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
+    pub extensions: IndexMap<String, serde_json::Value>,
+}
+
+crate::util::entries_of!(Callback, paths: BTreeMap<String, PathItem>, PathItem);
+
+/// Describes a single API operation on a path.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Operation {
+    /// A list of tags for API documentation control.
+    /// Tags can be used for logical grouping of operations
+    /// by resources or any other qualifier.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<Name>,
+    /// A short summary of what the operation does.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    /// A verbose explanation of the operation behavior.
+    /// CommonMark syntax MAY be used for rich text representation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Additional external documentation for this operation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_documentation: Option<ExternalDocumentation>,
+    /// Unique string used to identify the operation.
+    /// The id MUST be unique among all operations described in the API.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub operation_id: Option<String>,
+    /// A list of parameters that are applicable for this operation.
+    /// If a parameter is already defined at the Path Item, the new
+    /// definition will override it but can never remove it.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub parameters: Vec<ReferenceOr<Parameter>>,
+    /// The request body applicable for this operation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_body: Option<ReferenceOr<RequestBody>>,
+    /// The list of possible responses as they are returned
+    /// from executing this operation. Unlike in 3.0, it may be omitted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub responses: Option<Responses>,
+    /// A map of possible out-of band callbacks related to the parent operation.
+    /// The key is a unique identifier for the Callback Object. Each value in
+    /// the map is a Callback Object that describes a request that may be
+    /// initiated by the API provider and the expected responses.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub callbacks: BTreeMap<String, ReferenceOr<Callback>>,
+    /// Declares this operation to be deprecated. Default value is false.
+    #[serde(default, skip_serializing_if = "crate::is_false")]
+    pub deprecated: bool,
+    /// A declaration of which security mechanisms can be used for this operation.
+    /// This definition overrides any declared top-level security.
+    /// To remove a top-level security declaration, an empty array can be used.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub security: Option<Vec<SecurityRequirement>>,
+    /// An alternative server array to service this operation.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub servers: Vec<Server>,
+    /// Inline extensions to this object.
+    #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
+    pub extensions: IndexMap<String, serde_json::Value>,
+}
@@ -0,0 +1,41 @@
+use super::*;
+use crate::{Link, Map, MediaTypeRange, ReferenceOr, StatusCode};
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct Responses {
+    /// The documentation of responses other than the ones declared
+    /// for specific HTTP response codes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<ReferenceOr<Response>>,
+    /// Any HTTP status code can be used as the property name,
+    /// but only one property per code, to describe the expected
+    /// response for that HTTP status code.
+    #[serde(flatten, deserialize_with = "crate::responses::deserialize_responses")]
+    #[serde(default)]
+    pub responses: Map<StatusCode, ReferenceOr<Response>>,
+    /// Inline extensions to this object.
+    #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
+    pub extensions: IndexMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct Response {
+    /// REQUIRED. A short description of the response.
+    /// CommonMark syntax MAY be used for rich text representation.
+    pub description: String,
+    /// Maps a header name to its definition.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, ReferenceOr<Header>>,
+    /// A map containing descriptions of potential response payloads.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub content: BTreeMap<MediaTypeRange, ReferenceOr<MediaType>>,
+    /// A map of operations links that can be followed from the response.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub links: BTreeMap<String, ReferenceOr<Link>>,
+    /// Inline extensions to this object.
+    #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
+    pub extensions: IndexMap<String, serde_json::Value>,
+}
@@ -0,0 +1,200 @@
+use crate::{Discriminator, ExternalDocumentation, Map, Name};
+use serde::{Deserialize, Serialize};
+use serde_json::{Number, Value};
+use std::collections::BTreeMap;
+
+/// A JSON Schema 2020-12 schema, extended with the OpenAPI vocabulary.
+///
+/// References are expressed with the `$ref` keyword of the schema itself,
+/// and so may carry other keywords alongside them.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(untagged)]
+pub enum Schema {
+    /// `true` accepts every instance, `false` none.
+    Bool(bool),
+    Object(Box<SchemaObject>),
+}
+
+impl Default for Schema {
+    fn default() -> Self {
+        Schema::Bool(true)
+    }
+}
+
+impl From<SchemaObject> for Schema {
+    fn from(object: SchemaObject) -> Self {
+        Schema::Object(Box::new(object))
+    }
+}
+
+/// The primitive types of JSON Schema.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum SchemaType {
+    Null,
+    Boolean,
+    Object,
+    Array,
+    Number,
+    String,
+    Integer,
+}
+
+/// The value of `type`: either a single type or a list of them.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum SchemaTypes {
+    Single(SchemaType),
+    Multiple(Vec<SchemaType>),
+}
+
+impl SchemaTypes {
+    /// Whether an instance of type `ty` is allowed.
+    pub fn contains(&self, ty: SchemaType) -> bool {
+        match self {
+            SchemaTypes::Single(single) => *single == ty,
+            SchemaTypes::Multiple(types) => types.contains(&ty),
+        }
+    }
+}
+
+/// The keywords of a schema that is not a boolean.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaObject {
+    // Core
+    #[serde(rename = "$schema", skip_serializing_if = "Option::is_none")]
+    pub schema: Option<String>,
+    #[serde(rename = "$id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(rename = "$ref", skip_serializing_if = "Option::is_none")]
+    pub reference: Option<String>,
+    #[serde(rename = "$anchor", skip_serializing_if = "Option::is_none")]
+    pub anchor: Option<String>,
+    #[serde(rename = "$dynamicRef", skip_serializing_if = "Option::is_none")]
+    pub dynamic_ref: Option<String>,
+    #[serde(rename = "$dynamicAnchor", skip_serializing_if = "Option::is_none")]
+    pub dynamic_anchor: Option<String>,
+    #[serde(rename = "$defs", default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub defs: BTreeMap<String, Schema>,
+    #[serde(rename = "$comment", skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+
+    // Applicators
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub all_of: Vec<Schema>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub any_of: Vec<Schema>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub one_of: Vec<Schema>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub not: Option<Schema>,
+    #[serde(rename = "if", skip_serializing_if = "Option::is_none")]
+    pub if_: Option<Schema>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub then: Option<Schema>,
+    #[serde(rename = "else", skip_serializing_if = "Option::is_none")]
+    pub else_: Option<Schema>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub dependent_schemas: BTreeMap<String, Schema>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub prefix_items: Vec<Schema>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub items: Option<Schema>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contains: Option<Schema>,
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub properties: Map<Name, Schema>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub pattern_properties: BTreeMap<String, Schema>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub additional_properties: Option<Schema>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub property_names: Option<Schema>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unevaluated_items: Option<Schema>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unevaluated_properties: Option<Schema>,
+
+    // Validation
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub schema_type: Option<SchemaTypes>,
+    #[serde(rename = "const", skip_serializing_if = "Option::is_none")]
+    pub const_: Option<Value>,
+    #[serde(rename = "enum", skip_serializing_if = "Option::is_none")]
+    pub enumeration: Option<Vec<Value>>,
+    // Numbers are kept as written, so that integers beyond 2^53 are exact.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multiple_of: Option<Number>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maximum: Option<Number>,
+    /// Unlike in 3.0, the exclusive bound itself rather than a flag.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exclusive_maximum: Option<Number>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minimum: Option<Number>,
+    /// Unlike in 3.0, the exclusive bound itself rather than a flag.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exclusive_minimum: Option<Number>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_length: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_items: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_items: Option<usize>,
+    #[serde(default, skip_serializing_if = "crate::is_false")]
+    pub unique_items: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_contains: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_contains: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_properties: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_properties: Option<usize>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub required: Vec<Name>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub dependent_required: BTreeMap<String, Vec<String>>,
+
+    // Format, content and meta-data
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_encoding: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_media_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_schema: Option<Schema>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<Value>,
+    #[serde(default, skip_serializing_if = "crate::is_false")]
+    pub deprecated: bool,
+    #[serde(default, skip_serializing_if = "crate::is_false")]
+    pub read_only: bool,
+    #[serde(default, skip_serializing_if = "crate::is_false")]
+    pub write_only: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub examples: Vec<Value>,
+
+    // OpenAPI
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub discriminator: Option<Discriminator>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_docs: Option<ExternalDocumentation>,
+    /// Deprecated in favour of `examples`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub example: Option<Value>,
+
+    /// Keywords of other vocabularies, and extensions.
+    #[serde(flatten)]
+    pub other: BTreeMap<String, Value>,
+}
@@ -0,0 +1,818 @@
+use crate::reference::escape_pointer_segment;
+use crate::walk::{FindRefs, Location};
+use crate::*;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+
+/// A violation of the OpenAPI specification that the types of this crate
+/// cannot rule out by themselves.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationError {
+    /// A JSON Pointer to the offending part of the document.
+    pub location: String,
+    pub kind: ValidationErrorKind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValidationErrorKind {
+    /// The `operationId` is already used by the operation at `first`.
+    DuplicateOperationId { operation_id: String, first: String },
+    /// A parameter with the same name and location is declared
+    /// more than once for the same operation or path.
+    DuplicateParameter(String),
+    /// A `{name}` in the path template has no matching path parameter.
+    UndeclaredPathParameter(String),
+    /// A path parameter does not appear in the path template.
+    UnknownPathParameter(String),
+    /// A path parameter is not marked as required.
+    OptionalPathParameter(String),
+    /// The path is not a well-formed path template.
+    InvalidPathTemplate(ParsePathTemplateError),
+    /// A parameter has a style the specification does not define for its
+    /// `explode`, or for the type of its schema, for the reason given.
+    UndefinedStyle {
+        name: String,
+        style: ParameterStyle,
+        reason: &'static str,
+    },
+    /// The URL of a callback is not a well-formed runtime expression
+    /// template.
+    InvalidCallbackExpression(ParseRuntimeExpressionError),
+    /// The operation declares no responses.
+    NoResponses,
+    /// A discriminator mapping names a schema that does not exist.
+    UnknownDiscriminatorTarget(String),
+    /// A schema that a discriminator chooses does not declare the
+    /// discriminator property.
+    MissingDiscriminatorProperty { variant: String, property: String },
+    /// A security requirement names a security scheme
+    /// that is not declared in the components.
+    UnknownSecurityScheme(String),
+    /// A security requirement names a scope that its security scheme does
+    /// not declare. Schemes other than OAuth2 and OpenID Connect declare no
+    /// scopes.
+    UnknownScope { scheme: String, scope: String },
+    /// A reference into the components points at nothing.
+    UnresolvedReference(Reference),
+    /// The operation has neither a summary nor a description.
+    /// Not a violation of the specification, see [`Validator`].
+    MissingDescription,
+    /// The operation has no `operationId`.
+    /// Not a violation of the specification, see [`Validator`].
+    MissingOperationId,
+}
+
+impl ValidationErrorKind {
+    /// The rule of a [`Validator`] that reports this kind of problem.
+    pub fn rule(&self) -> Rule {
+        match self {
+            ValidationErrorKind::DuplicateOperationId { .. } => Rule::UniqueOperationIds,
+            ValidationErrorKind::DuplicateParameter(_) => Rule::UniqueParameters,
+            ValidationErrorKind::UndeclaredPathParameter(_)
+            | ValidationErrorKind::UnknownPathParameter(_)
+            | ValidationErrorKind::OptionalPathParameter(_)
+            | ValidationErrorKind::InvalidPathTemplate(_) => Rule::PathParameters,
+            ValidationErrorKind::UndefinedStyle { .. } => Rule::ParameterStyles,
+            ValidationErrorKind::InvalidCallbackExpression(_) => Rule::CallbackExpressions,
+            ValidationErrorKind::NoResponses => Rule::Responses,
+            ValidationErrorKind::UnknownDiscriminatorTarget(_)
+            | ValidationErrorKind::MissingDiscriminatorProperty { .. } => {
+                Rule::DiscriminatorMappings
+            }
+            ValidationErrorKind::UnknownSecurityScheme(_)
+            | ValidationErrorKind::UnknownScope { .. } => Rule::SecuritySchemes,
+            ValidationErrorKind::UnresolvedReference(_) => Rule::References,
+            ValidationErrorKind::MissingDescription => Rule::OperationDescriptions,
+            ValidationErrorKind::MissingOperationId => Rule::OperationIds,
+        }
+    }
+}
+
+impl fmt::Display for ValidationErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationErrorKind::DuplicateOperationId {
+                operation_id,
+                first,
+            } => write!(
+                f,
+                "operationId `{}` is already used at {}",
+                operation_id, first
+            ),
+            ValidationErrorKind::DuplicateParameter(name) => {
+                write!(f, "parameter `{}` is declared more than once", name)
+            }
+            ValidationErrorKind::UndeclaredPathParameter(name) => write!(
+                f,
+                "path template parameter `{}` is not declared as a path parameter",
+                name
+            ),
+            ValidationErrorKind::UnknownPathParameter(name) => write!(
+                f,
+                "path parameter `{}` does not appear in the path template",
+                name
+            ),
+            ValidationErrorKind::OptionalPathParameter(name) => {
+                write!(f, "path parameter `{}` must be required", name)
+            }
+            ValidationErrorKind::InvalidPathTemplate(error) => error.fmt(f),
+            ValidationErrorKind::UndefinedStyle {
+                name,
+                style,
+                reason,
+            } => write!(
+                f,
+                "the `{}` style of parameter `{}` is not defined {}",
+                style, name, reason
+            ),
+            ValidationErrorKind::InvalidCallbackExpression(error) => {
+                write!(f, "callback URL is malformed: {}", error)
+            }
+            ValidationErrorKind::NoResponses => f.write_str("operation has no responses"),
+            ValidationErrorKind::UnknownDiscriminatorTarget(target) => {
+                write!(f, "discriminator maps to unknown schema `{}`", target)
+            }
+            ValidationErrorKind::MissingDiscriminatorProperty { variant, property } => write!(
+                f,
+                "schema `{}` does not declare discriminator property `{}`",
+                variant, property
+            ),
+            ValidationErrorKind::UnknownSecurityScheme(name) => {
+                write!(f, "security scheme `{}` is not declared", name)
+            }
+            ValidationErrorKind::UnknownScope { scheme, scope } => write!(
+                f,
+                "scope `{}` is not declared by security scheme `{}`",
+                scope, scheme
+            ),
+            ValidationErrorKind::UnresolvedReference(reference) => {
+                write!(f, "reference `{}` not found", reference)
+            }
+            ValidationErrorKind::MissingDescription => {
+                f.write_str("operation has no summary or description")
+            }
+            ValidationErrorKind::MissingOperationId => f.write_str("operation has no operationId"),
+        }
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.location, self.kind)
+    }
+}
+
+impl Error for ValidationError {}
+
+impl OpenAPI {
+    /// Checks the constraints of the specification that deserializing
+    /// does not, returning every violation found, ordered by location.
+    ///
+    /// These are: unique `operationId`s, unique parameters, path template
+    /// parameters matching the declared (and required) path parameters,
+    /// parameter styles being defined, callback URLs being well-formed
+    /// runtime expression templates, operations having responses,
+    /// discriminator mappings and security requirements naming existing
+    /// components, the latter along with scopes their schemes declare,
+    /// schemas chosen by discriminators declaring their property, and
+    /// references into the components resolving. The operations of
+    /// callbacks are checked like those of paths.
+    pub fn validate(&self) -> Vec<ValidationError> {
+        let mut errors = self.check();
+        errors.retain(|error| error.kind.rule().default_severity() == Severity::Error);
+        errors
+    }
+
+    /// Like [`OpenAPI::validate`], checking the path items on several
+    /// threads.
+    #[cfg(feature = "parallel")]
+    pub fn par_validate(&self) -> Vec<ValidationError> {
+        let mut errors = self.par_check();
+        errors.retain(|error| error.kind.rule().default_severity() == Severity::Error);
+        errors
+    }
+
+    /// Runs every check, including those not required by the specification.
+    pub(crate) fn check(&self) -> Vec<ValidationError> {
+        let empty = Components::default();
+        let components = self.components.as_ref().unwrap_or(&empty);
+        let paths: Vec<_> = self.paths.iter().collect();
+        let (errors, operation_ids) = check_paths(components, &paths);
+        self.check_rest(components, errors, operation_ids)
+    }
+
+    /// Like [`OpenAPI::check`], checking the path items on several threads.
+    #[cfg(feature = "parallel")]
+    pub(crate) fn par_check(&self) -> Vec<ValidationError> {
+        let empty = Components::default();
+        let components = self.components.as_ref().unwrap_or(&empty);
+        let paths: Vec<_> = self.paths.iter().collect();
+        let mut errors = Vec::new();
+        let mut operation_ids: BTreeMap<&str, String> = BTreeMap::new();
+        let checked = crate::parallel::map_chunks(&paths, |paths| check_paths(components, paths));
+        // Each run of paths only knows its own operations, so the first
+        // with an `operationId` may be a duplicate of an earlier run's.
+        for (found, ids) in checked {
+            for mut error in found {
+                if let ValidationErrorKind::DuplicateOperationId {
+                    operation_id,
+                    first,
+                } = &mut error.kind
+                {
+                    if let Some(earlier) = operation_ids.get(operation_id.as_str()) {
+                        *first = earlier.clone();
+                    }
+                }
+                errors.push(error);
+            }
+            for (operation_id, location) in ids {
+                match operation_ids.get(operation_id) {
+                    Some(first) => errors.push(ValidationError {
+                        location: format!("{}/operationId", location),
+                        kind: ValidationErrorKind::DuplicateOperationId {
+                            operation_id: operation_id.to_owned(),
+                            first: first.clone(),
+                        },
+                    }),
+                    None => {
+                        operation_ids.insert(operation_id, location);
+                    }
+                }
+            }
+        }
+        self.check_rest(components, errors, operation_ids)
+    }
+
+    /// Runs the checks of everything but the path items, adding to what
+    /// was found in those.
+    fn check_rest<'a>(
+        &'a self,
+        components: &'a Components,
+        mut errors: Vec<ValidationError>,
+        mut operation_ids: BTreeMap<&'a str, String>,
+    ) -> Vec<ValidationError> {
+        let mut error = |location: String, kind| errors.push(ValidationError { location, kind });
+        check_callbacks(
+            components,
+            &components.callbacks,
+            "/components/callbacks",
+            &mut operation_ids,
+            &mut error,
+        );
+        check_security(components, &self.security, "/security", &mut error);
+
+        for (name, schema) in &components.schemas {
+            let discriminator = match schema {
+                ReferenceOr::Item(schema) => match &schema.schema_data.discriminator {
+                    Some(discriminator) => discriminator,
+                    None => continue,
+                },
+                ReferenceOr::Reference { .. } => continue,
+            };
+            for (value, target) in &discriminator.mapping {
+                let found = if target.contains('/') || target.contains('#') {
+                    match parse_component_reference(target) {
+                        Ok((ComponentKind::Schemas, name)) => {
+                            components.schemas.contains_key(&name)
+                        }
+                        Ok(_) => false,
+                        // Other documents are not checked.
+                        Err(ResolveError::External(_)) => true,
+                        Err(_) => false,
+                    }
+                } else {
+                    components.schemas.contains_key(target)
+                };
+                if !found {
+                    error(
+                        format!(
+                            "/components/schemas/{}/discriminator/mapping/{}",
+                            escape_pointer_segment(name),
+                            escape_pointer_segment(value)
+                        ),
+                        ValidationErrorKind::UnknownDiscriminatorTarget(target.clone()),
+                    );
+                }
+            }
+            let location = format!("/components/schemas/{}", escape_pointer_segment(name));
+            // Unresolved variants are reported as unresolved references.
+            let variants = match schema {
+                ReferenceOr::Item(schema) => schema.discriminator_variants(components),
+                ReferenceOr::Reference { .. } => continue,
+            };
+            for variant in variants.iter().flatten() {
+                if !variant.declares_property(components) {
+                    error(
+                        format!("{}/discriminator", location),
+                        ValidationErrorKind::MissingDiscriminatorProperty {
+                            variant: variant.reference.to_owned(),
+                            property: variant.property_name.to_owned(),
+                        },
+                    );
+                }
+            }
+        }
+
+        self.find_refs(&mut Location::default(), &mut |at, reference| {
+            if reference.is_external() {
+                return;
+            }
+            if let (Some(kind), Some(name)) = (reference.component_kind(), reference.name()) {
+                if !components.contains(kind, &name) {
+                    error(
+                        at.as_str().to_owned(),
+                        ValidationErrorKind::UnresolvedReference(reference.clone()),
+                    );
+                }
+            }
+        });
+
+        errors.sort_by(|a, b| a.location.cmp(&b.location));
+        errors
+    }
+}
+
+/// Checks the path items of `paths`, returning what was found along with
+/// the location of the first operation with each `operationId`.
+fn check_paths<'a>(
+    components: &'a Components,
+    paths: &[(&'a String, &'a ReferenceOr<PathItem>)],
+) -> (Vec<ValidationError>, BTreeMap<&'a str, String>) {
+    let mut errors = Vec::new();
+    let mut error = |location: String, kind| errors.push(ValidationError { location, kind });
+    let mut operation_ids = BTreeMap::new();
+    for &(path, item) in paths {
+        let item = match item {
+            ReferenceOr::Item(item) => item,
+            ReferenceOr::Reference { .. } => continue,
+        };
+        let item_location = format!("/paths/{}", escape_pointer_segment(path));
+        let parsed = PathTemplate::parse(path);
+        // Path parameters cannot be checked against a malformed template.
+        let template: Option<Vec<&str>> = match &parsed {
+            Ok(template) => Some(template.variables().collect()),
+            Err(invalid) => {
+                error(
+                    item_location.clone(),
+                    ValidationErrorKind::InvalidPathTemplate(invalid.clone()),
+                );
+                None
+            }
+        };
+        check_path_item(
+            components,
+            item,
+            &item_location,
+            template.as_deref(),
+            &mut operation_ids,
+            &mut error,
+        );
+    }
+    (errors, operation_ids)
+}
+
+/// Checks a path item at `location`, and its operations along with their
+/// callbacks. Path parameters are checked against the variables of
+/// `template`, where it is known.
+fn check_path_item<'a, E>(
+    components: &'a Components,
+    item: &'a PathItem,
+    item_location: &str,
+    template: Option<&[&str]>,
+    operation_ids: &mut BTreeMap<&'a str, String>,
+    error: &mut E,
+) where
+    E: FnMut(String, ValidationErrorKind),
+{
+    let shared = check_parameters(components, &item.parameters, item_location, template, error);
+
+    for (method, operation) in item.operations() {
+        let location = format!("{}/{}", item_location, method.as_str());
+        if operation.summary.is_none() && operation.description.is_none() {
+            error(location.clone(), ValidationErrorKind::MissingDescription);
+        }
+        if operation.operation_id.is_none() {
+            error(location.clone(), ValidationErrorKind::MissingOperationId);
+        }
+        if let Some(operation_id) = &operation.operation_id {
+            match operation_ids.get(operation_id.as_str()) {
+                Some(first) => error(
+                    format!("{}/operationId", location),
+                    ValidationErrorKind::DuplicateOperationId {
+                        operation_id: operation_id.clone(),
+                        first: first.clone(),
+                    },
+                ),
+                None => {
+                    operation_ids.insert(operation_id, location.clone());
+                }
+            }
+        }
+
+        let own = check_parameters(
+            components,
+            &operation.parameters,
+            &location,
+            template,
+            error,
+        );
+        for name in template.unwrap_or_default() {
+            let declared = |(parameter_in, declared): &(&str, &str)| {
+                *parameter_in == "path" && declared == name
+            };
+            if !own.iter().any(declared) && !shared.iter().any(declared) {
+                error(
+                    location.clone(),
+                    ValidationErrorKind::UndeclaredPathParameter((*name).to_owned()),
+                );
+            }
+        }
+
+        if operation.responses.default.is_none() && operation.responses.responses.is_empty() {
+            error(
+                format!("{}/responses", location),
+                ValidationErrorKind::NoResponses,
+            );
+        }
+        check_security(
+            components,
+            operation.security.as_deref().unwrap_or_default(),
+            &format!("{}/security", location),
+            error,
+        );
+        check_callbacks(
+            components,
+            &operation.callbacks,
+            &format!("{}/callbacks", location),
+            operation_ids,
+            error,
+        );
+    }
+}
+
+/// Checks the callbacks at `location`: that their expressions are well
+/// formed, and their path items as by [`check_path_item`]. The URLs of
+/// callbacks are not path templates, so path parameters are not matched
+/// against them.
+fn check_callbacks<'a, E>(
+    components: &'a Components,
+    callbacks: impl IntoIterator<Item = (&'a String, &'a ReferenceOr<Callback>)>,
+    location: &str,
+    operation_ids: &mut BTreeMap<&'a str, String>,
+    error: &mut E,
+) where
+    E: FnMut(String, ValidationErrorKind),
+{
+    for (name, callback) in callbacks {
+        let callback = match callback {
+            ReferenceOr::Item(callback) => callback,
+            ReferenceOr::Reference { .. } => continue,
+        };
+        for (expression, item) in callback {
+            let item_location = format!(
+                "{}/{}/{}",
+                location,
+                escape_pointer_segment(name),
+                escape_pointer_segment(expression)
+            );
+            if let Err(invalid) = ExpressionTemplate::parse(expression) {
+                error(
+                    item_location.clone(),
+                    ValidationErrorKind::InvalidCallbackExpression(invalid),
+                );
+            }
+            check_path_item(components, item, &item_location, None, operation_ids, error);
+        }
+    }
+}
+
+/// Checks a list of parameters and returns the location and name of each
+/// of those that could be resolved.
+fn check_parameters<'a, E>(
+    components: &'a Components,
+    parameters: &'a [ReferenceOr<Parameter>],
+    location: &str,
+    template: Option<&[&str]>,
+    error: &mut E,
+) -> Vec<(&'static str, &'a str)>
+where
+    E: FnMut(String, ValidationErrorKind),
+{
+    let mut declared = Vec::new();
+    for (i, parameter) in parameters.iter().enumerate() {
+        let parameter = match components.resolve(parameter) {
+            Ok(parameter) => parameter,
+            // Unresolved references are reported separately.
+            Err(_) => continue,
+        };
+        let at = format!("{}/parameters/{}", location, i);
+        let data = parameter.parameter_data_ref();
+        let key = (parameter.location(), data.name.as_str());
+        if declared.contains(&key) {
+            error(
+                at.clone(),
+                ValidationErrorKind::DuplicateParameter(data.name.clone()),
+            );
+        }
+        if let Some(reason) = undefined_style(components, parameter) {
+            error(
+                at.clone(),
+                ValidationErrorKind::UndefinedStyle {
+                    name: data.name.clone(),
+                    style: parameter.effective_style(),
+                    reason,
+                },
+            );
+        }
+        if let Parameter::Path { .. } = parameter {
+            if template.is_some_and(|template| !template.contains(&key.1)) {
+                error(
+                    at.clone(),
+                    ValidationErrorKind::UnknownPathParameter(data.name.clone()),
+                );
+            }
+            if !data.required {
+                error(
+                    at,
+                    ValidationErrorKind::OptionalPathParameter(data.name.clone()),
+                );
+            }
+        }
+        declared.push(key);
+    }
+    declared
+}
+
+/// Why the style of a parameter is not defined for it, if it is not. The
+/// types of the styles of each location rule out styles of other locations.
+fn undefined_style(components: &Components, parameter: &Parameter) -> Option<&'static str> {
+    let data = parameter.parameter_data_ref();
+    let kind = match &data.format {
+        ParameterSchemaOrContent::Schema(schema) => match components.resolve(schema) {
+            Ok(schema) => Some(&schema.schema_kind),
+            Err(_) => None,
+        },
+        // The media type, not the style, describes the serialization.
+        ParameterSchemaOrContent::Content(_) => return None,
+    };
+    let (object, array) = match kind {
+        Some(SchemaKind::Type(Type::Object(_))) => (true, false),
+        Some(SchemaKind::Type(Type::Array(_))) => (false, true),
+        Some(SchemaKind::Type(_)) => (false, false),
+        // Untyped and unresolved schemas may be of any type.
+        _ => (true, true),
+    };
+    let explode = parameter.effective_explode();
+    match parameter.effective_style() {
+        ParameterStyle::DeepObject if !explode => Some("without explode"),
+        ParameterStyle::DeepObject if !object => Some("for values other than objects"),
+        ParameterStyle::SpaceDelimited | ParameterStyle::PipeDelimited if explode => {
+            Some("with explode")
+        }
+        ParameterStyle::SpaceDelimited | ParameterStyle::PipeDelimited if !object && !array => {
+            Some("for primitive values")
+        }
+        _ => None,
+    }
+}
+
+fn check_security<E>(
+    components: &Components,
+    security: &[SecurityRequirement],
+    location: &str,
+    error: &mut E,
+) where
+    E: FnMut(String, ValidationErrorKind),
+{
+    for (i, requirement) in security.iter().enumerate() {
+        for (name, scopes) in requirement {
+            let at = format!("{}/{}/{}", location, i, escape_pointer_segment(name));
+            let scheme = match components.security_schemes.get(name) {
+                Some(scheme) => scheme,
+                None => {
+                    error(at, ValidationErrorKind::UnknownSecurityScheme(name.clone()));
+                    continue;
+                }
+            };
+            let declared = match components.resolve(scheme) {
+                // Any scopes may be requested of OpenID Connect.
+                Ok(SecurityScheme::OpenIDConnect { .. }) | Err(_) => continue,
+                Ok(scheme) => scheme.scopes(),
+            };
+            for (j, scope) in scopes.iter().enumerate() {
+                if !declared.contains_key(scope.as_str()) {
+                    error(
+                        format!("{}/{}", at, j),
+                        ValidationErrorKind::UnknownScope {
+                            scheme: name.clone(),
+                            scope: scope.clone(),
+                        },
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn reports_violations() {
+        let api: OpenAPI = serde_yaml::from_str(
+            "
+openapi: 3.0.0
+info: {title: t, version: '1'}
+security: [{missing: []}]
+paths:
+  /pets/{id}:
+    get:
+      operationId: getPet
+      parameters:
+        - {name: id, in: path, required: true, schema: {type: string}}
+        - {name: id, in: path, required: true, schema: {type: string}}
+      responses:
+        '200': {$ref: '#/components/responses/Missing'}
+    put:
+      operationId: getPet
+      parameters:
+        - {name: pet, in: path, schema: {type: string}}
+      responses: {}
+components:
+  schemas:
+    Pet:
+      oneOf: [{$ref: '#/components/schemas/Cat'}]
+      discriminator:
+        propertyName: kind
+        mapping: {cat: Cat, dog: '#/components/schemas/Dog'}
+    Cat: {type: object}
+",
+        )
+        .unwrap();
+
+        let errors: Vec<String> = api.validate().iter().map(ToString::to_string).collect();
+        assert_eq!(
+            errors,
+            vec![
+                "/components/schemas/Pet/discriminator: schema `#/components/schemas/Cat` \
+                 does not declare discriminator property `kind`",
+                "/components/schemas/Pet/discriminator/mapping/dog: \
+                 discriminator maps to unknown schema `#/components/schemas/Dog`",
+                "/paths/~1pets~1{id}/get/parameters/1: parameter `id` is declared more than once",
+                "/paths/~1pets~1{id}/get/responses/200: \
+                 reference `#/components/responses/Missing` not found",
+                "/paths/~1pets~1{id}/put: \
+                 path template parameter `id` is not declared as a path parameter",
+                "/paths/~1pets~1{id}/put/operationId: \
+                 operationId `getPet` is already used at /paths/~1pets~1{id}/get",
+                "/paths/~1pets~1{id}/put/parameters/0: \
+                 path parameter `pet` does not appear in the path template",
+                "/paths/~1pets~1{id}/put/parameters/0: path parameter `pet` must be required",
+                "/paths/~1pets~1{id}/put/responses: operation has no responses",
+                "/security/0/missing: security scheme `missing` is not declared",
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_malformed_path_templates() {
+        let api: OpenAPI = serde_yaml::from_str(
+            "
+openapi: 3.0.0
+info: {title: t, version: '1'}
+paths:
+  /pets/{id:
+    get:
+      operationId: getPet
+      parameters: [{name: id, in: path, required: true, schema: {type: string}}]
+      responses: {default: {description: error}}
+",
+        )
+        .unwrap();
+        let errors: Vec<String> = api.validate().iter().map(ToString::to_string).collect();
+        assert_eq!(
+            errors,
+            vec!["/paths/~1pets~1{id: path template `/pets/{id` has an unclosed `{`"]
+        );
+    }
+
+    #[test]
+    fn reports_undefined_styles() {
+        let api: OpenAPI = serde_yaml::from_str(
+            "
+openapi: 3.0.0
+info: {title: t, version: '1'}
+paths:
+  /pets:
+    get:
+      parameters:
+        - {name: a, in: query, style: deepObject, explode: false, schema: {type: object}}
+        - {name: b, in: query, style: deepObject, schema: {type: string}}
+        - {name: c, in: query, style: pipeDelimited, explode: true, schema: {type: array}}
+        - {name: d, in: query, style: spaceDelimited, schema: {type: integer}}
+        - {name: e, in: query, style: spaceDelimited, schema: {type: object}}
+        - {name: f, in: query, style: deepObject, schema: {$ref: '#/components/schemas/Filter'}}
+        - {name: g, in: query, style: pipeDelimited, schema: {}}
+      responses: {default: {description: error}}
+components:
+  schemas:
+    Filter: {type: object}
+",
+        )
+        .unwrap();
+        let errors: Vec<String> = api.validate().iter().map(ToString::to_string).collect();
+        assert_eq!(
+            errors,
+            vec![
+                "/paths/~1pets/get/parameters/0: \
+                 the `deepObject` style of parameter `a` is not defined without explode",
+                "/paths/~1pets/get/parameters/1: \
+                 the `deepObject` style of parameter `b` is not defined for values other than objects",
+                "/paths/~1pets/get/parameters/2: \
+                 the `pipeDelimited` style of parameter `c` is not defined with explode",
+                "/paths/~1pets/get/parameters/3: \
+                 the `spaceDelimited` style of parameter `d` is not defined for primitive values",
+            ]
+        );
+    }
+
+    #[test]
+    fn checks_callbacks() {
+        let api: OpenAPI = serde_yaml::from_str(
+            "
+openapi: 3.0.0
+info: {title: t, version: '1'}
+paths:
+  /streams:
+    post:
+      operationId: subscribe
+      responses: {201: {description: subscribed}}
+      callbacks:
+        onData:
+          '{$request.query.url}/data':
+            post:
+              operationId: subscribe
+              security: [{missing: []}]
+              responses: {}
+        onEvent: {$ref: '#/components/callbacks/OnEvent'}
+components:
+  callbacks:
+    OnEvent:
+      '{$request.body#/url':
+        post:
+          requestBody: {$ref: '#/components/requestBodies/Event'}
+          responses: {202: {description: received}}
+",
+        )
+        .unwrap();
+        let errors: Vec<String> = api.validate().iter().map(ToString::to_string).collect();
+        assert_eq!(
+            errors,
+            vec![
+                "/components/callbacks/OnEvent/{$request.body#~1url: \
+                 callback URL is malformed: `{$request.body#/url` is not a runtime expression",
+                "/components/callbacks/OnEvent/{$request.body#~1url/post/requestBody: \
+                 reference `#/components/requestBodies/Event` not found",
+                "/paths/~1streams/post/callbacks/onData/{$request.query.url}~1data/post/operationId: \
+                 operationId `subscribe` is already used at /paths/~1streams/post",
+                "/paths/~1streams/post/callbacks/onData/{$request.query.url}~1data/post/responses: \
+                 operation has no responses",
+                "/paths/~1streams/post/callbacks/onData/{$request.query.url}~1data/post/security/0/missing: \
+                 security scheme `missing` is not declared",
+            ]
+        );
+        assert_eq!(api.callback_operations().count(), 2);
+    }
+
+    #[test]
+    fn reports_unknown_scopes() {
+        let api: OpenAPI = serde_yaml::from_str(
+            "
+openapi: 3.0.0
+info: {title: t, version: '1'}
+security: [{oauth: [read, delete]}, {oidc: [profile]}, {key: [read]}]
+paths: {}
+components:
+  securitySchemes:
+    oauth:
+      type: oauth2
+      flows:
+        implicit: {authorizationUrl: 'https://a', scopes: {read: read}}
+        clientCredentials: {tokenUrl: 'https://t', scopes: {write: write}}
+    oidc: {type: openIdConnect, openIdConnectUrl: 'https://o'}
+    key: {type: apiKey, name: key, in: header}
+",
+        )
+        .unwrap();
+        let errors: Vec<String> = api.validate().iter().map(ToString::to_string).collect();
+        assert_eq!(
+            errors,
+            vec![
+                "/security/0/oauth/1: scope `delete` is not declared by security scheme `oauth`",
+                "/security/2/key/0: scope `read` is not declared by security scheme `key`",
+            ]
+        );
+    }
+}
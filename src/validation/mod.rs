@@ -0,0 +1,141 @@
+//! Validation of the requests and responses of an API against the
+//! operations of its document.
+//!
+//! Unlike [`OpenAPI::validate`](crate::OpenAPI::validate), which checks a
+//! document itself, these check HTTP messages exchanged by the API the
+//! document describes, such as in a middleware of a server.
+
+pub mod request;
+pub mod response;
+
+use crate::multipart::decode_parts;
+use crate::style::{decode, decode_pairs, form_pairs, query_raw, Raw};
+use crate::*;
+use serde_json::Value;
+
+/// Reads a body of the given content type as a value to check against its
+/// schema, or `None` if bodies of the content type are not checked. Fails
+/// with the reason it cannot be parsed.
+fn body_value(
+    components: &Components,
+    schema: &Schema,
+    content_type: &str,
+    body: &[u8],
+) -> Result<Option<Value>, String> {
+    let essence = essence(content_type);
+    if is_json(&essence) {
+        serde_json::from_slice(body)
+            .map(Some)
+            .map_err(|e| e.to_string())
+    } else if essence == "application/x-www-form-urlencoded" {
+        let form = std::str::from_utf8(body).map_err(|e| e.to_string())?;
+        Ok(decode_pairs(components, schema, form_pairs(form)))
+    } else if essence.ends_with("/xml") || essence.ends_with("+xml") {
+        let xml = std::str::from_utf8(body).map_err(|e| e.to_string())?;
+        schema
+            .from_xml(components, xml)
+            .map(Some)
+            .map_err(|e| e.to_string())
+    } else if essence.starts_with("multipart/") {
+        let multipart = Multipart::parse(content_type, body).map_err(|e| e.to_string())?;
+        Ok(Some(decode_parts(
+            components,
+            Some(schema),
+            &multipart.parts,
+        )))
+    } else if essence.starts_with("text/") {
+        let text = std::str::from_utf8(body).map_err(|e| e.to_string())?;
+        Ok(Some(Value::String(text.to_owned())))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Checks a parameter or header value serialized as the single media type
+/// of its `content`, returning how it does not conform to the schema of
+/// the media type. Fails with the reason it cannot be parsed.
+fn content_value(
+    components: &Components,
+    content_type: &str,
+    media_type: &MediaType,
+    raw: String,
+) -> Result<Vec<SchemaViolation>, String> {
+    let value = if is_json(content_type) {
+        serde_json::from_str(&raw).map_err(|e| e.to_string())?
+    } else {
+        Value::String(raw)
+    };
+    Ok(
+        match media_type
+            .schema
+            .as_ref()
+            .map(|schema| components.resolve(schema))
+        {
+            Some(Ok(schema)) => violations(components, schema, &value),
+            _ => Vec::new(),
+        },
+    )
+}
+
+/// Why the value of a parameter or header cannot be checked.
+enum Unreadable {
+    /// It is not serialized in the style of the name given.
+    Malformed(&'static str),
+    /// It cannot be parsed as its media type, for the reason given.
+    Unparsable(String),
+}
+
+/// Checks the strings of a parameter or header `name` against how it is
+/// described: decoded in `style` for a schema, or parsed as its media
+/// type. Returns how the value does not conform to its schema; values of
+/// schemas that cannot be resolved are not checked.
+fn check_value(
+    components: &Components,
+    described: &impl Schemed,
+    style: Style,
+    name: &str,
+    raw: Raw,
+) -> Result<Vec<SchemaViolation>, Unreadable> {
+    match described.format() {
+        ParameterSchemaOrContent::Schema(schema) => {
+            let schema = match components.resolve(schema) {
+                Ok(schema) => schema,
+                Err(_) => return Ok(Vec::new()),
+            };
+            let value =
+                decode(components, style, name, schema, raw).map_err(Unreadable::Malformed)?;
+            Ok(violations(components, schema, &value))
+        }
+        // The single media type describes how the value is serialized.
+        ParameterSchemaOrContent::Content(content) => match content.iter().next() {
+            Some((content_type, media_type)) => {
+                content_value(components, content_type.as_str(), media_type, raw.first())
+                    .map_err(Unreadable::Unparsable)
+            }
+            None => Ok(Vec::new()),
+        },
+    }
+}
+
+/// How `value` does not conform to `schema`.
+fn violations(components: &Components, schema: &Schema, value: &Value) -> Vec<SchemaViolation> {
+    schema
+        .validate_value_in(components, value)
+        .err()
+        .unwrap_or_default()
+}
+
+/// The content type without its parameters, in lower case, such as
+/// `text/plain` for `text/plain; charset=utf-8`.
+fn essence(content_type: &str) -> String {
+    content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase()
+}
+
+fn is_json(content_type: &str) -> bool {
+    MediaTypeRange::from(content_type).is_json()
+}
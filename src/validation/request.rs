@@ -0,0 +1,465 @@
+//! Validation of requests against the operation that handles them.
+//!
+//! ```
+//! use openapiv3::validation::request::{self, Request};
+//! use openapiv3::{Method, OpenAPI};
+//!
+//! let api: OpenAPI = serde_yaml::from_str(
+//!     "
+//! openapi: 3.0.0
+//! info: {title: t, version: '1'}
+//! paths:
+//!   /pets/{id}:
+//!     parameters: [{name: id, in: path, required: true, schema: {type: integer}}]
+//!     put:
+//!       parameters: [{name: tags, in: query, schema: {type: array, items: {type: string}}}]
+//!       requestBody:
+//!         required: true
+//!         content:
+//!           application/json:
+//!             schema: {type: object, required: [name], properties: {name: {type: string}}}
+//!       responses: {}
+//! ",
+//! )
+//! .unwrap();
+//! let route = api.router().route(Method::Put, "/pets/7").unwrap();
+//! let components = Default::default();
+//!
+//! let valid = Request::new()
+//!     .path_param("id", "7")
+//!     .query("tags=a&tags=b")
+//!     .body("application/json", br#"{"name": "Rex"}"#);
+//! assert!(request::validate(route.operation, route.path_item, &components, &valid).is_ok());
+//!
+//! let invalid = Request::new()
+//!     .path_param("id", "seven")
+//!     .body("application/json", b"{}");
+//! let errors: Vec<String> = request::validate(route.operation, route.path_item, &components, &invalid)
+//!     .unwrap_err()
+//!     .iter()
+//!     .map(ToString::to_string)
+//!     .collect();
+//! assert_eq!(
+//!     errors,
+//!     vec![
+//!         "path parameter `id`: expected integer",
+//!         "body: missing required property `name`",
+//!     ]
+//! );
+//! ```
+
+use super::*;
+use std::error::Error;
+use std::fmt;
+
+/// A request, as received by a server, in the parts that an operation
+/// describes.
+///
+/// The method and path are those the operation was found by, such as by
+/// an [`OpenApiRouter`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Request<'r> {
+    /// The values of the variables of the path, percent-decoded, such as
+    /// the [`RouteMatch::parameters`] of the request.
+    pub path_params: Vec<(&'r str, &'r str)>,
+    /// The query string, without its `?`, as sent.
+    pub query: &'r str,
+    /// The headers, by name and value. Names are matched in any case.
+    pub headers: Vec<(&'r str, &'r str)>,
+    /// The value of the `Content-Type` header, if the request has a body.
+    pub content_type: Option<&'r str>,
+    pub body: &'r [u8],
+}
+
+impl<'r> Request<'r> {
+    pub fn new() -> Self {
+        Request::default()
+    }
+
+    pub fn path_param(mut self, name: &'r str, value: &'r str) -> Self {
+        self.path_params.push((name, value));
+        self
+    }
+
+    pub fn query(mut self, query: &'r str) -> Self {
+        self.query = query;
+        self
+    }
+
+    pub fn header(mut self, name: &'r str, value: &'r str) -> Self {
+        self.headers.push((name, value));
+        self
+    }
+
+    pub fn body(mut self, content_type: &'r str, body: &'r [u8]) -> Self {
+        self.content_type = Some(content_type);
+        self.body = body;
+        self
+    }
+
+    /// The values of the headers named `name`, in any case.
+    fn header_values<'s>(&'s self, name: &'s str) -> impl Iterator<Item = &'r str> + 's {
+        self.headers
+            .iter()
+            .filter(move |(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| *value)
+    }
+}
+
+/// A way in which a request does not conform to its operation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RequestError {
+    pub part: RequestPart,
+    pub kind: RequestErrorKind,
+}
+
+/// The part of a request a [`RequestError`] is about.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RequestPart {
+    /// A parameter, by its location, such as `query`, and name.
+    Parameter {
+        location: &'static str,
+        name: String,
+    },
+    Body,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RequestErrorKind {
+    /// A required parameter or body is missing.
+    Missing,
+    /// A query parameter is empty without `allowEmptyValue`.
+    Empty,
+    /// A parameter is not serialized in the style it declares, such as a
+    /// `label` value without its leading `.`.
+    Malformed { style: &'static str },
+    /// The value does not conform to its schema.
+    Schema(SchemaViolation),
+    /// The request has a body without a content type.
+    MissingContentType,
+    /// The operation accepts no body of the content type.
+    UnsupportedContentType(String),
+    /// The value cannot be parsed as its content type, for the reason
+    /// given.
+    Unparsable(String),
+}
+
+impl fmt::Display for RequestPart {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RequestPart::Parameter { location, name } => {
+                write!(f, "{} parameter `{}`", location, name)
+            }
+            RequestPart::Body => f.write_str("body"),
+        }
+    }
+}
+
+impl fmt::Display for RequestErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RequestErrorKind::Missing => f.write_str("is required"),
+            RequestErrorKind::Empty => f.write_str("must not be empty"),
+            RequestErrorKind::Malformed { style } => {
+                write!(f, "is not serialized in the `{}` style", style)
+            }
+            RequestErrorKind::Schema(violation) => violation.fmt(f),
+            RequestErrorKind::MissingContentType => f.write_str("has no content type"),
+            RequestErrorKind::UnsupportedContentType(content_type) => {
+                write!(f, "content type `{}` is not accepted", content_type)
+            }
+            RequestErrorKind::Unparsable(reason) => write!(f, "cannot be parsed: {}", reason),
+        }
+    }
+}
+
+impl fmt::Display for RequestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.part, self.kind)
+    }
+}
+
+impl Error for RequestError {}
+
+/// Checks a request against the operation that handles it, along with the
+/// parameters of its path item: that required parameters and bodies are
+/// present, that parameters are serialized in their style, and that every
+/// value conforms to its schema.
+///
+/// Parameters are decoded into values of the types their schemas give
+/// them, so that `?limit=10` is checked as the number 10. Bodies of JSON
+/// and `application/x-www-form-urlencoded` content types are checked
+/// against their schemas; those of other content types are only checked
+/// to be accepted. References that cannot be resolved in `components` are
+/// left unchecked.
+pub fn validate(
+    operation: &Operation,
+    path_item: &PathItem,
+    components: &Components,
+    request: &Request<'_>,
+) -> Result<(), Vec<RequestError>> {
+    let query = form_pairs(request.query);
+    let cookies: Vec<(&str, String)> = request
+        .header_values("cookie")
+        .flat_map(|header| header.split(';'))
+        .filter_map(|cookie| cookie.trim().split_once('='))
+        .map(|(name, value)| (name, value.to_owned()))
+        .collect();
+    let parameters = operation.effective_parameters(path_item, components);
+    let query_names: Vec<&str> = parameters
+        .iter()
+        .filter(|parameter| matches!(parameter, Parameter::Query { .. }))
+        .map(|parameter| parameter.parameter_data_ref().name.as_str())
+        .collect();
+
+    let mut errors = Vec::new();
+    for parameter in &parameters {
+        let data = parameter.parameter_data_ref();
+        let mut error = |kind| {
+            errors.push(RequestError {
+                part: RequestPart::Parameter {
+                    location: parameter.location(),
+                    name: data.name.clone(),
+                },
+                kind,
+            })
+        };
+        let raw = match parameter {
+            Parameter::Path { .. } => request
+                .path_params
+                .iter()
+                .find(|(name, _)| *name == data.name)
+                .map(|(_, value)| Raw::One((*value).to_owned())),
+            Parameter::Query { .. } => query_raw(
+                &query,
+                &data.name,
+                Some(query_names.as_slice()).filter(|_| parameter.spreads(components)),
+            ),
+            Parameter::Header { .. } => {
+                // These are described by other fields of the operation.
+                let ignored = ["accept", "content-type", "authorization"];
+                if ignored.contains(&data.name.to_ascii_lowercase().as_str()) {
+                    continue;
+                }
+                let values: Vec<&str> = request.header_values(&data.name).collect();
+                Some(Raw::One(values.join(","))).filter(|_| !values.is_empty())
+            }
+            Parameter::Cookie { .. } => {
+                let values: Vec<String> = cookies
+                    .iter()
+                    .filter(|(name, _)| *name == data.name)
+                    .map(|(_, value)| value.clone())
+                    .collect();
+                Some(Raw::Many(values)).filter(|raw| !raw.is_empty())
+            }
+        };
+        let raw = match raw {
+            Some(raw) => raw,
+            None if data.required => {
+                error(RequestErrorKind::Missing);
+                continue;
+            }
+            None => continue,
+        };
+        if let Parameter::Query {
+            allow_empty_value, ..
+        } = parameter
+        {
+            if raw.is_empty_value() && *allow_empty_value != Some(true) {
+                error(RequestErrorKind::Empty);
+                continue;
+            }
+        }
+
+        match check_value(components, *parameter, parameter.style(), &data.name, raw) {
+            Ok(violations) => violations
+                .into_iter()
+                .for_each(|violation| error(RequestErrorKind::Schema(violation))),
+            Err(Unreadable::Malformed(style)) => error(RequestErrorKind::Malformed { style }),
+            Err(Unreadable::Unparsable(reason)) => error(RequestErrorKind::Unparsable(reason)),
+        }
+    }
+
+    check_body(operation, components, request, &mut errors);
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+fn check_body(
+    operation: &Operation,
+    components: &Components,
+    request: &Request<'_>,
+    errors: &mut Vec<RequestError>,
+) {
+    let mut error = |kind| {
+        errors.push(RequestError {
+            part: RequestPart::Body,
+            kind,
+        })
+    };
+    let body = match &operation.request_body {
+        Some(body) => match components.resolve(body) {
+            Ok(body) => body,
+            Err(_) => return,
+        },
+        None => return,
+    };
+    if request.body.is_empty() && request.content_type.is_none() {
+        if body.required {
+            error(RequestErrorKind::Missing);
+        }
+        return;
+    }
+    let content_type = match request.content_type {
+        Some(content_type) => content_type,
+        None => return error(RequestErrorKind::MissingContentType),
+    };
+    let media_type = match body.content.negotiate(content_type) {
+        Some((_, media_type)) => media_type,
+        None => {
+            return error(RequestErrorKind::UnsupportedContentType(
+                content_type.to_owned(),
+            ))
+        }
+    };
+    let schema = match media_type
+        .schema
+        .as_ref()
+        .map(|schema| components.resolve(schema))
+    {
+        Some(Ok(schema)) => schema,
+        _ => return,
+    };
+    match body_value(components, schema, content_type, request.body) {
+        Ok(Some(value)) => violations(components, schema, &value)
+            .into_iter()
+            .for_each(|violation| error(RequestErrorKind::Schema(violation))),
+        Ok(None) => {}
+        Err(reason) => error(RequestErrorKind::Unparsable(reason)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn api() -> OpenAPI {
+        serde_yaml::from_str(
+            "
+openapi: 3.0.0
+info: {title: t, version: '1'}
+paths:
+  /pets/{ids}:
+    get:
+      parameters:
+        - {name: ids, in: path, required: true, style: label, schema: {type: array, items: {type: integer}}}
+        - {name: filter, in: query, style: deepObject, schema: {$ref: '#/components/schemas/Filter'}}
+        - {name: page, in: query, schema: {type: integer, minimum: 1}}
+        - {name: sort, in: query, explode: false, schema: {type: array, items: {type: string}}}
+        - {name: tags, in: query, style: pipeDelimited, schema: {type: array, items: {type: string}}}
+        - {name: X-Trace, in: header, required: true, schema: {type: string}}
+        - {name: session, in: cookie, schema: {type: string, minLength: 4}}
+        - name: coordinates
+          in: query
+          content:
+            application/json: {schema: {type: object, required: [lat]}}
+      requestBody:
+        content:
+          application/x-www-form-urlencoded:
+            schema: {type: object, properties: {age: {type: integer}}}
+          multipart/form-data:
+            schema: {type: object, properties: {age: {type: integer}}}
+          text/*: {schema: {type: string, maxLength: 3}}
+      responses: {}
+components:
+  schemas:
+    Filter:
+      type: object
+      additionalProperties: false
+      properties: {color: {type: string}, age: {type: integer}}
+",
+        )
+        .unwrap()
+    }
+
+    fn errors(request: &Request<'_>) -> Vec<String> {
+        let api = api();
+        let route = api.router().route(Method::Get, "/pets/x").unwrap();
+        match validate(
+            route.operation,
+            route.path_item,
+            api.components.as_ref().unwrap(),
+            request,
+        ) {
+            Ok(()) => Vec::new(),
+            Err(errors) => errors.iter().map(ToString::to_string).collect(),
+        }
+    }
+
+    #[test]
+    fn decodes_parameter_styles() {
+        let request = Request::new()
+            .path_param("ids", ".1,2")
+            .query(
+                "filter[color]=red&filter[age]=3&page=2&sort=name,age&tags=a|b\
+                 &coordinates=%7B%22lat%22%3A1%7D",
+            )
+            .header("x-trace", "abc")
+            .header("Cookie", "theme=dark; session=abcd");
+        assert_eq!(errors(&request), Vec::<String>::new());
+
+        let request = Request::new()
+            .path_param("ids", "1,a")
+            .query("filter[size]=3&page=0&coordinates=%7B%7D")
+            .header("Cookie", "session=abc");
+        assert_eq!(
+            errors(&request),
+            vec![
+                "path parameter `ids`: is not serialized in the `label` style",
+                "query parameter `filter`: property `size` is not allowed",
+                "query parameter `page`: must be greater than or equal to 1",
+                "header parameter `X-Trace`: is required",
+                "cookie parameter `session`: must be at least 4 characters long",
+                "query parameter `coordinates`: missing required property `lat`",
+            ]
+        );
+    }
+
+    #[test]
+    fn checks_bodies() {
+        let request = |content_type, body| {
+            Request::new()
+                .path_param("ids", ".1")
+                .header("X-Trace", "abc")
+                .body(content_type, body)
+        };
+        assert_eq!(
+            errors(&request("application/x-www-form-urlencoded", b"age=3")),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            errors(&request("application/x-www-form-urlencoded", b"age=old")),
+            vec!["body: /age: expected integer"]
+        );
+        let multipart = b"--b\r\nContent-Disposition: form-data; name=age\r\n\r\nold\r\n--b--";
+        assert_eq!(
+            errors(&request("multipart/form-data; boundary=b", multipart)),
+            vec!["body: /age: expected integer"]
+        );
+        assert_eq!(
+            errors(&request("multipart/form-data; boundary=b", b"--c--")),
+            vec!["body: cannot be parsed: the body is not delimited by its boundary"]
+        );
+        assert_eq!(
+            errors(&request("text/plain; charset=utf-8", b"long")),
+            vec!["body: must be at most 3 characters long"]
+        );
+        assert_eq!(
+            errors(&request("application/json", b"{}")),
+            vec!["body: content type `application/json` is not accepted"]
+        );
+    }
+}
@@ -0,0 +1,380 @@
+//! Validation of responses against the operation that returned them.
+//!
+//! ```
+//! use openapiv3::validation::response::{self, Response};
+//! use openapiv3::{Method, OpenAPI};
+//!
+//! let api: OpenAPI = serde_yaml::from_str(
+//!     "
+//! openapi: 3.0.0
+//! info: {title: t, version: '1'}
+//! paths:
+//!   /pets:
+//!     get:
+//!       responses:
+//!         200:
+//!           description: the pets
+//!           headers: {X-Total: {required: true, schema: {type: integer}}}
+//!           content:
+//!             application/json: {schema: {type: array, items: {type: string}}}
+//!         4XX: {description: an error}
+//! ",
+//! )
+//! .unwrap();
+//! let operation = api.router().route(Method::Get, "/pets").unwrap().operation;
+//! let components = Default::default();
+//!
+//! let ok = Response::new(200)
+//!     .header("x-total", "2")
+//!     .body("application/json", br#"["Rex", "Tom"]"#);
+//! assert!(response::validate(operation, &components, &ok).is_ok());
+//! assert!(response::validate(operation, &components, &Response::new(404)).is_ok());
+//!
+//! let errors: Vec<String> = response::validate(operation, &components, &Response::new(500))
+//!     .unwrap_err()
+//!     .iter()
+//!     .map(ToString::to_string)
+//!     .collect();
+//! assert_eq!(errors, vec!["status: 500 is not a documented response"]);
+//! ```
+
+use super::*;
+use std::error::Error;
+use std::fmt;
+
+/// A response, as returned by a server, in the parts that an operation
+/// describes.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Response<'r> {
+    pub status: u16,
+    /// The headers, by name and value. Names are matched in any case.
+    pub headers: Vec<(&'r str, &'r str)>,
+    /// The value of the `Content-Type` header, if the response has a body.
+    pub content_type: Option<&'r str>,
+    pub body: &'r [u8],
+}
+
+impl<'r> Response<'r> {
+    pub fn new(status: u16) -> Self {
+        Response {
+            status,
+            ..Response::default()
+        }
+    }
+
+    pub fn header(mut self, name: &'r str, value: &'r str) -> Self {
+        self.headers.push((name, value));
+        self
+    }
+
+    pub fn body(mut self, content_type: &'r str, body: &'r [u8]) -> Self {
+        self.content_type = Some(content_type);
+        self.body = body;
+        self
+    }
+}
+
+/// A way in which a response does not conform to its operation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResponseError {
+    pub part: ResponsePart,
+    pub kind: ResponseErrorKind,
+}
+
+/// The part of a response a [`ResponseError`] is about.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResponsePart {
+    Status,
+    /// A header, by its name as declared.
+    Header(String),
+    Body,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ResponseErrorKind {
+    /// The operation describes no response for the status code, nor a
+    /// default one.
+    UndocumentedStatus(u16),
+    /// A required header is missing.
+    Missing,
+    /// A header is not serialized in the `simple` style.
+    Malformed { style: &'static str },
+    /// The value does not conform to its schema.
+    Schema(SchemaViolation),
+    /// The response has a body although it is described without content.
+    UnexpectedBody,
+    /// The response has a body without a content type.
+    MissingContentType,
+    /// The response describes no content of the content type.
+    UnsupportedContentType(String),
+    /// The value cannot be parsed as its content type, for the reason
+    /// given.
+    Unparsable(String),
+}
+
+impl fmt::Display for ResponsePart {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ResponsePart::Status => f.write_str("status"),
+            ResponsePart::Header(name) => write!(f, "header `{}`", name),
+            ResponsePart::Body => f.write_str("body"),
+        }
+    }
+}
+
+impl fmt::Display for ResponseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ResponseErrorKind::UndocumentedStatus(status) => {
+                write!(f, "{} is not a documented response", status)
+            }
+            ResponseErrorKind::Missing => f.write_str("is required"),
+            ResponseErrorKind::Malformed { style } => {
+                write!(f, "is not serialized in the `{}` style", style)
+            }
+            ResponseErrorKind::Schema(violation) => violation.fmt(f),
+            ResponseErrorKind::UnexpectedBody => f.write_str("is not expected"),
+            ResponseErrorKind::MissingContentType => f.write_str("has no content type"),
+            ResponseErrorKind::UnsupportedContentType(content_type) => {
+                write!(f, "content type `{}` is not described", content_type)
+            }
+            ResponseErrorKind::Unparsable(reason) => write!(f, "cannot be parsed: {}", reason),
+        }
+    }
+}
+
+impl fmt::Display for ResponseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.part, self.kind)
+    }
+}
+
+impl Error for ResponseError {}
+
+/// Checks a response against the operation that returned it: that its
+/// status code is documented, that required headers are present, and that
+/// headers and body conform to their schemas.
+///
+/// The response checked against is the one [`Responses::get_for`] finds for
+/// the status code. Bodies are checked as by
+/// [`request::validate`]. Media types and other
+/// references that cannot be resolved in `components` are left unchecked.
+pub fn validate(
+    operation: &Operation,
+    components: &Components,
+    response: &Response<'_>,
+) -> Result<(), Vec<ResponseError>> {
+    let described = operation.responses.get_for(response.status);
+    let described = match described.map(|described| components.resolve(described)) {
+        Some(Ok(described)) => described,
+        Some(Err(_)) => return Ok(()),
+        None => {
+            return Err(vec![ResponseError {
+                part: ResponsePart::Status,
+                kind: ResponseErrorKind::UndocumentedStatus(response.status),
+            }])
+        }
+    };
+
+    let mut errors = described
+        .validate_headers(components, &response.headers)
+        .err()
+        .unwrap_or_default();
+    let mut error = |part, kind| errors.push(ResponseError { part, kind });
+    check_body(described, components, response, &mut |kind| {
+        error(ResponsePart::Body, kind)
+    });
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+impl crate::Response {
+    /// Checks the headers of a response, by name and value, against those
+    /// the response describes: that required ones are present, and that
+    /// their values conform to their schemas.
+    ///
+    /// Names are matched in any case, and the values of a header given
+    /// more than once are joined with commas, as HTTP allows. A described
+    /// `Content-Type` header is ignored, as the content of the response
+    /// describes it. Headers that cannot be resolved in `components` are
+    /// left unchecked.
+    ///
+    /// ```
+    /// use openapiv3::validation::response::ResponseErrorKind;
+    /// use openapiv3::{Components, Response};
+    ///
+    /// let response: Response = serde_yaml::from_str(
+    ///     "
+    /// description: a page
+    /// headers:
+    ///   X-Page: {required: true, schema: {type: integer}}
+    ///   Content-Type: {required: true, schema: {type: string}}
+    /// ",
+    /// )
+    /// .unwrap();
+    /// let components = Components::default();
+    /// assert!(response.validate_headers(&components, &[("x-page", "2")]).is_ok());
+    ///
+    /// let errors = response.validate_headers(&components, &[]).unwrap_err();
+    /// assert_eq!(errors.len(), 1);
+    /// assert_eq!(errors[0].kind, ResponseErrorKind::Missing);
+    /// ```
+    pub fn validate_headers(
+        &self,
+        components: &Components,
+        headers: &[(&str, &str)],
+    ) -> Result<(), Vec<ResponseError>> {
+        let mut errors = Vec::new();
+        for (name, header) in &self.headers {
+            // The content type is described by the content of the response.
+            if name.eq_ignore_ascii_case("content-type") {
+                continue;
+            }
+            let header = match components.resolve(header) {
+                Ok(header) => header,
+                Err(_) => continue,
+            };
+            let mut error = |kind| {
+                errors.push(ResponseError {
+                    part: ResponsePart::Header(name.clone()),
+                    kind,
+                })
+            };
+            let values: Vec<&str> = headers
+                .iter()
+                .filter(|(actual, _)| actual.eq_ignore_ascii_case(name))
+                .map(|(_, value)| *value)
+                .collect();
+            if values.is_empty() {
+                if header.required {
+                    error(ResponseErrorKind::Missing);
+                }
+                continue;
+            }
+            let raw = Raw::One(values.join(","));
+            match check_value(components, header, header.style(), name, raw) {
+                Ok(violations) => violations
+                    .into_iter()
+                    .for_each(|violation| error(ResponseErrorKind::Schema(violation))),
+                Err(Unreadable::Malformed(style)) => error(ResponseErrorKind::Malformed { style }),
+                Err(Unreadable::Unparsable(reason)) => error(ResponseErrorKind::Unparsable(reason)),
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+fn check_body(
+    described: &crate::Response,
+    components: &Components,
+    response: &Response<'_>,
+    error: &mut impl FnMut(ResponseErrorKind),
+) {
+    if response.body.is_empty() && response.content_type.is_none() {
+        return;
+    }
+    if described.content.is_empty() {
+        return error(ResponseErrorKind::UnexpectedBody);
+    }
+    let content_type = match response.content_type {
+        Some(content_type) => content_type,
+        None => return error(ResponseErrorKind::MissingContentType),
+    };
+    let media_type = match described.content.negotiate(content_type) {
+        Some((_, ReferenceOr::Item(media_type))) => media_type,
+        Some((_, ReferenceOr::Reference { .. })) => return,
+        None => {
+            return error(ResponseErrorKind::UnsupportedContentType(
+                content_type.to_owned(),
+            ))
+        }
+    };
+    let schema = match media_type
+        .schema
+        .as_ref()
+        .map(|schema| components.resolve(schema))
+    {
+        Some(Ok(schema)) => schema,
+        _ => return,
+    };
+    match body_value(components, schema, content_type, response.body) {
+        Ok(Some(value)) => violations(components, schema, &value)
+            .into_iter()
+            .for_each(|violation| error(ResponseErrorKind::Schema(violation))),
+        Ok(None) => {}
+        Err(reason) => error(ResponseErrorKind::Unparsable(reason)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checks_responses() {
+        let operation: Operation = serde_yaml::from_str(
+            "
+responses:
+  200:
+    description: a pet
+    headers:
+      Content-Type: {required: true, schema: {type: integer}}
+      X-Rate: {schema: {type: array, items: {type: integer}}}
+      X-Meta: {content: {application/json: {schema: {type: object, required: [id]}}}}
+    content:
+      application/json:
+        schema: {type: object, required: [name], properties: {name: {type: string}}}
+  2XX: {description: accepted}
+  default:
+    description: an error
+    content: {text/plain: {schema: {type: string}}}
+",
+        )
+        .unwrap();
+        let errors = |response: &Response<'_>| -> Vec<String> {
+            match validate(&operation, &Components::default(), response) {
+                Ok(()) => Vec::new(),
+                Err(errors) => errors.iter().map(ToString::to_string).collect(),
+            }
+        };
+
+        let ok = Response::new(200)
+            .header("X-Rate", "1, 2")
+            .header("X-Rate", "3")
+            .body("application/json; charset=utf-8", br#"{"name": "Rex"}"#);
+        assert_eq!(errors(&ok), Vec::<String>::new());
+
+        let bad = Response::new(200)
+            .header("x-rate", "1,two")
+            .header("X-Meta", "{}")
+            .body("application/json", b"{\"name\": 1");
+        assert_eq!(
+            errors(&bad),
+            vec![
+                "header `X-Meta`: missing required property `id`",
+                "header `X-Rate`: /1: expected integer",
+                "body: cannot be parsed: EOF while parsing an object at line 1 column 10",
+            ]
+        );
+
+        assert_eq!(
+            errors(&Response::new(204).body("text/plain", b"done")),
+            vec!["body: is not expected"]
+        );
+        assert_eq!(
+            errors(&Response::new(500).body("application/json", b"{}")),
+            vec!["body: content type `application/json` is not described"]
+        );
+        assert_eq!(
+            errors(&Response::new(500).body("text/plain", b"oops")),
+            Vec::<String>::new()
+        );
+    }
+}
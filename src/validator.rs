@@ -0,0 +1,259 @@
+use crate::*;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// How serious a problem found by a [`Validator`] is.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        })
+    }
+}
+
+/// A check performed by a [`Validator`].
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Rule {
+    /// No two operations share an `operationId`.
+    UniqueOperationIds,
+    /// No parameter is declared twice for the same operation or path.
+    UniqueParameters,
+    /// Path templates and path parameters match, and the latter are required.
+    PathParameters,
+    /// Parameters have styles defined for their `explode` and schemas.
+    ParameterStyles,
+    /// The URLs of callbacks are well-formed runtime expression templates.
+    CallbackExpressions,
+    /// Every operation has at least one response.
+    Responses,
+    /// Discriminator mappings name existing schemas, and the schemas
+    /// discriminators choose declare their property.
+    DiscriminatorMappings,
+    /// Security requirements name declared security schemes, and scopes
+    /// that those declare.
+    SecuritySchemes,
+    /// References into the components resolve.
+    References,
+    /// Every operation has a summary or a description.
+    OperationDescriptions,
+    /// Every operation has an `operationId`.
+    OperationIds,
+}
+
+impl Rule {
+    /// Every rule, in the order they are checked.
+    pub const ALL: [Rule; 11] = [
+        Rule::UniqueOperationIds,
+        Rule::UniqueParameters,
+        Rule::PathParameters,
+        Rule::ParameterStyles,
+        Rule::CallbackExpressions,
+        Rule::Responses,
+        Rule::DiscriminatorMappings,
+        Rule::SecuritySchemes,
+        Rule::References,
+        Rule::OperationDescriptions,
+        Rule::OperationIds,
+    ];
+
+    /// The severity the rule has unless configured otherwise: an error for
+    /// requirements of the specification, less for matters of style.
+    pub fn default_severity(self) -> Severity {
+        match self {
+            Rule::OperationDescriptions => Severity::Warning,
+            Rule::OperationIds => Severity::Info,
+            _ => Severity::Error,
+        }
+    }
+
+    /// A short name for the rule, such as `unique-operation-ids`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Rule::UniqueOperationIds => "unique-operation-ids",
+            Rule::UniqueParameters => "unique-parameters",
+            Rule::PathParameters => "path-parameters",
+            Rule::ParameterStyles => "parameter-styles",
+            Rule::CallbackExpressions => "callback-expressions",
+            Rule::Responses => "responses",
+            Rule::DiscriminatorMappings => "discriminator-mappings",
+            Rule::SecuritySchemes => "security-schemes",
+            Rule::References => "references",
+            Rule::OperationDescriptions => "operation-descriptions",
+            Rule::OperationIds => "operation-ids",
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A configurable set of checks, each reported at its own severity.
+///
+/// ```
+/// use openapiv3::{Rule, Severity, Validator};
+///
+/// let validator = Validator::new()
+///     .severity(Rule::OperationIds, Severity::Error)
+///     .disable(Rule::OperationDescriptions);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Validator {
+    severities: BTreeMap<Rule, Option<Severity>>,
+}
+
+impl Default for Validator {
+    fn default() -> Self {
+        Validator {
+            severities: Rule::ALL
+                .iter()
+                .map(|&rule| (rule, Some(rule.default_severity())))
+                .collect(),
+        }
+    }
+}
+
+impl Validator {
+    /// A validator running every rule at its default severity.
+    pub fn new() -> Self {
+        Validator::default()
+    }
+
+    /// Reports problems found by `rule` at `severity`,
+    /// enabling the rule if it was disabled.
+    pub fn severity(mut self, rule: Rule, severity: Severity) -> Self {
+        self.severities.insert(rule, Some(severity));
+        self
+    }
+
+    /// Stops reporting problems found by `rule`.
+    pub fn disable(mut self, rule: Rule) -> Self {
+        self.severities.insert(rule, None);
+        self
+    }
+
+    /// The severity `rule` is reported at, or `None` if it is disabled.
+    pub fn severity_of(&self, rule: Rule) -> Option<Severity> {
+        self.severities.get(&rule).copied().flatten()
+    }
+
+    /// Checks a document against the enabled rules.
+    pub fn validate(&self, api: &OpenAPI) -> Report {
+        self.report(api.check())
+    }
+
+    /// Like [`Validator::validate`], checking the path items on several
+    /// threads.
+    #[cfg(feature = "parallel")]
+    pub fn par_validate(&self, api: &OpenAPI) -> Report {
+        self.report(api.par_check())
+    }
+
+    fn report(&self, errors: Vec<ValidationError>) -> Report {
+        let findings = errors
+            .into_iter()
+            .filter_map(|error| {
+                let rule = error.kind.rule();
+                self.severity_of(rule).map(|severity| Finding {
+                    severity,
+                    rule,
+                    error,
+                })
+            })
+            .collect();
+        Report { findings }
+    }
+}
+
+/// A problem found by a [`Validator`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Finding {
+    pub severity: Severity,
+    pub rule: Rule,
+    pub error: ValidationError,
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} [{}] {}", self.severity, self.rule, self.error)
+    }
+}
+
+/// The problems found by a [`Validator`], ordered by location.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Report {
+    pub findings: Vec<Finding>,
+}
+
+impl Report {
+    /// Whether any problem was reported as an error.
+    pub fn has_errors(&self) -> bool {
+        self.findings
+            .iter()
+            .any(|finding| finding.severity == Severity::Error)
+    }
+
+    /// The problems reported at exactly `severity`.
+    pub fn with_severity(&self, severity: Severity) -> impl Iterator<Item = &Finding> {
+        self.findings
+            .iter()
+            .filter(move |finding| finding.severity == severity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn reports_by_severity() {
+        let api: OpenAPI = serde_yaml::from_str(
+            "
+openapi: 3.0.0
+info: {title: t, version: '1'}
+paths:
+  /pets:
+    get:
+      summary: List pets
+      responses: {'200': {description: pets}}
+    post:
+      responses: {}
+",
+        )
+        .unwrap();
+
+        let report = Validator::new().validate(&api);
+        let findings: Vec<String> = report.findings.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            findings,
+            vec![
+                "info [operation-ids] /paths/~1pets/get: operation has no operationId",
+                "warning [operation-descriptions] /paths/~1pets/post: \
+                 operation has no summary or description",
+                "info [operation-ids] /paths/~1pets/post: operation has no operationId",
+                "error [responses] /paths/~1pets/post/responses: operation has no responses",
+            ]
+        );
+        assert!(report.has_errors());
+        assert_eq!(report.with_severity(Severity::Info).count(), 2);
+
+        let report = Validator::new()
+            .disable(Rule::Responses)
+            .severity(Rule::OperationIds, Severity::Warning)
+            .validate(&api);
+        assert!(!report.has_errors());
+        assert_eq!(report.with_severity(Severity::Warning).count(), 3);
+        assert_eq!(api.validate().len(), 1);
+    }
+}
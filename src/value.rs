@@ -0,0 +1,784 @@
+//! Checking of JSON values against schemas.
+
+use crate::walk::Location;
+use crate::*;
+use regex::Regex;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fmt;
+
+/// A way in which a value does not conform to a schema.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaViolation {
+    /// A JSON Pointer to the offending part of the value.
+    pub path: String,
+    pub kind: SchemaViolationKind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SchemaViolationKind {
+    /// The value is not of the type the schema requires.
+    Type {
+        expected: &'static str,
+    },
+    /// The value is not one of those the schema enumerates.
+    Enum,
+    Minimum {
+        minimum: f64,
+        exclusive: bool,
+    },
+    Maximum {
+        maximum: f64,
+        exclusive: bool,
+    },
+    MultipleOf(f64),
+    MinLength(usize),
+    MaxLength(usize),
+    /// The string does not match the regular expression.
+    Pattern(String),
+    /// The schema's `pattern` is not a valid regular expression.
+    InvalidPattern(String),
+    /// The value is not of the format the schema requires.
+    Format(String),
+    MinItems(usize),
+    MaxItems(usize),
+    UniqueItems,
+    MinProperties(usize),
+    MaxProperties(usize),
+    /// A required property is missing.
+    MissingProperty(String),
+    /// A property is present that the schema does not allow.
+    AdditionalProperty(String),
+    /// The value matches none of the `anyOf` schemas.
+    AnyOf,
+    /// The value matches `matched` of the `oneOf` schemas, rather than one.
+    OneOf {
+        matched: usize,
+    },
+    /// The schema, or a part of it, could not be resolved.
+    Reference(ResolveError),
+}
+
+impl fmt::Display for SchemaViolationKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bound = |exclusive: &bool| if *exclusive { "" } else { " or equal to" };
+        match self {
+            SchemaViolationKind::Type { expected } => write!(f, "expected {}", expected),
+            SchemaViolationKind::Enum => f.write_str("value is not one of the enumerated values"),
+            SchemaViolationKind::Minimum { minimum, exclusive } => {
+                write!(f, "must be greater than{} {}", bound(exclusive), minimum)
+            }
+            SchemaViolationKind::Maximum { maximum, exclusive } => {
+                write!(f, "must be less than{} {}", bound(exclusive), maximum)
+            }
+            SchemaViolationKind::MultipleOf(n) => write!(f, "must be a multiple of {}", n),
+            SchemaViolationKind::MinLength(n) => {
+                write!(f, "must be at least {} characters long", n)
+            }
+            SchemaViolationKind::MaxLength(n) => write!(f, "must be at most {} characters long", n),
+            SchemaViolationKind::Pattern(pattern) => write!(f, "must match `{}`", pattern),
+            SchemaViolationKind::InvalidPattern(pattern) => {
+                write!(f, "invalid pattern `{}`", pattern)
+            }
+            SchemaViolationKind::Format(format) => write!(f, "expected format `{}`", format),
+            SchemaViolationKind::MinItems(n) => write!(f, "must have at least {} items", n),
+            SchemaViolationKind::MaxItems(n) => write!(f, "must have at most {} items", n),
+            SchemaViolationKind::UniqueItems => f.write_str("items must be unique"),
+            SchemaViolationKind::MinProperties(n) => {
+                write!(f, "must have at least {} properties", n)
+            }
+            SchemaViolationKind::MaxProperties(n) => {
+                write!(f, "must have at most {} properties", n)
+            }
+            SchemaViolationKind::MissingProperty(name) => {
+                write!(f, "missing required property `{}`", name)
+            }
+            SchemaViolationKind::AdditionalProperty(name) => {
+                write!(f, "property `{}` is not allowed", name)
+            }
+            SchemaViolationKind::AnyOf => f.write_str("matches none of the `anyOf` schemas"),
+            SchemaViolationKind::OneOf { matched } => write!(
+                f,
+                "matches {} of the `oneOf` schemas instead of exactly one",
+                matched
+            ),
+            SchemaViolationKind::Reference(e) => e.fmt(f),
+        }
+    }
+}
+
+impl fmt::Display for SchemaViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            self.kind.fmt(f)
+        } else {
+            write!(f, "{}: {}", self.path, self.kind)
+        }
+    }
+}
+
+impl Error for SchemaViolation {}
+
+/// Checks values against schemas, resolving references in `components`.
+pub(crate) struct ValueChecker<'a> {
+    components: &'a Components,
+    formats: &'a FormatRegistry,
+    at: Location,
+    violations: Vec<SchemaViolation>,
+    /// The references being checked, each with the location in the value
+    /// it is checked against, so that loops of references end.
+    checking: HashSet<(Reference, String)>,
+    /// The compiled `pattern`s, or none for those that are not valid.
+    patterns: HashMap<String, Option<Regex>>,
+}
+
+impl<'a> ValueChecker<'a> {
+    fn new(components: &'a Components, formats: &'a FormatRegistry) -> Self {
+        ValueChecker {
+            components,
+            formats,
+            at: Location::default(),
+            violations: Vec::new(),
+            checking: HashSet::new(),
+            patterns: HashMap::new(),
+        }
+    }
+
+    pub(crate) fn check(
+        components: &'a Components,
+        schema: &ReferenceOr<Schema>,
+        value: &Value,
+    ) -> Vec<SchemaViolation> {
+        let mut checker = ValueChecker::new(components, &crate::format::BUILT_IN_FORMATS);
+        checker.reference(schema, value);
+        checker.violations
+    }
+
+    fn violation(&mut self, kind: SchemaViolationKind) {
+        self.violations.push(SchemaViolation {
+            path: self.at.as_str().to_owned(),
+            kind,
+        });
+    }
+
+    /// Runs `f` with `segment` appended to the location in the value.
+    fn nested<F: FnOnce(&mut Self)>(&mut self, segment: &str, f: F) {
+        let mut at = std::mem::take(&mut self.at);
+        at.with(segment, |at| {
+            std::mem::swap(&mut self.at, at);
+            f(self);
+            std::mem::swap(&mut self.at, at);
+        });
+        self.at = at;
+    }
+
+    /// Runs `f`, unless the schema of `reference` is already being checked
+    /// against the value at the current location, as it is when references
+    /// form a loop.
+    fn following<T, F: FnOnce(&mut Self)>(&mut self, reference: &ReferenceOr<T>, f: F) {
+        let key = match reference {
+            ReferenceOr::Reference { reference } => {
+                (reference.clone(), self.at.as_str().to_owned())
+            }
+            ReferenceOr::Item(_) => return f(self),
+        };
+        if self.checking.insert(key.clone()) {
+            f(self);
+            self.checking.remove(&key);
+        }
+    }
+
+    fn reference(&mut self, schema: &ReferenceOr<Schema>, value: &Value) {
+        match self.components.resolve(schema) {
+            Ok(resolved) => self.following(schema, |checker| checker.schema(resolved, value)),
+            Err(e) => self.violation(SchemaViolationKind::Reference(e)),
+        }
+    }
+
+    fn boxed(&mut self, schema: &ReferenceOr<Box<Schema>>, value: &Value) {
+        match self.components.resolve_boxed(schema) {
+            Ok(resolved) => self.following(schema, |checker| checker.schema(resolved, value)),
+            Err(e) => self.violation(SchemaViolationKind::Reference(e)),
+        }
+    }
+
+    /// Whether `value` conforms to `schema`, without reporting anything.
+    fn matches(&mut self, schema: &ReferenceOr<Schema>, value: &Value) -> bool {
+        let mut checker = ValueChecker {
+            at: self.at.clone(),
+            checking: std::mem::take(&mut self.checking),
+            patterns: std::mem::take(&mut self.patterns),
+            ..ValueChecker::new(self.components, self.formats)
+        };
+        checker.reference(schema, value);
+        self.checking = checker.checking;
+        self.patterns = checker.patterns;
+        checker.violations.is_empty()
+    }
+
+    fn schema(&mut self, schema: &Schema, value: &Value) {
+        if value.is_null() && schema.schema_data.nullable {
+            // The enumerations of typed schemas cannot list `null`, and so
+            // leave it out even of nullable ones.
+            let enumerated = match &schema.schema_kind {
+                SchemaKind::Type(Type::String(string)) => !string.enumeration.is_empty(),
+                SchemaKind::Type(Type::Number(number)) => !number.enumeration.is_empty(),
+                SchemaKind::Type(Type::Integer(integer)) => !integer.enumeration.is_empty(),
+                _ => false,
+            };
+            if enumerated {
+                self.violation(SchemaViolationKind::Enum);
+            }
+            return;
+        }
+        let format = match (&schema.schema_kind, value) {
+            (SchemaKind::Type(Type::String(string)), Value::String(_)) => {
+                string.format.name(StringFormat::as_str)
+            }
+            (SchemaKind::Type(Type::Number(number)), Value::Number(_)) => {
+                number.format.name(NumberFormat::as_str)
+            }
+            (SchemaKind::Type(Type::Integer(integer)), Value::Number(_)) => {
+                integer.format.name(IntegerFormat::as_str)
+            }
+            (SchemaKind::Any(any), _) => any.format.as_deref(),
+            _ => None,
+        };
+        if let Some(format) = format {
+            if self.formats.check(format, value) == Some(false) {
+                self.violation(SchemaViolationKind::Format(format.to_owned()));
+            }
+        }
+        match &schema.schema_kind {
+            SchemaKind::Type(Type::String(string)) => match value {
+                Value::String(s) => {
+                    if !string.enumeration.is_empty() && !string.enumeration.contains(s) {
+                        self.violation(SchemaViolationKind::Enum);
+                    }
+                    let format = string.format.name(StringFormat::as_str);
+                    self.string(
+                        s,
+                        StringRules {
+                            format,
+                            pattern: string.pattern.as_deref(),
+                            min_length: string.min_length,
+                            max_length: string.max_length,
+                        },
+                    );
+                }
+                _ => self.violation(SchemaViolationKind::Type { expected: "string" }),
+            },
+            SchemaKind::Type(Type::Number(number)) => match value.as_f64() {
+                Some(n) => {
+                    if !number.enumeration.is_empty() && !number.enumeration.contains(&n) {
+                        self.violation(SchemaViolationKind::Enum);
+                    }
+                    self.number(
+                        n,
+                        NumberRules {
+                            format: None,
+                            multiple_of: number.multiple_of,
+                            minimum: number.minimum,
+                            exclusive_minimum: number.exclusive_minimum,
+                            maximum: number.maximum,
+                            exclusive_maximum: number.exclusive_maximum,
+                        },
+                    );
+                }
+                None => self.violation(SchemaViolationKind::Type { expected: "number" }),
+            },
+            SchemaKind::Type(Type::Integer(integer)) => match value.as_f64() {
+                Some(n) if n.fract() == 0.0 => {
+                    let enumerated = integer.enumeration.iter().any(|&e| e as f64 == n);
+                    if !integer.enumeration.is_empty() && !enumerated {
+                        self.violation(SchemaViolationKind::Enum);
+                    }
+                    let format = integer.format.name(IntegerFormat::as_str);
+                    self.number(
+                        n,
+                        NumberRules {
+                            format,
+                            multiple_of: integer.multiple_of.map(|m| m as f64),
+                            minimum: integer.minimum.map(|m| m as f64),
+                            exclusive_minimum: integer.exclusive_minimum,
+                            maximum: integer.maximum.map(|m| m as f64),
+                            exclusive_maximum: integer.exclusive_maximum,
+                        },
+                    );
+                }
+                _ => self.violation(SchemaViolationKind::Type {
+                    expected: "integer",
+                }),
+            },
+            SchemaKind::Type(Type::Object(object)) => match value {
+                Value::Object(properties) => self.object(
+                    properties,
+                    ObjectRules {
+                        properties: &object.properties,
+                        required: &object.required,
+                        additional_properties: object.additional_properties.as_ref(),
+                        min_properties: object.min_properties,
+                        max_properties: object.max_properties,
+                    },
+                ),
+                _ => self.violation(SchemaViolationKind::Type { expected: "object" }),
+            },
+            SchemaKind::Type(Type::Array(array)) => match value {
+                Value::Array(items) => self.array(
+                    items,
+                    ArrayRules {
+                        items: Some(&array.items),
+                        min_items: array.min_items,
+                        max_items: array.max_items,
+                        unique_items: array.unique_items,
+                    },
+                ),
+                _ => self.violation(SchemaViolationKind::Type { expected: "array" }),
+            },
+            SchemaKind::Type(Type::Boolean {}) => {
+                if !value.is_boolean() {
+                    self.violation(SchemaViolationKind::Type {
+                        expected: "boolean",
+                    });
+                }
+            }
+            SchemaKind::OneOf { one_of } => {
+                let matched = one_of.iter().filter(|s| self.matches(s, value)).count();
+                if matched != 1 {
+                    self.violation(SchemaViolationKind::OneOf { matched });
+                }
+            }
+            SchemaKind::AllOf { all_of } => {
+                for schema in all_of {
+                    self.reference(schema, value);
+                }
+            }
+            SchemaKind::AnyOf { any_of } => {
+                if !any_of.iter().any(|s| self.matches(s, value)) {
+                    self.violation(SchemaViolationKind::AnyOf);
+                }
+            }
+            // Without a type, each keyword applies to the values it can.
+            SchemaKind::Any(any) => match value {
+                Value::String(s) => self.string(
+                    s,
+                    StringRules {
+                        format: any.format.as_deref(),
+                        pattern: any.pattern.as_deref(),
+                        min_length: any.min_length,
+                        max_length: any.max_length,
+                    },
+                ),
+                Value::Number(n) => self.number(
+                    n.as_f64().unwrap_or_default(),
+                    NumberRules {
+                        format: any.format.as_deref(),
+                        multiple_of: any.multiple_of,
+                        minimum: any.minimum,
+                        exclusive_minimum: any.exclusive_minimum.unwrap_or_default(),
+                        maximum: any.maximum,
+                        exclusive_maximum: any.exclusive_maximum.unwrap_or_default(),
+                    },
+                ),
+                Value::Object(properties) => self.object(
+                    properties,
+                    ObjectRules {
+                        properties: &any.properties,
+                        required: &any.required,
+                        additional_properties: any.additional_properties.as_ref(),
+                        min_properties: any.min_propeties,
+                        max_properties: any.max_properties,
+                    },
+                ),
+                Value::Array(items) => self.array(
+                    items,
+                    ArrayRules {
+                        items: any.items.as_ref(),
+                        min_items: any.min_items,
+                        max_items: any.max_items,
+                        unique_items: any.unique_items.unwrap_or_default(),
+                    },
+                ),
+                Value::Null | Value::Bool(_) => {}
+            },
+        }
+    }
+
+    fn string(&mut self, s: &str, rules: StringRules<'_>) {
+        let length = s.chars().count();
+        if let Some(min) = rules.min_length.filter(|&min| length < min) {
+            self.violation(SchemaViolationKind::MinLength(min));
+        }
+        if let Some(max) = rules.max_length.filter(|&max| length > max) {
+            self.violation(SchemaViolationKind::MaxLength(max));
+        }
+        if let Some(pattern) = rules.pattern {
+            let regex = self
+                .patterns
+                .entry(pattern.to_owned())
+                .or_insert_with(|| Regex::new(pattern).ok());
+            match regex {
+                Some(regex) if regex.is_match(s) => {}
+                Some(_) => self.violation(SchemaViolationKind::Pattern(pattern.to_owned())),
+                None => self.violation(SchemaViolationKind::InvalidPattern(pattern.to_owned())),
+            }
+        }
+        if let Some(format) = rules.format.filter(|format| !self.formats.checks(format)) {
+            let valid = match format {
+                "date" => is_date(s),
+                "date-time" => is_date_time(s),
+                "byte" => is_base64(s),
+                // Other formats, such as `password`, do not constrain the value.
+                _ => true,
+            };
+            if !valid {
+                self.violation(SchemaViolationKind::Format(format.to_owned()));
+            }
+        }
+    }
+
+    fn number(&mut self, n: f64, rules: NumberRules<'_>) {
+        if let Some(minimum) = rules.minimum {
+            if n < minimum || (rules.exclusive_minimum && n == minimum) {
+                self.violation(SchemaViolationKind::Minimum {
+                    minimum,
+                    exclusive: rules.exclusive_minimum,
+                });
+            }
+        }
+        if let Some(maximum) = rules.maximum {
+            if n > maximum || (rules.exclusive_maximum && n == maximum) {
+                self.violation(SchemaViolationKind::Maximum {
+                    maximum,
+                    exclusive: rules.exclusive_maximum,
+                });
+            }
+        }
+        if let Some(multiple_of) = rules.multiple_of {
+            let quotient = n / multiple_of;
+            if multiple_of > 0.0 && (quotient - quotient.round()).abs() > 1e-9 {
+                self.violation(SchemaViolationKind::MultipleOf(multiple_of));
+            }
+        }
+        let in_range = match rules.format.filter(|format| !self.formats.checks(format)) {
+            Some("int32") => i32::MIN as f64 <= n && n <= i32::MAX as f64,
+            Some("int64") => i64::MIN as f64 <= n && n <= i64::MAX as f64,
+            _ => true,
+        };
+        if !in_range {
+            self.violation(SchemaViolationKind::Format(
+                rules.format.unwrap().to_owned(),
+            ));
+        }
+    }
+
+    fn object(&mut self, object: &serde_json::Map<String, Value>, rules: ObjectRules<'_>) {
+        if let Some(min) = rules.min_properties.filter(|&min| object.len() < min) {
+            self.violation(SchemaViolationKind::MinProperties(min));
+        }
+        if let Some(max) = rules.max_properties.filter(|&max| object.len() > max) {
+            self.violation(SchemaViolationKind::MaxProperties(max));
+        }
+        for name in rules.required {
+            if !object.contains_key(name.as_str()) {
+                self.violation(SchemaViolationKind::MissingProperty(name.to_string()));
+            }
+        }
+        for (name, property) in object {
+            match (
+                rules.properties.get(name.as_str()),
+                rules.additional_properties,
+            ) {
+                (Some(schema), _) => self.nested(name, |checker| checker.boxed(schema, property)),
+                (None, Some(AdditionalProperties::None)) => {
+                    self.violation(SchemaViolationKind::AdditionalProperty(name.clone()))
+                }
+                (None, Some(AdditionalProperties::Schema(schema))) => {
+                    self.nested(name, |checker| checker.reference(schema, property))
+                }
+                (None, _) => {}
+            }
+        }
+    }
+
+    fn array(&mut self, array: &[Value], rules: ArrayRules<'_>) {
+        if let Some(min) = rules.min_items.filter(|&min| array.len() < min) {
+            self.violation(SchemaViolationKind::MinItems(min));
+        }
+        if let Some(max) = rules.max_items.filter(|&max| array.len() > max) {
+            self.violation(SchemaViolationKind::MaxItems(max));
+        }
+        if rules.unique_items
+            && array
+                .iter()
+                .enumerate()
+                .any(|(i, item)| array[..i].iter().any(|other| equal(item, other)))
+        {
+            self.violation(SchemaViolationKind::UniqueItems);
+        }
+        if let Some(items) = rules.items {
+            for (i, item) in array.iter().enumerate() {
+                self.nested(&i.to_string(), |checker| checker.boxed(items, item));
+            }
+        }
+    }
+}
+
+struct StringRules<'s> {
+    format: Option<&'s str>,
+    pattern: Option<&'s str>,
+    min_length: Option<usize>,
+    max_length: Option<usize>,
+}
+
+struct NumberRules<'s> {
+    format: Option<&'s str>,
+    multiple_of: Option<f64>,
+    minimum: Option<f64>,
+    exclusive_minimum: bool,
+    maximum: Option<f64>,
+    exclusive_maximum: bool,
+}
+
+struct ObjectRules<'s> {
+    properties: &'s Map<Name, ReferenceOr<Box<Schema>>>,
+    required: &'s [Name],
+    additional_properties: Option<&'s AdditionalProperties>,
+    min_properties: Option<usize>,
+    max_properties: Option<usize>,
+}
+
+struct ArrayRules<'s> {
+    items: Option<&'s ReferenceOr<Box<Schema>>>,
+    min_items: Option<usize>,
+    max_items: Option<usize>,
+    unique_items: bool,
+}
+
+fn digits(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// A `full-date` of RFC 3339, such as `2017-07-21`.
+fn is_date(s: &str) -> bool {
+    let parts: Vec<&str> = s.split('-').collect();
+    match parts[..] {
+        [year, month, day] if year.len() == 4 && month.len() == 2 && day.len() == 2 => {
+            digits(year)
+                && digits(month)
+                && digits(day)
+                && (1..=12).contains(&month.parse::<u8>().unwrap())
+                && (1..=31).contains(&day.parse::<u8>().unwrap())
+        }
+        _ => false,
+    }
+}
+
+/// A `date-time` of RFC 3339, such as `2017-07-21T17:32:28Z`.
+fn is_date_time(s: &str) -> bool {
+    let (date, time) = match s.find(['T', 't']) {
+        Some(i) => (&s[..i], &s[i + 1..]),
+        None => return false,
+    };
+    let (time, offset) = match time.find(['Z', 'z', '+', '-']) {
+        Some(i) => time.split_at(i),
+        None => return false,
+    };
+    let time = time.split('.').next().unwrap_or_default();
+    let hms: Vec<&str> = time.split(':').collect();
+    let valid_time = match hms[..] {
+        [h, m, s] => [h, m, s].iter().all(|part| part.len() == 2 && digits(part)),
+        _ => false,
+    };
+    let valid_offset = match offset {
+        "Z" | "z" => true,
+        _ => {
+            let hm: Vec<&str> = offset[1..].split(':').collect();
+            match hm[..] {
+                [h, m] => h.len() == 2 && m.len() == 2 && digits(h) && digits(m),
+                _ => false,
+            }
+        }
+    };
+    is_date(date) && valid_time && valid_offset
+}
+
+/// Whether two values are equal as JSON Schema has it, which compares
+/// numbers by value, so that `1` and `1.0` are the same.
+fn equal(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => a == b || a.as_f64() == b.as_f64(),
+        (Value::Array(a), Value::Array(b)) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| equal(a, b))
+        }
+        (Value::Object(a), Value::Object(b)) => {
+            a.len() == b.len()
+                && a.iter()
+                    .all(|(key, a)| b.get(key).is_some_and(|b| equal(a, b)))
+        }
+        _ => a == b,
+    }
+}
+
+/// Base64 encoded data, as for the `byte` format.
+fn is_base64(s: &str) -> bool {
+    let data = s.trim_end_matches('=');
+    s.len().is_multiple_of(4)
+        && s.len() - data.len() <= 2
+        && data
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'+' || b == b'/')
+}
+
+impl Schema {
+    /// Checks that `value` conforms to the schema, which must not contain
+    /// references; see [`Schema::validate_value_in`] for those that do.
+    ///
+    /// Implements the subset of JSON Schema used by OpenAPI: `type`,
+    /// `format`, `enum`, the bounds on numbers, strings, arrays and objects,
+    /// `pattern`, `required`, `properties`, `additionalProperties`, `items`,
+    /// `allOf`, `oneOf`, `anyOf` and `nullable`. Of the formats, `date`,
+    /// `date-time`, `byte`, `int32` and `int64` are checked.
+    pub fn validate_value(&self, value: &Value) -> Result<(), Vec<SchemaViolation>> {
+        self.validate_value_in(&Components::default(), value)
+    }
+
+    /// Like [`Schema::validate_value`], resolving references in `components`.
+    pub fn validate_value_in(
+        &self,
+        components: &Components,
+        value: &Value,
+    ) -> Result<(), Vec<SchemaViolation>> {
+        self.validate_value_with(components, &crate::format::BUILT_IN_FORMATS, value)
+    }
+
+    /// Like [`Schema::validate_value_in`], checking formats as `formats`
+    /// says.
+    pub fn validate_value_with(
+        &self,
+        components: &Components,
+        formats: &FormatRegistry,
+        value: &Value,
+    ) -> Result<(), Vec<SchemaViolation>> {
+        let mut checker = ValueChecker::new(components, formats);
+        checker.schema(self, value);
+        if checker.violations.is_empty() {
+            Ok(())
+        } else {
+            Err(checker.violations)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use serde_json::json;
+
+    #[test]
+    fn validates_values() {
+        let schema: Schema = serde_yaml::from_str(
+            "
+type: object
+required: [id, name]
+additionalProperties: false
+properties:
+  id: {type: integer, format: int32, minimum: 1}
+  name: {type: string, pattern: '^[A-Z]', maxLength: 5}
+  born: {type: string, format: date-time}
+  tags: {type: array, uniqueItems: true, items: {type: string, enum: [a, b]}}
+  weight: {type: number, multipleOf: 0.5, nullable: true}
+  owner: {oneOf: [{type: string}, {type: integer}]}
+",
+        )
+        .unwrap();
+
+        assert_eq!(
+            schema.validate_value(&json!({
+                "id": 3,
+                "name": "Rex",
+                "born": "2017-07-21T17:32:28+02:00",
+                "tags": ["a", "b"],
+                "weight": null,
+                "owner": 4,
+            })),
+            Ok(())
+        );
+
+        let violations: Vec<String> = schema
+            .validate_value(&json!({
+                "id": 4294967296u64,
+                "name": "rexanne",
+                "born": "yesterday",
+                "tags": ["a", "a", "c"],
+                "weight": 1.2,
+                "owner": true,
+                "age": 3,
+            }))
+            .unwrap_err()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            violations,
+            vec![
+                "property `age` is not allowed",
+                "/born: expected format `date-time`",
+                "/id: expected format `int32`",
+                "/name: must be at most 5 characters long",
+                "/name: must match `^[A-Z]`",
+                "/owner: matches 0 of the `oneOf` schemas instead of exactly one",
+                "/tags: items must be unique",
+                "/tags/2: value is not one of the enumerated values",
+                "/weight: must be a multiple of 0.5",
+            ]
+        );
+
+        let missing = schema.validate_value(&json!({})).unwrap_err();
+        assert_eq!(
+            missing[0].kind,
+            SchemaViolationKind::MissingProperty("id".to_owned())
+        );
+    }
+
+    #[test]
+    fn compares_numbers_by_value_and_enumerates_null() {
+        let unique: Schema = serde_yaml::from_str("{type: array, uniqueItems: true}").unwrap();
+        assert!(unique.validate_value(&json!([1, 1.0])).is_err());
+        assert!(unique
+            .validate_value(&json!([{"a": [2]}, {"a": [2.0]}]))
+            .is_err());
+        assert_eq!(unique.validate_value(&json!([1, 1.5, "1"])), Ok(()));
+
+        let nullable: Schema =
+            serde_yaml::from_str("{type: string, nullable: true, enum: [a, b]}").unwrap();
+        let violations = nullable.validate_value(&json!(null)).unwrap_err();
+        assert_eq!(violations[0].kind, SchemaViolationKind::Enum);
+        let nullable: Schema = serde_yaml::from_str("{type: string, nullable: true}").unwrap();
+        assert_eq!(nullable.validate_value(&json!(null)), Ok(()));
+    }
+
+    #[test]
+    fn stops_at_reference_loops() {
+        let components: Components = serde_yaml::from_str(
+            "
+schemas:
+  A: {allOf: [{$ref: '#/components/schemas/A'}, {type: object}]}
+  B: {anyOf: [{$ref: '#/components/schemas/B'}, {type: string, pattern: '^b'}]}
+  Tree:
+    type: object
+    properties: {children: {type: array, items: {$ref: '#/components/schemas/Tree'}}}
+",
+        )
+        .unwrap();
+        let check = |name: &str, value: serde_json::Value| {
+            let schema = ReferenceOr::ref_(&format!("#/components/schemas/{}", name));
+            crate::value::ValueChecker::check(&components, &schema, &value).len()
+        };
+        assert_eq!(check("A", json!({})), 0);
+        assert_eq!(check("A", json!(1)), 1);
+        assert_eq!(check("B", json!("b")), 0);
+        assert_eq!(check("Tree", json!({"children": [{"children": [1]}]})), 1);
+    }
+}
@@ -1,31 +1,32 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(untagged)]
 pub enum VariantOrUnknown<T> {
     Item(T),
     Unknown(String),
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
 #[serde(untagged)]
 pub enum VariantOrUnknownOrEmpty<T> {
     Item(T),
     Unknown(String),
+    #[default]
     Empty, // @todo this should serialize as nothing
 }
 
 impl<T> VariantOrUnknownOrEmpty<T> {
     pub fn is_empty(&self) -> bool {
-        match self {
-            VariantOrUnknownOrEmpty::Empty => true,
-            _ => false,
-        }
+        matches!(self, VariantOrUnknownOrEmpty::Empty)
     }
-}
 
-impl<T> Default for VariantOrUnknownOrEmpty<T> {
-    fn default() -> Self {
-        VariantOrUnknownOrEmpty::Empty
+    /// The name as written in documents, given the names of known variants.
+    pub(crate) fn name(&self, known: fn(&T) -> &'static str) -> Option<&str> {
+        match self {
+            VariantOrUnknownOrEmpty::Item(item) => Some(known(item)),
+            VariantOrUnknownOrEmpty::Unknown(name) => Some(name),
+            VariantOrUnknownOrEmpty::Empty => None,
+        }
     }
 }
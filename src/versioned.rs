@@ -0,0 +1,69 @@
+use crate::reference::stringify_keys;
+use crate::*;
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// A document of any supported version of the specification, told apart
+/// by its `openapi` field when deserializing.
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(untagged)]
+pub enum VersionedOpenAPI {
+    /// A 3.0.x document.
+    V3_0(OpenAPI),
+    /// A 3.1.x document.
+    V3_1(v3_1::OpenAPI),
+}
+
+impl VersionedOpenAPI {
+    /// The version of the specification the document uses, such as `3.1.0`.
+    pub fn version(&self) -> &str {
+        match self {
+            VersionedOpenAPI::V3_0(api) => &api.openapi,
+            VersionedOpenAPI::V3_1(api) => &api.openapi,
+        }
+    }
+}
+
+impl From<OpenAPI> for VersionedOpenAPI {
+    fn from(api: OpenAPI) -> Self {
+        VersionedOpenAPI::V3_0(api)
+    }
+}
+
+impl From<v3_1::OpenAPI> for VersionedOpenAPI {
+    fn from(api: v3_1::OpenAPI) -> Self {
+        VersionedOpenAPI::V3_1(api)
+    }
+}
+
+impl<'de> Deserialize<'de> for VersionedOpenAPI {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde_yaml::Value;
+
+        let mut value = Value::deserialize(deserializer)?;
+        stringify_keys(&mut value);
+        let version = match value.get("openapi") {
+            Some(Value::String(version)) => version.clone(),
+            Some(_) => return Err(D::Error::custom("`openapi` must be a string")),
+            None => return Err(D::Error::missing_field("openapi")),
+        };
+        if version.starts_with("3.0.") {
+            OpenAPI::deserialize(value)
+                .map(VersionedOpenAPI::V3_0)
+                .map_err(D::Error::custom)
+        } else if version.starts_with("3.1.") {
+            v3_1::OpenAPI::deserialize(value)
+                .map(VersionedOpenAPI::V3_1)
+                .map_err(D::Error::custom)
+        } else {
+            Err(D::Error::custom(format_args!(
+                "unsupported OpenAPI version `{}`",
+                version
+            )))
+        }
+    }
+}
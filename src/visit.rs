@@ -0,0 +1,917 @@
+//! Traversal of every object of a document.
+//!
+//! A [`Visitor`] is called with each object a [`Walker`] comes across,
+//! along with a JSON Pointer to where it is, so that tools need not each
+//! traverse the document by hand.
+//!
+//! ```
+//! use openapiv3::visit::{Visitor, Walker};
+//! use openapiv3::{OpenAPI, Operation};
+//!
+//! struct OperationIds(Vec<String>);
+//!
+//! impl Visitor<'_> for OperationIds {
+//!     fn visit_operation(&mut self, _location: &str, operation: &Operation) {
+//!         self.0.extend(operation.operation_id.clone());
+//!     }
+//! }
+//!
+//! let api: OpenAPI = serde_yaml::from_str(
+//!     "
+//! openapi: 3.0.0
+//! info: {title: t, version: '1'}
+//! paths:
+//!   /pets: {get: {operationId: listPets, responses: {}}}
+//! ",
+//! )
+//! .unwrap();
+//! let mut ids = OperationIds(Vec::new());
+//! Walker::new().walk(&api, &mut ids);
+//! assert_eq!(ids.0, vec!["listPets"]);
+//! ```
+//!
+//! A [`VisitMut`] is handed each object for modification instead, with
+//! [`Walker::walk_mut`]. [`OpenAPI::rewrite_refs`] builds on it to change
+//! references throughout a document.
+
+use crate::walk::Location;
+use crate::*;
+
+/// Receives the objects of a document as a [`Walker`] comes across them.
+///
+/// Every method does nothing unless overridden. Each is given a JSON
+/// Pointer to the object, such as `/paths/~1pets/get`, and is called before
+/// the objects within it are visited.
+#[allow(unused_variables)]
+pub trait Visitor<'a> {
+    fn visit_path_item(&mut self, location: &str, item: &'a PathItem) {}
+
+    fn visit_operation(&mut self, location: &str, operation: &'a Operation) {}
+
+    fn visit_parameter(&mut self, location: &str, parameter: &'a Parameter) {}
+
+    fn visit_request_body(&mut self, location: &str, request_body: &'a RequestBody) {}
+
+    fn visit_response(&mut self, location: &str, response: &'a Response) {}
+
+    fn visit_header(&mut self, location: &str, header: &'a Header) {}
+
+    fn visit_media_type(&mut self, location: &str, media_type: &'a MediaType) {}
+
+    /// Called with every schema, including those nested in other schemas.
+    fn visit_schema(&mut self, location: &str, schema: &'a Schema) {}
+
+    fn visit_example(&mut self, location: &str, example: &'a Example) {}
+
+    fn visit_link(&mut self, location: &str, link: &'a Link) {}
+
+    fn visit_security_scheme(&mut self, location: &str, scheme: &'a SecurityScheme) {}
+
+    fn visit_callback(&mut self, location: &str, callback: &'a Callback) {}
+
+    /// Called with every `$ref`, at the location of the object holding it.
+    fn visit_reference(&mut self, location: &str, reference: &'a Reference) {}
+}
+
+/// Walks a whole document, handing each of its objects to a [`Visitor`].
+///
+/// Paths are walked first, then components. By default references are not
+/// followed, so every object is visited once, where it is declared.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Walker {
+    follow_refs: bool,
+}
+
+impl Walker {
+    pub fn new() -> Self {
+        Walker::default()
+    }
+
+    /// Whether to also visit the component each reference points to, at the
+    /// location of the reference, as if it were declared there.
+    ///
+    /// A component is then visited once for each use of it, along with its
+    /// declaration. A reference is not followed within the component it
+    /// points to, nor when it cannot be resolved.
+    pub fn follow_refs(mut self, follow_refs: bool) -> Self {
+        self.follow_refs = follow_refs;
+        self
+    }
+
+    pub fn walk<'a, V: Visitor<'a>>(&self, api: &'a OpenAPI, visitor: &mut V) {
+        let mut driver = Driver {
+            api,
+            visitor,
+            follow_refs: self.follow_refs,
+            following: Vec::new(),
+        };
+        let at = &mut Location::default();
+        at.with("paths", |at| {
+            for (path, item) in &api.paths {
+                at.with(path, |at| item.accept(&mut driver, at));
+            }
+        });
+        if let Some(components) = &api.components {
+            at.with("components", |at| components.accept(&mut driver, at));
+        }
+    }
+}
+
+impl OpenAPI {
+    /// Hands every object of the document to `visitor`, without following
+    /// references; see [`Walker`].
+    pub fn visit<'a, V: Visitor<'a>>(&'a self, visitor: &mut V) {
+        Walker::new().walk(self, visitor)
+    }
+
+    /// Iterates over every schema of the document, along with a JSON
+    /// Pointer to it: those of components, and those declared inline in
+    /// parameters, bodies, headers and other schemas, such as properties
+    /// and array items.
+    ///
+    /// References are not followed, so each schema is yielded once.
+    ///
+    /// ```
+    /// let api: openapiv3::OpenAPI = serde_yaml::from_str(
+    ///     "
+    /// openapi: 3.0.0
+    /// info: {title: t, version: '1'}
+    /// paths:
+    ///   /pets:
+    ///     get:
+    ///       parameters: [{name: limit, in: query, schema: {type: integer}}]
+    ///       responses: {}
+    /// components:
+    ///   schemas:
+    ///     Pets: {type: array, items: {type: string}}
+    /// ",
+    /// )
+    /// .unwrap();
+    /// let locations: Vec<_> = api.all_schemas().map(|(location, _)| location).collect();
+    /// assert_eq!(
+    ///     locations,
+    ///     vec![
+    ///         "/paths/~1pets/get/parameters/0/schema",
+    ///         "/components/schemas/Pets",
+    ///         "/components/schemas/Pets/items",
+    ///     ]
+    /// );
+    /// ```
+    pub fn all_schemas(&self) -> impl Iterator<Item = (String, &Schema)> {
+        let mut schemas = Schemas(Vec::new());
+        self.visit(&mut schemas);
+        schemas.0.into_iter()
+    }
+}
+
+struct Schemas<'a>(Vec<(String, &'a Schema)>);
+
+impl<'a> Visitor<'a> for Schemas<'a> {
+    fn visit_schema(&mut self, location: &str, schema: &'a Schema) {
+        self.0.push((location.to_owned(), schema));
+    }
+}
+
+struct Driver<'a, 'v, V> {
+    api: &'a OpenAPI,
+    visitor: &'v mut V,
+    follow_refs: bool,
+    /// The references being followed, innermost last.
+    following: Vec<String>,
+}
+
+impl<'a, 'v, V: Visitor<'a>> Driver<'a, 'v, V> {
+    fn reference<T: Node + 'a>(&mut self, reference: &'a Reference, at: &mut Location) {
+        self.visitor.visit_reference(at.as_str(), reference);
+        if !self.follow_refs || self.following.iter().any(|r| r == reference.as_str()) {
+            return;
+        }
+        if let Some(target) = T::resolve(self.api, reference) {
+            self.following.push(reference.as_str().to_owned());
+            target.accept(self, at);
+            self.following.pop();
+        }
+    }
+
+    /// Visits the values of a map, each under its key.
+    fn each<K: AsRef<str> + 'a, T: Node + 'a>(
+        &mut self,
+        map: impl IntoIterator<Item = (&'a K, &'a T)>,
+        at: &mut Location,
+    ) {
+        for (key, value) in map {
+            at.with(key.as_ref(), |at| value.accept(self, at));
+        }
+    }
+
+    /// Visits the components of a section of the Components Object, not
+    /// following references back to the component being visited.
+    fn section<T: Node>(
+        &mut self,
+        kind: ComponentKind,
+        section: &'a Map<String, ReferenceOr<T>>,
+        at: &mut Location,
+    ) {
+        at.with(kind.as_str(), |at| {
+            for (name, component) in section {
+                let reference = Reference::component(kind, name);
+                self.following.push(reference.as_str().to_owned());
+                at.with(name, |at| component.accept(self, at));
+                self.following.pop();
+            }
+        });
+    }
+
+    /// Visits a schema nested in another, which is held in a box.
+    fn boxed(&mut self, schema: &'a ReferenceOr<Box<Schema>>, at: &mut Location) {
+        match schema {
+            ReferenceOr::Item(schema) => schema.accept(self, at),
+            ReferenceOr::Reference { reference } => self.reference::<Schema>(reference, at),
+        }
+    }
+
+    fn properties(
+        &mut self,
+        properties: &'a Map<Name, ReferenceOr<Box<Schema>>>,
+        at: &mut Location,
+    ) {
+        at.with("properties", |at| {
+            for (name, property) in properties {
+                at.with(name, |at| self.boxed(property, at));
+            }
+        });
+    }
+}
+
+/// An object a [`Driver`] can descend into.
+trait Node {
+    fn accept<'a, V: Visitor<'a>>(&'a self, d: &mut Driver<'a, '_, V>, at: &mut Location);
+
+    /// Looks up a reference to an object of this type, if it can be.
+    fn resolve<'a>(_api: &'a OpenAPI, _reference: &Reference) -> Option<&'a Self> {
+        None
+    }
+}
+
+impl<T: Node> Node for ReferenceOr<T> {
+    fn accept<'a, V: Visitor<'a>>(&'a self, d: &mut Driver<'a, '_, V>, at: &mut Location) {
+        match self {
+            ReferenceOr::Item(item) => item.accept(d, at),
+            ReferenceOr::Reference { reference } => d.reference::<T>(reference, at),
+        }
+    }
+}
+
+impl<T: Node> Node for Option<T> {
+    fn accept<'a, V: Visitor<'a>>(&'a self, d: &mut Driver<'a, '_, V>, at: &mut Location) {
+        if let Some(value) = self {
+            value.accept(d, at);
+        }
+    }
+}
+
+impl<T: Node> Node for Vec<T> {
+    fn accept<'a, V: Visitor<'a>>(&'a self, d: &mut Driver<'a, '_, V>, at: &mut Location) {
+        for (i, value) in self.iter().enumerate() {
+            at.with(&i.to_string(), |at| value.accept(d, at));
+        }
+    }
+}
+
+/// Implements `Node` for types that can be referenced as components,
+/// calling the given visitor method before descending with the given body.
+macro_rules! impl_component_node {
+    ($ty:ty, $visit:ident, |$self:ident, $d:ident, $at:ident| $body:block) => {
+        impl Node for $ty {
+            fn accept<'a, V: Visitor<'a>>(&'a $self, $d: &mut Driver<'a, '_, V>, $at: &mut Location) {
+                $d.visitor.$visit($at.as_str(), $self);
+                $body
+            }
+
+            fn resolve<'a>(api: &'a OpenAPI, reference: &Reference) -> Option<&'a Self> {
+                api.resolve_reference(reference).ok()
+            }
+        }
+    };
+}
+
+impl Node for Components {
+    fn accept<'a, V: Visitor<'a>>(&'a self, d: &mut Driver<'a, '_, V>, at: &mut Location) {
+        d.section(ComponentKind::SecuritySchemes, &self.security_schemes, at);
+        d.section(ComponentKind::Responses, &self.responses, at);
+        d.section(ComponentKind::Parameters, &self.parameters, at);
+        d.section(ComponentKind::Examples, &self.examples, at);
+        d.section(ComponentKind::RequestBodies, &self.request_bodies, at);
+        d.section(ComponentKind::Headers, &self.headers, at);
+        d.section(ComponentKind::Schemas, &self.schemas, at);
+        d.section(ComponentKind::Links, &self.links, at);
+        d.section(ComponentKind::Callbacks, &self.callbacks, at);
+    }
+}
+
+impl Node for PathItem {
+    fn accept<'a, V: Visitor<'a>>(&'a self, d: &mut Driver<'a, '_, V>, at: &mut Location) {
+        d.visitor.visit_path_item(at.as_str(), self);
+        for (method, operation) in self.operations() {
+            at.with(method.as_str(), |at| operation.accept(d, at));
+        }
+        at.with("parameters", |at| self.parameters.accept(d, at));
+    }
+}
+
+impl Node for Operation {
+    fn accept<'a, V: Visitor<'a>>(&'a self, d: &mut Driver<'a, '_, V>, at: &mut Location) {
+        d.visitor.visit_operation(at.as_str(), self);
+        at.with("parameters", |at| self.parameters.accept(d, at));
+        at.with("requestBody", |at| self.request_body.accept(d, at));
+        at.with("responses", |at| {
+            at.with("default", |at| self.responses.default.accept(d, at));
+            for (status, response) in &self.responses.responses {
+                at.with(&status.to_string(), |at| response.accept(d, at));
+            }
+        });
+        at.with("callbacks", |at| d.each(&self.callbacks, at));
+    }
+}
+
+impl Node for MediaType {
+    fn accept<'a, V: Visitor<'a>>(&'a self, d: &mut Driver<'a, '_, V>, at: &mut Location) {
+        d.visitor.visit_media_type(at.as_str(), self);
+        at.with("schema", |at| self.schema.accept(d, at));
+        at.with("examples", |at| d.each(&self.examples, at));
+        at.with("encoding", |at| {
+            for (name, encoding) in &self.encoding {
+                at.with(name, |at| {
+                    at.with("headers", |at| d.each(&encoding.headers, at));
+                });
+            }
+        });
+    }
+}
+
+impl Node for ParameterSchemaOrContent {
+    fn accept<'a, V: Visitor<'a>>(&'a self, d: &mut Driver<'a, '_, V>, at: &mut Location) {
+        match self {
+            ParameterSchemaOrContent::Schema(schema) => {
+                at.with("schema", |at| schema.accept(d, at))
+            }
+            ParameterSchemaOrContent::Content(content) => {
+                at.with("content", |at| d.each(content, at))
+            }
+        }
+    }
+}
+
+impl_component_node!(Parameter, visit_parameter, |self, d, at| {
+    let data = self.parameter_data_ref();
+    data.format.accept(d, at);
+    at.with("examples", |at| d.each(&data.examples, at));
+});
+
+impl_component_node!(RequestBody, visit_request_body, |self, d, at| {
+    at.with("content", |at| d.each(&self.content, at));
+});
+
+impl_component_node!(Response, visit_response, |self, d, at| {
+    at.with("headers", |at| d.each(&self.headers, at));
+    at.with("content", |at| d.each(&self.content, at));
+    at.with("links", |at| d.each(&self.links, at));
+});
+
+impl_component_node!(Header, visit_header, |self, d, at| {
+    self.format.accept(d, at);
+    at.with("examples", |at| d.each(&self.examples, at));
+});
+
+impl_component_node!(Schema, visit_schema, |self, d, at| {
+    match &self.schema_kind {
+        SchemaKind::Type(Type::Object(object)) => {
+            d.properties(&object.properties, at);
+            at.with("additionalProperties", |at| {
+                object.additional_properties.accept(d, at)
+            });
+        }
+        SchemaKind::Type(Type::Array(array)) => at.with("items", |at| d.boxed(&array.items, at)),
+        SchemaKind::Type(_) => {}
+        SchemaKind::OneOf { one_of } => at.with("oneOf", |at| one_of.accept(d, at)),
+        SchemaKind::AllOf { all_of } => at.with("allOf", |at| all_of.accept(d, at)),
+        SchemaKind::AnyOf { any_of } => at.with("anyOf", |at| any_of.accept(d, at)),
+        SchemaKind::Any(any) => {
+            d.properties(&any.properties, at);
+            if let Some(items) = &any.items {
+                at.with("items", |at| d.boxed(items, at));
+            }
+            at.with("additionalProperties", |at| {
+                any.additional_properties.accept(d, at)
+            });
+        }
+    }
+});
+
+impl Node for AdditionalProperties {
+    fn accept<'a, V: Visitor<'a>>(&'a self, d: &mut Driver<'a, '_, V>, at: &mut Location) {
+        match self {
+            AdditionalProperties::Any | AdditionalProperties::None => {}
+            AdditionalProperties::Schema(schema) => (**schema).accept(d, at),
+        }
+    }
+}
+
+impl_component_node!(Callback, visit_callback, |self, d, at| {
+    for (expression, item) in self {
+        at.with(expression, |at| item.accept(d, at));
+    }
+});
+
+impl_component_node!(Example, visit_example, |self, d, at| {});
+impl_component_node!(Link, visit_link, |self, d, at| {});
+impl_component_node!(SecurityScheme, visit_security_scheme, |self, d, at| {});
+
+/// Receives the objects of a document for modification, as
+/// [`Walker::walk_mut`] comes across them.
+///
+/// Like [`Visitor`], every method does nothing unless overridden, and each
+/// is called before the objects within what it is given are visited, so
+/// that objects it adds are visited too.
+#[allow(unused_variables)]
+pub trait VisitMut {
+    fn visit_path_item_mut(&mut self, location: &str, item: &mut PathItem) {}
+
+    fn visit_operation_mut(&mut self, location: &str, operation: &mut Operation) {}
+
+    fn visit_parameter_mut(&mut self, location: &str, parameter: &mut Parameter) {}
+
+    fn visit_request_body_mut(&mut self, location: &str, request_body: &mut RequestBody) {}
+
+    fn visit_response_mut(&mut self, location: &str, response: &mut Response) {}
+
+    fn visit_header_mut(&mut self, location: &str, header: &mut Header) {}
+
+    fn visit_media_type_mut(&mut self, location: &str, media_type: &mut MediaType) {}
+
+    fn visit_schema_mut(&mut self, location: &str, schema: &mut Schema) {}
+
+    fn visit_example_mut(&mut self, location: &str, example: &mut Example) {}
+
+    fn visit_link_mut(&mut self, location: &str, link: &mut Link) {}
+
+    fn visit_security_scheme_mut(&mut self, location: &str, scheme: &mut SecurityScheme) {}
+
+    fn visit_callback_mut(&mut self, location: &str, callback: &mut Callback) {}
+
+    /// Called with every `$ref`, at the location of the object holding it.
+    fn visit_reference_mut(&mut self, location: &str, reference: &mut Reference) {}
+}
+
+impl Walker {
+    /// Walks a document for modification, handing each of its objects to
+    /// `visitor`.
+    ///
+    /// References are never followed, whatever [`Walker::follow_refs`] is
+    /// set to, as their targets are visited for modification where they
+    /// are declared.
+    pub fn walk_mut<V: VisitMut>(&self, api: &mut OpenAPI, visitor: &mut V) {
+        let d = &mut DriverMut { visitor };
+        let at = &mut Location::default();
+        at.with("paths", |at| d.each(&mut api.paths, at));
+        if let Some(components) = &mut api.components {
+            at.with("components", |at| components.accept_mut(d, at));
+        }
+    }
+}
+
+impl OpenAPI {
+    /// Hands every object of the document to `visitor` for modification;
+    /// see [`Walker::walk_mut`].
+    pub fn visit_mut<V: VisitMut>(&mut self, visitor: &mut V) {
+        Walker::new().walk_mut(self, visitor)
+    }
+
+    /// Replaces every reference for which `f` returns a new one, including
+    /// those in the `mapping` of discriminators.
+    ///
+    /// ```
+    /// use openapiv3::{OpenAPI, Reference};
+    ///
+    /// let mut api: OpenAPI = serde_yaml::from_str(
+    ///     "
+    /// openapi: 3.0.0
+    /// info: {title: t, version: '1'}
+    /// paths: {}
+    /// components:
+    ///   schemas:
+    ///     Pets: {type: array, items: {$ref: '#/components/schemas/Pet'}}
+    /// ",
+    /// )
+    /// .unwrap();
+    /// api.rewrite_refs(|reference| {
+    ///     reference.as_str().strip_prefix("#/components/schemas/").map(|name| {
+    ///         format!("./schemas.yaml#/{}", name).parse::<Reference>().unwrap()
+    ///     })
+    /// });
+    /// let text = serde_yaml::to_string(&api).unwrap();
+    /// assert!(text.contains("./schemas.yaml#/Pet"));
+    /// ```
+    pub fn rewrite_refs<F: FnMut(&Reference) -> Option<Reference>>(&mut self, f: F) {
+        self.visit_mut(&mut RewriteRefs(f));
+    }
+
+    /// Replaces every reference to `from` with one to `to`; see
+    /// [`OpenAPI::rewrite_refs`].
+    pub fn replace_ref(&mut self, from: &Reference, to: &Reference) {
+        self.rewrite_refs(|reference| Some(to.clone()).filter(|_| reference == from));
+    }
+}
+
+/// Rewrites references with a function; see [`OpenAPI::rewrite_refs`].
+pub struct RewriteRefs<F>(pub F);
+
+impl<F: FnMut(&Reference) -> Option<Reference>> VisitMut for RewriteRefs<F> {
+    fn visit_schema_mut(&mut self, _: &str, schema: &mut Schema) {
+        let mapping = schema
+            .schema_data
+            .discriminator
+            .iter_mut()
+            .flat_map(|discriminator| discriminator.mapping.values_mut());
+        for target in mapping {
+            // Plain schema names, without a `#` or `/`, are left as they are.
+            if !target.contains(['#', '/']) {
+                continue;
+            }
+            if let Ok(reference) = target.parse::<Reference>() {
+                if let Some(new) = (self.0)(&reference) {
+                    *target = new.as_str().to_owned();
+                }
+            }
+        }
+    }
+
+    fn visit_reference_mut(&mut self, _: &str, reference: &mut Reference) {
+        if let Some(new) = (self.0)(reference) {
+            *reference = new;
+        }
+    }
+}
+
+struct DriverMut<'a, V> {
+    visitor: &'a mut V,
+}
+
+impl<'a, V: VisitMut> DriverMut<'a, V> {
+    fn each<'m, K: AsRef<str> + 'm, T: NodeMut + 'm>(
+        &mut self,
+        map: impl IntoIterator<Item = (&'m K, &'m mut T)>,
+        at: &mut Location,
+    ) {
+        for (key, value) in map {
+            at.with(key.as_ref(), |at| value.accept_mut(self, at));
+        }
+    }
+
+    fn boxed(&mut self, schema: &mut ReferenceOr<Box<Schema>>, at: &mut Location) {
+        match schema {
+            ReferenceOr::Item(schema) => schema.accept_mut(self, at),
+            ReferenceOr::Reference { reference } => {
+                self.visitor.visit_reference_mut(at.as_str(), reference)
+            }
+        }
+    }
+
+    fn properties(
+        &mut self,
+        properties: &mut Map<Name, ReferenceOr<Box<Schema>>>,
+        at: &mut Location,
+    ) {
+        at.with("properties", |at| {
+            for (name, property) in properties {
+                at.with(name, |at| self.boxed(property, at));
+            }
+        });
+    }
+}
+
+/// An object a [`DriverMut`] can descend into.
+trait NodeMut {
+    fn accept_mut<V: VisitMut>(&mut self, d: &mut DriverMut<'_, V>, at: &mut Location);
+}
+
+impl<T: NodeMut> NodeMut for ReferenceOr<T> {
+    fn accept_mut<V: VisitMut>(&mut self, d: &mut DriverMut<'_, V>, at: &mut Location) {
+        match self {
+            ReferenceOr::Item(item) => item.accept_mut(d, at),
+            ReferenceOr::Reference { reference } => {
+                d.visitor.visit_reference_mut(at.as_str(), reference)
+            }
+        }
+    }
+}
+
+impl<T: NodeMut> NodeMut for Option<T> {
+    fn accept_mut<V: VisitMut>(&mut self, d: &mut DriverMut<'_, V>, at: &mut Location) {
+        if let Some(value) = self {
+            value.accept_mut(d, at);
+        }
+    }
+}
+
+impl<T: NodeMut> NodeMut for Vec<T> {
+    fn accept_mut<V: VisitMut>(&mut self, d: &mut DriverMut<'_, V>, at: &mut Location) {
+        for (i, value) in self.iter_mut().enumerate() {
+            at.with(&i.to_string(), |at| value.accept_mut(d, at));
+        }
+    }
+}
+
+/// Implements `NodeMut`, calling the given visitor method before
+/// descending with the given body.
+macro_rules! impl_node_mut {
+    ($ty:ty, $visit:ident, |$self:ident, $d:ident, $at:ident| $body:block) => {
+        impl NodeMut for $ty {
+            fn accept_mut<V: VisitMut>(&mut $self, $d: &mut DriverMut<'_, V>, $at: &mut Location) {
+                $d.visitor.$visit($at.as_str(), $self);
+                $body
+            }
+        }
+    };
+}
+
+impl NodeMut for Components {
+    fn accept_mut<V: VisitMut>(&mut self, d: &mut DriverMut<'_, V>, at: &mut Location) {
+        at.with("securitySchemes", |at| {
+            d.each(&mut self.security_schemes, at)
+        });
+        at.with("responses", |at| d.each(&mut self.responses, at));
+        at.with("parameters", |at| d.each(&mut self.parameters, at));
+        at.with("examples", |at| d.each(&mut self.examples, at));
+        at.with("requestBodies", |at| d.each(&mut self.request_bodies, at));
+        at.with("headers", |at| d.each(&mut self.headers, at));
+        at.with("schemas", |at| d.each(&mut self.schemas, at));
+        at.with("links", |at| d.each(&mut self.links, at));
+        at.with("callbacks", |at| d.each(&mut self.callbacks, at));
+    }
+}
+
+impl_node_mut!(PathItem, visit_path_item_mut, |self, d, at| {
+    for method in Method::ALL {
+        let operation = self.operation_mut(method);
+        at.with(method.as_str(), |at| operation.accept_mut(d, at));
+    }
+    at.with("parameters", |at| self.parameters.accept_mut(d, at));
+});
+
+impl_node_mut!(Operation, visit_operation_mut, |self, d, at| {
+    at.with("parameters", |at| self.parameters.accept_mut(d, at));
+    at.with("requestBody", |at| self.request_body.accept_mut(d, at));
+    at.with("responses", |at| {
+        at.with("default", |at| self.responses.default.accept_mut(d, at));
+        for (status, response) in &mut self.responses.responses {
+            at.with(&status.to_string(), |at| response.accept_mut(d, at));
+        }
+    });
+    at.with("callbacks", |at| d.each(&mut self.callbacks, at));
+});
+
+impl_node_mut!(MediaType, visit_media_type_mut, |self, d, at| {
+    at.with("schema", |at| self.schema.accept_mut(d, at));
+    at.with("examples", |at| d.each(&mut self.examples, at));
+    at.with("encoding", |at| {
+        for (name, encoding) in &mut self.encoding {
+            at.with(name, |at| {
+                at.with("headers", |at| d.each(&mut encoding.headers, at));
+            });
+        }
+    });
+});
+
+impl NodeMut for ParameterSchemaOrContent {
+    fn accept_mut<V: VisitMut>(&mut self, d: &mut DriverMut<'_, V>, at: &mut Location) {
+        match self {
+            ParameterSchemaOrContent::Schema(schema) => {
+                at.with("schema", |at| schema.accept_mut(d, at))
+            }
+            ParameterSchemaOrContent::Content(content) => {
+                at.with("content", |at| d.each(content, at))
+            }
+        }
+    }
+}
+
+impl_node_mut!(Parameter, visit_parameter_mut, |self, d, at| {
+    let data = self.parameter_data_mut();
+    data.format.accept_mut(d, at);
+    at.with("examples", |at| d.each(&mut data.examples, at));
+});
+
+impl_node_mut!(RequestBody, visit_request_body_mut, |self, d, at| {
+    at.with("content", |at| d.each(&mut self.content, at));
+});
+
+impl_node_mut!(Response, visit_response_mut, |self, d, at| {
+    at.with("headers", |at| d.each(&mut self.headers, at));
+    at.with("content", |at| d.each(&mut self.content, at));
+    at.with("links", |at| d.each(&mut self.links, at));
+});
+
+impl_node_mut!(Header, visit_header_mut, |self, d, at| {
+    self.format.accept_mut(d, at);
+    at.with("examples", |at| d.each(&mut self.examples, at));
+});
+
+impl_node_mut!(Schema, visit_schema_mut, |self, d, at| {
+    match &mut self.schema_kind {
+        SchemaKind::Type(Type::Object(object)) => {
+            d.properties(&mut object.properties, at);
+            at.with("additionalProperties", |at| {
+                object.additional_properties.accept_mut(d, at)
+            });
+        }
+        SchemaKind::Type(Type::Array(array)) => {
+            at.with("items", |at| d.boxed(&mut array.items, at))
+        }
+        SchemaKind::Type(_) => {}
+        SchemaKind::OneOf { one_of } => at.with("oneOf", |at| one_of.accept_mut(d, at)),
+        SchemaKind::AllOf { all_of } => at.with("allOf", |at| all_of.accept_mut(d, at)),
+        SchemaKind::AnyOf { any_of } => at.with("anyOf", |at| any_of.accept_mut(d, at)),
+        SchemaKind::Any(any) => {
+            d.properties(&mut any.properties, at);
+            if let Some(items) = &mut any.items {
+                at.with("items", |at| d.boxed(items, at));
+            }
+            at.with("additionalProperties", |at| {
+                any.additional_properties.accept_mut(d, at)
+            });
+        }
+    }
+});
+
+impl NodeMut for AdditionalProperties {
+    fn accept_mut<V: VisitMut>(&mut self, d: &mut DriverMut<'_, V>, at: &mut Location) {
+        match self {
+            AdditionalProperties::Any | AdditionalProperties::None => {}
+            AdditionalProperties::Schema(schema) => (**schema).accept_mut(d, at),
+        }
+    }
+}
+
+impl_node_mut!(Callback, visit_callback_mut, |self, d, at| {
+    for (expression, item) in self.iter_mut() {
+        at.with(expression, |at| item.accept_mut(d, at));
+    }
+});
+
+impl_node_mut!(Example, visit_example_mut, |self, d, at| {});
+impl_node_mut!(Link, visit_link_mut, |self, d, at| {});
+impl_node_mut!(SecurityScheme, visit_security_scheme_mut, |self, d, at| {});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Record(Vec<String>);
+
+    impl Visitor<'_> for Record {
+        fn visit_operation(&mut self, location: &str, _: &Operation) {
+            self.0.push(format!("operation {}", location));
+        }
+
+        fn visit_schema(&mut self, location: &str, _: &Schema) {
+            self.0.push(format!("schema {}", location));
+        }
+
+        fn visit_reference(&mut self, location: &str, reference: &Reference) {
+            self.0
+                .push(format!("reference {} {}", location, reference.as_str()));
+        }
+    }
+
+    #[test]
+    fn visits_every_object() {
+        let api: OpenAPI = serde_yaml::from_str(
+            "
+openapi: 3.0.0
+info: {title: t, version: '1'}
+paths:
+  /pets:
+    get:
+      responses:
+        200:
+          description: ok
+          content:
+            application/json: {schema: {$ref: '#/components/schemas/Pet'}}
+components:
+  schemas:
+    Pet:
+      type: object
+      properties:
+        parent: {$ref: '#/components/schemas/Pet'}
+        tags: {type: array, items: {type: string}}
+",
+        )
+        .unwrap();
+
+        let mut record = Record::default();
+        api.visit(&mut record);
+        assert_eq!(
+            record.0,
+            vec![
+                "operation /paths/~1pets/get",
+                "reference /paths/~1pets/get/responses/200/content/application~1json/schema \
+                 #/components/schemas/Pet",
+                "schema /components/schemas/Pet",
+                "reference /components/schemas/Pet/properties/parent #/components/schemas/Pet",
+                "schema /components/schemas/Pet/properties/tags",
+                "schema /components/schemas/Pet/properties/tags/items",
+            ]
+        );
+
+        let mut record = Record::default();
+        Walker::new().follow_refs(true).walk(&api, &mut record);
+        let schema = "/paths/~1pets/get/responses/200/content/application~1json/schema";
+        assert_eq!(
+            record.0[..6],
+            [
+                "operation /paths/~1pets/get".to_owned(),
+                format!("reference {} #/components/schemas/Pet", schema),
+                format!("schema {}", schema),
+                format!(
+                    "reference {}/properties/parent #/components/schemas/Pet",
+                    schema
+                ),
+                format!("schema {}/properties/tags", schema),
+                format!("schema {}/properties/tags/items", schema),
+            ]
+        );
+        assert_eq!(record.0.len(), 10);
+    }
+
+    struct AddHeader;
+
+    impl VisitMut for AddHeader {
+        fn visit_operation_mut(&mut self, _: &str, operation: &mut Operation) {
+            operation
+                .parameters
+                .push(ReferenceOr::ref_("#/components/parameters/RequestId"));
+        }
+    }
+
+    #[test]
+    fn modifies_every_object() {
+        let mut api: OpenAPI = serde_yaml::from_str(
+            "
+openapi: 3.0.0
+info: {title: t, version: '1'}
+paths:
+  /pets:
+    get:
+      responses:
+        200:
+          description: ok
+          content:
+            application/json: {schema: {$ref: '#/components/schemas/Pet'}}
+    post:
+      responses: {}
+components:
+  schemas:
+    Pet:
+      properties:
+        parent: {$ref: '#/components/schemas/Pet'}
+      discriminator:
+        propertyName: kind
+        mapping: {dog: '#/components/schemas/Pet', cat: Cat}
+",
+        )
+        .unwrap();
+
+        api.visit_mut(&mut AddHeader);
+        api.replace_ref(
+            &"#/components/schemas/Pet".parse().unwrap(),
+            &"#/components/schemas/Animal".parse().unwrap(),
+        );
+        let mut record = Record::default();
+        api.visit(&mut record);
+        assert_eq!(
+            record.0,
+            vec![
+                "operation /paths/~1pets/get",
+                "reference /paths/~1pets/get/parameters/0 #/components/parameters/RequestId",
+                "reference /paths/~1pets/get/responses/200/content/application~1json/schema \
+                 #/components/schemas/Animal",
+                "operation /paths/~1pets/post",
+                "reference /paths/~1pets/post/parameters/0 #/components/parameters/RequestId",
+                "schema /components/schemas/Pet",
+                "reference /components/schemas/Pet/properties/parent \
+                 #/components/schemas/Animal",
+            ]
+        );
+        let schemas = &api.components.as_ref().unwrap().schemas;
+        let mapping = match &schemas["Pet"] {
+            ReferenceOr::Item(schema) => {
+                &schema.schema_data.discriminator.as_ref().unwrap().mapping
+            }
+            ReferenceOr::Reference { .. } => panic!("expected a schema"),
+        };
+        assert_eq!(mapping["dog"], "#/components/schemas/Animal");
+        assert_eq!(mapping["cat"], "Cat");
+    }
+}
@@ -0,0 +1,551 @@
+//! Internal traversal of every place in the model that may hold a reference.
+
+use crate::reference::escape_pointer_segment;
+use crate::*;
+use serde::de::DeserializeOwned;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Receives every `ReferenceOr` found while walking the document.
+/// Implementations decide whether to descend into items themselves,
+/// usually by calling [`Walk::walk`] on them.
+pub(crate) trait VisitRefs {
+    type Error;
+
+    fn reference<T: Referenceable>(
+        &mut self,
+        value: &mut ReferenceOr<T>,
+    ) -> Result<(), Self::Error>;
+
+    fn boxed<T: Referenceable>(
+        &mut self,
+        value: &mut ReferenceOr<Box<T>>,
+    ) -> Result<(), Self::Error> {
+        let placeholder = ReferenceOr::Reference {
+            reference: Reference::placeholder(),
+        };
+        let mut unboxed = std::mem::replace(value, placeholder).unbox();
+        let result = self.reference(&mut unboxed);
+        *value = match unboxed {
+            ReferenceOr::Reference { reference } => ReferenceOr::Reference { reference },
+            ReferenceOr::Item(item) => ReferenceOr::Item(Box::new(item)),
+        };
+        result
+    }
+
+    /// Receives each target of a discriminator `mapping`: the name of a
+    /// schema or a reference to one.
+    fn mapping(&mut self, _target: &mut String) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// A type that may appear as the target of a reference.
+pub(crate) trait Referenceable: Clone + DeserializeOwned + Walk + 'static {
+    /// The section of the Components Object holding objects of this type,
+    /// if there is one.
+    const COMPONENT_KIND: Option<ComponentKind>;
+
+    /// Looks up a reference within the components of the current document.
+    fn resolve_in<'a>(
+        components: &'a Components,
+        reference: &str,
+    ) -> Result<&'a Self, ResolveError>;
+
+    /// Returns the section of the Components Object holding objects of
+    /// this type, if there is one.
+    fn section_mut(components: &mut Components) -> Option<&mut Map<String, ReferenceOr<Self>>>;
+}
+
+macro_rules! impl_referenceable_component {
+    ($($ty:ty),*) => {
+        $(impl Referenceable for $ty {
+            const COMPONENT_KIND: Option<ComponentKind> = Some(<$ty as Component>::KIND);
+
+            fn resolve_in<'a>(components: &'a Components, reference: &str) -> Result<&'a Self, ResolveError> {
+                components.resolve_reference(reference)
+            }
+
+            fn section_mut(components: &mut Components) -> Option<&mut Map<String, ReferenceOr<Self>>> {
+                Some(<$ty as Component>::section_mut(components))
+            }
+        })*
+    };
+}
+
+impl_referenceable_component!(
+    Schema,
+    Response,
+    Parameter,
+    Example,
+    RequestBody,
+    Header,
+    SecurityScheme,
+    Link,
+    Callback
+);
+
+macro_rules! impl_referenceable_elsewhere {
+    ($($ty:ty),*) => {
+        $(impl Referenceable for $ty {
+            const COMPONENT_KIND: Option<ComponentKind> = None;
+
+            fn resolve_in<'a>(_: &'a Components, reference: &str) -> Result<&'a Self, ResolveError> {
+                Err(ResolveError::Unsupported(reference.to_owned()))
+            }
+
+            fn section_mut(_: &mut Components) -> Option<&mut Map<String, ReferenceOr<Self>>> {
+                None
+            }
+        })*
+    };
+}
+
+// These have no section in the Components Object.
+impl_referenceable_elsewhere!(PathItem, MediaType);
+
+/// Hands every reference held directly or indirectly by a value to a visitor.
+pub(crate) trait Walk {
+    fn walk<V: VisitRefs>(&mut self, v: &mut V) -> Result<(), V::Error>;
+}
+
+impl<T: Referenceable> Walk for ReferenceOr<T> {
+    fn walk<V: VisitRefs>(&mut self, v: &mut V) -> Result<(), V::Error> {
+        v.reference(self)
+    }
+}
+
+impl<T: Walk> Walk for Vec<T> {
+    fn walk<V: VisitRefs>(&mut self, v: &mut V) -> Result<(), V::Error> {
+        self.iter_mut().try_for_each(|value| value.walk(v))
+    }
+}
+
+impl<K, T: Walk> Walk for BTreeMap<K, T> {
+    fn walk<V: VisitRefs>(&mut self, v: &mut V) -> Result<(), V::Error> {
+        self.values_mut().try_for_each(|value| value.walk(v))
+    }
+}
+
+#[cfg(feature = "preserve_order")]
+impl<K, T: Walk> Walk for indexmap::IndexMap<K, T> {
+    fn walk<V: VisitRefs>(&mut self, v: &mut V) -> Result<(), V::Error> {
+        self.values_mut().try_for_each(|value| value.walk(v))
+    }
+}
+
+impl<T: Walk> Walk for Option<T> {
+    fn walk<V: VisitRefs>(&mut self, v: &mut V) -> Result<(), V::Error> {
+        self.iter_mut().try_for_each(|value| value.walk(v))
+    }
+}
+
+impl Walk for Paths {
+    fn walk<V: VisitRefs>(&mut self, v: &mut V) -> Result<(), V::Error> {
+        self.paths.walk(v)
+    }
+}
+
+impl Walk for Callback {
+    fn walk<V: VisitRefs>(&mut self, v: &mut V) -> Result<(), V::Error> {
+        self.paths.walk(v)
+    }
+}
+
+impl Walk for OpenAPI {
+    fn walk<V: VisitRefs>(&mut self, v: &mut V) -> Result<(), V::Error> {
+        self.paths.walk(v)?;
+        self.components.walk(v)
+    }
+}
+
+impl Walk for Components {
+    fn walk<V: VisitRefs>(&mut self, v: &mut V) -> Result<(), V::Error> {
+        self.security_schemes.walk(v)?;
+        self.responses.walk(v)?;
+        self.parameters.walk(v)?;
+        self.examples.walk(v)?;
+        self.request_bodies.walk(v)?;
+        self.headers.walk(v)?;
+        self.schemas.walk(v)?;
+        self.links.walk(v)?;
+        self.callbacks.walk(v)
+    }
+}
+
+impl Walk for PathItem {
+    fn walk<V: VisitRefs>(&mut self, v: &mut V) -> Result<(), V::Error> {
+        self.get.walk(v)?;
+        self.put.walk(v)?;
+        self.post.walk(v)?;
+        self.delete.walk(v)?;
+        self.options.walk(v)?;
+        self.head.walk(v)?;
+        self.patch.walk(v)?;
+        self.trace.walk(v)?;
+        self.parameters.walk(v)
+    }
+}
+
+impl Walk for Operation {
+    fn walk<V: VisitRefs>(&mut self, v: &mut V) -> Result<(), V::Error> {
+        self.parameters.walk(v)?;
+        self.request_body.walk(v)?;
+        self.responses.walk(v)?;
+        self.callbacks.walk(v)
+    }
+}
+
+impl Walk for Responses {
+    fn walk<V: VisitRefs>(&mut self, v: &mut V) -> Result<(), V::Error> {
+        self.default.walk(v)?;
+        self.responses.walk(v)
+    }
+}
+
+impl Walk for Response {
+    fn walk<V: VisitRefs>(&mut self, v: &mut V) -> Result<(), V::Error> {
+        self.headers.walk(v)?;
+        self.content.walk(v)?;
+        self.links.walk(v)
+    }
+}
+
+impl Walk for RequestBody {
+    fn walk<V: VisitRefs>(&mut self, v: &mut V) -> Result<(), V::Error> {
+        self.content.walk(v)
+    }
+}
+
+impl Walk for MediaType {
+    fn walk<V: VisitRefs>(&mut self, v: &mut V) -> Result<(), V::Error> {
+        self.schema.walk(v)?;
+        self.examples.walk(v)?;
+        self.encoding.walk(v)
+    }
+}
+
+impl Walk for Encoding {
+    fn walk<V: VisitRefs>(&mut self, v: &mut V) -> Result<(), V::Error> {
+        self.headers.walk(v)
+    }
+}
+
+impl Walk for Parameter {
+    fn walk<V: VisitRefs>(&mut self, v: &mut V) -> Result<(), V::Error> {
+        match self {
+            Parameter::Query { parameter_data, .. }
+            | Parameter::Header { parameter_data, .. }
+            | Parameter::Path { parameter_data, .. }
+            | Parameter::Cookie { parameter_data, .. } => parameter_data.walk(v),
+        }
+    }
+}
+
+impl Walk for ParameterData {
+    fn walk<V: VisitRefs>(&mut self, v: &mut V) -> Result<(), V::Error> {
+        self.format.walk(v)?;
+        self.examples.walk(v)
+    }
+}
+
+impl Walk for Header {
+    fn walk<V: VisitRefs>(&mut self, v: &mut V) -> Result<(), V::Error> {
+        self.format.walk(v)?;
+        self.examples.walk(v)
+    }
+}
+
+impl Walk for ParameterSchemaOrContent {
+    fn walk<V: VisitRefs>(&mut self, v: &mut V) -> Result<(), V::Error> {
+        match self {
+            ParameterSchemaOrContent::Schema(schema) => schema.walk(v),
+            ParameterSchemaOrContent::Content(content) => content.walk(v),
+        }
+    }
+}
+
+impl Walk for Schema {
+    fn walk<V: VisitRefs>(&mut self, v: &mut V) -> Result<(), V::Error> {
+        if let Some(discriminator) = &mut self.schema_data.discriminator {
+            for target in discriminator.mapping.values_mut() {
+                v.mapping(target)?;
+            }
+        }
+        match &mut self.schema_kind {
+            SchemaKind::Type(Type::Object(object)) => {
+                for property in object.properties.values_mut() {
+                    v.boxed(property)?;
+                }
+                object.additional_properties.walk(v)
+            }
+            SchemaKind::Type(Type::Array(array)) => v.boxed(&mut array.items),
+            SchemaKind::Type(_) => Ok(()),
+            SchemaKind::OneOf { one_of: schemas }
+            | SchemaKind::AllOf { all_of: schemas }
+            | SchemaKind::AnyOf { any_of: schemas } => schemas.walk(v),
+            SchemaKind::Any(any) => {
+                for property in any.properties.values_mut() {
+                    v.boxed(property)?;
+                }
+                if let Some(items) = &mut any.items {
+                    v.boxed(items)?;
+                }
+                any.additional_properties.walk(v)
+            }
+        }
+    }
+}
+
+impl Walk for AdditionalProperties {
+    fn walk<V: VisitRefs>(&mut self, v: &mut V) -> Result<(), V::Error> {
+        match self {
+            AdditionalProperties::Any | AdditionalProperties::None => Ok(()),
+            AdditionalProperties::Schema(schema) => schema.walk(v),
+        }
+    }
+}
+
+macro_rules! impl_leaf {
+    ($($ty:ty),*) => {
+        $(impl Walk for $ty {
+            fn walk<V: VisitRefs>(&mut self, _: &mut V) -> Result<(), V::Error> {
+                Ok(())
+            }
+        })*
+    };
+}
+
+impl_leaf!(Example, Link, SecurityScheme);
+
+/// A JSON Pointer to the part of the document being inspected.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Location {
+    pointer: String,
+}
+
+impl Location {
+    pub(crate) fn as_str(&self) -> &str {
+        &self.pointer
+    }
+
+    /// Runs `f` with `segment` appended to the location.
+    pub(crate) fn with<R, F: FnOnce(&mut Self) -> R>(&mut self, segment: &str, f: F) -> R {
+        let len = self.pointer.len();
+        self.pointer.push('/');
+        self.pointer.push_str(&escape_pointer_segment(segment));
+        let result = f(self);
+        self.pointer.truncate(len);
+        result
+    }
+}
+
+/// Hands every reference held directly or indirectly by a value to `f`,
+/// along with the location of the referring object, without modifying it.
+pub(crate) trait FindRefs {
+    fn find_refs<F: FnMut(&Location, &Reference)>(&self, at: &mut Location, f: &mut F);
+}
+
+impl<T: FindRefs> FindRefs for ReferenceOr<T> {
+    fn find_refs<F: FnMut(&Location, &Reference)>(&self, at: &mut Location, f: &mut F) {
+        match self {
+            ReferenceOr::Reference { reference } => f(at, reference),
+            ReferenceOr::Item(item) => item.find_refs(at, f),
+        }
+    }
+}
+
+impl<T: FindRefs> FindRefs for Box<T> {
+    fn find_refs<F: FnMut(&Location, &Reference)>(&self, at: &mut Location, f: &mut F) {
+        (**self).find_refs(at, f)
+    }
+}
+
+impl<T: FindRefs> FindRefs for Vec<T> {
+    fn find_refs<F: FnMut(&Location, &Reference)>(&self, at: &mut Location, f: &mut F) {
+        for (i, value) in self.iter().enumerate() {
+            at.with(&i.to_string(), |at| value.find_refs(at, f));
+        }
+    }
+}
+
+impl<K: fmt::Display, T: FindRefs> FindRefs for BTreeMap<K, T> {
+    fn find_refs<F: FnMut(&Location, &Reference)>(&self, at: &mut Location, f: &mut F) {
+        for (key, value) in self {
+            at.with(&key.to_string(), |at| value.find_refs(at, f));
+        }
+    }
+}
+
+#[cfg(feature = "preserve_order")]
+impl<K: fmt::Display, T: FindRefs> FindRefs for indexmap::IndexMap<K, T> {
+    fn find_refs<F: FnMut(&Location, &Reference)>(&self, at: &mut Location, f: &mut F) {
+        for (key, value) in self {
+            at.with(&key.to_string(), |at| value.find_refs(at, f));
+        }
+    }
+}
+
+impl<T: FindRefs> FindRefs for Option<T> {
+    fn find_refs<F: FnMut(&Location, &Reference)>(&self, at: &mut Location, f: &mut F) {
+        if let Some(value) = self {
+            value.find_refs(at, f);
+        }
+    }
+}
+
+/// Implements `FindRefs` for a struct by inspecting the given fields,
+/// each under its serialized name.
+macro_rules! impl_find_refs_fields {
+    ($ty:ty { $($field:ident: $name:literal),* }) => {
+        impl FindRefs for $ty {
+            fn find_refs<F: FnMut(&Location, &Reference)>(&self, at: &mut Location, f: &mut F) {
+                $(at.with($name, |at| self.$field.find_refs(at, f));)*
+            }
+        }
+    };
+}
+
+impl_find_refs_fields!(OpenAPI {
+    paths: "paths",
+    components: "components"
+});
+impl_find_refs_fields!(Components {
+    security_schemes: "securitySchemes",
+    responses: "responses",
+    parameters: "parameters",
+    examples: "examples",
+    request_bodies: "requestBodies",
+    headers: "headers",
+    schemas: "schemas",
+    links: "links",
+    callbacks: "callbacks"
+});
+impl_find_refs_fields!(PathItem {
+    get: "get",
+    put: "put",
+    post: "post",
+    delete: "delete",
+    options: "options",
+    head: "head",
+    patch: "patch",
+    trace: "trace",
+    parameters: "parameters"
+});
+impl_find_refs_fields!(Operation {
+    parameters: "parameters",
+    request_body: "requestBody",
+    responses: "responses",
+    callbacks: "callbacks"
+});
+impl_find_refs_fields!(Response {
+    headers: "headers",
+    content: "content",
+    links: "links"
+});
+impl_find_refs_fields!(RequestBody { content: "content" });
+impl_find_refs_fields!(MediaType {
+    schema: "schema",
+    examples: "examples",
+    encoding: "encoding"
+});
+impl_find_refs_fields!(Encoding { headers: "headers" });
+
+impl FindRefs for Paths {
+    fn find_refs<F: FnMut(&Location, &Reference)>(&self, at: &mut Location, f: &mut F) {
+        self.paths.find_refs(at, f)
+    }
+}
+
+impl FindRefs for Callback {
+    fn find_refs<F: FnMut(&Location, &Reference)>(&self, at: &mut Location, f: &mut F) {
+        self.paths.find_refs(at, f)
+    }
+}
+
+impl FindRefs for Responses {
+    fn find_refs<F: FnMut(&Location, &Reference)>(&self, at: &mut Location, f: &mut F) {
+        at.with("default", |at| self.default.find_refs(at, f));
+        // The status codes are flattened into the Responses Object.
+        self.responses.find_refs(at, f);
+    }
+}
+
+impl FindRefs for Parameter {
+    fn find_refs<F: FnMut(&Location, &Reference)>(&self, at: &mut Location, f: &mut F) {
+        self.parameter_data_ref().find_refs(at, f)
+    }
+}
+
+impl FindRefs for ParameterData {
+    fn find_refs<F: FnMut(&Location, &Reference)>(&self, at: &mut Location, f: &mut F) {
+        self.format.find_refs(at, f);
+        at.with("examples", |at| self.examples.find_refs(at, f));
+    }
+}
+
+impl FindRefs for Header {
+    fn find_refs<F: FnMut(&Location, &Reference)>(&self, at: &mut Location, f: &mut F) {
+        self.format.find_refs(at, f);
+        at.with("examples", |at| self.examples.find_refs(at, f));
+    }
+}
+
+impl FindRefs for ParameterSchemaOrContent {
+    fn find_refs<F: FnMut(&Location, &Reference)>(&self, at: &mut Location, f: &mut F) {
+        match self {
+            ParameterSchemaOrContent::Schema(schema) => {
+                at.with("schema", |at| schema.find_refs(at, f))
+            }
+            ParameterSchemaOrContent::Content(content) => {
+                at.with("content", |at| content.find_refs(at, f))
+            }
+        }
+    }
+}
+
+impl FindRefs for Schema {
+    fn find_refs<F: FnMut(&Location, &Reference)>(&self, at: &mut Location, f: &mut F) {
+        match &self.schema_kind {
+            SchemaKind::Type(Type::Object(object)) => {
+                at.with("properties", |at| object.properties.find_refs(at, f));
+                at.with("additionalProperties", |at| {
+                    object.additional_properties.find_refs(at, f)
+                });
+            }
+            SchemaKind::Type(Type::Array(array)) => {
+                at.with("items", |at| array.items.find_refs(at, f))
+            }
+            SchemaKind::Type(_) => {}
+            SchemaKind::OneOf { one_of } => at.with("oneOf", |at| one_of.find_refs(at, f)),
+            SchemaKind::AllOf { all_of } => at.with("allOf", |at| all_of.find_refs(at, f)),
+            SchemaKind::AnyOf { any_of } => at.with("anyOf", |at| any_of.find_refs(at, f)),
+            SchemaKind::Any(any) => {
+                at.with("properties", |at| any.properties.find_refs(at, f));
+                at.with("items", |at| any.items.find_refs(at, f));
+                at.with("additionalProperties", |at| {
+                    any.additional_properties.find_refs(at, f)
+                });
+            }
+        }
+    }
+}
+
+impl FindRefs for AdditionalProperties {
+    fn find_refs<F: FnMut(&Location, &Reference)>(&self, at: &mut Location, f: &mut F) {
+        match self {
+            AdditionalProperties::Any | AdditionalProperties::None => {}
+            AdditionalProperties::Schema(schema) => schema.find_refs(at, f),
+        }
+    }
+}
+
+macro_rules! impl_find_refs_leaf {
+    ($($ty:ty),*) => {
+        $(impl FindRefs for $ty {
+            fn find_refs<F: FnMut(&Location, &Reference)>(&self, _: &mut Location, _: &mut F) {}
+        })*
+    };
+}
+
+impl_find_refs_leaf!(Example, Link, SecurityScheme);
@@ -0,0 +1,781 @@
+use crate::style::coerce;
+use crate::*;
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map as JsonMap, Value};
+use std::error::Error;
+use std::fmt;
+
+/// Describes how a schema is represented in XML.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct Xml {
+    /// The name of the element or attribute, instead of the name of the
+    /// property or component. For the items of arrays, the name of each
+    /// item; for arrays themselves, the name of the wrapping element.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// The URI of the namespace of the element.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+    /// The prefix of the name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prefix: Option<String>,
+    /// Whether a property is an attribute of its object, rather than an
+    /// element within it.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub attribute: bool,
+    /// Whether the items of an array are wrapped in an element of their
+    /// own. Only applies to arrays.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub wrapped: bool,
+    /// Inline extensions to this object.
+    #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
+    pub extensions: IndexMap<String, serde_json::Value>,
+}
+
+/// Why a document cannot be read as XML, at the byte `offset` where it
+/// stops making sense.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseXmlError {
+    pub offset: usize,
+    pub kind: XmlErrorKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum XmlErrorKind {
+    /// The document ends within an element, or has none.
+    UnexpectedEnd,
+    /// Something other than what is named was found.
+    Expected(&'static str),
+    /// An element is closed by the end tag of another.
+    MismatchedTag { open: String, close: String },
+    /// An entity other than the predefined and numeric ones.
+    UnknownEntity(String),
+    /// Something other than comments follows the root element.
+    TrailingContent,
+}
+
+impl fmt::Display for ParseXmlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "at byte {}: ", self.offset)?;
+        match &self.kind {
+            XmlErrorKind::UnexpectedEnd => f.write_str("unexpected end of document"),
+            XmlErrorKind::Expected(what) => write!(f, "expected {}", what),
+            XmlErrorKind::MismatchedTag { open, close } => {
+                write!(f, "`<{}>` is closed by `</{}>`", open, close)
+            }
+            XmlErrorKind::UnknownEntity(entity) => write!(f, "unknown entity `&{};`", entity),
+            XmlErrorKind::TrailingContent => f.write_str("content after the root element"),
+        }
+    }
+}
+
+impl Error for ParseXmlError {}
+
+impl Schema {
+    /// Serializes a value of the schema as XML, as its `xml` objects and
+    /// those of its properties describe, in an element named `name` unless
+    /// the `xml` object of the schema names it otherwise.
+    ///
+    /// Properties are elements named after them unless they are
+    /// attributes. The items of arrays are elements named after the array,
+    /// or as the `xml` object of the items says, wrapped in an element named
+    /// after the array if `wrapped`; arrays at the root always are.
+    /// References are resolved in `components`, and `allOf` merged.
+    ///
+    /// ```
+    /// use openapiv3::{Components, Schema};
+    /// use serde_json::json;
+    ///
+    /// let schema: Schema = serde_yaml::from_str(
+    ///     "
+    /// type: object
+    /// xml: {name: pet, namespace: 'https://example.com/pets', prefix: p}
+    /// properties:
+    ///   id: {type: integer, xml: {attribute: true}}
+    ///   tags:
+    ///     type: array
+    ///     xml: {wrapped: true}
+    ///     items: {type: string, xml: {name: tag}}
+    /// ",
+    /// )
+    /// .unwrap();
+    /// let components = Components::default();
+    /// let value = json!({"id": 3, "tags": ["a & b", "c"]});
+    /// let xml = schema.to_xml(&components, "Pet", &value);
+    /// assert_eq!(
+    ///     xml,
+    ///     "<p:pet xmlns:p=\"https://example.com/pets\" id=\"3\">\
+    ///      <tags><tag>a &amp; b</tag><tag>c</tag></tags></p:pet>"
+    /// );
+    /// assert_eq!(schema.from_xml(&components, &xml), Ok(value));
+    /// ```
+    pub fn to_xml(&self, components: &Components, name: &str, value: &Value) -> String {
+        let mut out = String::new();
+        let writer = XmlWriter { components };
+        match value {
+            Value::Array(items) => {
+                let items_schema = writer.items(self);
+                let item_name = element_name(items_schema, name);
+                let wrapper = element_name(Some(self), name);
+                writer.open(&mut out, Some(self), &wrapper, &[]);
+                for item in items {
+                    writer.element(&mut out, items_schema, &item_name, item);
+                }
+                writer.close(&mut out, Some(self), &wrapper);
+            }
+            value => writer.element(&mut out, Some(self), &element_name(Some(self), name), value),
+        }
+        out
+    }
+
+    /// Reads a value of the schema from XML serialized as
+    /// [`Schema::to_xml`] does, for validation against the schema.
+    ///
+    /// Elements and attributes are matched by their names without their
+    /// prefixes, and their text read as the type their schema gives, as
+    /// parameters are. Elements that match no property are read as
+    /// additional properties where those are allowed, as objects of their
+    /// elements or as text.
+    pub fn from_xml(&self, components: &Components, xml: &str) -> Result<Value, ParseXmlError> {
+        let root = Parser { xml, at: 0 }.document()?;
+        let reader = XmlReader { components };
+        Ok(match reader.items(self) {
+            Some(items) => Value::Array(
+                root.children
+                    .iter()
+                    .map(|child| reader.value(items, child))
+                    .collect(),
+            ),
+            None => reader.value(Some(self), &root),
+        })
+    }
+}
+
+/// The name of the element of `schema`, as its `xml` object names it or
+/// else `name`.
+fn element_name(schema: Option<&Schema>, name: &str) -> String {
+    schema
+        .and_then(|schema| schema.schema_data.xml.as_ref())
+        .and_then(|xml| xml.name.clone())
+        .unwrap_or_else(|| name.to_owned())
+}
+
+/// The part of a name after its prefix.
+fn local_name(name: &str) -> &str {
+    name.rsplit(':').next().unwrap_or(name)
+}
+
+fn escape(text: &str, out: &mut String) {
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            c => out.push(c),
+        }
+    }
+}
+
+fn text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Null => String::new(),
+        value => value.to_string(),
+    }
+}
+
+/// The schemas of the properties of an object schema, resolving references
+/// and merging `allOf`, along with that of its additional properties if it
+/// allows them.
+struct Properties {
+    properties: Vec<(Name, Option<Schema>)>,
+    additional: Option<Option<Schema>>,
+}
+
+fn properties(components: &Components, schema: Option<&Schema>) -> Option<Properties> {
+    let schema = schema?.flatten_all_of(components).ok()?;
+    let (properties, additional) = match schema.schema_kind {
+        SchemaKind::Type(Type::Object(object)) => (object.properties, object.additional_properties),
+        SchemaKind::Any(any) if !any.properties.is_empty() => {
+            (any.properties, any.additional_properties)
+        }
+        _ => return None,
+    };
+    let resolve =
+        |schema: &ReferenceOr<Box<Schema>>| components.resolve_boxed(schema).ok().cloned();
+    Some(Properties {
+        properties: properties
+            .iter()
+            .map(|(name, schema)| (name.clone(), resolve(schema)))
+            .collect(),
+        additional: match additional {
+            Some(AdditionalProperties::None) => None,
+            Some(AdditionalProperties::Schema(schema)) => {
+                Some(components.resolve(&schema).ok().cloned())
+            }
+            Some(AdditionalProperties::Any) | None => Some(None),
+        },
+    })
+}
+
+fn is_attribute(schema: Option<&Schema>) -> bool {
+    schema
+        .and_then(|schema| schema.schema_data.xml.as_ref())
+        .is_some_and(|xml| xml.attribute)
+}
+
+fn is_wrapped(schema: Option<&Schema>) -> bool {
+    schema
+        .and_then(|schema| schema.schema_data.xml.as_ref())
+        .is_some_and(|xml| xml.wrapped)
+}
+
+/// The schema of the items of an array schema, or `None` inside if they
+/// cannot be resolved; `None` if the schema is not of an array.
+fn array_items<'a>(components: &'a Components, schema: &'a Schema) -> Option<Option<&'a Schema>> {
+    match &schema.schema_kind {
+        SchemaKind::Type(Type::Array(array)) => Some(components.resolve_boxed(&array.items).ok()),
+        SchemaKind::Any(AnySchema {
+            items: Some(items), ..
+        }) => Some(components.resolve_boxed(items).ok()),
+        _ => None,
+    }
+}
+
+struct XmlWriter<'c> {
+    components: &'c Components,
+}
+
+impl<'c> XmlWriter<'c> {
+    fn items<'s>(&self, schema: &'s Schema) -> Option<&'s Schema>
+    where
+        'c: 's,
+    {
+        array_items(self.components, schema).flatten()
+    }
+
+    /// Writes the start tag of an element, with the namespace its schema
+    /// declares and `attributes`.
+    fn open(
+        &self,
+        out: &mut String,
+        schema: Option<&Schema>,
+        name: &str,
+        attributes: &[(String, String)],
+    ) {
+        let xml = schema.and_then(|schema| schema.schema_data.xml.as_ref());
+        out.push('<');
+        self.qualified(out, schema, name);
+        if let Some(namespace) = xml.and_then(|xml| xml.namespace.as_ref()) {
+            match xml.and_then(|xml| xml.prefix.as_ref()) {
+                Some(prefix) => out.push_str(&format!(" xmlns:{}=\"", prefix)),
+                None => out.push_str(" xmlns=\""),
+            }
+            escape(namespace, out);
+            out.push('"');
+        }
+        for (name, value) in attributes {
+            out.push(' ');
+            out.push_str(name);
+            out.push_str("=\"");
+            escape(value, out);
+            out.push('"');
+        }
+        out.push('>');
+    }
+
+    fn close(&self, out: &mut String, schema: Option<&Schema>, name: &str) {
+        out.push_str("</");
+        self.qualified(out, schema, name);
+        out.push('>');
+    }
+
+    /// Writes `name` with the prefix the schema gives it.
+    fn qualified(&self, out: &mut String, schema: Option<&Schema>, name: &str) {
+        let prefix = schema
+            .and_then(|schema| schema.schema_data.xml.as_ref())
+            .and_then(|xml| xml.prefix.as_ref());
+        if let Some(prefix) = prefix {
+            out.push_str(prefix);
+            out.push(':');
+        }
+        out.push_str(name);
+    }
+
+    /// Writes a value as the element `name`, and its items as elements in
+    /// its stead if it is an array.
+    fn element(&self, out: &mut String, schema: Option<&Schema>, name: &str, value: &Value) {
+        match value {
+            Value::Array(items) => {
+                let items_schema = schema.and_then(|schema| self.items(schema));
+                let item_name = element_name(items_schema, name);
+                let wrapped = is_wrapped(schema);
+                if wrapped {
+                    self.open(out, schema, name, &[]);
+                }
+                for item in items {
+                    self.element(out, items_schema, &item_name, item);
+                }
+                if wrapped {
+                    self.close(out, schema, name);
+                }
+            }
+            Value::Object(object) => {
+                let described = properties(self.components, schema);
+                let mut attributes = Vec::new();
+                let mut children = Vec::new();
+                let described_properties = described.iter().flat_map(|d| &d.properties);
+                for (property, schema) in described_properties {
+                    let value = match object.get(property.as_str()) {
+                        Some(value) => value,
+                        None => continue,
+                    };
+                    let name = element_name(schema.as_ref(), property);
+                    if is_attribute(schema.as_ref()) {
+                        let mut qualified = String::new();
+                        self.qualified(&mut qualified, schema.as_ref(), &name);
+                        attributes.push((qualified, text(value)));
+                    } else {
+                        children.push((schema.as_ref(), name, value));
+                    }
+                }
+                let additional = described.as_ref().and_then(|d| d.additional.as_ref());
+                for (key, value) in object {
+                    let declared = described
+                        .as_ref()
+                        .is_some_and(|d| d.properties.iter().any(|(name, _)| name == key));
+                    if !declared {
+                        children.push((additional.and_then(Option::as_ref), key.clone(), value));
+                    }
+                }
+                self.open(out, schema, name, &attributes);
+                for (schema, name, value) in children {
+                    self.element(out, schema, &name, value);
+                }
+                self.close(out, schema, name);
+            }
+            value => {
+                self.open(out, schema, name, &[]);
+                escape(&text(value), out);
+                self.close(out, schema, name);
+            }
+        }
+    }
+}
+
+struct XmlReader<'c> {
+    components: &'c Components,
+}
+
+impl<'c> XmlReader<'c> {
+    fn items<'s>(&self, schema: &'s Schema) -> Option<Option<&'s Schema>>
+    where
+        'c: 's,
+    {
+        array_items(self.components, schema)
+    }
+
+    fn value(&self, schema: Option<&Schema>, element: &Element) -> Value {
+        if let Some(described) = properties(self.components, schema) {
+            return self.object(&described, element);
+        }
+        match schema {
+            Some(schema) => coerce(self.components, schema, &element.text),
+            None if element.children.is_empty() => Value::String(element.text.clone()),
+            None => {
+                let mut object = JsonMap::new();
+                for child in &element.children {
+                    object.insert(local_name(&child.name).to_owned(), self.value(None, child));
+                }
+                Value::Object(object)
+            }
+        }
+    }
+
+    fn object(&self, described: &Properties, element: &Element) -> Value {
+        let mut object = JsonMap::new();
+        let mut matched = vec![false; element.children.len()];
+        // The children named `name` that no property has yet taken.
+        let mut take = |name: &str| -> Vec<&Element> {
+            let mut taken = Vec::new();
+            for (child, matched) in element.children.iter().zip(matched.iter_mut()) {
+                if !*matched && local_name(&child.name) == local_name(name) {
+                    *matched = true;
+                    taken.push(child);
+                }
+            }
+            taken
+        };
+        for (property, schema) in &described.properties {
+            let schema = schema.as_ref();
+            let name = element_name(schema, property);
+            if is_attribute(schema) {
+                let attribute = element
+                    .attributes
+                    .iter()
+                    .find(|(attribute, _)| local_name(attribute) == local_name(&name));
+                if let Some((_, value)) = attribute {
+                    let value = match schema {
+                        Some(schema) => coerce(self.components, schema, value),
+                        None => Value::String(value.clone()),
+                    };
+                    object.insert(property.to_string(), value);
+                }
+                continue;
+            }
+            match schema.and_then(|schema| self.items(schema)) {
+                Some(items) => {
+                    let item_name = element_name(items, &name);
+                    let elements: Vec<&Element> = if is_wrapped(schema) {
+                        match take(&name).first() {
+                            Some(wrapper) => wrapper
+                                .children
+                                .iter()
+                                .filter(|child| local_name(&child.name) == local_name(&item_name))
+                                .collect(),
+                            None => continue,
+                        }
+                    } else {
+                        take(&item_name)
+                    };
+                    if !elements.is_empty() || is_wrapped(schema) {
+                        let values = elements.iter().map(|item| self.value(items, item));
+                        object.insert(property.to_string(), Value::Array(values.collect()));
+                    }
+                }
+                None => {
+                    if let Some(child) = take(&name).first() {
+                        object.insert(property.to_string(), self.value(schema, child));
+                    }
+                }
+            }
+        }
+        if let Some(additional) = &described.additional {
+            for (child, matched) in element.children.iter().zip(matched) {
+                if !matched {
+                    let value = self.value(additional.as_ref(), child);
+                    object.insert(local_name(&child.name).to_owned(), value);
+                }
+            }
+        }
+        Value::Object(object)
+    }
+}
+
+/// An element of a parsed document, with its text and the elements within
+/// it.
+#[derive(Debug, Default)]
+struct Element {
+    name: String,
+    attributes: Vec<(String, String)>,
+    children: Vec<Element>,
+    text: String,
+}
+
+struct Parser<'x> {
+    xml: &'x str,
+    at: usize,
+}
+
+impl<'x> Parser<'x> {
+    fn rest(&self) -> &'x str {
+        &self.xml[self.at..]
+    }
+
+    fn error(&self, kind: XmlErrorKind) -> ParseXmlError {
+        ParseXmlError {
+            offset: self.at,
+            kind,
+        }
+    }
+
+    fn eat(&mut self, prefix: &str) -> bool {
+        let found = self.rest().starts_with(prefix);
+        if found {
+            self.at += prefix.len();
+        }
+        found
+    }
+
+    fn expect(&mut self, prefix: &'static str) -> Result<(), ParseXmlError> {
+        if self.eat(prefix) {
+            Ok(())
+        } else if self.rest().is_empty() {
+            Err(self.error(XmlErrorKind::UnexpectedEnd))
+        } else {
+            Err(self.error(XmlErrorKind::Expected(prefix)))
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        let trimmed = self.rest().trim_start();
+        self.at = self.xml.len() - trimmed.len();
+    }
+
+    /// Skips everything up to and including `end`.
+    fn skip_past(&mut self, end: &'static str) -> Result<&'x str, ParseXmlError> {
+        match self.rest().find(end) {
+            Some(i) => {
+                let skipped = &self.rest()[..i];
+                self.at += i + end.len();
+                Ok(skipped)
+            }
+            None => {
+                self.at = self.xml.len();
+                Err(self.error(XmlErrorKind::UnexpectedEnd))
+            }
+        }
+    }
+
+    /// Skips whitespace, comments, processing instructions and doctypes.
+    fn skip_misc(&mut self) -> Result<(), ParseXmlError> {
+        loop {
+            self.skip_whitespace();
+            if self.eat("<!--") {
+                self.skip_past("-->")?;
+            } else if self.eat("<?") {
+                self.skip_past("?>")?;
+            } else if self.eat("<!DOCTYPE") {
+                self.skip_past(">")?;
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    fn document(mut self) -> Result<Element, ParseXmlError> {
+        self.skip_misc()?;
+        if self.rest().is_empty() {
+            return Err(self.error(XmlErrorKind::UnexpectedEnd));
+        }
+        let root = self.element()?;
+        self.skip_misc()?;
+        if self.rest().is_empty() {
+            Ok(root)
+        } else {
+            Err(self.error(XmlErrorKind::TrailingContent))
+        }
+    }
+
+    fn name(&mut self) -> Result<&'x str, ParseXmlError> {
+        let end = self
+            .rest()
+            .find(|c: char| c.is_whitespace() || "/>=".contains(c))
+            .unwrap_or(self.rest().len());
+        if end == 0 {
+            return Err(if self.rest().is_empty() {
+                self.error(XmlErrorKind::UnexpectedEnd)
+            } else {
+                self.error(XmlErrorKind::Expected("a name"))
+            });
+        }
+        let name = &self.rest()[..end];
+        self.at += end;
+        Ok(name)
+    }
+
+    fn element(&mut self) -> Result<Element, ParseXmlError> {
+        self.expect("<")?;
+        let mut element = Element {
+            name: self.name()?.to_owned(),
+            ..Element::default()
+        };
+        loop {
+            self.skip_whitespace();
+            if self.eat("/>") {
+                return Ok(element);
+            }
+            if self.eat(">") {
+                break;
+            }
+            let name = self.name()?.to_owned();
+            self.skip_whitespace();
+            self.expect("=")?;
+            self.skip_whitespace();
+            let quote = if self.eat("\"") {
+                "\""
+            } else {
+                self.expect("'")?;
+                "'"
+            };
+            let start = self.at;
+            let raw = self.skip_past(quote)?;
+            let value = unescape(raw, start)?;
+            element.attributes.push((name, value));
+        }
+
+        loop {
+            let end = self.rest().find('<').unwrap_or(self.rest().len());
+            let start = self.at;
+            let raw = &self.rest()[..end];
+            self.at += end;
+            element.text.push_str(&unescape(raw, start)?);
+            if self.rest().is_empty() {
+                return Err(self.error(XmlErrorKind::UnexpectedEnd));
+            } else if self.eat("<![CDATA[") {
+                let data = self.skip_past("]]>")?;
+                element.text.push_str(data);
+            } else if self.eat("<!--") {
+                self.skip_past("-->")?;
+            } else if self.eat("<?") {
+                self.skip_past("?>")?;
+            } else if self.eat("</") {
+                let start = self.at;
+                let close = self.name()?;
+                if close != element.name {
+                    return Err(ParseXmlError {
+                        offset: start,
+                        kind: XmlErrorKind::MismatchedTag {
+                            open: element.name,
+                            close: close.to_owned(),
+                        },
+                    });
+                }
+                self.skip_whitespace();
+                self.expect(">")?;
+                return Ok(element);
+            } else {
+                element.children.push(self.element()?);
+            }
+        }
+    }
+}
+
+/// Replaces the entities of text found at byte `offset` with what they
+/// stand for.
+fn unescape(raw: &str, offset: usize) -> Result<String, ParseXmlError> {
+    let mut out = String::new();
+    let mut rest = raw;
+    while let Some(i) = rest.find('&') {
+        out.push_str(&rest[..i]);
+        let at = offset + (raw.len() - rest.len()) + i;
+        let end = rest[i..].find(';').ok_or(ParseXmlError {
+            offset: at,
+            kind: XmlErrorKind::Expected(";"),
+        })?;
+        let entity = &rest[i + 1..i + end];
+        let c = match entity {
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "amp" => Some('&'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => match entity
+                .strip_prefix("#x")
+                .or_else(|| entity.strip_prefix("#X"))
+            {
+                Some(hex) => u32::from_str_radix(hex, 16).ok().and_then(char::from_u32),
+                None => entity
+                    .strip_prefix('#')
+                    .and_then(|decimal| decimal.parse().ok())
+                    .and_then(char::from_u32),
+            },
+        };
+        match c {
+            Some(c) => out.push(c),
+            None => {
+                return Err(ParseXmlError {
+                    offset: at,
+                    kind: XmlErrorKind::UnknownEntity(entity.to_owned()),
+                })
+            }
+        }
+        rest = &rest[i + end + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use serde_json::json;
+
+    #[test]
+    fn serializes_xml() {
+        let components: Components = serde_yaml::from_str(
+            "
+schemas:
+  Owner:
+    type: object
+    xml: {name: person}
+    properties:
+      name: {type: string, xml: {prefix: a, attribute: true}}
+",
+        )
+        .unwrap();
+        let schema: Schema = serde_yaml::from_str(
+            "
+type: object
+properties:
+  id: {type: integer}
+  owners:
+    type: array
+    items: {$ref: '#/components/schemas/Owner'}
+  visits:
+    type: array
+    xml: {name: history, wrapped: true}
+    items: {type: number}
+  wild: {type: boolean, xml: {namespace: 'urn:x'}}
+",
+        )
+        .unwrap();
+        let value = json!({
+            "id": 1,
+            "owners": [{"name": "Ann"}, {"name": "Bo"}],
+            "visits": [1.5],
+            "wild": true,
+            "note": "<hi>",
+        });
+        let xml = schema.to_xml(&components, "Pet", &value);
+        assert_eq!(
+            xml,
+            "<Pet><id>1</id><person a:name=\"Ann\"></person><person a:name=\"Bo\"></person>\
+             <history><history>1.5</history></history><wild xmlns=\"urn:x\">true</wild>\
+             <note>&lt;hi&gt;</note></Pet>"
+        );
+        assert_eq!(schema.from_xml(&components, &xml), Ok(value));
+
+        let parsed = schema.from_xml(
+            &components,
+            "<?xml version=\"1.0\"?>\n<!-- a pet -->\n<p:Pet xmlns:p=\"urn:p\">\
+             <p:id>&#x32;</p:id><history/><note><![CDATA[<a>]]></note>\
+             <other><x>1</x></other></p:Pet>",
+        );
+        assert_eq!(
+            parsed,
+            Ok(json!({"id": 2, "visits": [], "note": "<a>", "other": {"x": "1"}}))
+        );
+
+        let list: Schema =
+            serde_yaml::from_str("{type: array, items: {type: integer, xml: {name: n}}}").unwrap();
+        let xml = list.to_xml(&components, "list", &json!([1, 2]));
+        assert_eq!(xml, "<list><n>1</n><n>2</n></list>");
+        assert_eq!(list.from_xml(&components, &xml), Ok(json!([1, 2])));
+
+        let errors: Vec<String> = [
+            "",
+            "<a>",
+            "<a></b>",
+            "<a>&nbsp;</a>",
+            "<a/><b/>",
+            "<a x=1/>",
+        ]
+        .iter()
+        .map(|xml| list.from_xml(&components, xml).unwrap_err().to_string())
+        .collect();
+        assert_eq!(
+            errors,
+            [
+                "at byte 0: unexpected end of document",
+                "at byte 3: unexpected end of document",
+                "at byte 5: `<a>` is closed by `</b>`",
+                "at byte 3: unknown entity `&nbsp;`",
+                "at byte 4: content after the root element",
+                "at byte 5: expected '",
+            ]
+        );
+    }
+}
@@ -0,0 +1,402 @@
+use crate::reference::escape_pointer_segment;
+use crate::*;
+use serde_yaml::Value;
+use std::fmt::Write;
+
+/// How [`OpenAPI::to_yaml_string`] lays out a document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct YamlOptions {
+    /// The number of spaces each level of mappings and sequences is
+    /// indented by, at least one.
+    pub indent: usize,
+    /// Whether strings of more than one line are written as literal blocks
+    /// (`|`), keeping their lines as they are, rather than quoted with
+    /// their line breaks escaped.
+    pub literal_blocks: bool,
+    /// How strings are quoted where they are quoted.
+    pub quote: QuoteStyle,
+    /// Whether every string is quoted, rather than only those that would
+    /// otherwise be read as something else, such as `'true'` or `'200'`.
+    pub quote_all: bool,
+}
+
+impl Default for YamlOptions {
+    fn default() -> Self {
+        YamlOptions {
+            indent: 2,
+            literal_blocks: true,
+            quote: QuoteStyle::Single,
+            quote_all: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuoteStyle {
+    /// `'...'`, falling back to double quotes for strings with characters
+    /// that single quotes cannot hold, such as line breaks.
+    Single,
+    /// `"..."`, with characters escaped as in JSON.
+    Double,
+}
+
+impl OpenAPI {
+    /// Writes the document as YAML laid out as `options` ask for, in the
+    /// order of its fields.
+    ///
+    /// ```
+    /// use openapiv3::{OpenAPI, QuoteStyle, YamlOptions};
+    ///
+    /// let api: OpenAPI = serde_yaml::from_str(
+    ///     "
+    /// openapi: 3.0.0
+    /// info:
+    ///   title: Pets
+    ///   description: \"Lists pets.\\n\\nAnd owners.\"
+    ///   version: '1.0'
+    /// paths: {}
+    /// ",
+    /// )
+    /// .unwrap();
+    /// let options = YamlOptions {
+    ///     indent: 4,
+    ///     quote: QuoteStyle::Double,
+    ///     ..YamlOptions::default()
+    /// };
+    /// assert_eq!(
+    ///     api.to_yaml_string(options).unwrap(),
+    ///     "openapi: 3.0.0
+    /// info:
+    ///     title: Pets
+    ///     description: |-
+    ///         Lists pets.
+    ///
+    ///         And owners.
+    ///     version: \"1.0\"
+    /// paths: {}
+    /// "
+    /// );
+    /// ```
+    pub fn to_yaml_string(&self, options: YamlOptions) -> Result<String, serde_yaml::Error> {
+        Ok(emit(
+            &serde_yaml::to_value(self)?,
+            &options,
+            &Comments::default(),
+        ))
+    }
+}
+
+/// Writes a value as a YAML document, with the comments of its values.
+pub(crate) fn emit(value: &Value, options: &YamlOptions, comments: &Comments) -> String {
+    let mut out = String::new();
+    let mut emitter = Emitter {
+        options,
+        comments,
+        out: &mut out,
+    };
+    emitter.leading("", 0);
+    emitter.node(value, 0, false, "");
+    for comment in comments.end() {
+        let _ = writeln!(emitter.out, "#{}", comment);
+    }
+    out
+}
+
+struct Emitter<'a> {
+    options: &'a YamlOptions,
+    comments: &'a Comments,
+    out: &'a mut String,
+}
+
+impl Emitter<'_> {
+    fn step(&self) -> usize {
+        self.options.indent.max(1)
+    }
+
+    /// Writes the value at `pointer` as a block at `column`, ending with a
+    /// line break. With `inline`, the first line continues one already
+    /// started at `column`, as after `- `.
+    fn node(&mut self, value: &Value, column: usize, inline: bool, pointer: &str) {
+        match value {
+            Value::Mapping(mapping) if !mapping.is_empty() => {
+                for (i, (key, value)) in mapping.iter().enumerate() {
+                    let entry = format!("{}/{}", pointer, escape_pointer_segment(&key_string(key)));
+                    if i > 0 || !inline {
+                        self.leading(&entry, column);
+                        self.pad(column);
+                    }
+                    self.key(key);
+                    self.out.push(':');
+                    self.value(value, column, &entry);
+                }
+            }
+            Value::Sequence(sequence) if !sequence.is_empty() => {
+                for (i, item) in sequence.iter().enumerate() {
+                    let entry = format!("{}/{}", pointer, i);
+                    if i > 0 || !inline {
+                        self.leading(&entry, column);
+                        self.pad(column);
+                    }
+                    self.out.push_str("- ");
+                    match item {
+                        Value::Mapping(m) if !m.is_empty() => {
+                            self.node(item, column + 2, true, &entry)
+                        }
+                        Value::Sequence(s) if !s.is_empty() => {
+                            self.node(item, column + 2, true, &entry)
+                        }
+                        Value::String(s) if self.is_literal(s) => {
+                            self.literal(s, column + self.step(), &entry)
+                        }
+                        _ => {
+                            self.scalar(item);
+                            self.end_line(&entry);
+                        }
+                    }
+                }
+            }
+            _ => {
+                if !inline {
+                    self.pad(column);
+                }
+                self.scalar(value);
+                self.end_line(pointer);
+            }
+        }
+    }
+
+    /// Writes the value of the mapping entry at `pointer` and `column`,
+    /// after its `:`.
+    fn value(&mut self, value: &Value, column: usize, pointer: &str) {
+        match value {
+            Value::Mapping(m) if !m.is_empty() => {
+                self.end_line(pointer);
+                self.node(value, column + self.step(), false, pointer);
+            }
+            Value::Sequence(s) if !s.is_empty() => {
+                self.end_line(pointer);
+                self.node(value, column + self.step(), false, pointer);
+            }
+            Value::String(s) if self.is_literal(s) => {
+                self.out.push(' ');
+                self.literal(s, column + self.step(), pointer);
+            }
+            _ => {
+                self.out.push(' ');
+                self.scalar(value);
+                self.end_line(pointer);
+            }
+        }
+    }
+
+    /// Writes the comments above the value at `pointer`, at `column`.
+    fn leading(&mut self, pointer: &str, column: usize) {
+        for comment in self.comments.leading(pointer) {
+            self.pad(column);
+            let _ = writeln!(self.out, "#{}", comment);
+        }
+    }
+
+    /// Ends the line the value at `pointer` starts on, with its comment.
+    fn end_line(&mut self, pointer: &str) {
+        if let Some(comment) = self.comments.trailing(pointer) {
+            let _ = write!(self.out, " #{}", comment);
+        }
+        self.out.push('\n');
+    }
+
+    fn pad(&mut self, column: usize) {
+        self.out.push_str(&" ".repeat(column));
+    }
+
+    fn key(&mut self, key: &Value) {
+        match key {
+            Value::Mapping(_) | Value::Sequence(_) => {
+                self.out
+                    .push_str(&serde_json::to_string(key).unwrap_or_default());
+            }
+            key => self.scalar(key),
+        }
+    }
+
+    fn scalar(&mut self, value: &Value) {
+        match value {
+            Value::Null => self.out.push_str("null"),
+            Value::Bool(b) => {
+                let _ = write!(self.out, "{}", b);
+            }
+            Value::Number(n) => {
+                let _ = write!(self.out, "{}", n);
+            }
+            Value::String(s) => self.string(s),
+            Value::Mapping(_) => self.out.push_str("{}"),
+            Value::Sequence(_) => self.out.push_str("[]"),
+        }
+    }
+
+    fn string(&mut self, s: &str) {
+        let unquotable = s.chars().any(|c| c.is_control() && c != '\t');
+        if !self.options.quote_all && !needs_quotes(s) {
+            self.out.push_str(s);
+        } else if self.options.quote == QuoteStyle::Single && !unquotable {
+            let _ = write!(self.out, "'{}'", s.replace('\'', "''"));
+        } else {
+            self.out
+                .push_str(&serde_json::to_string(s).unwrap_or_default());
+        }
+    }
+
+    fn is_literal(&self, s: &str) -> bool {
+        self.options.literal_blocks
+            && s.trim_end_matches('\n').contains('\n')
+            && !s.starts_with([' ', '\n'])
+            && !s.chars().any(|c| c.is_control() && c != '\n' && c != '\t')
+    }
+
+    /// Writes a string of several lines as a literal block whose lines are
+    /// at `column`.
+    fn literal(&mut self, s: &str, column: usize, pointer: &str) {
+        let body = s.trim_end_matches('\n');
+        let chomping = match s.len() - body.len() {
+            0 => "-",
+            1 => "",
+            _ => "+",
+        };
+        let _ = write!(self.out, "|{}", chomping);
+        self.end_line(pointer);
+        // With `+`, the line breaks after the last line are lines of their own.
+        for line in s.strip_suffix('\n').unwrap_or(s).split('\n') {
+            if !line.is_empty() {
+                self.pad(column);
+                self.out.push_str(line);
+            }
+            self.out.push('\n');
+        }
+    }
+}
+
+/// The key of a mapping as it would be written in a JSON Pointer.
+fn key_string(key: &Value) -> String {
+    match key {
+        Value::String(s) => s.clone(),
+        Value::Bool(b) => b.to_string(),
+        Value::Number(n) => n.to_string(),
+        key => serde_json::to_string(key).unwrap_or_default(),
+    }
+}
+
+/// Whether a string would be read as something other than itself if
+/// written without quotes. Strings that some readers might take for
+/// numbers, dates or times, such as `200`, `.5` or `2020-01-01`, are
+/// quoted along with those that all would, but versions such as `3.0.0`
+/// are not.
+fn needs_quotes(s: &str) -> bool {
+    const INDICATORS: &[char] = &[
+        '-', '?', ':', ',', '[', ']', '{', '}', '#', '&', '*', '!', '|', '>', '\'', '"', '%', '@',
+        '`', ' ',
+    ];
+    const RESERVED: &[&str] = &[
+        "~", "null", "true", "false", "yes", "no", "on", "off", "y", "n", ".inf", "+.inf", ".nan",
+    ];
+    let unsigned = s.trim_start_matches('+');
+    let numeric = unsigned.starts_with(|c: char| c.is_ascii_digit() || c == '.')
+        && (unsigned.starts_with("0x")
+            || unsigned.starts_with("0o")
+            || unsigned.starts_with("0b")
+            || unsigned
+                .chars()
+                .all(|c| c.is_ascii_digit() || "+-._:eE".contains(c)));
+    let version = s.split('.').count() > 2
+        && s.split('.')
+            .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()));
+    s.is_empty()
+        || s.starts_with(INDICATORS)
+        || s.ends_with([' ', ':'])
+        || s.contains(": ")
+        || s.contains(" #")
+        || s.chars().any(|c| c.is_control())
+        || RESERVED.contains(&s.to_ascii_lowercase().as_str())
+        || numeric && !version
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_strings() {
+        let strings = [
+            "",
+            "yes",
+            "Null",
+            "200",
+            "1.5e3",
+            "+12",
+            "0x1F",
+            "1_000",
+            "1:30",
+            "2020-01-01",
+            ".5",
+            "+.INF",
+            "3.0.0",
+            ".gitignore",
+            ".inf",
+            "-1",
+            "-a",
+            "- a",
+            "a: b",
+            "a #b",
+            "# heading",
+            "it's",
+            "key:",
+            " padded ",
+            "tab\tand \"quotes\"",
+            "bell\u{7}",
+            "one\ntwo",
+            "one\ntwo\n",
+            "one\n\ntwo\n\n",
+            " indented\nlines",
+            "\r\n",
+        ];
+        let mut api = OpenAPI::default();
+        api.info.title = "Pets".to_owned();
+        api.info.version = "1.0".to_owned();
+        api.tags = strings
+            .iter()
+            .map(|s| Tag {
+                name: (*s).into(),
+                description: Some(s.to_string()),
+                ..Tag::default()
+            })
+            .collect();
+        for &quote in &[QuoteStyle::Single, QuoteStyle::Double] {
+            for &literal_blocks in &[true, false] {
+                for &quote_all in &[true, false] {
+                    let options = YamlOptions {
+                        indent: 3,
+                        literal_blocks,
+                        quote,
+                        quote_all,
+                    };
+                    let text = api.to_yaml_string(options).unwrap();
+                    let read: OpenAPI =
+                        serde_yaml::from_str(&text).unwrap_or_else(|e| panic!("{}\n{}", e, text));
+                    assert_eq!(read, api, "{}", text);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn lays_out_sequences() {
+        let value: Value = serde_yaml::from_str(
+            r#"{a: [1, [2, 3], {b: 4, c: [5]}, "x\ny"], d: {}, e: [], '200': null}"#,
+        )
+        .unwrap();
+        let options = YamlOptions::default();
+        assert_eq!(
+            emit(&value, &options, &Comments::default()),
+            "a:\n  - 1\n  - - 2\n    - 3\n  - b: 4\n    c:\n      - 5\n  - |-\n    x\n    y\nd: {}\ne: []\n'200': null\n"
+        );
+    }
+}
@@ -0,0 +1,58 @@
+//! Counts the allocations made reading large documents, which needs a
+//! global allocator of its own, and so a test binary of its own.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+struct Counting;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+/// The allocations made on this thread by `f`.
+fn allocations<R>(f: impl FnOnce() -> R) -> (usize, R) {
+    let before = ALLOCATIONS.with(Cell::get);
+    let result = f();
+    (ALLOCATIONS.with(Cell::get) - before, result)
+}
+
+#[test]
+fn borrowed_documents_allocate_less() {
+    for fixture in &["fixtures/slack.json", "fixtures/twilio.json"] {
+        let text = std::fs::read_to_string(fixture).unwrap();
+        let (owned, api) = allocations(|| serde_json::from_str::<openapiv3::OpenAPI>(&text));
+        let (borrowed, outline) =
+            allocations(|| serde_json::from_str::<openapiv3::borrowed::OpenAPI>(&text));
+        let (api, outline) = (api.unwrap(), outline.unwrap());
+        assert_eq!(outline.operations().count(), api.operations().count());
+        // Reading slack.json takes about 4,700 allocations borrowed, and
+        // about 62,000 owned.
+        assert!(
+            borrowed * 5 < owned,
+            "{}: {} allocations borrowed, {} owned",
+            fixture,
+            borrowed,
+            owned
+        );
+    }
+}
@@ -1,105 +1,103 @@
 use openapiv3::*;
-use serde_yaml;
-use std::collections::BTreeMap;
 
 enum FileType {
-    YAML,
-    JSON,
+    Yaml,
+    Json,
 }
 
 static TEST_CASES: &[(FileType, &str, &str)] = &[
     (
-        FileType::YAML,
+        FileType::Yaml,
         "quayio.yaml",
         include_str!("../fixtures/quayio.yaml"),
     ),
     (
-        FileType::JSON,
+        FileType::Json,
         "quayio.json",
         include_str!("../fixtures/quayio.json"),
     ),
     (
-        FileType::YAML,
+        FileType::Yaml,
         "petstore.yaml",
         include_str!("../fixtures/petstore.yaml"),
     ),
     (
-        FileType::YAML,
+        FileType::Yaml,
         "petstore-discriminated.yaml",
         include_str!("../fixtures/petstore-discriminated.yaml"),
     ),
     (
-        FileType::YAML,
+        FileType::Yaml,
         "api-with-examples.yaml",
         include_str!("../fixtures/api-with-examples.yaml"),
     ),
     (
-        FileType::YAML,
+        FileType::Yaml,
         "link-example.yaml",
         include_str!("../fixtures/link-example.yaml"),
     ),
     (
-        FileType::YAML,
+        FileType::Yaml,
         "callback-example.yaml",
         include_str!("../fixtures/callback-example.yaml"),
     ),
     (
-        FileType::YAML,
+        FileType::Yaml,
         "docker.yaml",
         include_str!("../fixtures/docker.yaml"),
     ),
     (
-        FileType::YAML,
+        FileType::Yaml,
         "forge.yaml",
         include_str!("../fixtures/forge.yaml"),
     ),
     (
-        FileType::YAML,
+        FileType::Yaml,
         "adobe_aem.yaml",
         include_str!("../fixtures/adobe_aem.yaml"),
     ),
     (
-        FileType::YAML,
+        FileType::Yaml,
         "azure_advisor.yaml",
         include_str!("../fixtures/azure_advisor.yaml"),
     ),
     (
-        FileType::JSON,
+        FileType::Json,
         "polygon.json",
         include_str!("../fixtures/polygon.json"),
     ),
     (
-        FileType::JSON,
+        FileType::Json,
         "slack.json",
         include_str!("../fixtures/slack.json"),
     ),
     (
-        FileType::JSON,
+        FileType::Json,
         "swagger_generator.json",
         include_str!("../fixtures/swagger_generator.json"),
     ),
     (
-        FileType::JSON,
+        FileType::Json,
         "twilio.json",
         include_str!("../fixtures/twilio.json"),
     ),
     (
-        FileType::JSON,
+        FileType::Json,
         "fitbit.json",
         include_str!("../fixtures/fitbit.json"),
     ),
     (
-        FileType::JSON,
+        FileType::Json,
         "walmart.json",
         include_str!("../fixtures/walmart.json"),
     ),
     (
-        FileType::JSON,
+        FileType::Json,
         "xkcd.json",
         include_str!("../fixtures/xkcd.json"),
     ),
     (
-        FileType::YAML,
+        FileType::Yaml,
         "authentiq.yaml",
         include_str!("../fixtures/authentiq.yaml"),
     ),
@@ -110,23 +108,35 @@ fn run_tests() {
     for (file_type, name, contents) in TEST_CASES {
         println!("{}", name);
         let openapi: OpenAPI = match file_type {
-            FileType::YAML => serde_yaml::from_str(contents)
-                .expect(&format!("Could not deserialize file {}", name)),
-            FileType::JSON => serde_json::from_str(contents)
-                .expect(&format!("Could not deserialize file {}", name)),
+            FileType::Yaml => serde_yaml::from_str(contents)
+                .unwrap_or_else(|_| panic!("Could not deserialize file {}", name)),
+            FileType::Json => serde_json::from_str(contents)
+                .unwrap_or_else(|_| panic!("Could not deserialize file {}", name)),
         };
-        let _yaml =
-            serde_yaml::to_string(&openapi).expect(&format!("Could not serialize YAML {}", name));
-        let _json =
-            serde_json::to_string(&openapi).expect(&format!("Could not serialize JSON {}", name));
+        let yaml = serde_yaml::to_string(&openapi)
+            .unwrap_or_else(|_| panic!("Could not serialize YAML {}", name));
+        let json = serde_json::to_string(&openapi)
+            .unwrap_or_else(|_| panic!("Could not serialize JSON {}", name));
+        assert_eq!(
+            serde_yaml::from_str::<OpenAPI>(&yaml).unwrap(),
+            openapi,
+            "YAML round trip of {}",
+            name
+        );
+        assert_eq!(
+            serde_json::from_str::<OpenAPI>(&json).unwrap(),
+            openapi,
+            "JSON round trip of {}",
+            name
+        );
     }
 }
 
 macro_rules! map {
     ( $( $key:expr => $value:expr ),* $(,)? ) => {{
         #[allow(unused_mut)]
-        let mut m = BTreeMap::new();
-        $(m.insert($key, $value);)*
+        let mut m = Map::new();
+        $(m.insert($key.into(), $value);)*
         m
     }};
 }
@@ -140,6 +150,7 @@ fn petstore_discriminated() {
             license: Some(License {
                 name: "MIT".to_owned(),
                 url: None,
+                ..Default::default()
             }),
             version: "1.0.0".to_owned(),
             ..Default::default()
@@ -177,7 +188,7 @@ fn petstore_discriminated() {
                                         })),
                                     }),
                                 },
-                                required: vec!["huntingSkill".to_owned()],
+                                required: vec!["huntingSkill".into()],
                                 ..Default::default()
                             })),
                         }),
@@ -207,7 +218,7 @@ fn petstore_discriminated() {
                                         })),
                                     }),
                                 },
-                                required: vec!["packSize".to_owned()],
+                                required: vec!["packSize".into()],
                                 ..Default::default()
                             })),
                         }),
@@ -233,7 +244,7 @@ fn petstore_discriminated() {
                                 schema_kind: SchemaKind::Type(Type::String(Default::default())),
                             }),
                         },
-                        required: vec!["name".to_owned(), "petType".to_owned()],
+                        required: vec!["name".into(), "petType".into()],
                         ..Default::default()
                     })),
                 }),
@@ -246,3 +257,349 @@ fn petstore_discriminated() {
     assert_eq!(serde_yaml::to_string(&api).unwrap(), yaml);
     assert_eq!(api, serde_yaml::from_str(yaml).unwrap());
 }
+
+#[test]
+fn versioned_documents() {
+    let petstore: VersionedOpenAPI =
+        serde_yaml::from_str(include_str!("../fixtures/petstore.yaml")).unwrap();
+    assert!(matches!(petstore, VersionedOpenAPI::V3_0(_)));
+
+    let webhooks: VersionedOpenAPI =
+        serde_yaml::from_str(include_str!("../fixtures/webhook-example.yaml")).unwrap();
+    let api = match &webhooks {
+        VersionedOpenAPI::V3_1(api) => api,
+        VersionedOpenAPI::V3_0(_) => panic!("expected a 3.1 document"),
+    };
+    assert_eq!(webhooks.version(), "3.1.0");
+    assert!(api.paths.is_empty());
+    assert!(api.webhooks.contains_key("newPet"));
+    assert_eq!(
+        api.info.license.as_ref().unwrap().identifier.as_deref(),
+        Some("Apache-2.0")
+    );
+
+    let components = api.components.as_ref().unwrap();
+    assert!(components.path_items.contains_key("ping"));
+    let pet = match &components.schemas["Pet"] {
+        v3_1::Schema::Object(pet) => pet,
+        v3_1::Schema::Bool(_) => panic!("expected a schema object"),
+    };
+    let property = |name: &str| match &pet.properties[name] {
+        v3_1::Schema::Object(property) => property.clone(),
+        v3_1::Schema::Bool(_) => panic!("expected a schema object"),
+    };
+    assert_eq!(property("id").exclusive_minimum, Some(0.into()));
+    assert_eq!(
+        property("name").schema_type,
+        Some(v3_1::SchemaTypes::Multiple(vec![
+            v3_1::SchemaType::String,
+            v3_1::SchemaType::Null
+        ]))
+    );
+    assert_eq!(property("kind").const_, Some(serde_json::json!("pet")));
+    let position = property("position");
+    assert_eq!(position.prefix_items.len(), 2);
+    assert_eq!(position.items, Some(v3_1::Schema::Bool(false)));
+
+    let yaml = serde_yaml::to_string(&webhooks).unwrap();
+    assert_eq!(
+        serde_yaml::from_str::<VersionedOpenAPI>(&yaml).unwrap(),
+        webhooks
+    );
+
+    let error = serde_yaml::from_str::<VersionedOpenAPI>("openapi: 2.0.0").unwrap_err();
+    assert!(error.to_string().contains("unsupported OpenAPI version"));
+}
+
+#[test]
+fn defaults_serialize_as_empty_objects() {
+    let header = Header {
+        description: Some("rate limit".to_owned()),
+        ..Header::default()
+    };
+    assert_eq!(
+        serde_json::to_value(&header).unwrap(),
+        serde_json::json!({"description": "rate limit", "style": "simple", "schema": {}})
+    );
+
+    let operation = Operation {
+        responses: vec![(
+            StatusCode::Code(204),
+            ReferenceOr::Item(Response::default()),
+        )]
+        .into_iter()
+        .collect(),
+        ..Operation::default()
+    };
+    let mut paths = Paths::new();
+    paths.insert(
+        "/ping".to_owned(),
+        ReferenceOr::Item(PathItem {
+            get: Some(operation),
+            ..PathItem::default()
+        }),
+    );
+    let api = OpenAPI {
+        paths,
+        ..OpenAPI::default()
+    };
+    assert_eq!(
+        serde_json::to_value(&api.paths).unwrap(),
+        serde_json::json!({"/ping": {"get": {"responses": {"204": {"description": ""}}}}})
+    );
+    assert_eq!(
+        serde_json::to_value(ArrayType::default()).unwrap(),
+        serde_json::json!({"items": {}})
+    );
+}
+
+#[cfg(feature = "preserve_order")]
+#[test]
+fn preserves_key_order() {
+    let source = "
+openapi: 3.0.0
+info: {title: t, version: '1'}
+paths:
+  /pets:
+    get:
+      responses:
+        '404': {description: missing}
+        '200': {description: found}
+  /owners: {}
+components:
+  schemas:
+    Pet:
+      type: object
+      properties:
+        name: {type: string}
+        id: {type: integer}
+    Owner: {type: object}
+";
+    let mut api: OpenAPI = serde_yaml::from_str(source).unwrap();
+    assert_eq!(
+        serde_yaml::to_value(&api).unwrap(),
+        serde_yaml::from_str::<serde_yaml::Value>(source).unwrap()
+    );
+
+    api.canonicalize();
+    assert_eq!(
+        api.paths.keys().collect::<Vec<_>>(),
+        vec!["/owners", "/pets"]
+    );
+    let schemas = &api.components.as_ref().unwrap().schemas;
+    assert_eq!(schemas.keys().collect::<Vec<_>>(), vec!["Owner", "Pet"]);
+}
+
+#[test]
+fn captures_extensions() {
+    let source = "
+openapi: 3.0.0
+info: {title: t, version: '1', x-audience: public}
+paths:
+  /pets:
+    x-router: pets
+    get:
+      x-rate-limit: 10
+      responses:
+        200: {description: ok, x-cache: true}
+        x-responses: {note: kept}
+components:
+  schemas:
+    Pet: {type: object, x-entity: {table: pets}, tpye: string}
+  securitySchemes:
+    key: {type: apiKey, in: header, name: key, x-vendor: acme}
+";
+    let api: OpenAPI = serde_yaml::from_str(source).unwrap();
+    assert_eq!(api.info.extensions["x-audience"], "public");
+    let item = match &api.paths["/pets"] {
+        ReferenceOr::Item(item) => item,
+        ReferenceOr::Reference { .. } => panic!("expected a path item"),
+    };
+    assert_eq!(item.extensions["x-router"], "pets");
+    let operation = item.get.as_ref().unwrap();
+    assert_eq!(operation.extensions["x-rate-limit"], 10);
+    assert_eq!(
+        operation.responses.extensions["x-responses"]["note"],
+        "kept"
+    );
+    match &operation.responses.responses[&StatusCode::Code(200)] {
+        ReferenceOr::Item(response) => assert_eq!(response.extensions["x-cache"], true),
+        ReferenceOr::Reference { .. } => panic!("expected a response"),
+    }
+
+    let components = api.components.as_ref().unwrap();
+    match &components.schemas["Pet"] {
+        ReferenceOr::Item(schema) => {
+            let extensions = &schema.schema_data.extensions;
+            assert_eq!(extensions.keys().collect::<Vec<_>>(), vec!["x-entity"]);
+        }
+        ReferenceOr::Reference { .. } => panic!("expected a schema"),
+    }
+    match &components.security_schemes["key"] {
+        ReferenceOr::Item(SecurityScheme::APIKey { extensions, .. }) => {
+            assert_eq!(extensions["x-vendor"], "acme")
+        }
+        _ => panic!("expected an API key scheme"),
+    }
+
+    let written = serde_yaml::to_value(&api).unwrap();
+    assert_eq!(serde_yaml::from_value::<OpenAPI>(written).unwrap(), api);
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn derives_schemas() {
+    /// A pet.
+    ///
+    /// Owned by someone.
+    #[derive(OpenApiSchema)]
+    #[serde(rename_all = "camelCase", deny_unknown_fields)]
+    #[allow(dead_code)]
+    struct Pet {
+        /// What the pet answers to.
+        pet_name: String,
+        kind: Kind,
+        #[serde(default)]
+        tags: Vec<String>,
+        parent: Option<Box<Pet>>,
+        #[serde(skip)]
+        cache: u8,
+    }
+
+    #[derive(OpenApiSchema)]
+    #[serde(rename_all = "kebab-case")]
+    #[allow(dead_code)]
+    enum Kind {
+        HouseCat,
+        Dog,
+    }
+
+    #[derive(OpenApiSchema)]
+    #[serde(tag = "type")]
+    #[allow(dead_code)]
+    enum Event {
+        Adopted { pet: Pet },
+        Lost(Pet),
+        Found,
+    }
+
+    #[derive(OpenApiSchema)]
+    #[serde(tag = "t", content = "c")]
+    #[allow(dead_code)]
+    enum Adjacent {
+        Count(u32),
+        Nothing,
+    }
+
+    #[derive(OpenApiSchema)]
+    #[allow(dead_code)]
+    enum External {
+        #[serde(rename = "n")]
+        Number(f64),
+        Unit,
+    }
+
+    let mut components = Components::default();
+    assert_eq!(
+        components.register::<Event>(),
+        ReferenceOr::ref_("#/components/schemas/Event")
+    );
+    components.register::<Adjacent>();
+    components.register::<External>();
+    let expected: Map<String, ReferenceOr<Schema>> = serde_yaml::from_str(
+        "
+Pet:
+  description: |-
+    A pet.
+
+    Owned by someone.
+  type: object
+  properties:
+    petName: {type: string, description: What the pet answers to.}
+    kind: {$ref: '#/components/schemas/Kind'}
+    tags: {type: array, items: {type: string}}
+    parent: {nullable: true, allOf: [{$ref: '#/components/schemas/Pet'}]}
+  required: [petName, kind]
+  additionalProperties: false
+Kind: {type: string, enum: [house-cat, dog]}
+Event:
+  oneOf:
+    - type: object
+      properties:
+        type: {type: string, enum: [Adopted]}
+        pet: {$ref: '#/components/schemas/Pet'}
+      required: [type, pet]
+    - allOf:
+        - type: object
+          properties: {type: {type: string, enum: [Lost]}}
+          required: [type]
+        - $ref: '#/components/schemas/Pet'
+    - type: object
+      properties: {type: {type: string, enum: [Found]}}
+      required: [type]
+Adjacent:
+  oneOf:
+    - type: object
+      properties:
+        t: {type: string, enum: [Count]}
+        c: {type: integer, format: int32, minimum: 0}
+      required: [t, c]
+    - type: object
+      properties: {t: {type: string, enum: [Nothing]}}
+      required: [t]
+External:
+  oneOf:
+    - type: object
+      properties: {n: {type: number, format: double}}
+      required: [n]
+    - {type: string, enum: [Unit]}
+",
+    )
+    .unwrap();
+    assert_eq!(components.schemas, expected);
+}
+
+#[test]
+fn captures_extensions_of_paths_and_callbacks() {
+    let source = "
+openapi: 3.0.3
+info: {title: t, version: '1'}
+paths:
+  x-foo: true
+  /subscribe:
+    post:
+      callbacks:
+        onEvent:
+          x-retries: 3
+          '{$request.body#/url}':
+            post: {responses: {'200': {description: ok}}}
+      responses: {'201': {description: subscribed}}
+";
+    let api: OpenAPI = serde_yaml::from_str(source).unwrap();
+    assert_eq!(api.paths.extensions["x-foo"], true);
+    assert_eq!(api.paths.keys().collect::<Vec<_>>(), vec!["/subscribe"]);
+    let operation = match &api.paths["/subscribe"] {
+        ReferenceOr::Item(item) => item.post.as_ref().unwrap(),
+        ReferenceOr::Reference { .. } => panic!("expected a path item"),
+    };
+    let callback = match &operation.callbacks["onEvent"] {
+        ReferenceOr::Item(callback) => callback,
+        ReferenceOr::Reference { .. } => panic!("expected a callback"),
+    };
+    assert_eq!(callback.extensions["x-retries"], 3);
+    assert_eq!(
+        callback.keys().collect::<Vec<_>>(),
+        vec!["{$request.body#/url}"]
+    );
+
+    let written = serde_yaml::to_value(&api).unwrap();
+    assert_eq!(written["paths"]["x-foo"], serde_yaml::Value::Bool(true));
+    assert_eq!(serde_yaml::from_value::<OpenAPI>(written).unwrap(), api);
+
+    let upgraded = api.clone().upgrade_to_3_1();
+    assert_eq!(upgraded.paths.extensions["x-foo"], true);
+    let written = serde_json::to_string(&upgraded).unwrap();
+    let read: openapiv3::v3_1::OpenAPI = serde_json::from_str(&written).unwrap();
+    assert_eq!(read, upgraded);
+    assert_eq!(read.downgrade_to_3_0().0, api);
+}